@@ -0,0 +1,111 @@
+//! Measures how much keeping the text layout of a huge, unchanged log pane across frames saves.
+//!
+//! Breaking a huge text into lines gets slower faster than the text grows, so without the cache
+//! only the smaller log is measured.
+//!
+//! Run with `cargo bench --features vello --bench cached_text`.
+
+use byor_gui::style::*;
+use byor_gui::widgets::*;
+use byor_gui::*;
+use std::time::{Duration, Instant};
+use vello::Scene;
+
+/// About 200 KB and 1 MB of text, and whether the layout is cached
+const RUNS: [(usize, bool); 3] = [(4_000, false), (4_000, true), (20_000, true)];
+const ITERATIONS: u32 = 5;
+
+const LOG_UID: Uid = Uid::from_array(b"log");
+
+fn build(
+    gui: &mut ByorGui<Scene>,
+    screen_size: Vec2<Pixel>,
+    log: &str,
+    cached: bool,
+    scroll_view_style: &Style,
+    log_style: &Style,
+) {
+    gui.frame(screen_size, |mut gui| {
+        gui.show_container(
+            ScrollView::vertical().with_style(scroll_view_style),
+            |mut gui| -> WidgetResult<()> {
+                let contents = if cached {
+                    NodeContents::text_cached(LOG_UID, 0, log)
+                } else {
+                    NodeContents::text(log)
+                };
+                gui.insert_node(Some(LOG_UID), log_style, contents)?;
+                Ok(())
+            },
+        )?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+}
+
+fn main() {
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+        layout_direction: Direction::TopToBottom,
+    };
+    let log_style = style! {
+        width: Sizing::Grow,
+    };
+
+    for (line_count, cached) in RUNS {
+        let log: String = (0..line_count)
+            .map(|i| {
+                format!(
+                    "[{i:>6}] worker {} finished job {} without errors\n",
+                    i % 8,
+                    i * 7
+                )
+            })
+            .collect();
+
+        let mut gui = ByorGui::<Scene>::default();
+        build(
+            &mut gui,
+            screen_size,
+            &log,
+            cached,
+            &scroll_view_style,
+            &log_style,
+        );
+
+        let mut scene = Scene::new();
+        let mut frame_time = Duration::ZERO;
+        let mut render_time = Duration::ZERO;
+        for _ in 0..ITERATIONS {
+            let start = Instant::now();
+            build(
+                &mut gui,
+                screen_size,
+                &log,
+                cached,
+                &scroll_view_style,
+                &log_style,
+            );
+            frame_time += start.elapsed();
+
+            scene.reset();
+            let start = Instant::now();
+            gui.render(&mut scene).unwrap();
+            render_time += start.elapsed();
+        }
+
+        println!(
+            "{} text of {} KB: building and laying out took {:?}, encoding took {:?} on average",
+            if cached { "cached" } else { "uncached" },
+            log.len() / 1024,
+            frame_time / ITERATIONS,
+            render_time / ITERATIONS,
+        );
+    }
+}
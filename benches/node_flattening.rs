@@ -0,0 +1,112 @@
+//! Measures how much removing pass-through wrappers saves for a long list of deeply wrapped items
+//! in a scroll view.
+//!
+//! Run with `cargo bench --features vello --bench node_flattening`.
+
+use byor_gui::style::*;
+use byor_gui::widgets::*;
+use byor_gui::*;
+use std::time::{Duration, Instant};
+use vello::Scene;
+
+const ITEM_COUNT: usize = 2_000;
+const WRAPPER_DEPTH: usize = 4;
+const ITERATIONS: u32 = 20;
+
+fn build(
+    gui: &mut ByorGui<Scene>,
+    screen_size: Vec2<Pixel>,
+    items: &[String],
+    scroll_view_style: &Style,
+    wrapper_style: &Style,
+) {
+    // Stands in for widgets that are composed of other widgets, each adding a node of its own
+    fn wrapped(
+        gui: &mut ByorGuiContext<'_, Scene>,
+        depth: usize,
+        wrapper_style: &Style,
+        item: &str,
+    ) -> WidgetResult<()> {
+        if depth == 0 {
+            return gui.show(Label::default().with_text(item));
+        }
+
+        gui.insert_node(
+            None,
+            wrapper_style,
+            NodeContents::builder(|mut gui| wrapped(&mut gui, depth - 1, wrapper_style, item)),
+        )?
+        .result
+    }
+
+    gui.frame(screen_size, |mut gui| {
+        gui.show_container(
+            ScrollView::vertical().with_style(scroll_view_style),
+            |mut gui| -> WidgetResult<()> {
+                for item in items {
+                    wrapped(&mut gui, WRAPPER_DEPTH, wrapper_style, item)?;
+                }
+                Ok(())
+            },
+        )?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+}
+
+fn main() {
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+        layout_direction: Direction::TopToBottom,
+    };
+    let wrapper_style = style! {
+        padding: 0.px(),
+    };
+    let items: Vec<_> = (0..ITEM_COUNT).map(|i| format!("Item {i}")).collect();
+
+    for node_flattening in [false, true] {
+        let mut gui = ByorGui::<Scene>::default();
+        gui.set_node_flattening(node_flattening);
+        build(
+            &mut gui,
+            screen_size,
+            &items,
+            &scroll_view_style,
+            &wrapper_style,
+        );
+
+        let mut scene = Scene::new();
+        let mut frame_time = Duration::ZERO;
+        let mut render_time = Duration::ZERO;
+        for _ in 0..ITERATIONS {
+            let start = Instant::now();
+            build(
+                &mut gui,
+                screen_size,
+                &items,
+                &scroll_view_style,
+                &wrapper_style,
+            );
+            frame_time += start.elapsed();
+
+            scene.reset();
+            let start = Instant::now();
+            gui.render(&mut scene).unwrap();
+            render_time += start.elapsed();
+        }
+
+        println!(
+            "flattening {}: {} nodes, building and laying out took {:?}, encoding took {:?} on average",
+            if node_flattening { "on" } else { "off" },
+            gui.node_count(),
+            frame_time / ITERATIONS,
+            render_time / ITERATIONS,
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Measures how long encoding a long scroll list into a vello scene takes.
+//!
+//! Run with `cargo bench --features vello --bench scroll_list`.
+
+use byor_gui::style::*;
+use byor_gui::widgets::*;
+use byor_gui::*;
+use std::time::{Duration, Instant};
+use vello::Scene;
+
+const ITEM_COUNT: usize = 10_000;
+const ITERATIONS: u32 = 50;
+
+fn main() {
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+        layout_direction: Direction::TopToBottom,
+    };
+    let item_style = style! {
+        border_width: 1.px(),
+        border_color: Color::greyscale(128),
+    };
+    let items: Vec<_> = (0..ITEM_COUNT).map(|i| format!("Item {i}")).collect();
+
+    let mut gui = ByorGui::<Scene>::default();
+    gui.frame(screen_size, |mut gui| {
+        gui.show_container(
+            ScrollView::vertical().with_style(&scroll_view_style),
+            |mut gui| -> WidgetResult<()> {
+                for item in &items {
+                    gui.show(Label::default().with_text(item).with_style(&item_style))?;
+                }
+                Ok(())
+            },
+        )?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+
+    let mut scene = Scene::new();
+    gui.render(&mut scene).unwrap();
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        scene.reset();
+
+        let start = Instant::now();
+        gui.render(&mut scene).unwrap();
+        total += start.elapsed();
+    }
+
+    println!(
+        "encoding {ITEM_COUNT} list items took {:?} on average",
+        total / ITERATIONS
+    );
+}
@@ -0,0 +1,106 @@
+//! Measures how long encoding a text box with a selection over all of its 500 lines into a vello
+//! scene takes, compared to the same text box without a selection.
+//!
+//! Run with `cargo bench --features vello --bench text_selection`.
+
+use byor_gui::input::*;
+use byor_gui::style::*;
+use byor_gui::widgets::*;
+use byor_gui::*;
+use std::time::{Duration, Instant};
+use vello::Scene;
+
+const LINE_COUNT: usize = 500;
+const ITERATIONS: u32 = 50;
+
+fn build(
+    gui: &mut ByorGui<Scene>,
+    screen_size: Vec2<Pixel>,
+    text: &mut String,
+    scroll_view_style: &Style,
+    text_box_style: &Style,
+) {
+    gui.frame(screen_size, |mut gui| {
+        gui.show_container(
+            ScrollView::vertical().with_style(scroll_view_style),
+            |mut gui| -> WidgetResult<()> {
+                gui.show(
+                    TextBox::new(text)
+                        .with_style(text_box_style)
+                        .with_select_on_focus(SelectOnFocus::Always),
+                )?;
+                Ok(())
+            },
+        )?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+}
+
+fn main() {
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+        layout_direction: Direction::TopToBottom,
+    };
+    // text boxes don't grow with their text
+    let text_box_style = style! {
+        width: Sizing::Grow,
+        height: 10_000.px(),
+    };
+
+    let mut text = (0..LINE_COUNT)
+        .map(|i| format!("line {i:>3} of the text that is selected as a whole"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut gui = ByorGui::<Scene>::default();
+    gui.set_caret_blink_interval(None);
+    let mut scene = Scene::new();
+    for selected in [false, true] {
+        if selected {
+            // the text box is the only focusable node, and reaching it selects all of the text
+            gui.on_input_event(InputEvent::KeyPressed {
+                key: Key::Named(NamedKey::Tab),
+                location: KeyLocation::Standard,
+                text: None,
+                repeat: false,
+            });
+        }
+        build(
+            &mut gui,
+            screen_size,
+            &mut text,
+            &scroll_view_style,
+            &text_box_style,
+        );
+
+        let mut render_time = Duration::ZERO;
+        for _ in 0..ITERATIONS {
+            build(
+                &mut gui,
+                screen_size,
+                &mut text,
+                &scroll_view_style,
+                &text_box_style,
+            );
+
+            scene.reset();
+            let start = Instant::now();
+            gui.render(&mut scene).unwrap();
+            render_time += start.elapsed();
+        }
+
+        println!(
+            "{LINE_COUNT} lines {}: encoding {} paths took {:?} on average",
+            if selected { "selected" } else { "unselected" },
+            scene.encoding().n_paths,
+            render_time / ITERATIONS,
+        );
+    }
+}
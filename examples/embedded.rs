@@ -0,0 +1,140 @@
+//! Shows the GUI inside a render target owned by the host application, without a window.
+//!
+//! The host places the GUI at `GUI_OFFSET` in its own scene and translates the input it receives
+//! into GUI-local pixels before passing it on.
+
+use anyhow::{Result, format_err};
+use byor_gui::input::*;
+use byor_gui::style::*;
+use byor_gui::theme::*;
+use byor_gui::widgets::*;
+use byor_gui::*;
+use vello::kurbo::{Affine, Rect};
+use vello::peniko::Fill;
+use vello::peniko::color::palette;
+use vello::wgpu;
+use vello::{AaConfig, AaSupport, RenderParams, Renderer, RendererOptions, Scene};
+
+const TARGET_WIDTH: u32 = 1280;
+const TARGET_HEIGHT: u32 = 720;
+
+/// The sub-rectangle of the render target the GUI is shown in.
+const GUI_OFFSET: Vec2<Pixel> = Vec2 {
+    x: Float::px(880.0),
+    y: Float::px(40.0),
+};
+const GUI_SIZE: Vec2<Pixel> = Vec2 {
+    x: Float::px(360.0),
+    y: Float::px(640.0),
+};
+
+const BUTTON_UID: Uid = Uid::from_slice(b"embedded_button");
+
+fn main() -> Result<()> {
+    pollster::block_on(run())
+}
+
+async fn run() -> Result<()> {
+    // the host owns the device, the GUI only ever sees a `Scene`
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions::default())
+        .await?;
+    let (device, queue) = adapter
+        .request_device(&wgpu::DeviceDescriptor::default())
+        .await?;
+
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Host Render Target"),
+        size: wgpu::Extent3d {
+            width: TARGET_WIDTH,
+            height: TARGET_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let target_view = target.create_view(&Default::default());
+
+    let mut renderer = Renderer::new(
+        &device,
+        RendererOptions {
+            antialiasing_support: AaSupport::area_only(),
+            ..Default::default()
+        },
+    )
+    .map_err(|e| format_err!("{e}"))?;
+
+    let mut gui = ByorGui::default();
+    gui.theme_mut().insert_style(
+        Theme::ROOT_TYPE_CLASS,
+        &style! {
+            padding: 10.pt(),
+            background: Color {
+                a: 224,
+                ..Color::greyscale(32)
+            },
+            text_color: Color::greyscale(224),
+        },
+    );
+
+    let mut clicks = 0;
+    for frame in 0..4 {
+        // the host receives input in render target pixels
+        match frame {
+            1 => {
+                let button = gui
+                    .nodes()
+                    .find(|node| node.uid() == Some(BUTTON_UID))
+                    .expect("button was not shown");
+                let host_position = GUI_OFFSET + button.position() + button.size() / 2.0;
+                gui.move_cursor(host_position - GUI_OFFSET);
+            }
+            2 => gui.press_button(MouseButton::Primary),
+            3 => gui.release_button(MouseButton::Primary),
+            _ => (),
+        }
+
+        gui.try_frame(GUI_SIZE, |mut gui| -> WidgetResult<()> {
+            gui.show(Label::default().with_text("Embedded GUI"))?;
+
+            let button = Button::default().with_text("Click me").with_uid(BUTTON_UID);
+            if gui.show(button)?.clicked(MouseButtons::PRIMARY) {
+                clicks += 1;
+            }
+
+            Ok(())
+        })
+        .result
+        .map_err(|e| format_err!("{e}"))?;
+
+        let mut scene = Scene::new();
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            palette::css::DARK_SLATE_GRAY,
+            None,
+            &Rect::new(0.0, 0.0, TARGET_WIDTH.into(), TARGET_HEIGHT.into()),
+        );
+        gui.render_into(&mut scene, GUI_OFFSET)?;
+
+        let render_params = RenderParams {
+            base_color: palette::css::BLACK,
+            width: TARGET_WIDTH,
+            height: TARGET_HEIGHT,
+            antialiasing_method: AaConfig::Area,
+        };
+        renderer
+            .render_to_texture(&device, &queue, &scene, &target_view, &render_params)
+            .map_err(|e| format_err!("{e}"))?;
+        device.poll(wgpu::PollType::Wait)?;
+    }
+
+    println!("the button was clicked {clicks} time(s)");
+
+    Ok(())
+}
@@ -0,0 +1,844 @@
+//! Shows every built-in widget, once enabled and once disabled.
+//!
+//! This module is shared by the gallery example and the gallery snapshot tests, so it is generic
+//! over the renderer and only uses the public API.
+
+use byor_gui::input::*;
+use byor_gui::rendering::{NodeRenderer, RenderContext, Renderer};
+use byor_gui::style::*;
+use byor_gui::theme::*;
+use byor_gui::widgets::*;
+use byor_gui::*;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tab {
+    Buttons,
+    Text,
+    Values,
+    Containers,
+    /// Only shows the Markdown widget with the `markdown` feature, but is always present so the
+    /// tab bar looks the same with and without it.
+    Markdown,
+}
+
+impl Tab {
+    pub const ALL: &[Self] = &[
+        Self::Buttons,
+        Self::Text,
+        Self::Values,
+        Self::Containers,
+        Self::Markdown,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Buttons => "Buttons",
+            Self::Text => "Text",
+            Self::Values => "Values",
+            Self::Containers => "Containers",
+            Self::Markdown => "Markdown",
+        }
+    }
+}
+
+pub struct Gallery {
+    pub tab: Tab,
+    pub right_to_left: bool,
+    /// Whether the theme should be extended with [`apply_high_contrast`]. The gallery only
+    /// toggles this, applying it is up to the owner of the theme.
+    pub high_contrast: bool,
+    pub single_line_text: String,
+    pub multi_line_text: String,
+    pub number_text: String,
+    pub scroll_value: f32,
+    pub price_range: (f32, f32),
+    pub due_date: Date,
+    pub gauge_value: f32,
+    pub popup_open: bool,
+    pub dialog_open: bool,
+    pub list_selection: SelectionState,
+    pub reorderable_items: Vec<&'static str>,
+    pub segment: usize,
+}
+
+impl Default for Gallery {
+    fn default() -> Self {
+        let mut list_selection = SelectionState::new();
+        list_selection.select(1);
+
+        Self {
+            tab: Tab::Buttons,
+            right_to_left: false,
+            high_contrast: false,
+            single_line_text: "Single line".to_string(),
+            multi_line_text: "Several lines\nof text that can\nbe edited".to_string(),
+            number_text: "42".to_string(),
+            scroll_value: 0.25,
+            price_range: (20.0, 60.0),
+            due_date: Date::new(2026, 10, 14),
+            gauge_value: 0.6,
+            popup_open: false,
+            dialog_open: false,
+            list_selection,
+            reorderable_items: vec!["First", "Second", "Third"],
+            segment: 0,
+        }
+    }
+}
+
+pub const TAB_CLASS: StyleClass = StyleClass::new_static("gallery_tab");
+pub const SELECTED_TAB_CLASS: StyleClass = StyleClass::new_static("gallery_selected_tab");
+pub const PAGE_CLASS: StyleClass = StyleClass::new_static("gallery_page");
+
+const TEXT_COLOR: Color = Color::greyscale(224);
+const DISABLED_TEXT_COLOR: Color = Color::greyscale(128);
+
+pub fn create_theme(theme: &mut Theme) {
+    theme.set_role(ColorRole::Accent, Color::rgb(38, 79, 120));
+    theme.set_role(ColorRole::Surface, Color::greyscale(32));
+    theme.set_role(ColorRole::OnSurface, TEXT_COLOR);
+
+    let text_color: PropertyFn<Color> = |_, _, enabled, _| {
+        if enabled {
+            TEXT_COLOR
+        } else {
+            DISABLED_TEXT_COLOR
+        }
+    };
+
+    theme.insert_style(
+        Theme::UNIVERSAL_CLASS,
+        &style! {
+            padding: 4.pt(),
+            child_spacing: 4.pt(),
+            border_color: Color::greyscale(160),
+            border_width: 1.0.pt(),
+            corner_radius: 4.0.pt(),
+            text_color: text_color,
+        },
+    );
+
+    theme.insert_style(
+        Theme::ROOT_TYPE_CLASS,
+        &style! {
+            font_size: 14.pt(),
+            background: Color::greyscale(48),
+            border_width: 0.0.pt(),
+            corner_radius: 0.0.pt(),
+            layout_direction: Direction::TopToBottom,
+            selection_color: ColorRole::Accent,
+        },
+    );
+
+    theme.insert_style(
+        Label::TYPE_CLASS,
+        &style! {
+            border_width: 0.0.pt(),
+            corner_radius: 0.0.pt(),
+        },
+    );
+
+    let button_background: PropertyFn<Brush> = |_, input_state, enabled, _| {
+        if !enabled {
+            Color::greyscale(56).into()
+        } else if input_state.pressed(MouseButtons::PRIMARY) {
+            Color::greyscale(96).into()
+        } else if input_state.is_hovered() {
+            Color::greyscale(80).into()
+        } else {
+            Color::greyscale(64).into()
+        }
+    };
+    let focus_border: PropertyFn<Color> = |_, input_state, _, theme| {
+        if input_state.focused {
+            theme.role(ColorRole::OnSurface)
+        } else if input_state.is_hovered() {
+            Color::greyscale(192)
+        } else {
+            Color::greyscale(128)
+        }
+    };
+
+    theme.insert_style(
+        Button::TYPE_CLASS,
+        &style! {
+            background: button_background,
+            border_color: focus_border,
+            vertical_text_alignment: VerticalTextAlignment::Center,
+        },
+    );
+
+    theme.insert_style(
+        TAB_CLASS,
+        &style! {
+            corner_radius: 0.0.pt(),
+        },
+    );
+    theme.insert_style(
+        SELECTED_TAB_CLASS,
+        &style! {
+            background: ColorRole::Accent,
+        },
+    );
+
+    theme.insert_style(
+        FlexPanel::TYPE_CLASS,
+        &style! {
+            border_width: 0.0.pt(),
+            padding: 0.pt(),
+        },
+    );
+
+    theme.insert_style(
+        PAGE_CLASS,
+        &style! {
+            width: Sizing::Grow,
+            height: Sizing::Grow,
+            layout_direction: Direction::TopToBottom,
+        },
+    );
+
+    theme.insert_style(
+        ScrollBar::HORIZONTAL_TYPE_CLASS,
+        &style! {
+            width: 160.pt(),
+            height: 16.pt(),
+            padding: 0.px(),
+            child_spacing: 1.pt(),
+            border_width: 0.0.px(),
+            background: ColorRole::Surface,
+        },
+    );
+
+    theme.insert_style(
+        ScrollBar::VERTICAL_TYPE_CLASS,
+        &style! {
+            width: 16.pt(),
+            height: Sizing::Grow,
+            padding: 0.px(),
+            child_spacing: 1.pt(),
+            border_width: 0.0.px(),
+            background: ColorRole::Surface,
+        },
+    );
+
+    let scroll_bar_button_style = style! {
+        width: 16.pt(),
+        height: 16.pt(),
+        background: button_background,
+    };
+    theme.insert_style(ScrollBar::LEFT_BUTTON_CLASS, &scroll_bar_button_style);
+    theme.insert_style(ScrollBar::RIGHT_BUTTON_CLASS, &scroll_bar_button_style);
+    theme.insert_style(ScrollBar::UP_BUTTON_CLASS, &scroll_bar_button_style);
+    theme.insert_style(ScrollBar::DOWN_BUTTON_CLASS, &scroll_bar_button_style);
+
+    theme.insert_style(
+        ScrollBar::HORIZONTAL_THUMB_CLASS,
+        &style! {
+            width: Sizing::Grow,
+            height: 16.pt(),
+            min_width: 16.pt(),
+            background: button_background,
+        },
+    );
+
+    theme.insert_style(
+        ScrollBar::VERTICAL_THUMB_CLASS,
+        &style! {
+            width: 16.pt(),
+            height: Sizing::Grow,
+            min_height: 16.pt(),
+            background: button_background,
+        },
+    );
+
+    theme.insert_style(
+        RangeSlider::TYPE_CLASS,
+        &style! {
+            width: 160.pt(),
+            padding: 0.px(),
+            border_width: 0.0.px(),
+        },
+    );
+
+    theme.insert_style(
+        Slider::TRACK_CLASS,
+        &style! {
+            height: 4.pt(),
+            padding: 0.px(),
+            border_width: 0.0.px(),
+            background: ColorRole::Surface,
+        },
+    );
+
+    theme.insert_style(
+        Slider::THUMB_CLASS,
+        &style! {
+            width: 16.pt(),
+            height: 16.pt(),
+            corner_radius: 8.pt(),
+            background: button_background,
+            border_color: focus_border,
+        },
+    );
+
+    theme.insert_style(
+        DatePicker::TYPE_CLASS,
+        &style! {
+            padding: 0.px(),
+            border_width: 0.0.px(),
+        },
+    );
+
+    theme.insert_style(
+        DatePicker::FIELD_CLASS,
+        &style! {
+            width: 96.pt(),
+        },
+    );
+
+    theme.insert_style(
+        DatePicker::HEADER_CLASS,
+        &style! {
+            width: Sizing::Grow,
+            padding: 0.px(),
+            border_width: 0.0.px(),
+        },
+    );
+
+    let calendar_column_style = style! {
+        width: 32.pt(),
+        horizontal_text_alignment: HorizontalTextAlignment::Center,
+    };
+    theme.insert_style(DatePicker::WEEKDAY_CLASS, &calendar_column_style);
+    theme.insert_style(DatePicker::CELL_CLASS, &calendar_column_style);
+
+    theme.insert_style(
+        ScrollView::VERTICAL_TYPE_CLASS,
+        &style! {
+            width: Sizing::Grow,
+            height: Sizing::Grow,
+            border_width: 0.0.pt(),
+            layout_direction: Direction::TopToBottom,
+        },
+    );
+
+    theme.insert_style(
+        Popup::TYPE_CLASS,
+        &style! {
+            background: Color::greyscale(40),
+            drop_shadow_width: 8.pt(),
+            drop_shadow_color: Color { r: 0, g: 0, b: 0, a: 160 },
+        },
+    );
+
+    theme.insert_style(
+        Dialog::SURFACE_CLASS,
+        &style! {
+            background: Color::greyscale(40),
+            corner_radius: 4.pt(),
+            drop_shadow_width: 8.pt(),
+            drop_shadow_color: Color { r: 0, g: 0, b: 0, a: 160 },
+        },
+    );
+
+    theme.insert_style(
+        ListBox::TYPE_CLASS,
+        &style! {
+            child_spacing: 0.pt(),
+        },
+    );
+
+    theme.insert_style(
+        ListBox::ROW_CLASS,
+        &style! {
+            border_width: 0.0.pt(),
+            corner_radius: 0.0.pt(),
+        },
+    );
+
+    theme.insert_style(
+        TextBox::TYPE_CLASS,
+        &style! {
+            width: 200.pt(),
+            height: 27.pt(),
+            border_color: focus_border,
+            background: ColorRole::Surface,
+        },
+    );
+
+    theme.insert_style(
+        Gauge::TYPE_CLASS,
+        &style! {
+            border_width: 0.0.pt(),
+            border_color: Color::greyscale(80),
+        },
+    );
+}
+
+/// Enforces white text and borders on a black background on top of [`create_theme`].
+///
+/// The styles have override priority or important properties, so they win over the instance
+/// styles of the widgets as well.
+pub fn apply_high_contrast(theme: &mut Theme) {
+    let text_color: PropertyFn<Color> = |_, _, enabled, _| {
+        if enabled {
+            Color::WHITE
+        } else {
+            Color::greyscale(176)
+        }
+    };
+    let focus_border: PropertyFn<Color> = |_, input_state, _, _| {
+        if input_state.focused || input_state.is_hovered() {
+            Color::rgb(255, 255, 0)
+        } else {
+            Color::WHITE
+        }
+    };
+
+    theme.insert_style_with_priority(
+        Theme::UNIVERSAL_CLASS,
+        StylePriority::Override,
+        &style! {
+            text_color: text_color,
+            border_color: Color::WHITE,
+        },
+    );
+    theme.insert_style_with_priority(
+        Theme::ROOT_TYPE_CLASS,
+        StylePriority::Override,
+        &style! {
+            background: Color::BLACK,
+        },
+    );
+    // the type class comes before the universal class, so buttons keep a focus indicator
+    theme.insert_style_with_priority(
+        Button::TYPE_CLASS,
+        StylePriority::Override,
+        &style! {
+            border_color: focus_border,
+        },
+    );
+    theme.insert_style(
+        Button::TYPE_CLASS,
+        &style! {
+            border_width: %important(2.pt()),
+        },
+    );
+}
+
+/// A cross, to have something to show in icon and canvas buttons.
+struct CrossIcon<R: Renderer>(PhantomData<fn(R)>);
+
+impl<R: Renderer> CrossIcon<R> {
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Renderer> NodeRenderer for CrossIcon<R> {
+    type Renderer = R;
+
+    fn render(&self, context: RenderContext<'_, R>) -> Result<(), R::Error> {
+        let size = context.size.x.min(context.size.y);
+        let center = context.position + context.size / 2.0;
+        let offset = Vec2 {
+            x: size / 3.0,
+            y: size / 3.0,
+        };
+        let mirrored_offset = Vec2 {
+            x: offset.x,
+            y: -offset.y,
+        };
+        // the text color of a style isn't public, node renderers bring their own colors
+        let color = TEXT_COLOR;
+
+        context
+            .renderer
+            .stroke_line(center - offset, center + offset, size / 8.0, color.into())?;
+        context.renderer.stroke_line(
+            center - mirrored_offset,
+            center + mirrored_offset,
+            size / 8.0,
+            color.into(),
+        )
+    }
+}
+
+/// Tags the node around every widget sample, so tests can find the widgets of a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub row: &'static str,
+    pub enabled: bool,
+}
+
+/// Shows a form row with `contents` twice side by side, enabled and disabled.
+///
+/// The UIDs of `contents` are scoped by the row, so the same widgets can be shown in every row.
+fn sample_row<R: Renderer>(
+    gui: &mut ByorGuiContext<'_, R>,
+    label: &'static str,
+    mut contents: impl FnMut(&mut ByorGuiContext<'_, R>) -> WidgetResult<()>,
+) -> WidgetResult<()> {
+    let row_style = style! {
+        layout_direction: Direction::LeftToRight,
+        cross_axis_alignment: Alignment::Center,
+        border_width: 0.0.pt(),
+        padding: 0.pt(),
+    };
+    let enabled_style = style! {
+        border_width: 0.0.pt(),
+        padding: 0.pt(),
+    };
+    let disabled_style = enabled_style.clone().with_enabled(false);
+
+    gui.form_row(label, |mut gui| {
+        gui.uid_scope(Uid::from_slice(label.as_bytes()), |gui| {
+            gui.insert_node(
+                Some(uid!("samples")),
+                &row_style,
+                NodeContents::builder(|mut gui| {
+                    for (enabled, style) in [(true, &enabled_style), (false, &disabled_style)] {
+                        gui.uid_scope(Uid::new(enabled), |gui| {
+                            let sample = Sample {
+                                row: label,
+                                enabled,
+                            };
+                            gui.insert_node(
+                                Some(uid!("sample")),
+                                style,
+                                NodeContents::builder(|mut gui| contents(&mut gui))
+                                    .with_tag(sample),
+                            )?
+                            .result
+                        })?;
+                    }
+
+                    Ok(())
+                }),
+            )?
+            .result
+        })
+    })?
+}
+
+pub fn show_gallery<R: Renderer>(
+    gallery: &mut Gallery,
+    mut gui: ByorGuiContext<'_, R>,
+) -> WidgetResult<()> {
+    let tab_bar_style = style! {
+        width: Sizing::Grow,
+        layout_direction: Direction::LeftToRight,
+        cross_axis_alignment: Alignment::Center,
+        border_width: 0.0.pt(),
+    };
+    gui.insert_node(
+        None,
+        &tab_bar_style,
+        NodeContents::builder(|mut gui| -> WidgetResult<()> {
+            for &tab in Tab::ALL {
+                let classes: &[StyleClass] = if tab == gallery.tab {
+                    &[TAB_CLASS, SELECTED_TAB_CLASS]
+                } else {
+                    &[TAB_CLASS]
+                };
+                let button = Button::default()
+                    .with_text(tab.name())
+                    .with_uid(Uid::new(tab))
+                    .with_classes(classes);
+                if gui.show(button)?.clicked(MouseButtons::PRIMARY) {
+                    gallery.tab = tab;
+                }
+            }
+
+            let direction_text = if gallery.right_to_left {
+                "Right to left"
+            } else {
+                "Left to right"
+            };
+            let direction_button = Button::default()
+                .with_text(direction_text)
+                .with_uid(uid!("direction"));
+            if gui.show(direction_button)?.clicked(MouseButtons::PRIMARY) {
+                gallery.right_to_left = !gallery.right_to_left;
+            }
+
+            let contrast_text = if gallery.high_contrast {
+                "High contrast"
+            } else {
+                "Normal contrast"
+            };
+            let contrast_button = Button::default()
+                .with_text(contrast_text)
+                .with_uid(uid!("contrast"));
+            if gui.show(contrast_button)?.clicked(MouseButtons::PRIMARY) {
+                gallery.high_contrast = !gallery.high_contrast;
+            }
+
+            Ok(())
+        }),
+    )?
+    .result?;
+
+    let ui_direction = if gallery.right_to_left {
+        UiDirection::Rtl
+    } else {
+        UiDirection::Ltr
+    };
+    let page_style = Style::default().with_ui_direction(ui_direction);
+    let page_classes = [PAGE_CLASS];
+    let page = ScrollView::vertical()
+        .with_uid(uid!("page"))
+        .with_classes(&page_classes)
+        .with_style(&page_style);
+    gui.show_container(page, |mut gui| {
+        gui.form(|mut gui| match gallery.tab {
+            Tab::Buttons => buttons_page(gallery, &mut gui),
+            Tab::Text => text_page(gallery, &mut gui),
+            Tab::Values => values_page(gallery, &mut gui),
+            Tab::Containers => containers_page(gallery, &mut gui),
+            Tab::Markdown => markdown_page(&mut gui),
+        })?
+    })?
+    .result
+}
+
+fn buttons_page<R: Renderer>(
+    gallery: &mut Gallery,
+    gui: &mut ByorGuiContext<'_, R>,
+) -> WidgetResult<()> {
+    sample_row(gui, "Button", |gui| gui.button("Click me").map(|_| ()))?;
+
+    sample_row(gui, "Icon button", |gui| {
+        gui.icon_button(CrossIcon::new(), "Close")?;
+        let trailing = Button::default()
+            .with_text("Next")
+            .with_icon(CrossIcon::new())
+            .with_icon_placement(IconPlacement::Trailing);
+        gui.show(trailing)?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Canvas button", |gui| {
+        let style = style! {
+            width: 24.pt(),
+            height: 24.pt(),
+        };
+        gui.show(CanvasButton::new(CrossIcon::new()).with_style(&style))?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Content button", |gui| {
+        gui.show_container(ContentButton::default(), |mut gui| {
+            gui.label("Label inside")?;
+            gui.gauge(0.5)
+        })?
+        .result
+    })?;
+
+    sample_row(gui, "Segmented control", |gui| {
+        gui.segmented_control(&["Day", "Week", "Month"], &mut gallery.segment)?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Breadcrumbs", |gui| {
+        let style = style! {
+            width: 200.pt(),
+        };
+        let labels = ["Home", "Documents", "Projects", "byor_gui", "src"];
+        gui.show(Breadcrumbs::new(&labels).with_style(&style))?;
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+fn text_page<R: Renderer>(
+    gallery: &mut Gallery,
+    gui: &mut ByorGuiContext<'_, R>,
+) -> WidgetResult<()> {
+    sample_row(gui, "Label", |gui| gui.label("The quick brown fox"))?;
+
+    let shortcut = Shortcut {
+        modifiers: Modifiers::CONTROL | Modifiers::SHIFT,
+        key: "s".into(),
+        location: None,
+    };
+    sample_row(gui, "Shortcut label", |gui| {
+        gui.show(ShortcutLabel::new(&shortcut).with_platform(Platform::Linux))
+    })?;
+
+    sample_row(gui, "Single line", |gui| {
+        let text_box = TextBox::new(&mut gallery.single_line_text).with_single_line(true);
+        gui.show(text_box).map(|_| ())
+    })?;
+
+    let multi_line_style = style! {
+        height: 72.pt(),
+    };
+    sample_row(gui, "Multi line", |gui| {
+        let text_box = TextBox::new(&mut gallery.multi_line_text).with_style(&multi_line_style);
+        gui.show(text_box).map(|_| ())
+    })?;
+
+    sample_row(gui, "Integer", |gui| {
+        let text_box = TextBox::new(&mut gallery.number_text)
+            .with_single_line(true)
+            .with_filter(TextFilter::integer());
+        gui.show(text_box).map(|_| ())
+    })?;
+
+    let mut read_only_text = "Can be selected, but not edited".to_string();
+    sample_row(gui, "Read only", |gui| {
+        let text_box = TextBox::new(&mut read_only_text)
+            .with_single_line(true)
+            .read_only(true);
+        gui.show(text_box).map(|_| ())
+    })?;
+
+    Ok(())
+}
+
+fn values_page<R: Renderer>(
+    gallery: &mut Gallery,
+    gui: &mut ByorGuiContext<'_, R>,
+) -> WidgetResult<()> {
+    sample_row(gui, "Scroll bar", |gui| {
+        gallery.scroll_value = gui.horizontal_scroll_bar(gallery.scroll_value, 0.0, 1.0)?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Range slider", |gui| {
+        gui.range_slider(&mut gallery.price_range, 0.0, 100.0)?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Date picker", |gui| {
+        gui.date_picker(&mut gallery.due_date)?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Gauge", |gui| {
+        gui.gauge(gallery.gauge_value)?;
+        let half = Gauge::new(gallery.gauge_value)
+            .with_start_angle(std::f32::consts::PI)
+            .with_sweep(std::f32::consts::PI)
+            .with_value_text(1);
+        gui.show(half)
+    })?;
+
+    Ok(())
+}
+
+fn containers_page<R: Renderer>(
+    gallery: &mut Gallery,
+    gui: &mut ByorGuiContext<'_, R>,
+) -> WidgetResult<()> {
+    sample_row(gui, "Flex panel", |gui| {
+        let style = style! {
+            layout_direction: Direction::LeftToRight,
+            border_width: 1.0.pt(),
+            padding: 4.pt(),
+        };
+        gui.show_container(FlexPanel::default().with_style(&style), |mut gui| {
+            gui.label("First")?;
+            gui.label("Second")
+        })?
+    })?;
+
+    sample_row(gui, "Scroll view", |gui| {
+        let style = style! {
+            width: 160.pt(),
+            height: 80.pt(),
+            border_width: 1.0.pt(),
+        };
+        let scroll_view = ScrollView::vertical().with_style(&style);
+        gui.show_container(scroll_view, |mut gui| {
+            gui.with_index_scope(0..8, |gui, i| gui.label(&format!("Item {i}")))
+        })?
+        .result
+    })?;
+
+    sample_row(gui, "List box", |gui| {
+        let style = style! {
+            width: 160.pt(),
+            border_width: 1.0.pt(),
+            padding: 0.pt(),
+        };
+        let list_box = ListBox::new(&mut gallery.list_selection, 4)
+            .with_mode(SelectionMode::Multiple)
+            .with_style(&style);
+        gui.list_box(list_box, |gui, i| gui.label(&format!("Item {i}")))?;
+        Ok(())
+    })?;
+
+    sample_row(gui, "Reorderable list", |gui| {
+        let style = style! {
+            width: 160.pt(),
+            border_width: 0.0.pt(),
+            padding: 0.pt(),
+        };
+        let items = &mut gallery.reorderable_items;
+        let list = ReorderableList::new(items.len())
+            .with_drag_handles(true)
+            .with_style(&style);
+        if let Some((from, to)) = gui.reorderable_list(list, |gui, i| gui.label(items[i]))? {
+            let item = items.remove(from);
+            items.insert(to, item);
+        }
+        Ok(())
+    })?;
+
+    gui.form_row("Popup", |mut gui| -> WidgetResult<()> {
+        if gui.button("Open popup")?.clicked(MouseButtons::PRIMARY) {
+            gallery.popup_open = true;
+        }
+
+        let position = FloatPosition::Popup {
+            x: PopupPosition::ParentStart,
+            y: PopupPosition::AfterParent,
+        };
+        gui.popup(&mut gallery.popup_open, position, |mut gui| {
+            gui.label("Click outside to close")
+        })?
+        .transpose()?;
+
+        Ok(())
+    })??;
+
+    gui.form_row("Dialog", |mut gui| {
+        if gui.button("Open dialog")?.clicked(MouseButtons::PRIMARY) {
+            gallery.dialog_open = true;
+        }
+
+        let options = ConfirmOptions {
+            title: "Delete item",
+            message: "The item can't be restored.",
+            confirm_label: "Delete",
+            danger: true,
+            ..ConfirmOptions::default()
+        };
+        gui.confirm_dialog(&mut gallery.dialog_open, options)
+            .map(|_| ())
+    })??;
+
+    Ok(())
+}
+
+#[cfg(feature = "markdown")]
+fn markdown_page<R: Renderer>(gui: &mut ByorGuiContext<'_, R>) -> WidgetResult<()> {
+    const DOCUMENT: &str = "# Heading\n\nA paragraph with *emphasis*, **strong** text and \
+        `code`.\n\n- First item\n- Second item\n\n```\nfn main() {}\n```\n\n---\n\nAnd a \
+        [link](https://example.com).";
+
+    sample_row(gui, "Markdown", |gui| gui.markdown(DOCUMENT).map(|_| ()))?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "markdown"))]
+fn markdown_page<R: Renderer>(gui: &mut ByorGuiContext<'_, R>) -> WidgetResult<()> {
+    gui.label("Run the gallery with the `markdown` feature to show this page.")
+}
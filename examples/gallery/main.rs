@@ -0,0 +1,282 @@
+//! Shows every built-in widget on a set of tabs.
+//!
+//! The same gallery is rendered headlessly by the gallery snapshot tests, see `tests/gallery.rs`.
+
+mod gallery;
+
+use anyhow::{Result, format_err};
+use byor_gui::input::*;
+use byor_gui::theme::Theme;
+use byor_gui::*;
+use gallery::Gallery;
+use std::sync::Arc;
+use vello::util::{RenderContext, RenderSurface};
+use vello::{Renderer, RendererOptions, Scene};
+use winit::event::{ElementState, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
+use winit::window::{Window, WindowId};
+
+fn main() -> Result<()> {
+    use winit::event_loop::EventLoop;
+
+    let event_loop = EventLoop::builder().build()?;
+    let mut app = GalleryApp::new();
+    event_loop.run_app(&mut app)?;
+
+    Ok(())
+}
+
+struct RenderState {
+    surface: RenderSurface<'static>,
+    renderer: Renderer,
+    surface_valid: bool,
+}
+
+struct GalleryApp {
+    context: RenderContext,
+    window: Option<Arc<Window>>,
+    state: Option<RenderState>,
+    required_redraws: u8,
+    gui: ByorGui<Scene>,
+    gallery: Gallery,
+    high_contrast: bool,
+}
+
+impl GalleryApp {
+    fn new() -> Self {
+        let mut gui = ByorGui::default();
+        gallery::create_theme(gui.theme_mut());
+
+        Self {
+            context: RenderContext::new(),
+            window: None,
+            state: None,
+            required_redraws: 2,
+            gui,
+            gallery: Gallery::default(),
+            high_contrast: false,
+        }
+    }
+
+    /// Rebuilds the theme after the gallery toggled the high contrast theme.
+    fn update_theme(&mut self) {
+        if self.gallery.high_contrast == self.high_contrast {
+            return;
+        }
+        self.high_contrast = self.gallery.high_contrast;
+
+        let theme = self.gui.theme_mut();
+        *theme = Theme::default();
+        gallery::create_theme(theme);
+        if self.high_contrast {
+            gallery::apply_high_contrast(theme);
+        }
+    }
+
+    fn on_input_event(&mut self, event: InputEvent) {
+        self.gui.on_input_event(event);
+
+        self.required_redraws = self.required_redraws.max(2);
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+}
+
+impl winit::application::ApplicationHandler for GalleryApp {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        use vello::AaSupport;
+        use vello::wgpu::PresentMode;
+
+        let window = if let Some(window) = self.window.as_ref() {
+            window.clone()
+        } else {
+            let window = event_loop
+                .create_window(Window::default_attributes().with_title("byorGUI Gallery"))
+                .expect("failed to create window");
+            let window = Arc::new(window);
+            self.window = Some(window.clone());
+            window
+        };
+
+        if self.state.is_none() {
+            let window_size = window.inner_size();
+            let surface = pollster::block_on(self.context.create_surface(
+                window,
+                window_size.width,
+                window_size.height,
+                PresentMode::AutoNoVsync,
+            ))
+            .expect("failed to create surface");
+
+            let device_handle = &self.context.devices[surface.dev_id];
+            let renderer = Renderer::new(
+                &device_handle.device,
+                RendererOptions {
+                    antialiasing_support: AaSupport::area_only(),
+                    ..Default::default()
+                },
+            )
+            .map_err(|e| format_err!("{e}"))
+            .expect("failed to create renderer");
+
+            self.state = Some(RenderState {
+                surface,
+                renderer,
+                surface_valid: false,
+            });
+        }
+    }
+
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        self.state = None;
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        let Some(window) = self.window.clone() else {
+            return;
+        };
+        if window.id() != window_id {
+            return;
+        }
+
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.gui.set_scale_factor(scale_factor as f32);
+
+                self.required_redraws = self.required_redraws.max(2);
+                window.request_redraw();
+            }
+            WindowEvent::KeyboardInput { event, .. } => self.on_input_event(event.into()),
+            WindowEvent::MouseInput { state, button, .. } => {
+                if let Ok(button) = button.try_into() {
+                    match state {
+                        ElementState::Pressed => {
+                            self.on_input_event(InputEvent::ButtonPressed { button })
+                        }
+                        ElementState::Released => {
+                            self.on_input_event(InputEvent::ButtonReleased { button })
+                        }
+                    }
+                }
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.on_input_event(InputEvent::Scrolled {
+                    delta: delta.into(),
+                });
+            }
+            WindowEvent::CursorLeft { .. } => self.on_input_event(InputEvent::CursorLeft),
+            WindowEvent::CursorMoved { position, .. } => {
+                self.on_input_event(InputEvent::CursorMoved {
+                    position: position.into(),
+                });
+            }
+            WindowEvent::Resized(size) => {
+                if let Some(state) = self.state.as_mut() {
+                    if (size.width != 0) && (size.height != 0) {
+                        self.context
+                            .resize_surface(&mut state.surface, size.width, size.height);
+                        state.surface_valid = true;
+
+                        self.required_redraws = self.required_redraws.max(2);
+                        window.request_redraw();
+                    } else {
+                        state.surface_valid = false;
+                    }
+                }
+            }
+            WindowEvent::RedrawRequested => {
+                use vello::wgpu::{CommandEncoderDescriptor, PollType};
+                use vello::{AaConfig, RenderParams};
+
+                if let Some(&mut RenderState {
+                    ref mut surface,
+                    ref mut renderer,
+                    surface_valid,
+                }) = self.state.as_mut()
+                    && surface_valid
+                {
+                    self.gui
+                        .try_frame(
+                            Vec2 {
+                                x: surface.config.width.px(),
+                                y: surface.config.height.px(),
+                            },
+                            |gui| gallery::show_gallery(&mut self.gallery, gui),
+                        )
+                        .result
+                        .map_err(|e| format_err!("{e}"))
+                        .expect("error building GUI");
+
+                    let mut scene = Scene::new();
+                    self.gui.render(&mut scene).unwrap();
+
+                    let device_handle = &self.context.devices[surface.dev_id];
+                    let render_params = RenderParams {
+                        base_color: vello::peniko::Color::BLACK,
+                        width: surface.config.width,
+                        height: surface.config.height,
+                        antialiasing_method: AaConfig::Area,
+                    };
+                    renderer
+                        .render_to_texture(
+                            &device_handle.device,
+                            &device_handle.queue,
+                            &scene,
+                            &surface.target_view,
+                            &render_params,
+                        )
+                        .expect("failed to render scene");
+
+                    let frame_buffer = surface
+                        .surface
+                        .get_current_texture()
+                        .expect("failed to aquire frame buffer");
+                    let mut encoder =
+                        device_handle
+                            .device
+                            .create_command_encoder(&CommandEncoderDescriptor {
+                                label: Some("Frame Buffer Blit"),
+                            });
+                    surface.blitter.copy(
+                        &device_handle.device,
+                        &mut encoder,
+                        &surface.target_view,
+                        &frame_buffer.texture.create_view(&Default::default()),
+                    );
+                    device_handle.queue.submit([encoder.finish()]);
+                    frame_buffer.present();
+
+                    device_handle.device.poll(PollType::Poll).unwrap();
+                }
+                // the next of the required redraws shows the new theme
+                self.update_theme();
+
+                self.required_redraws = self.required_redraws.saturating_sub(1);
+                if self.required_redraws > 0 {
+                    window.request_redraw();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        match self.gui.next_animation_frame() {
+            Some(next_frame) if next_frame <= std::time::Instant::now() => {
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+                event_loop.set_control_flow(ControlFlow::Wait);
+            }
+            Some(next_frame) => event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame)),
+            None => event_loop.set_control_flow(ControlFlow::Wait),
+        }
+    }
+}
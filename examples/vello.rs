@@ -7,8 +7,8 @@ use byor_gui::*;
 use std::sync::Arc;
 use vello::util::{RenderContext, RenderSurface};
 use vello::{Renderer, RendererOptions, Scene};
-use winit::event::{ElementState, MouseScrollDelta, WindowEvent};
-use winit::event_loop::ActiveEventLoop;
+use winit::event::{ElementState, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
 use winit::window::{Window, WindowId};
 
 fn main() -> Result<()> {
@@ -30,6 +30,7 @@ struct RenderState {
 struct ExampleAppState {
     show_popup: bool,
     edit_text: String,
+    scroll_speed: f32,
 }
 
 impl Default for ExampleAppState {
@@ -37,6 +38,7 @@ impl Default for ExampleAppState {
         Self {
             show_popup: false,
             edit_text: include_str!("lorem_ipsum.txt").to_string(),
+            scroll_speed: 1.0,
         }
     }
 }
@@ -160,38 +162,51 @@ impl winit::application::ApplicationHandler for ExampleApp {
                 window.request_redraw();
             }
             WindowEvent::MouseWheel { delta, .. } => {
-                match delta {
-                    MouseScrollDelta::LineDelta(x, y) => {
-                        let delta = if self
-                            .gui
-                            .input_state()
-                            .modifiers()
-                            .contains(Modifiers::CONTROL)
-                        {
-                            ScrollDelta::Point(Vec2 {
-                                x: y * POINTS_PER_SCROLL_LINE,
-                                y: x * POINTS_PER_SCROLL_LINE,
-                            })
-                        } else {
-                            ScrollDelta::Point(Vec2 {
-                                x: x * POINTS_PER_SCROLL_LINE,
-                                y: y * POINTS_PER_SCROLL_LINE,
-                            })
-                        };
-
-                        self.gui.on_input_event(InputEvent::Scrolled { delta });
-                    }
-                    MouseScrollDelta::PixelDelta(delta) => {
-                        self.gui.on_input_event(InputEvent::Scrolled {
-                            delta: ScrollDelta::Pixel(delta.into()),
+                let mut delta = ScrollDelta::from(delta);
+                let modifiers = self.gui.input_state().modifiers();
+
+                if modifiers.contains(Modifiers::CONTROL) {
+                    let center = self.gui.input_state().last_cursor_position();
+                    self.gui.on_input_event(InputEvent::Zoom {
+                        delta: delta.to_zoom_delta(self.gui.scale_factor()),
+                        center,
+                    });
+                } else {
+                    // without a tilt wheel, Shift turns the vertical wheel into a horizontal one
+                    if let ScrollDelta::Point(line_delta) = delta
+                        && (line_delta.x == 0.pt())
+                        && modifiers.contains(Modifiers::SHIFT)
+                    {
+                        delta = ScrollDelta::Point(Vec2 {
+                            x: line_delta.y,
+                            y: line_delta.x,
                         });
                     }
+
+                    self.gui.on_input_event(InputEvent::Scrolled { delta });
                 }
 
                 self.required_redraws = self.required_redraws.max(2);
                 window.request_redraw();
             }
-            WindowEvent::CursorEntered { .. } | WindowEvent::CursorLeft { .. } => {
+            WindowEvent::PinchGesture { delta, .. } => {
+                let center = self.gui.input_state().last_cursor_position();
+                self.gui.on_input_event(InputEvent::Zoom {
+                    delta: delta as f32,
+                    center,
+                });
+
+                self.required_redraws = self.required_redraws.max(2);
+                window.request_redraw();
+            }
+            WindowEvent::CursorEntered { .. } => {
+                // winit doesn't report where the cursor entered, it sends a `CursorMoved` instead
+                self.required_redraws = self.required_redraws.max(2);
+                window.request_redraw();
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.gui.on_input_event(InputEvent::CursorLeft);
+
                 self.required_redraws = self.required_redraws.max(2);
                 window.request_redraw();
             }
@@ -229,13 +244,16 @@ impl winit::application::ApplicationHandler for ExampleApp {
                     && surface_valid
                 {
                     self.gui
-                        .frame(
+                        .set_scroll_speed_multiplier(self.app_state.scroll_speed);
+                    self.gui
+                        .try_frame(
                             Vec2 {
                                 x: surface.config.width.px(),
                                 y: surface.config.height.px(),
                             },
                             |gui| build_gui(&mut self.app_state, gui),
                         )
+                        .result
                         .map_err(|e| format_err!("{e}"))
                         .expect("error building GUI");
 
@@ -289,9 +307,28 @@ impl winit::application::ApplicationHandler for ExampleApp {
             _ => (),
         }
     }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        match self.gui.next_animation_frame() {
+            Some(next_frame) if next_frame <= std::time::Instant::now() => {
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+                event_loop.set_control_flow(ControlFlow::Wait);
+            }
+            Some(next_frame) => event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame)),
+            None => event_loop.set_control_flow(ControlFlow::Wait),
+        }
+    }
 }
 
+const WARM_SELECTION_CLASS: StyleClass = StyleClass::new_static("warm_selection");
+
 fn create_theme(theme: &mut Theme) {
+    theme.set_role(ColorRole::Accent, Color::rgb(38, 79, 120));
+    theme.set_role(ColorRole::Surface, Color::greyscale(32));
+    theme.set_role(ColorRole::OnSurface, Color::greyscale(224));
+
     theme.insert_style(
         Theme::UNIVERSAL_CLASS,
         &style! {
@@ -310,7 +347,8 @@ fn create_theme(theme: &mut Theme) {
             background: Color::greyscale(48),
             border_width: 0.0.pt(),
             corner_radius: 0.0.pt(),
-            text_color: Color::greyscale(224),
+            text_color: ColorRole::OnSurface,
+            selection_color: ColorRole::Accent,
         },
     );
 
@@ -322,7 +360,7 @@ fn create_theme(theme: &mut Theme) {
         },
     );
 
-    let button_background: PropertyFn<Brush> = |_, input_state| {
+    let button_background: PropertyFn<Brush> = |_, input_state, _, _| {
         if input_state.pressed(MouseButtons::PRIMARY) {
             Color::greyscale(96).into()
         } else if input_state.is_hovered() {
@@ -336,6 +374,8 @@ fn create_theme(theme: &mut Theme) {
         Button::TYPE_CLASS,
         &style! {
             background: button_background,
+            vertical_text_alignment: VerticalTextAlignment::Center,
+            vertical_text_anchor: VerticalTextAnchor::CapHeight,
         },
     );
 
@@ -355,7 +395,7 @@ fn create_theme(theme: &mut Theme) {
             padding: 0.px(),
             child_spacing: 1.pt(),
             border_width: 0.0.px(),
-            background: Color::greyscale(32),
+            background: ColorRole::Surface,
         },
     );
 
@@ -367,7 +407,7 @@ fn create_theme(theme: &mut Theme) {
             padding: 0.px(),
             child_spacing: 1.pt(),
             border_width: 0.0.px(),
-            background: Color::greyscale(32),
+            background: ColorRole::Surface,
         },
     );
 
@@ -429,15 +469,16 @@ fn create_theme(theme: &mut Theme) {
     theme.insert_style(
         Popup::TYPE_CLASS,
         &style! {
-            background: Color::greyscale(40),
+            background: Color { r: 40, g: 40, b: 40, a: 160 },
+            backdrop_blur: 8.pt(),
             drop_shadow_width: 20.pt(),
             drop_shadow_color: Color { r: 0, g: 0, b: 0, a: 196 },
         },
     );
 
-    let text_box_border: PropertyFn<Color> = |_, input_state| {
+    let text_box_border: PropertyFn<Color> = |_, input_state, _, theme| {
         if input_state.focused {
-            Color::greyscale(224)
+            theme.role(ColorRole::OnSurface)
         } else if input_state.is_hovered() {
             Color::greyscale(192)
         } else {
@@ -451,7 +492,15 @@ fn create_theme(theme: &mut Theme) {
             width: Sizing::Grow,
             height: Sizing::Grow,
             border_color: text_box_border,
-            background: Color::greyscale(32),
+            background: ColorRole::Surface,
+        },
+    );
+
+    theme.insert_style(
+        WARM_SELECTION_CLASS,
+        &style! {
+            selection_color: ColorRole::Warning,
+            selection_text_color: ColorRole::Surface,
         },
     );
 }
@@ -460,9 +509,21 @@ fn build_gui(
     app_state: &mut ExampleAppState,
     mut gui: ByorGuiContext<'_, Scene>,
 ) -> WidgetResult<()> {
-    gui.vertical_scroll_view(|mut gui| {
+    let speed_text = format!("Scroll speed: {}x", app_state.scroll_speed);
+    if gui
+        .show(Button::default().with_text(&speed_text))?
+        .clicked(MouseButtons::PRIMARY)
+    {
+        app_state.scroll_speed = (app_state.scroll_speed % 3.0) + 1.0;
+    }
+
+    // color glyphs come from a fallback font and share a line with regular text
+    let emoji = "Emoji: \u{2705} done, \u{1F389} party, \u{1F44D}\u{1F3FD} thumbs up";
+    gui.show(Label::default().with_text(emoji))?;
+
+    gui.vertical_scroll_view(|mut gui| -> WidgetResult<()> {
         for i in 0..5 {
-            gui.uid_scope(Uid::new(i), |gui| {
+            gui.uid_scope(Uid::new(i), |gui| -> WidgetResult<()> {
                 gui.insert_node(
                     Some(Uid::from_array(b"test")),
                     &style! {
@@ -480,9 +541,10 @@ fn build_gui(
         }
 
         Ok(())
-    })??;
+    })?
+    .result?;
 
-    gui.horizontal_scroll_view(|mut gui| {
+    gui.horizontal_scroll_view(|mut gui| -> WidgetResult<()> {
         for _ in 0..5 {
             gui.insert_node(
                 None,
@@ -498,13 +560,14 @@ fn build_gui(
         }
 
         Ok(())
-    })??;
+    })?
+    .result?;
 
     let style = style! {
         layout_direction: Direction::TopToBottom,
     };
     let panel = FlexPanel::default().with_style(&style);
-    gui.show_container(panel, |mut gui| {
+    gui.show_container(panel, |mut gui| -> WidgetResult<()> {
         gui.insert_node(
             None,
             &style! {
@@ -519,9 +582,9 @@ fn build_gui(
             child_alignment: Alignment::Center,
         };
         let panel = FlexPanel::default().with_style(&style);
-        gui.show_container(panel, |mut gui| {
+        gui.show_container(panel, |mut gui| -> WidgetResult<()> {
             gui.insert_node(
-                const { Some(Uid::from_slice(b"popup_parent")) },
+                Some(uid!("popup_parent")),
                 &style! {
                     width: 100.pt(),
                     height: 100.pt(),
@@ -530,15 +593,15 @@ fn build_gui(
                     border_color: %inherit,
                     corner_radius: 5.0.pt(),
                 },
-                NodeContents::builder(|mut gui| {
+                NodeContents::builder(|mut gui| -> WidgetResult<()> {
                     if gui.parent_input_state().clicked(MouseButtons::SECONDARY) {
                         app_state.show_popup = true;
                     }
 
                     gui.popup(
                         &mut app_state.show_popup,
-                        FloatPosition::CursorFixed,
-                        |mut gui| {
+                        FloatPosition::CURSOR_FIXED,
+                        |mut gui| -> WidgetResult<()> {
                             let style = style! {
                                 max_width: 300.px(),
                                 padding: 5.pt(),
@@ -559,7 +622,7 @@ fn build_gui(
             )?
             .result?;
 
-            gui.text_box(&mut app_state.edit_text)?;
+            gui.show(TextBox::new(&mut app_state.edit_text).with_classes(&[WARM_SELECTION_CLASS]))?;
 
             Ok(())
         })??;
@@ -0,0 +1,308 @@
+//! A canvas that zooms around the cursor, with a pinch gesture or the scroll wheel while
+//! <kbd>Ctrl</kbd> is held.
+
+use anyhow::{Result, format_err};
+use byor_gui::input::*;
+use byor_gui::rendering::{NodeRenderer, RenderContext};
+use byor_gui::style::*;
+use byor_gui::*;
+use std::sync::Arc;
+use vello::util::{RenderContext as VelloContext, RenderSurface};
+use vello::{Renderer, RendererOptions, Scene};
+use winit::event::{ElementState, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
+use winit::window::{Window, WindowId};
+
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 8.0;
+const CELL_SIZE: Float<Pixel> = Float::px(40.0);
+const GRID_SIZE: usize = 32;
+
+fn main() -> Result<()> {
+    use winit::event_loop::EventLoop;
+
+    let event_loop = EventLoop::builder().build()?;
+    let mut app = ZoomApp {
+        context: VelloContext::new(),
+        window: None,
+        state: None,
+        gui: ByorGui::default(),
+        canvas: CanvasState {
+            zoom: 1.0,
+            offset: Vec2::ZERO,
+        },
+    };
+    event_loop.run_app(&mut app)?;
+
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+struct CanvasState {
+    zoom: f32,
+    /// Where the top left corner of the grid is shown, relative to the canvas.
+    offset: Vec2<Pixel>,
+}
+
+impl CanvasState {
+    /// Zooms while keeping the point under `center` in place.
+    fn zoom_around(&mut self, delta: f32, center: Vec2<Pixel>) {
+        let zoom = (self.zoom * (1.0 + delta)).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset = center - (center - self.offset) * (zoom / self.zoom);
+        self.zoom = zoom;
+    }
+}
+
+struct CanvasRenderer {
+    state: CanvasState,
+}
+
+impl NodeRenderer for CanvasRenderer {
+    type Renderer = Scene;
+
+    fn render(&self, context: RenderContext<'_, Scene>) -> Result<(), std::convert::Infallible> {
+        use byor_gui::rendering::Renderer as _;
+
+        let cell_size = CELL_SIZE * self.state.zoom;
+        for row in 0..GRID_SIZE {
+            for column in 0..GRID_SIZE {
+                let color = if (row + column) % 2 == 0 {
+                    Color::greyscale(64)
+                } else {
+                    Color::rgb(66, 135, 245)
+                };
+                let position = Vec2 {
+                    x: cell_size * (column as f32),
+                    y: cell_size * (row as f32),
+                };
+
+                context.renderer.fill_rect(
+                    context.position + self.state.offset + position,
+                    Vec2 {
+                        x: cell_size,
+                        y: cell_size,
+                    },
+                    0.px(),
+                    color.into(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct RenderState {
+    surface: RenderSurface<'static>,
+    renderer: Renderer,
+}
+
+struct ZoomApp {
+    context: VelloContext,
+    window: Option<Arc<Window>>,
+    state: Option<RenderState>,
+    gui: ByorGui<Scene>,
+    canvas: CanvasState,
+}
+
+impl ZoomApp {
+    fn build_gui(&mut self, size: Vec2<Pixel>) {
+        const CANVAS_UID: Uid = Uid::from_slice(b"canvas");
+
+        let canvas = &mut self.canvas;
+        self.gui.frame(size, |mut gui| {
+            let style = style! {
+                width: Sizing::Grow,
+                height: Sizing::Grow,
+            };
+            let contents = NodeContents::renderer(CanvasRenderer { state: *canvas });
+            let response = gui
+                .insert_node(Some(CANVAS_UID), &style, contents)
+                .expect("error building GUI");
+
+            let input_state = gui.global_input_state();
+            if response.is_hovered()
+                && let Some(center) = input_state.zoom_center()
+            {
+                let position = gui
+                    .previous_state(CANVAS_UID)
+                    .map(|state| state.position)
+                    .unwrap_or_default();
+                canvas.zoom_around(input_state.zoom_delta(), center - position);
+            }
+        });
+    }
+}
+
+impl winit::application::ApplicationHandler for ZoomApp {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        use vello::AaSupport;
+        use vello::wgpu::PresentMode;
+
+        if self.state.is_some() {
+            return;
+        }
+
+        let window = Arc::new(
+            event_loop
+                .create_window(Window::default_attributes().with_title("byorGUI Zoom"))
+                .expect("failed to create window"),
+        );
+        self.window = Some(window.clone());
+
+        let window_size = window.inner_size();
+        let surface = pollster::block_on(self.context.create_surface(
+            window,
+            window_size.width,
+            window_size.height,
+            PresentMode::AutoNoVsync,
+        ))
+        .expect("failed to create surface");
+
+        let device_handle = &self.context.devices[surface.dev_id];
+        let renderer = Renderer::new(
+            &device_handle.device,
+            RendererOptions {
+                antialiasing_support: AaSupport::area_only(),
+                ..Default::default()
+            },
+        )
+        .map_err(|e| format_err!("{e}"))
+        .expect("failed to create renderer");
+
+        self.state = Some(RenderState { surface, renderer });
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        _window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        let Some(window) = self.window.clone() else {
+            return;
+        };
+
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::KeyboardInput { event, .. } => self.gui.on_input_event(event.into()),
+            WindowEvent::CursorMoved { position, .. } => {
+                self.gui.on_input_event(InputEvent::CursorMoved {
+                    position: position.into(),
+                })
+            }
+            WindowEvent::CursorLeft { .. } => self.gui.on_input_event(InputEvent::CursorLeft),
+            WindowEvent::MouseInput { state, button, .. } => {
+                if let Ok(button) = button.try_into() {
+                    self.gui.on_input_event(match state {
+                        ElementState::Pressed => InputEvent::ButtonPressed { button },
+                        ElementState::Released => InputEvent::ButtonReleased { button },
+                    });
+                }
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = ScrollDelta::from(delta);
+                let center = self.gui.input_state().last_cursor_position();
+
+                // the scroll wheel only zooms while Ctrl is held, like in most applications
+                if self
+                    .gui
+                    .input_state()
+                    .modifiers()
+                    .contains(Modifiers::CONTROL)
+                {
+                    self.gui.on_input_event(InputEvent::Zoom {
+                        delta: delta.to_zoom_delta(self.gui.scale_factor()),
+                        center,
+                    });
+                } else {
+                    self.gui.on_input_event(InputEvent::Scrolled { delta });
+                }
+            }
+            WindowEvent::PinchGesture { delta, .. } => {
+                let center = self.gui.input_state().last_cursor_position();
+                self.gui.on_input_event(InputEvent::Zoom {
+                    delta: delta as f32,
+                    center,
+                });
+            }
+            WindowEvent::Resized(size) => {
+                if let Some(state) = self.state.as_mut()
+                    && (size.width != 0)
+                    && (size.height != 0)
+                {
+                    self.context
+                        .resize_surface(&mut state.surface, size.width, size.height);
+                }
+            }
+            WindowEvent::RedrawRequested => {
+                use vello::wgpu::{CommandEncoderDescriptor, PollType};
+                use vello::{AaConfig, RenderParams};
+
+                let Some(state) = self.state.as_mut() else {
+                    return;
+                };
+                let (width, height) = (state.surface.config.width, state.surface.config.height);
+
+                self.build_gui(Vec2 {
+                    x: width.px(),
+                    y: height.px(),
+                });
+
+                let Some(state) = self.state.as_mut() else {
+                    return;
+                };
+                let mut scene = Scene::new();
+                self.gui.render(&mut scene).unwrap();
+
+                let device_handle = &self.context.devices[state.surface.dev_id];
+                let render_params = RenderParams {
+                    base_color: vello::peniko::Color::BLACK,
+                    width,
+                    height,
+                    antialiasing_method: AaConfig::Area,
+                };
+                state
+                    .renderer
+                    .render_to_texture(
+                        &device_handle.device,
+                        &device_handle.queue,
+                        &scene,
+                        &state.surface.target_view,
+                        &render_params,
+                    )
+                    .expect("failed to render scene");
+
+                let frame_buffer = state
+                    .surface
+                    .surface
+                    .get_current_texture()
+                    .expect("failed to aquire frame buffer");
+                let mut encoder =
+                    device_handle
+                        .device
+                        .create_command_encoder(&CommandEncoderDescriptor {
+                            label: Some("Frame Buffer Blit"),
+                        });
+                state.surface.blitter.copy(
+                    &device_handle.device,
+                    &mut encoder,
+                    &state.surface.target_view,
+                    &frame_buffer.texture.create_view(&Default::default()),
+                );
+                device_handle.queue.submit([encoder.finish()]);
+                frame_buffer.present();
+
+                device_handle.device.poll(PollType::Poll).unwrap();
+                return;
+            }
+            _ => return,
+        }
+
+        window.request_redraw();
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        event_loop.set_control_flow(ControlFlow::Wait);
+    }
+}
@@ -0,0 +1,149 @@
+pub mod easing;
+
+use crate::*;
+
+/// How an animated value moves from its start to its target, see [`easing`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    #[default]
+    CubicInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    /// Overshoots the target and settles in a damped oscillation.
+    Spring,
+}
+
+impl Easing {
+    /// Maps the linear progress `t` in `0..=1` onto the eased progress.
+    #[must_use]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => easing::linear(t),
+            Self::QuadIn => easing::quad_in(t),
+            Self::QuadOut => easing::quad_out(t),
+            Self::QuadInOut => easing::quad_in_out(t),
+            Self::CubicIn => easing::cubic_in(t),
+            Self::CubicOut => easing::cubic_out(t),
+            Self::CubicInOut => easing::cubic_in_out(t),
+            Self::ExpoIn => easing::expo_in(t),
+            Self::ExpoOut => easing::expo_out(t),
+            Self::ExpoInOut => easing::expo_in_out(t),
+            Self::Spring => easing::spring(t),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationConfig {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl AnimationConfig {
+    pub const DEFAULT: Self = Self {
+        duration: Duration::from_millis(200),
+        easing: Easing::CubicInOut,
+    };
+
+    #[must_use]
+    #[inline]
+    pub const fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            ..Self::DEFAULT
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn with_easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+}
+
+impl Default for AnimationConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimatedValue {
+    pub value: f32,
+    /// Whether the value has reached its target.
+    pub finished: bool,
+}
+
+/// The persistent state of an animated value.
+pub(crate) struct Tween {
+    from: f32,
+    to: f32,
+    start: Instant,
+}
+
+impl Tween {
+    #[must_use]
+    fn progress(&self, time: Instant, duration: Duration) -> f32 {
+        if duration.is_zero() {
+            return 1.0;
+        }
+
+        let elapsed = time.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+    }
+
+    #[must_use]
+    fn value(&self, time: Instant, config: AnimationConfig) -> f32 {
+        let t = config.easing.apply(self.progress(time, config.duration));
+        self.from + (self.to - self.from) * t
+    }
+}
+
+impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    /// Animates a value towards `target`, restarting from the current value whenever the target
+    /// changes. The first call for a UID starts out at the target.
+    ///
+    /// While the value is still moving, the GUI requests to be rebuilt every frame.
+    #[must_use]
+    pub fn animate(&mut self, uid: Uid, target: f32, config: AnimationConfig) -> AnimatedValue {
+        let time = self.time();
+        self.discard_mismatched_state::<Tween>(uid, PersistentStateKey::Animation);
+        let tween = self
+            .persistent_state_mut(uid)
+            .get_or_insert_with(PersistentStateKey::Animation, || Tween {
+                from: target,
+                to: target,
+                start: time,
+            })
+            .expect("mismatched animation was discarded");
+
+        if tween.to != target {
+            *tween = Tween {
+                from: tween.value(time, config),
+                to: target,
+                start: time,
+            };
+        }
+
+        let finished = (tween.from == tween.to) || (tween.progress(time, config.duration) >= 1.0);
+        let value = if finished {
+            target
+        } else {
+            tween.value(time, config)
+        };
+        if !finished {
+            self.request_animation_frame(time);
+        }
+
+        AnimatedValue { value, finished }
+    }
+}
@@ -0,0 +1,91 @@
+//! Easing functions mapping a linear progress `t` in `0..=1` onto an eased progress.
+//! All of them start at 0 and end at 1.
+
+use std::f32::consts::PI;
+
+#[must_use]
+#[inline]
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+#[must_use]
+#[inline]
+pub fn quad_in(t: f32) -> f32 {
+    t * t
+}
+
+#[must_use]
+#[inline]
+pub fn quad_out(t: f32) -> f32 {
+    1.0 - quad_in(1.0 - t)
+}
+
+#[must_use]
+#[inline]
+pub fn quad_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (2.0 - 2.0 * t).powi(2) / 2.0
+    }
+}
+
+#[must_use]
+#[inline]
+pub fn cubic_in(t: f32) -> f32 {
+    t * t * t
+}
+
+#[must_use]
+#[inline]
+pub fn cubic_out(t: f32) -> f32 {
+    1.0 - cubic_in(1.0 - t)
+}
+
+#[must_use]
+#[inline]
+pub fn cubic_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+    }
+}
+
+#[must_use]
+#[inline]
+pub fn expo_in(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else {
+        2f32.powf(10.0 * t - 10.0)
+    }
+}
+
+#[must_use]
+#[inline]
+pub fn expo_out(t: f32) -> f32 {
+    1.0 - expo_in(1.0 - t)
+}
+
+#[must_use]
+#[inline]
+pub fn expo_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        expo_in(2.0 * t) / 2.0
+    } else {
+        1.0 - expo_in(2.0 - 2.0 * t) / 2.0
+    }
+}
+
+/// Overshoots the target and settles in a damped oscillation.
+#[must_use]
+#[inline]
+pub fn spring(t: f32) -> f32 {
+    if t >= 1.0 {
+        1.0
+    } else {
+        1.0 - (-6.0 * t).exp() * (2.5 * PI * t).cos()
+    }
+}
@@ -0,0 +1,120 @@
+//! Showing values that become available later, like images downloaded in the background.
+//!
+//! The GUI doesn't run or poll any futures itself. The app stores a [`Deferred`], replaces it
+//! from its own tasks once the value is loaded, and shows it with [`ByorGuiContext::deferred`].
+
+use crate::*;
+
+/// How long a frame showing a [`Deferred::Loading`] value stays valid, see
+/// [`ByorGui::next_animation_frame`]. The GUI can't know when the value changes, so it gets rebuilt
+/// in this interval until the value has settled. Apps that wake their event loop when a value
+/// finishes loading pick up the change right away.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A value that is either still loading, loaded or failed to load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deferred<T, E> {
+    Loading,
+    Ready(T),
+    Failed(E),
+}
+
+impl<T, E> Default for Deferred<T, E> {
+    #[inline]
+    fn default() -> Self {
+        Self::Loading
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Deferred<T, E> {
+    #[inline]
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Self::Ready(value),
+            Err(error) => Self::Failed(error),
+        }
+    }
+}
+
+impl<T, E> Deferred<T, E> {
+    #[must_use]
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Ready(_))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Self::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn error(&self) -> Option<&E> {
+        match self {
+            Self::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn as_ref(&self) -> Deferred<&T, &E> {
+        match self {
+            Self::Loading => Deferred::Loading,
+            Self::Ready(value) => Deferred::Ready(value),
+            Self::Failed(error) => Deferred::Failed(error),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Deferred<U, E> {
+        match self {
+            Self::Loading => Deferred::Loading,
+            Self::Ready(value) => Deferred::Ready(f(value)),
+            Self::Failed(error) => Deferred::Failed(error),
+        }
+    }
+}
+
+impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    /// Shows the branch matching the state of `value`.
+    ///
+    /// While the value is loading, the GUI requests to be rebuilt within [`POLL_INTERVAL`], so it
+    /// notices when the app replaces the value. Once every deferred value of a frame has settled,
+    /// no more frames are requested because of them.
+    pub fn deferred<T, E, R>(
+        &mut self,
+        value: &Deferred<T, E>,
+        on_loading: impl FnOnce(&mut ByorGuiContext<'_, Renderer>) -> R,
+        on_ready: impl FnOnce(&mut ByorGuiContext<'_, Renderer>, &T) -> R,
+        on_failed: impl FnOnce(&mut ByorGuiContext<'_, Renderer>, &E) -> R,
+    ) -> R {
+        match value {
+            Deferred::Loading => {
+                let time = self.time();
+                self.request_animation_frame(time + POLL_INTERVAL);
+                on_loading(self)
+            }
+            Deferred::Ready(value) => on_ready(self, value),
+            Deferred::Failed(error) => on_failed(self, error),
+        }
+    }
+}
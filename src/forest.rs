@@ -1,5 +1,6 @@
 use crate::multi_vec::MultiVec;
 use modular_bitfield::prelude::*;
+use smallvec::SmallVec;
 use std::fmt;
 use std::marker::PhantomData;
 use std::ops::{Deref, Index, IndexMut};
@@ -111,7 +112,11 @@ impl<'a, T, M: Mutability> Descendants<'a, T, M> {
 
     #[must_use]
     pub fn split_first(self) -> Option<(TreeRef<'a, T, Shared>, Descendants<'a, T, Shared>)> {
+        // SAFETY: both pointers come from slices of `len` elements that are borrowed for `'a`.
+        // The nodes are only read through the returned shared references, and for exclusive
+        // descendants `self` is consumed, so it can't write to them while they are alive.
         let nodes = unsafe { std::slice::from_raw_parts(self.nodes, self.len as usize) };
+        // SAFETY: see above, tree properties are never written through `Descendants`
         let tree_properties =
             unsafe { std::slice::from_raw_parts(self.tree_properties, self.len as usize) };
 
@@ -136,6 +141,8 @@ impl<'a, T, M: Mutability> Descendants<'a, T, M> {
         ))
     }
 
+    /// The nodes can't be written through `self` while the returned descendants are alive,
+    /// since they borrow it.
     #[must_use]
     #[inline]
     pub fn reborrow(&self) -> Descendants<'_, T, Shared> {
@@ -154,7 +161,10 @@ impl<'a, T> Descendants<'a, T, Exclusive> {
     pub fn split_first_mut(
         self,
     ) -> Option<(TreeRef<'a, T, Exclusive>, Descendants<'a, T, Exclusive>)> {
+        // SAFETY: the pointer comes from a slice of `len` elements that is exclusively borrowed
+        // for `'a`, and `self` is consumed, so this is the only reference to the nodes.
         let nodes = unsafe { std::slice::from_raw_parts_mut(self.nodes, self.len as usize) };
+        // SAFETY: tree properties are only ever read through `Descendants`
         let tree_properties =
             unsafe { std::slice::from_raw_parts(self.tree_properties, self.len as usize) };
 
@@ -169,6 +179,17 @@ impl<'a, T> Descendants<'a, T, Exclusive> {
         let (child_tree_properties, tree_properties) =
             tree_properties.split_at(child_tree_size as usize);
 
+        // The returned parts must not overlap, otherwise there would be aliasing `&mut`s
+        debug_assert!(std::ptr::eq(
+            child_nodes.as_ptr_range().end,
+            nodes.as_ptr_range().start
+        ));
+        debug_assert_eq!(
+            (child as *const T).wrapping_add(1),
+            child_nodes.as_ptr_range().start
+        );
+        debug_assert_eq!(nodes.len() + child_nodes.len() + 1, self.len as usize);
+
         Some((
             TreeRef {
                 parent: child,
@@ -179,6 +200,8 @@ impl<'a, T> Descendants<'a, T, Exclusive> {
         ))
     }
 
+    /// `self` is unusable while the returned descendants are alive, so they have exclusive
+    /// access to the nodes.
     #[must_use]
     #[inline]
     pub fn reborrow_mut(&mut self) -> Descendants<'_, T, Exclusive> {
@@ -299,12 +322,13 @@ macro_rules! iter_child_indices {
     };
     ($tree:expr => |mut $child:ident, $index:ident| $body:stmt) => {
         let mut tree = $tree.reborrow_mut();
-        let mut index = 0u32;
+        let mut offset = 0u32;
         while let Some((subtree, remaining)) = tree.split_first_mut() {
             tree = remaining;
+            let len = subtree.len();
             let $child = subtree.parent;
             let $index = offset;
-            offset += subtree.len();
+            offset += len;
             if subtree.is_root {
                 continue;
             }
@@ -328,6 +352,7 @@ impl<T, M: Mutability> Index<u32> for Descendants<'_, T, M> {
 
     #[inline]
     fn index(&self, index: u32) -> &Self::Output {
+        // SAFETY: the result borrows `self`, so the nodes can't be written while it's alive
         let nodes = unsafe { std::slice::from_raw_parts(self.nodes, self.len as usize) };
         &nodes[index as usize]
     }
@@ -336,6 +361,7 @@ impl<T, M: Mutability> Index<u32> for Descendants<'_, T, M> {
 impl<T> IndexMut<u32> for Descendants<'_, T, Exclusive> {
     #[inline]
     fn index_mut(&mut self, index: u32) -> &mut Self::Output {
+        // SAFETY: the result mutably borrows `self`, which has exclusive access to the nodes
         let nodes = unsafe { std::slice::from_raw_parts_mut(self.nodes, self.len as usize) };
         &mut nodes[index as usize]
     }
@@ -344,10 +370,15 @@ impl<T> IndexMut<u32> for Descendants<'_, T, Exclusive> {
 pub struct Forest<T> {
     nodes: MultiVec<(T, TreeProperties)>,
     root_indices: Vec<u32>,
+    /// For every primary tree, the position of its root in `root_indices`.
+    primary_roots: Vec<u32>,
 }
 
-unsafe impl<T> Send for Forest<T> {}
-unsafe impl<T> Sync for Forest<T> {}
+// SAFETY: the forest owns its nodes like a `Vec` does, `MultiVec` only lacks the impls because it
+// stores raw pointers
+unsafe impl<T: Send> Send for Forest<T> {}
+// SAFETY: see above
+unsafe impl<T: Sync> Sync for Forest<T> {}
 
 impl<T> Default for Forest<T> {
     #[inline]
@@ -355,6 +386,7 @@ impl<T> Default for Forest<T> {
         Self {
             nodes: MultiVec::new(),
             root_indices: Vec::new(),
+            primary_roots: Vec::new(),
         }
     }
 }
@@ -390,14 +422,12 @@ impl<T> Forest<T> {
 pub struct TreeIter<'a, T: 'a, M: Mutability> {
     forest: M::Ref<'a, Forest<T>>,
     tree_index: usize,
+    tree_end: usize,
 }
 
-impl<T> TreeIter<'_, T, Shared> {
-    pub fn next(&mut self) -> Option<TreeRef<'_, T, Shared>> {
-        let root_index = *self.forest.root_indices.get(self.tree_index)? as usize;
-        self.tree_index += 1;
-
-        let (nodes, tree_properties) = self.forest.nodes.as_slices();
+impl<T> Forest<T> {
+    fn tree_at(&self, root_index: usize) -> TreeRef<'_, T, Shared> {
+        let (nodes, tree_properties) = self.nodes.as_slices();
         let nodes = &nodes[root_index..];
         let tree_properties = &tree_properties[root_index..];
 
@@ -411,20 +441,15 @@ impl<T> TreeIter<'_, T, Shared> {
         let nodes = &nodes[..tree_size];
         let tree_properties = &tree_properties[..tree_size];
 
-        Some(TreeRef {
+        TreeRef {
             parent,
             descendants: Descendants::new(nodes, tree_properties),
             is_root: true,
-        })
+        }
     }
-}
 
-impl<T> TreeIter<'_, T, Exclusive> {
-    pub fn next(&mut self) -> Option<TreeRef<'_, T, Exclusive>> {
-        let root_index = *self.forest.root_indices.get(self.tree_index)? as usize;
-        self.tree_index += 1;
-
-        let (nodes, tree_properties) = self.forest.nodes.as_mut_slices();
+    fn tree_at_mut(&mut self, root_index: usize) -> TreeRef<'_, T, Exclusive> {
+        let (nodes, tree_properties) = self.nodes.as_mut_slices();
         let nodes = &mut nodes[root_index..];
         let tree_properties = &tree_properties[root_index..];
 
@@ -438,11 +463,37 @@ impl<T> TreeIter<'_, T, Exclusive> {
         let nodes = &mut nodes[..tree_size];
         let tree_properties = &tree_properties[..tree_size];
 
-        Some(TreeRef {
+        TreeRef {
             parent,
             descendants: Descendants::new_mut(nodes, tree_properties),
             is_root: true,
-        })
+        }
+    }
+}
+
+impl<T> TreeIter<'_, T, Shared> {
+    pub fn next(&mut self) -> Option<TreeRef<'_, T, Shared>> {
+        if self.tree_index >= self.tree_end {
+            return None;
+        }
+
+        let root_index = *self.forest.root_indices.get(self.tree_index)? as usize;
+        self.tree_index += 1;
+
+        Some(self.forest.tree_at(root_index))
+    }
+}
+
+impl<T> TreeIter<'_, T, Exclusive> {
+    pub fn next(&mut self) -> Option<TreeRef<'_, T, Exclusive>> {
+        if self.tree_index >= self.tree_end {
+            return None;
+        }
+
+        let root_index = *self.forest.root_indices.get(self.tree_index)? as usize;
+        self.tree_index += 1;
+
+        Some(self.forest.tree_at_mut(root_index))
     }
 }
 
@@ -452,6 +503,7 @@ impl<T> Forest<T> {
         TreeIter {
             forest: self,
             tree_index: 0,
+            tree_end: usize::MAX,
         }
     }
 
@@ -460,6 +512,91 @@ impl<T> Forest<T> {
         TreeIter {
             forest: self,
             tree_index: 0,
+            tree_end: usize::MAX,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn primary_count(&self) -> usize {
+        self.primary_roots.len()
+    }
+
+    /// Iterates the `primary_index`th primary tree and all trees nested inside it.
+    #[inline]
+    pub fn trees_of_primary(&self, primary_index: usize) -> TreeIter<'_, T, Shared> {
+        let tree_index = self.primary_roots[primary_index] as usize;
+        let tree_end = self
+            .primary_roots
+            .get(primary_index + 1)
+            .map_or(usize::MAX, |&end| end as usize);
+
+        TreeIter {
+            forest: self,
+            tree_index,
+            tree_end,
+        }
+    }
+
+    #[must_use]
+    pub fn nth_primary_mut(&mut self, primary_index: usize) -> Option<TreeRef<'_, T, Exclusive>> {
+        let tree_index = *self.primary_roots.get(primary_index)? as usize;
+        let root_index = self.root_indices[tree_index] as usize;
+        Some(self.tree_at_mut(root_index))
+    }
+}
+
+/// Iterates all nodes of a forest in depth-first order, together with their depth and whether
+/// they are the root of a tree.
+pub struct Preorder<'a, T> {
+    nodes: &'a [T],
+    tree_properties: &'a [TreeProperties],
+    index: usize,
+    subtree_ends: SmallVec<[usize; 16]>,
+}
+
+impl<'a, T> Iterator for Preorder<'a, T> {
+    type Item = (&'a T, u32, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.get(self.index)?;
+        let tree_properties = self.tree_properties[self.index];
+
+        while self
+            .subtree_ends
+            .last()
+            .is_some_and(|&end| end <= self.index)
+        {
+            self.subtree_ends.pop();
+        }
+
+        let depth = self.subtree_ends.len() as u32;
+        self.index += 1;
+        self.subtree_ends
+            .push(self.index + (tree_properties.size() as usize));
+
+        Some((node, depth, tree_properties.is_root()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.nodes.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Preorder<'_, T> {}
+
+impl<T> Forest<T> {
+    #[inline]
+    pub fn preorder(&self) -> Preorder<'_, T> {
+        let (nodes, tree_properties) = self.nodes.as_slices();
+
+        Preorder {
+            nodes,
+            tree_properties,
+            index: 0,
+            subtree_ends: SmallVec::new(),
         }
     }
 }
@@ -470,17 +607,109 @@ pub struct ForestBuilder<'a, T> {
 }
 
 impl<T> Forest<T> {
-    pub fn insert_primary(&mut self, root: T) -> ForestBuilder<'_, T> {
+    /// The total number of nodes in all trees.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Makes sure `node_count` nodes fit into the forest without reallocating.
+    pub fn reserve(&mut self, node_count: usize) {
+        self.nodes
+            .reserve(node_count.saturating_sub(self.nodes.len()));
+        self.root_indices
+            .reserve(node_count.saturating_sub(self.root_indices.len()));
+    }
+
+    /// Removes all trees while keeping the allocated capacity, so rebuilding a forest of similar
+    /// size doesn't allocate again.
+    pub fn clear(&mut self) {
         self.nodes.clear();
         self.root_indices.clear();
+        self.primary_roots.clear();
+    }
+
+    /// Removes every node that isn't the root of a tree and for which `elide` returns `true`.
+    /// The descendants of a removed node take its place in its parent, in the same order.
+    ///
+    /// `elide` sees the subtrees as they were before any node was removed. Returns the number
+    /// of removed nodes.
+    pub fn elide(&mut self, mut elide: impl FnMut(TreeRef<'_, T, Shared>) -> bool) -> usize {
+        let len = self.nodes.len();
+        // the kept ancestors of the current node, with their new index and their old end
+        let mut open_trees = SmallVec::<[(usize, usize); 32]>::new();
+        let mut root_index = 0;
+        let mut kept = 0;
+
+        for index in 0..len {
+            let (nodes, tree_properties) = self.nodes.as_mut_slices();
+
+            while let Some(&(tree_index, tree_end)) = open_trees.last()
+                && (tree_end <= index)
+            {
+                tree_properties[tree_index].set_size((kept - tree_index - 1) as u32);
+                open_trees.pop();
+            }
+
+            // Everything from `index` on is still in its original place, only the nodes before
+            // it have been moved.
+            let node_tree_properties = tree_properties[index];
+            let tree_size = node_tree_properties.size() as usize;
+            let is_root = node_tree_properties.is_root();
+            if !is_root {
+                let tree = TreeRef {
+                    parent: &nodes[index],
+                    descendants: Descendants::new(
+                        &nodes[(index + 1)..(index + 1 + tree_size)],
+                        &tree_properties[(index + 1)..(index + 1 + tree_size)],
+                    ),
+                    is_root,
+                };
+
+                if elide(tree) {
+                    continue;
+                }
+            }
 
+            // the nodes between `kept` and `index` have all been elided
+            nodes.swap(kept, index);
+            tree_properties.swap(kept, index);
+            if is_root {
+                self.root_indices[root_index] = kept as u32;
+                root_index += 1;
+            }
+
+            open_trees.push((kept, index + 1 + tree_size));
+            kept += 1;
+        }
+
+        let tree_properties = self.nodes.as_mut_slices().1;
+        for (tree_index, _) in open_trees {
+            tree_properties[tree_index].set_size((kept - tree_index - 1) as u32);
+        }
+
+        debug_assert_eq!(root_index, self.root_indices.len());
+        self.nodes.truncate(kept);
+        len - kept
+    }
+
+    pub fn insert_primary(&mut self, root: T) -> ForestBuilder<'_, T> {
+        self.clear();
+        self.push_primary(root)
+    }
+
+    /// Inserts another primary tree after all existing trees.
+    pub fn push_primary(&mut self, root: T) -> ForestBuilder<'_, T> {
+        let index = self.nodes.len();
         let tree_properties = TreeProperties::new().with_is_root(true);
         self.nodes.push((root, tree_properties));
-        self.root_indices.push(0);
+        self.primary_roots.push(self.root_indices.len() as u32);
+        self.root_indices.push(index as u32);
 
         ForestBuilder {
             forest: self,
-            parent_index: 0,
+            parent_index: index,
         }
     }
 }
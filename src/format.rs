@@ -0,0 +1,71 @@
+//! Formatting the numbers and dates shown by the built-in widgets for the user's locale.
+//!
+//! The crate doesn't know about locales itself. Apps install a [`ValueFormatter`] with
+//! [`ByorGui::set_formatter`], for example one backed by ICU, and every built-in widget that shows
+//! a value formats it through that formatter.
+//!
+//! [`ByorGui::set_formatter`]: crate::ByorGui::set_formatter
+
+use crate::widgets::{Date, Weekday};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Formats the values shown by built-in widgets, see
+/// [`ByorGui::set_formatter`](crate::ByorGui::set_formatter).
+///
+/// Every method defaults to the plain English format of [`EnglishFormatter`], so implementations
+/// only override what their locale formats differently.
+pub trait ValueFormatter: Send + 'static {
+    /// `value` rounded to `precision` fractional digits.
+    fn format_number(&self, value: f64, precision: usize) -> String {
+        format!("{value:.precision$}")
+    }
+
+    /// `fraction` as a percentage rounded to `precision` fractional digits, where `1.0` is 100 %.
+    fn format_percent(&self, fraction: f64, precision: usize) -> String {
+        format!("{}%", self.format_number(fraction * 100.0, precision))
+    }
+
+    /// A date in the format users type it in, which [`parse_date`](Self::parse_date) has to
+    /// accept. Defaults to `YYYY-MM-DD`.
+    fn format_date(&self, date: Date) -> String {
+        date.to_string()
+    }
+
+    /// Parses a date typed by the user, [`None`] if the text isn't a valid date.
+    fn parse_date(&self, text: &str) -> Option<Date> {
+        Date::from_iso(text)
+    }
+
+    /// A month of a year, like the title of a calendar.
+    fn format_month(&self, year: i32, month: u8) -> String {
+        format!(
+            "{} {year}",
+            MONTH_NAMES[usize::from(month.clamp(1, 12)) - 1]
+        )
+    }
+
+    /// The abbreviated name of a weekday, like above the columns of a calendar.
+    fn format_weekday(&self, weekday: Weekday) -> String {
+        weekday.short_name().to_owned()
+    }
+}
+
+/// The default formatter, using `.` as decimal separator, no digit grouping and ISO 8601 dates.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnglishFormatter;
+
+impl ValueFormatter for EnglishFormatter {}
@@ -0,0 +1,126 @@
+use crate::*;
+use std::borrow::Cow;
+
+const SOFT_HYPHEN: char = '\u{AD}';
+const SOFT_HYPHEN_LEN: usize = SOFT_HYPHEN.len_utf8();
+
+/// The text a layout is built from, with soft hyphens inserted between syllables if the style
+/// enables hyphenation for a supported locale.
+pub(crate) struct HyphenatedText<'a> {
+    pub(crate) text: Cow<'a, str>,
+    pub(crate) spans: Cow<'a, [TextSpan]>,
+    /// Byte indices into the original text a soft hyphen was inserted at, in ascending order.
+    insertions: Vec<usize>,
+}
+
+impl<'a> HyphenatedText<'a> {
+    #[must_use]
+    pub(crate) fn new(text: &'a str, spans: &'a [TextSpan], style: &ComputedStyle) -> Self {
+        let insertions = if style.hyphenation() {
+            soft_hyphen_positions(text, style.locale())
+        } else {
+            Vec::new()
+        };
+
+        if insertions.is_empty() {
+            return Self {
+                text: Cow::Borrowed(text),
+                spans: Cow::Borrowed(spans),
+                insertions,
+            };
+        }
+
+        let mut hyphenated = String::with_capacity(text.len() + insertions.len() * SOFT_HYPHEN_LEN);
+        let mut start = 0;
+        for &index in &insertions {
+            hyphenated.push_str(&text[start..index]);
+            hyphenated.push(SOFT_HYPHEN);
+            start = index;
+        }
+        hyphenated.push_str(&text[start..]);
+
+        let mut this = Self {
+            text: Cow::Owned(hyphenated),
+            spans: Cow::Borrowed(&[]),
+            insertions,
+        };
+        this.spans = spans
+            .iter()
+            .map(|span| {
+                let range = this.layout_index(span.range.start)..this.layout_index(span.range.end);
+                TextSpan {
+                    range,
+                    ..span.clone()
+                }
+            })
+            .collect();
+        this
+    }
+
+    /// Maps a byte index into the original text onto the hyphenated text.
+    #[must_use]
+    fn layout_index(&self, index: usize) -> usize {
+        let inserted_before = self
+            .insertions
+            .partition_point(|&insertion| insertion < index);
+        index + inserted_before * SOFT_HYPHEN_LEN
+    }
+
+    /// Maps a byte index into the hyphenated text back onto the original text.
+    #[cfg(feature = "markdown")]
+    #[must_use]
+    pub(crate) fn original_index(&self, index: usize) -> usize {
+        let inserted_before = self
+            .insertions
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &insertion)| insertion + (i + 1) * SOFT_HYPHEN_LEN <= index)
+            .count();
+        index - inserted_before * SOFT_HYPHEN_LEN
+    }
+}
+
+#[cfg(feature = "hyphenation")]
+#[must_use]
+fn soft_hyphen_positions(text: &str, locale: &str) -> Vec<usize> {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    let Ok(code) = <[u8; 2]>::try_from(language.to_ascii_lowercase().as_bytes()) else {
+        return Vec::new();
+    };
+    let Some(lang) = hypher::Lang::from_iso(code) else {
+        return Vec::new();
+    };
+
+    let mut positions = Vec::new();
+    let mut words = text.char_indices().peekable();
+    while let Some((start, c)) = words.next() {
+        if !c.is_alphabetic() {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        while let Some(&(index, c)) = words.peek().filter(|(_, c)| c.is_alphabetic()) {
+            end = index + c.len_utf8();
+            words.next();
+        }
+
+        let word = &text[start..end];
+        let mut offset = start;
+        let mut syllables = hypher::hyphenate(word, lang).peekable();
+        while let Some(syllable) = syllables.next() {
+            offset += syllable.len();
+            if syllables.peek().is_some() {
+                positions.push(offset);
+            }
+        }
+    }
+
+    positions
+}
+
+#[cfg(not(feature = "hyphenation"))]
+#[must_use]
+#[inline]
+fn soft_hyphen_positions(_text: &str, _locale: &str) -> Vec<usize> {
+    Vec::new()
+}
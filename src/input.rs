@@ -1,8 +1,20 @@
+use crate::style::axis::Axis;
 use crate::{Float, Pixel, Point, Vec2};
 use bitflags::bitflags;
 use smol_str::SmolStr;
+use std::time::{Duration, Instant};
 
+/// How far one line of a scroll wheel scrolls by default, see
+/// [`ByorGui::set_scroll_line_height`](crate::ByorGui::set_scroll_line_height).
 pub const POINTS_PER_SCROLL_LINE: Float<Point> = Float::new(40.0);
+/// How far one line of a scroll wheel zooms when it is used for zooming, see
+/// [`InputEvent::Zoom`].
+pub const ZOOM_PER_SCROLL_LINE: f32 = 0.1;
+
+/// The longest time between two clicks for them to count as a double click.
+pub const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+/// The farthest the cursor may move between two clicks for them to count as a double click.
+pub const MULTI_CLICK_DISTANCE: Float<Pixel> = Float::new(4.0);
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 struct ModifiersState {
@@ -67,6 +79,7 @@ impl ModifiersState {
 
 bitflags! {
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Modifiers: u8 {
         const CONTROL = 0x01;
         const SHIFT = 0x02;
@@ -108,6 +121,7 @@ impl From<ModifiersState> for Modifiers {
 
 /// Named keys as defined in https://w3c.github.io/uievents-key/
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum NamedKey {
     /// The <kbd>Alt</kbd> (Alternative) key.
@@ -770,6 +784,7 @@ pub enum NamedKey {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     Named(NamedKey),
     Character(SmolStr),
@@ -824,6 +839,7 @@ impl From<&str> for Key {
 
 bitflags! {
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct KeyLocations: u8 {
         const STANDARD = 0x01;
         const LEFT = 0x02;
@@ -833,6 +849,7 @@ bitflags! {
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyLocation {
     #[default]
     Standard,
@@ -854,6 +871,7 @@ impl From<KeyLocation> for KeyLocations {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shortcut {
     pub modifiers: Modifiers,
     pub key: Key,
@@ -925,24 +943,173 @@ impl KeyEvent {
     }
 }
 
+/// The platform conventions shortcuts are displayed with, see [`format_shortcut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    Mac,
+    Windows,
+    Linux,
+}
+
+impl Platform {
+    /// The platform the application is compiled for when using winit, otherwise there is no way
+    /// to know which conventions the host follows and the Linux ones are used.
+    pub const DEFAULT: Self = if cfg!(all(feature = "winit", target_os = "macos")) {
+        Self::Mac
+    } else if cfg!(all(feature = "winit", target_os = "windows")) {
+        Self::Windows
+    } else {
+        Self::Linux
+    };
+}
+
+impl Default for Platform {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Formats a shortcut the way the platform shows it in menus, e.g. "Ctrl+Shift+S" on Windows and
+/// Linux and "⇧⌘S" on macOS.
+#[must_use]
+pub fn format_shortcut(shortcut: &Shortcut, platform: Platform) -> SmolStr {
+    // modifiers are listed in the order the platforms show them in
+    let mut text = String::new();
+    match platform {
+        Platform::Mac => {
+            let modifiers = [
+                (Modifiers::CONTROL, "⌃"),
+                (Modifiers::ALT, "⌥"),
+                (Modifiers::ALT_GRAPH, "⌥"),
+                (Modifiers::SHIFT, "⇧"),
+                (Modifiers::META, "⌘"),
+            ];
+
+            for (modifier, symbol) in modifiers {
+                // Option is shown once even if both Alt keys are part of the shortcut
+                if shortcut.modifiers.contains(modifier) && !text.ends_with(symbol) {
+                    text.push_str(symbol);
+                }
+            }
+        }
+        Platform::Windows | Platform::Linux => {
+            let meta = if platform == Platform::Windows {
+                "Win"
+            } else {
+                "Super"
+            };
+            let modifiers = [
+                (Modifiers::CONTROL, "Ctrl"),
+                (Modifiers::META, meta),
+                (Modifiers::ALT, "Alt"),
+                (Modifiers::ALT_GRAPH, "AltGr"),
+                (Modifiers::SHIFT, "Shift"),
+            ];
+
+            for (modifier, name) in modifiers {
+                if shortcut.modifiers.contains(modifier) {
+                    text.push_str(name);
+                    text.push('+');
+                }
+            }
+        }
+    }
+
+    match &shortcut.key {
+        Key::Named(key) => text.push_str(&named_key_name(*key, platform)),
+        Key::Character(c) if c == " " => text.push_str("Space"),
+        Key::Character(c) => text.extend(c.chars().flat_map(char::to_uppercase)),
+        Key::Dead(Some(c)) => text.extend(c.to_uppercase()),
+        Key::Dead(None) | Key::Unknown(_) => text.push('?'),
+    }
+
+    text.into()
+}
+
+#[must_use]
+fn named_key_name(key: NamedKey, platform: Platform) -> SmolStr {
+    let mac = platform == Platform::Mac;
+    let name = match key {
+        NamedKey::ArrowLeft => "←",
+        NamedKey::ArrowUp => "↑",
+        NamedKey::ArrowRight => "→",
+        NamedKey::ArrowDown => "↓",
+        NamedKey::Enter if mac => "↩",
+        NamedKey::Enter => "Enter",
+        NamedKey::Tab if mac => "⇥",
+        NamedKey::Tab => "Tab",
+        NamedKey::Backspace if mac => "⌫",
+        NamedKey::Backspace => "Backspace",
+        NamedKey::Delete if mac => "⌦",
+        NamedKey::Delete => "Del",
+        NamedKey::Escape if mac => "⎋",
+        NamedKey::Escape => "Esc",
+        NamedKey::Home if mac => "↖",
+        NamedKey::Home => "Home",
+        NamedKey::End if mac => "↘",
+        NamedKey::End => "End",
+        NamedKey::PageUp if mac => "⇞",
+        NamedKey::PageUp => "PgUp",
+        NamedKey::PageDown if mac => "⇟",
+        NamedKey::PageDown => "PgDn",
+        NamedKey::Insert => "Ins",
+        // the names of the remaining keys, like function keys, are fine as they are
+        key => return smol_str::format_smolstr!("{key:?}"),
+    };
+
+    SmolStr::new_static(name)
+}
+
 bitflags! {
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
-    pub struct MouseButtons: u8 {
-        const PRIMARY = 0x01;
-        const SECONDARY = 0x02;
-        const MIDDLE = 0x04;
-        const BACK = 0x08;
-        const FORWARD = 0x10;
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct MouseButtons: u16 {
+        const PRIMARY = 0x0001;
+        const SECONDARY = 0x0002;
+        const MIDDLE = 0x0004;
+        const BACK = 0x0008;
+        const FORWARD = 0x0010;
+        const EXTRA1 = 0x0020;
+        const EXTRA2 = 0x0040;
+        const EXTRA3 = 0x0080;
+        const EXTRA4 = 0x0100;
+        const EXTRA5 = 0x0200;
+        const EXTRA6 = 0x0400;
+        const EXTRA7 = 0x0800;
+        const EXTRA8 = 0x1000;
     }
 }
 
+impl MouseButtons {
+    /// The flag of the extra button with the given index, see [`MouseButton::Other`].
+    #[must_use]
+    #[inline]
+    pub const fn extra(index: u8) -> Self {
+        if index < MAX_EXTRA_MOUSE_BUTTONS {
+            Self::from_bits_retain(Self::EXTRA1.bits() << index)
+        } else {
+            Self::empty()
+        }
+    }
+}
+
+/// How many extra mouse buttons are tracked, see [`MouseButton::Other`].
+pub const MAX_EXTRA_MOUSE_BUTTONS: u8 = 8;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     Primary,
     Secondary,
     Middle,
     Back,
     Forward,
+    /// An extra button, by index starting at 0.
+    ///
+    /// Only the first [`MAX_EXTRA_MOUSE_BUTTONS`] extra buttons have a flag in [`MouseButtons`],
+    /// presses of the others are ignored.
+    Other(u8),
 }
 
 impl From<MouseButton> for MouseButtons {
@@ -954,17 +1121,95 @@ impl From<MouseButton> for MouseButtons {
             MouseButton::Middle => MouseButtons::MIDDLE,
             MouseButton::Back => MouseButtons::BACK,
             MouseButton::Forward => MouseButtons::FORWARD,
+            MouseButton::Other(index) => MouseButtons::extra(index),
         }
     }
 }
 
+/// A pixel delta is reported by precise devices like touchpads, a point delta by scroll wheels
+/// that move in lines.
+///
+/// Point deltas are expected to be [`POINTS_PER_SCROLL_LINE`] per line, and are rescaled to the
+/// line height the GUI is configured with.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScrollDelta {
     Pixel(Vec2<Pixel>),
     Point(Vec2<Point>),
 }
 
+impl ScrollDelta {
+    /// Converts the vertical part of the delta into a zoom delta, for integrations that zoom with
+    /// the scroll wheel, see [`InputEvent::Zoom`].
+    #[must_use]
+    pub fn to_zoom_delta(self, scale_factor: f32) -> f32 {
+        let lines = match self {
+            Self::Pixel(delta) => delta.y.value() / scale_factor / POINTS_PER_SCROLL_LINE.value(),
+            Self::Point(delta) => delta.y / POINTS_PER_SCROLL_LINE,
+        };
+        lines * ZOOM_PER_SCROLL_LINE
+    }
+}
+
+/// How far scroll events move, see
+/// [`ByorGui::set_scroll_line_height`](crate::ByorGui::set_scroll_line_height) and
+/// [`ByorGui::set_scroll_speed_multiplier`](crate::ByorGui::set_scroll_speed_multiplier).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ScrollConfig {
+    pub(crate) line_height: Float<Point>,
+    pub(crate) speed_multiplier: f32,
+}
+
+impl ScrollConfig {
+    pub(crate) const DEFAULT: Self = Self {
+        line_height: POINTS_PER_SCROLL_LINE,
+        speed_multiplier: 1.0,
+    };
+
+    /// The distance of one scroll step, e.g. a click on a scroll bar's arrow.
+    #[must_use]
+    #[inline]
+    pub(crate) fn step(self) -> Float<Point> {
+        self.line_height * self.speed_multiplier
+    }
+
+    /// The factor point deltas are scaled by, since they are reported with the default line
+    /// height.
+    #[must_use]
+    #[inline]
+    fn line_scale(self) -> f32 {
+        (self.line_height / POINTS_PER_SCROLL_LINE) * self.speed_multiplier
+    }
+}
+
+/// A single scroll event received during the current frame, see [`InputState::scroll_events`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollEvent {
+    pub delta: Vec2<Pixel>,
+    /// The delta was reported in pixels by a precise device like a touchpad, instead of in lines
+    /// by a scroll wheel.
+    pub is_precise: bool,
+}
+
+/// The scroll deltas along one axis taken by [`InputState::consume_scroll`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ConsumedScroll {
+    /// The sum of the deltas reported by precise devices like touchpads.
+    pub precise: Float<Pixel>,
+    /// The sum of the deltas reported in lines by scroll wheels.
+    pub lines: Float<Pixel>,
+}
+
+impl ConsumedScroll {
+    #[must_use]
+    #[inline]
+    pub fn total(self) -> Float<Pixel> {
+        self.precise + self.lines
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InputEvent {
     KeyPressed {
         key: Key,
@@ -980,6 +1225,15 @@ pub enum InputEvent {
     CursorMoved {
         position: Vec2<Pixel>,
     },
+    /// The cursor entered the window at `position`.
+    CursorEntered {
+        position: Vec2<Pixel>,
+    },
+    /// The cursor left the window, so no node is hovered until it moves again.
+    ///
+    /// While a mouse button is pressed the cursor is considered captured, and keeps its last
+    /// position until the button is released.
+    CursorLeft,
     ButtonPressed {
         button: MouseButton,
     },
@@ -989,6 +1243,25 @@ pub enum InputEvent {
     Scrolled {
         delta: ScrollDelta,
     },
+    /// A zoom gesture, like pinching on a touchpad, centered around `center`.
+    ///
+    /// A positive delta zooms in, `0.1` by ten percent. Integrations may synthesize this from other
+    /// input, for example from the scroll wheel while <kbd>Ctrl</kbd> is held.
+    Zoom {
+        delta: f32,
+        center: Vec2<Pixel>,
+    },
+}
+
+/// An input event captured while recording, see
+/// [`ByorGui::input_recording`](crate::ByorGui::input_recording).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedInputEvent {
+    /// The time of the frame the event was received before, relative to the frame the recording
+    /// started in.
+    pub time: Duration,
+    pub event: InputEvent,
 }
 
 #[derive(Debug, Clone)]
@@ -1016,16 +1289,29 @@ pub struct InputState {
 
     prev_position: Option<Vec2<Pixel>>,
     position: Vec2<Pixel>,
+    cursor_left: bool,
 
     prev_pressed_buttons: MouseButtons,
     pressed_buttons: MouseButtons,
+    last_click: Option<(MouseButton, Instant, Vec2<Pixel>)>,
+    click_count: u32,
 
     scroll_delta: Vec2<Pixel>,
+    scroll_events: Vec<ScrollEvent>,
+
+    zoom_delta: f32,
+    zoom_center: Option<Vec2<Pixel>>,
 }
 
 impl InputState {
     #[inline]
-    pub(crate) fn on_event(&mut self, event: InputEvent, scale_factor: f32) {
+    pub(crate) fn on_event(
+        &mut self,
+        event: InputEvent,
+        time: Instant,
+        scale_factor: f32,
+        scroll: ScrollConfig,
+    ) {
         match event {
             InputEvent::KeyPressed {
                 key,
@@ -1079,22 +1365,74 @@ impl InputState {
                     modifiers: self.modifiers(),
                 });
             }
-            InputEvent::CursorMoved { position } => self.position = position,
-            InputEvent::ButtonPressed { button } => self.pressed_buttons.insert(button.into()),
+            InputEvent::CursorMoved { position } | InputEvent::CursorEntered { position } => {
+                self.position = position;
+                self.cursor_left = false;
+            }
+            InputEvent::CursorLeft => self.cursor_left = true,
+            InputEvent::ButtonPressed { button } => {
+                let flag = MouseButtons::from(button);
+                if !flag.is_empty() {
+                    self.pressed_buttons.insert(flag);
+                    self.count_click(button, time);
+                }
+            }
             InputEvent::ButtonReleased { button } => self.pressed_buttons.remove(button.into()),
-            InputEvent::Scrolled { delta } => match delta {
-                ScrollDelta::Pixel(delta) => self.scroll_delta += delta,
-                ScrollDelta::Point(delta) => self.scroll_delta += delta.to_pixel(scale_factor),
-            },
+            InputEvent::Scrolled { delta } => {
+                let event = match delta {
+                    ScrollDelta::Pixel(delta) => ScrollEvent {
+                        delta: delta * scroll.speed_multiplier,
+                        is_precise: true,
+                    },
+                    ScrollDelta::Point(delta) => ScrollEvent {
+                        delta: (delta * scroll.line_scale()).to_pixel(scale_factor),
+                        is_precise: false,
+                    },
+                };
+
+                // a delta that isn't finite would poison every offset it is added to
+                if !(event.delta.x.value().is_finite() && event.delta.y.value().is_finite()) {
+                    return;
+                }
+
+                self.scroll_delta += event.delta;
+                self.scroll_events.push(event);
+            }
+            InputEvent::Zoom { delta, center } => {
+                self.zoom_delta += delta;
+                self.zoom_center = Some(center);
+            }
         }
     }
 
+    fn count_click(&mut self, button: MouseButton, time: Instant) {
+        let is_repeated_click =
+            self.last_click
+                .is_some_and(|(last_button, last_time, last_position)| {
+                    let distance = self.position - last_position;
+                    (last_button == button)
+                        && (time.saturating_duration_since(last_time) <= MULTI_CLICK_INTERVAL)
+                        && (distance.x.abs() <= MULTI_CLICK_DISTANCE)
+                        && (distance.y.abs() <= MULTI_CLICK_DISTANCE)
+                });
+
+        self.click_count = if is_repeated_click {
+            self.click_count.saturating_add(1)
+        } else {
+            1
+        };
+        self.last_click = Some((button, time, self.position));
+    }
+
     #[inline]
     pub(crate) fn end_frame(&mut self) {
         self.key_events.clear();
         self.prev_position = Some(self.position);
         self.prev_pressed_buttons = self.pressed_buttons;
         self.scroll_delta = Vec2::ZERO;
+        self.scroll_events.clear();
+        self.zoom_delta = 0.0;
+        self.zoom_center = None;
     }
 
     #[must_use]
@@ -1140,9 +1478,19 @@ impl InputState {
         shortcut_pressed
     }
 
+    /// The position of the cursor, or `None` if it left the window and no mouse button is
+    /// pressed.
     #[must_use]
     #[inline]
-    pub fn cursor_position(&self) -> Vec2<Pixel> {
+    pub fn cursor_position(&self) -> Option<Vec2<Pixel>> {
+        let captured = !self.pressed_buttons.is_empty();
+        (!self.cursor_left || captured).then_some(self.position)
+    }
+
+    /// The position the cursor was last seen at, even if it left the window since.
+    #[must_use]
+    #[inline]
+    pub fn last_cursor_position(&self) -> Vec2<Pixel> {
         self.position
     }
 
@@ -1174,9 +1522,66 @@ impl InputState {
         self.prev_pressed_buttons & !self.pressed_buttons
     }
 
+    /// How many times in a row the most recently pressed mouse button was clicked, e.g. `2` for a
+    /// double click.
+    ///
+    /// Clicks are timed with the frame clock, the time of the frame built before the button was
+    /// pressed, so [`ByorGui::set_time`](crate::ByorGui::set_time) and replayed input count clicks
+    /// the same way every time.
+    #[must_use]
+    #[inline]
+    pub fn click_count(&self) -> u32 {
+        self.click_count
+    }
+
+    /// The sum of all scroll deltas received during the current frame that weren't
+    /// [consumed](Self::consume_scroll) yet.
     #[must_use]
     #[inline]
     pub fn scroll_delta(&self) -> Vec2<Pixel> {
         self.scroll_delta
     }
+
+    /// The individual scroll events received during the current frame, in the order they were
+    /// received.
+    #[must_use]
+    #[inline]
+    pub fn scroll_events(&self) -> &[ScrollEvent] {
+        &self.scroll_events
+    }
+
+    /// Removes the deltas along `axis` from the scroll events of the current frame and returns
+    /// them, so widgets further out don't scroll as well.
+    ///
+    /// Containers should consume scrolling after building their contents, that way the most
+    /// deeply nested hovered widget gets the wheel first and only the leftovers propagate outward.
+    pub fn consume_scroll(&mut self, axis: Axis) -> ConsumedScroll {
+        let mut consumed = ConsumedScroll::default();
+        for event in &mut self.scroll_events {
+            let delta = std::mem::take(event.delta.along_axis_mut(axis));
+            if event.is_precise {
+                consumed.precise += delta;
+            } else {
+                consumed.lines += delta;
+            }
+        }
+
+        self.scroll_events.retain(|event| event.delta != Vec2::ZERO);
+        *self.scroll_delta.along_axis_mut(axis) = Float::px(0.0);
+        consumed
+    }
+
+    /// The sum of all zoom deltas received during the current frame, see [`InputEvent::Zoom`].
+    #[must_use]
+    #[inline]
+    pub fn zoom_delta(&self) -> f32 {
+        self.zoom_delta
+    }
+
+    /// The center of the last zoom gesture received during the current frame, if any.
+    #[must_use]
+    #[inline]
+    pub fn zoom_center(&self) -> Option<Vec2<Pixel>> {
+        self.zoom_center
+    }
 }
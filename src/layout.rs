@@ -1,6 +1,7 @@
 use crate::style::axis::*;
+use crate::widgets::scroll::ScrollSnapState;
 use crate::*;
-use smallvec::SmallVec;
+use std::hash::{Hash, Hasher};
 
 #[must_use]
 fn scroll_along_axis(
@@ -17,20 +18,174 @@ fn scroll_along_axis(
         .copied()
 }
 
-fn wrap_text(node: &mut Node, text_layout: &mut TextLayout<Color>) {
-    use parley::AlignmentOptions as TextAlignmentOptions;
+#[must_use]
+pub(crate) fn cap_height(run: &parley::Run<'_, Color>) -> Option<f32> {
+    let font = run.font();
+    let font_ref = parley::swash::FontRef::from_index(font.data.as_ref(), font.index as usize)?;
+    let metrics = font_ref
+        .metrics(run.normalized_coords())
+        .scale(run.font_size());
+
+    (metrics.cap_height > 0.0).then_some(metrics.cap_height)
+}
 
-    let horizontal_padding = node.style.padding().left + node.style.padding().right;
-    let wrap_width = (node.style.fixed_size.x - horizontal_padding).value();
+/// Returns the top and the height of the box that text gets vertically centered by.
+#[must_use]
+fn text_anchor_box(
+    text_layout: &TextLayout<Color>,
+    anchor: VerticalTextAnchor,
+) -> (Float<Pixel>, Float<Pixel>) {
+    let layout_box = (0.px(), text_layout.height().px().ceil());
+
+    match anchor {
+        VerticalTextAnchor::LayoutBox => layout_box,
+        VerticalTextAnchor::CapHeight => {
+            let (Some(first_line), Some(last_line)) =
+                (text_layout.lines().next(), text_layout.lines().last())
+            else {
+                return layout_box;
+            };
 
-    text_layout.break_all_lines(node.style.text_wrap().then_some(wrap_width));
-    text_layout.align(
-        Some(wrap_width),
-        node.style.horizontal_text_alignment().into(),
-        TextAlignmentOptions {
-            align_when_overflowing: true,
-        },
-    );
+            let first_metrics = first_line.metrics();
+            let cap_height = first_line
+                .runs()
+                .next()
+                .and_then(|run| cap_height(&run))
+                .unwrap_or(first_metrics.ascent);
+
+            let top = first_metrics.baseline - cap_height;
+            let bottom = last_line.metrics().baseline;
+            (top.px(), (bottom - top).max(0.0).px())
+        }
+    }
+}
+
+/// Keeps a stored scroll offset within the content that is actually scrollable this frame.
+#[must_use]
+fn fit_scroll<Renderer: rendering::Renderer>(
+    data: &mut ByorGuiData<Renderer>,
+    uid: Option<Uid>,
+    axis: Axis,
+    content_size: Float<Pixel>,
+    available_size: Float<Pixel>,
+) -> Float<Pixel> {
+    let Some(uid) = uid else {
+        return 0.px();
+    };
+    let Some(persistent_state) = data.persistent_state.get_mut(uid) else {
+        return 0.px();
+    };
+    let key = axis.persistent_state_scroll_key();
+    let Some(&stored_scroll) = persistent_state.get::<Float<Pixel>>(key) else {
+        return 0.px();
+    };
+
+    let mut scroll = stored_scroll;
+    if persistent_state
+        .get::<bool>(PersistentStateKey::ProportionalScroll)
+        .copied()
+        .unwrap_or_default()
+        && let Some(previous_state) = data.previous_state.get(uid)
+    {
+        let previous_content_size = previous_state.content_size.along_axis(axis);
+        if (previous_content_size > 0.px()) && (previous_content_size != content_size) {
+            scroll = (scroll * (content_size / previous_content_size)).round();
+        }
+    }
+
+    let max_scroll = (content_size - available_size).max(0.px());
+    scroll = scroll.max(0.px()).min(max_scroll);
+
+    if let Some(snap_state) =
+        persistent_state.get::<ScrollSnapState>(PersistentStateKey::ScrollSnap)
+        && snap_state.at_rest
+        && (snap_state.axis == Some(axis))
+        && let Some(edge) = snap_state.nearest_edge(scroll)
+    {
+        scroll = edge;
+    }
+
+    if scroll != stored_scroll {
+        persistent_state.insert(key, scroll);
+    }
+
+    scroll
+}
+
+/// Everything line breaking and aligning a text layout depends on.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct TextWrap {
+    wrap_width: f32,
+    text_wrap: bool,
+    alignment: parley::Alignment,
+}
+
+impl TextWrap {
+    #[must_use]
+    fn new(style: &ComputedStyle, width: Float<Pixel>) -> Self {
+        let horizontal_padding = style.padding().left + style.padding().right;
+
+        Self {
+            wrap_width: (width - horizontal_padding).max(0.px()).value(),
+            text_wrap: style.text_wrap(),
+            alignment: style.horizontal_text_alignment().into(),
+        }
+    }
+
+    fn apply(self, text_layout: &mut TextLayout<Color>) {
+        use parley::AlignmentOptions as TextAlignmentOptions;
+
+        text_layout.break_all_lines(self.text_wrap.then_some(self.wrap_width));
+        text_layout.align(
+            Some(self.wrap_width),
+            self.alignment,
+            TextAlignmentOptions {
+                align_when_overflowing: true,
+            },
+        );
+    }
+}
+
+/// What layout computed from a text layout, which stays valid as long as the text layout isn't
+/// rebuilt.
+#[derive(Default, Clone, Copy)]
+pub(crate) struct TextLayoutState {
+    content_widths: Option<parley::ContentWidths>,
+    /// How the lines were last broken
+    wrap: Option<TextWrap>,
+}
+
+pub(crate) fn wrap_text_layout(
+    text_layout: &mut TextLayout<Color>,
+    style: &ComputedStyle,
+    width: Float<Pixel>,
+) {
+    TextWrap::new(style, width).apply(text_layout);
+}
+
+/// Whether the text doesn't fit into the content box of its node, so part of it is cut off.
+#[must_use]
+fn is_text_truncated(text_layout: &TextLayout<Color>, style: &ComputedStyle) -> bool {
+    let padding = style.padding();
+    let content_width = style.fixed_size.x - padding.left - padding.right;
+    let content_height = style.fixed_size.y - padding.top - padding.bottom;
+
+    (text_layout.width().px().ceil() > content_width)
+        || (text_layout.height().px().ceil() > content_height)
+}
+
+/// Breaks the lines of the text of `node` for its width, unless they already are.
+fn wrap_text<Renderer: rendering::Renderer>(
+    node: &Node,
+    text_layout_id: TextLayoutId,
+    data: &mut ByorGuiData<Renderer>,
+) {
+    let wrap = TextWrap::new(&node.style, node.style.fixed_size.x);
+    let state = &mut data.text_layout_states[text_layout_id];
+    if state.wrap != Some(wrap) {
+        wrap.apply(&mut data.text_layouts[text_layout_id]);
+        state.wrap = Some(wrap);
+    }
 }
 
 // must be bottom up recursive
@@ -63,8 +218,7 @@ fn compute_node_size<Renderer: rendering::Renderer>(
         if let Some(text_layout_id) = node.text_layout.expand()
             && (axis == Axis::Y)
         {
-            let text_layout = &mut data.text_layouts[text_layout_id];
-            wrap_text(node, text_layout);
+            wrap_text(node, text_layout_id, data);
         }
 
         return;
@@ -72,15 +226,17 @@ fn compute_node_size<Renderer: rendering::Renderer>(
 
     // text sizing
     if let Some(text_layout_id) = node.text_layout.expand() {
-        let text_layout = &mut data.text_layouts[text_layout_id];
         let padding: Float<Pixel> = node.style.padding().along_axis(axis).into_iter().sum();
 
         match axis {
             Axis::X => {
+                let state = &mut data.text_layout_states[text_layout_id];
                 let TextMeasurements {
                     min: min_width,
                     max: preferred_width,
-                } = text_layout.calculate_content_widths();
+                } = *state.content_widths.get_or_insert_with(|| {
+                    data.text_layouts[text_layout_id].calculate_content_widths()
+                });
 
                 let min_width = (min_width.px().ceil() + padding).clamp(min_size, max_size);
                 let width = (preferred_width.px().ceil() + padding).clamp(min_width, max_size);
@@ -93,8 +249,9 @@ fn compute_node_size<Renderer: rendering::Renderer>(
                 node.style.fixed_size.x = width;
             }
             Axis::Y => {
-                wrap_text(node, text_layout);
+                wrap_text(node, text_layout_id, data);
 
+                let text_layout = &data.text_layouts[text_layout_id];
                 let height = (text_layout.height().px().ceil() + padding).clamp(min_size, max_size);
                 node.style.min_size.y = height;
                 node.style.fixed_size.y = height;
@@ -175,6 +332,9 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
         ..
     } = tree;
 
+    // the size of the parent along the axis is final now
+    parent.style.resolve_percent_spacing(axis);
+
     let parent_size = parent.style.fixed_size.along_axis(axis);
     let parent_padding: Float<Pixel> = parent.style.padding().along_axis(axis).into_iter().sum();
 
@@ -182,16 +342,18 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
         let node_count = descendants.child_count();
         let total_spacing = (node_count.saturating_sub(1) as f32) * parent.style.child_spacing();
 
-        let mut total_target_size = parent_size - parent_padding - total_spacing;
+        let mut total_target_size = (parent_size - parent_padding - total_spacing).max(0.px());
         let mut available_space = total_target_size;
-        let mut nodes_to_resize = SmallVec::<[u32; 10]>::new();
+        // the buffer is shared by all nodes, so wide containers don't allocate every frame
+        let mut nodes_to_resize = std::mem::take(&mut data.nodes_to_resize);
         let mut flex_ratio_sum = 0.0;
         iter_child_indices!(descendants => |node, node_index| {
             available_space -= node.style.fixed_size.along_axis(axis);
 
             // filter out nodes that cannot be resized
             if node.style.min_size.along_axis(axis) == node.style.max_size.along_axis(axis) {
-                total_target_size -= node.style.fixed_size.along_axis(axis);
+                total_target_size =
+                    (total_target_size - node.style.fixed_size.along_axis(axis)).max(0.px());
             } else {
                 nodes_to_resize.push(node_index);
                 flex_ratio_sum += node.style.flex_ratio();
@@ -209,7 +371,7 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
 
             loop {
                 let mut collection_changed = false;
-                nodes_to_resize.retain(|&mut node_index| {
+                nodes_to_resize.retain(|&node_index| {
                     let node = &mut descendants[node_index];
 
                     let min_size = node.style.min_size.along_axis(axis);
@@ -227,7 +389,7 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
                         let new_size = target_size.clamp(min_size, max_size);
                         *node.style.fixed_size.along_axis_mut(axis) = new_size;
 
-                        total_target_size -= new_size;
+                        total_target_size = (total_target_size - new_size).max(0.px());
                         flex_ratio_sum -= flex_ratio;
                         collection_changed = true;
                         false
@@ -241,6 +403,10 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
                 }
             }
 
+            // Rounding every size on its own can leave the space a pixel short or over, so the
+            // edges between the nodes are rounded instead and the sizes derived from them.
+            let mut exact_end = 0.px();
+            let mut rounded_end = 0.px();
             for node_id in nodes_to_resize.drain(..) {
                 let node = &mut descendants[node_id];
 
@@ -250,12 +416,20 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
                 } else {
                     0.0
                 };
-                let target_size = (total_target_size * flex_factor).round();
+                exact_end += total_target_size * flex_factor;
+                let target_size = (exact_end.round() - rounded_end).clamp(
+                    node.style.min_size.along_axis(axis),
+                    node.style.max_size.along_axis(axis),
+                );
                 *node.style.fixed_size.along_axis_mut(axis) = target_size;
+                rounded_end += target_size;
             }
         }
+
+        nodes_to_resize.clear();
+        data.nodes_to_resize = nodes_to_resize;
     } else {
-        let available_space = parent_size - parent_padding;
+        let available_space = (parent_size - parent_padding).max(0.px());
         iter_children!(descendants => |mut node| {
             let node_min_size = node.style.min_size.along_axis(axis);
             let node_max_size = node.style.max_size.along_axis(axis);
@@ -269,9 +443,82 @@ fn grow_or_shrink_children<Renderer: rendering::Renderer>(
     });
 }
 
+/// Feeds everything hover resolution depends on into the layout damage hash.
+fn hash_hover_bounds(damage: &mut impl Hasher, node: &Node, descendant_count: u32) {
+    let (clip_position, clip_size) = node.clip_bounds();
+
+    node.uid.hash(damage);
+    for value in [
+        node.position.x,
+        node.position.y,
+        node.style.fixed_size.x,
+        node.style.fixed_size.y,
+        node.style.min_hit_size.x,
+        node.style.min_hit_size.y,
+        clip_position.x,
+        clip_position.y,
+        clip_size.x,
+        clip_size.y,
+    ] {
+        damage.write_u32(value.value().to_bits());
+    }
+    node.hit_target.hash(damage);
+    damage.write_u32(descendant_count);
+}
+
+/// Places a popup along one axis, falling back to the mirrored position if it would not fit
+/// into the viewport.
+#[must_use]
+fn popup_position(
+    parent: &Node,
+    node: &Node,
+    position: PopupPosition,
+    viewport_size: Vec2<Pixel>,
+    axis: Axis,
+) -> Float<Pixel> {
+    let parent_position = parent.position.along_axis(axis);
+    let parent_size = parent.style.fixed_size.along_axis(axis);
+    let size = node.style.fixed_size.along_axis(axis);
+    let place = |position| match position {
+        PopupPosition::BeforeParent => parent_position - size,
+        PopupPosition::ParentStart => parent_position,
+        PopupPosition::ParentEnd => parent_position + parent_size - size,
+        PopupPosition::AfterParent => parent_position + parent_size,
+    };
+    let fits = |offset: Float<Pixel>| {
+        (offset >= 0.px()) && (offset + size <= viewport_size.along_axis(axis))
+    };
+
+    let offset = place(position);
+    if fits(offset) {
+        return offset;
+    }
+
+    let mirrored = place(position.mirrored());
+    if fits(mirrored) { mirrored } else { offset }
+}
+
+#[must_use]
+fn clamp_to_viewport(
+    position: Vec2<Pixel>,
+    size: Vec2<Pixel>,
+    viewport_size: Vec2<Pixel>,
+) -> Vec2<Pixel> {
+    // start of the viewport wins if the node is larger than the viewport
+    let clamp =
+        |offset: Float<Pixel>, size, viewport_size| offset.min(viewport_size - size).max(0.px());
+
+    Vec2 {
+        x: clamp(position.x, size.x, viewport_size.x),
+        y: clamp(position.y, size.y, viewport_size.y),
+    }
+}
+
 fn position_children<Renderer: rendering::Renderer>(
     tree: TreeRef<'_, Node, Exclusive>,
     data: &mut ByorGuiData<Renderer>,
+    viewport_size: Vec2<Pixel>,
+    damage: &mut impl Hasher,
 ) {
     let TreeRef {
         parent,
@@ -284,12 +531,18 @@ fn position_children<Renderer: rendering::Renderer>(
 
         parent.vertical_text_offset = match parent.style.vertical_text_alignment() {
             VerticalTextAlignment::Top => 0.px(),
-            VerticalTextAlignment::Center => ((parent.style.fixed_size.y
-                - text_layout.height().px().ceil()
-                - parent.style.padding().top
-                - parent.style.padding().bottom)
-                / 2.0)
-                .round(),
+            VerticalTextAlignment::Center => {
+                let (anchor_top, anchor_height) =
+                    text_anchor_box(text_layout, parent.style.vertical_text_anchor());
+
+                ((parent.style.fixed_size.y
+                    - anchor_height
+                    - parent.style.padding().top
+                    - parent.style.padding().bottom)
+                    / 2.0
+                    - anchor_top)
+                    .round()
+            }
             VerticalTextAlignment::Bottom => {
                 parent.style.fixed_size.y
                     - text_layout.height().px().ceil()
@@ -301,26 +554,106 @@ fn position_children<Renderer: rendering::Renderer>(
 
     let primary_axis = parent.style.layout_direction().primary_axis();
     let cross_axis = parent.style.layout_direction().cross_axis();
-    let parent_persistent_state = parent.uid.and_then(|uid| data.persistent_state.get(uid));
 
     let parent_primary_position = parent.position.along_axis(primary_axis);
     let parent_cross_position = parent.position.along_axis(cross_axis);
     let parent_primary_size = parent.style.fixed_size.along_axis(primary_axis);
     let parent_cross_size = parent.style.fixed_size.along_axis(cross_axis);
-    let parent_primary_padding = parent.style.padding().along_axis(primary_axis);
-    let parent_cross_padding = parent.style.padding().along_axis(cross_axis);
-    let parent_primary_scroll =
-        scroll_along_axis(parent_persistent_state, primary_axis).unwrap_or_default();
-    let parent_cross_scroll =
-        scroll_along_axis(parent_persistent_state, cross_axis).unwrap_or_default();
+    // In right-to-left mode children are positioned as if the UI was left-to-right and then
+    // mirrored horizontally, so the horizontal padding has to be considered in logical order.
+    let rtl = parent.style.ui_direction() == UiDirection::Rtl;
+    let logical_padding = |axis: Axis| {
+        let [start, end] = parent.style.padding().along_axis(axis);
+        if rtl && (axis == Axis::X) {
+            [end, start]
+        } else {
+            [start, end]
+        }
+    };
+    let parent_primary_padding = logical_padding(primary_axis);
+    let parent_cross_padding = logical_padding(cross_axis);
 
     let mut total_primary_node_size = 0.px();
+    let mut max_cross_node_size = 0.px();
     iter_children!(descendants => |node| {
         total_primary_node_size += node.style.fixed_size.along_axis(primary_axis);
         total_primary_node_size += parent.style.child_spacing();
+        max_cross_node_size = max_cross_node_size.max(node.style.fixed_size.along_axis(cross_axis));
     });
     total_primary_node_size = (total_primary_node_size - parent.style.child_spacing()).max(0.px());
 
+    let parent_primary_available_size =
+        parent_primary_size - parent_primary_padding[0] - parent_primary_padding[1];
+
+    // snapping scroll views need this layout's edges, so offsets stored for children of a
+    // different size are snapped to where the children are now
+    if let Some(uid) = parent.uid
+        && let Some(snap_state) = data
+            .persistent_state
+            .get_mut(uid)
+            .and_then(|state| state.get_mut::<ScrollSnapState>(PersistentStateKey::ScrollSnap))
+        && (snap_state.axis == Some(primary_axis))
+    {
+        let max_scroll = (total_primary_node_size - parent_primary_available_size).max(0.px());
+        snap_state.edges.clear();
+        let mut edge = 0.px();
+        iter_children!(descendants => |node| {
+            snap_state.edges.push(edge.min(max_scroll));
+            edge += node.style.fixed_size.along_axis(primary_axis) + parent.style.child_spacing();
+        });
+        snap_state.edges.dedup();
+        if snap_state
+            .edges
+            .last()
+            .is_none_or(|&last| last < max_scroll)
+        {
+            snap_state.edges.push(max_scroll);
+        }
+    }
+
+    let parent_primary_scroll = fit_scroll(
+        data,
+        parent.uid,
+        primary_axis,
+        total_primary_node_size,
+        parent_primary_available_size,
+    );
+    let parent_cross_scroll = fit_scroll(
+        data,
+        parent.uid,
+        cross_axis,
+        max_cross_node_size,
+        parent_cross_size - parent_cross_padding[0] - parent_cross_padding[1],
+    );
+
+    if let Some(uid) = parent.uid {
+        let text_truncated = parent.text_layout.expand().is_some_and(|text_layout_id| {
+            is_text_truncated(&data.text_layouts[text_layout_id], &parent.style)
+        });
+
+        let state = data.previous_state.entry(uid).or_default();
+        // overflow tooltips were shown according to the previous layout, so they need another frame
+        let stale_tooltip = (state.text_truncated != text_truncated)
+            && (state.hover_state != HoverState::NotHovered);
+        state.text_truncated = text_truncated;
+        state.size = parent.style.fixed_size;
+        state.position = parent.position;
+        *state.content_size.along_axis_mut(primary_axis) = total_primary_node_size;
+        *state.content_size.along_axis_mut(cross_axis) = max_cross_node_size;
+        state.style = parent
+            .style_snapshot
+            .then(|| ComputedStyleSnapshot::new(&parent.style));
+
+        if stale_tooltip {
+            let frame_time = data.frame_time;
+            data.next_animation_frame = Some(
+                data.next_animation_frame
+                    .map_or(frame_time, |time| time.min(frame_time)),
+            );
+        }
+    }
+    hash_hover_bounds(damage, parent, descendants.len());
+
     let mut primary_offset = match parent.style.child_alignment() {
         Alignment::Start => 0.px(),
         Alignment::Center => {
@@ -343,31 +676,29 @@ fn position_children<Renderer: rendering::Renderer>(
             node.position = if let Some(uid) = node.uid
                 && let Some(&float_pos) = data.float_positions.get(uid)
             {
-                // FIXME: if the position has the node end up outside the window bounds, fall back to a different position
                 match float_pos {
-                    PersistentFloatPosition::Cursor { x, y, .. }
-                    | PersistentFloatPosition::CursorFixed { x, y, .. }
-                    | PersistentFloatPosition::Fixed { x, y, .. } => {
-                        Vec2 { x, y }
+                    PersistentFloatPosition::Cursor { x, y, pivot, .. }
+                    | PersistentFloatPosition::CursorFixed { x, y, pivot, .. }
+                    | PersistentFloatPosition::Fixed { x, y, pivot, .. } => {
+                        Vec2 { x, y } - pivot.offset(node.style.fixed_size)
                     }
                     PersistentFloatPosition::Popup { x, y, .. } => {
-                        Vec2 {
-                            x: match x {
-                                PopupPosition::BeforeParent => parent.position.x - node.style.fixed_size.x,
-                                PopupPosition::ParentStart => parent.position.x,
-                                PopupPosition::ParentEnd => parent.position.x + parent.style.fixed_size.x - node.style.fixed_size.x,
-                                PopupPosition::AfterParent => parent.position.x + parent.style.fixed_size.x,
-                            },
-                            y: match y {
-                                PopupPosition::BeforeParent => parent.position.y - node.style.fixed_size.y,
-                                PopupPosition::ParentStart => parent.position.y,
-                                PopupPosition::ParentEnd => parent.position.y + parent.style.fixed_size.y - node.style.fixed_size.y,
-                                PopupPosition::AfterParent => parent.position.y + parent.style.fixed_size.y,
-                            },
-                        }
+                        let x = match node.style.ui_direction() {
+                            UiDirection::Ltr => x,
+                            UiDirection::Rtl => x.mirrored(),
+                        };
+
+                        // Popups are kept inside the viewport using the sizes of this frame, so
+                        // they are in their final place on the frame they first appear in.
+                        let position = Vec2 {
+                            x: popup_position(parent, node, x, viewport_size, Axis::X),
+                            y: popup_position(parent, node, y, viewport_size, Axis::Y),
+                        };
+                        clamp_to_viewport(position, node.style.fixed_size, viewport_size)
                     }
                 }
             } else {
+                trace!(uid:? = node.uid; "floating node without a stored position");
                 Vec2::ZERO
             };
         } else {
@@ -391,20 +722,120 @@ fn position_children<Renderer: rendering::Renderer>(
                         - parent_cross_padding[1]
                 }
             } - parent_cross_scroll;
+
+            if rtl {
+                node.position.x = parent.position.x + parent.position.x + parent.style.fixed_size.x
+                    - node.position.x
+                    - node.style.fixed_size.x;
+            }
         }
 
-        position_children(subtree, data);
+        is_root.hash(damage);
+        position_children(subtree, data, viewport_size, damage);
     });
 }
 
+/// Whether `tree` is a wrapper that can be merged into its only child without changing what is
+/// laid out, hovered or drawn: it has no UID, contents or visible style of its own, and it
+/// constrains its size exactly like its child does. Since computed sizes are whole pixels, the
+/// child then always ends up with the bounds of the wrapper.
+///
+/// The wrapper also limits how far the hit rect of its child reaches, so it is kept if the child
+/// has a minimum hit size past its minimum size, or is one of the `sticky_uids` that can be hit
+/// outside of their bounds.
+#[must_use]
+fn is_pass_through(tree: TreeRef<'_, Node, Shared>, sticky_uids: &[Uid]) -> bool {
+    let TreeRef {
+        parent: node,
+        descendants,
+        ..
+    } = tree;
+
+    // floating descendants are placed relative to their parent, so they must stay attached
+    let Some((child, remaining)) = descendants.split_first() else {
+        return false;
+    };
+    if child.is_root || (remaining.len() > 0) {
+        return false;
+    }
+    let child = child.parent;
+
+    let hit_in_bounds = (child.style.min_hit_size.x <= child.style.min_size.x)
+        && (child.style.min_hit_size.y <= child.style.min_size.y)
+        && child.uid.is_none_or(|uid| !sticky_uids.contains(&uid));
+
+    let is_invisible = (node.style.drop_shadow_width() <= 0.px())
+        && (node.style.backdrop_blur() <= 0.px())
+        && (node.style.border_width() <= 0.px())
+        && matches!(node.style.background(), ComputedBrush::Solid(color) if color.a == 0);
+
+    node.uid.is_none()
+        && node.text_layout.is_none()
+        && node.renderer.is_none()
+        && node.tag.is_none()
+        && node.hit_target.is_none()
+        && !node.style_snapshot
+        && is_invisible
+        && hit_in_bounds
+        && (*node.style.padding() == ComputedPadding::ZERO)
+        && !node.style.has_percent_spacing()
+        // the wrapper clips the shadow of its child
+        && (child.style.drop_shadow_width() <= 0.px())
+        && (node.style.width() == child.style.width())
+        && (node.style.height() == child.style.height())
+        && (node.style.fixed_size == child.style.fixed_size)
+        && (node.style.min_size == child.style.min_size)
+        && (node.style.max_size == child.style.max_size)
+        && (node.style.flex_ratio() == child.style.flex_ratio())
+        && (node.style.cross_axis_alignment() == child.style.cross_axis_alignment())
+}
+
 impl<Renderer: rendering::Renderer> ByorGui<Renderer> {
+    #[must_use]
+    #[inline]
+    pub fn node_flattening(&self) -> bool {
+        self.data.node_flattening
+    }
+
+    /// If enabled, wrapper nodes that only pass their single child through are removed before
+    /// layout, which saves layout and rendering work for deeply nested widgets.
+    ///
+    /// Wrappers are only removed if that doesn't change the outcome, apart from the
+    /// [`node_count`](Self::node_count) and the [`depth`](NodeInfo::depth) of the nodes
+    /// inside them.
+    #[inline]
+    pub fn set_node_flattening(&mut self, node_flattening: bool) {
+        self.data.node_flattening = node_flattening;
+    }
+
     pub(crate) fn layout(&mut self) {
-        if let Some(mut tree) = self.forest.primary_mut() {
+        if self.data.node_flattening {
+            // the nodes hovered last are sticky when hover is resolved after this layout
+            let sticky_uids: &[Uid] = if self.data.hover_hysteresis > 0.px() {
+                &self.data.hovered_path
+            } else {
+                &[]
+            };
+            self.forest.elide(|tree| is_pass_through(tree, sticky_uids));
+        }
+
+        let mut damage = rapidhash::fast::RapidHasher::default();
+        for primary_index in 0..self.forest.primary_count() {
+            self.data.viewport_ids[primary_index].hash(&mut damage);
+
+            let mut tree = self.forest.nth_primary_mut(primary_index).unwrap();
             compute_node_size(tree.reborrow_mut(), &mut self.data, Axis::X);
             grow_or_shrink_children(tree.reborrow_mut(), &mut self.data, Axis::X);
             compute_node_size(tree.reborrow_mut(), &mut self.data, Axis::Y);
             grow_or_shrink_children(tree.reborrow_mut(), &mut self.data, Axis::Y);
-            position_children(tree.reborrow_mut(), &mut self.data);
+            let viewport_size = tree.parent.style.fixed_size;
+            position_children(
+                tree.reborrow_mut(),
+                &mut self.data,
+                viewport_size,
+                &mut damage,
+            );
         }
+        self.data.layout_damage = damage.finish();
     }
 }
@@ -1,18 +1,35 @@
+/// Forwards to [`log::trace!`] if the `log` feature is enabled.
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        ::log::trace!(target: "byor_gui", $($arg)+);
+    };
+}
+
+pub mod animation;
+pub mod deferred;
+#[allow(dead_code)] // general purpose container, not every function is used (yet)
 mod forest;
+pub mod format;
+mod hyphenation;
 pub mod input;
 mod layout;
 mod math;
+#[allow(dead_code)] // general purpose container, not every function is used (yet)
 mod multi_vec;
+pub mod persistence;
 pub mod rendering;
 pub mod style;
+pub mod testing;
 #[cfg(test)]
 mod tests;
 pub mod theme;
 pub mod widgets;
 
-use cranelift_entity::PrimaryMap;
 use cranelift_entity::packed_option::PackedOption;
+use cranelift_entity::{PrimaryMap, SecondaryMap};
 use forest::*;
+use hyphenation::HyphenatedText;
 use input::*;
 use intmap::{IntKey, IntMap};
 use parley::layout::Layout as TextLayout;
@@ -22,6 +39,8 @@ use std::any::Any;
 use std::fmt;
 use std::hash::Hasher;
 use std::num::NonZeroU64;
+use std::ops::Range;
+use std::time::{Duration, Instant};
 use style::computed::*;
 use style::*;
 use theme::Theme;
@@ -32,6 +51,13 @@ pub use smol_str;
 
 type SmallBox<T, const INLINE_SIZE: usize> = smallbox::SmallBox<T, [usize; INLINE_SIZE]>;
 
+/// The built-in renderers need at most 2 `usize`s besides their icon, but custom renderers often
+/// hold a few colors and a transform, which needs more than 8. Only nodes with a renderer store
+/// one, so the space is cheap compared to an allocation for each of them in every frame.
+const RENDERER_INLINE_SIZE: usize = 16;
+/// Most values are offsets and flags, but forms store a `Vec` of label UIDs every frame.
+const PERSISTENT_STATE_INLINE_SIZE: usize = 4;
+
 #[derive(Default)]
 struct ParleyGlobalData {
     layout_context: parley::LayoutContext<Color>,
@@ -55,7 +81,7 @@ mod global_cache {
 
     pub(crate) fn with_parley_global_data<R>(f: impl FnOnce(&mut ParleyGlobalData) -> R) -> R {
         let mut lock = PARLEY_GLOBAL_DATA.lock().unwrap();
-        f(&mut *lock)
+        f(&mut lock)
     }
 }
 
@@ -93,10 +119,30 @@ fn point_in_rect<U: Unit>(point: Vec2<U>, position: Vec2<U>, size: Vec2<U>) -> b
         && (point.y <= position.y + size.y)
 }
 
+/// Identifies a node across frames.
+///
+/// UIDs are 64 bit hashes, so two different inputs collide with a chance of about `n² / 2⁶⁵` for
+/// `n` UIDs in use. Collisions among the nodes of one frame are reported as
+/// [`DuplicateUidError`]s instead of silently sharing state. The UIDs of nodes are relative to
+/// their parent, so the same UID can be reused in different parts of the tree.
+///
+/// [`from_array`](Self::from_array), [`from_slice`](Self::from_slice), [`concat`](Self::concat)
+/// and the [`uid!`] macro are usable in const contexts, so widget IDs can be computed at compile
+/// time, e.g. `const SEARCH: Uid = uid!("sidebar").concat(uid!("search"));`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Uid(NonZeroU64);
 
+/// Creates a [`Uid`] from a string at compile time.
+///
+/// `uid!("name")` is the same UID as `Uid::from_slice(b"name")`.
+#[macro_export]
+macro_rules! uid {
+    ($name:expr) => {
+        const { $crate::Uid::from_slice(<str>::as_bytes($name)) }
+    };
+}
+
 #[must_use]
 #[inline]
 const fn uid_hash(seed: u64, data: &[u8]) -> u64 {
@@ -161,6 +207,30 @@ impl Uid {
         Self(hash_fallback(hasher.finish()))
     }
 
+    /// A UID derived from a type, for widgets that only exist once within their parent.
+    ///
+    /// The value is only stable for the same build of the program.
+    #[must_use]
+    pub fn from_type<T: ?Sized + 'static>() -> Self {
+        Self::new(std::any::TypeId::of::<T>())
+    }
+
+    /// A UID derived from the source location of the caller, which is also what widgets without
+    /// an explicit UID use.
+    ///
+    /// This is convenient for prototyping, but the UID changes whenever the code around the call
+    /// moves, so state tied to it doesn't survive edits. All calls at the same location get the
+    /// same UID, so widgets shown in a loop or by a shared helper function need to be told apart
+    /// with [`uid_scope`](ByorGuiContext::uid_scope) or an explicit UID.
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn from_location() -> Self {
+        Self::new(std::panic::Location::caller())
+    }
+
+    /// Combines two UIDs into a new one. The order matters, `a.concat(b)` and `b.concat(a)` are
+    /// different UIDs.
     #[must_use]
     pub const fn concat(self, other: Self) -> Self {
         let low_bytes = self.0.get().to_ne_bytes();
@@ -212,14 +282,21 @@ macro_rules! define_id_type {
 
 define_id_type!(TextLayoutId);
 define_id_type!(NodeRendererId);
+define_id_type!(NodeTagId);
 
 struct Node {
     uid: Option<Uid>,
     text_layout: PackedOption<TextLayoutId>,
     renderer: PackedOption<NodeRendererId>,
+    tag: PackedOption<NodeTagId>,
     style: ComputedStyle,
     position: Vec2<Pixel>,
     vertical_text_offset: Float<Pixel>,
+    style_snapshot: bool,
+    /// Whether the node is drawn by the retained renderer stored for its UID
+    retained_renderer: bool,
+    /// `None` for nodes that don't handle mouse buttons themselves
+    hit_target: Option<HitTarget>,
 }
 
 impl Node {
@@ -230,9 +307,13 @@ impl Node {
             uid: None,
             text_layout: PackedOption::default(),
             renderer: PackedOption::default(),
+            tag: PackedOption::default(),
             style,
             position: Vec2::default(),
             vertical_text_offset: 0.px(),
+            style_snapshot: false,
+            retained_renderer: false,
+            hit_target: None,
         }
     }
 
@@ -242,15 +323,20 @@ impl Node {
         uid: Option<Uid>,
         text_layout: Option<TextLayoutId>,
         renderer: Option<NodeRendererId>,
+        tag: Option<NodeTagId>,
         style: ComputedStyle,
     ) -> Self {
         Self {
             uid,
             text_layout: text_layout.into(),
             renderer: renderer.into(),
+            tag: tag.into(),
             style,
             position: Vec2::default(),
             vertical_text_offset: 0.px(),
+            style_snapshot: false,
+            retained_renderer: false,
+            hit_target: None,
         }
     }
 
@@ -263,25 +349,51 @@ impl Node {
         let clip_size = Vec2 {
             x: self.style.fixed_size.x - self.style.padding().left - self.style.padding().right,
             y: self.style.fixed_size.y - self.style.padding().top - self.style.padding().bottom,
-        };
+        }
+        .max(Vec2::ZERO);
 
         (clip_position, clip_size)
     }
+
+    /// How far the hit rect extends past the node on each side, to reach the minimum hit size.
+    fn hit_margin(&self) -> Vec2<Pixel> {
+        (self.style.min_hit_size - self.style.fixed_size).max(Vec2::ZERO) / 2.0
+    }
+
+    #[must_use]
+    fn center(&self) -> Vec2<Pixel> {
+        self.position + self.style.fixed_size / 2.0
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PersistentStateKey {
     HorizontalScroll,
     VerticalScroll,
+    ProportionalScroll,
+    ScrollNearEnd,
     ScrollBarThumbMouseOffset,
+    RangeSliderDrag,
+    ScrollBarShown,
+    ScrollSnap,
     PreviousPopupState,
+    PopupOpen,
     TextBoxEditor,
+    DatePickerText,
+    DatePickerCursor,
+    ReorderDrag,
+    Animation,
+    FormLabels,
+    #[cfg(feature = "markdown")]
+    MarkdownDocument,
 
     Custom(&'static str),
 }
 
-type PersistentStateStorage =
-    rapidhash::RapidHashMap<PersistentStateKey, SmallBox<dyn Any + Send, 2>>;
+type PersistentStateStorage = rapidhash::RapidHashMap<
+    PersistentStateKey,
+    SmallBox<dyn Any + Send, PERSISTENT_STATE_INLINE_SIZE>,
+>;
 
 #[derive(Default)]
 enum PersistentStateRepr {
@@ -323,7 +435,7 @@ impl PersistentState {
 
     #[must_use]
     #[inline]
-    fn storage_mut<'a>(&'a mut self) -> &'a mut PersistentStateStorage {
+    fn storage_mut(&mut self) -> &mut PersistentStateStorage {
         if let PersistentStateRepr::Empty = self.0 {
             self.0 = PersistentStateRepr::Populated {
                 storage: PersistentStateStorage::default(),
@@ -365,6 +477,30 @@ impl PersistentState {
     pub fn insert<T: Any + Send>(&mut self, key: PersistentStateKey, value: T) {
         self.storage_mut().insert(key, smallbox!(value));
     }
+
+    /// The number of values that don't fit into their inline storage.
+    #[cfg(feature = "profiling")]
+    #[must_use]
+    fn spilled_values(&self) -> usize {
+        let PersistentStateRepr::Populated { storage } = &self.0 else {
+            return 0;
+        };
+
+        storage.values().filter(|value| value.is_heap()).count()
+    }
+
+    /// Removes the value stored for `key` if it isn't a `T`, and returns whether it was removed.
+    pub(crate) fn discard_mismatched<T: Any>(&mut self, key: PersistentStateKey) -> bool {
+        let PersistentStateRepr::Populated { storage } = &mut self.0 else {
+            return false;
+        };
+
+        let mismatched = storage.get(&key).is_some_and(|any| !any.is::<T>());
+        if mismatched {
+            storage.remove(&key);
+        }
+        mismatched
+    }
 }
 
 assert_impl_all!(PersistentState: Send);
@@ -381,65 +517,588 @@ pub enum HoverState {
     DirectlyHovered,
 }
 
+/// How the focused node got the focus, see [`ByorGuiContext::focus_source`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FocusSource {
+    /// The node was pressed with a mouse button
+    #[default]
+    Pointer,
+    /// The node was reached with <kbd>Tab</kbd> or <kbd>Shift</kbd>+<kbd>Tab</kbd>
+    Keyboard,
+    /// The node was focused before a focus trap opened and got the focus back when it closed
+    Restored,
+}
+
+/// What <kbd>Esc</kbd> can close, see [`ByorGuiContext::register_escapable`].
+///
+/// Every press of <kbd>Esc</kbd> that no widget handled itself does one thing, the first that
+/// applies of: cancelling a mouse drag, closing the innermost open popup, closing the topmost
+/// modal if it is dismissable, and clearing the keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Escapable {
+    /// Closed before any modal, innermost first
+    Popup,
+    /// Closed if it is the topmost modal, unless it isn't `dismissable`, in which case
+    /// <kbd>Esc</kbd> leaves the modals open
+    Modal { dismissable: bool },
+}
+
+/// Which mouse button presses a node handles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HitTarget {
+    /// The node handles presses that land on itself but not on any of its children
+    #[default]
+    SelfOnly,
+    /// The node also handles presses that land on any of its descendants that don't handle them
+    /// themselves
+    Subtree,
+}
+
 #[derive(Default)]
 pub struct PreviousState {
     /// Keeps track of whether this state still needs to be stored
     referenced: bool,
+    /// Whether this node is the one that gets the mouse buttons
+    hit: bool,
 
     pub hover_state: HoverState,
     pub size: Vec2<Pixel>,
     pub content_size: Vec2<Pixel>,
     pub position: Vec2<Pixel>,
+    /// Whether the text of the node didn't fit into its content box.
+    pub text_truncated: bool,
+    /// Only recorded for nodes inserted with [`NodeContents::with_style_snapshot`].
+    pub style: Option<ComputedStyleSnapshot>,
 }
 
 assert_impl_all!(PreviousState: Send);
 
-type NodeRendererStorage<Renderer> = SmallBox<dyn rendering::NodeRenderer<Renderer = Renderer>, 8>;
+/// Read-only information about a node of the last completed frame.
+#[derive(Clone, Copy)]
+pub struct NodeInfo<'gui> {
+    node: &'gui Node,
+    text: Option<&'gui str>,
+    tag: Option<&'gui (dyn Any + Send)>,
+    depth: u32,
+    is_floating_root: bool,
+}
+
+impl<'gui> NodeInfo<'gui> {
+    #[must_use]
+    #[inline]
+    pub fn uid(&self) -> Option<Uid> {
+        self.node.uid
+    }
+
+    /// The number of ancestors of this node, `0` for the root node.
+    #[must_use]
+    #[inline]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_floating_root(&self) -> bool {
+        self.is_floating_root
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn position(&self) -> Vec2<Pixel> {
+        self.node.position
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn size(&self) -> Vec2<Pixel> {
+        self.node.style.fixed_size
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn text(&self) -> Option<&'gui str> {
+        self.text
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn style(&self) -> &'gui ComputedStyle {
+        &self.node.style
+    }
+
+    /// The tag the node was given with [`NodeContents::with_tag`], if it is of type `T`.
+    #[must_use]
+    #[inline]
+    pub fn tag<T: Any>(&self) -> Option<&'gui T> {
+        self.tag?.downcast_ref()
+    }
+}
+
+impl fmt::Debug for NodeInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeInfo")
+            .field("uid", &self.uid())
+            .field("depth", &self.depth)
+            .field("is_floating_root", &self.is_floating_root)
+            .field("position", &self.position())
+            .field("size", &self.size())
+            .field("text", &self.text)
+            .finish_non_exhaustive()
+    }
+}
+
+const DEFAULT_CARET_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+/// How many diagnostics are kept until they are taken, see [`ByorGui::take_diagnostics`].
+pub const MAX_PENDING_DIAGNOSTICS: usize = 256;
+
+/// See [`ByorGui::set_hover_hysteresis`].
+pub const DEFAULT_HOVER_HYSTERESIS: Float<Pixel> = Float::px(1.5);
+
+type NodeRendererStorage<Renderer> =
+    SmallBox<dyn rendering::NodeRenderer<Renderer = Renderer>, RENDERER_INLINE_SIZE>;
+type RetainedRendererStorage<Renderer> =
+    SmallBox<dyn rendering::ErasedRetainedRenderer<Renderer>, RENDERER_INLINE_SIZE>;
+type NodeTagStorage = SmallBox<dyn Any + Send, 8>;
+
+/// A text layout kept across frames for [`NodeContents::text_cached`].
+struct CachedTextLayout {
+    revision: u64,
+    style_hash: u64,
+    /// Lent to `text_layouts` while a frame shows it
+    layout: TextLayout<Color>,
+    state: layout::TextLayoutState,
+    /// Whether the current frame shows the layout
+    referenced: bool,
+}
 
 struct ByorGuiData<Renderer: rendering::Renderer> {
     text_layouts: PrimaryMap<TextLayoutId, TextLayout<Color>>,
+    /// The layouts of previous frames that aren't cached, rebuilt for new texts so they don't
+    /// allocate again
+    spare_text_layouts: Vec<TextLayout<Color>>,
+    text_backgrounds: SecondaryMap<TextLayoutId, Vec<(Range<usize>, Color)>>,
+    text_contents: String,
+    text_ranges: SecondaryMap<TextLayoutId, Range<usize>>,
+    text_layout_states: SecondaryMap<TextLayoutId, layout::TextLayoutState>,
+    text_layout_cache: IntMap<Uid, CachedTextLayout>,
+    /// Scratch space of the layout
+    nodes_to_resize: Vec<u32>,
+    /// The layouts of this frame that are lent from `text_layout_cache`
+    cached_text_layouts: Vec<(TextLayoutId, Uid)>,
     renderers: PrimaryMap<NodeRendererId, NodeRendererStorage<Renderer>>,
+    retained_renderers: IntMap<Uid, rendering::RetainedRendererEntry<Renderer>>,
+    tags: PrimaryMap<NodeTagId, NodeTagStorage>,
     persistent_state: IntMap<Uid, PersistentState>,
     previous_state: IntMap<Uid, PreviousState>,
+    /// The UIDs whose previous state was created during the current frame
+    new_previous_states: Vec<Uid>,
     float_positions: IntMap<Uid, PersistentFloatPosition>,
     uid_stack: Vec<Uid>,
 
     theme: Theme,
+    shared_computed_values: SharedComputedValues,
+    formatter: Box<dyn format::ValueFormatter>,
     scale_factor: f32,
+    screen_size: Vec2<Pixel>,
+    scroll_config: ScrollConfig,
     input_state: InputState,
+    /// The frame time the input recording started at, if input is being recorded.
+    input_recording_start: Option<Instant>,
+    recorded_input: Vec<RecordedInputEvent>,
     hovered_node_override: Option<Uid>,
+    /// Set when Escape cancelled a drag, until all mouse buttons are released
+    pointer_capture_cancelled: bool,
     focused_node: Option<Uid>,
+    /// The node that was focused while the previous frame was built
+    previous_focused_node: Option<Uid>,
+    focus_source: FocusSource,
+    /// Set once the focused node is inserted, scroll views reset it around their contents to
+    /// find out whether the focus is inside of them
+    focused_node_inserted: bool,
+    /// Set by [`ByorGuiContext::request_drag_autoscroll`], scroll views reset it around their
+    /// contents like `focused_node_inserted`
+    drag_autoscroll: bool,
+    diagnostics: Vec<Diagnostic>,
+    /// The diagnostics reported in the current frame, so a widget that is shown many times only
+    /// reports the same problem once
+    frame_diagnostics: IntMap<Uid, ()>,
+    error_policy: ErrorPolicy,
+    frame_errors: Vec<DuplicateUidError>,
+    caret_blink_interval: Option<Duration>,
+    frame_time: Instant,
+    delta_time: Duration,
+    next_frame_time: Option<Instant>,
+    next_animation_frame: Option<Instant>,
+    viewport_ids: Vec<ViewportId>,
+    input_viewport: Option<ViewportId>,
+    lenient_rendering: bool,
+    node_flattening: bool,
+    keyed_uids: IntMap<Uid, ()>,
+    /// The namespaces of the stable scopes of the current frame
+    stable_scopes: IntMap<Uid, ()>,
+    /// The namespaces of the stable scopes around the node being built, innermost last
+    stable_scope_stack: Vec<Uid>,
+    /// The classes of the widgets of the current frame, only recorded in debug builds for the
+    /// dumps of [`ByorGui::expect_node`]. Nodes that aren't shown as a widget have no classes.
+    widget_classes: IntMap<Uid, Vec<theme::StyleClass>>,
+    layout_damage: u64,
+    hovered_path: Vec<Uid>,
+    /// The hovered nodes of the last hover resolution, while the next one runs
+    previous_hovered_path: Vec<Uid>,
+    hover_hysteresis: Float<Pixel>,
+    last_hover: Option<(HoverInputs, Option<Uid>)>,
+    /// The focusable nodes of the current frame, in insertion order
+    tab_order: Vec<TabStop>,
+    /// The focus traps around the node being built, innermost last
+    focus_trap_stack: Vec<Uid>,
+    /// The open focus traps in the order they were opened
+    focus_traps: Vec<FocusTrap>,
+    /// The popups and modals open during the current frame, in the order they were shown
+    escape_layers: Vec<EscapeLayer>,
+    /// The popups and modals that were open during the previous frame, which Escape applies to
+    previous_escape_layers: Vec<EscapeLayer>,
+    /// What the Escape press of the current frame did, once it was resolved
+    escape_target: Option<EscapeTarget>,
+    form_stack: Vec<widgets::form::FormScope>,
+    debug_overlay: rendering::DebugOverlay,
+    debug_shortcut: Option<Shortcut>,
 }
 
 impl<Renderer: rendering::Renderer> Default for ByorGuiData<Renderer> {
     fn default() -> Self {
         Self {
             text_layouts: PrimaryMap::new(),
+            spare_text_layouts: Vec::new(),
+            text_backgrounds: SecondaryMap::new(),
+            text_contents: String::new(),
+            text_ranges: SecondaryMap::new(),
+            text_layout_states: SecondaryMap::new(),
+            text_layout_cache: IntMap::default(),
+            nodes_to_resize: Vec::new(),
+            cached_text_layouts: Vec::new(),
             renderers: PrimaryMap::new(),
+            retained_renderers: IntMap::new(),
+            tags: PrimaryMap::new(),
             persistent_state: IntMap::new(),
             previous_state: IntMap::new(),
+            new_previous_states: Vec::new(),
             float_positions: IntMap::new(),
             uid_stack: Vec::new(),
 
             theme: Theme::default(),
+            shared_computed_values: SharedComputedValues::default(),
+            formatter: Box::new(format::EnglishFormatter),
             scale_factor: 1.0,
+            screen_size: Vec2::ZERO,
+            scroll_config: ScrollConfig::DEFAULT,
             input_state: InputState::default(),
+            input_recording_start: None,
+            recorded_input: Vec::new(),
             hovered_node_override: None,
+            pointer_capture_cancelled: false,
             focused_node: None,
+            previous_focused_node: None,
+            focus_source: FocusSource::default(),
+            focused_node_inserted: false,
+            drag_autoscroll: false,
+            diagnostics: Vec::new(),
+            frame_diagnostics: IntMap::new(),
+            error_policy: ErrorPolicy::default(),
+            frame_errors: Vec::new(),
+            caret_blink_interval: Some(DEFAULT_CARET_BLINK_INTERVAL),
+            frame_time: Instant::now(),
+            delta_time: Duration::ZERO,
+            next_frame_time: None,
+            next_animation_frame: None,
+            viewport_ids: Vec::new(),
+            input_viewport: None,
+            lenient_rendering: false,
+            node_flattening: false,
+            keyed_uids: IntMap::new(),
+            stable_scopes: IntMap::new(),
+            stable_scope_stack: Vec::new(),
+            widget_classes: IntMap::new(),
+            layout_damage: 0,
+            hovered_path: Vec::new(),
+            previous_hovered_path: Vec::new(),
+            hover_hysteresis: DEFAULT_HOVER_HYSTERESIS,
+            last_hover: None,
+            tab_order: Vec::new(),
+            focus_trap_stack: Vec::new(),
+            focus_traps: Vec::new(),
+            escape_layers: Vec::new(),
+            previous_escape_layers: Vec::new(),
+            escape_target: None,
+            form_stack: Vec::new(),
+            debug_overlay: rendering::DebugOverlay::Off,
+            debug_shortcut: cfg!(debug_assertions)
+                .then_some(rendering::DebugOverlay::DEFAULT_SHORTCUT),
         }
     }
 }
 
 impl<Renderer: rendering::Renderer> ByorGuiData<Renderer> {
+    /// Records a diagnostic, unless an identical one was already reported this frame or the
+    /// buffer is full.
+    pub(crate) fn push_diagnostic(&mut self, diagnostic: Diagnostic) {
+        if self
+            .frame_diagnostics
+            .insert(Uid::new(&diagnostic), ())
+            .is_none()
+        {
+            #[cfg(feature = "log")]
+            diagnostic.log();
+
+            if self.diagnostics.len() < MAX_PENDING_DIAGNOSTICS {
+                self.diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    fn report_invalid_properties(
+        &mut self,
+        invalid_properties: &InvalidProperties,
+        location: Option<&'static std::panic::Location<'static>>,
+        uid: Option<Uid>,
+    ) {
+        for property in invalid_properties {
+            self.push_diagnostic(Diagnostic {
+                severity: Severity::Warning,
+                message: format!("invalid value for style property `{property}` was replaced"),
+                location,
+                uid,
+            });
+        }
+    }
+
+    /// Turns unconsumed <kbd>Enter</kbd> and <kbd>Space</kbd> presses into primary clicks.
+    /// <kbd>Enter</kbd> clicks on press, <kbd>Space</kbd> additionally shows as pressed while held.
+    fn apply_keyboard_activation(&mut self, input_state: &mut NodeInputState) {
+        const SPACE: Key = Key::Character(smol_str::SmolStr::new_inline(" "));
+
+        let mut clicked = false;
+        let mut released = false;
+        self.input_state.retain_key_events(|event| {
+            let (KeyEvent::Pressed { key, modifiers, .. }
+            | KeyEvent::Released { key, modifiers, .. }) = event;
+
+            let is_space = *key == SPACE;
+            if !modifiers.is_empty() || !(is_space || (*key == Key::Named(NamedKey::Enter))) {
+                return true;
+            }
+
+            match event {
+                KeyEvent::Pressed { repeat: false, .. } => clicked = true,
+                KeyEvent::Released { .. } if is_space => released = true,
+                _ => (),
+            }
+
+            false
+        });
+
+        if clicked {
+            input_state.clicked_buttons |= MouseButtons::PRIMARY;
+        }
+        if released {
+            input_state.released_buttons |= MouseButtons::PRIMARY;
+        }
+        if self.input_state.key_pressed(&SPACE) {
+            input_state.pressed_buttons |= MouseButtons::PRIMARY;
+        }
+    }
+
+    /// Cycles the debug overlay if its shortcut was pressed, before any widget could see it.
+    fn apply_debug_shortcut(&mut self) {
+        if let Some(shortcut) = &self.debug_shortcut
+            && self.input_state.consume_shortcut(shortcut)
+        {
+            self.debug_overlay = self.debug_overlay.next();
+        }
+    }
+
+    /// Moves the focus along the tab order on unconsumed <kbd>Tab</kbd> and
+    /// <kbd>Shift</kbd>+<kbd>Tab</kbd> presses.
+    fn apply_tab_navigation(&mut self) {
+        // the unmodified shortcut also matches with Shift held, so this has to go first
+        let backward = self.input_state.consume_shortcut(&Shortcut {
+            modifiers: Modifiers::SHIFT,
+            key: Key::Named(NamedKey::Tab),
+            location: None,
+        });
+        let forward = self.input_state.consume_shortcut(&Shortcut {
+            modifiers: Modifiers::empty(),
+            key: Key::Named(NamedKey::Tab),
+            location: None,
+        });
+        if forward == backward {
+            return;
+        }
+
+        // only the nodes directly inside the innermost open trap can be reached
+        let focus_trap = self.focus_traps.last().map(|trap| trap.uid);
+        self.tab_order
+            .retain(|tab_stop| tab_stop.focus_trap == focus_trap);
+
+        // Like in HTML, positive indices come first and ties keep the insertion order
+        self.tab_order
+            .sort_by_key(|tab_stop| (tab_stop.tab_index == 0, tab_stop.tab_index));
+        let Some(last) = self.tab_order.len().checked_sub(1) else {
+            return;
+        };
+
+        let current = self
+            .tab_order
+            .iter()
+            .position(|tab_stop| Some(tab_stop.uid) == self.focused_node);
+        let next = match current {
+            Some(current) if forward => {
+                if current == last {
+                    0
+                } else {
+                    current + 1
+                }
+            }
+            Some(current) => current.checked_sub(1).unwrap_or(last),
+            None if forward => 0,
+            None => last,
+        };
+        self.focused_node = Some(self.tab_order[next].uid);
+        self.focus_source = FocusSource::Keyboard;
+    }
+
+    /// Consumes a press of Escape and decides what it does, based on the popups and modals that
+    /// were open when it was pressed. Resolved at most once per frame, so every widget asking
+    /// gets the same answer.
+    fn resolve_escape(&mut self) -> Option<EscapeTarget> {
+        if self.escape_target.is_some() {
+            return self.escape_target;
+        }
+
+        if !self.input_state.consume_shortcut(&Shortcut {
+            modifiers: Modifiers::empty(),
+            key: Key::Named(NamedKey::Escape),
+            location: None,
+        }) {
+            return None;
+        }
+
+        let dragging =
+            self.hovered_node_override.is_some() && !self.input_state.pressed_buttons().is_empty();
+        let popup = self
+            .previous_escape_layers
+            .iter()
+            .rfind(|layer| layer.escapable == Escapable::Popup);
+        let modal = self
+            .previous_escape_layers
+            .iter()
+            .rfind(|layer| matches!(layer.escapable, Escapable::Modal { .. }));
+
+        let target = if dragging {
+            EscapeTarget::PointerCapture
+        } else if let Some(popup) = popup {
+            EscapeTarget::Layer(popup.uid)
+        } else if let Some(modal) = modal
+            && (modal.escapable == Escapable::Modal { dismissable: true })
+        {
+            EscapeTarget::Layer(modal.uid)
+        } else {
+            EscapeTarget::Focus
+        };
+        self.escape_target = Some(target);
+        self.escape_target
+    }
+
+    /// Applies the effects of the Escape cascade that aren't up to the widgets, and makes the
+    /// popups and modals of this frame the ones the next press applies to.
+    fn apply_escape(&mut self) {
+        match self.resolve_escape() {
+            Some(EscapeTarget::PointerCapture) => {
+                self.hovered_node_override = None;
+                self.pointer_capture_cancelled = true;
+            }
+            Some(EscapeTarget::Focus) => self.focused_node = None,
+            Some(EscapeTarget::Layer(_)) | None => (),
+        }
+
+        std::mem::swap(&mut self.escape_layers, &mut self.previous_escape_layers);
+        self.escape_layers.clear();
+    }
+
+    /// Marks the focus trap with this UID as shown this frame, opening it if it wasn't open.
+    fn show_focus_trap(&mut self, uid: Uid) {
+        if let Some(trap) = self.focus_traps.iter_mut().find(|trap| trap.uid == uid) {
+            trap.shown = true;
+        } else {
+            self.focus_traps.push(FocusTrap {
+                uid,
+                opener: self.focused_node,
+                shown: true,
+            });
+        }
+    }
+
+    /// Closes the focus traps that weren't shown this frame. If the focus was inside a closed
+    /// trap, it returns to the node that was focused when the trap opened.
+    fn close_focus_traps(&mut self) {
+        while let Some(index) = self.focus_traps.iter().rposition(|trap| !trap.shown) {
+            let trap = self.focus_traps.remove(index);
+            let focus_lost = self
+                .focused_node
+                .is_none_or(|uid| !self.previous_state.contains_key(uid));
+            if focus_lost {
+                self.focused_node = trap.opener;
+                self.focus_source = FocusSource::Restored;
+            }
+        }
+    }
+
+    /// Gives the text layouts of the last frame that are kept across frames back to the cache,
+    /// and keeps all other ones around to be reused.
+    fn recycle_text_layouts(&mut self) {
+        // layouts are lent in the order they are pushed, so the IDs are sorted
+        let mut lent_ids = self
+            .cached_text_layouts
+            .iter()
+            .map(|&(id, _)| id)
+            .peekable();
+        for (text_layout_id, text_layout) in self.text_layouts.iter_mut() {
+            if lent_ids.next_if_eq(&text_layout_id).is_none() {
+                self.spare_text_layouts.push(std::mem::take(text_layout));
+            }
+        }
+
+        for (text_layout_id, cache_id) in self.cached_text_layouts.drain(..) {
+            if let Some(cached) = self.text_layout_cache.get_mut(cache_id) {
+                cached.layout = std::mem::take(&mut self.text_layouts[text_layout_id]);
+                cached.state = self.text_layout_states[text_layout_id];
+            }
+        }
+
+        for cached in self.text_layout_cache.values_mut() {
+            cached.referenced = false;
+        }
+    }
+
+    /// Whether a node with this UID was already inserted during the current frame.
+    #[must_use]
+    fn is_uid_taken(&self, uid: Uid) -> bool {
+        self.previous_state
+            .get(uid)
+            .is_some_and(|state| state.referenced)
+    }
+
     #[must_use]
     fn compute_node_input_state(&self, uid: Option<Uid>) -> NodeInputState {
-        let hover_state = uid
+        let (hover_state, hit) = uid
             .and_then(|uid| self.previous_state.get(uid))
-            .map(|previous_state| previous_state.hover_state)
+            .map(|previous_state| (previous_state.hover_state, previous_state.hit))
             .unwrap_or_default();
 
+        // a cancelled drag ignores the mouse buttons until they are released
         let (pressed_buttons, clicked_buttons, released_buttons) =
-            if hover_state == HoverState::DirectlyHovered {
+            if hit && !self.pointer_capture_cancelled {
                 (
                     self.input_state.pressed_buttons(),
                     self.input_state.clicked_buttons(),
@@ -459,6 +1118,12 @@ impl<Renderer: rendering::Renderer> ByorGuiData<Renderer> {
             clicked_buttons,
             released_buttons,
             focused: uid.is_some() && (uid == self.focused_node),
+            focus_gained: uid.is_some()
+                && (uid == self.focused_node)
+                && (uid != self.previous_focused_node),
+            focus_lost: uid.is_some()
+                && (uid == self.previous_focused_node)
+                && (uid != self.focused_node),
         }
     }
 }
@@ -482,84 +1147,230 @@ assert_impl_all!(ByorGui<vello::Scene>: Send);
 #[cfg(feature = "vello")]
 assert_not_impl_all!(ByorGui<vello::Scene>: Sync);
 
+#[derive(Clone, Copy)]
+struct TabStop {
+    tab_index: i32,
+    uid: Uid,
+    /// The innermost focus trap the node is inside of
+    focus_trap: Option<Uid>,
+}
+
+/// A popup or modal that was open during a frame.
+#[derive(Clone, Copy)]
+struct EscapeLayer {
+    uid: Uid,
+    escapable: Escapable,
+}
+
+/// The namespace of the outermost stable scopes, see [`ByorGuiContext::stable_scope`].
+enum StableScope {}
+
+/// What a press of Escape did, see [`Escapable`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EscapeTarget {
+    PointerCapture,
+    Layer(Uid),
+    Focus,
+}
+
+/// A floating node that Tab navigation is held inside of while it is open.
+#[derive(Clone, Copy)]
+struct FocusTrap {
+    uid: Uid,
+    /// The node that was focused when the trap opened
+    opener: Option<Uid>,
+    shown: bool,
+}
+
+/// The boxes that were allocated on the heap because their contents didn't fit inline, see
+/// [`ByorGui::allocation_stats`].
+#[cfg(feature = "profiling")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// Renderers added to nodes during the last frame
+    pub renderer_spills: usize,
+    /// Retained renderers of the nodes that still exist
+    pub retained_renderer_spills: usize,
+    /// Tags added to nodes during the last frame
+    pub tag_spills: usize,
+    /// Values in the persistent state of all nodes
+    pub persistent_state_spills: usize,
+}
+
+/// Everything the result of hover resolution depends on.
+#[derive(Clone, Copy, PartialEq)]
+struct HoverInputs {
+    cursor_position: Option<Vec2<Pixel>>,
+    pressed_buttons: MouseButtons,
+    input_viewport: Option<ViewportId>,
+    hovered_node_override: Option<Uid>,
+    layout_damage: u64,
+}
+
+/// The frame clock before a frame started, so a failed frame can be undone.
+#[derive(Clone, Copy)]
+struct FrameClock {
+    frame_time: Instant,
+    delta_time: Duration,
+    next_frame_time: Option<Instant>,
+    next_animation_frame: Option<Instant>,
+}
+
+/// The node that gets the mouse buttons in a hovered subtree.
+#[derive(Clone, Copy)]
+struct Hit {
+    uid: Uid,
+    /// Whether the node handles mouse buttons itself, so no ancestor may claim them
+    handled: bool,
+}
+
+/// The nodes hovered before, which stay hovered until the cursor is `hysteresis` outside of them.
+#[derive(Clone, Copy)]
+struct StickyHover<'a> {
+    uids: &'a [Uid],
+    hysteresis: Float<Pixel>,
+}
+
+impl StickyHover<'_> {
+    /// How far `node` can be hit outside of its hit rect.
+    #[must_use]
+    fn margin(&self, node: &Node) -> Vec2<Pixel> {
+        match node.uid {
+            Some(uid) if self.uids.contains(&uid) => self.hysteresis.into(),
+            _ => Vec2::ZERO,
+        }
+    }
+}
+
+/// Resets the hover state of nodes that turned out not to be hovered.
+fn clear_hover(previous_state: &mut IntMap<Uid, PreviousState>, uids: impl Iterator<Item = Uid>) {
+    for uid in uids {
+        if let Some(state) = previous_state.get_mut(uid) {
+            state.hover_state = HoverState::NotHovered;
+            state.hit = false;
+        }
+    }
+}
+
+/// Ranks siblings whose hit rects all contain the cursor, lower is better. A node the cursor is
+/// actually over wins over one that only reaches it with its minimum hit size, and a node that
+/// was hovered before wins over its neighbors while the cursor is within its hysteresis.
+/// Otherwise the nearest center wins.
 #[must_use]
-fn compute_previous_state<Renderer: rendering::Renderer>(
+fn hit_rank(node: &Node, mouse_position: Vec2<Pixel>, sticky: StickyHover) -> (bool, bool, f32) {
+    let margin = sticky.margin(node);
+    let inside = point_in_rect(
+        mouse_position,
+        node.position - margin,
+        node.style.fixed_size + margin * 2.0,
+    );
+    let offset = mouse_position - node.center();
+    let distance_squared =
+        offset.x.value() * offset.x.value() + offset.y.value() * offset.y.value();
+    let sticky = inside && (margin != Vec2::ZERO);
+    (!inside, !sticky, distance_squared)
+}
+
+/// Resolves the hovered nodes of a tree, only descending into subtrees that contain the cursor.
+/// Nodes whose hover state gets set are recorded in `hovered_path`.
+///
+/// Nodes are hit in their rect grown to their minimum hit size, and sticky nodes additionally by
+/// the hysteresis. The grown rect is still limited by the ancestors, a node only reaches past its
+/// parent if the parent has a minimum hit size or is sticky too.
+#[must_use]
+fn resolve_hover(
     tree: TreeRef<'_, Node, Shared>,
-    data: &mut ByorGuiData<Renderer>,
+    previous_state: &mut IntMap<Uid, PreviousState>,
+    hovered_path: &mut Vec<Uid>,
+    mouse_position: Vec2<Pixel>,
     mouse_in_parent_clip_bounds: bool,
-) -> Option<Uid> {
-    let mut hovered_node = None;
-
+    sticky: StickyHover,
+) -> Option<Hit> {
     let TreeRef {
         parent: node,
         descendants,
         ..
     } = tree;
 
-    let mouse_position = data.input_state.cursor_position();
+    let hit_margin = node.hit_margin() + sticky.margin(node);
     let mouse_in_bounds = mouse_in_parent_clip_bounds
-        && point_in_rect(mouse_position, node.position, node.style.fixed_size);
-
-    let (clip_position, clip_size) = node.clip_bounds();
-    let mouse_in_clip_bounds =
-        mouse_in_bounds && point_in_rect(mouse_position, clip_position, clip_size);
+        && point_in_rect(
+            mouse_position,
+            node.position - hit_margin,
+            node.style.fixed_size + hit_margin * 2.0,
+        );
+    if !mouse_in_bounds {
+        // none of the descendants can contain the cursor either
+        return None;
+    }
 
-    iter_subtrees!(descendants => |subtree| {
-        if subtree.is_root {
-            continue;
-        }
+    let mut hovered_node = None;
 
-        if let Some(uid) = compute_previous_state(subtree, data, mouse_in_clip_bounds) {
-            assert!(hovered_node.is_none(), "multiple nodes hovered");
-            hovered_node = Some(uid);
-        }
-    });
+    let (clip_position, clip_size) = node.clip_bounds();
+    if point_in_rect(
+        mouse_position,
+        clip_position - hit_margin,
+        clip_size + hit_margin * 2.0,
+    ) {
+        // the best hit among the children, with the rank of the child and where its hovered
+        // nodes start in `hovered_path`
+        let mut best_child: Option<(Hit, (bool, bool, f32), usize)> = None;
+        iter_subtrees!(descendants => |subtree| {
+            if subtree.is_root {
+                continue;
+            }
 
-    if let Some(uid) = node.uid {
-        let mut child_count = 0u32;
-        let mut total_content_size = Vec2::default();
-        let mut max_content_size = Vec2::default();
-        iter_children!(descendants => |child| {
-            child_count += 1;
-            total_content_size += child.style.fixed_size;
-            max_content_size = max_content_size.max(child.style.fixed_size);
+            let path_start = hovered_path.len();
+            let child = subtree.parent;
+            if let Some(hit) = resolve_hover(
+                subtree,
+                previous_state,
+                hovered_path,
+                mouse_position,
+                true,
+                sticky,
+            ) {
+                let rank = hit_rank(child, mouse_position, sticky);
+                match best_child {
+                    Some((_, best_rank, _)) if best_rank <= rank => {
+                        clear_hover(previous_state, hovered_path.drain(path_start..));
+                    }
+                    Some((_, _, best_start)) => {
+                        // the previous best child pushed its nodes right before this one
+                        clear_hover(previous_state, hovered_path.drain(best_start..path_start));
+                        best_child = Some((hit, rank, best_start));
+                    }
+                    None => best_child = Some((hit, rank, path_start)),
+                }
+            }
         });
+        hovered_node = best_child.map(|(hit, ..)| hit);
+    }
 
-        let total_spacing = (child_count.saturating_sub(1) as f32) * node.style.child_spacing();
-
-        let state = data.previous_state.entry(uid).or_default();
-        state.referenced = true; // this state is indeed still referenced
-
-        state.hover_state = if let Some(hovered_node_override) = data.hovered_node_override {
-            if uid == hovered_node_override {
-                hovered_node = Some(uid);
-                HoverState::DirectlyHovered
-            } else {
-                HoverState::NotHovered
+    if let Some(uid) = node.uid {
+        let state = previous_state.entry(uid).or_default();
+        match hovered_node {
+            None => {
+                state.hover_state = HoverState::DirectlyHovered;
+                state.hit = true;
+                hovered_node = Some(Hit {
+                    uid,
+                    handled: node.hit_target.is_some(),
+                });
             }
-        } else if mouse_in_bounds {
-            if hovered_node.is_none() {
-                hovered_node = Some(uid);
-                HoverState::DirectlyHovered
-            } else {
-                HoverState::Hovered
+            Some(hit) => {
+                state.hover_state = HoverState::Hovered;
+                if !hit.handled && (node.hit_target == Some(HitTarget::Subtree)) {
+                    // claim the buttons from the passive descendant, so they are only handled once
+                    state.hit = true;
+                    if let Some(descendant_state) = previous_state.get_mut(hit.uid) {
+                        descendant_state.hit = false;
+                    }
+                    hovered_node = Some(Hit { uid, handled: true });
+                }
             }
-        } else {
-            HoverState::NotHovered
-        };
-
-        state.size = node.style.fixed_size;
-        state.content_size = match node.style.layout_direction() {
-            Direction::LeftToRight => Vec2 {
-                x: total_content_size.x + total_spacing,
-                y: max_content_size.y,
-            },
-            Direction::TopToBottom => Vec2 {
-                x: max_content_size.x,
-                y: total_content_size.y + total_spacing,
-            },
-        };
-        state.position = node.position;
+        }
+        hovered_path.push(uid);
     }
 
     hovered_node
@@ -578,61 +1389,482 @@ impl<Renderer: rendering::Renderer> ByorGui<Renderer> {
         &mut self.data.theme
     }
 
-    fn update_previous_states(&mut self) {
-        if self.data.input_state.pressed_buttons().is_empty() {
-            self.data.hovered_node_override = None;
-        }
-
-        let mut hovered_node = None;
-        let mut trees = self.forest.trees();
-        while let Some(tree) = trees.next() {
-            // FIXME: floating nodes should stop nodes underneath from being hovered
-            if let Some(uid) = compute_previous_state(tree, &mut self.data, true) {
-                hovered_node = Some(uid);
-            }
-        }
-
-        self.data.previous_state.retain(|_, state| state.referenced);
-
-        if !self.data.input_state.pressed_buttons().is_empty() {
-            self.data.hovered_node_override = hovered_node;
-            if hovered_node.is_some() {
-                self.data.focused_node = hovered_node;
-            }
-        }
-    }
-
     #[must_use]
     #[inline]
-    pub fn scale_factor(&self) -> f32 {
-        self.data.scale_factor
+    pub fn formatter(&self) -> &dyn format::ValueFormatter {
+        &*self.data.formatter
     }
 
+    /// Sets how built-in widgets format the numbers and dates they show, which is
+    /// [`EnglishFormatter`](format::EnglishFormatter) by default.
     #[inline]
-    pub fn set_scale_factor(&mut self, scale_factor: f32) {
-        self.data.scale_factor = scale_factor;
+    pub fn set_formatter(&mut self, formatter: impl format::ValueFormatter) {
+        self.data.formatter = Box::new(formatter);
     }
 
     #[must_use]
-    #[inline]
-    pub fn input_state(&self) -> &InputState {
-        &self.data.input_state
-    }
+    fn resolve_hovered_node(&mut self) -> Option<Uid> {
+        std::mem::swap(
+            &mut self.data.hovered_path,
+            &mut self.data.previous_hovered_path,
+        );
+        self.data.hovered_path.clear();
+        clear_hover(
+            &mut self.data.previous_state,
+            self.data.previous_hovered_path.iter().copied(),
+        );
 
-    pub fn on_input_event(&mut self, event: InputEvent) {
-        self.data
-            .input_state
-            .on_event(event, self.data.scale_factor);
-    }
+        if let Some(hovered_node_override) = self.data.hovered_node_override {
+            // the override is the only hovered node, regardless of the cursor position
+            let state = self
+                .data
+                .previous_state
+                .get_mut(hovered_node_override)
+                .filter(|state| state.referenced)?;
+            state.hover_state = HoverState::DirectlyHovered;
+            state.hit = true;
+            self.data.hovered_path.push(hovered_node_override);
+            return Some(hovered_node_override);
+        }
+
+        let mouse_position = self.data.input_state.cursor_position()?;
+        // the hysteresis only absorbs jitter, after a jump the cursor hits whatever it landed on
+        let moved_slowly = self
+            .data
+            .last_hover
+            .and_then(|(inputs, _)| inputs.cursor_position)
+            .is_some_and(|previous_position| {
+                let offset = mouse_position - previous_position;
+                offset.x.abs().max(offset.y.abs()) <= self.data.hover_hysteresis
+            });
+        let sticky = StickyHover {
+            uids: if moved_slowly {
+                &self.data.previous_hovered_path
+            } else {
+                &[]
+            },
+            hysteresis: self.data.hover_hysteresis,
+        };
+
+        let mut hovered_node = None;
+        for primary_index in 0..self.forest.primary_count() {
+            let viewport_id = self.data.viewport_ids[primary_index];
+            let mouse_in_viewport = self
+                .data
+                .input_viewport
+                .is_none_or(|input_viewport| input_viewport == viewport_id);
+
+            let mut trees = self.forest.trees_of_primary(primary_index);
+            while let Some(tree) = trees.next() {
+                // FIXME: floating nodes should stop nodes underneath from being hovered
+                if let Some(Hit { uid, .. }) = resolve_hover(
+                    tree,
+                    &mut self.data.previous_state,
+                    &mut self.data.hovered_path,
+                    mouse_position,
+                    mouse_in_viewport,
+                    sticky,
+                ) {
+                    hovered_node = Some(uid);
+                }
+            }
+        }
+
+        hovered_node
+    }
+
+    fn update_previous_states(&mut self) {
+        if self.data.input_state.pressed_buttons().is_empty() {
+            self.data.hovered_node_override = None;
+            self.data.pointer_capture_cancelled = false;
+        }
+
+        self.data.previous_state.retain(|_, state| state.referenced);
+        let previous_state = &self.data.previous_state;
+        self.data
+            .retained_renderers
+            .retain(|uid, _| previous_state.contains_key(uid));
+        // the classes of widgets that weren't shown were cleared when the frame started
+        self.data
+            .widget_classes
+            .retain(|_, classes| !classes.is_empty());
+
+        let hover_inputs = HoverInputs {
+            cursor_position: self.data.input_state.cursor_position(),
+            pressed_buttons: self.data.input_state.pressed_buttons(),
+            input_viewport: self.data.input_viewport,
+            hovered_node_override: self.data.hovered_node_override,
+            layout_damage: self.data.layout_damage,
+        };
+
+        // if neither the input nor any node bounds changed, last frame's hover states are still valid
+        let hovered_node = match self.data.last_hover {
+            Some((last_inputs, hovered_node)) if last_inputs == hover_inputs => hovered_node,
+            _ => {
+                let hovered_node = self.resolve_hovered_node();
+                self.data.last_hover = Some((hover_inputs, hovered_node));
+                hovered_node
+            }
+        };
+
+        if !self.data.input_state.pressed_buttons().is_empty()
+            && !self.data.pointer_capture_cancelled
+        {
+            self.data.hovered_node_override = hovered_node;
+            if hovered_node.is_some() {
+                self.data.focused_node = hovered_node;
+                self.data.focus_source = FocusSource::Pointer;
+            }
+        }
+    }
 
     #[must_use]
-    #[inline(never)]
-    fn begin_frame<'gui>(
-        &'gui mut self,
-        screen_size: Vec2<Pixel>,
-    ) -> ByorGuiContext<'gui, Renderer> {
+    #[inline]
+    pub fn scale_factor(&self) -> f32 {
+        self.data.scale_factor
+    }
+
+    /// Sets how many pixels make up one point.
+    ///
+    /// When the GUI is shown in a window this is the window's scale factor, otherwise it can be
+    /// chosen freely.
+    #[inline]
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.data.scale_factor = scale_factor;
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn scroll_line_height(&self) -> Float<Point> {
+        self.data.scroll_config.line_height
+    }
+
+    /// Sets how far one line of a scroll wheel scrolls, [`POINTS_PER_SCROLL_LINE`] by default.
+    ///
+    /// Platforms that let users choose how many lines one notch of the wheel scrolls can pass that
+    /// setting on here.
+    #[inline]
+    pub fn set_scroll_line_height(&mut self, line_height: Float<Point>) {
+        self.data.scroll_config.line_height = line_height;
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn scroll_speed_multiplier(&self) -> f32 {
+        self.data.scroll_config.speed_multiplier
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn hover_hysteresis(&self) -> Float<Pixel> {
+        self.data.hover_hysteresis
+    }
+
+    /// Sets how far the cursor has to move outside of a hovered node before it stops being hovered,
+    /// [`DEFAULT_HOVER_HYSTERESIS`] by default.
+    ///
+    /// Without it, a cursor resting on the edge between two nodes, or moving slowly across it, lets
+    /// the hover jump between them from frame to frame as positions round differently. The
+    /// hysteresis only applies while the cursor moves less than it between frames, if it jumps
+    /// further the node it lands on is hovered right away.
+    #[inline]
+    pub fn set_hover_hysteresis(&mut self, hysteresis: Float<Pixel>) {
+        self.data.hover_hysteresis = hysteresis.max(0.px());
+    }
+
+    /// Sets a factor all scrolling is multiplied by, including precise devices like touchpads and
+    /// the steps of scroll bars.
+    #[inline]
+    pub fn set_scroll_speed_multiplier(&mut self, multiplier: f32) {
+        self.data.scroll_config.speed_multiplier = multiplier;
+    }
+
+    /// The size of the main viewport in the last frame, as passed to [`frame`](Self::frame).
+    #[must_use]
+    #[inline]
+    pub fn screen_size(&self) -> Vec2<Pixel> {
+        self.data.screen_size
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn input_state(&self) -> &InputState {
+        &self.data.input_state
+    }
+
+    /// The computed style of a node inserted with [`NodeContents::with_style_snapshot`].
+    ///
+    /// Like all previous state, this is the style of the last completed frame, so while building
+    /// a frame it lags one frame behind.
+    #[must_use]
+    #[inline]
+    pub fn computed_style(&self, uid: Uid) -> Option<&ComputedStyleSnapshot> {
+        self.data.previous_state.get(uid)?.style.as_ref()
+    }
+
+    /// Iterates all nodes of the last completed frame in depth-first order.
+    ///
+    /// Floating nodes are visited where they were inserted, so they appear as descendants of the
+    /// node they were inserted into. After a frame was discarded by
+    /// [`try_frame`](Self::try_frame) there are no nodes until the next frame completes.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeInfo<'_>> {
+        self.forest
+            .preorder()
+            .map(|(node, depth, is_root)| NodeInfo {
+                node,
+                text: node
+                    .text_layout
+                    .expand()
+                    .map(|id| &self.data.text_contents[self.data.text_ranges[id].clone()]),
+                tag: node.tag.expand().map(|id| &*self.data.tags[id]),
+                depth,
+                is_floating_root: is_root && (depth > 0),
+            })
+    }
+
+    /// The number of nodes of the last completed frame, including the viewport roots.
+    #[must_use]
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.forest.len()
+    }
+
+    /// Reserves space for `node_count` nodes, so frames up to that size don't have to grow the
+    /// node storage.
+    ///
+    /// Storage is kept between frames, so this is only needed to avoid growing it repeatedly while
+    /// a large UI is built for the first time. The same goes for text layouts and computed styles,
+    /// so once it was built a few times, rebuilding a frame that doesn't change doesn't allocate,
+    /// as long as its nodes use at most a few dozen distinct paddings, backgrounds and fonts.
+    #[inline]
+    pub fn reserve_nodes(&mut self, node_count: usize) {
+        self.forest.reserve(node_count);
+    }
+
+    /// Returns all diagnostics recorded since the last call.
+    ///
+    /// Identical diagnostics are only recorded once per frame, so a problem that persists is
+    /// reported again by every frame. At most [`MAX_PENDING_DIAGNOSTICS`] are kept, later ones
+    /// are dropped until the diagnostics are taken.
+    #[must_use]
+    #[inline]
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.data.diagnostics)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn error_policy(&self) -> ErrorPolicy {
+        self.data.error_policy
+    }
+
+    #[inline]
+    pub fn set_error_policy(&mut self, error_policy: ErrorPolicy) {
+        self.data.error_policy = error_policy;
+    }
+
+    /// How long the text caret stays visible or hidden while blinking, if it blinks at all.
+    #[must_use]
+    #[inline]
+    pub fn caret_blink_interval(&self) -> Option<Duration> {
+        self.data.caret_blink_interval
+    }
+
+    #[inline]
+    pub fn set_caret_blink_interval(&mut self, caret_blink_interval: Option<Duration>) {
+        self.data.caret_blink_interval = caret_blink_interval;
+    }
+
+    /// The point in time at which the last frame starts to look outdated, even if no input is
+    /// received until then.
+    ///
+    /// Apps that only redraw on input should schedule a redraw for this instant.
+    #[must_use]
+    #[inline]
+    pub fn next_animation_frame(&self) -> Option<Instant> {
+        self.data.next_animation_frame
+    }
+
+    /// Sets the time the next frame is built at, instead of the current time.
+    ///
+    /// This lets tests drive animations and other time-based behavior deterministically.
+    #[inline]
+    pub fn set_time(&mut self, time: Instant) {
+        self.data.next_frame_time = Some(time);
+    }
+
+    /// Whether the last frame contains anything that changes over time, see
+    /// [`next_animation_frame`](Self::next_animation_frame).
+    #[must_use]
+    #[inline]
+    pub fn needs_animation(&self) -> bool {
+        self.data.next_animation_frame.is_some()
+    }
+
+    /// Counts the renderers, tags and state of the last frame that were too large for their
+    /// inline storage, so each of them costs a heap allocation.
+    #[cfg(feature = "profiling")]
+    #[must_use]
+    pub fn allocation_stats(&self) -> AllocationStats {
+        AllocationStats {
+            renderer_spills: self
+                .data
+                .renderers
+                .values()
+                .filter(|renderer| renderer.is_heap())
+                .count(),
+            retained_renderer_spills: self
+                .data
+                .retained_renderers
+                .values()
+                .filter(|entry| entry.is_heap())
+                .count(),
+            tag_spills: self.data.tags.values().filter(|tag| tag.is_heap()).count(),
+            persistent_state_spills: self
+                .data
+                .persistent_state
+                .values()
+                .map(PersistentState::spilled_values)
+                .sum(),
+        }
+    }
+
+    /// Positions in input events are relative to the top left corner of the GUI, which isn't
+    /// necessarily the top left corner of the window it's shown in.
+    pub fn on_input_event(&mut self, event: InputEvent) {
+        if let Some(start) = self.data.input_recording_start {
+            self.data.recorded_input.push(RecordedInputEvent {
+                time: self.data.frame_time.saturating_duration_since(start),
+                event: event.clone(),
+            });
+        }
+
+        self.data.input_state.on_event(
+            event,
+            self.data.frame_time,
+            self.data.scale_factor,
+            self.data.scroll_config,
+        );
+    }
+
+    /// Passes all `events` to [`on_input_event`](Self::on_input_event) in order, for example to
+    /// replay [recorded input](Self::input_recording) or to drive the GUI from a test.
+    pub fn inject_events(&mut self, events: impl IntoIterator<Item = InputEvent>) {
+        for event in events {
+            self.on_input_event(event);
+        }
+    }
+
+    /// Starts or stops capturing all input events the GUI receives, see
+    /// [`recorded_input`](Self::recorded_input).
+    ///
+    /// Starting a new recording discards the events of the previous one. Events are timestamped
+    /// with the frame clock, so recordings made with [`set_time`](Self::set_time) are
+    /// deterministic.
+    pub fn input_recording(&mut self, enabled: bool) {
+        match (enabled, self.data.input_recording_start) {
+            (true, None) => {
+                self.data.recorded_input.clear();
+                self.data.input_recording_start = Some(self.data.frame_time);
+            }
+            (false, Some(_)) => self.data.input_recording_start = None,
+            _ => (),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_recording_input(&self) -> bool {
+        self.data.input_recording_start.is_some()
+    }
+
+    /// The events captured by the current or last [input recording](Self::input_recording).
+    #[must_use]
+    #[inline]
+    pub fn recorded_input(&self) -> &[RecordedInputEvent] {
+        &self.data.recorded_input
+    }
+
+    /// Like [`recorded_input`](Self::recorded_input), but leaves the recording empty.
+    #[must_use]
+    #[inline]
+    pub fn take_recorded_input(&mut self) -> Vec<RecordedInputEvent> {
+        std::mem::take(&mut self.data.recorded_input)
+    }
+
+    /// Moves the cursor to `position`, relative to the top left corner of the GUI.
+    #[inline]
+    pub fn move_cursor(&mut self, position: Vec2<Pixel>) {
+        self.on_input_event(InputEvent::CursorMoved { position });
+    }
+
+    #[inline]
+    pub fn press_button(&mut self, button: MouseButton) {
+        self.on_input_event(InputEvent::ButtonPressed { button });
+    }
+
+    #[inline]
+    pub fn release_button(&mut self, button: MouseButton) {
+        self.on_input_event(InputEvent::ButtonReleased { button });
+    }
+
+    /// Like [`on_input_event`](Self::on_input_event), for events received by the window showing
+    /// `viewport`.
+    ///
+    /// Afterwards only nodes inside the viewport the cursor last moved in can be hovered, until a
+    /// frame is built without that viewport.
+    pub fn on_viewport_input_event(&mut self, viewport: ViewportId, event: InputEvent) {
+        if let InputEvent::CursorMoved { .. } | InputEvent::CursorEntered { .. } = event {
+            self.data.input_viewport = Some(viewport);
+        }
+
+        self.on_input_event(event);
+    }
+
+    /// Clears everything the builder of a frame inserts.
+    fn clear_frame_data(&mut self) {
+        self.forest.clear();
+        self.data.viewport_ids.clear();
+        self.data.recycle_text_layouts();
         self.data.text_layouts.clear();
+        self.data.text_layout_states.clear();
+        self.data.text_backgrounds.clear();
+        self.data.text_contents.clear();
+        self.data.text_ranges.clear();
         self.data.renderers.clear();
+        self.data.tags.clear();
+        // a panicking builder may have left scopes behind
+        self.data.uid_stack.clear();
+        self.data.stable_scope_stack.clear();
+        self.data.focused_node_inserted = false;
+        self.data.drag_autoscroll = false;
+        self.data.keyed_uids.clear();
+        self.data.stable_scopes.clear();
+        self.data.widget_classes.values_mut().for_each(Vec::clear);
+        self.data.tab_order.clear();
+        self.data.focus_trap_stack.clear();
+        for trap in &mut self.data.focus_traps {
+            trap.shown = false;
+        }
+        self.data.escape_layers.clear();
+        self.data.escape_target = None;
+        self.data.form_stack.clear();
+    }
+
+    #[inline(never)]
+    fn reset_frame_data(&mut self) {
+        self.clear_frame_data();
+        self.data.frame_errors.clear();
+        self.data.frame_diagnostics.clear();
+        self.data.new_previous_states.clear();
+        let frame_time = self
+            .data
+            .next_frame_time
+            .take()
+            .unwrap_or_else(Instant::now);
+        self.data.delta_time = frame_time.saturating_duration_since(self.data.frame_time);
+        self.data.frame_time = frame_time;
+        self.data.next_animation_frame = None;
         self.data
             .previous_state
             .values_mut()
@@ -641,16 +1873,38 @@ impl<Renderer: rendering::Renderer> ByorGui<Renderer> {
             .float_positions
             .values_mut()
             .for_each(PersistentFloatPosition::reset_referenced);
+    }
+
+    #[must_use]
+    fn insert_viewport_root<'gui>(
+        &'gui mut self,
+        viewport_id: ViewportId,
+        screen_size: Vec2<Pixel>,
+    ) -> ByorGuiContext<'gui, Renderer> {
+        self.data.viewport_ids.push(viewport_id);
+        if viewport_id == ViewportId::MAIN {
+            self.data.screen_size = screen_size;
+        }
 
         let input_state = NodeInputState::default();
         let root_style = self
             .data
             .theme
             .build_style(None, &[], Theme::ROOT_TYPE_CLASS);
-        let cascaded_style = root_style.cascade_root(screen_size, input_state);
-        let computed_style =
-            compute_style(&root_style, &cascaded_style, None, self.data.scale_factor);
-        let primary_builder = self.forest.insert_primary(Node::new_root(computed_style));
+        let cascaded_style = root_style.cascade_root(screen_size, input_state, &self.data.theme);
+        let mut invalid_properties = InvalidProperties::new();
+        let computed_style = compute_style(
+            &root_style,
+            &cascaded_style,
+            None,
+            self.data.scale_factor,
+            self.data.theme.density(),
+            &mut self.data.shared_computed_values,
+            &mut invalid_properties,
+        );
+        self.data
+            .report_invalid_properties(&invalid_properties, None, None);
+        let primary_builder = self.forest.push_primary(Node::new_root(computed_style));
 
         ByorGuiContext {
             builder: primary_builder,
@@ -660,25 +1914,161 @@ impl<Renderer: rendering::Renderer> ByorGui<Renderer> {
         }
     }
 
+    #[must_use]
+    #[inline(never)]
+    fn begin_frame<'gui>(
+        &'gui mut self,
+        screen_size: Vec2<Pixel>,
+    ) -> ByorGuiContext<'gui, Renderer> {
+        self.reset_frame_data();
+        self.data.apply_debug_shortcut();
+        self.insert_viewport_root(ViewportId::MAIN, screen_size)
+    }
+
     #[inline(never)]
     fn end_frame(&mut self) {
+        // the cursor can't be inside of a viewport that isn't shown anymore
+        if let Some(input_viewport) = self.data.input_viewport
+            && !self.data.viewport_ids.contains(&input_viewport)
+        {
+            self.data.input_viewport = None;
+        }
+
+        self.data.previous_focused_node = self.data.focused_node;
+        self.finish_layout();
+        self.update_previous_states();
+        self.data.apply_escape();
+        self.data.close_focus_traps();
+        self.data.apply_tab_navigation();
+        self.data.input_state.end_frame();
+    }
+
+    fn finish_layout(&mut self) {
         self.data.float_positions.retain(|_, pos| pos.referenced());
         self.layout();
-        self.update_previous_states();
+        self.data
+            .text_layout_cache
+            .retain(|_, cached| cached.referenced);
+        self.data.shared_computed_values.retain_used();
+    }
+
+    #[must_use]
+    fn frame_clock(&self) -> FrameClock {
+        FrameClock {
+            frame_time: self.data.frame_time,
+            delta_time: self.data.delta_time,
+            next_frame_time: self.data.next_frame_time,
+            next_animation_frame: self.data.next_animation_frame,
+        }
+    }
+
+    /// Undoes a frame started with `begin_frame`, with the frame clock and debug overlay from
+    /// before the frame.
+    #[inline(never)]
+    fn abort_frame(&mut self, clock: FrameClock, debug_overlay: rendering::DebugOverlay) {
+        self.clear_frame_data();
+        self.data.debug_overlay = debug_overlay;
+        for uid in self.data.new_previous_states.drain(..) {
+            self.data.previous_state.remove(uid);
+        }
+
+        let FrameClock {
+            frame_time,
+            delta_time,
+            next_frame_time,
+            next_animation_frame,
+        } = clock;
+        self.data.frame_time = frame_time;
+        self.data.delta_time = delta_time;
+        self.data.next_frame_time = next_frame_time;
+        self.data.next_animation_frame = next_animation_frame;
+
+        // the builder has already seen the input, delivering it again would activate widgets twice
         self.data.input_state.end_frame();
     }
 
+    /// Builds a frame that covers `screen_size` pixels, starting at the GUI's origin.
+    ///
+    /// The screen size doesn't have to match any window, so the GUI can be shown in a part of a
+    /// window, or without a window at all.
+    ///
+    /// Returns the result of the builder together with the errors that were collected instead of
+    /// returned, see [`ErrorPolicy::CollectAndContinue`].
     #[inline]
     pub fn frame<T>(
         &mut self,
         screen_size: Vec2<Pixel>,
         builder: impl FnOnce(ByorGuiContext<'_, Renderer>) -> T,
-    ) -> T {
+    ) -> FrameReport<T> {
         let context = self.begin_frame(screen_size);
         let result = builder(context);
         self.end_frame();
 
-        result
+        self.frame_report(result)
+    }
+
+    #[must_use]
+    #[inline]
+    fn frame_report<T>(&mut self, result: T) -> FrameReport<T> {
+        FrameReport {
+            result,
+            errors: std::mem::take(&mut self.data.frame_errors),
+        }
+    }
+
+    /// Builds a frame that is shown in several viewports, for example one per window.
+    ///
+    /// All viewports share persistent state, so a node keeps its state when it moves from one
+    /// viewport to another. Viewports the builder doesn't build are left empty.
+    pub fn frame_multi<T>(
+        &mut self,
+        viewports: &[ViewportDesc],
+        builder: impl FnOnce(ViewportsBuilder<'_, Renderer>) -> T,
+    ) -> FrameReport<T> {
+        self.reset_frame_data();
+        self.data.apply_debug_shortcut();
+        let result = builder(ViewportsBuilder {
+            gui: self,
+            viewports,
+        });
+
+        for viewport in viewports {
+            if !self.data.viewport_ids.contains(&viewport.id) {
+                let _ = self.insert_viewport_root(viewport.id, viewport.size);
+            }
+        }
+
+        self.end_frame();
+
+        self.frame_report(result)
+    }
+
+    /// Like [`frame`](Self::frame), but discards the frame if the builder returns an error.
+    ///
+    /// The partially built GUI is neither laid out nor rendered. The state of nodes the failed
+    /// frame inserted for the first time is dropped, the state of the other nodes and persistent
+    /// state are kept as is, and the frame clock and debug overlay are reset to before the frame.
+    /// Input received for the failed frame is consumed like by a successful frame, since the
+    /// builder may have acted on it before it failed, so a debug shortcut pressed for it is lost.
+    /// The errors collected during a failed frame are reported as well.
+    #[inline]
+    pub fn try_frame<T, E>(
+        &mut self,
+        screen_size: Vec2<Pixel>,
+        builder: impl FnOnce(ByorGuiContext<'_, Renderer>) -> Result<T, E>,
+    ) -> FrameReport<Result<T, E>> {
+        let clock = self.frame_clock();
+        let debug_overlay = self.data.debug_overlay;
+        let context = self.begin_frame(screen_size);
+        let result = builder(context);
+
+        if result.is_ok() {
+            self.end_frame();
+        } else {
+            self.abort_frame(clock, debug_overlay);
+        }
+
+        self.frame_report(result)
     }
 }
 
@@ -689,6 +2079,10 @@ pub struct NodeInputState {
     pub clicked_buttons: MouseButtons,
     pub released_buttons: MouseButtons,
     pub focused: bool,
+    /// The node is focused during this frame but wasn't during the previous one
+    pub focus_gained: bool,
+    /// The node was focused during the previous frame but isn't anymore
+    pub focus_lost: bool,
 }
 
 impl NodeInputState {
@@ -735,12 +2129,27 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         &self.data.theme
     }
 
+    /// The formatter widgets format the values they show with, see [`ByorGui::set_formatter`].
+    #[must_use]
+    #[inline]
+    pub fn formatter(&self) -> &dyn format::ValueFormatter {
+        &*self.data.formatter
+    }
+
     #[must_use]
     #[inline]
     pub fn scale_factor(&self) -> f32 {
         self.data.scale_factor
     }
 
+    /// The distance one step of a scroll bar or scroll wheel line moves, taking the configured
+    /// line height and speed multiplier into account.
+    #[must_use]
+    #[inline]
+    pub fn scroll_step(&self) -> Float<Point> {
+        self.data.scroll_config.step()
+    }
+
     #[must_use]
     #[inline]
     pub fn parent_style(&self) -> &CascadedStyle {
@@ -771,6 +2180,83 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         self.parent_input_state
     }
 
+    /// Registers the popup or modal with this UID as open during this frame, so the next press of
+    /// <kbd>Esc</kbd> can close it, see [`escaped`](Self::escaped).
+    #[inline]
+    pub fn register_escapable(&mut self, uid: Uid, escapable: Escapable) {
+        self.data.escape_layers.push(EscapeLayer { uid, escapable });
+    }
+
+    /// Whether <kbd>Esc</kbd> was pressed during this frame to close the popup or modal with this
+    /// UID, which was registered during the previous frame. The widget has to close itself.
+    ///
+    /// Asking consumes the press, so widgets that handle <kbd>Esc</kbd> themselves have to do so
+    /// before.
+    #[must_use]
+    #[inline]
+    pub fn escaped(&mut self, uid: Uid) -> bool {
+        self.data.resolve_escape() == Some(EscapeTarget::Layer(uid))
+    }
+
+    /// How the focused node got the focus, or [`None`] if no node is focused.
+    #[must_use]
+    #[inline]
+    pub fn focus_source(&self) -> Option<FocusSource> {
+        self.data.focused_node.map(|_| self.data.focus_source)
+    }
+
+    /// The point in time at which the current frame was started, see [`ByorGui::set_time`].
+    #[must_use]
+    #[inline]
+    pub fn time(&self) -> Instant {
+        self.data.frame_time
+    }
+
+    /// The time since the previous frame was started, or since the GUI was created during the
+    /// first frame.
+    #[must_use]
+    #[inline]
+    pub fn delta_time(&self) -> Duration {
+        self.data.delta_time
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn caret_blink_interval(&self) -> Option<Duration> {
+        self.data.caret_blink_interval
+    }
+
+    /// Requests the GUI to be rebuilt at `time` at the latest, see
+    /// [`ByorGui::next_animation_frame`].
+    #[inline]
+    pub fn request_animation_frame(&mut self, time: Instant) {
+        self.data.next_animation_frame = Some(match self.data.next_animation_frame {
+            Some(next_animation_frame) => next_animation_frame.min(time),
+            None => time,
+        });
+    }
+
+    /// Makes the innermost scrollable [`ScrollView`](widgets::ScrollView) around the current node
+    /// scroll toward the cursor while the cursor is close to one of its ends, for widgets that
+    /// drag something across the view. Has to be called during every frame of the drag.
+    #[inline]
+    pub fn request_drag_autoscroll(&mut self) {
+        self.data.drag_autoscroll = true;
+    }
+
+    /// Aborts building the GUI with an error of the application, e.g.
+    /// `std::fs::read_dir(path).or_else(|error| gui.fail(error))?`.
+    ///
+    /// The error is passed on unchanged as [`WidgetError::User`], so it can be recovered from
+    /// the result of [`ByorGui::frame`].
+    #[inline]
+    pub fn fail<T>(
+        &self,
+        error: impl std::error::Error + Send + Sync + 'static,
+    ) -> widgets::WidgetResult<T> {
+        Err(WidgetError::user(error))
+    }
+
     #[must_use]
     #[inline]
     fn compute_recursive_uid(&self, uid: Uid) -> Uid {
@@ -796,11 +2282,117 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         self.data.persistent_state.entry(uid).or_default()
     }
 
+    /// Removes the persistent state stored for `key` if it isn't a `T`, for example because two
+    /// different widgets share a UID, and reports it.
+    pub(crate) fn discard_mismatched_state<T: Any>(&mut self, uid: Uid, key: PersistentStateKey) {
+        if self.persistent_state_mut(uid).discard_mismatched::<T>(key) {
+            self.data.push_diagnostic(
+                Diagnostic::new(
+                    Severity::Warning,
+                    format!("persistent state `{key:?}` had an unexpected type and was reset"),
+                )
+                .with_uid(Some(uid)),
+            );
+        }
+    }
+
+    /// Reads the scroll offset stored for `axis`. An offset that isn't finite can't be the result
+    /// of scrolling, so it is reset and reported.
+    pub(crate) fn stored_scroll(&mut self, uid: Uid, axis: style::axis::Axis) -> Float<Pixel> {
+        let key = axis.persistent_state_scroll_key();
+        let scroll = self
+            .persistent_state(uid)
+            .get::<Float<Pixel>>(key)
+            .copied()
+            .unwrap_or_default();
+        if scroll.value().is_finite() {
+            return scroll;
+        }
+
+        self.persistent_state_mut(uid).insert(key, 0.px());
+        self.data.push_diagnostic(
+            Diagnostic::new(
+                Severity::Warning,
+                format!("persistent state `{key:?}` was {scroll:?} and was reset"),
+            )
+            .with_uid(Some(uid)),
+        );
+        0.px()
+    }
+
     #[must_use]
     pub fn previous_state(&self, uid: Uid) -> Option<&PreviousState> {
         let uid = self.compute_recursive_uid(uid);
         self.data.previous_state.get(uid)
     }
+
+    /// The computed style of a node in the last frame, see [`ByorGui::computed_style`].
+    #[must_use]
+    pub fn computed_style(&self, uid: Uid) -> Option<&ComputedStyleSnapshot> {
+        self.previous_state(uid)?.style.as_ref()
+    }
+}
+
+/// Identifies a viewport built by [`ByorGui::frame_multi`].
+///
+/// IDs are derived from UIDs, which are never 0, so they can't collide with [`MAIN`](Self::MAIN).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ViewportId(u64);
+
+impl ViewportId {
+    /// The viewport built by [`ByorGui::frame`].
+    pub const MAIN: Self = Self(0);
+
+    #[must_use]
+    #[inline]
+    pub const fn from_uid(uid: Uid) -> Self {
+        Self(uid.0.get())
+    }
+
+    /// An ID derived from `data`, for example the ID of the window showing the viewport.
+    #[must_use]
+    #[inline]
+    pub fn new(data: impl std::hash::Hash) -> Self {
+        Self::from_uid(Uid::new(data))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ViewportDesc {
+    pub id: ViewportId,
+    pub size: Vec2<Pixel>,
+}
+
+pub struct ViewportsBuilder<'gui, Renderer: rendering::Renderer> {
+    gui: &'gui mut ByorGui<Renderer>,
+    viewports: &'gui [ViewportDesc],
+}
+
+impl<Renderer: rendering::Renderer> ViewportsBuilder<'_, Renderer> {
+    /// Builds the contents of one viewport.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the viewport wasn't passed to [`ByorGui::frame_multi`] or was already built
+    /// during this frame.
+    #[track_caller]
+    pub fn viewport<T>(
+        &mut self,
+        id: ViewportId,
+        builder: impl FnOnce(ByorGuiContext<'_, Renderer>) -> T,
+    ) -> T {
+        let viewport = self
+            .viewports
+            .iter()
+            .find(|viewport| viewport.id == id)
+            .expect("unknown viewport");
+        assert!(
+            !self.gui.data.viewport_ids.contains(&id),
+            "viewport was already built"
+        );
+
+        builder(self.gui.insert_viewport_root(id, viewport.size))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -839,56 +2431,355 @@ impl<T> NodeResponse<T> {
     }
 
     #[inline]
-    pub fn released(&self, buttons: MouseButtons) -> bool {
-        self.input_state.released(buttons)
+    pub fn released(&self, buttons: MouseButtons) -> bool {
+        self.input_state.released(buttons)
+    }
+}
+
+/// Determines what happens when an error is encountered while building the GUI.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorPolicy {
+    /// Errors are returned to the builder, which usually aborts the frame.
+    #[default]
+    Strict,
+    /// Errors are recorded as diagnostics and in the [`FrameReport`] of the frame, and the
+    /// offending node is inserted anyway so the rest of the frame can still be built.
+    /// Nodes with duplicate UIDs receive a synthesized UID derived from the original one.
+    CollectAndContinue,
+}
+
+/// The outcome of building a frame, see [`ByorGui::frame`].
+#[derive(Debug)]
+pub struct FrameReport<T> {
+    /// The value returned by the builder.
+    pub result: T,
+    /// The errors that were collected instead of returned, in the order they occurred. Always
+    /// empty unless the error policy is [`ErrorPolicy::CollectAndContinue`].
+    pub errors: Vec<DuplicateUidError>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateUidError {
+    location: &'static std::panic::Location<'static>,
+}
+
+impl fmt::Display for DuplicateUidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "duplicate UID at {}:{}:{}",
+            self.location.file(),
+            self.location.line(),
+            self.location.column(),
+        )
+    }
+}
+
+impl std::error::Error for DuplicateUidError {}
+
+/// An error that aborts building the GUI, returned from widgets and builders as part of a
+/// [`WidgetResult`](widgets::WidgetResult).
+#[derive(Debug)]
+pub enum WidgetError {
+    DuplicateUid(DuplicateUidError),
+    /// An error of the application, see [`ByorGuiContext::fail`].
+    User(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl WidgetError {
+    #[must_use]
+    #[inline]
+    pub fn user(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::User(error.into())
+    }
+
+    /// The application error, if it is of type `E`.
+    #[must_use]
+    #[inline]
+    pub fn downcast_user_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        match self {
+            Self::DuplicateUid(_) => None,
+            Self::User(error) => error.downcast_ref(),
+        }
+    }
+}
+
+impl From<DuplicateUidError> for WidgetError {
+    #[inline]
+    fn from(error: DuplicateUidError) -> Self {
+        Self::DuplicateUid(error)
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for WidgetError {
+    #[inline]
+    fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self::User(error)
+    }
+}
+
+impl fmt::Display for WidgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateUid(error) => fmt::Display::fmt(error, f),
+            Self::User(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for WidgetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::DuplicateUid(error) => Some(error),
+            Self::User(error) => Some(&**error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A recoverable problem that was detected while building or laying out a frame.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub location: Option<&'static std::panic::Location<'static>>,
+    pub uid: Option<Uid>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    #[inline]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            location: None,
+            uid: None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_location(self, location: &'static std::panic::Location<'static>) -> Self {
+        Self {
+            location: Some(location),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_uid(self, uid: Option<Uid>) -> Self {
+        Self { uid, ..self }
+    }
+
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        let level = match self.severity {
+            Severity::Warning => log::Level::Warn,
+            Severity::Error => log::Level::Error,
+        };
+        let uid = self.uid.map(|uid| uid.0);
+        let location = self.location.map(|location| location.to_string());
+
+        log::log!(
+            target: "byor_gui",
+            level,
+            uid:? = uid,
+            location:? = location;
+            "{}",
+            self.message,
+        );
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+
+        if let Some(location) = self.location {
+            write!(
+                f,
+                " at {}:{}:{}",
+                location.file(),
+                location.line(),
+                location.column(),
+            )?;
+        }
+
+        if let Some(uid) = self.uid {
+            write!(f, " (node {:#018x})", uid.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub type InsertNodeResult<T> = widgets::WidgetResult<NodeResponse<T>>;
+
+pub trait GuiBuilder<Renderer: rendering::Renderer> {
+    type Result;
+
+    fn build(self, gui: ByorGuiContext<'_, Renderer>) -> Self::Result;
+}
+
+impl<Renderer, R, F> GuiBuilder<Renderer> for F
+where
+    Renderer: rendering::Renderer,
+    F: FnOnce(ByorGuiContext<'_, Renderer>) -> R,
+{
+    type Result = R;
+
+    #[inline]
+    fn build(self, gui: ByorGuiContext<'_, Renderer>) -> Self::Result {
+        self(gui)
+    }
+}
+
+impl<Renderer: rendering::Renderer> GuiBuilder<Renderer> for () {
+    type Result = ();
+
+    #[inline]
+    fn build(self, _gui: ByorGuiContext<'_, Renderer>) -> Self::Result {}
+}
+
+/// Overrides the style of a byte range of a node's text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub range: Range<usize>,
+    pub font_family: Option<FontStack<'static>>,
+    pub font_style: Option<FontStyle>,
+    pub font_weight: Option<FontWeight>,
+    pub text_underline: Option<bool>,
+    pub text_strikethrough: Option<bool>,
+    pub text_color: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl TextSpan {
+    #[must_use]
+    #[inline]
+    pub const fn new(range: Range<usize>) -> Self {
+        Self {
+            range,
+            font_family: None,
+            font_style: None,
+            font_weight: None,
+            text_underline: None,
+            text_strikethrough: None,
+            text_color: None,
+            background: None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_font_family(self, font_family: FontStack<'static>) -> Self {
+        Self {
+            font_family: Some(font_family),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_font_style(self, font_style: FontStyle) -> Self {
+        Self {
+            font_style: Some(font_style),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_font_weight(self, font_weight: FontWeight) -> Self {
+        Self {
+            font_weight: Some(font_weight),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_text_underline(self, text_underline: bool) -> Self {
+        Self {
+            text_underline: Some(text_underline),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_text_strikethrough(self, text_strikethrough: bool) -> Self {
+        Self {
+            text_strikethrough: Some(text_strikethrough),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_text_color(self, text_color: Color) -> Self {
+        Self {
+            text_color: Some(text_color),
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_background(self, background: Color) -> Self {
+        Self {
+            background: Some(background),
+            ..self
+        }
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct DuplicateUidError {
-    location: &'static std::panic::Location<'static>,
-}
 
-impl fmt::Display for DuplicateUidError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "duplicate UID at {}:{}:{}",
-            self.location.file(),
-            self.location.line(),
-            self.location.column(),
-        )
+    #[must_use]
+    fn is_valid_for(&self, text: &str) -> bool {
+        (self.range.start <= self.range.end)
+            && text.is_char_boundary(self.range.start)
+            && text.is_char_boundary(self.range.end)
     }
 }
 
-impl std::error::Error for DuplicateUidError {}
-
-pub type InsertNodeResult<T> = widgets::WidgetResult<NodeResponse<T>>;
-
-pub trait GuiBuilder<Renderer: rendering::Renderer> {
-    type Result;
-
-    fn build(self, gui: ByorGuiContext<'_, Renderer>) -> Self::Result;
+#[derive(Clone, Copy)]
+struct NodeOptions {
+    keyboard_activation: bool,
+    style_snapshot: bool,
+    hit_target: Option<HitTarget>,
+    tab_index: Option<i32>,
+    focus_trap: bool,
 }
 
-impl<Renderer, R, F> GuiBuilder<Renderer> for F
-where
-    Renderer: rendering::Renderer,
-    F: FnOnce(ByorGuiContext<'_, Renderer>) -> R,
-{
-    type Result = R;
-
-    #[inline]
-    fn build(self, gui: ByorGuiContext<'_, Renderer>) -> Self::Result {
-        self(gui)
-    }
+impl NodeOptions {
+    const DEFAULT: Self = Self {
+        keyboard_activation: false,
+        style_snapshot: false,
+        hit_target: None,
+        tab_index: None,
+        focus_trap: false,
+    };
 }
 
-impl<Renderer: rendering::Renderer> GuiBuilder<Renderer> for () {
-    type Result = ();
-
-    #[inline]
-    fn build(self, _gui: ByorGuiContext<'_, Renderer>) -> Self::Result {}
+#[derive(Clone, Copy)]
+struct TextCacheKey {
+    id: Uid,
+    revision: u64,
 }
 
 pub struct NodeContents<'text, Renderer, Builder = ()>
@@ -897,7 +2788,12 @@ where
     Builder: GuiBuilder<Renderer>,
 {
     text: Option<&'text str>,
+    text_spans: &'text [TextSpan],
+    text_cache: Option<TextCacheKey>,
     renderer: Option<NodeRendererStorage<Renderer>>,
+    retained_renderer: Option<RetainedRendererStorage<Renderer>>,
+    tag: Option<NodeTagStorage>,
+    options: NodeOptions,
     builder: Builder,
 }
 
@@ -911,7 +2807,12 @@ impl<Renderer: rendering::Renderer> Default for NodeContents<'_, Renderer> {
 impl<'text, Renderer: rendering::Renderer> NodeContents<'text, Renderer> {
     pub const EMPTY: Self = Self {
         text: None,
+        text_spans: &[],
+        text_cache: None,
         renderer: None,
+        retained_renderer: None,
+        tag: None,
+        options: NodeOptions::DEFAULT,
         builder: (),
     };
 
@@ -924,6 +2825,34 @@ impl<'text, Renderer: rendering::Renderer> NodeContents<'text, Renderer> {
         }
     }
 
+    #[must_use]
+    #[inline]
+    pub const fn rich_text(text: &'text str, spans: &'text [TextSpan]) -> Self {
+        Self {
+            text: Some(text),
+            text_spans: spans,
+            ..Self::EMPTY
+        }
+    }
+
+    /// Like [`text`](Self::text), but the text layout is kept across frames under `id` and only
+    /// rebuilt if `revision` or the style of the node changed. The app bumps `revision` whenever
+    /// it changes the text, the text itself is never compared.
+    ///
+    /// This makes showing huge texts that rarely change, like log panes, cheap: an unchanged
+    /// layout is neither rebuilt nor broken into lines again unless the node changes its width.
+    /// `id` is resolved in the current UID scope like the UIDs of nodes, a layout is dropped once
+    /// a frame doesn't show it anymore.
+    #[must_use]
+    #[inline]
+    pub const fn text_cached(id: Uid, revision: u64, text: &'text str) -> Self {
+        Self {
+            text: Some(text),
+            text_cache: Some(TextCacheKey { id, revision }),
+            ..Self::EMPTY
+        }
+    }
+
     #[must_use]
     #[inline]
     pub fn renderer(renderer: impl rendering::NodeRenderer<Renderer = Renderer>) -> Self {
@@ -944,7 +2873,12 @@ where
     pub const fn builder(f: F) -> Self {
         Self {
             text: None,
+            text_spans: &[],
+            text_cache: None,
             renderer: None,
+            retained_renderer: None,
+            tag: None,
+            options: NodeOptions::DEFAULT,
             builder: f,
         }
     }
@@ -963,7 +2897,31 @@ where
     ) -> NodeContents<'new_text, Renderer, Builder> {
         NodeContents {
             text: Some(text),
+            text_spans: &[],
+            text_cache: None,
+            renderer: self.renderer,
+            retained_renderer: self.retained_renderer,
+            tag: self.tag,
+            options: self.options,
+            builder: self.builder,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_rich_text<'new_text>(
+        self,
+        text: &'new_text str,
+        spans: &'new_text [TextSpan],
+    ) -> NodeContents<'new_text, Renderer, Builder> {
+        NodeContents {
+            text: Some(text),
+            text_spans: spans,
+            text_cache: None,
             renderer: self.renderer,
+            retained_renderer: self.retained_renderer,
+            tag: self.tag,
+            options: self.options,
             builder: self.builder,
         }
     }
@@ -976,11 +2934,145 @@ where
     ) -> NodeContents<'text, Renderer, Builder> {
         NodeContents {
             text: self.text,
+            text_spans: self.text_spans,
+            text_cache: self.text_cache,
             renderer: Some(smallbox!(renderer)),
+            retained_renderer: self.retained_renderer,
+            tag: self.tag,
+            options: self.options,
             builder: self.builder,
         }
     }
 
+    /// Draws the node with a renderer that is kept across frames, see
+    /// [`RetainedNodeRenderer`](rendering::RetainedNodeRenderer). Use this instead of
+    /// [`with_renderer`](Self::with_renderer) for renderers that cache expensive resources.
+    ///
+    /// The node needs a UID for this.
+    #[must_use]
+    #[inline]
+    pub fn with_retained_renderer(
+        self,
+        renderer: impl rendering::RetainedNodeRenderer<Renderer = Renderer>,
+    ) -> Self {
+        Self {
+            retained_renderer: Some(smallbox!(rendering::RetainedSlot(Some(renderer)))),
+            ..self
+        }
+    }
+
+    /// Attaches arbitrary data to the node for the current frame, which can be read back by its
+    /// renderer with [`RenderContext::tag`](rendering::RenderContext::tag) and through
+    /// [`NodeInfo::tag`].
+    #[must_use]
+    #[inline]
+    pub fn with_tag(self, tag: impl Any + Send) -> Self {
+        Self {
+            tag: Some(smallbox!(tag)),
+            ..self
+        }
+    }
+
+    /// Lets the node be clicked with <kbd>Enter</kbd> and <kbd>Space</kbd> while it is focused.
+    #[must_use]
+    #[inline]
+    pub fn with_keyboard_activation(self) -> Self {
+        Self {
+            options: NodeOptions {
+                keyboard_activation: true,
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Marks the node as handling mouse buttons itself.
+    ///
+    /// With [`HitTarget::Subtree`] the node also receives the buttons pressed on descendants that
+    /// aren't marked, so a container like a list row can be clicked as a whole. Marked
+    /// descendants, including nodes with keyboard activation, still take precedence.
+    ///
+    /// The node needs a UID for this.
+    #[must_use]
+    #[inline]
+    pub fn with_hit_target(self, hit_target: HitTarget) -> Self {
+        Self {
+            options: NodeOptions {
+                hit_target: Some(hit_target),
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Makes the node reachable with <kbd>Tab</kbd>, following the semantics of the HTML
+    /// `tabindex` attribute: nodes with a positive index are visited first in ascending order,
+    /// then nodes with index 0 in insertion order. A negative index takes the node out of the tab
+    /// order.
+    ///
+    /// Nodes with keyboard activation are in the tab order with index 0 by default.
+    ///
+    /// The node needs a UID for this.
+    #[must_use]
+    #[inline]
+    pub fn with_tab_index(self, tab_index: i32) -> Self {
+        Self {
+            options: NodeOptions {
+                tab_index: Some(tab_index),
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Keeps <kbd>Tab</kbd> navigation inside the node while it is shown. Once it is no longer
+    /// shown, the focus returns to the node that was focused when it appeared.
+    ///
+    /// Only floating nodes can be focus traps, this has no effect on other nodes.
+    #[must_use]
+    #[inline]
+    pub fn with_focus_trap(self) -> Self {
+        Self {
+            options: NodeOptions {
+                focus_trap: true,
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    /// Records the computed style of the node, so it can be retrieved with
+    /// [`ByorGui::computed_style`] after the frame is completed.
+    ///
+    /// The node needs a UID for this.
+    #[must_use]
+    #[inline]
+    pub fn with_style_snapshot(self) -> Self {
+        Self {
+            options: NodeOptions {
+                style_snapshot: true,
+                ..self.options
+            },
+            ..self
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    fn split_builder(self) -> (NodeContents<'text, Renderer>, Builder) {
+        let contents = NodeContents {
+            text: self.text,
+            text_spans: self.text_spans,
+            text_cache: self.text_cache,
+            renderer: self.renderer,
+            retained_renderer: self.retained_renderer,
+            tag: self.tag,
+            options: self.options,
+            builder: (),
+        };
+        (contents, self.builder)
+    }
+
     #[must_use]
     #[inline]
     pub fn with_builder<R, F>(self, f: F) -> NodeContents<'text, Renderer, F>
@@ -989,70 +3081,427 @@ where
     {
         NodeContents {
             text: self.text,
+            text_spans: self.text_spans,
+            text_cache: self.text_cache,
             renderer: self.renderer,
+            retained_renderer: self.retained_renderer,
+            tag: self.tag,
+            options: self.options,
             builder: f,
         }
     }
 }
 
+#[must_use]
+fn build_text_layout(text: &str, spans: &[TextSpan], style: &ComputedStyle) -> TextLayout<Color> {
+    let mut layout = TextLayout::default();
+    build_text_layout_into(&mut layout, text, spans, style);
+    layout
+}
+
+/// Like [`build_text_layout`], but reuses the allocations of `layout`.
+fn build_text_layout_into(
+    layout: &mut TextLayout<Color>,
+    text: &str,
+    spans: &[TextSpan],
+    style: &ComputedStyle,
+) {
+    use parley::style::{LineHeight, StyleProperty};
+
+    global_cache::with_parley_global_data(|parley_global_data| {
+        let mut builder = parley_global_data.builder(text, 1.0);
+
+        builder.push_default(StyleProperty::Brush(style.text_color()));
+        builder.push_default(StyleProperty::FontStack(style.font_family().clone()));
+        builder.push_default(StyleProperty::FontSize(style.font_size().value()));
+        builder.push_default(StyleProperty::FontStyle(style.font_style()));
+        builder.push_default(StyleProperty::LineHeight(LineHeight::FontSizeRelative(1.3)));
+        builder.push_default(StyleProperty::FontWeight(style.font_weight()));
+        builder.push_default(StyleProperty::FontWidth(style.font_width()));
+        builder.push_default(StyleProperty::Underline(style.text_underline()));
+        builder.push_default(StyleProperty::Strikethrough(style.text_strikethrough()));
+        builder.push_default(StyleProperty::OverflowWrap(style.overflow_wrap()));
+        builder.push_default(StyleProperty::Locale(
+            Some(style.locale().as_str()).filter(|locale| !locale.is_empty()),
+        ));
+
+        for span in spans.iter().filter(|span| span.is_valid_for(text)) {
+            let range = span.range.clone();
+
+            if let Some(font_family) = &span.font_family {
+                builder.push(StyleProperty::FontStack(font_family.clone()), range.clone());
+            }
+            if let Some(font_style) = span.font_style {
+                builder.push(StyleProperty::FontStyle(font_style), range.clone());
+            }
+            if let Some(font_weight) = span.font_weight {
+                builder.push(StyleProperty::FontWeight(font_weight), range.clone());
+            }
+            if let Some(text_underline) = span.text_underline {
+                builder.push(StyleProperty::Underline(text_underline), range.clone());
+            }
+            if let Some(text_strikethrough) = span.text_strikethrough {
+                builder.push(
+                    StyleProperty::Strikethrough(text_strikethrough),
+                    range.clone(),
+                );
+            }
+            if let Some(text_color) = span.text_color {
+                builder.push(StyleProperty::Brush(text_color), range);
+            }
+        }
+
+        builder.build_into(layout, text);
+    })
+}
+
+/// The size and vertical metrics of a text, see [`ByorGuiContext::text_metrics`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMetrics {
+    /// The size of a node showing the text, including its padding.
+    pub size: Vec2<Pixel>,
+    /// The distance from the top of the node to the baseline of the first line.
+    pub baseline: Float<Pixel>,
+    /// The ascent of the first line.
+    pub ascent: Float<Pixel>,
+    /// The height of the first line.
+    pub line_height: Float<Pixel>,
+    pub line_count: usize,
+}
+
 impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
     #[must_use]
     #[inline]
-    fn layout_text(&mut self, text: &str) -> TextLayoutId {
-        use parley::style::{LineHeight, OverflowWrap, StyleProperty};
+    fn layout_text(
+        &mut self,
+        uid: Option<Uid>,
+        location: &'static std::panic::Location<'static>,
+        text: &str,
+        spans: &[TextSpan],
+        cache: Option<TextCacheKey>,
+        style: &ComputedStyle,
+    ) -> TextLayoutId {
+        let cache = cache.map(|cache| {
+            let mut hasher = rapidhash::fast::RapidHasher::default();
+            style.hash_relevant(&mut hasher);
+            (
+                self.compute_recursive_uid(cache.id),
+                cache.revision,
+                hasher.finish(),
+            )
+        });
+
+        if let Some((cache_id, revision, style_hash)) = cache
+            && let Some(cached) = self.data.text_layout_cache.get_mut(cache_id)
+            && !cached.referenced
+            && (cached.revision == revision)
+            && (cached.style_hash == style_hash)
+        {
+            cached.referenced = true;
+            let text_layout_id = self
+                .data
+                .text_layouts
+                .push(std::mem::take(&mut cached.layout));
+            self.data.text_layout_states[text_layout_id] = cached.state;
+            self.data
+                .cached_text_layouts
+                .push((text_layout_id, cache_id));
+
+            let text_start = self.data.text_contents.len();
+            self.data.text_contents.push_str(text);
+            self.data.text_ranges[text_layout_id] = text_start..self.data.text_contents.len();
+            return text_layout_id;
+        }
 
-        global_cache::with_parley_global_data(|parley_global_data| {
-            let mut builder = parley_global_data.builder(text, 1.0);
+        for span in spans.iter().filter(|span| !span.is_valid_for(text)) {
+            self.data.push_diagnostic(
+                Diagnostic::new(
+                    Severity::Warning,
+                    format!(
+                        "text span {:?} is out of bounds or not on a character boundary and was ignored",
+                        span.range,
+                    ),
+                )
+                .with_location(location)
+                .with_uid(uid),
+            );
+        }
 
-            let style = &self.builder.parent_node().style;
-            builder.push_default(StyleProperty::Brush(style.text_color()));
-            builder.push_default(StyleProperty::FontStack(style.font_family().clone()));
-            builder.push_default(StyleProperty::FontSize(style.font_size().value()));
-            builder.push_default(StyleProperty::FontStyle(style.font_style()));
-            builder.push_default(StyleProperty::LineHeight(LineHeight::FontSizeRelative(1.3)));
-            builder.push_default(StyleProperty::FontWeight(style.font_weight()));
-            builder.push_default(StyleProperty::FontWidth(style.font_width()));
-            builder.push_default(StyleProperty::Underline(style.text_underline()));
-            builder.push_default(StyleProperty::Strikethrough(style.text_strikethrough()));
-            builder.push_default(StyleProperty::OverflowWrap(OverflowWrap::BreakWord));
+        let hyphenated = HyphenatedText::new(text, spans, style);
+        let mut text_layout = self.data.spare_text_layouts.pop().unwrap_or_default();
+        build_text_layout_into(&mut text_layout, &hyphenated.text, &hyphenated.spans, style);
+        let text_layout_id = self.data.text_layouts.push(text_layout);
+
+        let text_start = self.data.text_contents.len();
+        self.data.text_contents.push_str(text);
+        self.data.text_ranges[text_layout_id] = text_start..self.data.text_contents.len();
+
+        let backgrounds: Vec<_> = hyphenated
+            .spans
+            .iter()
+            .filter(|span| span.is_valid_for(&hyphenated.text))
+            .filter_map(|span| Some((span.range.clone(), span.background?)))
+            .collect();
+        if !backgrounds.is_empty() {
+            self.data.text_backgrounds[text_layout_id] = backgrounds;
+        }
 
-            self.data.text_layouts.push(builder.build(text))
-        })
+        // a cache ID shown twice in one frame keeps the layout it was shown with first
+        if let Some((cache_id, revision, style_hash)) = cache
+            && self
+                .data
+                .text_layout_cache
+                .get(cache_id)
+                .is_none_or(|cached| !cached.referenced)
+        {
+            self.data.text_layout_cache.insert(
+                cache_id,
+                CachedTextLayout {
+                    revision,
+                    style_hash,
+                    layout: TextLayout::default(),
+                    state: layout::TextLayoutState::default(),
+                    referenced: true,
+                },
+            );
+            self.data
+                .cached_text_layouts
+                .push((text_layout_id, cache_id));
+        }
+
+        text_layout_id
     }
 
-    #[track_caller]
+    /// The size a node showing `text` with `style` would have if it was inserted into the current
+    /// parent, including its padding. Nothing is added to the GUI.
+    #[must_use]
+    #[inline]
+    pub fn measure_text(&self, text: &str, style: &Style) -> Vec2<Pixel> {
+        self.text_metrics(text, style, None).size
+    }
+
+    /// Like [`measure_text`](Self::measure_text), but the node is at most `max_width` wide and the
+    /// text wraps if the style allows it.
+    #[must_use]
+    #[inline]
+    pub fn measure_wrapped_text(
+        &self,
+        text: &str,
+        style: &Style,
+        max_width: Float<Pixel>,
+    ) -> Vec2<Pixel> {
+        self.text_metrics(text, style, Some(max_width)).size
+    }
+
+    /// Measures `text` like [`measure_text`](Self::measure_text) and
+    /// [`measure_wrapped_text`](Self::measure_wrapped_text), including the vertical metrics of its
+    /// first line.
+    #[must_use]
+    pub fn text_metrics(
+        &self,
+        text: &str,
+        style: &Style,
+        max_width: Option<Float<Pixel>>,
+    ) -> TextMetrics {
+        let cascaded_style = style.cascade(
+            &self.parent_style,
+            NodeInputState::default(),
+            &self.data.theme,
+        );
+        // invalid properties are reported once the text is actually shown
+        let mut invalid_properties = InvalidProperties::new();
+        let computed_style = compute_style(
+            style,
+            &cascaded_style,
+            Some(&self.builder.parent_node().style),
+            self.data.scale_factor,
+            self.data.theme.density(),
+            // measuring doesn't keep the style around
+            &mut SharedComputedValues::default(),
+            &mut invalid_properties,
+        );
+
+        let padding = computed_style.padding();
+        let horizontal_padding = padding.left + padding.right;
+        let vertical_padding = padding.top + padding.bottom;
+        let min_size = computed_style.min_size;
+        let mut max_size = computed_style.max_size;
+        if let Some(max_width) = max_width {
+            max_size.x = max_size.x.min(max_width);
+        }
+
+        // the same steps as sizing a text node during layout
+        let hyphenated = HyphenatedText::new(text, &[], &computed_style);
+        let mut text_layout = build_text_layout(&hyphenated.text, &[], &computed_style);
+        let preferred_width = text_layout.calculate_content_widths().max;
+        let width = (preferred_width.px().ceil() + horizontal_padding)
+            .min(max_size.x)
+            .max(min_size.x);
+        layout::wrap_text_layout(&mut text_layout, &computed_style, width);
+        let height = (text_layout.height().px().ceil() + vertical_padding)
+            .min(max_size.y)
+            .max(min_size.y);
+
+        let first_line = text_layout.lines().next().map(|line| *line.metrics());
+        TextMetrics {
+            size: Vec2 {
+                x: width,
+                y: height,
+            },
+            baseline: padding.top + first_line.map_or(0.0, |metrics| metrics.baseline).px(),
+            ascent: first_line.map_or(0.0, |metrics| metrics.ascent).px(),
+            line_height: first_line.map_or(0.0, |metrics| metrics.line_height).px(),
+            line_count: text_layout.len(),
+        }
+    }
+
+    /// Returns the byte index into `text` that was under the cursor, assuming `text` and `spans`
+    /// are the contents of the parent node and the node was laid out the same way last frame.
+    #[cfg(feature = "markdown")]
     #[must_use]
+    pub(crate) fn parent_text_index_at_cursor(
+        &self,
+        text: &str,
+        spans: &[TextSpan],
+    ) -> Option<usize> {
+        let parent = self.builder.parent_node();
+        let previous_state = self.data.previous_state.get(parent.uid?)?;
+
+        let hyphenated = HyphenatedText::new(text, spans, &parent.style);
+        let mut text_layout = build_text_layout(&hyphenated.text, &hyphenated.spans, &parent.style);
+        layout::wrap_text_layout(&mut text_layout, &parent.style, previous_state.size.x);
+
+        let text_position = Vec2 {
+            x: previous_state.position.x + parent.style.padding().left,
+            y: previous_state.position.y + parent.style.padding().top,
+        };
+        let cursor_position = self.data.input_state.cursor_position()? - text_position;
+        if (cursor_position.x < 0.px())
+            || (cursor_position.y < 0.px())
+            || (cursor_position.x.value() > text_layout.width())
+            || (cursor_position.y.value() > text_layout.height())
+        {
+            return None;
+        }
+
+        let (cluster, _) = parley::Cluster::from_point(
+            &text_layout,
+            cursor_position.x.value(),
+            cursor_position.y.value(),
+        )?;
+        Some(hyphenated.original_index(cluster.text_range().start))
+    }
+
+    #[track_caller]
     #[inline(never)] // Don't inline this to avoid monomorphization duplication
     fn insert_leaf_node<'gui>(
         &'gui mut self,
         uid: Option<Uid>,
         style: &Style,
         is_root: bool,
-        text: Option<&str>,
-        renderer: Option<NodeRendererStorage<Renderer>>,
+        contents: NodeContents<'_, Renderer>,
     ) -> widgets::WidgetResult<ByorGuiContext<'gui, Renderer>> {
-        let input_state = self.data.compute_node_input_state(uid);
-        let cascaded_style = style.cascade(&self.parent_style, input_state);
+        let location = std::panic::Location::caller();
+        let NodeContents {
+            text,
+            text_spans,
+            text_cache,
+            renderer,
+            retained_renderer,
+            tag,
+            options,
+            builder: (),
+        } = contents;
+
+        let uid = match uid {
+            Some(uid) if self.data.is_uid_taken(uid) => {
+                self.data.push_diagnostic(
+                    Diagnostic::new(Severity::Error, "duplicate UID")
+                        .with_location(location)
+                        .with_uid(Some(uid)),
+                );
+
+                let error = DuplicateUidError { location };
+                match self.data.error_policy {
+                    ErrorPolicy::Strict => return Err(error.into()),
+                    ErrorPolicy::CollectAndContinue => {
+                        self.data.frame_errors.push(error);
+
+                        // Deterministic, so the node keeps its state across frames
+                        let unique_uid = (1u64..)
+                            .map(|occurrence| uid.concat(Uid::new(occurrence)))
+                            .find(|&uid| !self.data.is_uid_taken(uid))
+                            .unwrap();
+                        Some(unique_uid)
+                    }
+                }
+            }
+            uid => uid,
+        };
+
+        let tab_index = options
+            .tab_index
+            .or(options.keyboard_activation.then_some(0));
+        if let Some(uid) = uid
+            && let Some(tab_index) = tab_index.filter(|&tab_index| tab_index >= 0)
+        {
+            self.data.tab_order.push(TabStop {
+                tab_index,
+                uid,
+                focus_trap: self.data.focus_trap_stack.last().copied(),
+            });
+        }
+
+        let mut input_state = self.data.compute_node_input_state(uid);
+        self.data.focused_node_inserted |= input_state.focused;
+        if options.keyboard_activation && input_state.focused {
+            self.data.apply_keyboard_activation(&mut input_state);
+        }
+
+        let cascaded_style = style.cascade(&self.parent_style, input_state, &self.data.theme);
+        let mut invalid_properties = InvalidProperties::new();
         let computed_style = compute_style(
             style,
             &cascaded_style,
             Some(&self.builder.parent_node().style),
             self.data.scale_factor,
+            self.data.theme.density(),
+            &mut self.data.shared_computed_values,
+            &mut invalid_properties,
         );
 
-        let text_layout = text.map(|text| self.layout_text(text));
+        self.data
+            .report_invalid_properties(&invalid_properties, Some(location), uid);
+
+        let text_layout = text.map(|text| {
+            self.layout_text(uid, location, text, text_spans, text_cache, &computed_style)
+        });
         let renderer = renderer.map(|renderer| self.data.renderers.push(renderer));
-        let node = Node::new(uid, text_layout, renderer, computed_style);
+        let tag = tag.map(|tag| self.data.tags.push(tag));
+        let mut node = Node::new(uid, text_layout, renderer, tag, computed_style);
+        node.style_snapshot = options.style_snapshot;
+        if let Some(uid) = uid
+            && let Some(retained_renderer) = retained_renderer
+        {
+            rendering::update_retained_renderer(
+                &mut self.data.retained_renderers,
+                uid,
+                retained_renderer,
+            );
+            node.retained_renderer = true;
+        }
+        node.hit_target = options
+            .hit_target
+            .or(options.keyboard_activation.then_some(HitTarget::SelfOnly));
         let builder = self.builder.insert(node, is_root);
 
         if let Some(uid) = uid {
-            let prev_state = self.data.previous_state.entry(uid).or_default();
-            if prev_state.referenced {
-                return Err(DuplicateUidError {
-                    location: std::panic::Location::caller(),
-                });
-            }
-            prev_state.referenced = true;
+            let new_previous_states = &mut self.data.new_previous_states;
+            let state = self.data.previous_state.entry(uid).or_insert_with(|| {
+                new_previous_states.push(uid);
+                PreviousState::default()
+            });
+            state.referenced = true;
         }
 
         Ok(ByorGuiContext {
@@ -1066,8 +3515,8 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
     #[inline(never)] // Don't inline this to avoid monomorphization duplication
     fn update_float_position(&mut self, uid: Uid, position: FloatPosition) {
         match position {
-            FloatPosition::Cursor => {
-                let cursor_position = self.data.input_state.cursor_position();
+            FloatPosition::Cursor { pivot } => {
+                let cursor_position = self.data.input_state.last_cursor_position();
 
                 self.data.float_positions.insert(
                     uid,
@@ -1075,17 +3524,23 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
                         referenced: true,
                         x: cursor_position.x,
                         y: cursor_position.y,
+                        pivot,
                     },
                 );
             }
-            FloatPosition::CursorFixed => {
+            FloatPosition::CursorFixed { pivot } => {
                 let persistent_position = self.data.float_positions.entry(uid).or_default();
 
-                if let PersistentFloatPosition::CursorFixed { referenced, .. } = persistent_position
+                if let PersistentFloatPosition::CursorFixed {
+                    referenced,
+                    pivot: persistent_pivot,
+                    ..
+                } = persistent_position
                 {
                     *referenced = true;
+                    *persistent_pivot = pivot;
                 } else {
-                    let cursor_position = self.data.input_state.cursor_position();
+                    let cursor_position = self.data.input_state.last_cursor_position();
 
                     self.data.float_positions.insert(
                         uid,
@@ -1093,11 +3548,12 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
                             referenced: true,
                             x: cursor_position.x,
                             y: cursor_position.y,
+                            pivot,
                         },
                     );
                 }
             }
-            FloatPosition::Fixed { x, y } => {
+            FloatPosition::Fixed { x, y, pivot } => {
                 let parent_font_size = self.builder.parent_node().style.font_size().value();
 
                 self.data.float_positions.insert(
@@ -1106,6 +3562,7 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
                         referenced: true,
                         x: x.to_pixel(self.data.scale_factor, parent_font_size),
                         y: y.to_pixel(self.data.scale_factor, parent_font_size),
+                        pivot,
                     },
                 );
             }
@@ -1122,6 +3579,10 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         }
     }
 
+    /// Makes the UIDs of all nodes inserted by `contents` relative to `uid`.
+    ///
+    /// For list items, prefer [`keyed`](Self::keyed) so their state follows the item rather than
+    /// its position in the list.
     pub fn uid_scope<R>(
         &mut self,
         uid: Uid,
@@ -1134,6 +3595,130 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         result
     }
 
+    /// Scopes the UIDs of `contents` by `name` alone instead of by the scopes around it, so
+    /// persistent state like scroll offsets or editor contents survives wrapping the section in
+    /// another panel or moving it into a tab. Stable scopes inside of each other are scoped by
+    /// the names of all of them.
+    ///
+    /// The trade-off is that unrelated call sites using the same name share their UIDs and their
+    /// state, so names have to be unique across the whole GUI. Using the same name twice during
+    /// the same frame is reported as a diagnostic.
+    #[track_caller]
+    pub fn stable_scope<R>(
+        &mut self,
+        name: &str,
+        contents: impl FnOnce(&mut ByorGuiContext<'_, Renderer>) -> R,
+    ) -> R {
+        let namespace = self
+            .data
+            .stable_scope_stack
+            .last()
+            .copied()
+            .unwrap_or_else(Uid::from_type::<StableScope>)
+            .concat(Uid::from_slice(name.as_bytes()));
+        if self.data.stable_scopes.insert(namespace, ()).is_some() {
+            self.data.push_diagnostic(
+                Diagnostic::new(
+                    Severity::Warning,
+                    format!("duplicate stable scope `{name}`"),
+                )
+                .with_location(std::panic::Location::caller())
+                .with_uid(Some(namespace)),
+            );
+        }
+
+        self.data.uid_stack.push(namespace);
+        self.data.stable_scope_stack.push(namespace);
+        let result = contents(self);
+        self.data.stable_scope_stack.pop();
+        self.data.uid_stack.pop();
+        result
+    }
+
+    /// Scopes the UIDs of `contents` by a key identifying the item they show, typically the ID
+    /// of the item's data.
+    ///
+    /// Unlike scoping by the item's index, persistent state like scroll offsets or editor
+    /// contents stays with the item when items are reordered. Using the same key twice within
+    /// the same parent scope is reported as a diagnostic.
+    #[track_caller]
+    pub fn keyed<R>(
+        &mut self,
+        key: impl std::hash::Hash,
+        contents: impl FnOnce(&mut ByorGuiContext<'_, Renderer>) -> R,
+    ) -> R {
+        let uid = Uid::new(key);
+        if let Some(scoped_uid) = self.register_key(uid) {
+            self.data.push_diagnostic(
+                Diagnostic::new(Severity::Warning, "duplicate key")
+                    .with_location(std::panic::Location::caller())
+                    .with_uid(Some(scoped_uid)),
+            );
+        }
+
+        self.uid_scope(uid, contents)
+    }
+
+    /// Runs `contents` for every item, scoped by the key `key` derives from the item like with
+    /// [`keyed`](Self::keyed). Stops at the first error.
+    ///
+    /// Duplicate keys are reported as a diagnostic that names the key.
+    #[track_caller]
+    pub fn for_each_keyed<I, K, E>(
+        &mut self,
+        items: I,
+        mut key: impl FnMut(&I::Item) -> K,
+        mut contents: impl FnMut(&mut ByorGuiContext<'_, Renderer>, I::Item) -> Result<(), E>,
+    ) -> Result<(), E>
+    where
+        I: IntoIterator,
+        K: std::hash::Hash + std::fmt::Debug,
+    {
+        let location = std::panic::Location::caller();
+        for item in items {
+            let key = key(&item);
+            let uid = Uid::new(&key);
+            if let Some(scoped_uid) = self.register_key(uid) {
+                self.data.push_diagnostic(
+                    Diagnostic::new(Severity::Warning, format!("duplicate key `{key:?}`"))
+                        .with_location(location)
+                        .with_uid(Some(scoped_uid)),
+                );
+            }
+
+            self.uid_scope(uid, |gui| contents(gui, item))?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs `contents` for every index in `indices`, scoped by the index. Stops at the first
+    /// error.
+    ///
+    /// For items that can be reordered, prefer [`for_each_keyed`](Self::for_each_keyed).
+    pub fn with_index_scope<E>(
+        &mut self,
+        indices: Range<usize>,
+        mut contents: impl FnMut(&mut ByorGuiContext<'_, Renderer>, usize) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for index in indices {
+            self.uid_scope(Uid::new(index), |gui| contents(gui, index))?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the key of a keyed scope. Returns the scoped UID if the key was already used
+    /// within the same parent scope during this frame.
+    #[must_use]
+    fn register_key(&mut self, uid: Uid) -> Option<Uid> {
+        let scoped_uid = self.compute_recursive_uid(uid);
+        self.data
+            .keyed_uids
+            .insert(scoped_uid, ())
+            .map(|()| scoped_uid)
+    }
+
     #[track_caller]
     pub fn insert_node<Builder: GuiBuilder<Renderer>>(
         &mut self,
@@ -1142,11 +3727,12 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         contents: NodeContents<Renderer, Builder>,
     ) -> InsertNodeResult<Builder::Result> {
         let uid = uid.map(|uid| self.compute_recursive_uid(uid));
-        let context = self.insert_leaf_node(uid, style, false, contents.text, contents.renderer)?;
+        let (contents, builder) = contents.split_builder();
+        let context = self.insert_leaf_node(uid, style, false, contents)?;
 
         Ok(NodeResponse {
             input_state: context.parent_input_state,
-            result: contents.builder.build(context),
+            result: builder.build(context),
         })
     }
 
@@ -1160,12 +3746,23 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
     ) -> InsertNodeResult<Builder::Result> {
         let uid = self.compute_recursive_uid(uid);
         self.update_float_position(uid, position);
-        let context =
-            self.insert_leaf_node(Some(uid), style, true, contents.text, contents.renderer)?;
+        let (contents, builder) = contents.split_builder();
+        let focus_trap = contents.options.focus_trap;
+        let context = self.insert_leaf_node(Some(uid), style, true, contents)?;
+
+        if focus_trap {
+            context.data.show_focus_trap(uid);
+            context.data.focus_trap_stack.push(uid);
+        }
+        let input_state = context.parent_input_state;
+        let result = builder.build(context);
+        if focus_trap {
+            self.data.focus_trap_stack.pop();
+        }
 
         Ok(NodeResponse {
-            input_state: context.parent_input_state,
-            result: contents.builder.build(context),
+            input_state,
+            result,
         })
     }
 }
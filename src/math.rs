@@ -358,6 +358,12 @@ impl<U: Unit> Float<U> {
         Self::new(self.value.max(other.value))
     }
 
+    #[must_use]
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Self::new(self.value.abs())
+    }
+
     #[must_use]
     #[inline]
     pub const fn clamp(self, min: Self, max: Self) -> Self {
@@ -389,6 +395,22 @@ impl<U: Unit> Float<U> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<U: Unit> serde::Serialize for Float<U> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, U: Unit> serde::Deserialize<'de> for Float<U> {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f32::deserialize(deserializer).map(Self::new)
+    }
+}
+
 impl<U: Unit> Sum for Float<U> {
     #[inline]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
@@ -397,6 +419,11 @@ impl<U: Unit> Sum for Float<U> {
 }
 
 #[repr(C)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
 pub struct Vec2<U: Unit> {
     pub x: Float<U>,
     pub y: Float<U>,
@@ -1,5 +1,6 @@
 use std::alloc::{Layout, alloc, dealloc, realloc};
 use std::fmt;
+use std::ops::Range;
 use std::ptr::NonNull;
 use std::slice;
 
@@ -29,7 +30,7 @@ pub trait Tuple {
     fn get_mut<'a>(slices: Self::MutSlices<'a>, index: usize) -> Option<Self::RefMut<'a>>;
     unsafe fn realloc_ptrs(ptrs: &mut Self::Pointers, old_cap: usize, new_cap: usize);
     unsafe fn write_items(ptrs: Self::Pointers, items: Self, index: usize);
-    unsafe fn drop_items(ptrs: Self::Pointers, len: usize);
+    unsafe fn drop_items(ptrs: Self::Pointers, range: Range<usize>);
     unsafe fn dealloc_ptrs(ptrs: Self::Pointers, cap: usize);
 }
 
@@ -115,11 +116,11 @@ macro_rules! impl_tuple {
                 }
             }
 
-            unsafe fn drop_items(ptrs: Self::Pointers, len: usize) {
+            unsafe fn drop_items(ptrs: Self::Pointers, range: Range<usize>) {
                 let ($($ptr,)+) = ptrs;
 
                 $({
-                    for i in 0..len {
+                    for i in range.clone() {
                         unsafe {
                             $ptr.as_ptr().add(i).drop_in_place();
                         }
@@ -217,6 +218,11 @@ impl<T: Tuple> MultiVec<T> {
         self.len
     }
 
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.cap
+    }
+
     #[inline]
     pub fn as_slices(&self) -> T::Slices<'_> {
         unsafe { T::ptrs_as_slices(self.ptrs, self.len) }
@@ -237,13 +243,30 @@ impl<T: Tuple> MultiVec<T> {
         T::get_mut(self.as_mut_slices(), index)
     }
 
+    fn grow_to(&mut self, new_cap: usize) {
+        unsafe {
+            T::realloc_ptrs(&mut self.ptrs, self.cap, new_cap);
+        }
+        self.cap = new_cap;
+    }
+
+    /// Reserves capacity for at least `additional` more items.
+    pub fn reserve(&mut self, additional: usize) {
+        let required_cap = self.len.checked_add(additional).expect("capacity overflow");
+        if required_cap > self.cap {
+            let new_cap = self
+                .cap
+                .checked_mul(2)
+                .expect("capacity overflow")
+                .max(required_cap);
+            self.grow_to(new_cap);
+        }
+    }
+
     pub fn push(&mut self, items: T) {
         if self.len == self.cap {
             let new_cap = self.cap.checked_mul(2).expect("capacity overflow").max(4);
-            unsafe {
-                T::realloc_ptrs(&mut self.ptrs, self.cap, new_cap);
-            }
-            self.cap = new_cap;
+            self.grow_to(new_cap);
         }
 
         unsafe {
@@ -252,11 +275,21 @@ impl<T: Tuple> MultiVec<T> {
         self.len += 1;
     }
 
+    /// Removes all items while keeping the allocated capacity.
     pub fn clear(&mut self) {
-        unsafe {
-            T::drop_items(self.ptrs, self.len);
+        self.truncate(0);
+    }
+
+    /// Removes all items after the first `len`, keeping the allocated capacity.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            let old_len = self.len;
+            // like `Vec`, set the length first so a panicking drop can't cause a double drop
+            self.len = len;
+            unsafe {
+                T::drop_items(self.ptrs, len..old_len);
+            }
         }
-        self.len = 0;
     }
 }
 
@@ -264,7 +297,7 @@ impl<T: Tuple> Drop for MultiVec<T> {
     fn drop(&mut self) {
         if self.cap > 0 {
             unsafe {
-                T::drop_items(self.ptrs, self.len);
+                T::drop_items(self.ptrs, 0..self.len);
                 T::dealloc_ptrs(self.ptrs, self.cap);
             }
         }
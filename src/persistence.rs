@@ -0,0 +1,101 @@
+use crate::*;
+use std::collections::BTreeMap;
+
+/// The persistent state of a node that survives restarts of the application.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedNodeState {
+    /// The horizontal and vertical scroll offset in pixels.
+    pub scroll: Option<(f32, f32)>,
+    /// Whether a popup is open.
+    pub open: Option<bool>,
+    /// The position of a node floating at a fixed cursor position.
+    pub float_position: Option<(f32, f32)>,
+}
+
+impl SavedNodeState {
+    #[must_use]
+    #[inline]
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The well-known parts of the persistent state of a GUI, keyed by raw UID values.
+///
+/// Created by [`ByorGui::export_persistent`] and restored with [`ByorGui::import_persistent`].
+/// Values stored under [`PersistentStateKey::Custom`] and widget internals like text editors are
+/// not included. UIDs created with [`Uid::from_type`] are only stable for the same build of the
+/// program, so their state may not be restored by a different build.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedUiState {
+    pub nodes: BTreeMap<u64, SavedNodeState>,
+}
+
+impl<Renderer: rendering::Renderer> ByorGui<Renderer> {
+    #[must_use]
+    pub fn export_persistent(&self) -> SavedUiState {
+        let mut nodes = BTreeMap::<u64, SavedNodeState>::new();
+
+        for (uid, state) in self.data.persistent_state.iter() {
+            let scroll_offset = |key| state.get::<Float<Pixel>>(key).copied();
+            let horizontal_scroll = scroll_offset(PersistentStateKey::HorizontalScroll);
+            let vertical_scroll = scroll_offset(PersistentStateKey::VerticalScroll);
+
+            let saved = SavedNodeState {
+                scroll: (horizontal_scroll.is_some() || vertical_scroll.is_some()).then(|| {
+                    (
+                        horizontal_scroll.unwrap_or_default().value(),
+                        vertical_scroll.unwrap_or_default().value(),
+                    )
+                }),
+                open: state.get::<bool>(PersistentStateKey::PopupOpen).copied(),
+                float_position: None,
+            };
+            if !saved.is_empty() {
+                nodes.insert(uid.0.get(), saved);
+            }
+        }
+
+        for (uid, position) in self.data.float_positions.iter() {
+            if let &PersistentFloatPosition::CursorFixed { x, y, .. } = position {
+                nodes.entry(uid.0.get()).or_default().float_position = Some((x.value(), y.value()));
+            }
+        }
+
+        SavedUiState { nodes }
+    }
+
+    /// Restores state saved with [`export_persistent`](Self::export_persistent), typically
+    /// before the first frame is built. Existing values for the same nodes are replaced.
+    pub fn import_persistent(&mut self, saved: &SavedUiState) {
+        for (&raw_uid, saved) in &saved.nodes {
+            let Some(uid) = NonZeroU64::new(raw_uid).map(Uid) else {
+                continue;
+            };
+
+            let state = self.data.persistent_state.entry(uid).or_default();
+            if let Some((horizontal_scroll, vertical_scroll)) = saved.scroll {
+                state.insert(PersistentStateKey::HorizontalScroll, horizontal_scroll.px());
+                state.insert(PersistentStateKey::VerticalScroll, vertical_scroll.px());
+            }
+            if let Some(open) = saved.open {
+                state.insert(PersistentStateKey::PopupOpen, open);
+            }
+
+            if let Some((x, y)) = saved.float_position {
+                self.data.float_positions.insert(
+                    uid,
+                    PersistentFloatPosition::CursorFixed {
+                        // kept until the end of the first frame, which references it if it is used
+                        referenced: true,
+                        x: x.px(),
+                        y: y.px(),
+                        pivot: Alignment2D::default(),
+                    },
+                );
+            }
+        }
+    }
+}
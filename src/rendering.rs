@@ -1,4 +1,8 @@
+pub mod display_list;
+
 use super::*;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 pub trait InlineBoxRenderer {
@@ -39,6 +43,200 @@ impl<R: Renderer> InlineBoxRenderer for UnimplementedBoxRenderer<R> {
     }
 }
 
+/// A segment of a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    /// Starts a new subpath.
+    MoveTo(Vec2<Pixel>),
+    LineTo(Vec2<Pixel>),
+    QuadTo {
+        control: Vec2<Pixel>,
+        end: Vec2<Pixel>,
+    },
+    CubicTo {
+        control1: Vec2<Pixel>,
+        control2: Vec2<Pixel>,
+        end: Vec2<Pixel>,
+    },
+    /// Closes the current subpath with a line back to its start.
+    Close,
+}
+
+/// A sequence of lines and curves, built from [`PathSegment`]s.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    /// The number of line segments a curve gets split into by the default implementations of
+    /// [`Renderer::fill_path`] and [`Renderer::stroke_path`].
+    const CURVE_SUBDIVISIONS: u32 = 16;
+
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    #[inline]
+    pub fn move_to(&mut self, point: Vec2<Pixel>) -> &mut Self {
+        self.segments.push(PathSegment::MoveTo(point));
+        self
+    }
+
+    #[inline]
+    pub fn line_to(&mut self, point: Vec2<Pixel>) -> &mut Self {
+        self.segments.push(PathSegment::LineTo(point));
+        self
+    }
+
+    #[inline]
+    pub fn quad_to(&mut self, control: Vec2<Pixel>, end: Vec2<Pixel>) -> &mut Self {
+        self.segments.push(PathSegment::QuadTo { control, end });
+        self
+    }
+
+    #[inline]
+    pub fn cubic_to(
+        &mut self,
+        control1: Vec2<Pixel>,
+        control2: Vec2<Pixel>,
+        end: Vec2<Pixel>,
+    ) -> &mut Self {
+        self.segments.push(PathSegment::CubicTo {
+            control1,
+            control2,
+            end,
+        });
+        self
+    }
+
+    #[inline]
+    pub fn close(&mut self) -> &mut Self {
+        self.segments.push(PathSegment::Close);
+        self
+    }
+
+    /// Starts a new subpath with a circular arc, approximated by cubic curves.
+    ///
+    /// Angles are in radians and grow clockwise from the positive X axis, since Y points down.
+    /// A negative `sweep` draws the arc counterclockwise.
+    pub fn arc(
+        &mut self,
+        center: Vec2<Pixel>,
+        radius: Float<Pixel>,
+        start_angle: f32,
+        sweep: f32,
+    ) -> &mut Self {
+        let point_at = |angle: f32| {
+            center
+                + Vec2 {
+                    x: radius * angle.cos(),
+                    y: radius * angle.sin(),
+                }
+        };
+        let tangent_at = |angle: f32| Vec2 {
+            x: radius * -angle.sin(),
+            y: radius * angle.cos(),
+        };
+
+        self.move_to(point_at(start_angle));
+
+        // a quarter circle per curve keeps the error well below a pixel for any sane radius
+        let curve_count = (sweep.abs() / std::f32::consts::FRAC_PI_2).ceil().max(1.0);
+        let curve_sweep = sweep / curve_count;
+        let handle_length = (4.0 / 3.0) * (curve_sweep / 4.0).tan();
+        for i in 0..(curve_count as u32) {
+            let from = start_angle + curve_sweep * (i as f32);
+            let to = from + curve_sweep;
+            self.cubic_to(
+                point_at(from) + tangent_at(from) * handle_length,
+                point_at(to) - tangent_at(to) * handle_length,
+                point_at(to),
+            );
+        }
+
+        self
+    }
+
+    /// Approximates every subpath with straight lines, returning its vertices and whether it is
+    /// closed.
+    #[must_use]
+    pub fn flatten(&self) -> Vec<(Vec<Vec2<Pixel>>, bool)> {
+        let mut subpaths = Vec::new();
+        let mut vertices: Vec<Vec2<Pixel>> = Vec::new();
+
+        let mut finish_subpath = |vertices: &mut Vec<Vec2<Pixel>>, closed: bool| {
+            if vertices.len() > 1 {
+                subpaths.push((std::mem::take(vertices), closed));
+            } else {
+                vertices.clear();
+            }
+        };
+
+        for &segment in &self.segments {
+            let current = vertices.last().copied().unwrap_or(Vec2::ZERO);
+            match segment {
+                PathSegment::MoveTo(point) => {
+                    finish_subpath(&mut vertices, false);
+                    vertices.push(point);
+                }
+                PathSegment::LineTo(point) => {
+                    if vertices.is_empty() {
+                        vertices.push(current);
+                    }
+                    vertices.push(point);
+                }
+                PathSegment::QuadTo { control, end } => {
+                    if vertices.is_empty() {
+                        vertices.push(current);
+                    }
+                    vertices.extend((1..=Self::CURVE_SUBDIVISIONS).map(|i| {
+                        let t = (i as f32) / (Self::CURVE_SUBDIVISIONS as f32);
+                        let u = 1.0 - t;
+                        current * (u * u) + control * (2.0 * u * t) + end * (t * t)
+                    }));
+                }
+                PathSegment::CubicTo {
+                    control1,
+                    control2,
+                    end,
+                } => {
+                    if vertices.is_empty() {
+                        vertices.push(current);
+                    }
+                    vertices.extend((1..=Self::CURVE_SUBDIVISIONS).map(|i| {
+                        let t = (i as f32) / (Self::CURVE_SUBDIVISIONS as f32);
+                        let u = 1.0 - t;
+                        current * (u * u * u)
+                            + control1 * (3.0 * u * u * t)
+                            + control2 * (3.0 * u * t * t)
+                            + end * (t * t * t)
+                    }));
+                }
+                PathSegment::Close => {
+                    let start = vertices.first().copied();
+                    finish_subpath(&mut vertices, true);
+                    // a new subpath implicitly starts where the closed one started
+                    vertices.extend(start);
+                }
+            }
+        }
+        finish_subpath(&mut vertices, false);
+
+        subpaths
+    }
+}
+
 pub trait Renderer: 'static {
     type Error;
 
@@ -67,6 +265,48 @@ pub trait Renderer: 'static {
         brush: ComputedBrush,
     ) -> Result<(), Self::Error>;
 
+    /// Fills many rects of the same brush at once, like the rects of a text selection spanning
+    /// many lines, given as pairs of position and size.
+    ///
+    /// The default implementation fills every rect with [`fill_rect`](Self::fill_rect).
+    /// Renderers with a per-draw overhead should submit them as a single shape.
+    fn fill_rects(
+        &mut self,
+        rects: &[(Vec2<Pixel>, Vec2<Pixel>)],
+        corner_radius: Float<Pixel>,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        for &(position, size) in rects {
+            self.fill_rect(position, size, corner_radius, brush.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the renderer implements [`blur_region`](Self::blur_region).
+    ///
+    /// If not, nodes with a backdrop blur get a more opaque background instead.
+    #[must_use]
+    #[inline]
+    fn supports_backdrop_blur(&self) -> bool {
+        false
+    }
+
+    /// Blurs everything drawn so far inside of the rounded rect, with a blur radius of `radius`.
+    ///
+    /// This is only called if [`supports_backdrop_blur`](Self::supports_backdrop_blur) returns
+    /// `true`.
+    fn blur_region(
+        &mut self,
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+        radius: Float<Pixel>,
+        corner_radius: Float<Pixel>,
+    ) -> Result<(), Self::Error> {
+        let _ = (position, size, radius, corner_radius);
+        Ok(())
+    }
+
     fn draw_poly(
         &mut self,
         vertices: &[Vec2<Pixel>],
@@ -80,12 +320,137 @@ pub trait Renderer: 'static {
         brush: ComputedBrush,
     ) -> Result<(), Self::Error>;
 
+    /// Draws a straight line with butt caps.
+    ///
+    /// The default implementation fills the line's outline as a polygon.
+    fn stroke_line(
+        &mut self,
+        from: Vec2<Pixel>,
+        to: Vec2<Pixel>,
+        width: Float<Pixel>,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        let delta = to - from;
+        let length = delta.x.value().hypot(delta.y.value());
+        if length == 0.0 {
+            return Ok(());
+        }
+
+        let normal = Vec2 {
+            x: -delta.y,
+            y: delta.x,
+        } * (width.value() / (2.0 * length));
+        self.fill_poly(
+            &[from + normal, to + normal, to - normal, from - normal],
+            brush,
+        )
+    }
+
+    /// Draws the outline of a polygon, or an open polyline if `closed` is false.
+    ///
+    /// The default implementation draws every edge with [`stroke_line`](Self::stroke_line), so
+    /// the corners aren't joined.
+    fn stroke_poly(
+        &mut self,
+        vertices: &[Vec2<Pixel>],
+        width: Float<Pixel>,
+        closed: bool,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        for edge in vertices.windows(2) {
+            self.stroke_line(edge[0], edge[1], width, brush.clone())?;
+        }
+
+        if closed && let [first, .., last] = vertices {
+            self.stroke_line(*last, *first, width, brush)?;
+        }
+
+        Ok(())
+    }
+
+    /// The default implementation fills every subpath of the [flattened](Path::flatten) path
+    /// separately.
+    fn fill_path(&mut self, path: &Path, brush: ComputedBrush) -> Result<(), Self::Error> {
+        for (vertices, _) in path.flatten() {
+            self.fill_poly(&vertices, brush.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// The default implementation strokes the [flattened](Path::flatten) path with
+    /// [`stroke_poly`](Self::stroke_poly).
+    fn stroke_path(
+        &mut self,
+        path: &Path,
+        width: Float<Pixel>,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        for (vertices, closed) in path.flatten() {
+            self.stroke_poly(&vertices, width, closed, brush.clone())?;
+        }
+
+        Ok(())
+    }
+
     fn draw_text(
         &mut self,
         text: parley::GlyphRun<'_, Color>,
         position: Vec2<Pixel>,
     ) -> Result<(), Self::Error>;
 
+    /// Draws a glyph run like [`draw_text`](Self::draw_text), but in `color` instead of the
+    /// run's own brush.
+    ///
+    /// The default implementation ignores `color`.
+    fn draw_text_with_color(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        let _ = color;
+        self.draw_text(text, position)
+    }
+
+    /// Strokes the outlines of the glyphs in a run, so the text stays legible when it is drawn
+    /// on top. `width` is how far the outline reaches beyond the glyphs.
+    ///
+    /// Runs of color fonts, which draw their glyphs with color layers or bitmaps (`COLR`, `CBDT`
+    /// or `sbix` tables, like most emoji fonts), can't be stroked and are left without an outline
+    /// by the renderers of this crate.
+    ///
+    /// The default implementation draws nothing.
+    fn stroke_text(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        width: Float<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        let _ = (text, position, width, color);
+        Ok(())
+    }
+
+    /// Strokes the outlines of all glyphs of a layout, see [`stroke_text`](Self::stroke_text).
+    fn stroke_text_layout(
+        &mut self,
+        layout: &parley::Layout<Color>,
+        position: Vec2<Pixel>,
+        width: Float<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        for line in layout.lines() {
+            for item in line.items() {
+                if let parley::PositionedLayoutItem::GlyphRun(text) = item {
+                    self.stroke_text(text, position, width, color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn draw_text_layout<B>(
         &mut self,
         layout: &parley::Layout<Color>,
@@ -123,13 +488,121 @@ pub trait Renderer: 'static {
 pub struct RenderContext<'a, R: Renderer> {
     pub position: Vec2<Pixel>,
     pub size: Vec2<Pixel>,
+    /// The position of the clip rect in effect, that is the node's own clip bounds intersected
+    /// with the ones of all its ancestors.
+    pub clip_position: Vec2<Pixel>,
+    pub clip_size: Vec2<Pixel>,
     pub style: &'a ComputedStyle,
     pub scale_factor: f32,
     pub input_state: NodeInputState,
     pub persistent_state: &'a PersistentState,
     pub renderer: &'a mut R,
+    pub(crate) tag: Option<&'a (dyn Any + Send)>,
+    pub(crate) theme: &'a Theme,
+}
+
+impl<R: Renderer> RenderContext<'_, R> {
+    #[must_use]
+    #[inline]
+    pub fn clip_rect(&self) -> (Vec2<Pixel>, Vec2<Pixel>) {
+        (self.clip_position, self.clip_size)
+    }
+
+    /// Whether the node is completely outside the clip rect, so nothing drawn inside its bounds
+    /// would be visible.
+    #[must_use]
+    #[inline]
+    pub fn is_culled(&self) -> bool {
+        let (_, visible_size) = intersect_rects(
+            (self.position, self.size),
+            (self.clip_position, self.clip_size),
+        );
+        (visible_size.x <= 0.px()) || (visible_size.y <= 0.px())
+    }
+
+    /// The tag the node was given with [`NodeContents::with_tag`], if it is of type `T`.
+    #[must_use]
+    #[inline]
+    pub fn tag<T: Any>(&self) -> Option<&T> {
+        self.tag?.downcast_ref()
+    }
+
+    /// The theme the frame was built with, for example to look up
+    /// [color tokens](Theme::color_token).
+    #[must_use]
+    #[inline]
+    pub fn theme(&self) -> &Theme {
+        self.theme
+    }
+}
+
+#[must_use]
+fn intersect_rects(
+    (a_position, a_size): (Vec2<Pixel>, Vec2<Pixel>),
+    (b_position, b_size): (Vec2<Pixel>, Vec2<Pixel>),
+) -> (Vec2<Pixel>, Vec2<Pixel>) {
+    let min = a_position.max(b_position);
+    let max = (a_position + a_size).min(b_position + b_size);
+    (min, (max - min).max(Vec2::ZERO))
+}
+
+/// Whether the font draws its glyphs with color layers or embedded bitmaps, like most emoji fonts.
+/// Those glyphs ignore stroke styles, so [`Renderer::stroke_text`] skips their runs.
+#[must_use]
+pub(crate) fn is_color_font(font: &parley::FontData) -> bool {
+    use parley::swash::{FontRef, tag_from_bytes};
+
+    let Some(font) = FontRef::from_index(font.data.data(), font.index as usize) else {
+        return false;
+    };
+
+    [b"COLR", b"CBDT", b"sbix"]
+        .into_iter()
+        .any(|tag| font.table(tag_from_bytes(tag)).is_some())
+}
+
+/// The rects of a text selection offset by `position`, for [`Renderer::fill_rects`].
+///
+/// Vertically adjacent rects with the same horizontal extent are merged, so fully selected lines
+/// of a block of equally long lines become a single rect.
+#[must_use]
+pub(crate) fn selection_rects(
+    geometry: impl IntoIterator<Item = parley::BoundingBox>,
+    position: Vec2<Pixel>,
+) -> Vec<(Vec2<Pixel>, Vec2<Pixel>)> {
+    let mut rects: Vec<(Vec2<Pixel>, Vec2<Pixel>)> = Vec::new();
+    for rect in geometry {
+        let min = Vec2 {
+            x: rect.x0.px(),
+            y: rect.y0.px(),
+        };
+        let max = Vec2 {
+            x: rect.x1.px(),
+            y: rect.y1.px(),
+        };
+        let (rect_position, rect_size) = (position + min, max - min);
+
+        if let Some((last_position, last_size)) = rects.last_mut()
+            && (last_position.x == rect_position.x)
+            && (last_size.x == rect_size.x)
+            && (last_position.y + last_size.y == rect_position.y)
+        {
+            last_size.y += rect_size.y;
+        } else {
+            rects.push((rect_position, rect_size));
+        }
+    }
+    rects
 }
 
+/// Draws the contents of a node inserted with [`NodeContents::renderer`] or
+/// [`NodeContents::with_renderer`].
+///
+/// The renderer is created every time the node is inserted and dropped when the next frame starts,
+/// so it should be cheap to create and only hold what it needs to draw a single frame. This fits
+/// most custom drawing, like the widgets of this crate that draw a few shapes from their style.
+/// Renderers that keep expensive resources between frames, like tessellated geometry or uploaded
+/// textures, implement [`RetainedNodeRenderer`] instead.
 pub trait NodeRenderer: Send + 'static {
     type Renderer: Renderer;
 
@@ -139,6 +612,196 @@ pub trait NodeRenderer: Send + 'static {
     ) -> Result<(), <Self::Renderer as Renderer>::Error>;
 }
 
+/// Draws the contents of a node inserted with [`NodeContents::with_retained_renderer`], and keeps
+/// its state for as long as the node exists.
+///
+/// The renderer is stored by the UID of its node. In every frame the node is inserted with a new
+/// renderer, which is passed to the stored one with [`update`](Self::update). The stored renderer
+/// is dropped once a frame is built without the node, or when the node gets a renderer of another
+/// type.
+pub trait RetainedNodeRenderer: Send + 'static {
+    type Renderer: Renderer;
+
+    /// Takes over the data of the renderer the node was inserted with this frame. Replaces this
+    /// renderer by default, implementations keep the caches that are still valid for `next`.
+    #[inline]
+    fn update(&mut self, next: Self)
+    where
+        Self: Sized,
+    {
+        *self = next;
+    }
+
+    /// Called before the first [`render`](Self::render), and again whenever the size, the style
+    /// or the scale factor of the node changed since the last call.
+    #[inline]
+    fn prepare(&mut self, context: PrepareContext<'_>) {
+        let _ = context;
+    }
+
+    fn render(
+        &mut self,
+        context: RenderContext<'_, Self::Renderer>,
+    ) -> Result<(), <Self::Renderer as Renderer>::Error>;
+}
+
+pub struct PrepareContext<'a> {
+    pub size: Vec2<Pixel>,
+    pub style: &'a ComputedStyle,
+    pub scale_factor: f32,
+    pub(crate) theme: &'a Theme,
+}
+
+impl PrepareContext<'_> {
+    #[must_use]
+    #[inline]
+    pub fn theme(&self) -> &Theme {
+        self.theme
+    }
+}
+
+/// A [`RetainedNodeRenderer`] with its concrete type erased.
+pub(crate) trait ErasedRetainedRenderer<R: Renderer>: Send {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Updates this renderer with `next` and returns whether they are of the same type.
+    fn update_from(&mut self, next: &mut dyn Any) -> bool;
+
+    fn prepare(&mut self, context: PrepareContext<'_>);
+
+    fn render(&mut self, context: RenderContext<'_, R>) -> Result<(), R::Error>;
+}
+
+/// Lets the renderer be moved out of an erased `next` renderer.
+pub(crate) struct RetainedSlot<T>(pub(crate) Option<T>);
+
+impl<T: RetainedNodeRenderer> ErasedRetainedRenderer<T::Renderer> for RetainedSlot<T> {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn update_from(&mut self, next: &mut dyn Any) -> bool {
+        let Some(next) = next.downcast_mut::<Self>() else {
+            return false;
+        };
+
+        if let (Some(renderer), Some(next)) = (&mut self.0, next.0.take()) {
+            renderer.update(next);
+        }
+        true
+    }
+
+    fn prepare(&mut self, context: PrepareContext<'_>) {
+        if let Some(renderer) = &mut self.0 {
+            renderer.prepare(context);
+        }
+    }
+
+    fn render(
+        &mut self,
+        context: RenderContext<'_, T::Renderer>,
+    ) -> Result<(), <T::Renderer as Renderer>::Error> {
+        match &mut self.0 {
+            Some(renderer) => renderer.render(context),
+            None => Ok(()),
+        }
+    }
+}
+
+pub(crate) struct RetainedRendererEntry<R: Renderer> {
+    renderer: RetainedRendererStorage<R>,
+    /// Identifies the size, style and scale factor the renderer was last prepared for
+    prepared_for: Option<u64>,
+}
+
+impl<R: Renderer> RetainedRendererEntry<R> {
+    #[cfg(feature = "profiling")]
+    #[must_use]
+    #[inline]
+    pub(crate) fn is_heap(&self) -> bool {
+        self.renderer.is_heap()
+    }
+}
+
+/// Stores `next` as the retained renderer of `uid`, or updates the stored one with it.
+pub(crate) fn update_retained_renderer<R: Renderer>(
+    renderers: &mut IntMap<Uid, RetainedRendererEntry<R>>,
+    uid: Uid,
+    mut next: RetainedRendererStorage<R>,
+) {
+    if let Some(entry) = renderers.get_mut(uid)
+        && entry.renderer.update_from(next.as_any_mut())
+    {
+        return;
+    }
+
+    renderers.insert(
+        uid,
+        RetainedRendererEntry {
+            renderer: next,
+            prepared_for: None,
+        },
+    );
+}
+
+/// The part of a node that was being rendered when an error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderPhase {
+    Shadow,
+    BackdropBlur,
+    Background,
+    Border,
+    Clip,
+    Custom,
+    Text,
+    DebugOverlay,
+}
+
+impl fmt::Display for RenderPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shadow => f.write_str("drop shadow"),
+            Self::BackdropBlur => f.write_str("backdrop blur"),
+            Self::Background => f.write_str("background"),
+            Self::Border => f.write_str("border"),
+            Self::Clip => f.write_str("clip rect"),
+            Self::Custom => f.write_str("custom renderer"),
+            Self::Text => f.write_str("text"),
+            Self::DebugOverlay => f.write_str("debug overlay"),
+        }
+    }
+}
+
+/// An error returned by the [`Renderer`], together with the node it occurred in.
+#[derive(Debug, Clone)]
+pub struct RenderError<E> {
+    pub source: E,
+    pub uid: Option<Uid>,
+    pub phase: RenderPhase,
+}
+
+impl<E: fmt::Display> fmt::Display for RenderError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to render the {}", self.phase)?;
+        if let Some(uid) = self.uid {
+            write!(f, " of node {uid:?}")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for RenderError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[inline]
+fn in_phase<E>(node: &Node, phase: RenderPhase) -> impl FnOnce(E) -> RenderError<E> {
+    let uid = node.uid;
+    move |source| RenderError { source, uid, phase }
+}
+
 fn draw_drop_shadow<R: Renderer>(node: &Node, renderer: &mut R) -> Result<(), R::Error> {
     const STOP_COUNT: usize = 8;
 
@@ -322,59 +985,124 @@ fn draw_drop_shadow<R: Renderer>(node: &Node, renderer: &mut R) -> Result<(), R:
     Ok(())
 }
 
-fn draw_tree<R: Renderer>(
-    tree: TreeRef<'_, Node, Shared>,
-    data: &ByorGuiData<R>,
-    scale_factor: f32,
+fn draw_node_background<R: Renderer>(
+    node: &Node,
     renderer: &mut R,
-) -> Result<(), R::Error> {
-    let TreeRef {
-        parent: node,
-        descendants,
-        ..
-    } = tree;
-
+) -> Result<(), RenderError<R::Error>> {
     if node.style.drop_shadow_width() > 0.px() {
-        draw_drop_shadow(node, renderer)?;
+        draw_drop_shadow(node, renderer).map_err(in_phase(node, RenderPhase::Shadow))?;
     }
 
-    renderer.fill_rect(
-        node.position,
-        node.style.fixed_size,
-        node.style.corner_radius(),
-        node.style.background().offset(node.position),
-    )?;
+    let mut background = node.style.background().offset(node.position);
+    if node.style.backdrop_blur() > 0.px() {
+        if renderer.supports_backdrop_blur() {
+            renderer
+                .blur_region(
+                    node.position,
+                    node.style.fixed_size,
+                    node.style.backdrop_blur(),
+                    node.style.corner_radius(),
+                )
+                .map_err(in_phase(node, RenderPhase::BackdropBlur))?;
+        } else if let ComputedBrush::Solid(color) = &mut background {
+            // without the blur, whatever is beneath would make the contents hard to read
+            color.a += (u8::MAX - color.a) / 2;
+        }
+    }
 
-    if node.style.border_width() > 0.px() {
-        renderer.draw_rect(
-            node.position + node.style.border_width() * 0.5,
-            node.style.fixed_size - node.style.border_width(),
+    renderer
+        .fill_rect(
+            node.position,
+            node.style.fixed_size,
             node.style.corner_radius(),
-            node.style.border_width(),
-            node.style.border_color(),
-        )?;
+            background,
+        )
+        .map_err(in_phase(node, RenderPhase::Background))?;
+
+    if node.style.border_width() > 0.px() {
+        renderer
+            .draw_rect(
+                node.position + node.style.border_width() * 0.5,
+                node.style.fixed_size - node.style.border_width(),
+                node.style.corner_radius(),
+                node.style.border_width(),
+                node.style.border_color(),
+            )
+            .map_err(in_phase(node, RenderPhase::Border))?;
     }
 
-    let (clip_position, clip_size) = node.clip_bounds();
-    renderer.push_clip_rect(clip_position, clip_size)?;
+    Ok(())
+}
+
+fn draw_node_contents<R: Renderer>(
+    node: &Node,
+    data: &ByorGuiData<R>,
+    retained_renderers: &mut IntMap<Uid, RetainedRendererEntry<R>>,
+    scale_factor: f32,
+    (clip_position, clip_size): (Vec2<Pixel>, Vec2<Pixel>),
+    renderer: &mut R,
+) -> Result<(), RenderError<R::Error>> {
+    let persistent_state = node
+        .uid
+        .and_then(|uid| data.persistent_state.get(uid))
+        .unwrap_or(&PersistentState::EMPTY);
 
     if let Some(node_renderer_id) = node.renderer.expand() {
-        let persistent_state = node
-            .uid
-            .and_then(|uid| data.persistent_state.get(uid))
-            .unwrap_or(&PersistentState::EMPTY);
+        let context = RenderContext {
+            position: node.position,
+            size: node.style.fixed_size,
+            clip_position,
+            clip_size,
+            style: &node.style,
+            scale_factor,
+            input_state: data.compute_node_input_state(node.uid),
+            persistent_state,
+            renderer,
+            tag: node.tag.expand().map(|id| &*data.tags[id]),
+            theme: &data.theme,
+        };
+
+        data.renderers[node_renderer_id]
+            .render(context)
+            .map_err(in_phase(node, RenderPhase::Custom))?;
+    }
+
+    if node.retained_renderer
+        && let Some(uid) = node.uid
+        && let Some(entry) = retained_renderers.get_mut(uid)
+    {
+        let mut hasher = rapidhash::fast::RapidHasher::default();
+        node.style.hash_relevant(&mut hasher);
+        scale_factor.to_bits().hash(&mut hasher);
+        let prepared_for = hasher.finish();
+        if entry.prepared_for != Some(prepared_for) {
+            entry.renderer.prepare(PrepareContext {
+                size: node.style.fixed_size,
+                style: &node.style,
+                scale_factor,
+                theme: &data.theme,
+            });
+            entry.prepared_for = Some(prepared_for);
+        }
 
         let context = RenderContext {
             position: node.position,
             size: node.style.fixed_size,
+            clip_position,
+            clip_size,
             style: &node.style,
             scale_factor,
             input_state: data.compute_node_input_state(node.uid),
             persistent_state,
             renderer,
+            tag: node.tag.expand().map(|id| &*data.tags[id]),
+            theme: &data.theme,
         };
 
-        data.renderers[node_renderer_id].render(context)?;
+        entry
+            .renderer
+            .render(context)
+            .map_err(in_phase(node, RenderPhase::Custom))?;
     }
 
     if let Some(text_layout_id) = node.text_layout.expand() {
@@ -384,11 +1112,213 @@ fn draw_tree<R: Renderer>(
             y: node.position.y + node.style.padding().top + node.vertical_text_offset,
         };
 
-        renderer.draw_text_layout(
-            text_layout,
-            text_position,
-            &mut UnimplementedBoxRenderer::default(),
+        for (range, color) in &data.text_backgrounds[text_layout_id] {
+            let selection = parley::Selection::new(
+                parley::Cursor::from_byte_index(
+                    text_layout,
+                    range.start,
+                    parley::Affinity::Downstream,
+                ),
+                parley::Cursor::from_byte_index(text_layout, range.end, parley::Affinity::Upstream),
+            );
+
+            let rects = selection_rects(
+                selection
+                    .geometry(text_layout)
+                    .into_iter()
+                    .map(|(rect, _)| rect),
+                text_position,
+            );
+            renderer
+                .fill_rects(&rects, 0.px(), (*color).into())
+                .map_err(in_phase(node, RenderPhase::Text))?;
+        }
+
+        let stroke_width = node.style.text_stroke_width();
+        let stroke_color = node.style.text_stroke_color();
+        if (stroke_width > 0.px()) && (stroke_color.a > 0) {
+            renderer
+                .stroke_text_layout(text_layout, text_position, stroke_width, stroke_color)
+                .map_err(in_phase(node, RenderPhase::Text))?;
+        }
+
+        renderer
+            .draw_text_layout(
+                text_layout,
+                text_position,
+                &mut UnimplementedBoxRenderer::default(),
+            )
+            .map_err(in_phase(node, RenderPhase::Text))?;
+    }
+
+    Ok(())
+}
+
+/// Collects errors instead of returning them, if rendering is lenient.
+struct RenderErrors<E> {
+    lenient: bool,
+    errors: Vec<RenderError<E>>,
+}
+
+impl<E> RenderErrors<E> {
+    fn handle(&mut self, result: Result<(), RenderError<E>>) -> Result<bool, RenderError<E>> {
+        match result {
+            Ok(()) => Ok(true),
+            Err(error) if self.lenient => {
+                self.errors.push(error);
+                Ok(false)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn draw_tree<R: Renderer>(
+    tree: TreeRef<'_, Node, Shared>,
+    data: &ByorGuiData<R>,
+    retained_renderers: &mut IntMap<Uid, RetainedRendererEntry<R>>,
+    scale_factor: f32,
+    parent_clip: Option<(Vec2<Pixel>, Vec2<Pixel>)>,
+    renderer: &mut R,
+    errors: &mut RenderErrors<R::Error>,
+) -> Result<(), RenderError<R::Error>> {
+    let TreeRef {
+        parent: node,
+        descendants,
+        ..
+    } = tree;
+
+    // children can't escape the clip rect, so neither the node nor its children would be visible
+    if let Some(parent_clip) = parent_clip {
+        let shadow_width = node.style.drop_shadow_width().max(0.px());
+        let bounds = (
+            node.position - shadow_width,
+            node.style.fixed_size + 2.0 * shadow_width,
+        );
+
+        let (_, visible_size) = intersect_rects(bounds, parent_clip);
+        if (visible_size.x <= 0.px()) || (visible_size.y <= 0.px()) {
+            return Ok(());
+        }
+    }
+
+    // a node that failed to render is skipped, but its children are still rendered
+    let background_drawn = errors.handle(draw_node_background(node, renderer))?;
+
+    let (clip_position, clip_size) = node.clip_bounds();
+    let clipped = renderer
+        .push_clip_rect(clip_position, clip_size)
+        .map_err(in_phase(node, RenderPhase::Clip));
+    if !errors.handle(clipped)? {
+        // rendering the children without clipping them would be wrong
+        return Ok(());
+    }
+
+    let clip = match parent_clip {
+        Some(parent_clip) => intersect_rects(parent_clip, (clip_position, clip_size)),
+        None => (clip_position, clip_size),
+    };
+
+    if background_drawn {
+        errors.handle(draw_node_contents(
+            node,
+            data,
+            retained_renderers,
+            scale_factor,
+            clip,
+            renderer,
+        ))?;
+    }
+
+    iter_subtrees!(descendants => |subtree| {
+        if subtree.is_root {
+            continue;
+        }
+
+        draw_tree(
+            subtree,
+            data,
+            retained_renderers,
+            scale_factor,
+            Some(clip),
+            renderer,
+            errors,
         )?;
+    });
+
+    let unclipped = renderer
+        .pop_clip_rect()
+        .map_err(in_phase(node, RenderPhase::Clip));
+    errors.handle(unclipped)?;
+    Ok(())
+}
+
+/// What the debug overlay draws on top of the GUI, see [`ByorGui::set_debug_overlay`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugOverlay {
+    #[default]
+    Off,
+    /// Outlines the bounds of every node
+    Bounds,
+    /// Additionally outlines the area inside the padding of every node
+    BoundsAndPadding,
+}
+
+impl DebugOverlay {
+    const BOUNDS_COLOR: Color = Color::rgb(255, 0, 255);
+    const PADDING_COLOR: Color = Color::rgb(0, 200, 255);
+
+    /// <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>D</kbd>
+    pub const DEFAULT_SHORTCUT: Shortcut = Shortcut {
+        modifiers: Modifiers::CONTROL.union(Modifiers::SHIFT),
+        key: Key::Character(smol_str::SmolStr::new_inline("d")),
+        location: None,
+    };
+
+    /// The mode the debug shortcut switches to from this one.
+    #[must_use]
+    #[inline]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Bounds,
+            Self::Bounds => Self::BoundsAndPadding,
+            Self::BoundsAndPadding => Self::Off,
+        }
+    }
+}
+
+fn draw_debug_overlay<R: Renderer>(
+    tree: TreeRef<'_, Node, Shared>,
+    overlay: DebugOverlay,
+    renderer: &mut R,
+) -> Result<(), RenderError<R::Error>> {
+    let TreeRef {
+        parent: node,
+        descendants,
+        ..
+    } = tree;
+
+    renderer
+        .draw_rect(
+            node.position,
+            node.style.fixed_size,
+            0.px(),
+            1.px(),
+            DebugOverlay::BOUNDS_COLOR,
+        )
+        .map_err(in_phase(node, RenderPhase::DebugOverlay))?;
+
+    if overlay == DebugOverlay::BoundsAndPadding {
+        let (content_position, content_size) = node.clip_bounds();
+        renderer
+            .draw_rect(
+                content_position,
+                content_size,
+                0.px(),
+                1.px(),
+                DebugOverlay::PADDING_COLOR,
+            )
+            .map_err(in_phase(node, RenderPhase::DebugOverlay))?;
     }
 
     iter_subtrees!(descendants => |subtree| {
@@ -396,20 +1326,128 @@ fn draw_tree<R: Renderer>(
             continue;
         }
 
-        draw_tree(subtree, data, scale_factor, renderer)?;
+        draw_debug_overlay(subtree, overlay, renderer)?;
     });
 
-    renderer.pop_clip_rect()?;
     Ok(())
 }
 
 impl<R: Renderer> ByorGui<R> {
-    pub fn render(&mut self, renderer: &mut R) -> Result<(), R::Error> {
-        let mut trees = self.forest.trees();
+    /// Whether nodes that fail to render are skipped instead of aborting rendering.
+    #[must_use]
+    #[inline]
+    pub fn lenient_rendering(&self) -> bool {
+        self.data.lenient_rendering
+    }
+
+    /// If enabled, a node that fails to render is skipped and a diagnostic is recorded, and the
+    /// remaining nodes are rendered as usual.
+    #[inline]
+    pub fn set_lenient_rendering(&mut self, lenient_rendering: bool) {
+        self.data.lenient_rendering = lenient_rendering;
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn debug_overlay(&self) -> DebugOverlay {
+        self.data.debug_overlay
+    }
+
+    #[inline]
+    pub fn set_debug_overlay(&mut self, debug_overlay: DebugOverlay) {
+        self.data.debug_overlay = debug_overlay;
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn debug_shortcut(&self) -> Option<&Shortcut> {
+        self.data.debug_shortcut.as_ref()
+    }
+
+    /// Sets the shortcut that cycles through the [`DebugOverlay`] modes. It is consumed at the
+    /// start of a frame, so widgets never see it.
+    ///
+    /// Debug builds default to [`DebugOverlay::DEFAULT_SHORTCUT`] and release builds to `None`.
+    /// To keep the overlay out of a build entirely, set `None` here and never call
+    /// [`set_debug_overlay`](Self::set_debug_overlay); the overlay then stays off.
+    #[inline]
+    pub fn set_debug_shortcut(&mut self, debug_shortcut: Option<Shortcut>) {
+        self.data.debug_shortcut = debug_shortcut;
+    }
+
+    fn render_trees(
+        &mut self,
+        primary_index: Option<usize>,
+        renderer: &mut R,
+    ) -> Result<(), RenderError<R::Error>> {
+        let mut errors = RenderErrors {
+            lenient: self.data.lenient_rendering,
+            errors: Vec::new(),
+        };
+
+        // taken out while rendering, since the nodes are drawn from shared references
+        let mut retained_renderers = std::mem::take(&mut self.data.retained_renderers);
+        let mut trees = match primary_index {
+            Some(primary_index) => self.forest.trees_of_primary(primary_index),
+            None => self.forest.trees(),
+        };
+        let mut result = Ok(());
         while let Some(tree) = trees.next() {
-            draw_tree(tree, &self.data, self.scale_factor(), renderer)?;
+            result = draw_tree(
+                tree,
+                &self.data,
+                &mut retained_renderers,
+                self.data.scale_factor,
+                None,
+                renderer,
+                &mut errors,
+            );
+            if result.is_err() {
+                break;
+            }
+        }
+        self.data.retained_renderers = retained_renderers;
+        result?;
+
+        let debug_overlay = self.data.debug_overlay;
+        if debug_overlay != DebugOverlay::Off {
+            let mut trees = match primary_index {
+                Some(primary_index) => self.forest.trees_of_primary(primary_index),
+                None => self.forest.trees(),
+            };
+            while let Some(tree) = trees.next() {
+                errors.handle(draw_debug_overlay(tree, debug_overlay, renderer))?;
+            }
+        }
+
+        for error in errors.errors {
+            self.data.push_diagnostic(
+                Diagnostic::new(
+                    Severity::Error,
+                    format!("failed to render the {} of a node", error.phase),
+                )
+                .with_uid(error.uid),
+            );
         }
 
         Ok(())
     }
+
+    pub fn render(&mut self, renderer: &mut R) -> Result<(), RenderError<R::Error>> {
+        self.render_trees(None, renderer)
+    }
+
+    /// Renders only the nodes of one viewport, see [`ByorGui::frame_multi`].
+    pub fn render_viewport(
+        &mut self,
+        viewport: ViewportId,
+        renderer: &mut R,
+    ) -> Result<(), RenderError<R::Error>> {
+        let Some(primary_index) = self.data.viewport_ids.iter().position(|&id| id == viewport)
+        else {
+            return Ok(());
+        };
+
+        self.render_trees(Some(primary_index), renderer)
+    }
 }
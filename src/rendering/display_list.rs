@@ -0,0 +1,433 @@
+//! A renderer that records draw calls instead of executing them.
+
+use super::*;
+use crate::style::computed::ComputedBrush;
+use crate::style::{Color, GradientStop};
+
+/// An owned copy of a [`ComputedBrush`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedBrush {
+    Solid(Color),
+    LinearGradient {
+        start: Vec2<Pixel>,
+        end: Vec2<Pixel>,
+        stops: Vec<GradientStop>,
+    },
+    RadialGradient {
+        center: Vec2<Pixel>,
+        radius: Vec2<Pixel>,
+        stops: Vec<GradientStop>,
+    },
+}
+
+impl From<ComputedBrush<'_>> for RecordedBrush {
+    fn from(brush: ComputedBrush<'_>) -> Self {
+        match brush {
+            ComputedBrush::Solid(color) => Self::Solid(color),
+            ComputedBrush::LinearGradient { start, end, stops } => Self::LinearGradient {
+                start,
+                end,
+                stops: stops.to_vec(),
+            },
+            ComputedBrush::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => Self::RadialGradient {
+                center,
+                radius,
+                stops: stops.to_vec(),
+            },
+        }
+    }
+}
+
+/// A single call to a [`Renderer`], see [`DisplayList`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    PushClipRect {
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+    },
+    PopClipRect,
+    DrawRect {
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+        corner_radius: Float<Pixel>,
+        stroke_width: Float<Pixel>,
+        color: Color,
+    },
+    FillRect {
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+        corner_radius: Float<Pixel>,
+        brush: RecordedBrush,
+    },
+    DrawPoly {
+        vertices: Vec<Vec2<Pixel>>,
+        stroke_width: Float<Pixel>,
+        color: Color,
+    },
+    FillPoly {
+        vertices: Vec<Vec2<Pixel>>,
+        brush: RecordedBrush,
+    },
+    /// A glyph run. Glyphs are recorded by their ID in the font, only the start of the run is
+    /// recorded as a position.
+    Text {
+        /// The start of the run on its baseline
+        position: Vec2<Pixel>,
+        advance: Float<Pixel>,
+        font_size: f32,
+        color: Color,
+        glyphs: Vec<u32>,
+    },
+    /// The outline of a glyph run, see [`Renderer::stroke_text`]. Runs of color fonts aren't
+    /// recorded, since they can't be stroked.
+    StrokeText {
+        /// The start of the run on its baseline
+        position: Vec2<Pixel>,
+        width: Float<Pixel>,
+        color: Color,
+        glyphs: Vec<u32>,
+    },
+}
+
+/// A [`Renderer`] that records every draw call as a [`DrawCommand`].
+///
+/// Its [`Display`](fmt::Display) implementation prints one command per line with positions
+/// rounded to hundredths of a pixel, so the output of a frame can be compared against a stored
+/// reference in snapshot tests. Primitives the renderer doesn't implement itself, like paths and
+/// lines, are recorded as the polygons the default implementations turn them into.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DisplayList {
+    commands: Vec<DrawCommand>,
+}
+
+impl DisplayList {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    fn record_text(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        color: Option<Color>,
+    ) {
+        let run = text.run();
+        self.commands.push(DrawCommand::Text {
+            position: position
+                + Vec2 {
+                    x: text.offset().px(),
+                    y: text.baseline().px(),
+                },
+            advance: text.advance().px(),
+            font_size: run.font_size(),
+            color: color.unwrap_or(text.style().brush),
+            glyphs: text.glyphs().map(|glyph| glyph.id).collect(),
+        });
+    }
+}
+
+impl Renderer for DisplayList {
+    type Error = std::convert::Infallible;
+
+    fn push_clip_rect(
+        &mut self,
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.commands
+            .push(DrawCommand::PushClipRect { position, size });
+        Ok(())
+    }
+
+    fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+        self.commands.push(DrawCommand::PopClipRect);
+        Ok(())
+    }
+
+    fn draw_rect(
+        &mut self,
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+        corner_radius: Float<Pixel>,
+        stroke_width: Float<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        self.commands.push(DrawCommand::DrawRect {
+            position,
+            size,
+            corner_radius,
+            stroke_width,
+            color,
+        });
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        position: Vec2<Pixel>,
+        size: Vec2<Pixel>,
+        corner_radius: Float<Pixel>,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        self.commands.push(DrawCommand::FillRect {
+            position,
+            size,
+            corner_radius,
+            brush: brush.into(),
+        });
+        Ok(())
+    }
+
+    fn draw_poly(
+        &mut self,
+        vertices: &[Vec2<Pixel>],
+        stroke_width: Float<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        self.commands.push(DrawCommand::DrawPoly {
+            vertices: vertices.to_vec(),
+            stroke_width,
+            color,
+        });
+        Ok(())
+    }
+
+    fn fill_poly(
+        &mut self,
+        vertices: &[Vec2<Pixel>],
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        self.commands.push(DrawCommand::FillPoly {
+            vertices: vertices.to_vec(),
+            brush: brush.into(),
+        });
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.record_text(text, position, None);
+        Ok(())
+    }
+
+    fn draw_text_with_color(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        self.record_text(text, position, Some(color));
+        Ok(())
+    }
+
+    fn stroke_text(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        width: Float<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        if !is_color_font(text.run().font()) {
+            self.commands.push(DrawCommand::StrokeText {
+                position: position
+                    + Vec2 {
+                        x: text.offset().px(),
+                        y: text.baseline().px(),
+                    },
+                width,
+                color,
+                glyphs: text.glyphs().map(|glyph| glyph.id).collect(),
+            });
+        }
+        Ok(())
+    }
+}
+
+struct Point(Vec2<Pixel>);
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2},{:.2}", self.0.x.value(), self.0.y.value())
+    }
+}
+
+struct Size(Vec2<Pixel>);
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}x{:.2}", self.0.x.value(), self.0.y.value())
+    }
+}
+
+struct Hex(Color);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Color { r, g, b, a } = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+struct Vertices<'a>(&'a [Vec2<Pixel>]);
+
+impl fmt::Display for Vertices<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, &vertex) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", Point(vertex))?;
+        }
+        f.write_str("]")
+    }
+}
+
+fn write_stops(f: &mut fmt::Formatter<'_>, stops: &[GradientStop]) -> fmt::Result {
+    for stop in stops {
+        write!(f, " {}@{:.2}", Hex(stop.color), stop.offset)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for RecordedBrush {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            &Self::Solid(color) => write!(f, "{}", Hex(color)),
+            Self::LinearGradient { start, end, stops } => {
+                write!(f, "linear({} {}", Point(*start), Point(*end))?;
+                write_stops(f, stops)?;
+                f.write_str(")")
+            }
+            Self::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => {
+                write!(f, "radial({} {}", Point(*center), Size(*radius))?;
+                write_stops(f, stops)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+impl fmt::Display for DrawCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            &Self::PushClipRect { position, size } => {
+                write!(f, "push_clip {} {}", Point(position), Size(size))
+            }
+            Self::PopClipRect => f.write_str("pop_clip"),
+            &Self::DrawRect {
+                position,
+                size,
+                corner_radius,
+                stroke_width,
+                color,
+            } => write!(
+                f,
+                "draw_rect {} {} radius {:.2} stroke {:.2} {}",
+                Point(position),
+                Size(size),
+                corner_radius.value(),
+                stroke_width.value(),
+                Hex(color),
+            ),
+            Self::FillRect {
+                position,
+                size,
+                corner_radius,
+                brush,
+            } => write!(
+                f,
+                "fill_rect {} {} radius {:.2} {brush}",
+                Point(*position),
+                Size(*size),
+                corner_radius.value(),
+            ),
+            Self::DrawPoly {
+                vertices,
+                stroke_width,
+                color,
+            } => write!(
+                f,
+                "draw_poly {} stroke {:.2} {}",
+                Vertices(vertices),
+                stroke_width.value(),
+                Hex(*color),
+            ),
+            Self::FillPoly { vertices, brush } => {
+                write!(f, "fill_poly {} {brush}", Vertices(vertices))
+            }
+            Self::Text {
+                position,
+                advance,
+                font_size,
+                color,
+                glyphs,
+            } => {
+                write!(
+                    f,
+                    "text {} advance {:.2} size {font_size:.2} {} glyphs",
+                    Point(*position),
+                    advance.value(),
+                    Hex(*color),
+                )?;
+                for glyph in glyphs {
+                    write!(f, " {glyph}")?;
+                }
+                Ok(())
+            }
+            Self::StrokeText {
+                position,
+                width,
+                color,
+                glyphs,
+            } => {
+                write!(
+                    f,
+                    "stroke_text {} width {:.2} {} glyphs",
+                    Point(*position),
+                    width.value(),
+                    Hex(*color),
+                )?;
+                for glyph in glyphs {
+                    write!(f, " {glyph}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for DisplayList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for command in &self.commands {
+            writeln!(f, "{command}")?;
+        }
+        Ok(())
+    }
+}
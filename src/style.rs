@@ -4,11 +4,14 @@ pub mod computed;
 use super::*;
 use byor_gui_procmacro::StyleBuilder;
 use modular_bitfield::prelude::*;
+use smol_str::SmolStr;
 use std::fmt;
 use std::ops::{Div, DivAssign, Mul, MulAssign};
 use std::sync::{Arc, LazyLock};
 
-pub use parley::{FontFamily, FontStack, FontStyle, FontWeight, FontWidth, GenericFamily};
+pub use parley::{
+    FontFamily, FontStack, FontStyle, FontWeight, FontWidth, GenericFamily, OverflowWrap,
+};
 pub use smallvec::{SmallVec, smallvec};
 
 macro_rules! def_measurement {
@@ -119,6 +122,17 @@ impl RelativeMeasurement {
     }
 }
 
+impl From<AbsoluteMeasurement> for RelativeMeasurement {
+    #[inline]
+    fn from(value: AbsoluteMeasurement) -> Self {
+        match value {
+            AbsoluteMeasurement::Pixel(value) => Self::Pixel(value),
+            AbsoluteMeasurement::Point(value) => Self::Point(value),
+            AbsoluteMeasurement::EM(value) => Self::EM(value),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum Sizing {
     #[default]
@@ -134,20 +148,66 @@ impl<T: Into<AbsoluteMeasurement>> From<T> for Sizing {
     }
 }
 
+/// A value for both axes, used by [`Style::with_size`], [`Style::with_min_size`],
+/// [`Style::with_max_size`] and [`Style::with_min_hit_size`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizePair<T> {
+    pub width: T,
+    pub height: T,
+}
+
+macro_rules! impl_size_pair_from {
+    ($t:ty) => {
+        impl<T: Into<$t>> From<T> for SizePair<$t> {
+            #[inline]
+            fn from(value: T) -> Self {
+                let value = value.into();
+
+                Self {
+                    width: value,
+                    height: value,
+                }
+            }
+        }
+
+        impl<T1, T2> From<(T1, T2)> for SizePair<$t>
+        where
+            T1: Into<$t>,
+            T2: Into<$t>,
+        {
+            #[inline]
+            fn from(value: (T1, T2)) -> Self {
+                Self {
+                    width: value.0.into(),
+                    height: value.1.into(),
+                }
+            }
+        }
+    };
+}
+
+impl_size_pair_from!(Sizing);
+impl_size_pair_from!(AbsoluteMeasurement);
+
+/// The space between the border of a node and its contents.
+///
+/// Percentages are relative to the size of the node itself, its width for the left and right
+/// side and its height for the top and bottom. They are resolved during layout, once that size
+/// is known, and count as zero while the size of a node that fits its content is measured.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Padding {
-    pub left: AbsoluteMeasurement,
-    pub right: AbsoluteMeasurement,
-    pub top: AbsoluteMeasurement,
-    pub bottom: AbsoluteMeasurement,
+    pub left: RelativeMeasurement,
+    pub right: RelativeMeasurement,
+    pub top: RelativeMeasurement,
+    pub bottom: RelativeMeasurement,
 }
 
 impl Padding {
     pub const ZERO: Self = Self {
-        left: AbsoluteMeasurement::Pixel(Float::px(0.0)),
-        right: AbsoluteMeasurement::Pixel(Float::px(0.0)),
-        top: AbsoluteMeasurement::Pixel(Float::px(0.0)),
-        bottom: AbsoluteMeasurement::Pixel(Float::px(0.0)),
+        left: RelativeMeasurement::Pixel(Float::px(0.0)),
+        right: RelativeMeasurement::Pixel(Float::px(0.0)),
+        top: RelativeMeasurement::Pixel(Float::px(0.0)),
+        bottom: RelativeMeasurement::Pixel(Float::px(0.0)),
     };
 }
 
@@ -158,7 +218,7 @@ impl Default for Padding {
     }
 }
 
-impl<T: Into<AbsoluteMeasurement>> From<T> for Padding {
+impl<T: Into<RelativeMeasurement>> From<T> for Padding {
     #[inline]
     fn from(value: T) -> Self {
         let value = value.into();
@@ -172,7 +232,7 @@ impl<T: Into<AbsoluteMeasurement>> From<T> for Padding {
     }
 }
 
-impl<T: Into<AbsoluteMeasurement>> From<[T; 2]> for Padding {
+impl<T: Into<RelativeMeasurement>> From<[T; 2]> for Padding {
     #[inline]
     fn from(value: [T; 2]) -> Self {
         let value = value.map(Into::into);
@@ -186,7 +246,7 @@ impl<T: Into<AbsoluteMeasurement>> From<[T; 2]> for Padding {
     }
 }
 
-impl<T: Into<AbsoluteMeasurement>> From<[T; 4]> for Padding {
+impl<T: Into<RelativeMeasurement>> From<[T; 4]> for Padding {
     #[inline]
     fn from(value: [T; 4]) -> Self {
         let value = value.map(Into::into);
@@ -202,8 +262,8 @@ impl<T: Into<AbsoluteMeasurement>> From<[T; 4]> for Padding {
 
 impl<T1, T2> From<(T1, T2)> for Padding
 where
-    T1: Into<AbsoluteMeasurement>,
-    T2: Into<AbsoluteMeasurement>,
+    T1: Into<RelativeMeasurement>,
+    T2: Into<RelativeMeasurement>,
 {
     #[inline]
     fn from(value: (T1, T2)) -> Self {
@@ -221,10 +281,10 @@ where
 
 impl<T1, T2, T3, T4> From<(T1, T2, T3, T4)> for Padding
 where
-    T1: Into<AbsoluteMeasurement>,
-    T2: Into<AbsoluteMeasurement>,
-    T3: Into<AbsoluteMeasurement>,
-    T4: Into<AbsoluteMeasurement>,
+    T1: Into<RelativeMeasurement>,
+    T2: Into<RelativeMeasurement>,
+    T3: Into<RelativeMeasurement>,
+    T4: Into<RelativeMeasurement>,
 {
     #[inline]
     fn from(value: (T1, T2, T3, T4)) -> Self {
@@ -258,6 +318,48 @@ pub enum Alignment {
     End,
 }
 
+/// Which point of a node is meant by a position, e.g. to center a floating node on a point
+/// without knowing its size, see [`FloatPosition`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alignment2D {
+    pub x: Alignment,
+    pub y: Alignment,
+}
+
+impl Alignment2D {
+    pub const TOP_LEFT: Self = Self::new(Alignment::Start, Alignment::Start);
+    pub const TOP: Self = Self::new(Alignment::Center, Alignment::Start);
+    pub const TOP_RIGHT: Self = Self::new(Alignment::End, Alignment::Start);
+    pub const LEFT: Self = Self::new(Alignment::Start, Alignment::Center);
+    pub const CENTER: Self = Self::new(Alignment::Center, Alignment::Center);
+    pub const RIGHT: Self = Self::new(Alignment::End, Alignment::Center);
+    pub const BOTTOM_LEFT: Self = Self::new(Alignment::Start, Alignment::End);
+    pub const BOTTOM: Self = Self::new(Alignment::Center, Alignment::End);
+    pub const BOTTOM_RIGHT: Self = Self::new(Alignment::End, Alignment::End);
+
+    #[must_use]
+    #[inline]
+    pub const fn new(x: Alignment, y: Alignment) -> Self {
+        Self { x, y }
+    }
+
+    /// The offset of the aligned point from the top left corner of a node of the given size.
+    #[must_use]
+    #[inline]
+    pub(crate) fn offset(self, size: Vec2<Pixel>) -> Vec2<Pixel> {
+        let offset = |alignment, size: Float<Pixel>| match alignment {
+            Alignment::Start => 0.px(),
+            Alignment::Center => (size / 2.0).round(),
+            Alignment::End => size,
+        };
+
+        Vec2 {
+            x: offset(self.x, size.x),
+            y: offset(self.y, size.y),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Specifier)]
 #[bits = 3]
 pub enum HorizontalTextAlignment {
@@ -296,6 +398,42 @@ impl From<HorizontalTextAlignment> for parley::Alignment {
     }
 }
 
+/// The box that is used to vertically center text inside of a node.
+///
+/// Only has an effect together with [`VerticalTextAlignment::Center`]. Labels of a [`Button`]
+/// default to [`CapHeight`](Self::CapHeight), all other text has to opt in.
+///
+/// [`Button`]: crate::widgets::Button
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Specifier)]
+#[bits = 1]
+pub enum VerticalTextAnchor {
+    /// The full layout box of the text, including line gaps.
+    #[default]
+    LayoutBox,
+    /// The box from the cap height of the first line to the baseline of the last line.
+    /// Falls back to the ascent if the font does not specify a cap height.
+    CapHeight,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Specifier)]
+#[bits = 1]
+pub enum UiDirection {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+/// The shape of the text caret.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Specifier)]
+#[bits = 1]
+pub enum CaretShape {
+    /// A vertical bar in front of the character, see [`Style::caret_width`].
+    #[default]
+    Bar,
+    /// A translucent block covering the character.
+    Block,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Specifier)]
 #[bits = 2]
 pub enum VerticalTextAlignment {
@@ -387,14 +525,39 @@ pub enum PopupPosition {
     AfterParent,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+impl PopupPosition {
+    /// The position on the opposite side of the parent.
+    #[must_use]
+    #[inline]
+    pub const fn mirrored(self) -> Self {
+        match self {
+            Self::BeforeParent => Self::AfterParent,
+            Self::ParentStart => Self::ParentEnd,
+            Self::ParentEnd => Self::ParentStart,
+            Self::AfterParent => Self::BeforeParent,
+        }
+    }
+}
+
+/// Where a floating node is shown.
+///
+/// The `pivot` of a variant chooses which point of the node lands on the position, so a node
+/// can be centered on a point without knowing its size in advance. Popup positions already take
+/// the size of the node into account.
+#[derive(Debug, Clone, Copy)]
 pub enum FloatPosition {
-    #[default]
-    Cursor,
-    CursorFixed,
+    /// Follows the cursor.
+    Cursor {
+        pivot: Alignment2D,
+    },
+    /// Stays where the cursor was when the node was first shown.
+    CursorFixed {
+        pivot: Alignment2D,
+    },
     Fixed {
         x: AbsoluteMeasurement,
         y: AbsoluteMeasurement,
+        pivot: Alignment2D,
     },
     Popup {
         x: PopupPosition,
@@ -402,22 +565,52 @@ pub enum FloatPosition {
     },
 }
 
+impl FloatPosition {
+    pub const CURSOR: Self = Self::Cursor {
+        pivot: Alignment2D::TOP_LEFT,
+    };
+    pub const CURSOR_FIXED: Self = Self::CursorFixed {
+        pivot: Alignment2D::TOP_LEFT,
+    };
+
+    /// Positions the top left corner of the node at `x` and `y`.
+    #[must_use]
+    #[inline]
+    pub fn fixed(x: impl Into<AbsoluteMeasurement>, y: impl Into<AbsoluteMeasurement>) -> Self {
+        Self::Fixed {
+            x: x.into(),
+            y: y.into(),
+            pivot: Alignment2D::TOP_LEFT,
+        }
+    }
+}
+
+impl Default for FloatPosition {
+    #[inline]
+    fn default() -> Self {
+        Self::CURSOR
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum PersistentFloatPosition {
     Cursor {
         referenced: bool,
         x: Float<Pixel>,
         y: Float<Pixel>,
+        pivot: Alignment2D,
     },
     CursorFixed {
         referenced: bool,
         x: Float<Pixel>,
         y: Float<Pixel>,
+        pivot: Alignment2D,
     },
     Fixed {
         referenced: bool,
         x: Float<Pixel>,
         y: Float<Pixel>,
+        pivot: Alignment2D,
     },
     Popup {
         referenced: bool,
@@ -455,12 +648,17 @@ impl Default for PersistentFloatPosition {
             referenced: true,
             x: 0.px(),
             y: 0.px(),
+            pivot: Alignment2D::TOP_LEFT,
         }
     }
 }
 
-pub type PropertyFn<T> =
-    fn(parent_style: &CascadedStyle, input_state: NodeInputState, enabled: bool) -> T;
+pub type PropertyFn<T> = fn(
+    parent_style: &CascadedStyle,
+    input_state: NodeInputState,
+    enabled: bool,
+    theme: &Theme,
+) -> T;
 
 #[derive(Debug, Default, Clone, Copy)]
 pub enum Property<T, const INHERIT_FALLBACK: bool> {
@@ -475,6 +673,9 @@ pub enum Property<T, const INHERIT_FALLBACK: bool> {
     Value(T),
     /// Compute the value using a custom function
     Compute(PropertyFn<T>),
+    /// A specific value that wins over instance styles when it is part of a theme style, see
+    /// [`Theme::insert_style_with_priority`]. Anywhere else it is the same as [`Value`](Self::Value).
+    Important(T),
 }
 
 impl<T: Clone, const INHERIT_FALLBACK: bool> Property<T, INHERIT_FALLBACK> {
@@ -496,6 +697,7 @@ impl<T: Clone, const INHERIT_FALLBACK: bool> Property<T, INHERIT_FALLBACK> {
         parent_style: &CascadedStyle,
         input_state: NodeInputState,
         enabled: bool,
+        theme: &Theme,
         initial_value: T, // Eventually this should become a const generic, if the type system allows it.
     ) -> T {
         match self {
@@ -505,8 +707,19 @@ impl<T: Clone, const INHERIT_FALLBACK: bool> Property<T, INHERIT_FALLBACK> {
             },
             Self::Initial => initial_value,
             Self::Inherit => parent_value.clone(),
-            Self::Value(value) => value,
-            Self::Compute(f) => f(parent_style, input_state, enabled),
+            Self::Value(value) | Self::Important(value) => value,
+            Self::Compute(f) => f(parent_style, input_state, enabled, theme),
+        }
+    }
+
+    /// Splits the property into its normal and its important part, with the important value
+    /// turned into a normal one.
+    #[must_use]
+    #[inline]
+    fn split_important(self) -> (Self, Self) {
+        match self {
+            Self::Important(value) => (Self::Unspecified, Self::Value(value)),
+            property => (property, Self::Unspecified),
         }
     }
 }
@@ -588,12 +801,45 @@ macro_rules! define_style {
                 }
             }
 
+            /// Splits the style into the properties that aren't [`Property::Important`] and the
+            /// ones that are, as normal values. The second style is `None` if no property is
+            /// important.
             #[must_use]
-            pub fn cascade_root(&self, screen_size: Vec2<Pixel>, input_state: NodeInputState) -> CascadedStyle {
+            pub(crate) fn split_important(&self) -> (Self, Option<Self>) {
+                let mut has_important = false;
+                let (enabled, important_enabled) = self.enabled.split_important();
+                has_important |= !matches!(important_enabled, Property::Unspecified);
+                $(
+                    let $property_name = self.$property_name.clone().split_important();
+                    has_important |= !matches!($property_name.1, Property::Unspecified);
+                )*
+
+                let normal = Self {
+                    enabled,
+                    $($property_name: $property_name.0,)*
+                };
+                let important = has_important.then(|| Self {
+                    enabled: important_enabled,
+                    $($property_name: $property_name.1,)*
+                });
+                (normal, important)
+            }
+
+            /// Turns every [`Property::Important`] into a normal value.
+            #[must_use]
+            pub(crate) fn without_importance(&self) -> Self {
+                match self.split_important() {
+                    (normal, Some(important)) => important.or_else(&normal),
+                    (normal, None) => normal,
+                }
+            }
+
+            #[must_use]
+            pub fn cascade_root(&self, screen_size: Vec2<Pixel>, input_state: NodeInputState, theme: &Theme) -> CascadedStyle {
                 let enabled = match &self.enabled {
                     Property::Unspecified | Property::Initial | Property::Inherit => INITIAL_ENABLED,
-                    &Property::Value(value) => value,
-                    Property::Compute(f) => f(&CascadedStyle::INITIAL, input_state, true),
+                    &Property::Value(value) | &Property::Important(value) => value,
+                    Property::Compute(f) => f(&CascadedStyle::INITIAL, input_state, true, theme),
                 };
 
                 let mut style = CascadedStyle {
@@ -601,8 +847,8 @@ macro_rules! define_style {
                     $(
                         $property_name: match &self.$property_name {
                             Property::Unspecified | Property::Initial | Property::Inherit => $initial_value,
-                            Property::Value(value) => value.clone(),
-                            Property::Compute(f) => f(&CascadedStyle::INITIAL, input_state, enabled),
+                            Property::Value(value) | Property::Important(value) => value.clone(),
+                            Property::Compute(f) => f(&CascadedStyle::INITIAL, input_state, enabled, theme),
                         },
                     )*
                 };
@@ -618,10 +864,10 @@ macro_rules! define_style {
             }
 
             #[must_use]
-            pub fn cascade(&self, parent_style: &CascadedStyle, input_state: NodeInputState) -> CascadedStyle {
+            pub fn cascade(&self, parent_style: &CascadedStyle, input_state: NodeInputState, theme: &Theme) -> CascadedStyle {
                 let enabled = self
                     .enabled
-                    .cascade(&parent_style.enabled, &parent_style, input_state, true, INITIAL_ENABLED);
+                    .cascade(&parent_style.enabled, &parent_style, input_state, true, theme, INITIAL_ENABLED);
 
                 CascadedStyle {
                     enabled,
@@ -629,7 +875,7 @@ macro_rules! define_style {
                         $property_name: self
                             .$property_name
                             .clone()
-                            .cascade(&parent_style.$property_name, &parent_style, input_state, enabled, $initial_value),
+                            .cascade(&parent_style.$property_name, &parent_style, input_state, enabled, theme, $initial_value),
                     )*
                 }
             }
@@ -655,9 +901,10 @@ pub const INITIAL_ENABLED: bool = true;
 pub const INITIAL_SIZE: Sizing = Sizing::FitContent;
 pub const INITIAL_MIN_SIZE: AbsoluteMeasurement = AbsoluteMeasurement::Pixel(Float::px(0.0));
 pub const INITIAL_MAX_SIZE: AbsoluteMeasurement = AbsoluteMeasurement::Pixel(Float::px(f32::MAX));
+pub const INITIAL_MIN_HIT_SIZE: AbsoluteMeasurement = AbsoluteMeasurement::Pixel(Float::px(0.0));
 pub const INITIAL_FLEX_RATIO: f32 = 1.0;
 pub const INITIAL_PADDING: Padding = Padding::ZERO;
-pub const INITIAL_CHILD_SPACING: AbsoluteMeasurement = AbsoluteMeasurement::Pixel(Float::px(0.0));
+pub const INITIAL_CHILD_SPACING: RelativeMeasurement = RelativeMeasurement::Pixel(Float::px(0.0));
 pub const INITIAL_LAYOUT_DIRECTION: Direction = Direction::LeftToRight;
 pub const INITIAL_ALIGNMENT: Alignment = Alignment::Start;
 pub const INITIAL_BACKGROUND: Brush = Brush::Solid(Color::TRANSPARENT);
@@ -667,6 +914,7 @@ pub const INITIAL_BORDER_COLOR: Color = Color::TRANSPARENT;
 pub const INITIAL_DROP_SHADOW_WIDTH: AbsoluteMeasurement =
     AbsoluteMeasurement::Pixel(Float::px(0.0));
 pub const INITIAL_DROP_SHADOW_COLOR: Color = Color::TRANSPARENT;
+pub const INITIAL_BACKDROP_BLUR: AbsoluteMeasurement = AbsoluteMeasurement::Pixel(Float::px(0.0));
 pub const INITIAL_FONT_FAMILY: FontStack<'static> =
     FontStack::Single(FontFamily::Generic(GenericFamily::SystemUi));
 pub const INITIAL_FONT_SIZE: AbsoluteMeasurement = AbsoluteMeasurement::Pixel(ROOT_FONT_SIZE);
@@ -676,10 +924,24 @@ pub const INITIAL_FONT_WIDTH: FontWidth = FontWidth::NORMAL;
 pub const INITIAL_TEXT_UNDERLINE: bool = false;
 pub const INITIAL_TEXT_STRIKETHROUGH: bool = false;
 pub const INITIAL_TEXT_WRAP: bool = true;
+pub const INITIAL_OVERFLOW_WRAP: OverflowWrap = OverflowWrap::BreakWord;
+pub const INITIAL_HYPHENATION: bool = false;
+pub const INITIAL_LOCALE: SmolStr = SmolStr::new_static("");
 pub const INITIAL_TEXT_COLOR: Color = Color::BLACK;
+pub const INITIAL_TEXT_STROKE_WIDTH: AbsoluteMeasurement =
+    AbsoluteMeasurement::Pixel(Float::px(0.0));
+pub const INITIAL_TEXT_STROKE_COLOR: Color = Color::TRANSPARENT;
+pub const INITIAL_SELECTION_COLOR: Color = Color::rgb(66, 135, 245);
+pub const INITIAL_SELECTION_TEXT_COLOR: Option<Color> = None;
+pub const INITIAL_CARET_COLOR: Option<Color> = None;
+pub const INITIAL_CARET_WIDTH: AbsoluteMeasurement = AbsoluteMeasurement::Point(Float::pt(1.0));
+pub const INITIAL_CARET_SHAPE: CaretShape = CaretShape::Bar;
+pub const INITIAL_OVERWRITE_CARET_SHAPE: CaretShape = CaretShape::Block;
 pub const INITIAL_HORIZONTAL_TEXT_ALIGNMENT: HorizontalTextAlignment =
     HorizontalTextAlignment::Start;
 pub const INITIAL_VERTICAL_TEXT_ALIGNMENT: VerticalTextAlignment = VerticalTextAlignment::Top;
+pub const INITIAL_VERTICAL_TEXT_ANCHOR: VerticalTextAnchor = VerticalTextAnchor::LayoutBox;
+pub const INITIAL_UI_DIRECTION: UiDirection = UiDirection::Ltr;
 
 define_style! {
     // `enabled` property is hardcoded in the macro because of special behavior
@@ -689,9 +951,11 @@ define_style! {
     [Initial] min_height: AbsoluteMeasurement { INITIAL_MIN_SIZE },
     [Initial] max_width: AbsoluteMeasurement { INITIAL_MAX_SIZE },
     [Initial] max_height: AbsoluteMeasurement { INITIAL_MAX_SIZE },
+    [Initial] min_hit_width: AbsoluteMeasurement { INITIAL_MIN_HIT_SIZE },
+    [Initial] min_hit_height: AbsoluteMeasurement { INITIAL_MIN_HIT_SIZE },
     [Initial] flex_ratio: f32 { INITIAL_FLEX_RATIO },
     [Initial] padding: Padding { INITIAL_PADDING },
-    [Initial] child_spacing: AbsoluteMeasurement { INITIAL_CHILD_SPACING },
+    [Initial] child_spacing: RelativeMeasurement { INITIAL_CHILD_SPACING },
     [Initial] layout_direction: Direction { INITIAL_LAYOUT_DIRECTION },
     [Initial] child_alignment: Alignment { INITIAL_ALIGNMENT },
     [Initial] cross_axis_alignment: Alignment { INITIAL_ALIGNMENT },
@@ -701,6 +965,7 @@ define_style! {
     [Initial] border_color: Color { INITIAL_BORDER_COLOR },
     [Initial] drop_shadow_width: AbsoluteMeasurement { INITIAL_DROP_SHADOW_WIDTH },
     [Initial] drop_shadow_color: Color { INITIAL_DROP_SHADOW_COLOR },
+    [Initial] backdrop_blur: AbsoluteMeasurement { INITIAL_BACKDROP_BLUR },
     [Inherit] font_family: FontStack<'static> { INITIAL_FONT_FAMILY },
     [Inherit] font_size: AbsoluteMeasurement { INITIAL_FONT_SIZE },
     [Inherit] font_style: FontStyle { INITIAL_FONT_STYLE },
@@ -709,9 +974,56 @@ define_style! {
     [Inherit] text_underline: bool { INITIAL_TEXT_UNDERLINE },
     [Inherit] text_strikethrough: bool { INITIAL_TEXT_STRIKETHROUGH },
     [Inherit] text_wrap: bool { INITIAL_TEXT_WRAP },
+    [Inherit] overflow_wrap: OverflowWrap { INITIAL_OVERFLOW_WRAP },
+    [Inherit] hyphenation: bool { INITIAL_HYPHENATION },
+    [Inherit] locale: SmolStr { INITIAL_LOCALE },
     [Inherit] text_color: Color { INITIAL_TEXT_COLOR },
+    [Inherit] text_stroke_width: AbsoluteMeasurement { INITIAL_TEXT_STROKE_WIDTH },
+    [Inherit] text_stroke_color: Color { INITIAL_TEXT_STROKE_COLOR },
+    [Inherit] selection_color: Color { INITIAL_SELECTION_COLOR },
+    [Inherit] selection_text_color: Option<Color> { INITIAL_SELECTION_TEXT_COLOR },
+    [Inherit] caret_color: Option<Color> { INITIAL_CARET_COLOR },
+    [Inherit] caret_width: AbsoluteMeasurement { INITIAL_CARET_WIDTH },
+    [Inherit] caret_shape: CaretShape { INITIAL_CARET_SHAPE },
+    [Inherit] overwrite_caret_shape: CaretShape { INITIAL_OVERWRITE_CARET_SHAPE },
     [Inherit] horizontal_text_alignment: HorizontalTextAlignment { INITIAL_HORIZONTAL_TEXT_ALIGNMENT },
     [Inherit] vertical_text_alignment: VerticalTextAlignment { INITIAL_VERTICAL_TEXT_ALIGNMENT },
+    [Inherit] vertical_text_anchor: VerticalTextAnchor { INITIAL_VERTICAL_TEXT_ANCHOR },
+    [Inherit] ui_direction: UiDirection { INITIAL_UI_DIRECTION },
+}
+
+impl Style {
+    #[must_use]
+    #[inline]
+    pub fn with_size(self, size: impl Into<SizePair<Sizing>>) -> Self {
+        let size = size.into();
+        self.with_width(size.width).with_height(size.height)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_min_size(self, size: impl Into<SizePair<AbsoluteMeasurement>>) -> Self {
+        let size = size.into();
+        self.with_min_width(size.width).with_min_height(size.height)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_max_size(self, size: impl Into<SizePair<AbsoluteMeasurement>>) -> Self {
+        let size = size.into();
+        self.with_max_width(size.width).with_max_height(size.height)
+    }
+
+    /// Makes the node react to the cursor in an area of at least this size, centered on the node,
+    /// without changing its layout or how it is drawn. Meant to give small controls a usable
+    /// touch target.
+    #[must_use]
+    #[inline]
+    pub fn with_min_hit_size(self, size: impl Into<SizePair<AbsoluteMeasurement>>) -> Self {
+        let size = size.into();
+        self.with_min_hit_width(size.width)
+            .with_min_hit_height(size.height)
+    }
 }
 
 /// This type is a hack to help the compiler perform double type conversions in the style macro.
@@ -744,17 +1056,132 @@ impl<T, const INHERIT_FALLBACK: bool> Property<T, INHERIT_FALLBACK> {
             _PropertyValue::Compute(f) => Self::Compute(f),
         }
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn _important_from_value<I: Into<T>>(value: I) -> Self {
+        Self::Important(value.into())
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __style_property {
+    (%initial) => {
+        $crate::style::Property::Initial
+    };
+    (%inherit) => {
+        $crate::style::Property::Inherit
+    };
+    (%important($value:expr)) => {
+        $crate::style::Property::_important_from_value($value)
+    };
+    ($value:expr) => {
+        $crate::style::Property::_from_value($value.into())
+    };
+}
+
+/// Splits a `(width, height)` pair of the style macro into the property for one axis.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __style_pair {
+    (@width %initial, $($height:tt)+) => {
+        $crate::style::Property::Initial
+    };
+    (@width %inherit, $($height:tt)+) => {
+        $crate::style::Property::Inherit
+    };
+    (@width %important($width:expr), $($height:tt)+) => {
+        $crate::__style_property!(%important($width))
+    };
+    (@width $width:expr, $($height:tt)+) => {
+        $crate::__style_property!($width)
+    };
+    (@height %initial, $($height:tt)+) => {
+        $crate::__style_property!($($height)+)
+    };
+    (@height %inherit, $($height:tt)+) => {
+        $crate::__style_property!($($height)+)
+    };
+    (@height %important($width:expr), $($height:tt)+) => {
+        $crate::__style_property!($($height)+)
+    };
+    (@height $width:expr, $($height:tt)+) => {
+        $crate::__style_property!($($height)+)
+    };
+}
+
+/// Expands a shorthand of the style macro into the properties for both axes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __style_axes {
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $width:ident, $height:ident; ($($pair:tt)+) $(, $($t:tt)*)?) => {
+        $crate::__style_recursive!(
+            $(($parsed_name, $parsed_property),)*
+            ($width, $crate::__style_pair!(@width $($pair)+)),
+            ($height, $crate::__style_pair!(@height $($pair)+));
+            $($($t)*)?
+        )
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $width:ident, $height:ident; %initial $(, $($t:tt)*)?) => {
+        $crate::__style_recursive!(
+            $(($parsed_name, $parsed_property),)*
+            ($width, $crate::style::Property::Initial),
+            ($height, $crate::style::Property::Initial);
+            $($($t)*)?
+        )
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $width:ident, $height:ident; %inherit $(, $($t:tt)*)?) => {
+        $crate::__style_recursive!(
+            $(($parsed_name, $parsed_property),)*
+            ($width, $crate::style::Property::Inherit),
+            ($height, $crate::style::Property::Inherit);
+            $($($t)*)?
+        )
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $width:ident, $height:ident; %important($value:expr) $(, $($t:tt)*)?) => {
+        $crate::__style_recursive!(
+            $(($parsed_name, $parsed_property),)*
+            ($width, $crate::__style_property!(%important($value))),
+            ($height, $crate::__style_property!(%important($value)));
+            $($($t)*)?
+        )
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $width:ident, $height:ident; $value:expr $(, $($t:tt)*)?) => {
+        $crate::__style_recursive!(
+            $(($parsed_name, $parsed_property),)*
+            ($width, $crate::__style_property!($value)),
+            ($height, $crate::__style_property!($value));
+            $($($t)*)?
+        )
+    };
 }
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __style_recursive {
+    // shorthands for both axes
+    ($(($parsed_name:ident, $parsed_property:expr)),*; size: $($t:tt)+) => {
+        $crate::__style_axes!($(($parsed_name, $parsed_property)),*; width, height; $($t)+)
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; min_size: $($t:tt)+) => {
+        $crate::__style_axes!($(($parsed_name, $parsed_property)),*; min_width, min_height; $($t)+)
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; max_size: $($t:tt)+) => {
+        $crate::__style_axes!($(($parsed_name, $parsed_property)),*; max_width, max_height; $($t)+)
+    };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; min_hit_size: $($t:tt)+) => {
+        $crate::__style_axes!($(($parsed_name, $parsed_property)),*; min_hit_width, min_hit_height; $($t)+)
+    };
     ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: %initial, $($t:tt)*) => {
         $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::style::Property::Initial); $($t)*)
     };
     ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: %inherit, $($t:tt)*) => {
         $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::style::Property::Inherit); $($t)*)
     };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: %important($value:expr), $($t:tt)*) => {
+        $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::__style_property!(%important($value))); $($t)*)
+    };
     ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: $value:expr, $($t:tt)*) => {
         $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::style::Property::_from_value($value.into())); $($t)*)
     };
@@ -764,6 +1191,9 @@ macro_rules! __style_recursive {
     ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: %inherit) => {
         $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::style::Property::Inherit);)
     };
+    ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: %important($value:expr)) => {
+        $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::__style_property!(%important($value)));)
+    };
     ($(($parsed_name:ident, $parsed_property:expr)),*; $name:ident: $value:expr) => {
         $crate::__style_recursive!($(($parsed_name, $parsed_property),)* ($name, $crate::style::Property::_from_value($value.into()));)
     };
@@ -1,9 +1,21 @@
+//! Helpers to write a widget once for both orientations.
+//!
+//! A widget like a slider or splitter stores the [`Axis`] it is laid out along and uses it
+//! everywhere it would otherwise pick `x` or `y`, `width` or `height`: [`Vec2::along_axis`] to read
+//! positions and sizes from the previous frame, the `*_along_axis` builders on [`Style`] to size
+//! its nodes, [`ComputedPadding::along_axis`] for the padding of its parent and
+//! [`Axis::primary_direction`] to lay out its children. `!axis` is the cross axis. The built-in
+//! scroll bars are written this way, see `tests/axis.rs` for a small example.
+
 use super::computed::*;
 use super::*;
 
+/// One of the two axes of the screen, `!axis` is the other one.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Axis {
+    /// The horizontal axis, pointing to the right.
     X,
+    /// The vertical axis, pointing down.
     Y,
 }
 
@@ -20,6 +32,7 @@ impl std::ops::Not for Axis {
 }
 
 impl Axis {
+    /// The layout direction that places children one after another along this axis.
     #[must_use]
     #[inline]
     pub fn primary_direction(self) -> Direction {
@@ -29,6 +42,7 @@ impl Axis {
         }
     }
 
+    /// The layout direction that places children one after another along the other axis.
     #[must_use]
     #[inline]
     pub fn cross_direction(self) -> Direction {
@@ -38,12 +52,15 @@ impl Axis {
         }
     }
 
+    /// Whether children laid out in `direction` are placed along this axis.
     #[must_use]
     #[inline]
     pub fn is_primary(self, direction: Direction) -> bool {
         self.primary_direction() == direction
     }
 
+    /// The key the scroll offset of a node along this axis is stored under in its persistent
+    /// state, as a `Float<Pixel>`. Layout offsets the children of every node that stores one.
     #[must_use]
     #[inline]
     pub fn persistent_state_scroll_key(self) -> PersistentStateKey {
@@ -55,6 +72,7 @@ impl Axis {
 }
 
 impl Direction {
+    /// The axis children are placed along.
     #[must_use]
     #[inline]
     pub fn primary_axis(self) -> Axis {
@@ -64,6 +82,7 @@ impl Direction {
         }
     }
 
+    /// The axis children are aligned on with the cross axis alignment.
     #[must_use]
     #[inline]
     pub fn cross_axis(self) -> Axis {
@@ -75,6 +94,7 @@ impl Direction {
 }
 
 impl<U: Unit> Vec2<U> {
+    /// The component along `axis`.
     #[must_use]
     #[inline]
     pub fn along_axis(self, axis: Axis) -> Float<U> {
@@ -95,6 +115,10 @@ impl<U: Unit> Vec2<U> {
 }
 
 impl ComputedPadding {
+    /// The padding at the start and end of `axis`, i.e. `[left, right]` or `[top, bottom]`.
+    ///
+    /// Computed padding is physical, so in right-to-left mode the start of [`Axis::X`] is still
+    /// the left side.
     #[must_use]
     #[inline]
     pub fn along_axis(&self, axis: Axis) -> [Float<Pixel>; 2] {
@@ -106,6 +130,7 @@ impl ComputedPadding {
 }
 
 impl Style {
+    /// The `width` or `height` property.
     #[must_use]
     #[inline]
     pub fn size_along_axis(&self, axis: Axis) -> Property<Sizing, false> {
@@ -115,6 +140,7 @@ impl Style {
         }
     }
 
+    /// Sets the `width` or `height` property.
     #[must_use]
     #[inline]
     pub fn with_size_along_axis(self, axis: Axis, size: impl Into<Sizing>) -> Self {
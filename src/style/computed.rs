@@ -1,4 +1,6 @@
 use super::*;
+use crate::axis::Axis;
+use std::hash::{Hash, Hasher};
 
 impl RelativeMeasurement {
     #[must_use]
@@ -11,6 +13,17 @@ impl RelativeMeasurement {
             Self::Percent(value) => (true, value.value()),
         }
     }
+
+    /// Splits off percentages, which are only resolved during layout.
+    #[inline]
+    fn split_percent(self) -> Result<AbsoluteMeasurement, Float<Percent>> {
+        match self {
+            Self::Pixel(value) => Ok(value.into()),
+            Self::Point(value) => Ok(value.into()),
+            Self::EM(value) => Ok(value.into()),
+            Self::Percent(value) => Err(value),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Specifier)]
@@ -22,21 +35,87 @@ pub(crate) enum ComputedSizing {
     Percent,
 }
 
+/// Names of the properties that had invalid values while computing a style.
+pub(crate) type InvalidProperties = SmallVec<[&'static str; 2]>;
+
+/// Replaces non-finite values with the initial value and clamps negative values to zero.
+#[must_use]
+#[inline]
+fn sanitize(
+    value: Float<Pixel>,
+    initial: Float<Pixel>,
+    property: &'static str,
+    invalid_properties: &mut InvalidProperties,
+) -> Float<Pixel> {
+    if !value.value().is_finite() {
+        invalid_properties.push(property);
+        initial
+    } else if value < 0.px() {
+        invalid_properties.push(property);
+        0.px()
+    } else {
+        value
+    }
+}
+
 impl Sizing {
     #[must_use]
     #[inline]
-    fn compute(self, pixel_per_point: f32, pixel_per_em: f32) -> (ComputedSizing, Float<Pixel>) {
+    fn compute(
+        self,
+        pixel_per_point: f32,
+        pixel_per_em: f32,
+        property: &'static str,
+        invalid_properties: &mut InvalidProperties,
+    ) -> (ComputedSizing, Float<Pixel>) {
         match self {
             Self::FitContent => (ComputedSizing::FitContent, 0.px()),
             Self::Grow => (ComputedSizing::Grow, 0.px()),
-            Self::Fixed(fixed_size) => (
-                ComputedSizing::Fixed,
-                fixed_size.to_pixel(pixel_per_point, pixel_per_em).round(),
-            ),
+            Self::Fixed(fixed_size) => {
+                let fixed_size = fixed_size.to_pixel(pixel_per_point, pixel_per_em).round();
+
+                if fixed_size.value().is_finite() {
+                    (
+                        ComputedSizing::Fixed,
+                        sanitize(fixed_size, 0.px(), property, invalid_properties),
+                    )
+                } else {
+                    invalid_properties.push(property);
+                    INITIAL_SIZE.compute(
+                        pixel_per_point,
+                        pixel_per_em,
+                        property,
+                        invalid_properties,
+                    )
+                }
+            }
         }
     }
 }
 
+/// Turns a percentage into a fraction of the size it is relative to, clamping negative values to
+/// zero like [`sanitize`].
+#[must_use]
+fn compute_percent(
+    value: Float<Percent>,
+    property: &'static str,
+    invalid_properties: &mut InvalidProperties,
+) -> f32 {
+    let fraction = value.value() / 100.0;
+    if !fraction.is_finite() || (fraction < 0.0) {
+        invalid_properties.push(property);
+        0.0
+    } else {
+        fraction
+    }
+}
+
+/// Hashes a float consistently with `==`, so `0.0` and `-0.0` hash the same.
+#[inline]
+fn hash_f32<H: Hasher>(value: f32, state: &mut H) {
+    (value + 0.0).to_bits().hash(state);
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ComputedPadding {
     pub left: Float<Pixel>,
@@ -54,6 +133,25 @@ impl ComputedPadding {
     };
 }
 
+impl ComputedPadding {
+    #[must_use]
+    #[inline]
+    fn mirrored(&self) -> Self {
+        Self {
+            left: self.right,
+            right: self.left,
+            top: self.top,
+            bottom: self.bottom,
+        }
+    }
+
+    fn hash_relevant<H: Hasher>(&self, state: &mut H) {
+        for side in [self.left, self.right, self.top, self.bottom] {
+            hash_f32(side.value(), state);
+        }
+    }
+}
+
 impl Default for ComputedPadding {
     #[inline]
     fn default() -> Self {
@@ -61,15 +159,78 @@ impl Default for ComputedPadding {
     }
 }
 
+/// The padding and child spacing given in percent, as fractions of the size of the node along
+/// their axis. Layout resolves them once that size is known, until then they count as zero.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct PercentSpacing {
+    left: Option<f32>,
+    right: Option<f32>,
+    top: Option<f32>,
+    bottom: Option<f32>,
+    child_spacing: Option<f32>,
+}
+
+impl PercentSpacing {
+    #[must_use]
+    #[inline]
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    #[must_use]
+    #[inline]
+    fn mirrored(&self) -> Self {
+        Self {
+            left: self.right,
+            right: self.left,
+            ..self.clone()
+        }
+    }
+
+    fn hash_relevant<H: Hasher>(&self, state: &mut H) {
+        for fraction in [
+            self.left,
+            self.right,
+            self.top,
+            self.bottom,
+            self.child_spacing,
+        ] {
+            fraction.is_some().hash(state);
+            hash_f32(fraction.unwrap_or_default(), state);
+        }
+    }
+}
+
 impl Padding {
+    /// Computes the absolute sides and records the sides given in percent in `percent_spacing`.
     #[must_use]
     #[inline]
-    fn compute(&self, pixel_per_point: f32, pixel_per_em: f32) -> ComputedPadding {
+    fn compute(
+        &self,
+        pixel_per_point: f32,
+        pixel_per_em: f32,
+        density: f32,
+        percent_spacing: &mut PercentSpacing,
+        invalid_properties: &mut InvalidProperties,
+    ) -> ComputedPadding {
+        let mut compute_side = |side: RelativeMeasurement, percent_side: &mut Option<f32>| {
+            match side.split_percent() {
+                Ok(side) => {
+                    let value = (side.to_pixel(pixel_per_point, pixel_per_em) * density).round();
+                    sanitize(value, 0.px(), "padding", invalid_properties)
+                }
+                Err(percent) => {
+                    *percent_side = Some(compute_percent(percent, "padding", invalid_properties));
+                    0.px()
+                }
+            }
+        };
+
         ComputedPadding {
-            left: self.left.to_pixel(pixel_per_point, pixel_per_em).round(),
-            right: self.right.to_pixel(pixel_per_point, pixel_per_em).round(),
-            top: self.top.to_pixel(pixel_per_point, pixel_per_em).round(),
-            bottom: self.bottom.to_pixel(pixel_per_point, pixel_per_em).round(),
+            left: compute_side(self.left, &mut percent_spacing.left),
+            right: compute_side(self.right, &mut percent_spacing.right),
+            top: compute_side(self.top, &mut percent_spacing.top),
+            bottom: compute_side(self.bottom, &mut percent_spacing.bottom),
         }
     }
 }
@@ -211,12 +372,73 @@ impl ComputedBrush<'_> {
     }
 }
 
+fn hash_stops<H: Hasher>(stops: &[GradientStop], state: &mut H) {
+    stops.len().hash(state);
+    for stop in stops {
+        stop.color.hash(state);
+        hash_f32(stop.offset, state);
+    }
+}
+
 impl PrecomputedBrush {
+    fn hash_relevant<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            Self::Solid(color) => color.hash(state),
+            Self::LinearGradient {
+                start_x_is_percent,
+                start_y_is_percent,
+                end_x_is_percent,
+                end_y_is_percent,
+                start_x,
+                start_y,
+                end_x,
+                end_y,
+                ref stops,
+            } => {
+                [
+                    start_x_is_percent,
+                    start_y_is_percent,
+                    end_x_is_percent,
+                    end_y_is_percent,
+                ]
+                .hash(state);
+                for value in [start_x, start_y, end_x, end_y] {
+                    hash_f32(value, state);
+                }
+                hash_stops(stops, state);
+            }
+            Self::RadialGradient {
+                center_x_is_percent,
+                center_y_is_percent,
+                radius_x_is_percent,
+                radius_y_is_percent,
+                center_x,
+                center_y,
+                radius_x,
+                radius_y,
+                ref stops,
+            } => {
+                [
+                    center_x_is_percent,
+                    center_y_is_percent,
+                    radius_x_is_percent,
+                    radius_y_is_percent,
+                ]
+                .hash(state);
+                for value in [center_x, center_y, radius_x, radius_y] {
+                    hash_f32(value, state);
+                }
+                hash_stops(stops, state);
+            }
+        }
+    }
+
     #[must_use]
     fn as_computed(&self, one_hundred_percent_value: Vec2<Pixel>) -> ComputedBrush<'_> {
-        match self {
-            &Self::Solid(color) => ComputedBrush::Solid(color),
-            &Self::LinearGradient {
+        match *self {
+            Self::Solid(color) => ComputedBrush::Solid(color),
+            Self::LinearGradient {
                 start_x_is_percent,
                 start_y_is_percent,
                 end_x_is_percent,
@@ -253,7 +475,7 @@ impl PrecomputedBrush {
                 },
                 stops: stops.as_ref(),
             },
-            &Self::RadialGradient {
+            Self::RadialGradient {
                 center_x_is_percent,
                 center_y_is_percent,
                 radius_x_is_percent,
@@ -294,6 +516,7 @@ impl PrecomputedBrush {
     }
 }
 
+#[derive(Clone, PartialEq)]
 struct ComputedFont {
     family: FontStack<'static>,
     size: Float<Pixel>,
@@ -319,57 +542,257 @@ impl Default for ComputedFont {
     }
 }
 
-#[bitfield(bits = 18)]
-struct ComputedStylePackedFields {
-    enabled: bool,
-    width: ComputedSizing,
-    height: ComputedSizing,
-    layout_direction: Direction,
-    child_alignment: Alignment,
-    cross_axis_alignment: Alignment,
-    text_underline: bool,
-    text_strikethrough: bool,
-    text_wrap: bool,
-    horizontal_text_alignment: HorizontalTextAlignment,
-    vertical_text_alignment: VerticalTextAlignment,
+fn hash_font_family<H: Hasher>(family: &FontFamily<'_>, state: &mut H) {
+    match family {
+        FontFamily::Named(name) => {
+            0u8.hash(state);
+            name.hash(state);
+        }
+        FontFamily::Generic(generic) => {
+            1u8.hash(state);
+            generic.hash(state);
+        }
+    }
 }
 
+impl ComputedFont {
+    fn hash_relevant<H: Hasher>(&self, state: &mut H) {
+        match &self.family {
+            FontStack::Source(source) => {
+                0u8.hash(state);
+                source.hash(state);
+            }
+            FontStack::Single(family) => {
+                1u8.hash(state);
+                hash_font_family(family, state);
+            }
+            FontStack::List(families) => {
+                2u8.hash(state);
+                families.len().hash(state);
+                for family in families.iter() {
+                    hash_font_family(family, state);
+                }
+            }
+        }
+
+        hash_f32(self.size.value(), state);
+        match self.style {
+            FontStyle::Normal => 0u8.hash(state),
+            FontStyle::Italic => 1u8.hash(state),
+            FontStyle::Oblique(angle) => {
+                2u8.hash(state);
+                angle.is_some().hash(state);
+                hash_f32(angle.unwrap_or_default(), state);
+            }
+        }
+        hash_f32(self.weight.value(), state);
+        hash_f32(self.width.ratio(), state);
+    }
+}
+
+#[allow(dead_code)] // the bitfield macro generates functions we don't need
+mod packed_fields {
+    use super::*;
+
+    #[bitfield(bits = 23)]
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub(super) struct ComputedStylePackedFields {
+        pub(super) enabled: bool,
+        pub(super) width: ComputedSizing,
+        pub(super) height: ComputedSizing,
+        pub(super) layout_direction: Direction,
+        pub(super) child_alignment: Alignment,
+        pub(super) cross_axis_alignment: Alignment,
+        pub(super) text_underline: bool,
+        pub(super) text_strikethrough: bool,
+        pub(super) text_wrap: bool,
+        pub(super) hyphenation: bool,
+        pub(super) horizontal_text_alignment: HorizontalTextAlignment,
+        pub(super) vertical_text_alignment: VerticalTextAlignment,
+        pub(super) vertical_text_anchor: VerticalTextAnchor,
+        pub(super) ui_direction: UiDirection,
+        pub(super) caret_shape: CaretShape,
+        pub(super) overwrite_caret_shape: CaretShape,
+    }
+}
+
+use packed_fields::ComputedStylePackedFields;
+
+/// The style of a node after cascading and resolving measurements to pixels.
+///
+/// Cloning is cheap, the larger values are shared between clones and with the styles of other
+/// nodes that didn't change them. Two styles compare equal if everything that affects layout and
+/// rendering is equal, regardless of whether the shared values are the same allocation.
+#[derive(Clone)]
 pub struct ComputedStyle {
     packed_fields: ComputedStylePackedFields,
 
     flex_ratio: f32,
     padding: Arc<ComputedPadding>,
     child_spacing: Float<Pixel>,
+    /// [`None`] unless the padding or child spacing are given in percent
+    percent_spacing: Option<Arc<PercentSpacing>>,
     background: Arc<PrecomputedBrush>,
     corner_radius: Float<Pixel>,
     border_width: Float<Pixel>,
     border_color: Color,
     drop_shadow_width: Float<Pixel>,
     drop_shadow_color: Color,
+    backdrop_blur: Float<Pixel>,
     font: Arc<ComputedFont>,
     text_color: Color,
+    text_stroke_width: Float<Pixel>,
+    text_stroke_color: Color,
+    overflow_wrap: OverflowWrap,
+    locale: SmolStr,
+    selection_color: Color,
+    selection_text_color: Option<Color>,
+    caret_color: Color,
+    caret_width: Float<Pixel>,
 
     pub(crate) fixed_size: Vec2<Pixel>,
     pub(crate) min_size: Vec2<Pixel>,
     pub(crate) max_size: Vec2<Pixel>,
+    pub(crate) min_hit_size: Vec2<Pixel>,
+}
+
+/// Compares the pointers first, so values shared between styles compare without looking at them.
+#[must_use]
+#[inline]
+fn arc_eq<T: PartialEq>(a: &Arc<T>, b: &Arc<T>) -> bool {
+    Arc::ptr_eq(a, b) || (**a == **b)
+}
+
+impl PartialEq for ComputedStyle {
+    fn eq(&self, other: &Self) -> bool {
+        (self.packed_fields == other.packed_fields)
+            && (self.flex_ratio == other.flex_ratio)
+            && arc_eq(&self.padding, &other.padding)
+            && (self.child_spacing == other.child_spacing)
+            && (self.percent_spacing == other.percent_spacing)
+            && arc_eq(&self.background, &other.background)
+            && (self.corner_radius == other.corner_radius)
+            && (self.border_width == other.border_width)
+            && (self.border_color == other.border_color)
+            && (self.drop_shadow_width == other.drop_shadow_width)
+            && (self.drop_shadow_color == other.drop_shadow_color)
+            && (self.backdrop_blur == other.backdrop_blur)
+            && arc_eq(&self.font, &other.font)
+            && (self.text_color == other.text_color)
+            && (self.text_stroke_width == other.text_stroke_width)
+            && (self.text_stroke_color == other.text_stroke_color)
+            && (self.overflow_wrap == other.overflow_wrap)
+            && (self.locale == other.locale)
+            && (self.selection_color == other.selection_color)
+            && (self.selection_text_color == other.selection_text_color)
+            && (self.caret_color == other.caret_color)
+            && (self.caret_width == other.caret_width)
+            && (self.fixed_size == other.fixed_size)
+            && (self.min_size == other.min_size)
+            && (self.max_size == other.max_size)
+            && (self.min_hit_size == other.min_hit_size)
+    }
 }
 
 impl ComputedStyle {
+    /// Feeds everything that affects layout and rendering into `state`, to key caches on the
+    /// style.
+    ///
+    /// Styles that compare equal hash the same. The hash doesn't depend on where shared values
+    /// are allocated, so for the same hasher it is stable across frames and runs.
+    pub fn hash_relevant<H: Hasher>(&self, state: &mut H) {
+        self.packed_fields.hash(state);
+        hash_f32(self.flex_ratio, state);
+        self.padding.hash_relevant(state);
+        hash_f32(self.child_spacing.value(), state);
+        if let Some(percent_spacing) = &self.percent_spacing {
+            percent_spacing.hash_relevant(state);
+        }
+        self.background.hash_relevant(state);
+        hash_f32(self.corner_radius.value(), state);
+        hash_f32(self.border_width.value(), state);
+        self.border_color.hash(state);
+        hash_f32(self.drop_shadow_width.value(), state);
+        self.drop_shadow_color.hash(state);
+        hash_f32(self.backdrop_blur.value(), state);
+        self.font.hash_relevant(state);
+        self.text_color.hash(state);
+        hash_f32(self.text_stroke_width.value(), state);
+        self.text_stroke_color.hash(state);
+        (self.overflow_wrap as u8).hash(state);
+        self.locale.hash(state);
+        self.selection_color.hash(state);
+        self.selection_text_color.hash(state);
+        self.caret_color.hash(state);
+        hash_f32(self.caret_width.value(), state);
+        for size in [
+            self.fixed_size,
+            self.min_size,
+            self.max_size,
+            self.min_hit_size,
+        ] {
+            hash_f32(size.x.value(), state);
+            hash_f32(size.y.value(), state);
+        }
+    }
+
     // values that differ from the cascaded style
     // ------------------------------------------------------
 
+    /// Sides given in percent are zero until layout resolves them.
     #[must_use]
     #[inline]
     pub fn padding(&self) -> &ComputedPadding {
         &self.padding
     }
 
+    /// Zero until layout resolves it, if it is given in percent.
     #[must_use]
     #[inline]
     pub fn child_spacing(&self) -> Float<Pixel> {
         self.child_spacing
     }
 
+    #[must_use]
+    #[inline]
+    pub(crate) fn has_percent_spacing(&self) -> bool {
+        self.percent_spacing.is_some()
+    }
+
+    /// Resolves the padding along `axis`, and the child spacing if `axis` is the primary axis,
+    /// where they are given in percent of the size of the node along `axis`.
+    pub(crate) fn resolve_percent_spacing(&mut self, axis: Axis) {
+        let Some(percent_spacing) = self.percent_spacing.as_deref() else {
+            return;
+        };
+
+        let size = self.fixed_size.along_axis(axis);
+        let resolve = |fraction: Option<f32>, side: &mut Float<Pixel>| {
+            if let Some(fraction) = fraction {
+                *side = (size * fraction).round();
+            }
+        };
+
+        let (start, end) = match axis {
+            Axis::X => (percent_spacing.left, percent_spacing.right),
+            Axis::Y => (percent_spacing.top, percent_spacing.bottom),
+        };
+        if start.is_some() || end.is_some() {
+            let padding = Arc::make_mut(&mut self.padding);
+            let sides = match axis {
+                Axis::X => [&mut padding.left, &mut padding.right],
+                Axis::Y => [&mut padding.top, &mut padding.bottom],
+            };
+            let [start_side, end_side] = sides;
+            resolve(start, start_side);
+            resolve(end, end_side);
+        }
+
+        if axis.is_primary(self.layout_direction()) {
+            resolve(percent_spacing.child_spacing, &mut self.child_spacing);
+        }
+    }
+
     #[must_use]
     #[inline]
     pub fn corner_radius(&self) -> Float<Pixel> {
@@ -388,12 +811,31 @@ impl ComputedStyle {
         self.drop_shadow_width
     }
 
+    #[must_use]
+    #[inline]
+    pub fn backdrop_blur(&self) -> Float<Pixel> {
+        self.backdrop_blur
+    }
+
     #[must_use]
     #[inline]
     pub fn font_size(&self) -> Float<Pixel> {
         self.font.size
     }
 
+    /// The caret color, falling back to the text color.
+    #[must_use]
+    #[inline]
+    pub fn caret_color(&self) -> Color {
+        self.caret_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn caret_width(&self) -> Float<Pixel> {
+        self.caret_width
+    }
+
     // values that don't
     // ------------------------------------------------------
 
@@ -451,6 +893,12 @@ impl ComputedStyle {
         self.packed_fields.text_wrap()
     }
 
+    #[must_use]
+    #[inline]
+    pub(crate) fn hyphenation(&self) -> bool {
+        self.packed_fields.hyphenation()
+    }
+
     #[must_use]
     #[inline]
     pub(crate) fn horizontal_text_alignment(&self) -> HorizontalTextAlignment {
@@ -463,6 +911,30 @@ impl ComputedStyle {
         self.packed_fields.vertical_text_alignment()
     }
 
+    #[must_use]
+    #[inline]
+    pub(crate) fn vertical_text_anchor(&self) -> VerticalTextAnchor {
+        self.packed_fields.vertical_text_anchor()
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn ui_direction(&self) -> UiDirection {
+        self.packed_fields.ui_direction()
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn caret_shape(&self) -> CaretShape {
+        self.packed_fields.caret_shape()
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn overwrite_caret_shape(&self) -> CaretShape {
+        self.packed_fields.overwrite_caret_shape()
+    }
+
     #[must_use]
     #[inline]
     pub(crate) fn flex_ratio(&self) -> f32 {
@@ -516,6 +988,176 @@ impl ComputedStyle {
     pub(crate) fn text_color(&self) -> Color {
         self.text_color
     }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn text_stroke_width(&self) -> Float<Pixel> {
+        self.text_stroke_width
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn text_stroke_color(&self) -> Color {
+        self.text_stroke_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn overflow_wrap(&self) -> OverflowWrap {
+        self.overflow_wrap
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn locale(&self) -> &SmolStr {
+        &self.locale
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn selection_color(&self) -> Color {
+        self.selection_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub(crate) fn selection_text_color(&self) -> Option<Color> {
+        self.selection_text_color
+    }
+}
+
+/// A public subset of the computed style of a node, see [`ByorGui::computed_style`].
+#[derive(Clone)]
+pub struct ComputedStyleSnapshot {
+    size: Vec2<Pixel>,
+    padding: Arc<ComputedPadding>,
+    child_spacing: Float<Pixel>,
+    background: Arc<PrecomputedBrush>,
+    corner_radius: Float<Pixel>,
+    border_width: Float<Pixel>,
+    border_color: Color,
+    drop_shadow_width: Float<Pixel>,
+    drop_shadow_color: Color,
+    font: Arc<ComputedFont>,
+    text_color: Color,
+    selection_color: Color,
+}
+
+impl ComputedStyleSnapshot {
+    #[must_use]
+    pub(crate) fn new(style: &ComputedStyle) -> Self {
+        Self {
+            size: style.fixed_size,
+            padding: Arc::clone(&style.padding),
+            child_spacing: style.child_spacing,
+            background: Arc::clone(&style.background),
+            corner_radius: style.corner_radius,
+            border_width: style.border_width,
+            border_color: style.border_color,
+            drop_shadow_width: style.drop_shadow_width,
+            drop_shadow_color: style.drop_shadow_color,
+            font: Arc::clone(&style.font),
+            text_color: style.text_color,
+            selection_color: style.selection_color,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn size(&self) -> Vec2<Pixel> {
+        self.size
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn padding(&self) -> &ComputedPadding {
+        &self.padding
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn child_spacing(&self) -> Float<Pixel> {
+        self.child_spacing
+    }
+
+    /// The background brush, relative to the top left corner of the node.
+    #[must_use]
+    #[inline]
+    pub fn background(&self) -> ComputedBrush<'_> {
+        self.background.as_computed(self.size)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn corner_radius(&self) -> Float<Pixel> {
+        self.corner_radius
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn border_width(&self) -> Float<Pixel> {
+        self.border_width
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn border_color(&self) -> Color {
+        self.border_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn drop_shadow_width(&self) -> Float<Pixel> {
+        self.drop_shadow_width
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn drop_shadow_color(&self) -> Color {
+        self.drop_shadow_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn font_family(&self) -> &FontStack<'static> {
+        &self.font.family
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn font_size(&self) -> Float<Pixel> {
+        self.font.size
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn font_style(&self) -> FontStyle {
+        self.font.style
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn font_weight(&self) -> FontWeight {
+        self.font.weight
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn font_width(&self) -> FontWidth {
+        self.font.width
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn text_color(&self) -> Color {
+        self.text_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn selection_color(&self) -> Color {
+        self.selection_color
+    }
 }
 
 macro_rules! all_match {
@@ -524,6 +1166,40 @@ macro_rules! all_match {
     };
 }
 
+/// How many distinct values of each kind [`SharedComputedValues`] keeps at most.
+const MAX_SHARED_VALUES: usize = 64;
+
+/// The computed paddings, backgrounds and fonts in use, so nodes computing the same value share it
+/// instead of allocating a new one every frame.
+#[derive(Default)]
+pub(crate) struct SharedComputedValues {
+    padding: Vec<Arc<ComputedPadding>>,
+    background: Vec<Arc<PrecomputedBrush>>,
+    font: Vec<Arc<ComputedFont>>,
+}
+
+#[must_use]
+fn share<T: PartialEq>(shared: &mut Vec<Arc<T>>, value: T) -> Arc<T> {
+    if let Some(existing) = shared.iter().find(|existing| ***existing == value) {
+        return Arc::clone(existing);
+    }
+
+    let value = Arc::new(value);
+    if shared.len() < MAX_SHARED_VALUES {
+        shared.push(Arc::clone(&value));
+    }
+    value
+}
+
+impl SharedComputedValues {
+    /// Drops the values no computed style uses anymore, after a frame was built.
+    pub(crate) fn retain_used(&mut self) {
+        self.padding.retain(|value| Arc::strong_count(value) > 1);
+        self.background.retain(|value| Arc::strong_count(value) > 1);
+        self.font.retain(|value| Arc::strong_count(value) > 1);
+    }
+}
+
 static INITIAL_COMPUTED_PADDING: LazyLock<Arc<ComputedPadding>> =
     LazyLock::new(|| Arc::new(ComputedPadding::default()));
 static INITIAL_COMPUTED_BACKGROUND: LazyLock<Arc<PrecomputedBrush>> =
@@ -537,50 +1213,137 @@ pub(crate) fn compute_style(
     cascaded_style: &CascadedStyle,
     parent_style: Option<&ComputedStyle>,
     scale_factor: f32,
+    density: f32,
+    shared_values: &mut SharedComputedValues,
+    invalid_properties: &mut InvalidProperties,
 ) -> ComputedStyle {
     let parent_font_size = parent_style
         .map(ComputedStyle::font_size)
         .unwrap_or(ROOT_FONT_SIZE);
-    let font_size = cascaded_style
-        .font_size
-        .to_pixel(scale_factor, parent_font_size.value());
-
-    let (width, fixed_width) = cascaded_style
-        .width
-        .compute(scale_factor, font_size.value());
-    let (height, fixed_height) = cascaded_style
-        .height
-        .compute(scale_factor, font_size.value());
-
-    let min_width = cascaded_style
-        .min_width
-        .to_pixel(scale_factor, font_size.value())
-        .round();
-    let min_height = cascaded_style
-        .min_height
-        .to_pixel(scale_factor, font_size.value())
-        .round();
-    let max_width = cascaded_style
-        .max_width
-        .to_pixel(scale_factor, font_size.value())
-        .round();
-    let max_height = cascaded_style
-        .max_height
-        .to_pixel(scale_factor, font_size.value())
-        .round();
-    let child_spacing = cascaded_style
-        .child_spacing
-        .to_pixel(scale_factor, font_size.value())
-        .round();
-    let corner_radius = cascaded_style
-        .corner_radius
-        .to_pixel(scale_factor, font_size.value());
-    let border_width = cascaded_style
-        .border_width
-        .to_pixel(scale_factor, font_size.value());
-    let drop_shadow_width = cascaded_style
-        .drop_shadow_width
-        .to_pixel(scale_factor, font_size.value());
+    let font_size = sanitize(
+        cascaded_style
+            .font_size
+            .to_pixel(scale_factor, parent_font_size.value()),
+        INITIAL_FONT_SIZE.to_pixel(scale_factor, parent_font_size.value()),
+        "font_size",
+        invalid_properties,
+    );
+
+    // The density only scales spacing, fonts and sizes stay the same
+    let mut percent_spacing = PercentSpacing::default();
+    let child_spacing = match cascaded_style.child_spacing.split_percent() {
+        Ok(child_spacing) => {
+            let child_spacing = sanitize(
+                child_spacing.to_pixel(scale_factor, font_size.value()),
+                0.px(),
+                "child_spacing",
+                invalid_properties,
+            );
+            child_spacing * density
+        }
+        Err(percent) => {
+            percent_spacing.child_spacing = Some(compute_percent(
+                percent,
+                "child_spacing",
+                invalid_properties,
+            ));
+            0.px()
+        }
+    };
+
+    let mut compute_measurement =
+        |measurement: AbsoluteMeasurement, initial: AbsoluteMeasurement, property: &'static str| {
+            sanitize(
+                measurement.to_pixel(scale_factor, font_size.value()),
+                initial.to_pixel(scale_factor, font_size.value()),
+                property,
+                invalid_properties,
+            )
+        };
+
+    let min_width = compute_measurement(cascaded_style.min_width, INITIAL_MIN_SIZE, "min_width");
+    let min_height = compute_measurement(cascaded_style.min_height, INITIAL_MIN_SIZE, "min_height");
+    let max_width = compute_measurement(cascaded_style.max_width, INITIAL_MAX_SIZE, "max_width");
+    let max_height = compute_measurement(cascaded_style.max_height, INITIAL_MAX_SIZE, "max_height");
+    let min_hit_size = Vec2 {
+        x: compute_measurement(
+            cascaded_style.min_hit_width,
+            INITIAL_MIN_HIT_SIZE,
+            "min_hit_width",
+        ),
+        y: compute_measurement(
+            cascaded_style.min_hit_height,
+            INITIAL_MIN_HIT_SIZE,
+            "min_hit_height",
+        ),
+    };
+    let corner_radius = compute_measurement(
+        cascaded_style.corner_radius,
+        INITIAL_CORNER_RADIUS,
+        "corner_radius",
+    ) * density;
+    let border_width = compute_measurement(
+        cascaded_style.border_width,
+        INITIAL_BORDER_WIDTH,
+        "border_width",
+    );
+    let drop_shadow_width = compute_measurement(
+        cascaded_style.drop_shadow_width,
+        INITIAL_DROP_SHADOW_WIDTH,
+        "drop_shadow_width",
+    );
+    let backdrop_blur = compute_measurement(
+        cascaded_style.backdrop_blur,
+        INITIAL_BACKDROP_BLUR,
+        "backdrop_blur",
+    );
+    let caret_width = compute_measurement(
+        cascaded_style.caret_width,
+        INITIAL_CARET_WIDTH,
+        "caret_width",
+    );
+    let text_stroke_width = compute_measurement(
+        cascaded_style.text_stroke_width,
+        INITIAL_TEXT_STROKE_WIDTH,
+        "text_stroke_width",
+    );
+
+    let min_width = min_width.round();
+    let min_height = min_height.round();
+    let mut max_width = max_width.round();
+    let mut max_height = max_height.round();
+    let child_spacing = child_spacing.round();
+
+    // A maximum smaller than the minimum would make clamping panic
+    if max_width < min_width {
+        invalid_properties.push("max_width");
+        max_width = min_width;
+    }
+    if max_height < min_height {
+        invalid_properties.push("max_height");
+        max_height = min_height;
+    }
+
+    let (width, fixed_width) =
+        cascaded_style
+            .width
+            .compute(scale_factor, font_size.value(), "width", invalid_properties);
+    let (height, fixed_height) = cascaded_style.height.compute(
+        scale_factor,
+        font_size.value(),
+        "height",
+        invalid_properties,
+    );
+
+    let flex_ratio = if !cascaded_style.flex_ratio.is_finite() {
+        invalid_properties.push("flex_ratio");
+        INITIAL_FLEX_RATIO
+    } else if cascaded_style.flex_ratio < 0.0 {
+        invalid_properties.push("flex_ratio");
+        0.0
+    } else {
+        cascaded_style.flex_ratio
+    };
 
     let min_size = Vec2 {
         x: min_width,
@@ -596,22 +1359,60 @@ pub(crate) fn compute_style(
     }
     .clamp(min_size, max_size);
 
+    // Computed padding is physical, so left and right swap in right-to-left mode
+    let rtl = cascaded_style.ui_direction == UiDirection::Rtl;
     let padding = match &style.padding {
         // The padding property uses "Initial" fallback
         Property::Unspecified | Property::Initial => Arc::clone(&*INITIAL_COMPUTED_PADDING),
         Property::Inherit => {
             if let Some(parent_style) = parent_style {
-                Arc::clone(&parent_style.padding)
+                // the percentages are inherited, not what they resolved to for the parent
+                let same_direction = parent_style.ui_direction() == cascaded_style.ui_direction;
+                if let Some(parent_percent) = &parent_style.percent_spacing {
+                    let parent_percent = if same_direction {
+                        (**parent_percent).clone()
+                    } else {
+                        parent_percent.mirrored()
+                    };
+                    percent_spacing.left = parent_percent.left;
+                    percent_spacing.right = parent_percent.right;
+                    percent_spacing.top = parent_percent.top;
+                    percent_spacing.bottom = parent_percent.bottom;
+                }
+
+                if same_direction {
+                    Arc::clone(&parent_style.padding)
+                } else {
+                    share(&mut shared_values.padding, parent_style.padding.mirrored())
+                }
             } else {
                 Arc::clone(&*INITIAL_COMPUTED_PADDING)
             }
         }
-        Property::Value(_) | Property::Compute(_) => Arc::new(
-            cascaded_style
-                .padding
-                .compute(scale_factor, font_size.value()),
-        ),
+        Property::Value(_) | Property::Compute(_) | Property::Important(_) => {
+            let mut padding_percent = PercentSpacing::default();
+            let padding = cascaded_style.padding.compute(
+                scale_factor,
+                font_size.value(),
+                density,
+                &mut padding_percent,
+                invalid_properties,
+            );
+            if rtl {
+                padding_percent = padding_percent.mirrored();
+            }
+            percent_spacing.left = padding_percent.left;
+            percent_spacing.right = padding_percent.right;
+            percent_spacing.top = padding_percent.top;
+            percent_spacing.bottom = padding_percent.bottom;
+
+            share(
+                &mut shared_values.padding,
+                if rtl { padding.mirrored() } else { padding },
+            )
+        }
     };
+    let percent_spacing = (!percent_spacing.is_empty()).then(|| Arc::new(percent_spacing));
 
     let background = match &style.background {
         // The background property uses "Initial" fallback
@@ -623,7 +1424,8 @@ pub(crate) fn compute_style(
                 Arc::clone(&*INITIAL_COMPUTED_BACKGROUND)
             }
         }
-        Property::Value(_) | Property::Compute(_) => Arc::new(
+        Property::Value(_) | Property::Compute(_) | Property::Important(_) => share(
+            &mut shared_values.background,
             cascaded_style
                 .background
                 .precompute(scale_factor, font_size.value()),
@@ -658,13 +1460,16 @@ pub(crate) fn compute_style(
             Arc::clone(&*INITIAL_COMPUTED_FONT)
         }
     } else {
-        Arc::new(ComputedFont {
-            family: cascaded_style.font_family.clone(),
-            size: font_size,
-            style: cascaded_style.font_style,
-            weight: cascaded_style.font_weight,
-            width: cascaded_style.font_width,
-        })
+        share(
+            &mut shared_values.font,
+            ComputedFont {
+                family: cascaded_style.font_family.clone(),
+                size: font_size,
+                style: cascaded_style.font_style,
+                weight: cascaded_style.font_weight,
+                width: cascaded_style.font_width,
+            },
+        )
     };
 
     ComputedStyle {
@@ -678,23 +1483,41 @@ pub(crate) fn compute_style(
             .with_text_underline(cascaded_style.text_underline)
             .with_text_strikethrough(cascaded_style.text_strikethrough)
             .with_text_wrap(cascaded_style.text_wrap)
+            .with_hyphenation(cascaded_style.hyphenation)
             .with_horizontal_text_alignment(cascaded_style.horizontal_text_alignment)
-            .with_vertical_text_alignment(cascaded_style.vertical_text_alignment),
+            .with_vertical_text_alignment(cascaded_style.vertical_text_alignment)
+            .with_vertical_text_anchor(cascaded_style.vertical_text_anchor)
+            .with_ui_direction(cascaded_style.ui_direction)
+            .with_caret_shape(cascaded_style.caret_shape)
+            .with_overwrite_caret_shape(cascaded_style.overwrite_caret_shape),
 
-        flex_ratio: cascaded_style.flex_ratio,
+        flex_ratio,
         padding,
         child_spacing,
+        percent_spacing,
         background,
         corner_radius,
         border_width,
         border_color: cascaded_style.border_color,
         drop_shadow_width,
         drop_shadow_color: cascaded_style.drop_shadow_color,
+        backdrop_blur,
         font,
         text_color: cascaded_style.text_color,
+        text_stroke_width,
+        text_stroke_color: cascaded_style.text_stroke_color,
+        overflow_wrap: cascaded_style.overflow_wrap,
+        locale: cascaded_style.locale.clone(),
+        selection_color: cascaded_style.selection_color,
+        selection_text_color: cascaded_style.selection_text_color,
+        caret_color: cascaded_style
+            .caret_color
+            .unwrap_or(cascaded_style.text_color),
+        caret_width,
 
         fixed_size,
         min_size,
         max_size,
+        min_hit_size,
     }
 }
@@ -0,0 +1,206 @@
+//! Assertions about the layout of the last frame, for tests of GUIs built with this crate.
+//!
+//! [`ByorGui::expect_node`] finds a node by its UID and panics with a dump of the nodes around it
+//! if it doesn't exist, so failing tests show what was built instead of only what is missing.
+
+use crate::hyphenation::HyphenatedText;
+use crate::theme::StyleClass;
+use crate::*;
+use std::fmt::Write;
+
+/// How many nodes the dump of a missing node lists at most.
+const MAX_DUMPED_NODES: usize = 64;
+
+/// A node of the last frame, found with [`ByorGui::expect_node`].
+pub struct NodeExpectation<'gui, Renderer: rendering::Renderer> {
+    gui: &'gui ByorGui<Renderer>,
+    nodes: Vec<NodeInfo<'gui>>,
+    index: usize,
+}
+
+impl<'gui, Renderer: rendering::Renderer> NodeExpectation<'gui, Renderer> {
+    #[must_use]
+    #[inline]
+    pub fn info(&self) -> &NodeInfo<'gui> {
+        &self.nodes[self.index]
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn size(&self) -> Vec2<Pixel> {
+        self.info().size()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn position(&self) -> Vec2<Pixel> {
+        self.info().position()
+    }
+
+    /// Whether any part of the node lies inside of the bounds of its ancestor `parent_uid`, for
+    /// example whether an item of a scroll view is scrolled into view.
+    ///
+    /// Panics if the node isn't a descendant of `parent_uid`.
+    #[track_caller]
+    #[must_use]
+    pub fn visible_in(&self, parent_uid: Uid) -> bool {
+        let Some(parent) = self
+            .ancestors()
+            .find(|&index| self.nodes[index].uid() == Some(parent_uid))
+        else {
+            panic!(
+                "expected node {:?} to be inside of {parent_uid:?}\n{}",
+                self.info().uid(),
+                self.dump_around(),
+            );
+        };
+
+        let node = self.info();
+        let parent = &self.nodes[parent];
+        let min = node.position().max(parent.position());
+        let max = (node.position() + node.size()).min(parent.position() + parent.size());
+        (min.x < max.x) && (min.y < max.y)
+    }
+
+    /// The text of the node split into the lines it was laid out in, without trailing whitespace.
+    /// Lines broken at a hyphenation point end with a hyphen.
+    ///
+    /// Panics if the node doesn't show text.
+    #[track_caller]
+    #[must_use]
+    pub fn text_lines(&self) -> Vec<String> {
+        let node = self.info();
+        let (Some(text), Some(text_layout)) = (node.text(), node.node.text_layout.expand()) else {
+            panic!(
+                "expected node {:?} to show text\n{}",
+                node.uid(),
+                self.dump_around(),
+            );
+        };
+
+        let hyphenated = HyphenatedText::new(text, &[], node.style());
+        self.gui.data.text_layouts[text_layout]
+            .lines()
+            .map(|line| {
+                let line = hyphenated.text[line.text_range()].trim_end();
+                let mut unhyphenated: String = line.chars().filter(|&c| c != '\u{AD}').collect();
+                if line.ends_with('\u{AD}') {
+                    unhyphenated.push('-');
+                }
+                unhyphenated
+            })
+            .collect()
+    }
+
+    /// The indices of the ancestors of the node, innermost first.
+    fn ancestors(&self) -> impl Iterator<Item = usize> {
+        let mut depth = self.info().depth();
+        (0..self.index).rev().filter(move |&index| {
+            let is_ancestor = self.nodes[index].depth() < depth;
+            if is_ancestor {
+                depth = self.nodes[index].depth();
+            }
+            is_ancestor
+        })
+    }
+
+    /// The ancestors of the node, the node itself and its children.
+    fn dump_around(&self) -> String {
+        let mut ancestors: Vec<_> = self.ancestors().collect();
+        ancestors.reverse();
+        let depth = self.info().depth();
+        let children = self.nodes[(self.index + 1)..]
+            .iter()
+            .take_while(|node| node.depth() > depth)
+            .enumerate()
+            .filter(|(_, node)| node.depth() == depth + 1)
+            .map(|(offset, _)| self.index + 1 + offset);
+
+        let mut dump = String::new();
+        for index in ancestors
+            .into_iter()
+            .chain(std::iter::once(self.index))
+            .chain(children)
+        {
+            let marker = if index == self.index { "> " } else { "  " };
+            dump_node(&mut dump, self.gui, &self.nodes[index], marker);
+        }
+        dump
+    }
+}
+
+fn dump_node<Renderer: rendering::Renderer>(
+    dump: &mut String,
+    gui: &ByorGui<Renderer>,
+    node: &NodeInfo<'_>,
+    marker: &str,
+) {
+    let indent = "  ".repeat(node.depth() as usize);
+    let _ = write!(dump, "{marker}{indent}");
+    match node.uid() {
+        Some(uid) => {
+            let _ = write!(dump, "{uid:?}");
+            if let Some(classes) = gui.data.widget_classes.get(uid)
+                && !classes.is_empty()
+            {
+                let classes: Vec<&str> = classes.iter().map(StyleClass::as_str).collect();
+                let _ = write!(dump, " [{}]", classes.join(", "));
+            }
+        }
+        None => dump.push_str("<anonymous>"),
+    }
+    let _ = write!(dump, " at {} size {}", node.position(), node.size());
+    if let Some(text) = node.text() {
+        let _ = write!(dump, " {text:?}");
+    }
+    if node.is_floating_root() {
+        dump.push_str(" (floating)");
+    }
+    dump.push('\n');
+}
+
+impl<Renderer: rendering::Renderer> ByorGui<Renderer> {
+    /// Finds the node with this UID in the last frame, to assert where it was laid out.
+    ///
+    /// Panics with a dump of the nodes of the last frame if there is no such node. The dump lists
+    /// the classes of nodes shown as widgets in debug builds.
+    #[track_caller]
+    #[must_use]
+    pub fn expect_node(&self, uid: Uid) -> NodeExpectation<'_, Renderer> {
+        let nodes: Vec<_> = self.nodes().collect();
+        let Some(index) = nodes.iter().position(|node| node.uid() == Some(uid)) else {
+            let mut dump = String::new();
+            for node in nodes.iter().take(MAX_DUMPED_NODES) {
+                dump_node(&mut dump, self, node, "  ");
+            }
+            if nodes.len() > MAX_DUMPED_NODES {
+                let _ = writeln!(dump, "  ... {} more", nodes.len() - MAX_DUMPED_NODES);
+            }
+            panic!("expected a node {uid:?} in the last frame\n{dump}");
+        };
+
+        NodeExpectation {
+            gui: self,
+            nodes,
+            index,
+        }
+    }
+
+    /// Like [`frame`](Self::frame), but only builds and lays out the frame, which is all layout
+    /// assertions need.
+    ///
+    /// Nodes aren't hit tested, so hover and focus stay as they are and input received for the
+    /// frame is delivered to the next one instead. The state of nodes that disappeared is cleaned
+    /// up by the next full frame.
+    pub fn layout_only_frame<T>(
+        &mut self,
+        screen_size: Vec2<Pixel>,
+        builder: impl FnOnce(ByorGuiContext<'_, Renderer>) -> T,
+    ) -> T {
+        let context = self.begin_frame(screen_size);
+        let result = builder(context);
+        self.finish_layout();
+
+        result
+    }
+}
@@ -1,24 +1,9311 @@
-#[cfg(miri)]
+/// Builds and lays out a frame with nested and floating nodes, so running the tests under Miri
+/// exercises the raw pointer code of the forest:
+/// `cargo +nightly miri test --lib -- test_ub forest_matches_a_naive_reference`
 #[test]
 fn test_ub() {
     use crate::*;
 
-    let mut gui = ByorGui::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
 
+    let mut gui = ByorGui::<NullRenderer>::default();
+    for _ in 0..2 {
+        gui.frame(screen_size, |mut gui| -> Result<(), WidgetError> {
+            for i in 0..10u32 {
+                gui.insert_node(
+                    None,
+                    &Style::DEFAULT,
+                    NodeContents::builder(|mut gui| {
+                        gui.insert_node(None, &Style::DEFAULT, NodeContents::EMPTY)?;
+                        gui.insert_floating_node(
+                            Uid::new(i),
+                            FloatPosition::Cursor {
+                                pivot: Alignment2D::CENTER,
+                            },
+                            &Style::DEFAULT,
+                            NodeContents::EMPTY,
+                        )
+                        .map(|_| ())
+                    }),
+                )?
+                .result?;
+            }
+
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+    }
+
+    gui.render(&mut NullRenderer).unwrap();
+}
+
+struct NullRenderer;
+
+impl crate::rendering::Renderer for NullRenderer {
+    type Error = std::convert::Infallible;
+
+    fn push_clip_rect(
+        &mut self,
+        _position: crate::Vec2<crate::Pixel>,
+        _size: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn draw_rect(
+        &mut self,
+        _position: crate::Vec2<crate::Pixel>,
+        _size: crate::Vec2<crate::Pixel>,
+        _corner_radius: crate::Float<crate::Pixel>,
+        _stroke_width: crate::Float<crate::Pixel>,
+        _color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        _position: crate::Vec2<crate::Pixel>,
+        _size: crate::Vec2<crate::Pixel>,
+        _corner_radius: crate::Float<crate::Pixel>,
+        _brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn draw_poly(
+        &mut self,
+        _vertices: &[crate::Vec2<crate::Pixel>],
+        _stroke_width: crate::Float<crate::Pixel>,
+        _color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn fill_poly(
+        &mut self,
+        _vertices: &[crate::Vec2<crate::Pixel>],
+        _brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        _text: parley::GlyphRun<'_, crate::style::Color>,
+        _position: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn hostile_styles() -> [crate::style::Style; 4] {
+    use crate::style::*;
+    use crate::*;
+
+    [
+        Style::default()
+            .with_width(Sizing::Fixed(f32::NAN.px().into()))
+            .with_height(Sizing::Fixed((-10.0).px().into()))
+            .with_flex_ratio(f32::NAN)
+            .with_padding(Padding::from((-5.0).px())),
+        Style::default()
+            .with_width(Sizing::Grow)
+            .with_height(Sizing::Fixed(f32::INFINITY.px().into()))
+            .with_flex_ratio(-1.0)
+            .with_font_size(f32::INFINITY.px()),
+        Style::default()
+            .with_min_width(f32::NAN.px())
+            .with_max_width((-1.0).px())
+            .with_min_height(20.px())
+            .with_max_height(10.px())
+            .with_flex_ratio(f32::INFINITY),
+        Style::default()
+            .with_child_spacing(f32::NEG_INFINITY.px())
+            .with_corner_radius((-3.0).px())
+            .with_border_width(f32::NAN.px())
+            .with_drop_shadow_width((-2.0).px())
+            .with_font_size(f32::NAN.em()),
+    ]
+}
+
+#[test]
+fn compute_style_sanitizes_hostile_values() {
+    use crate::style::computed::*;
+    use crate::*;
+
+    let theme = theme::Theme::default();
+    let root_style = Style::DEFAULT;
+    let root_cascaded_style = root_style.cascade_root(
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+        NodeInputState::default(),
+        &theme,
+    );
+    let mut invalid_properties = InvalidProperties::new();
+    let root_computed_style = compute_style(
+        &root_style,
+        &root_cascaded_style,
+        None,
+        1.0,
+        1.0,
+        &mut SharedComputedValues::default(),
+        &mut invalid_properties,
+    );
+    assert!(invalid_properties.is_empty());
+
+    for style in hostile_styles() {
+        let cascaded_style = style.cascade(&root_cascaded_style, NodeInputState::default(), &theme);
+        let mut invalid_properties = InvalidProperties::new();
+        let computed_style = compute_style(
+            &style,
+            &cascaded_style,
+            Some(&root_computed_style),
+            1.0,
+            1.0,
+            &mut SharedComputedValues::default(),
+            &mut invalid_properties,
+        );
+        assert!(!invalid_properties.is_empty());
+
+        let padding = computed_style.padding();
+        let values = [
+            computed_style.fixed_size.x,
+            computed_style.fixed_size.y,
+            computed_style.min_size.x,
+            computed_style.min_size.y,
+            computed_style.max_size.x,
+            computed_style.max_size.y,
+            computed_style.flex_ratio().px(),
+            padding.left,
+            padding.right,
+            padding.top,
+            padding.bottom,
+            computed_style.child_spacing(),
+            computed_style.corner_radius(),
+            computed_style.border_width(),
+            computed_style.drop_shadow_width(),
+            computed_style.font_size(),
+        ];
+
+        for value in values {
+            assert!(value.value().is_finite(), "{style:?} produced {value:?}");
+            assert!(value >= 0.px(), "{style:?} produced {value:?}");
+        }
+
+        assert!(computed_style.min_size.x <= computed_style.max_size.x);
+        assert!(computed_style.min_size.y <= computed_style.max_size.y);
+    }
+}
+
+#[test]
+fn layout_of_hostile_styles_terminates() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let styles = hostile_styles();
+
+    gui.frame(
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+        |mut gui| -> widgets::WidgetResult<()> {
+            for outer in &styles {
+                gui.insert_node(
+                    None,
+                    outer,
+                    NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                        for inner in &styles {
+                            gui.insert_node(None, inner, NodeContents::text("hostile"))?;
+                        }
+
+                        Ok(())
+                    }),
+                )?
+                .result?;
+            }
+
+            Ok(())
+        },
+    )
+    .result
+    .expect("error building GUI");
+
+    gui.render(&mut NullRenderer).unwrap();
+
+    let diagnostics = gui.take_diagnostics();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.message.contains("`flex_ratio`"))
+    );
+}
+
+fn realistic_gui(
+    mut gui: crate::ByorGuiContext<'_, NullRenderer>,
+    text: &mut String,
+) -> crate::widgets::WidgetResult<()> {
+    use crate::style::*;
+    use crate::*;
+
+    gui.vertical_scroll_view(|mut gui| -> widgets::WidgetResult<()> {
+        for i in 0..20 {
+            gui.uid_scope(Uid::new(i), |gui| gui.label("scrolled label"))?;
+        }
+
+        Ok(())
+    })?
+    .result?;
+
+    let style = style! {
+        layout_direction: Direction::TopToBottom,
+        padding: 8.px(),
+        child_spacing: 4.px(),
+    };
+    let panel = widgets::FlexPanel::default().with_style(&style);
+    gui.show_container(panel, |mut gui| -> widgets::WidgetResult<()> {
+        gui.label("a label with some text that might need to wrap")?;
+        gui.button("button")?;
+        gui.text_box(text)?;
+        gui.insert_node(
+            None,
+            &style! {
+                width: Sizing::Grow,
+                height: 20.px(),
+            },
+            NodeContents::EMPTY,
+        )?;
+
+        Ok(())
+    })??;
+
+    Ok(())
+}
+
+fn assert_layout_is_sane(tree: crate::forest::TreeRef<'_, crate::Node, crate::forest::Shared>) {
+    use crate::forest::*;
+    use crate::*;
+
+    let TreeRef {
+        parent: node,
+        descendants,
+        ..
+    } = tree;
+
+    let values = [
+        node.style.fixed_size.x,
+        node.style.fixed_size.y,
+        node.clip_bounds().1.x,
+        node.clip_bounds().1.y,
+    ];
+    for value in values {
+        assert!(value.value().is_finite(), "non-finite size {value:?}");
+        assert!(value >= 0.px(), "negative size {value:?}");
+    }
+    assert!(node.position.x.value().is_finite() && node.position.y.value().is_finite());
+
+    iter_subtrees!(descendants => |subtree| {
+        assert_layout_is_sane(subtree);
+    });
+}
+
+fn run_realistic_gui(
+    gui: &mut crate::ByorGui<NullRenderer>,
+    screen_size: crate::Vec2<crate::Pixel>,
+) {
+    let mut text = String::from("text box");
+    for _ in 0..3 {
+        gui.frame(screen_size, |gui| realistic_gui(gui, &mut text))
+            .result
+            .expect("error building GUI");
+        gui.render(&mut NullRenderer).unwrap();
+
+        let mut trees = gui.forest.trees();
+        while let Some(tree) = trees.next() {
+            assert_layout_is_sane(tree);
+        }
+    }
+}
+
+#[test]
+fn layout_survives_degenerate_screen_sizes() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    for screen_size in [
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+        Vec2::ZERO,
+        Vec2 {
+            x: 1.px(),
+            y: 1.px(),
+        },
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+    ] {
+        run_realistic_gui(&mut gui, screen_size);
+    }
+}
+
+#[test]
+fn layout_survives_padding_exceeding_node_size() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.theme_mut().insert_style(
+        theme::Theme::UNIVERSAL_CLASS,
+        &style! {
+            padding: 50.px(),
+            max_width: 20.px(),
+            max_height: 20.px(),
+        },
+    );
+
+    run_realistic_gui(
+        &mut gui,
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+    );
+}
+
+#[test]
+fn failed_frame_is_rolled_back() {
+    use crate::style::*;
+    use crate::*;
+
+    const NODE_UID: Uid = Uid::from_array(b"node");
+    const FLOATING_UID: Uid = Uid::from_array(b"floating");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 50.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.try_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+    assert_eq!(
+        gui.data.previous_state.get(NODE_UID).unwrap().size.x,
+        100.px()
+    );
+
+    let result = gui
+        .try_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.insert_floating_node(
+                FLOATING_UID,
+                FloatPosition::CURSOR,
+                &Style::default(),
+                NodeContents::EMPTY,
+            )?;
+            gui.insert_node(None, &Style::default(), NodeContents::text("text"))?;
+            gui.uid_scope(Uid::new(0), |gui| {
+                gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+                gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+                Ok(())
+            })
+        })
+        .result;
+    assert!(result.is_err());
+    assert!(gui.forest.primary().is_none());
+    assert!(gui.data.text_layouts.is_empty());
+    assert!(gui.data.uid_stack.is_empty());
+    // state of nodes the failed frame inserted for the first time is dropped
+    assert_eq!(gui.data.previous_state.len(), 1);
+    // state of nodes that were missing from the failed frame is kept
+    assert_eq!(
+        gui.data.previous_state.get(NODE_UID).unwrap().size.x,
+        100.px()
+    );
+
+    gui.render(&mut NullRenderer).unwrap();
+
+    gui.try_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+    assert_eq!(
+        gui.data.previous_state.get(NODE_UID).unwrap().size.x,
+        100.px()
+    );
+    assert!(gui.data.float_positions.get(FLOATING_UID).is_none());
+    assert_eq!(gui.data.previous_state.len(), 1);
+}
+
+#[test]
+fn panicking_frame_does_not_leak_uid_scopes() {
+    use crate::*;
+
+    const NODE_UID: Uid = Uid::from_array(b"node");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        gui.frame(screen_size, |mut gui| {
+            gui.uid_scope(Uid::new(0), |_| panic!("builder panicked"))
+        })
+        .result
+    }));
+    assert!(result.is_err());
+
+    gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        assert!(gui.data.uid_stack.is_empty());
+        gui.insert_node(Some(NODE_UID), &Style::default(), NodeContents::EMPTY)?;
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+    assert!(gui.data.previous_state.get(NODE_UID).is_some());
+    gui.render(&mut NullRenderer).unwrap();
+}
+
+fn directional_gui(
+    gui: &mut crate::ByorGui<NullRenderer>,
+    ui_direction: crate::style::UiDirection,
+) -> [crate::Uid; 8] {
+    use crate::style::*;
+    use crate::*;
+
+    const ROW_UID: Uid = Uid::from_array(b"row");
+    const ROW_START_UID: Uid = Uid::from_array(b"row_start");
+    const ROW_END_UID: Uid = Uid::from_array(b"row_end");
+    const COLUMN_UID: Uid = Uid::from_array(b"column");
+    const COLUMN_START_UID: Uid = Uid::from_array(b"column_start");
+    const COLUMN_CENTER_UID: Uid = Uid::from_array(b"column_center");
+    const COLUMN_END_UID: Uid = Uid::from_array(b"column_end");
+    const POPUP_UID: Uid = Uid::from_array(b"popup");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let outer_style = style! {
+        width: 800.px(),
+        height: 600.px(),
+        padding: (5.px(), 15.px(), 0.px(), 0.px()),
+        child_spacing: 10.px(),
+        ui_direction: ui_direction,
+    };
+    let row_style = style! {
+        width: 400.px(),
+        height: 100.px(),
+        padding: (10.px(), 30.px(), 5.px(), 5.px()),
+        child_spacing: 5.px(),
+        layout_direction: Direction::LeftToRight,
+    };
+    let column_style = style! {
+        width: 300.px(),
+        height: 200.px(),
+        padding: (10.px(), 30.px(), 5.px(), 5.px()),
+        layout_direction: Direction::TopToBottom,
+    };
+    let leaf_style = |width: f32, alignment: Alignment| {
+        style! {
+            width: width.px(),
+            height: 20.px(),
+            cross_axis_alignment: alignment,
+        }
+    };
+
+    gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        gui.insert_node(
+            None,
+            &outer_style,
+            NodeContents::builder(|mut gui| {
+                gui.insert_node(
+                    Some(ROW_UID),
+                    &row_style,
+                    NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                        let start_style = leaf_style(50.0, Alignment::Start);
+                        let end_style = leaf_style(70.0, Alignment::End);
+                        gui.insert_node(Some(ROW_START_UID), &start_style, NodeContents::EMPTY)?;
+                        gui.insert_node(Some(ROW_END_UID), &end_style, NodeContents::EMPTY)?;
+                        gui.insert_floating_node(
+                            POPUP_UID,
+                            FloatPosition::Popup {
+                                x: PopupPosition::AfterParent,
+                                y: PopupPosition::ParentStart,
+                            },
+                            &leaf_style(120.0, Alignment::Start),
+                            NodeContents::EMPTY,
+                        )?;
+                        Ok(())
+                    }),
+                )?
+                .result?;
+                gui.insert_node(
+                    Some(COLUMN_UID),
+                    &column_style,
+                    NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                        let start_style = leaf_style(40.0, Alignment::Start);
+                        let center_style = leaf_style(50.0, Alignment::Center);
+                        let end_style = leaf_style(60.0, Alignment::End);
+                        gui.insert_node(Some(COLUMN_START_UID), &start_style, NodeContents::EMPTY)?;
+                        gui.insert_node(
+                            Some(COLUMN_CENTER_UID),
+                            &center_style,
+                            NodeContents::EMPTY,
+                        )?;
+                        gui.insert_node(Some(COLUMN_END_UID), &end_style, NodeContents::EMPTY)?;
+                        Ok(())
+                    }),
+                )?
+                .result?;
+                Ok(())
+            }),
+        )?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+
+    [
+        ROW_UID,
+        ROW_START_UID,
+        ROW_END_UID,
+        COLUMN_UID,
+        COLUMN_START_UID,
+        COLUMN_CENTER_UID,
+        COLUMN_END_UID,
+        POPUP_UID,
+    ]
+}
+
+#[test]
+fn rtl_layout_is_mirrored() {
+    use crate::style::*;
+    use crate::*;
+
+    let mut ltr_gui = ByorGui::<NullRenderer>::default();
+    let uids = directional_gui(&mut ltr_gui, UiDirection::Ltr);
+    let mut rtl_gui = ByorGui::<NullRenderer>::default();
+    directional_gui(&mut rtl_gui, UiDirection::Rtl);
+
+    for uid in uids {
+        let ltr = ltr_gui.expect_node(uid);
+        let rtl = rtl_gui.expect_node(uid);
+
+        assert_eq!(ltr.size(), rtl.size());
+        assert_eq!(rtl.position().y, ltr.position().y);
+        assert_eq!(rtl.position().x, 800.px() - ltr.position().x - ltr.size().x,);
+    }
+
+    // sanity check the left-to-right layout so the comparison above is meaningful
+    assert_eq!(ltr_gui.expect_node(uids[1]).position().x, 15.px());
+    assert_eq!(ltr_gui.expect_node(uids[7]).position().x, 405.px());
+}
+
+#[test]
+fn lenient_error_policy_collects_duplicate_uids() {
+    use crate::*;
+
+    const NODE_UID: Uid = Uid::from_array(b"node");
+    const LATER_UID: Uid = Uid::from_array(b"later");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let style = style! {
+        width: 10.px(),
+        height: 10.px(),
+    };
+    let build = |mut gui: ByorGuiContext<'_, NullRenderer>| -> widgets::WidgetResult<()> {
+        gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+        gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+        gui.insert_node(Some(NODE_UID), &style, NodeContents::EMPTY)?;
+        gui.insert_node(Some(LATER_UID), &style, NodeContents::EMPTY)?;
+        Ok(())
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    assert_eq!(gui.error_policy(), ErrorPolicy::Strict);
+    let report = gui.frame(screen_size, build);
+    assert!(report.result.is_err());
+    assert!(report.errors.is_empty());
+    assert!(gui.data.previous_state.get(LATER_UID).is_none());
+
+    gui.set_error_policy(ErrorPolicy::CollectAndContinue);
+    for _ in 0..2 {
+        let report = gui.frame(screen_size, build);
+        report.result.expect("error building GUI");
+        assert_eq!(report.errors.len(), 2);
+        assert!(gui.data.previous_state.get(LATER_UID).is_some());
+
+        let root = gui.forest.primary().unwrap();
+        let mut uids = Vec::new();
+        crate::forest::iter_children!(root.descendants => |node| uids.push(node.uid.unwrap()));
+        assert_eq!(uids.len(), 4);
+        assert!(
+            uids.iter()
+                .enumerate()
+                .all(|(i, uid)| !uids[..i].contains(uid))
+        );
+    }
+
+    let diagnostics = gui.take_diagnostics();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    );
+}
+
+#[test]
+fn nodes_reflect_last_frame() {
+    use crate::style::*;
+    use crate::*;
+
+    const PANEL_UID: Uid = Uid::from_array(b"panel");
+    const LABEL_UID: Uid = Uid::from_array(b"label");
+    const FLOATING_UID: Uid = Uid::from_array(b"floating");
+    const SIBLING_UID: Uid = Uid::from_array(b"sibling");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 50.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        gui.insert_node(
+            Some(PANEL_UID),
+            &Style::default(),
+            NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                gui.insert_node(Some(LABEL_UID), &style, NodeContents::text("hello"))?;
+                gui.insert_floating_node(
+                    FLOATING_UID,
+                    FloatPosition::CURSOR,
+                    &style,
+                    NodeContents::EMPTY,
+                )?;
+                Ok(())
+            }),
+        )?
+        .result?;
+        gui.insert_node(Some(SIBLING_UID), &style, NodeContents::text("world"))?;
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+
+    let nodes: Vec<_> = gui.nodes().collect();
+    let summary: Vec<_> = nodes
+        .iter()
+        .map(|node| {
+            (
+                node.uid(),
+                node.depth(),
+                node.is_floating_root(),
+                node.text(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (None, 0, false, None),
+            (Some(PANEL_UID), 1, false, None),
+            (Some(LABEL_UID), 2, false, Some("hello")),
+            (Some(FLOATING_UID), 2, true, None),
+            (Some(SIBLING_UID), 1, false, Some("world")),
+        ]
+    );
+
+    assert_eq!(nodes[0].size(), screen_size);
+    assert_eq!(nodes[4].position().x, nodes[1].size().x);
+    assert_eq!(
+        nodes[2].size(),
+        Vec2 {
+            x: 100.px(),
+            y: 50.px()
+        }
+    );
+
+    let result = gui
+        .try_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.insert_node(Some(PANEL_UID), &style, NodeContents::EMPTY)?;
+            gui.insert_node(Some(PANEL_UID), &style, NodeContents::EMPTY)?;
+            Ok(())
+        })
+        .result;
+    assert!(result.is_err());
+    assert_eq!(gui.nodes().count(), 0);
+}
+
+fn scrolled_gui(
+    gui: &mut crate::ByorGui<NullRenderer>,
+    item_count: usize,
+    item_height: f32,
+    proportional_resize: bool,
+) -> (crate::Float<crate::Pixel>, crate::Float<crate::Pixel>) {
+    use crate::style::*;
+    use crate::*;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: item_height.px(),
+    };
+
+    let scroll_view = widgets::ScrollView::vertical()
+        .with_uid(SCROLL_UID)
+        .with_style(&scroll_view_style)
+        .with_proportional_resize(proportional_resize);
+    gui.frame(screen_size, |mut gui| {
+        gui.show_container(scroll_view, |mut gui| -> widgets::WidgetResult<()> {
+            for i in 0..item_count {
+                gui.uid_scope(Uid::new(i), |gui| {
+                    gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                })?;
+            }
+            Ok(())
+        })?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+
+    let mut nodes = gui
+        .nodes()
+        .skip_while(|node| node.uid() != Some(SCROLL_UID));
+    let container = nodes.next().unwrap();
+    let first_item = nodes.next().unwrap();
+    let scroll = gui
+        .data
+        .persistent_state
+        .get(SCROLL_UID)
+        .unwrap()
+        .get::<Float<Pixel>>(PersistentStateKey::VerticalScroll)
+        .copied()
+        .unwrap();
+
+    (container.position().y - first_item.position().y, scroll)
+}
+
+#[test]
+fn wheel_scrolling_moves_by_whole_items() {
+    use crate::input::*;
+    use crate::*;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 30.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style)
+            .with_item_height(30.px());
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(scroll_view, |mut gui| -> widgets::WidgetResult<()> {
+                for i in 0..20 {
+                    gui.uid_scope(Uid::new(i), |gui| {
+                        gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                    })?;
+                }
+                Ok(())
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.data
+            .persistent_state
+            .get(SCROLL_UID)
+            .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+            .copied()
+            .unwrap_or_default()
+    };
+    let scroll = |gui: &mut ByorGui<NullRenderer>, delta: ScrollDelta| {
+        gui.on_input_event(InputEvent::Scrolled { delta });
+    };
+
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui);
+    frame(&mut gui);
+
+    scroll(
+        &mut gui,
+        ScrollDelta::Point(Vec2 {
+            x: 0.pt(),
+            y: -10.pt(),
+        }),
+    );
+    scroll(
+        &mut gui,
+        ScrollDelta::Pixel(Vec2 {
+            x: 0.px(),
+            y: -5.px(),
+        }),
+    );
+    let events = gui.input_state().scroll_events();
+    assert_eq!(events.len(), 2);
+    assert!(!events[0].is_precise && events[1].is_precise);
+    assert_eq!(gui.input_state().scroll_delta().y, -15.px());
+    // the wheel moves by at least one item, the touchpad by exactly its delta
+    assert_eq!(frame(&mut gui), 35.px());
+
+    scroll(
+        &mut gui,
+        ScrollDelta::Point(Vec2 {
+            x: 0.pt(),
+            y: -50.pt(),
+        }),
+    );
+    assert_eq!(frame(&mut gui), 95.px());
+    assert!(gui.input_state().scroll_events().is_empty());
+}
+
+#[test]
+fn scroll_views_report_reaching_the_end_once() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 30.px(),
+    };
+
+    // returns whether the view was near the end and whether it was scrolled
+    let frame = |gui: &mut ByorGui<NullRenderer>, item_count: u64| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(Uid::from_array(b"scroll"))
+            .with_style(&scroll_view_style)
+            .with_end_threshold(100.px());
+        let response = gui
+            .frame(screen_size, |mut gui| {
+                gui.show_container(scroll_view, |mut gui| -> widgets::WidgetResult<()> {
+                    for i in 0..item_count {
+                        gui.uid_scope(Uid::new(i), |gui| {
+                            gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                        })?;
+                    }
+                    Ok(())
+                })
+            })
+            .result
+            .expect("error building GUI");
+        (response.near_end, response.scrolled_this_frame)
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    assert_eq!(
+        frame(&mut gui, 10),
+        (false, false),
+        "the layout is not known yet"
+    );
+    assert_eq!(frame(&mut gui, 10), (false, false));
+
+    gui.on_input_event(InputEvent::Scrolled {
+        delta: ScrollDelta::Pixel(Vec2 {
+            x: 0.px(),
+            y: -150.px(),
+        }),
+    });
+    assert_eq!(frame(&mut gui, 10), (true, true));
+    assert_eq!(frame(&mut gui, 10), (false, false), "staying at the end");
+
+    // the next page takes effect one frame later, when its size is known
+    assert_eq!(frame(&mut gui, 11), (false, false));
+    assert_eq!(
+        frame(&mut gui, 11),
+        (true, false),
+        "still near the end of the new content"
+    );
+    assert_eq!(frame(&mut gui, 11), (false, false));
+}
+
+#[test]
+fn hovered_scroll_bars_take_the_wheel_from_outer_scroll_views() {
+    use crate::input::*;
+    use crate::*;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+    const BAR_UID: Uid = Uid::from_array(b"bar");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+    let bar_style = style! {
+        width: 150.px(),
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 30.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut value = 0.0;
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style);
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(scroll_view, |mut gui| -> widgets::WidgetResult<()> {
+                let bar = widgets::ScrollBar::horizontal()
+                    .with_uid(BAR_UID)
+                    .with_style(&bar_style)
+                    .with_value(value)
+                    .with_max(100.0)
+                    .with_step(10.0);
+                value = gui.show(bar)?;
+
+                for i in 0..20 {
+                    gui.uid_scope(Uid::new(i), |gui| {
+                        gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                    })?;
+                }
+                Ok(())
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        let scroll = gui
+            .data
+            .persistent_state
+            .get(SCROLL_UID)
+            .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+            .copied()
+            .unwrap_or_default();
+        (value, scroll)
+    };
+    let scroll_one_line_down = |gui: &mut ByorGui<NullRenderer>| {
+        gui.on_input_event(InputEvent::Scrolled {
+            delta: ScrollDelta::Point(Vec2 {
+                x: 0.pt(),
+                y: -POINTS_PER_SCROLL_LINE,
+            }),
+        });
+    };
+
+    // the unthemed bar's buttons are empty and all placed at the origin
+    gui.move_cursor(Vec2 {
+        x: 100.px(),
+        y: 500.px(),
+    });
+    frame(&mut gui);
+    let bar = gui.data.previous_state.get(BAR_UID).unwrap();
+    gui.move_cursor(bar.position + bar.size / 2.0);
+    frame(&mut gui);
+
+    scroll_one_line_down(&mut gui);
+    assert_eq!(frame(&mut gui), (10.0, 0.px()));
+
+    // below the bar the wheel scrolls the view again
+    let bar = gui.data.previous_state.get(BAR_UID).unwrap();
+    gui.move_cursor(
+        bar.position
+            + Vec2 {
+                x: 10.px(),
+                y: 50.px(),
+            },
+    );
+    frame(&mut gui);
+
+    scroll_one_line_down(&mut gui);
+    let (value, scroll) = frame(&mut gui);
+    assert_eq!(value, 10.0);
+    assert!(scroll > 0.px());
+}
+
+#[test]
+fn scroll_distance_follows_the_configuration() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_scale_factor(2.0);
+    gui.set_scroll_line_height(60.pt());
+    gui.set_scroll_speed_multiplier(1.5);
+
+    gui.on_input_event(InputEvent::Scrolled {
+        delta: ScrollDelta::Point(Vec2 {
+            x: 0.pt(),
+            y: POINTS_PER_SCROLL_LINE,
+        }),
+    });
+    gui.on_input_event(InputEvent::Scrolled {
+        delta: ScrollDelta::Pixel(Vec2 {
+            x: 10.px(),
+            y: 0.px(),
+        }),
+    });
+
+    let (events, step) = gui
+        .frame(screen_size, |gui| {
+            let events = gui.global_input_state().scroll_events().to_vec();
+            (events, gui.scroll_step())
+        })
+        .result;
+    assert_eq!(step, 90.pt());
+    assert_eq!(
+        events,
+        [
+            ScrollEvent {
+                delta: Vec2 {
+                    x: 0.px(),
+                    y: 180.px(),
+                },
+                is_precise: false,
+            },
+            ScrollEvent {
+                delta: Vec2 {
+                    x: 15.px(),
+                    y: 0.px(),
+                },
+                is_precise: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn floating_nodes_are_aligned_by_their_pivot() {
+    use crate::*;
+
+    const DIALOG_UID: Uid = Uid::from_array(b"dialog");
+    const TOOLTIP_UID: Uid = Uid::from_array(b"tooltip");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 200.px(),
+        height: 100.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.try_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        gui.centered_float(DIALOG_UID, |mut gui| {
+            gui.insert_node(None, &style, NodeContents::EMPTY)
+        })??;
+
+        let position = FloatPosition::Fixed {
+            x: 500.px().into(),
+            y: 400.px().into(),
+            pivot: Alignment2D::BOTTOM_RIGHT,
+        };
+        gui.insert_floating_node(TOOLTIP_UID, position, &style, NodeContents::EMPTY)?;
+
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+
+    let dialog = gui.data.previous_state.get(DIALOG_UID).unwrap();
+    assert_eq!(
+        dialog.position,
+        Vec2 {
+            x: 300.px(),
+            y: 250.px(),
+        }
+    );
+
+    let tooltip = gui.data.previous_state.get(TOOLTIP_UID).unwrap();
+    assert_eq!(
+        tooltip.position,
+        Vec2 {
+            x: 300.px(),
+            y: 300.px(),
+        }
+    );
+}
+
+#[test]
+fn popups_near_the_edge_are_placed_on_their_first_frame() {
+    use crate::*;
+
+    const POPUP_UID: Uid = Uid::from_array(b"popup");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let row_style = style! {
+        width: Sizing::Grow,
+        child_alignment: Alignment::End,
+    };
+    let anchor_style = style! {
+        width: 50.px(),
+        height: 20.px(),
+    };
+    let popup_style = style! {
+        max_width: 300.px(),
+        padding: 5.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut frame = || {
+        gui.frame(screen_size, |mut gui| {
+            let anchor = NodeContents::builder(|mut gui: ByorGuiContext<'_, NullRenderer>| {
+                let position = FloatPosition::Popup {
+                    x: PopupPosition::AfterParent,
+                    y: PopupPosition::BeforeParent,
+                };
+                let text = NodeContents::text(
+                    "a popup that is wide enough to need wrapping, so its size is only known after \
+                     layout",
+                );
+                gui.insert_floating_node(POPUP_UID, position, &popup_style, text)
+                    .map(|_| ())
+            });
+            let row = NodeContents::builder(|mut gui: ByorGuiContext<'_, NullRenderer>| {
+                gui.insert_node(None, &anchor_style, anchor)
+                    .and_then(|response| response.result)
+            });
+            gui.insert_node(None, &row_style, row)
+                .and_then(|response| response.result)
+        })
+        .result
+        .expect("error building GUI");
+
+        let popup = gui.data.previous_state.get(POPUP_UID).unwrap();
+        (popup.position, popup.size)
+    };
+
+    let (first_position, size) = frame();
+    let (second_position, _) = frame();
+    assert_eq!(first_position, second_position);
+    // neither after nor above the anchor fits, so the popup is mirrored to the other side
+    assert_eq!(first_position.x, 750.px() - size.x);
+    assert_eq!(first_position.y, 20.px());
+}
+
+#[test]
+fn shortcuts_are_formatted_per_platform() {
+    use crate::input::*;
+
+    let save_as = Shortcut {
+        modifiers: Modifiers::CONTROL | Modifiers::SHIFT,
+        key: "s".into(),
+        location: None,
+    };
+    let submit = Shortcut {
+        modifiers: Modifiers::META | Modifiers::ALT,
+        key: NamedKey::Enter.into(),
+        location: None,
+    };
+    let move_up = Shortcut {
+        modifiers: Modifiers::ALT,
+        key: NamedKey::ArrowUp.into(),
+        location: None,
+    };
+
+    assert_eq!(format_shortcut(&save_as, Platform::Windows), "Ctrl+Shift+S");
+    assert_eq!(format_shortcut(&save_as, Platform::Mac), "⌃⇧S");
+    assert_eq!(format_shortcut(&submit, Platform::Windows), "Win+Alt+Enter");
+    assert_eq!(format_shortcut(&submit, Platform::Linux), "Super+Alt+Enter");
+    assert_eq!(format_shortcut(&submit, Platform::Mac), "⌥⌘↩");
+    assert_eq!(format_shortcut(&move_up, Platform::Linux), "Alt+↑");
+    assert_eq!(format_shortcut(&move_up, Platform::Mac), "⌥↑");
+}
+
+#[test]
+fn measured_text_matches_the_laid_out_node() {
+    use crate::*;
+
+    const SHORT_UID: Uid = Uid::from_array(b"short");
+    const WRAPPED_UID: Uid = Uid::from_array(b"wrapped");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        padding: 4.px(),
+    };
+    let wrapped_style = style! {
+        padding: 4.px(),
+        max_width: 120.px(),
+    };
+    let long_text = "a text that is far too long to fit into a single line of the node";
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let (short, wrapped, metrics) = gui
+        .frame(screen_size, |mut gui| {
+            let short = gui.measure_text("short", &style);
+            let wrapped = gui.measure_wrapped_text(long_text, &style, 120.px());
+            let metrics = gui.text_metrics(long_text, &style, Some(120.px()));
+
+            gui.insert_node(Some(SHORT_UID), &style, NodeContents::text("short"))
+                .unwrap();
+            gui.insert_node(
+                Some(WRAPPED_UID),
+                &wrapped_style,
+                NodeContents::text(long_text),
+            )
+            .unwrap();
+
+            (short, wrapped, metrics)
+        })
+        .result;
+
+    // measuring doesn't insert anything
+    assert_eq!(gui.nodes().count(), 3);
+    assert_eq!(short, gui.data.previous_state.get(SHORT_UID).unwrap().size);
+    assert_eq!(
+        wrapped,
+        gui.data.previous_state.get(WRAPPED_UID).unwrap().size
+    );
+    assert_eq!(metrics.size, wrapped);
+    assert!(metrics.line_count > 1);
+    assert!(metrics.baseline > 4.px());
+    assert!(metrics.ascent <= metrics.line_height);
+}
+
+#[test]
+fn long_words_wrap_according_to_the_overflow_wrap() {
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let word = "Donaudampfschifffahrtsgesellschaft";
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let (normal, break_word) = gui
+        .frame(screen_size, |gui| {
+            let normal = style! {
+                overflow_wrap: OverflowWrap::Normal,
+            };
+            let normal = gui.text_metrics(word, &normal, Some(60.px()));
+            let break_word = gui.text_metrics(word, &Style::DEFAULT, Some(60.px()));
+
+            (normal, break_word)
+        })
+        .result;
+
+    assert_eq!(normal.line_count, 1);
+    assert!(break_word.line_count > 1);
+}
+
+#[cfg(feature = "hyphenation")]
+#[test]
+fn hyphenation_breaks_words_at_syllables_for_the_locale() {
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let word = "Donaudampfschifffahrtsgesellschaft";
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let (german, unsupported) = gui
+        .frame(screen_size, |gui| {
+            let german = style! {
+                overflow_wrap: OverflowWrap::Normal,
+                hyphenation: true,
+                locale: "de-DE",
+            };
+            let german = gui.text_metrics(word, &german, Some(100.px()));
+
+            let unsupported = style! {
+                overflow_wrap: OverflowWrap::Normal,
+                hyphenation: true,
+                locale: "xx",
+            };
+            let unsupported = gui.text_metrics(word, &unsupported, Some(100.px()));
+
+            (german, unsupported)
+        })
+        .result;
+
+    assert!(german.line_count > 1);
+    assert_eq!(unsupported.line_count, 1);
+}
+
+#[test]
+fn zoom_gestures_accumulate_per_frame() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let center = Vec2 {
+        x: 30.px(),
+        y: 40.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.on_input_event(InputEvent::Zoom {
+        delta: 0.25,
+        center: Vec2::ZERO,
+    });
+    let wheel = ScrollDelta::Point(Vec2 {
+        x: 0.pt(),
+        y: POINTS_PER_SCROLL_LINE,
+    });
+    gui.on_input_event(InputEvent::Zoom {
+        delta: wheel.to_zoom_delta(gui.scale_factor()),
+        center,
+    });
+
+    let (delta, zoom_center) = gui
+        .frame(screen_size, |gui| {
+            let input_state = gui.global_input_state();
+            (input_state.zoom_delta(), input_state.zoom_center())
+        })
+        .result;
+    assert_eq!(delta, 0.25 + ZOOM_PER_SCROLL_LINE);
+    assert_eq!(zoom_center, Some(center));
+
+    let (delta, zoom_center) = gui
+        .frame(screen_size, |gui| {
+            let input_state = gui.global_input_state();
+            (input_state.zoom_delta(), input_state.zoom_center())
+        })
+        .result;
+    assert_eq!(delta, 0.0);
+    assert_eq!(zoom_center, None);
+}
+
+fn set_vertical_scroll(gui: &mut crate::ByorGui<NullRenderer>, scroll: f32) {
+    use crate::*;
+
+    let uid = Uid::from_array(b"scroll");
+    gui.data
+        .persistent_state
+        .entry(uid)
+        .or_default()
+        .insert(PersistentStateKey::VerticalScroll, scroll.px());
+}
+
+#[test]
+fn scroll_offset_is_clamped_to_content() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    scrolled_gui(&mut gui, 20, 20.0, false);
+
+    // 400px of content in a 100px container
+    set_vertical_scroll(&mut gui, 10_000.0);
+    assert_eq!(
+        scrolled_gui(&mut gui, 20, 20.0, false),
+        (300.px(), 300.px())
+    );
+    set_vertical_scroll(&mut gui, -50.0);
+    assert_eq!(scrolled_gui(&mut gui, 20, 20.0, false), (0.px(), 0.px()));
+
+    // the content shrinks within the same frame
+    set_vertical_scroll(&mut gui, 250.0);
+    assert_eq!(
+        scrolled_gui(&mut gui, 20, 20.0, false),
+        (250.px(), 250.px())
+    );
+    assert_eq!(
+        scrolled_gui(&mut gui, 10, 20.0, false),
+        (100.px(), 100.px())
+    );
+    assert_eq!(scrolled_gui(&mut gui, 4, 20.0, false), (0.px(), 0.px()));
+    assert_eq!(scrolled_gui(&mut gui, 4, 20.0, false), (0.px(), 0.px()));
+}
+
+#[test]
+fn scroll_offset_is_rescaled_proportionally() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    scrolled_gui(&mut gui, 20, 20.0, true);
+
+    set_vertical_scroll(&mut gui, 150.0);
+    assert_eq!(scrolled_gui(&mut gui, 20, 20.0, true), (150.px(), 150.px()));
+    assert_eq!(scrolled_gui(&mut gui, 20, 40.0, true), (300.px(), 300.px()));
+    assert_eq!(scrolled_gui(&mut gui, 20, 40.0, true), (300.px(), 300.px()));
+    assert_eq!(scrolled_gui(&mut gui, 20, 10.0, true), (75.px(), 75.px()));
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    scrolled_gui(&mut gui, 20, 20.0, false);
+
+    set_vertical_scroll(&mut gui, 150.0);
+    assert_eq!(
+        scrolled_gui(&mut gui, 20, 40.0, false),
+        (150.px(), 150.px())
+    );
+}
+
+#[test]
+fn tab_moves_the_focus_in_tab_index_order() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            let nodes = [
+                ("a", NodeContents::EMPTY.with_tab_index(2)),
+                ("b", NodeContents::EMPTY.with_keyboard_activation()),
+                ("c", NodeContents::EMPTY.with_tab_index(1)),
+                ("d", NodeContents::EMPTY.with_tab_index(0)),
+                (
+                    "e",
+                    NodeContents::EMPTY
+                        .with_keyboard_activation()
+                        .with_tab_index(-1),
+                ),
+                ("f", NodeContents::EMPTY.with_tab_index(1)),
+                ("g", NodeContents::EMPTY),
+            ];
+            for (name, contents) in nodes {
+                gui.insert_node(Some(Uid::from_slice(name.as_bytes())), &style, contents)?;
+            }
+            Ok(())
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(key),
+            location: KeyLocation::Left,
+            text: None,
+            repeat: false,
+        });
+    };
+    let release = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: Key::Named(key),
+            location: KeyLocation::Left,
+            text: None,
+        });
+    };
+    let tab = |gui: &mut ByorGui<NullRenderer>| {
+        press(gui, NamedKey::Tab);
+        frame(gui);
+        release(gui, NamedKey::Tab);
+        gui.data.focused_node
+    };
+    let uid = |name: &str| Some(Uid::from_slice(name.as_bytes()));
+
+    frame(&mut gui);
+    let order: Vec<_> = (0..6).map(|_| tab(&mut gui)).collect();
+    assert_eq!(
+        order,
+        ["c", "f", "a", "b", "d", "c"].map(uid),
+        "positive indices first, then insertion order, skipping excluded nodes"
+    );
+
+    press(&mut gui, NamedKey::Shift);
+    assert_eq!(tab(&mut gui), uid("d"), "Shift+Tab goes back");
+    assert_eq!(tab(&mut gui), uid("b"));
+    release(&mut gui, NamedKey::Shift);
+    assert_eq!(tab(&mut gui), uid("d"));
+}
+
+#[test]
+fn focused_buttons_are_activated_by_keyboard() {
+    use crate::input::*;
+    use crate::*;
+
+    const BUTTON_UID: Uid = Uid::from_array(b"button");
+    const TEXT_BOX_UID: Uid = Uid::from_array(b"text_box");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+    let space = Key::Character(" ".into());
+    let enter = Key::Named(NamedKey::Enter);
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut text = String::new();
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+            let button = widgets::Button::default()
+                .with_text("button")
+                .with_uid(BUTTON_UID)
+                .with_style(&style);
+            let response = gui.show(button)?;
+            let unconsumed_events = gui.global_input_state().key_events().len();
+            let text_box = widgets::TextBox::new(&mut text)
+                .with_uid(TEXT_BOX_UID)
+                .with_style(&style);
+            gui.show(text_box)?;
+            Ok((response, unconsumed_events))
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let click_at =
+        |gui: &mut ByorGui<NullRenderer>,
+         frame: &mut dyn FnMut(&mut ByorGui<NullRenderer>) -> (NodeInputState, usize),
+         x: f32| {
+            gui.on_input_event(InputEvent::CursorMoved {
+                position: Vec2 {
+                    x: x.px(),
+                    y: 10.px(),
+                },
+            });
+            frame(gui);
+            gui.on_input_event(InputEvent::ButtonPressed {
+                button: MouseButton::Primary,
+            });
+            frame(gui);
+            gui.on_input_event(InputEvent::ButtonReleased {
+                button: MouseButton::Primary,
+            });
+            frame(gui);
+            gui.on_input_event(InputEvent::CursorMoved {
+                position: Vec2 {
+                    x: 700.px(),
+                    y: 500.px(),
+                },
+            });
+            frame(gui);
+        };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: &Key| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: key.clone(),
+            location: KeyLocation::Standard,
+            text: None,
+            repeat: false,
+        });
+    };
+    let release = |gui: &mut ByorGui<NullRenderer>, key: &Key| {
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: key.clone(),
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+
+    // not focused yet
+    press(&mut gui, &enter);
+    let (response, unconsumed_events) = frame(&mut gui);
+    assert!(!response.clicked(MouseButtons::PRIMARY));
+    assert_eq!(unconsumed_events, 1);
+    release(&mut gui, &enter);
+    frame(&mut gui);
+
+    click_at(&mut gui, &mut frame, 10.0);
+    assert!(frame(&mut gui).0.focused);
+
+    press(&mut gui, &enter);
+    let (response, unconsumed_events) = frame(&mut gui);
+    assert!(response.clicked(MouseButtons::PRIMARY));
+    assert!(!response.pressed(MouseButtons::PRIMARY));
+    assert_eq!(unconsumed_events, 0);
+    release(&mut gui, &enter);
+    let (response, unconsumed_events) = frame(&mut gui);
+    assert!(!response.clicked(MouseButtons::PRIMARY));
+    assert_eq!(unconsumed_events, 0);
+
+    press(&mut gui, &space);
+    let (response, _) = frame(&mut gui);
+    assert!(response.clicked(MouseButtons::PRIMARY));
+    assert!(response.pressed(MouseButtons::PRIMARY));
+    let (response, _) = frame(&mut gui);
+    assert!(!response.clicked(MouseButtons::PRIMARY));
+    assert!(response.pressed(MouseButtons::PRIMARY));
+    release(&mut gui, &space);
+    let (response, _) = frame(&mut gui);
+    assert!(!response.pressed(MouseButtons::PRIMARY));
+    assert!(response.released(MouseButtons::PRIMARY));
+
+    // text boxes keep receiving the keys
+    click_at(&mut gui, &mut frame, 110.0);
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: space.clone(),
+        location: KeyLocation::Standard,
+        text: Some(" ".into()),
+        repeat: false,
+    });
+    let (response, unconsumed_events) = frame(&mut gui);
+    assert!(!response.clicked(MouseButtons::PRIMARY));
+    assert!(!response.pressed(MouseButtons::PRIMARY));
+    assert_eq!(unconsumed_events, 1);
+    release(&mut gui, &space);
+    frame(&mut gui);
+    assert_eq!(text, " ");
+}
+
+#[test]
+fn focused_text_box_requests_animation_frames() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut text = String::new();
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            let text_box = widgets::TextBox::new(&mut text)
+                .with_uid(Uid::from_array(b"text_box"))
+                .with_style(&style);
+            gui.show(text_box)
+        })
+        .result
+        .expect("error building GUI");
+    };
+
+    frame(&mut gui);
+    assert!(!gui.needs_animation());
+
+    gui.on_input_event(InputEvent::CursorMoved {
+        position: Vec2 {
+            x: 10.px(),
+            y: 10.px(),
+        },
+    });
+    gui.on_input_event(InputEvent::ButtonPressed {
+        button: MouseButton::Primary,
+    });
+    frame(&mut gui);
+    gui.on_input_event(InputEvent::ButtonReleased {
+        button: MouseButton::Primary,
+    });
+    frame(&mut gui);
+    assert!(gui.needs_animation());
+    let next_frame = gui.next_animation_frame().unwrap();
+    assert!(next_frame <= std::time::Instant::now() + gui.caret_blink_interval().unwrap());
+
+    gui.set_caret_blink_interval(None);
+    frame(&mut gui);
+    assert!(!gui.needs_animation());
+}
+
+#[test]
+fn click_count_tracks_repeated_clicks() {
+    use crate::input::*;
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut click_at = |x: f32| {
+        gui.on_input_event(InputEvent::CursorMoved {
+            position: Vec2 {
+                x: x.px(),
+                y: 10.px(),
+            },
+        });
+        gui.on_input_event(InputEvent::ButtonPressed {
+            button: MouseButton::Primary,
+        });
+        gui.on_input_event(InputEvent::ButtonReleased {
+            button: MouseButton::Primary,
+        });
+        gui.input_state().click_count()
+    };
+
+    assert_eq!(click_at(10.0), 1);
+    assert_eq!(click_at(11.0), 2);
+    assert_eq!(click_at(10.0), 3);
+    assert_eq!(click_at(100.0), 1);
+
+    gui.on_input_event(InputEvent::ButtonPressed {
+        button: MouseButton::Secondary,
+    });
+    assert_eq!(gui.input_state().click_count(), 1);
+}
+
+#[test]
+fn deferred_text_box_commits_on_submit_and_blur() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut text = String::new();
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        let response = gui
+            .frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+                let text_box = widgets::TextBox::deferred(&mut text)
+                    .with_single_line(true)
+                    .with_uid(Uid::from_array(b"text_box"))
+                    .with_style(&style);
+                let response = gui.show(text_box)?;
+                let button = widgets::Button::default()
+                    .with_text("button")
+                    .with_uid(Uid::from_array(b"button"))
+                    .with_style(&style);
+                gui.show(button)?;
+                Ok(response)
+            })
+            .result
+            .expect("error building GUI");
+        (response, text.clone())
+    };
+    let click_at = |gui: &mut ByorGui<NullRenderer>, x: f32| {
+        gui.on_input_event(InputEvent::CursorMoved {
+            position: Vec2 {
+                x: x.px(),
+                y: 10.px(),
+            },
+        });
+        gui.on_input_event(InputEvent::ButtonPressed {
+            button: MouseButton::Primary,
+        });
+    };
+    let release = |gui: &mut ByorGui<NullRenderer>| {
+        gui.on_input_event(InputEvent::ButtonReleased {
+            button: MouseButton::Primary,
+        });
+    };
+    let type_key = |gui: &mut ByorGui<NullRenderer>, key: Key, text: Option<&str>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key,
+            location: KeyLocation::Standard,
+            text: text.map(Into::into),
+            repeat: false,
+        });
+    };
+
+    frame(&mut gui);
+    click_at(&mut gui, 10.0);
+    frame(&mut gui);
+    release(&mut gui);
+    frame(&mut gui);
+
+    type_key(&mut gui, Key::Character("a".into()), Some("a"));
+    let (response, text) = frame(&mut gui);
+    assert!(response.changed && !response.submitted && !response.focus_lost);
+    assert_eq!(text, "");
+
+    type_key(&mut gui, Key::Named(NamedKey::Enter), Some("\r"));
+    let (response, text) = frame(&mut gui);
+    assert!(!response.changed && response.submitted);
+    assert_eq!(text, "a");
+
+    type_key(&mut gui, Key::Character("b".into()), Some("b"));
+    let (_, text) = frame(&mut gui);
+    assert_eq!(text, "a");
+
+    click_at(&mut gui, 110.0);
+    let (response, _) = frame(&mut gui);
+    assert!(!response.focus_lost);
+    release(&mut gui);
+    let (response, text) = frame(&mut gui);
+    assert!(response.focus_lost);
+    assert_eq!(text, "ab");
+    let (response, _) = frame(&mut gui);
+    assert_eq!(response, widgets::TextBoxResponse::default());
+}
+
+#[test]
+fn text_box_filter_rejects_insertions() {
+    use crate::input::*;
+    use crate::widgets::TextFilter;
+    use crate::*;
+
+    let decimal = TextFilter::decimal().and(TextFilter::max_len(4));
+    assert!(decimal.matches(""));
+    assert!(decimal.matches("-"));
+    assert!(decimal.matches("-1.5"));
+    assert!(!decimal.matches("1.5.")); // second decimal point
+    assert!(!decimal.matches("12345"));
+    assert!(!TextFilter::integer().matches("1.5"));
+    assert!(!TextFilter::integer().matches("1-"));
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut text = String::from("1");
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            let text_box = widgets::TextBox::new(&mut text)
+                .with_filter(|text: &str| !text.starts_with('0'))
+                .with_uid(Uid::from_array(b"text_box"))
+                .with_style(&style);
+            gui.show(text_box)
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let type_text = |gui: &mut ByorGui<NullRenderer>, text: &str| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Character(text.into()),
+            location: KeyLocation::Standard,
+            text: Some(text.into()),
+            repeat: false,
+        });
+    };
+
+    gui.on_input_event(InputEvent::CursorMoved {
+        position: Vec2 {
+            x: 1.px(),
+            y: 10.px(),
+        },
+    });
+    gui.on_input_event(InputEvent::ButtonPressed {
+        button: MouseButton::Primary,
+    });
+    frame(&mut gui);
+    gui.on_input_event(InputEvent::ButtonReleased {
+        button: MouseButton::Primary,
+    });
+
+    // the caret was placed in front of the `1`
+    type_text(&mut gui, "0");
+    let response = frame(&mut gui);
+    assert!(response.rejected && !response.changed);
+    type_text(&mut gui, "2");
+    let response = frame(&mut gui);
+    assert!(!response.rejected && response.changed);
+    type_text(&mut gui, "0");
+    let response = frame(&mut gui);
+    assert!(!response.rejected && response.changed);
+    assert_eq!(text, "201");
+}
+
+#[test]
+fn input_positions_are_relative_to_the_gui() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 200.px(),
+        y: 100.px(),
+    };
+    let style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(
+                widgets::Button::default()
+                    .with_uid(Uid::from_array(b"button"))
+                    .with_style(&style),
+            )
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    frame(&mut gui);
+    assert_eq!(gui.screen_size(), screen_size);
+
+    // a host showing the GUI in a part of its window passes positions outside of it as negative
+    gui.move_cursor(Vec2 {
+        x: -10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui);
+    assert!(!frame(&mut gui).is_hovered());
+
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui);
+    assert!(frame(&mut gui).is_hovered());
+
+    gui.press_button(MouseButton::Primary);
+    assert!(frame(&mut gui).clicked(MouseButtons::PRIMARY));
+    gui.release_button(MouseButton::Primary);
+    assert!(frame(&mut gui).released(MouseButtons::PRIMARY));
+}
+
+#[test]
+fn recorded_input_replays_the_same_interaction() {
+    use crate::input::*;
+    use crate::*;
+    use std::time::{Duration, Instant};
+
+    let screen_size = Vec2 {
+        x: 200.px(),
+        y: 100.px(),
+    };
+    let style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(
+                widgets::Button::default()
+                    .with_uid(Uid::from_array(b"button"))
+                    .with_style(&style),
+            )
+        })
+        .result
+        .expect("error building GUI")
+        .clicked(MouseButtons::PRIMARY)
+    };
+
+    let start = Instant::now();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_time(start);
+    frame(&mut gui);
+
+    gui.input_recording(true);
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    gui.set_time(start + Duration::from_millis(16));
+    frame(&mut gui);
+    gui.press_button(MouseButton::Primary);
+    gui.set_time(start + Duration::from_millis(32));
+    assert!(frame(&mut gui));
+    gui.release_button(MouseButton::Primary);
+    gui.input_recording(false);
+    gui.move_cursor(Vec2::ZERO);
+
+    let recording = gui.take_recorded_input();
+    let times: Vec<_> = recording.iter().map(|recorded| recorded.time).collect();
+    assert_eq!(
+        times,
+        [0, 16, 32].map(Duration::from_millis),
+        "events are timestamped with the frame clock"
+    );
+
+    let mut replay = ByorGui::<NullRenderer>::default();
+    frame(&mut replay);
+    let mut clicked = false;
+    for time in times {
+        replay.inject_events(
+            recording
+                .iter()
+                .filter(|recorded| recorded.time == time)
+                .map(|recorded| recorded.event.clone()),
+        );
+        clicked |= frame(&mut replay);
+    }
+    assert!(clicked);
+}
+
+#[test]
+fn leaving_the_window_clears_hover_unless_captured() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 200.px(),
+        y: 100.px(),
+    };
+    let style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+    };
+    let position = Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(
+                widgets::Button::default()
+                    .with_uid(Uid::from_array(b"button"))
+                    .with_style(&style),
+            )
+        })
+        .result
+        .expect("error building GUI")
+        .is_hovered()
+    };
+
+    gui.move_cursor(position);
+    frame(&mut gui);
+    assert!(frame(&mut gui));
+
+    gui.on_input_event(InputEvent::CursorLeft);
+    frame(&mut gui);
+    assert!(!frame(&mut gui));
+    assert_eq!(gui.input_state().cursor_position(), None);
+    assert_eq!(gui.input_state().last_cursor_position(), position);
+
+    gui.on_input_event(InputEvent::CursorEntered { position });
+    frame(&mut gui);
+    assert!(frame(&mut gui));
+
+    // dragging out of the window keeps the pressed node hovered
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui);
+    gui.on_input_event(InputEvent::CursorLeft);
+    frame(&mut gui);
+    assert!(frame(&mut gui));
+    assert_eq!(gui.input_state().cursor_position(), Some(position));
+
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui);
+    assert!(!frame(&mut gui));
+}
+
+#[test]
+fn extra_mouse_buttons_are_tracked() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 200.px(),
+        y: 100.px(),
+    };
+    let style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(
+                widgets::Button::default()
+                    .with_uid(Uid::from_array(b"button"))
+                    .with_style(&style),
+            )
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui);
+
+    gui.press_button(MouseButton::Other(7));
+    let response = frame(&mut gui);
+    assert!(response.clicked(MouseButtons::EXTRA8));
+    assert!(!response.clicked(MouseButtons::PRIMARY));
+    assert!(response.pressed(MouseButtons::EXTRA8));
+
+    gui.press_button(MouseButton::Other(0));
+    let response = frame(&mut gui);
+    assert!(response.clicked(MouseButtons::EXTRA1));
+    assert!(!response.clicked(MouseButtons::EXTRA8));
+    assert!(response.pressed(MouseButtons::EXTRA1 | MouseButtons::EXTRA8));
+
+    gui.release_button(MouseButton::Other(7));
+    let response = frame(&mut gui);
+    assert!(response.released(MouseButtons::EXTRA8));
+    assert!(response.pressed(MouseButtons::EXTRA1));
+
+    // buttons without a flag are ignored
+    gui.press_button(MouseButton::Other(MAX_EXTRA_MOUSE_BUTTONS));
+    assert_eq!(gui.input_state().pressed_buttons(), MouseButtons::EXTRA1);
+}
+
+#[test]
+fn viewports_resolve_hover_separately() {
+    use crate::input::*;
+    use crate::*;
+
+    const LEFT: ViewportId = ViewportId::from_uid(Uid::from_array(b"left"));
+    const RIGHT: ViewportId = ViewportId::from_uid(Uid::from_array(b"right"));
+
+    let viewports = [
+        ViewportDesc {
+            id: LEFT,
+            size: Vec2 {
+                x: 800.px(),
+                y: 600.px(),
+            },
+        },
+        ViewportDesc {
+            id: RIGHT,
+            size: Vec2 {
+                x: 400.px(),
+                y: 300.px(),
+            },
+        },
+    ];
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame_multi(&viewports, |mut viewports| -> widgets::WidgetResult<_> {
+            let left = viewports.viewport(LEFT, |mut gui| {
+                gui.show(
+                    widgets::Button::default()
+                        .with_text("left")
+                        .with_uid(Uid::from_array(b"left"))
+                        .with_style(&style),
+                )
+            })?;
+            let right = viewports.viewport(RIGHT, |mut gui| {
+                gui.show(
+                    widgets::Button::default()
+                        .with_text("right")
+                        .with_uid(Uid::from_array(b"right"))
+                        .with_style(&style),
+                )
+            })?;
+            Ok((left.is_hovered(), right.is_hovered()))
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    gui.on_viewport_input_event(
+        RIGHT,
+        InputEvent::CursorMoved {
+            position: Vec2 {
+                x: 10.px(),
+                y: 10.px(),
+            },
+        },
+    );
+    frame(&mut gui);
+    assert_eq!(frame(&mut gui), (false, true));
+
+    gui.on_viewport_input_event(
+        LEFT,
+        InputEvent::CursorMoved {
+            position: Vec2 {
+                x: 10.px(),
+                y: 10.px(),
+            },
+        },
+    );
+    frame(&mut gui);
+    assert_eq!(frame(&mut gui), (true, false));
+
+    let sizes: Vec<_> = gui
+        .nodes()
+        .filter(|node| node.depth() == 0)
+        .map(|node| node.size())
+        .collect();
+    assert_eq!(sizes, [viewports[0].size, viewports[1].size]);
+
+    gui.render_viewport(RIGHT, &mut NullRenderer).unwrap();
+}
+
+/// Fails the `fail_at`th call to any of its methods.
+struct FailingRenderer {
+    calls: usize,
+    fail_at: usize,
+}
+
+impl FailingRenderer {
+    fn call(&mut self) -> Result<(), &'static str> {
+        self.calls += 1;
+        if self.calls == self.fail_at {
+            Err("mock failure")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl crate::rendering::Renderer for FailingRenderer {
+    type Error = &'static str;
+
+    fn push_clip_rect(
+        &mut self,
+        _position: crate::Vec2<crate::Pixel>,
+        _size: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.call()
+    }
+
+    fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+        self.call()
+    }
+
+    fn draw_rect(
+        &mut self,
+        _position: crate::Vec2<crate::Pixel>,
+        _size: crate::Vec2<crate::Pixel>,
+        _corner_radius: crate::Float<crate::Pixel>,
+        _stroke_width: crate::Float<crate::Pixel>,
+        _color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        self.call()
+    }
+
+    fn fill_rect(
+        &mut self,
+        _position: crate::Vec2<crate::Pixel>,
+        _size: crate::Vec2<crate::Pixel>,
+        _corner_radius: crate::Float<crate::Pixel>,
+        _brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        self.call()
+    }
+
+    fn draw_poly(
+        &mut self,
+        _vertices: &[crate::Vec2<crate::Pixel>],
+        _stroke_width: crate::Float<crate::Pixel>,
+        _color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        self.call()
+    }
+
+    fn fill_poly(
+        &mut self,
+        _vertices: &[crate::Vec2<crate::Pixel>],
+        _brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        self.call()
+    }
+
+    fn draw_text(
+        &mut self,
+        _text: parley::GlyphRun<'_, crate::style::Color>,
+        _position: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.call()
+    }
+}
+
+#[test]
+fn render_errors_name_the_failing_node() {
+    use crate::rendering::RenderPhase;
+    use crate::*;
+
+    const FIRST_UID: Uid = Uid::from_array(b"first");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 10.px(),
+        height: 10.px(),
+    };
+
+    let mut gui = ByorGui::<FailingRenderer>::default();
+    gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+        gui.insert_node(Some(FIRST_UID), &style, NodeContents::EMPTY)?;
+        gui.insert_node(
+            Some(Uid::from_array(b"second")),
+            &style,
+            NodeContents::EMPTY,
+        )?;
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+
+    // root background, root clip, then the background of the first node
+    let mut renderer = FailingRenderer {
+        calls: 0,
+        fail_at: 3,
+    };
+    let error = gui.render(&mut renderer).unwrap_err();
+    assert_eq!(error.uid, Some(FIRST_UID));
+    assert_eq!(error.phase, RenderPhase::Background);
+    assert_eq!(error.source, "mock failure");
+
+    gui.set_lenient_rendering(true);
+    let mut renderer = FailingRenderer {
+        calls: 0,
+        fail_at: 3,
+    };
+    gui.render(&mut renderer).unwrap();
+    // everything but the failed background was still rendered
+    assert_eq!(renderer.calls, 9);
+
+    let diagnostics = gui.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].uid, Some(FIRST_UID));
+}
+
+#[test]
+fn node_tags_are_visible_to_renderers_and_traversal() {
+    use crate::rendering::*;
+    use crate::*;
+    use std::sync::{Arc, Mutex};
+
+    const CHART_UID: Uid = Uid::from_array(b"chart");
+
+    struct Series(Vec<f32>);
+
+    struct ChartRenderer(Arc<Mutex<Option<f32>>>);
+
+    impl NodeRenderer for ChartRenderer {
+        type Renderer = NullRenderer;
+
+        fn render(
+            &self,
+            context: RenderContext<'_, NullRenderer>,
+        ) -> Result<(), std::convert::Infallible> {
+            *self.0.lock().unwrap() = context.tag::<Series>().map(|series| series.0.iter().sum());
+            Ok(())
+        }
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let style = style! {
+        width: 100.px(),
+        height: 50.px(),
+    };
+
+    let sum = Arc::default();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| {
+        let contents = NodeContents::renderer(ChartRenderer(Arc::clone(&sum)))
+            .with_tag(Series(vec![1.0, 2.0, 3.0]));
+        gui.insert_node(Some(CHART_UID), &style, contents)
+            .map(|_| ())
+    })
+    .result
+    .expect("error building GUI");
+    gui.render(&mut NullRenderer).unwrap();
+    assert_eq!(*sum.lock().unwrap(), Some(6.0));
+
+    let chart = gui
+        .nodes()
+        .find(|node| node.uid() == Some(CHART_UID))
+        .unwrap();
+    assert_eq!(chart.tag::<Series>().map(|series| series.0.len()), Some(3));
+    assert!(chart.tag::<u32>().is_none());
+    assert!(
+        gui.nodes()
+            .filter(|node| node.uid() != Some(CHART_UID))
+            .all(|node| node.tag::<Series>().is_none())
+    );
+}
+
+#[test]
+fn color_tokens_are_visible_to_property_fns_and_renderers() {
+    use crate::rendering::*;
+    use crate::*;
+    use std::sync::{Arc, Mutex};
+
+    const UID: Uid = Uid::from_array(b"accented");
+    const ACCENT: Color = Color::rgb(66, 135, 245);
+
+    struct AccentRenderer(Arc<Mutex<Option<Color>>>);
+
+    impl NodeRenderer for AccentRenderer {
+        type Renderer = NullRenderer;
+
+        fn render(
+            &self,
+            context: RenderContext<'_, NullRenderer>,
+        ) -> Result<(), std::convert::Infallible> {
+            *self.0.lock().unwrap() = context.theme().color_token("accent");
+            Ok(())
+        }
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let text_color: PropertyFn<Color> =
+        |_, _, _, theme| theme.color_token("accent").unwrap_or(INITIAL_TEXT_COLOR);
+    let style = style! {
+        width: 100.px(),
+        height: 50.px(),
+        text_color: text_color,
+    };
+
+    let accent = Arc::default();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.theme_mut().insert_token("accent", ACCENT);
+    gui.frame(screen_size, |mut gui| {
+        let contents =
+            NodeContents::renderer(AccentRenderer(Arc::clone(&accent))).with_style_snapshot();
+        gui.insert_node(Some(UID), &style, contents).map(|_| ())
+    })
+    .result
+    .expect("error building GUI");
+    gui.render(&mut NullRenderer).unwrap();
+
+    assert_eq!(*accent.lock().unwrap(), Some(ACCENT));
+    assert_eq!(gui.computed_style(UID).unwrap().text_color(), ACCENT);
+    assert_eq!(gui.theme().color_token("missing"), None);
+}
+
+#[test]
+fn color_roles_follow_the_theme() {
+    use crate::style::computed::ComputedBrush;
+    use crate::theme::ColorRole;
+    use crate::*;
+
+    const UID: Uid = Uid::from_array(b"role");
+    const DANGER: Color = Color::rgb(200, 0, 0);
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 50.px(),
+        background: ColorRole::Danger,
+        text_color: ColorRole::OnSurface,
+        border_color: ColorRole::Accent,
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            let contents = NodeContents::EMPTY.with_style_snapshot();
+            gui.insert_node(Some(UID), &style, contents).map(|_| ())
+        })
+        .result
+        .expect("error building GUI");
+
+        let style = gui.computed_style(UID).unwrap();
+        let ComputedBrush::Solid(background) = style.background() else {
+            panic!("expected a solid background");
+        };
+        (background, style.text_color(), style.border_color())
+    };
+
+    let theme = Theme::default();
+    assert_eq!(
+        frame(&mut gui),
+        (
+            theme.role(ColorRole::Danger),
+            theme.role(ColorRole::OnSurface),
+            theme.role(ColorRole::Accent),
+        )
+    );
+
+    gui.theme_mut().set_role(ColorRole::Danger, DANGER);
+    assert_eq!(frame(&mut gui).0, DANGER);
+}
+
+#[test]
+fn node_renderers_see_the_clip_rect() {
+    use crate::rendering::*;
+    use crate::*;
+    use std::sync::{Arc, Mutex};
+
+    type Clips = Arc<Mutex<Vec<((Vec2<Pixel>, Vec2<Pixel>), bool)>>>;
+
+    struct ClipRecorder(Clips);
+
+    impl NodeRenderer for ClipRecorder {
+        type Renderer = NullRenderer;
+
+        fn render(
+            &self,
+            context: RenderContext<'_, NullRenderer>,
+        ) -> Result<(), std::convert::Infallible> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((context.clip_rect(), context.is_culled()));
+            Ok(())
+        }
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let parent_style = style! {
+        width: 50.px(),
+        height: 50.px(),
+        padding: 5.px(),
+        layout_direction: Direction::TopToBottom,
+        child_spacing: 0.px(),
+    };
+    let child_style = style! {
+        width: 40.px(),
+        height: 30.px(),
+        padding: 0.px(),
+    };
+
+    let clips = Clips::default();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| {
+        gui.insert_node(
+            None,
+            &parent_style,
+            NodeContents::builder(|mut gui| -> widgets::WidgetResult<_> {
+                for _ in 0..3 {
+                    gui.insert_node(
+                        None,
+                        &child_style,
+                        NodeContents::renderer(ClipRecorder(clips.clone())),
+                    )?;
+                }
+                Ok(())
+            }),
+        )?
+        .result
+    })
+    .result
+    .expect("error building GUI");
+    gui.render(&mut NullRenderer).unwrap();
+
+    let parent_clip = (
+        Vec2 {
+            x: 5.px(),
+            y: 5.px(),
+        },
+        Vec2 {
+            x: 40.px(),
+            y: 40.px(),
+        },
+    );
+    // the third child is outside the parent's clip rect and isn't rendered at all
+    let clips = clips.lock().unwrap();
+    assert_eq!(clips.len(), 2);
+    assert!(!clips[0].1);
+    assert!(!clips[1].1);
+
+    // the first child is inside the parent's clip rect, the second one only partially
+    assert_eq!(clips[0].0.0, parent_clip.0);
+    assert_eq!(clips[0].0.1.y, 30.px());
+    assert_eq!(clips[1].0.0.y, 35.px());
+    assert_eq!(clips[1].0.1.y, 10.px());
+}
+
+/// The straightforward full-tree hover walk, used as a reference for the incremental one.
+fn reference_hover(
+    tree: crate::forest::TreeRef<'_, crate::Node, crate::forest::Shared>,
+    mouse_position: crate::Vec2<crate::Pixel>,
+    mouse_in_parent_clip_bounds: bool,
+    hovered_node_override: Option<crate::Uid>,
+    states: &mut Vec<(crate::Uid, crate::HoverState)>,
+) -> Option<crate::Uid> {
+    use crate::forest::*;
+    use crate::*;
+
+    let mut hovered_node = None;
+
+    let node = tree.parent;
+    let mouse_in_bounds = mouse_in_parent_clip_bounds
+        && point_in_rect(mouse_position, node.position, node.style.fixed_size);
+    let (clip_position, clip_size) = node.clip_bounds();
+    let mouse_in_clip_bounds =
+        mouse_in_bounds && point_in_rect(mouse_position, clip_position, clip_size);
+
+    iter_subtrees!(tree.descendants => |subtree| {
+        if subtree.is_root {
+            continue;
+        }
+
+        if let Some(uid) = reference_hover(
+            subtree,
+            mouse_position,
+            mouse_in_clip_bounds,
+            hovered_node_override,
+            states,
+        ) {
+            assert!(hovered_node.is_none(), "multiple nodes hovered");
+            hovered_node = Some(uid);
+        }
+    });
+
+    if let Some(uid) = node.uid {
+        let hover_state = if let Some(hovered_node_override) = hovered_node_override {
+            if uid == hovered_node_override {
+                hovered_node = Some(uid);
+                HoverState::DirectlyHovered
+            } else {
+                HoverState::NotHovered
+            }
+        } else if mouse_in_bounds {
+            if hovered_node.is_none() {
+                hovered_node = Some(uid);
+                HoverState::DirectlyHovered
+            } else {
+                HoverState::Hovered
+            }
+        } else {
+            HoverState::NotHovered
+        };
+        states.push((uid, hover_state));
+    }
+
+    hovered_node
+}
+
+fn insert_random_nodes(
+    gui: &mut crate::ByorGuiContext<'_, NullRenderer>,
+    seed: &mut u64,
+    depth: u32,
+    next_uid: &mut u64,
+) -> crate::widgets::WidgetResult<()> {
+    use crate::style::*;
+    use crate::*;
+    use rapidhash::rng::rapidrng_fast;
+
+    let child_count = if depth < 4 {
+        rapidrng_fast(seed) % 4
+    } else {
+        0
+    };
+    for _ in 0..child_count {
+        let mut random = |n: u64| rapidrng_fast(seed) % n;
+
+        let style = Style::default()
+            .with_width(Sizing::Fixed((10 + random(60)).px().into()))
+            .with_height(Sizing::Fixed((10 + random(60)).px().into()))
+            .with_padding(random(8).px())
+            .with_child_spacing(random(4).px())
+            .with_layout_direction(if random(2) == 0 {
+                Direction::LeftToRight
+            } else {
+                Direction::TopToBottom
+            });
+        let uid = (random(4) > 0).then(|| {
+            *next_uid += 1;
+            Uid::new(*next_uid)
+        });
+        let float_uid = (random(6) == 0).then(|| {
+            *next_uid += 1;
+            Uid::new(*next_uid)
+        });
+
+        let contents = NodeContents::builder(|mut gui: ByorGuiContext<'_, NullRenderer>| {
+            insert_random_nodes(&mut gui, seed, depth + 1, next_uid)
+        });
+        if let Some(float_uid) = float_uid {
+            let position = FloatPosition::Popup {
+                x: PopupPosition::AfterParent,
+                y: PopupPosition::ParentStart,
+            };
+            gui.insert_floating_node(float_uid, position, &style, contents)?
+                .result?;
+        } else {
+            gui.insert_node(uid, &style, contents)?.result?;
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn incremental_hover_matches_full_walk() {
+    use crate::input::*;
+    use crate::*;
+    use rapidhash::rng::rapidrng_fast;
+
+    let screen_size = Vec2 {
+        x: 400.px(),
+        y: 300.px(),
+    };
+
+    for tree_seed in 0..20 {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        let mut input_seed = tree_seed;
+
+        for _ in 0..30 {
+            // keep the cursor still every now and then so the cached result gets reused
+            if !rapidrng_fast(&mut input_seed).is_multiple_of(3) {
+                // half-pixel positions never land on the shared edge of two siblings
+                let x = (rapidrng_fast(&mut input_seed) % 400) as f32 + 0.5;
+                let y = (rapidrng_fast(&mut input_seed) % 300) as f32 + 0.5;
+                gui.on_input_event(InputEvent::CursorMoved {
+                    position: Vec2 {
+                        x: x.px(),
+                        y: y.px(),
+                    },
+                });
+            }
+            match rapidrng_fast(&mut input_seed) % 4 {
+                0 => gui.on_input_event(InputEvent::ButtonPressed {
+                    button: MouseButton::Primary,
+                }),
+                1 => gui.on_input_event(InputEvent::ButtonReleased {
+                    button: MouseButton::Primary,
+                }),
+                _ => (),
+            }
+
+            let hovered_node_override = if gui.input_state().pressed_buttons().is_empty() {
+                None
+            } else {
+                gui.data.hovered_node_override
+            };
+
+            let mut seed = tree_seed;
+            let mut next_uid = 0;
+            gui.frame(screen_size, |mut gui| {
+                insert_random_nodes(&mut gui, &mut seed, 0, &mut next_uid)
+            })
+            .result
+            .expect("error building GUI");
+
+            let mouse_position = gui.input_state().last_cursor_position();
+            let mut expected = Vec::new();
+            let mut trees = gui.forest.trees();
+            while let Some(tree) = trees.next() {
+                let _ = reference_hover(
+                    tree,
+                    mouse_position,
+                    true,
+                    hovered_node_override,
+                    &mut expected,
+                );
+            }
+
+            for (uid, hover_state) in expected {
+                assert_eq!(
+                    gui.data.previous_state.get(uid).unwrap().hover_state,
+                    hover_state,
+                    "tree {tree_seed}, node {uid:?}",
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn keyed_scopes_follow_the_item() {
+    use crate::*;
+
+    const ITEM_UID: Uid = Uid::from_array(b"item");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let item_style = style! {
+        width: 50.px(),
+        height: 50.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let item_uids = |gui: &mut ByorGui<NullRenderer>, keys: &[&str]| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            for key in keys {
+                gui.keyed(key, |gui| {
+                    gui.insert_node(Some(ITEM_UID), &item_style, NodeContents::EMPTY)
+                })?;
+            }
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.nodes()
+            .filter_map(|node| node.uid())
+            .collect::<Vec<_>>()
+    };
+
+    let sorted = item_uids(&mut gui, &["a", "b"]);
+    let reversed = item_uids(&mut gui, &["b", "a"]);
+    assert_eq!(sorted, [reversed[1], reversed[0]]);
+    assert!(gui.take_diagnostics().is_empty());
+
+    gui.set_error_policy(ErrorPolicy::CollectAndContinue);
+    let _ = item_uids(&mut gui, &["a", "a"]);
+    let diagnostics = gui.take_diagnostics();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.message == "duplicate key")
+    );
+
+    struct Sidebar;
+    struct Toolbar;
+    assert_eq!(Uid::from_type::<Sidebar>(), Uid::from_type::<Sidebar>());
+    assert_ne!(Uid::from_type::<Sidebar>(), Uid::from_type::<Toolbar>());
+}
+
+#[test]
+fn keyed_iteration_names_duplicate_keys() {
+    use crate::*;
+
+    const ITEM_UID: Uid = Uid::from_array(b"item");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let item_style = style! {
+        width: 50.px(),
+        height: 50.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_error_policy(ErrorPolicy::CollectAndContinue);
+    let item_uids = |gui: &mut ByorGui<NullRenderer>, ids: &[u32]| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.for_each_keyed(
+                ids,
+                |&&id| id,
+                |gui, _| -> widgets::WidgetResult<()> {
+                    gui.insert_node(Some(ITEM_UID), &item_style, NodeContents::EMPTY)?;
+                    Ok(())
+                },
+            )?;
+            gui.with_index_scope(0..2, |gui, _| {
+                gui.insert_node(Some(ITEM_UID), &item_style, NodeContents::EMPTY)?;
+                Ok(())
+            })
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.nodes()
+            .filter_map(|node| node.uid())
+            .collect::<Vec<_>>()
+    };
+
+    let sorted = item_uids(&mut gui, &[1, 2]);
+    let reversed = item_uids(&mut gui, &[2, 1]);
+    assert_eq!(sorted[..2], [reversed[1], reversed[0]]);
+    assert_eq!(sorted[2..], reversed[2..], "index scopes stay in place");
+    assert!(gui.take_diagnostics().is_empty());
+
+    let _ = item_uids(&mut gui, &[7, 7]);
+    let diagnostics = gui.take_diagnostics();
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.message == "duplicate key `7`")
+    );
+}
+
+#[test]
+fn animated_values_move_towards_their_target() {
+    use crate::animation::*;
+    use crate::*;
+    use std::time::{Duration, Instant};
+
+    const UID: Uid = Uid::from_array(b"animated");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let config = AnimationConfig::new(Duration::from_millis(100)).with_easing(Easing::Linear);
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let start = Instant::now();
+    let animate = |gui: &mut ByorGui<NullRenderer>, millis: u64, target: f32| {
+        gui.set_time(start + Duration::from_millis(millis));
+        gui.frame(screen_size, |mut gui| {
+            (gui.animate(UID, target, config), gui.delta_time())
+        })
+        .result
+    };
+
+    let (value, _) = animate(&mut gui, 0, 1.0);
+    assert_eq!(value.value, 1.0);
+    assert!(value.finished);
+    assert!(!gui.needs_animation());
+
+    let (value, _) = animate(&mut gui, 10, 5.0);
+    assert_eq!(value.value, 1.0);
+    assert!(!value.finished);
+    assert!(gui.needs_animation());
+
+    let (value, delta_time) = animate(&mut gui, 35, 5.0);
+    assert_eq!(delta_time, Duration::from_millis(25));
+    assert!((value.value - 2.0).abs() < 1e-4);
+    assert!(!value.finished);
+
+    let (value, _) = animate(&mut gui, 110, 5.0);
+    assert_eq!(value.value, 5.0);
+    assert!(value.finished);
+    assert!(!gui.needs_animation());
+
+    for easing in [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::Spring,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+        assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+    }
+    assert!(Easing::Spring.apply(0.4) > 1.0);
+}
+
+#[test]
+fn default_stroke_and_path_primitives() {
+    use crate::rendering::*;
+    use crate::style::Color;
+    use crate::style::computed::ComputedBrush;
+    use crate::*;
+
+    /// Records the polygons the default implementations reduce everything to.
+    #[derive(Default)]
+    struct PolyRecorder(Vec<Vec<Vec2<Pixel>>>);
+
+    impl Renderer for PolyRecorder {
+        type Error = std::convert::Infallible;
+
+        fn push_clip_rect(&mut self, _: Vec2<Pixel>, _: Vec2<Pixel>) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn draw_rect(
+            &mut self,
+            _: Vec2<Pixel>,
+            _: Vec2<Pixel>,
+            _: Float<Pixel>,
+            _: Float<Pixel>,
+            _: Color,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn fill_rect(
+            &mut self,
+            _: Vec2<Pixel>,
+            _: Vec2<Pixel>,
+            _: Float<Pixel>,
+            _: ComputedBrush,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn draw_poly(
+            &mut self,
+            _: &[Vec2<Pixel>],
+            _: Float<Pixel>,
+            _: Color,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn fill_poly(
+            &mut self,
+            vertices: &[Vec2<Pixel>],
+            _: ComputedBrush,
+        ) -> Result<(), Self::Error> {
+            self.0.push(vertices.to_vec());
+            Ok(())
+        }
+
+        fn draw_text(
+            &mut self,
+            _: parley::GlyphRun<'_, Color>,
+            _: Vec2<Pixel>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    let point = |x: f32, y: f32| Vec2 {
+        x: x.px(),
+        y: y.px(),
+    };
+    let brush = || ComputedBrush::Solid(Color::greyscale(0));
+
+    let mut renderer = PolyRecorder::default();
+    renderer
+        .stroke_line(point(0.0, 0.0), point(10.0, 0.0), 2.px(), brush())
+        .unwrap();
+    assert_eq!(
+        renderer.0,
+        [[
+            point(0.0, 1.0),
+            point(10.0, 1.0),
+            point(10.0, -1.0),
+            point(0.0, -1.0)
+        ]]
+    );
+
+    renderer.0.clear();
+    renderer
+        .stroke_poly(
+            &[point(0.0, 0.0), point(10.0, 0.0), point(10.0, 10.0)],
+            1.px(),
+            true,
+            brush(),
+        )
+        .unwrap();
+    assert_eq!(renderer.0.len(), 3);
+
+    let mut path = Path::new();
+    path.move_to(point(0.0, 0.0))
+        .line_to(point(10.0, 0.0))
+        .quad_to(point(10.0, 10.0), point(0.0, 10.0))
+        .close()
+        .move_to(point(20.0, 0.0))
+        .cubic_to(point(30.0, 0.0), point(30.0, 10.0), point(20.0, 10.0));
+    let subpaths = path.flatten();
+    assert_eq!(subpaths.len(), 2);
+    assert!(subpaths[0].1);
+    assert!(!subpaths[1].1);
+    assert_eq!(subpaths[0].0.first(), Some(&point(0.0, 0.0)));
+    assert_eq!(subpaths[0].0.last(), Some(&point(0.0, 10.0)));
+    assert_eq!(subpaths[1].0.last(), Some(&point(20.0, 10.0)));
+
+    renderer.0.clear();
+    renderer.fill_path(&path, brush()).unwrap();
+    assert_eq!(renderer.0.len(), 2);
+}
+
+/// Records the solid fills and the colors glyph runs are recolored with.
+#[derive(Default)]
+struct ColorRecorder {
+    fills: Vec<crate::style::Color>,
+    recolored: Vec<crate::style::Color>,
+    supports_backdrop_blur: bool,
+    /// The radius and corner radius of every blurred region.
+    blurs: Vec<(crate::Float<crate::Pixel>, crate::Float<crate::Pixel>)>,
+    text_strokes: Vec<(crate::Float<crate::Pixel>, crate::style::Color)>,
+}
+
+impl crate::rendering::Renderer for ColorRecorder {
+    type Error = std::convert::Infallible;
+
+    fn push_clip_rect(
+        &mut self,
+        _: crate::Vec2<crate::Pixel>,
+        _: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn draw_rect(
+        &mut self,
+        _: crate::Vec2<crate::Pixel>,
+        _: crate::Vec2<crate::Pixel>,
+        _: crate::Float<crate::Pixel>,
+        _: crate::Float<crate::Pixel>,
+        _: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        _: crate::Vec2<crate::Pixel>,
+        _: crate::Vec2<crate::Pixel>,
+        _: crate::Float<crate::Pixel>,
+        brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        if let crate::style::computed::ComputedBrush::Solid(color) = brush {
+            self.fills.push(color);
+        }
+        Ok(())
+    }
+
+    fn supports_backdrop_blur(&self) -> bool {
+        self.supports_backdrop_blur
+    }
+
+    fn blur_region(
+        &mut self,
+        _: crate::Vec2<crate::Pixel>,
+        _: crate::Vec2<crate::Pixel>,
+        radius: crate::Float<crate::Pixel>,
+        corner_radius: crate::Float<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.blurs.push((radius, corner_radius));
+        Ok(())
+    }
+
+    fn draw_poly(
+        &mut self,
+        _: &[crate::Vec2<crate::Pixel>],
+        _: crate::Float<crate::Pixel>,
+        _: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn fill_poly(
+        &mut self,
+        _: &[crate::Vec2<crate::Pixel>],
+        _: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        _: parley::GlyphRun<'_, crate::style::Color>,
+        _: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn draw_text_with_color(
+        &mut self,
+        _: parley::GlyphRun<'_, crate::style::Color>,
+        _: crate::Vec2<crate::Pixel>,
+        color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        self.recolored.push(color);
+        Ok(())
+    }
+
+    fn stroke_text(
+        &mut self,
+        _: parley::GlyphRun<'_, crate::style::Color>,
+        _: crate::Vec2<crate::Pixel>,
+        width: crate::Float<crate::Pixel>,
+        color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        self.text_strokes.push((width, color));
+        Ok(())
+    }
+}
+
+#[test]
+fn text_is_stroked_only_with_a_visible_outline() {
+    use crate::style::Color;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let outlined = style! {
+        text_stroke_width: 2.px(),
+        text_stroke_color: Color::BLACK,
+    };
+    let transparent = style! {
+        text_stroke_width: 2.px(),
+    };
+
+    let mut gui = ByorGui::<ColorRecorder>::default();
+    gui.frame(screen_size, |mut gui| {
+        let parent = NodeContents::builder(|mut gui: ByorGuiContext<'_, ColorRecorder>| {
+            // the outline is inherited
+            gui.insert_node(None, &Style::default(), NodeContents::text("inherited"))
+                .map(|_| ())
+        });
+        gui.insert_node(None, &outlined, parent).unwrap();
+        gui.insert_node(None, &transparent, NodeContents::text("transparent"))
+            .unwrap();
+        gui.insert_node(None, &Style::default(), NodeContents::text("plain"))
+            .unwrap();
+    });
+
+    let mut renderer = ColorRecorder::default();
+    gui.render(&mut renderer).unwrap();
+    // only the text inheriting the visible outline is stroked
+    assert_eq!(renderer.text_strokes, [(2.px(), Color::BLACK)]);
+}
+
+#[test]
+fn selection_is_drawn_in_the_selection_colors() {
+    use crate::input::*;
+    use crate::style::Color;
+    use crate::*;
+
+    const SELECTION_COLOR: Color = Color::rgb(1, 2, 3);
+    const SELECTION_TEXT_COLOR: Color = Color::rgb(4, 5, 6);
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+        selection_color: SELECTION_COLOR,
+        selection_text_color: SELECTION_TEXT_COLOR,
+    };
+
+    let mut gui = ByorGui::<ColorRecorder>::default();
+    let mut text = String::from("selected");
+    let mut frame = |gui: &mut ByorGui<ColorRecorder>| {
+        gui.frame(screen_size, |mut gui| {
+            let text_box = widgets::TextBox::new(&mut text)
+                .with_uid(Uid::from_array(b"text_box"))
+                .with_style(&style);
+            gui.show(text_box)
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let render = |gui: &mut ByorGui<ColorRecorder>| {
+        let mut renderer = ColorRecorder::default();
+        gui.render(&mut renderer).unwrap();
+        renderer
+    };
+
+    gui.on_input_event(InputEvent::CursorMoved {
+        position: Vec2 {
+            x: 1.px(),
+            y: 10.px(),
+        },
+    });
+    gui.on_input_event(InputEvent::ButtonPressed {
+        button: MouseButton::Primary,
+    });
+    frame(&mut gui);
+    gui.on_input_event(InputEvent::ButtonReleased {
+        button: MouseButton::Primary,
+    });
+    frame(&mut gui);
+
+    let renderer = render(&mut gui);
+    assert!(!renderer.fills.contains(&SELECTION_COLOR));
+    assert!(renderer.recolored.is_empty());
+
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: Key::Named(NamedKey::Control),
+        location: KeyLocation::Left,
+        text: None,
+        repeat: false,
+    });
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: Key::Character("a".into()),
+        location: KeyLocation::Standard,
+        text: Some("a".into()),
+        repeat: false,
+    });
+    frame(&mut gui);
+
+    let renderer = render(&mut gui);
+    assert!(renderer.fills.contains(&SELECTION_COLOR));
+    assert!(!renderer.recolored.is_empty());
+    assert!(
+        renderer
+            .recolored
+            .iter()
+            .all(|&color| color == SELECTION_TEXT_COLOR)
+    );
+}
+
+#[test]
+fn insert_key_toggles_overwrite_mode() {
+    use crate::input::*;
+    use crate::style::Color;
+    use crate::*;
+
+    const CARET_COLOR: Color = Color::rgb(7, 8, 9);
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+        caret_color: CARET_COLOR,
+    };
+
+    let mut gui = ByorGui::<ColorRecorder>::default();
+    let mut text = String::from("abc");
+    let mut frame = |gui: &mut ByorGui<ColorRecorder>| {
+        gui.frame(screen_size, |mut gui| {
+            let text_box = widgets::TextBox::new(&mut text)
+                .with_uid(Uid::from_array(b"text_box"))
+                .with_style(&style);
+            gui.show(text_box)
+        })
+        .result
+        .expect("error building GUI");
+        let mut renderer = ColorRecorder::default();
+        gui.render(&mut renderer).unwrap();
+        (renderer.fills, text.clone())
+    };
+    let press = |gui: &mut ByorGui<ColorRecorder>, key: Key, text: Option<&str>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key,
+            location: KeyLocation::Standard,
+            text: text.map(Into::into),
+            repeat: false,
+        });
+    };
+    let type_text = |gui: &mut ByorGui<ColorRecorder>, text: &str| {
+        press(gui, Key::Character(text.into()), Some(text));
+    };
+
+    gui.on_input_event(InputEvent::CursorMoved {
+        position: Vec2 {
+            x: 1.px(),
+            y: 10.px(),
+        },
+    });
+    gui.on_input_event(InputEvent::ButtonPressed {
+        button: MouseButton::Primary,
+    });
+    frame(&mut gui);
+    gui.on_input_event(InputEvent::ButtonReleased {
+        button: MouseButton::Primary,
+    });
+    let (fills, _) = frame(&mut gui);
+    // the bar caret is drawn in the caret color
+    assert!(fills.contains(&CARET_COLOR));
+
+    press(&mut gui, Key::Named(NamedKey::Insert), None);
+    let (fills, _) = frame(&mut gui);
+    // the block caret is translucent
+    assert!(!fills.contains(&CARET_COLOR));
+    assert!(fills.contains(&Color {
+        a: 127,
+        ..CARET_COLOR
+    }));
+
+    type_text(&mut gui, "x");
+    frame(&mut gui);
+    type_text(&mut gui, "yz");
+    frame(&mut gui);
+    type_text(&mut gui, "!");
+    assert_eq!(frame(&mut gui).1, "xyz!");
+
+    press(&mut gui, Key::Named(NamedKey::Home), None);
+    press(&mut gui, Key::Named(NamedKey::Insert), None);
+    type_text(&mut gui, "-");
+    assert_eq!(frame(&mut gui).1, "-xyz!");
+}
+
+#[test]
+fn read_only_and_disabled_text_boxes_keep_their_text() {
+    use crate::input::*;
+    use crate::style::Color;
+    use crate::*;
+
+    const CARET_COLOR: Color = Color::rgb(7, 8, 9);
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    for (read_only, enabled) in [(true, true), (false, false)] {
+        let style = style! {
+            enabled: enabled,
+            width: 100.px(),
+            height: 20.px(),
+            caret_color: CARET_COLOR,
+        };
+
+        let mut gui = ByorGui::<ColorRecorder>::default();
+        let mut text = String::from("abc");
+        let mut frame = |gui: &mut ByorGui<ColorRecorder>| {
+            let response = gui
+                .frame(screen_size, |mut gui| {
+                    let text_box = widgets::TextBox::new(&mut text)
+                        .read_only(read_only)
+                        .with_single_line(true)
+                        .with_uid(Uid::from_array(b"text_box"))
+                        .with_style(&style);
+                    gui.show(text_box)
+                })
+                .result
+                .expect("error building GUI");
+            let mut renderer = ColorRecorder::default();
+            gui.render(&mut renderer).unwrap();
+            (response, renderer.fills, text.clone())
+        };
+
+        gui.on_input_event(InputEvent::CursorMoved {
+            position: Vec2 {
+                x: 1.px(),
+                y: 10.px(),
+            },
+        });
+        gui.on_input_event(InputEvent::ButtonPressed {
+            button: MouseButton::Primary,
+        });
+        frame(&mut gui);
+        gui.on_input_event(InputEvent::ButtonReleased {
+            button: MouseButton::Primary,
+        });
+        let (_, fills, _) = frame(&mut gui);
+        // a read-only text box can still be focused, a disabled one can't
+        assert_eq!(fills.contains(&CARET_COLOR), enabled);
+
+        for key in [
+            Key::Character("x".into()),
+            Key::Named(NamedKey::Backspace),
+            Key::Named(NamedKey::Delete),
+            Key::Named(NamedKey::Enter),
+        ] {
+            gui.on_input_event(InputEvent::KeyPressed {
+                text: matches!(key, Key::Character(_)).then(|| "x".into()),
+                key,
+                location: KeyLocation::Standard,
+                repeat: false,
+            });
+            let (response, _, text) = frame(&mut gui);
+            assert!(!response.changed);
+            assert!(!response.submitted);
+            assert_eq!(text, "abc");
+        }
+    }
+}
+
+#[test]
+fn mismatched_editor_state_is_recreated() {
+    use crate::*;
+
+    const TEXT_BOX_UID: Uid = Uid::from_array(b"text_box");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    // another widget stored something else under the same key
+    gui.data
+        .persistent_state
+        .entry(TEXT_BOX_UID)
+        .or_default()
+        .insert(PersistentStateKey::TextBoxEditor, 42u32);
+
+    let mut text = String::from("abc");
+    gui.frame(screen_size, |mut gui| {
+        gui.show(widgets::TextBox::new(&mut text).with_uid(TEXT_BOX_UID))
+    })
+    .result
+    .expect("error building GUI");
+
+    let diagnostics = gui.take_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].uid, Some(TEXT_BOX_UID));
+    assert!(
+        gui.data
+            .persistent_state
+            .get(TEXT_BOX_UID)
+            .unwrap()
+            .get::<u32>(PersistentStateKey::TextBoxEditor)
+            .is_none()
+    );
+    assert_eq!(text, "abc");
+}
+
+#[test]
+fn caret_motion_shortcuts() {
+    use crate::input::*;
+    use crate::*;
+
+    const TEXT: &str = "one two three\nfour five";
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 400.px(),
+        height: 50.px(),
+    };
+
+    // Performs the key presses, then marks the selection by typing `|` and reverts that again.
+    let marked = |mac_bindings: bool, keys: &[(Modifiers, NamedKey)]| -> String {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        let mut text = String::from(TEXT);
+        let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+            gui.frame(screen_size, |mut gui| {
+                let text_box = widgets::TextBox::new(&mut text)
+                    .with_mac_bindings(mac_bindings)
+                    .with_uid(Uid::from_array(b"text_box"))
+                    .with_style(&style);
+                gui.show(text_box)
+            })
+            .result
+            .expect("error building GUI");
+        };
+        let modifier_keys = [
+            (Modifiers::CONTROL, NamedKey::Control),
+            (Modifiers::SHIFT, NamedKey::Shift),
+            (Modifiers::ALT, NamedKey::Alt),
+            (Modifiers::META, NamedKey::Meta),
+        ];
+        let key_event = |gui: &mut ByorGui<NullRenderer>, key: NamedKey, pressed: bool| {
+            let key = Key::Named(key);
+            let location = KeyLocation::Left;
+            gui.on_input_event(if pressed {
+                InputEvent::KeyPressed {
+                    key,
+                    location,
+                    text: None,
+                    repeat: false,
+                }
+            } else {
+                InputEvent::KeyReleased {
+                    key,
+                    location,
+                    text: None,
+                }
+            });
+        };
+
+        // focus the text box with the caret in front of `two`
+        gui.on_input_event(InputEvent::CursorMoved {
+            position: Vec2 {
+                x: 1.px(),
+                y: 5.px(),
+            },
+        });
+        gui.on_input_event(InputEvent::ButtonPressed {
+            button: MouseButton::Primary,
+        });
+        frame(&mut gui);
+        gui.on_input_event(InputEvent::ButtonReleased {
+            button: MouseButton::Primary,
+        });
+        for _ in 0..4 {
+            key_event(&mut gui, NamedKey::ArrowRight, true);
+        }
+        frame(&mut gui);
+
+        for &(modifiers, key) in keys {
+            for (modifier, modifier_key) in modifier_keys {
+                if modifiers.contains(modifier) {
+                    key_event(&mut gui, modifier_key, true);
+                }
+            }
+            key_event(&mut gui, key, true);
+            key_event(&mut gui, key, false);
+            for (modifier, modifier_key) in modifier_keys {
+                if modifiers.contains(modifier) {
+                    key_event(&mut gui, modifier_key, false);
+                }
+            }
+            frame(&mut gui);
+        }
+
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Character("|".into()),
+            location: KeyLocation::Standard,
+            text: Some("|".into()),
+            repeat: false,
+        });
+        frame(&mut gui);
+        text
+    };
+
+    let none = Modifiers::empty();
+    let ctrl = Modifiers::CONTROL;
+    let shift = Modifiers::SHIFT;
+    let alt = Modifiers::ALT;
+    let cmd = Modifiers::META;
+
+    assert_eq!(marked(false, &[]), "one |two three\nfour five");
+    assert_eq!(
+        marked(false, &[(none, NamedKey::ArrowLeft)]),
+        "one| two three\nfour five"
+    );
+    assert_eq!(
+        marked(false, &[(ctrl, NamedKey::ArrowRight)]),
+        "one two| three\nfour five"
+    );
+    assert_eq!(
+        marked(
+            false,
+            &[(ctrl, NamedKey::ArrowRight), (ctrl, NamedKey::ArrowRight)]
+        ),
+        "one two three|\nfour five"
+    );
+    assert_eq!(
+        marked(false, &[(ctrl, NamedKey::ArrowLeft)]),
+        "|one two three\nfour five"
+    );
+    assert_eq!(
+        marked(false, &[(none, NamedKey::End)]),
+        "one two three|\nfour five"
+    );
+    assert_eq!(
+        marked(false, &[(none, NamedKey::Home)]),
+        "|one two three\nfour five"
+    );
+    assert_eq!(
+        marked(false, &[(ctrl, NamedKey::End)]),
+        "one two three\nfour five|"
+    );
+    assert_eq!(
+        marked(
+            false,
+            &[(none, NamedKey::ArrowDown), (ctrl, NamedKey::Home)]
+        ),
+        "|one two three\nfour five"
+    );
+
+    // Shift extends the selection, which is then replaced by the marker
+    assert_eq!(
+        marked(false, &[(shift, NamedKey::ArrowRight)]),
+        "one |wo three\nfour five"
+    );
+    assert_eq!(
+        marked(false, &[(ctrl | shift, NamedKey::ArrowRight)]),
+        "one | three\nfour five"
+    );
+    assert_eq!(marked(false, &[(shift, NamedKey::End)]), "one |\nfour five");
+    assert_eq!(
+        marked(false, &[(shift, NamedKey::Home)]),
+        "|two three\nfour five"
+    );
+    assert_eq!(marked(false, &[(ctrl | shift, NamedKey::End)]), "one |");
+    assert_eq!(
+        marked(
+            false,
+            &[
+                (ctrl | shift, NamedKey::ArrowLeft),
+                (shift, NamedKey::ArrowRight)
+            ]
+        ),
+        "o|two three\nfour five"
+    );
+
+    assert_eq!(
+        marked(true, &[(alt, NamedKey::ArrowRight)]),
+        "one two| three\nfour five"
+    );
+    assert_eq!(
+        marked(true, &[(cmd, NamedKey::ArrowRight)]),
+        "one two three|\nfour five"
+    );
+    assert_eq!(
+        marked(true, &[(cmd, NamedKey::ArrowLeft)]),
+        "|one two three\nfour five"
+    );
+    assert_eq!(
+        marked(true, &[(cmd, NamedKey::ArrowDown)]),
+        "one two three\nfour five|"
+    );
+    assert_eq!(
+        marked(true, &[(cmd | shift, NamedKey::ArrowUp)]),
+        "|two three\nfour five"
+    );
+    // Control has no meaning with macOS bindings
+    assert_eq!(
+        marked(true, &[(ctrl, NamedKey::ArrowRight)]),
+        "one t|wo three\nfour five"
+    );
+}
+
+#[test]
+fn backdrop_blur_degrades_to_an_opaque_background() {
+    use crate::style::Color;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 100.px(),
+        corner_radius: 2.px(),
+        backdrop_blur: 4.px(),
+        background: Color { r: 0, g: 0, b: 0, a: 100 },
+    };
+
+    let mut gui = ByorGui::<ColorRecorder>::default();
+    gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+        gui.insert_node(None, &style, NodeContents::EMPTY)?;
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+
+    let mut renderer = ColorRecorder {
+        supports_backdrop_blur: true,
+        ..Default::default()
+    };
+    gui.render(&mut renderer).unwrap();
+    assert_eq!(renderer.blurs, [(4.px(), 2.px())]);
+    assert!(renderer.fills.contains(&Color {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 100
+    }));
+
+    let mut renderer = ColorRecorder::default();
+    gui.render(&mut renderer).unwrap();
+    assert!(renderer.blurs.is_empty());
+    assert!(renderer.fills.contains(&Color {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 177
+    }));
+}
+
+#[test]
+fn density_scales_spacing_only() {
+    use crate::*;
+
+    const PANEL_UID: Uid = Uid::from_array(b"panel");
+    const FIRST_UID: Uid = Uid::from_array(b"first");
+    const SECOND_UID: Uid = Uid::from_array(b"second");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let panel_style = style! {
+        padding: 10.px(),
+        child_spacing: 4.px(),
+        font_size: 20.px(),
+    };
+    let item_style = style! {
+        width: 1.em(),
+        height: 10.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.insert_node(
+                Some(PANEL_UID),
+                &panel_style,
+                NodeContents::builder(|mut gui| -> widgets::WidgetResult<_> {
+                    gui.insert_node(Some(FIRST_UID), &item_style, NodeContents::EMPTY)?;
+                    gui.insert_node(Some(SECOND_UID), &item_style, NodeContents::EMPTY)?;
+                    Ok(())
+                }),
+            )?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+    };
+    let state = |gui: &ByorGui<NullRenderer>, uid: Uid| {
+        let state = gui.data.previous_state.get(uid).unwrap();
+        (state.position.x, state.size.x)
+    };
+
+    frame(&mut gui);
+    assert_eq!(state(&gui, FIRST_UID), (10.px(), 20.px()));
+    assert_eq!(state(&gui, SECOND_UID), (34.px(), 20.px()));
+
+    gui.theme_mut().set_density(2.0);
+    frame(&mut gui);
+    assert_eq!(state(&gui, PANEL_UID).1, 88.px());
+    assert_eq!(state(&gui, FIRST_UID), (20.px(), 20.px()));
+    assert_eq!(state(&gui, SECOND_UID), (48.px(), 20.px()));
+
+    gui.theme_mut().set_density(f32::NAN);
+    assert_eq!(gui.theme().density(), 2.0);
+}
+
+#[test]
+fn style_snapshots_are_opt_in() {
+    use crate::style::Color;
+    use crate::style::computed::ComputedBrush;
+    use crate::*;
+
+    const SOURCE_UID: Uid = Uid::from_array(b"source");
+    const OTHER_UID: Uid = Uid::from_array(b"other");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>, color: Color| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+            let seen = gui
+                .computed_style(SOURCE_UID)
+                .map(|style| style.background());
+            let seen = match seen {
+                Some(ComputedBrush::Solid(color)) => Some(color),
+                _ => None,
+            };
+
+            let style = style! {
+                width: 20.px(),
+                height: 10.px(),
+                padding: 2.px(),
+                background: color,
+            };
+            gui.insert_node(
+                Some(SOURCE_UID),
+                &style,
+                NodeContents::EMPTY.with_style_snapshot(),
+            )?;
+            gui.insert_node(Some(OTHER_UID), &style, NodeContents::EMPTY)?;
+
+            Ok(seen)
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    let red = Color::rgb(255, 0, 0);
+    let blue = Color::rgb(0, 0, 255);
+    assert_eq!(frame(&mut gui, red), None);
+
+    let snapshot = gui.computed_style(SOURCE_UID).unwrap();
+    assert_eq!(
+        snapshot.size(),
+        Vec2 {
+            x: 20.px(),
+            y: 10.px()
+        }
+    );
+    assert_eq!(snapshot.padding().left, 2.px());
+    assert!(gui.computed_style(OTHER_UID).is_none());
+
+    // while building, the snapshot is the one of the last frame
+    assert_eq!(frame(&mut gui, blue), Some(red));
+    assert_eq!(frame(&mut gui, blue), Some(blue));
+}
+
+#[test]
+fn subtree_hit_targets_claim_clicks_on_passive_descendants() {
+    use crate::input::*;
+    use crate::style::*;
+    use crate::*;
+
+    const ROW_UID: Uid = Uid::from_array(b"row");
+    const LABEL_UID: Uid = Uid::from_array(b"label");
+    const BUTTON_UID: Uid = Uid::from_array(b"button");
+
+    let screen_size = Vec2 {
+        x: 200.px(),
+        y: 100.px(),
+    };
+
+    // returns whether the row, the label and the button were clicked
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+            let row_style = style! {
+                width: Sizing::Grow,
+                height: 20.px(),
+                layout_direction: Direction::LeftToRight,
+            };
+            let child_style = style! {
+                width: 50.px(),
+                height: Sizing::Grow,
+            };
+
+            let row = gui.insert_node(
+                Some(ROW_UID),
+                &row_style,
+                NodeContents::builder(|mut gui| -> widgets::WidgetResult<_> {
+                    let label = gui.insert_node(
+                        Some(LABEL_UID),
+                        &child_style,
+                        NodeContents::text("label"),
+                    )?;
+                    let button = gui.show(
+                        widgets::Button::default()
+                            .with_uid(BUTTON_UID)
+                            .with_style(&child_style),
+                    )?;
+                    Ok((
+                        label.input_state.clicked(MouseButtons::PRIMARY),
+                        button.clicked(MouseButtons::PRIMARY),
+                    ))
+                })
+                .with_hit_target(HitTarget::Subtree),
+            )?;
+            let (label_clicked, button_clicked) = row.result?;
+
+            Ok((
+                row.input_state.clicked(MouseButtons::PRIMARY),
+                label_clicked,
+                button_clicked,
+            ))
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    let click_at = |gui: &mut ByorGui<NullRenderer>, x: f32| {
+        gui.move_cursor(Vec2 {
+            x: x.px(),
+            y: 10.px(),
+        });
+        frame(gui);
+        gui.press_button(MouseButton::Primary);
+        let clicked = frame(gui);
+        gui.release_button(MouseButton::Primary);
+        frame(gui);
+        clicked
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    frame(&mut gui);
+
+    assert_eq!(
+        click_at(&mut gui, 10.0),
+        (true, false, false),
+        "the row handles clicks on the label"
+    );
+    assert_eq!(
+        click_at(&mut gui, 60.0),
+        (false, false, true),
+        "the button keeps its own clicks"
+    );
+    assert_eq!(
+        click_at(&mut gui, 150.0),
+        (true, false, false),
+        "the row handles clicks on itself"
+    );
+}
+
+#[test]
+fn size_shorthands_set_both_axes() {
+    use crate::style::*;
+    use crate::*;
+
+    const SHORTHAND_UID: Uid = Uid::from_array(b"shorthand");
+    const BUILDER_UID: Uid = Uid::from_array(b"builder");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        let shorthand_style = style! {
+            size: (Sizing::Grow, 30.px()),
+            min_size: 20.px(),
+            max_size: (60.px(), %initial),
+        };
+        gui.insert_node(Some(SHORTHAND_UID), &shorthand_style, NodeContents::EMPTY)?;
+
+        let builder_style = Style::default()
+            .with_size(5.px())
+            .with_min_size((40.px(), 50.px()));
+        gui.insert_node(Some(BUILDER_UID), &builder_style, NodeContents::EMPTY)?;
+
+        Ok(())
+    })
+    .result
+    .expect("error building GUI");
+
+    let size = |uid| gui.data.previous_state.get(uid).unwrap().size;
+    assert_eq!(
+        size(SHORTHAND_UID),
+        Vec2 {
+            x: 60.px(),
+            y: 30.px(),
+        }
+    );
+    assert_eq!(
+        size(BUILDER_UID),
+        Vec2 {
+            x: 40.px(),
+            y: 50.px(),
+        }
+    );
+}
+
+#[test]
+fn exported_persistent_state_is_restored() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    scrolled_gui(&mut gui, 20, 20.0, false);
+    set_vertical_scroll(&mut gui, 120.0);
+    assert_eq!(
+        scrolled_gui(&mut gui, 20, 20.0, false),
+        (120.px(), 120.px())
+    );
+    gui.data
+        .persistent_state
+        .entry(Uid::from_array(b"custom"))
+        .or_default()
+        .insert(PersistentStateKey::Custom("custom"), 1u32);
+
+    let saved = gui.export_persistent();
+    assert_eq!(saved.nodes.len(), 1, "custom values are skipped");
+
+    let mut restored = ByorGui::<NullRenderer>::default();
+    restored.import_persistent(&saved);
+    assert_eq!(
+        scrolled_gui(&mut restored, 20, 20.0, false),
+        (120.px(), 120.px()),
+        "the offset is kept before the first layout"
+    );
+    assert_eq!(restored.export_persistent(), saved);
+}
+
+#[test]
+fn form_rows_share_the_label_column() {
+    use crate::*;
+
+    const SHORT_UID: Uid = Uid::from_array(b"short");
+    const LONG_UID: Uid = Uid::from_array(b"long");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let control_style = style! {
+        width: 50.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    for _ in 0..2 {
+        gui.frame(screen_size, |mut gui| {
+            gui.form(|mut gui| -> Result<(), WidgetError> {
+                gui.form_row("A", |mut gui| {
+                    gui.insert_node(Some(SHORT_UID), &control_style, NodeContents::EMPTY)
+                        .map(|_| ())
+                })??;
+                gui.form_row("A much longer label", |mut gui| {
+                    gui.insert_node(Some(LONG_UID), &control_style, NodeContents::EMPTY)
+                        .map(|_| ())
+                })??;
+                Ok(())
+            })?
+        })
+        .result
+        .expect("error building GUI");
+    }
+
+    let position = |uid| gui.data.previous_state.get(uid).unwrap().position;
+    assert_eq!(position(SHORT_UID).x, position(LONG_UID).x);
+    assert!(position(SHORT_UID).y < position(LONG_UID).y);
+}
+
+#[test]
+fn gauges_are_square_and_draw_circular_arcs() {
+    use crate::rendering::Path;
+    use crate::widgets::Gauge;
+    use crate::*;
+    use std::f32::consts::PI;
+
+    const UID: Uid = Uid::from_array(b"gauge");
+
+    let center = Vec2 {
+        x: 50.px(),
+        y: 50.px(),
+    };
+    let mut path = Path::new();
+    path.arc(center, 10.px(), -0.75 * PI, 1.5 * PI);
+    let subpaths = path.flatten();
+    assert_eq!(subpaths.len(), 1);
+    for &vertex in &subpaths[0].0 {
+        let offset = vertex - center;
+        let distance = offset.x.value().hypot(offset.y.value());
+        assert!(
+            (distance - 10.0).abs() < 0.05,
+            "{distance} is off the circle"
+        );
+    }
+    let end = *subpaths[0].0.last().unwrap() - center;
+    assert!((end.x.value() - 10.0 * (0.75 * PI).cos()).abs() < 1e-3);
+    assert!((end.y.value() - 10.0 * (0.75 * PI).sin()).abs() < 1e-3);
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| {
+        let gauge = Gauge::new(42.0)
+            .with_max(100.0)
+            .with_value_text(0)
+            .with_uid(UID);
+        gui.show(gauge)
+    })
+    .result
+    .expect("error building GUI");
+
+    let size = gui.data.previous_state.get(UID).unwrap().size;
+    assert!(size.x > 0.px());
+    assert_eq!(size.x, size.y);
+}
+
+#[test]
+fn debug_shortcut_cycles_the_overlay_before_widgets_see_it() {
+    use crate::input::*;
+    use crate::rendering::DebugOverlay;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_debug_shortcut(Some(DebugOverlay::DEFAULT_SHORTCUT));
+    for key in [NamedKey::Control, NamedKey::Shift] {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(key),
+            location: KeyLocation::Left,
+            text: None,
+            repeat: false,
+        });
+    }
+    let d = Key::Character("D".into());
+    // returns whether the builder saw the key
+    let press_d = |gui: &mut ByorGui<NullRenderer>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: d.clone(),
+            location: KeyLocation::Standard,
+            text: Some("D".into()),
+            repeat: false,
+        });
+        let seen = gui
+            .frame(screen_size, |gui| {
+                gui.data
+                    .input_state
+                    .key_events()
+                    .iter()
+                    .any(|event| matches!(event, KeyEvent::Pressed { key, .. } if *key == d))
+            })
+            .result;
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: d.clone(),
+            location: KeyLocation::Standard,
+            text: None,
+        });
+        seen
+    };
+
+    let mut modes = Vec::new();
+    for _ in 0..3 {
+        assert!(!press_d(&mut gui));
+        modes.push(gui.debug_overlay());
+    }
+    assert_eq!(
+        modes,
+        [
+            DebugOverlay::Bounds,
+            DebugOverlay::BoundsAndPadding,
+            DebugOverlay::Off,
+        ]
+    );
+
+    gui.set_debug_shortcut(None);
+    assert!(press_d(&mut gui));
+    assert_eq!(gui.debug_overlay(), DebugOverlay::Off);
+}
+
+/// A naive model of a forest tree, to check the forest against.
+#[derive(Debug, Clone)]
+struct ReferenceNode {
+    value: u32,
+    is_root: bool,
+    children: Vec<ReferenceNode>,
+}
+
+impl ReferenceNode {
+    fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {
+        use proptest::prelude::*;
+
+        let leaf = (any::<u32>(), prop::bool::weighted(0.2)).prop_map(|(value, is_root)| Self {
+            value,
+            is_root,
+            children: Vec::new(),
+        });
+        leaf.prop_recursive(5, 64, 6, |inner| {
+            (
+                any::<u32>(),
+                prop::bool::weighted(0.2),
+                prop::collection::vec(inner, 0..6),
+            )
+                .prop_map(|(value, is_root, children)| Self {
+                    value,
+                    is_root,
+                    children,
+                })
+        })
+    }
+
+    fn len(&self) -> u32 {
+        1 + self.children.iter().map(Self::len).sum::<u32>()
+    }
+
+    fn insert_children(&self, builder: &mut crate::forest::ForestBuilder<'_, u32>) {
+        for child in &self.children {
+            let mut child_builder = builder.insert(child.value, child.is_root);
+            child.insert_children(&mut child_builder);
+        }
+    }
+
+    fn preorder(&self, values: &mut Vec<(u32, bool)>) {
+        values.push((self.value, self.is_root));
+        for child in &self.children {
+            child.preorder(values);
+        }
+    }
+
+    fn root_count(&self) -> usize {
+        usize::from(self.is_root) + self.children.iter().map(Self::root_count).sum::<usize>()
+    }
+
+    fn assert_matches(&self, tree: crate::forest::TreeRef<'_, u32, crate::forest::Shared>) {
+        use crate::forest::*;
+
+        assert_eq!(*tree.parent, self.value);
+        assert_eq!(tree.len(), self.len());
+
+        let mut subtrees = Vec::new();
+        iter_subtrees!(tree.descendants => |subtree| subtrees.push(subtree));
+        assert_eq!(subtrees.len(), self.children.len());
+        for (subtree, child) in subtrees.into_iter().zip(&self.children) {
+            assert_eq!(subtree.is_root, child.is_root);
+            child.assert_matches(subtree);
+        }
+
+        let expected_children: Vec<u32> = self
+            .children
+            .iter()
+            .filter(|child| !child.is_root)
+            .map(|child| child.value)
+            .collect();
+        let mut children = Vec::new();
+        iter_children!(tree.descendants => |child| children.push(*child));
+        assert_eq!(children, expected_children);
+        assert_eq!(tree.descendants.child_count(), expected_children.len());
+
+        let mut indexed_children = Vec::new();
+        iter_child_indices!(tree.descendants => |child, index| {
+            assert_eq!(tree.descendants[index], *child);
+            indexed_children.push(*child);
+        });
+        assert_eq!(indexed_children, expected_children);
+    }
+}
+
+/// Increments every node of the tree that doesn't belong to a nested tree.
+fn increment_tree(mut tree: crate::forest::TreeRef<'_, u32, crate::forest::Exclusive>) {
+    use crate::forest::*;
+
+    *tree.parent = tree.parent.wrapping_add(1);
+    iter_subtrees!(tree.descendants => |mut subtree| {
+        if subtree.is_root {
+            continue;
+        }
+        increment_tree(subtree.reborrow_mut());
+    });
+}
+
+proptest::proptest! {
+    #![proptest_config(proptest::test_runner::Config {
+        cases: if cfg!(miri) { 4 } else { 256 },
+        failure_persistence: None,
+        ..Default::default()
+    })]
+
+    #[test]
+    fn forest_matches_a_naive_reference(
+        primaries in proptest::collection::vec(ReferenceNode::strategy(), 1..4),
+    ) {
+        use crate::forest::*;
+
+        let mut forest = Forest::<u32>::default();
+        for primary in &primaries {
+            let mut builder = forest.push_primary(primary.value);
+            primary.insert_children(&mut builder);
+        }
+
+        let mut expected_preorder = Vec::new();
+        for primary in &primaries {
+            primary.preorder(&mut expected_preorder);
+            // the root of a primary tree is a root regardless of the model
+            let primary_index = expected_preorder.len() - (primary.len() as usize);
+            expected_preorder[primary_index].1 = true;
+        }
+        let preorder: Vec<(u32, bool)> = forest
+            .preorder()
+            .map(|(&value, _, is_root)| (value, is_root))
+            .collect();
+        assert_eq!(preorder, expected_preorder);
+
+        assert_eq!(forest.primary_count(), primaries.len());
+        for (primary_index, primary) in primaries.iter().enumerate() {
+            let mut trees = forest.trees_of_primary(primary_index);
+            primary.assert_matches(trees.next().unwrap());
+
+            let mut tree_count = 1;
+            while trees.next().is_some() {
+                tree_count += 1;
+            }
+            assert_eq!(tree_count, 1 + primary.root_count() - usize::from(primary.is_root));
+        }
+
+        let mut trees = forest.trees_mut();
+        while let Some(tree) = trees.next() {
+            increment_tree(tree);
+        }
+        let incremented: Vec<(u32, bool)> = forest
+            .preorder()
+            .map(|(&value, _, is_root)| (value, is_root))
+            .collect();
+        let expected_incremented: Vec<(u32, bool)> = expected_preorder
+            .iter()
+            .map(|&(value, is_root)| (value.wrapping_add(1), is_root))
+            .collect();
+        assert_eq!(incremented, expected_incremented);
+
+        let mut primary = forest.nth_primary_mut(0).unwrap();
+        let mut indices = Vec::new();
+        iter_child_indices!(primary.descendants => |mut child, index| {
+            *child = child.wrapping_sub(1);
+            indices.push(index);
+        });
+        // the children were incremented once and decremented again
+        let primary = forest.primary().unwrap();
+        let mut expected_indices = Vec::new();
+        let mut children = Vec::new();
+        iter_child_indices!(primary.descendants => |child, index| {
+            expected_indices.push(index);
+            children.push(*child);
+        });
+        let expected_children: Vec<u32> = primaries[0]
+            .children
+            .iter()
+            .filter(|child| !child.is_root)
+            .map(|child| child.value)
+            .collect();
+        assert_eq!(children, expected_children);
+        assert_eq!(indices, expected_indices);
+    }
+}
+
+#[test]
+fn word_boundary_policies_split_paths_differently() {
+    use crate::input::*;
+    use crate::widgets::{TextBox, WordBoundaryPolicy};
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 300.px(),
+        height: 20.px(),
+    };
+
+    let press = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(key),
+            location: KeyLocation::Left,
+            text: None,
+            repeat: false,
+        });
+    };
+    let release = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: Key::Named(key),
+            location: KeyLocation::Left,
+            text: None,
+        });
+    };
+
+    // the texts left after each word deletion with Control held
+    let delete_words = |policy: WordBoundaryPolicy, key: NamedKey| {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        let mut text = String::from("foo_bar.baz/qux");
+        let frame = |gui: &mut ByorGui<NullRenderer>, text: &mut String| {
+            gui.frame(screen_size, |mut gui| {
+                let text_box = TextBox::new(text)
+                    .with_word_boundaries(policy)
+                    .with_uid(Uid::from_array(b"text_box"))
+                    .with_style(&style);
+                gui.show(text_box)
+            })
+            .result
+            .expect("error building GUI");
+        };
+
+        gui.on_input_event(InputEvent::CursorMoved {
+            position: Vec2 {
+                x: 1.px(),
+                y: 10.px(),
+            },
+        });
+        gui.on_input_event(InputEvent::ButtonPressed {
+            button: MouseButton::Primary,
+        });
+        frame(&mut gui, &mut text);
+        gui.on_input_event(InputEvent::ButtonReleased {
+            button: MouseButton::Primary,
+        });
+        if key == NamedKey::Backspace {
+            press(&mut gui, NamedKey::End);
+            frame(&mut gui, &mut text);
+            release(&mut gui, NamedKey::End);
+        }
+
+        let mut texts = Vec::new();
+        press(&mut gui, NamedKey::Control);
+        while !text.is_empty() && texts.len() < 10 {
+            press(&mut gui, key);
+            frame(&mut gui, &mut text);
+            release(&mut gui, key);
+            texts.push(text.clone());
+        }
+        texts
+    };
+
+    assert_eq!(
+        delete_words(WordBoundaryPolicy::Identifier, NamedKey::Backspace),
+        ["foo_bar.baz/", "foo_bar.baz", "foo_bar.", "foo_bar", ""],
+    );
+    assert_eq!(
+        delete_words(WordBoundaryPolicy::Identifier, NamedKey::Delete),
+        [".baz/qux", "baz/qux", "/qux", "qux", ""],
+    );
+    assert_eq!(
+        delete_words(WordBoundaryPolicy::Whitespace, NamedKey::Backspace),
+        [""],
+    );
+    assert_ne!(
+        delete_words(WordBoundaryPolicy::Unicode, NamedKey::Backspace),
+        delete_words(WordBoundaryPolicy::Identifier, NamedKey::Backspace),
+    );
+}
+
+#[derive(Debug, Clone)]
+struct ElisionReference {
+    value: u32,
+    is_root: bool,
+    elide: bool,
+    children: Vec<ElisionReference>,
+}
+
+impl ElisionReference {
+    fn new(node: &ReferenceNode, is_primary: bool) -> Self {
+        Self {
+            value: node.value,
+            is_root: is_primary || node.is_root,
+            // the model elides every non-root node with an odd value
+            elide: !is_primary && !node.is_root && (node.value % 2 == 1),
+            children: node
+                .children
+                .iter()
+                .map(|child| Self::new(child, false))
+                .collect(),
+        }
+    }
+
+    fn elided_preorder(&self, values: &mut Vec<(u32, bool)>) {
+        if !self.elide {
+            values.push((self.value, self.is_root));
+        }
+        for child in &self.children {
+            child.elided_preorder(values);
+        }
+    }
+
+    /// The children a node has after eliding, with the children of elided nodes spliced in.
+    fn elided_children(&self, children: &mut Vec<u32>) {
+        for child in &self.children {
+            if child.is_root {
+                continue;
+            }
+
+            if child.elide {
+                child.elided_children(children);
+            } else {
+                children.push(child.value);
+            }
+        }
+    }
+
+    fn elided_count(&self) -> usize {
+        usize::from(self.elide) + self.children.iter().map(Self::elided_count).sum::<usize>()
+    }
+}
+
+proptest::proptest! {
+    #![proptest_config(proptest::test_runner::Config {
+        cases: if cfg!(miri) { 4 } else { 256 },
+        failure_persistence: None,
+        ..Default::default()
+    })]
+
+    #[test]
+    fn eliding_nodes_splices_their_children_into_the_parent(
+        primaries in proptest::collection::vec(ReferenceNode::strategy(), 1..4),
+    ) {
+        use crate::forest::*;
+
+        let mut forest = Forest::<u32>::default();
+        for primary in &primaries {
+            let mut builder = forest.push_primary(primary.value);
+            primary.insert_children(&mut builder);
+        }
+        let tree_count = {
+            let mut trees = forest.trees();
+            let mut tree_count = 0;
+            while trees.next().is_some() {
+                tree_count += 1;
+            }
+            tree_count
+        };
+
+        let references: Vec<ElisionReference> = primaries
+            .iter()
+            .map(|primary| ElisionReference::new(primary, true))
+            .collect();
+        let elided = forest.elide(|tree| *tree.parent % 2 == 1);
+        assert_eq!(
+            elided,
+            references.iter().map(ElisionReference::elided_count).sum::<usize>()
+        );
+
+        let mut expected_preorder = Vec::new();
+        for reference in &references {
+            reference.elided_preorder(&mut expected_preorder);
+        }
+        let preorder: Vec<(u32, bool)> = forest
+            .preorder()
+            .map(|(&value, _, is_root)| (value, is_root))
+            .collect();
+        assert_eq!(preorder, expected_preorder);
+        assert_eq!(forest.len(), expected_preorder.len());
+
+        // every tree is still there, and nodes have the children of their elided children
+        assert_eq!(forest.primary_count(), primaries.len());
+        let mut trees = forest.trees();
+        let mut remaining_tree_count = 0;
+        while let Some(tree) = trees.next() {
+            remaining_tree_count += 1;
+            assert!(tree.is_root);
+        }
+        assert_eq!(remaining_tree_count, tree_count);
+
+        for (primary_index, reference) in references.iter().enumerate() {
+            let mut trees = forest.trees_of_primary(primary_index);
+            let primary = trees.next().unwrap();
+            assert_eq!(*primary.parent, reference.value);
+
+            let mut children = Vec::new();
+            iter_children!(primary.descendants => |child| children.push(*child));
+            let mut expected_children = Vec::new();
+            reference.elided_children(&mut expected_children);
+            assert_eq!(children, expected_children);
+        }
+    }
+}
+
+/// Records everything visible that is drawn, together with the clip rect it is drawn in.
+#[derive(Default)]
+struct DrawRecorder {
+    /// The minimum and maximum corners of the clip rects
+    clips: Vec<(crate::Vec2<crate::Pixel>, crate::Vec2<crate::Pixel>)>,
+    draws: Vec<String>,
+}
+
+impl DrawRecorder {
+    fn clip(&self) -> Option<(crate::Vec2<crate::Pixel>, crate::Vec2<crate::Pixel>)> {
+        self.clips
+            .iter()
+            .copied()
+            .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.max(b_min), a_max.min(b_max)))
+    }
+
+    fn record(&mut self, draw: String) {
+        let clip = self.clip();
+        // empty clip rects can differ without anything being visible
+        if let Some((min, max)) = clip
+            && ((min.x >= max.x) || (min.y >= max.y))
+        {
+            return;
+        }
+        self.draws.push(format!("{draw} in {clip:?}"));
+    }
+
+    /// Records a rect by the part of it that is visible, since the clip rects it is drawn in
+    /// can differ without changing what ends up on screen.
+    fn record_rect(
+        &mut self,
+        draw: String,
+        position: crate::Vec2<crate::Pixel>,
+        size: crate::Vec2<crate::Pixel>,
+    ) {
+        let (mut min, mut max) = (position, position + size);
+        if let Some((clip_min, clip_max)) = self.clip() {
+            min = min.max(clip_min);
+            max = max.min(clip_max);
+        }
+        if (min.x < max.x) && (min.y < max.y) {
+            self.draws
+                .push(format!("{draw} visible in {min:?} {max:?}"));
+        }
+    }
+}
+
+impl crate::rendering::Renderer for DrawRecorder {
+    type Error = std::convert::Infallible;
+
+    fn push_clip_rect(
+        &mut self,
+        position: crate::Vec2<crate::Pixel>,
+        size: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.clips.push((position, position + size));
+        Ok(())
+    }
+
+    fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+        self.clips.pop();
+        Ok(())
+    }
+
+    fn draw_rect(
+        &mut self,
+        position: crate::Vec2<crate::Pixel>,
+        size: crate::Vec2<crate::Pixel>,
+        corner_radius: crate::Float<crate::Pixel>,
+        stroke_width: crate::Float<crate::Pixel>,
+        color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        self.record_rect(
+            format!("rect {position:?} {size:?} {corner_radius:?} {stroke_width:?} {color:?}"),
+            position - stroke_width / 2.0,
+            size + stroke_width,
+        );
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        position: crate::Vec2<crate::Pixel>,
+        size: crate::Vec2<crate::Pixel>,
+        corner_radius: crate::Float<crate::Pixel>,
+        brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        use crate::style::computed::ComputedBrush;
+
+        if !matches!(brush, ComputedBrush::Solid(color) if color.a == 0) {
+            self.record_rect(
+                format!("fill {position:?} {size:?} {corner_radius:?} {brush:?}"),
+                position,
+                size,
+            );
+        }
+        Ok(())
+    }
+
+    fn draw_poly(
+        &mut self,
+        vertices: &[crate::Vec2<crate::Pixel>],
+        stroke_width: crate::Float<crate::Pixel>,
+        color: crate::style::Color,
+    ) -> Result<(), Self::Error> {
+        self.record(format!("poly {vertices:?} {stroke_width:?} {color:?}"));
+        Ok(())
+    }
+
+    fn fill_poly(
+        &mut self,
+        vertices: &[crate::Vec2<crate::Pixel>],
+        brush: crate::style::computed::ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        self.record(format!("fill poly {vertices:?} {brush:?}"));
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        text: parley::GlyphRun<'_, crate::style::Color>,
+        position: crate::Vec2<crate::Pixel>,
+    ) -> Result<(), Self::Error> {
+        self.record(format!(
+            "text {position:?} {} glyphs",
+            text.positioned_glyphs().count()
+        ));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TestSizing {
+    FitContent,
+    Grow,
+    Fixed(f32),
+}
+
+impl From<TestSizing> for crate::style::Sizing {
+    fn from(sizing: TestSizing) -> Self {
+        use crate::*;
+
+        match sizing {
+            TestSizing::FitContent => Self::FitContent,
+            TestSizing::Grow => Self::Grow,
+            TestSizing::Fixed(size) => Self::Fixed(size.px().into()),
+        }
+    }
+}
+
+/// A randomly generated widget, optionally wrapped in nodes that only pass it through.
+#[derive(Debug, Clone)]
+struct TestWidget {
+    width: TestSizing,
+    height: TestSizing,
+    min_width: f32,
+    min_hit_size: f32,
+    padding: f32,
+    background: bool,
+    direction: crate::style::Direction,
+    child_alignment: crate::style::Alignment,
+    cross_axis_alignment: crate::style::Alignment,
+    has_uid: bool,
+    text: Option<&'static str>,
+    /// The wrappers around the widget, with the layout properties they don't share with it
+    wrappers: Vec<(crate::style::Direction, crate::style::Alignment)>,
+    children: Vec<TestWidget>,
+}
+
+impl TestWidget {
+    fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {
+        use crate::style::*;
+        use proptest::prelude::*;
+
+        let sizing = prop_oneof![
+            Just(TestSizing::FitContent),
+            Just(TestSizing::Grow),
+            (1u8..120).prop_map(|size| TestSizing::Fixed(f32::from(size) * 2.5)),
+        ];
+        let direction = prop_oneof![Just(Direction::LeftToRight), Just(Direction::TopToBottom)];
+        let alignment = || {
+            prop_oneof![
+                Just(Alignment::Start),
+                Just(Alignment::Center),
+                Just(Alignment::End),
+            ]
+        };
+        let text = prop_oneof![
+            Just(None),
+            Just(Some("Lorem")),
+            Just(Some("Lorem ipsum dolor sit amet")),
+        ];
+
+        let widget = (
+            (sizing.clone(), sizing),
+            prop_oneof![Just(0.0), (1u8..40).prop_map(|size| f32::from(size) * 2.5)],
+            prop_oneof![Just(0.0), Just(24.0), Just(60.0)],
+            prop_oneof![Just(0.0), Just(1.5), Just(4.0)],
+            prop::bool::weighted(0.3),
+            (direction.clone(), alignment(), alignment()),
+            prop::bool::weighted(0.7),
+            text,
+            prop::collection::vec((direction, alignment()), 0..3),
+        )
+            .prop_map(
+                |(
+                    (width, height),
+                    min_width,
+                    min_hit_size,
+                    padding,
+                    background,
+                    (direction, child_alignment, cross_axis_alignment),
+                    has_uid,
+                    text,
+                    wrappers,
+                )| Self {
+                    width,
+                    height,
+                    min_width,
+                    min_hit_size,
+                    padding,
+                    background,
+                    direction,
+                    child_alignment,
+                    cross_axis_alignment,
+                    has_uid,
+                    text,
+                    wrappers,
+                    children: Vec::new(),
+                },
+            );
+
+        widget.clone().prop_recursive(4, 48, 5, move |inner| {
+            (widget.clone(), prop::collection::vec(inner, 0..5)).prop_map(|(widget, children)| {
+                Self {
+                    text: None,
+                    children,
+                    ..widget
+                }
+            })
+        })
+    }
+
+    fn show<Renderer: crate::rendering::Renderer>(
+        &self,
+        gui: &mut crate::ByorGuiContext<'_, Renderer>,
+        next_uid: &mut u64,
+    ) -> Result<(), crate::WidgetError> {
+        self.show_wrapped(gui, &self.wrappers, next_uid)
+    }
+
+    fn show_wrapped<Renderer: crate::rendering::Renderer>(
+        &self,
+        gui: &mut crate::ByorGuiContext<'_, Renderer>,
+        wrappers: &[(crate::style::Direction, crate::style::Alignment)],
+        next_uid: &mut u64,
+    ) -> Result<(), crate::WidgetError> {
+        use crate::style::*;
+        use crate::*;
+
+        if let Some((&(direction, child_alignment), wrappers)) = wrappers.split_first() {
+            // it sizes itself like the widget, but lays out its child differently
+            let style = style! {
+                width: Sizing::from(self.width),
+                height: Sizing::from(self.height),
+                min_width: self.min_width.px(),
+                padding: 0.px(),
+                cross_axis_alignment: self.cross_axis_alignment,
+                layout_direction: direction,
+                child_alignment: child_alignment,
+            };
+
+            return gui
+                .insert_node(
+                    None,
+                    &style,
+                    NodeContents::builder(|mut gui| {
+                        self.show_wrapped(&mut gui, wrappers, next_uid)
+                    }),
+                )?
+                .result;
+        }
+
+        let mut style = style! {
+            width: Sizing::from(self.width),
+            height: Sizing::from(self.height),
+            min_width: self.min_width.px(),
+            min_hit_size: self.min_hit_size.px(),
+            padding: self.padding.px(),
+            child_spacing: 2.px(),
+            layout_direction: self.direction,
+            child_alignment: self.child_alignment,
+            cross_axis_alignment: self.cross_axis_alignment,
+        };
+        if self.background {
+            style = style.with_background(Color::rgb(200, 100, 50));
+        }
+
+        let uid = self.has_uid.then(|| {
+            *next_uid += 1;
+            Uid::new(*next_uid)
+        });
+        let contents = match self.text {
+            Some(text) => NodeContents::text(text),
+            None => NodeContents::EMPTY,
+        };
+        gui.insert_node(
+            uid,
+            &style,
+            contents.with_builder(|mut gui| -> Result<(), WidgetError> {
+                for child in &self.children {
+                    child.show(&mut gui, next_uid)?;
+                }
+                Ok(())
+            }),
+        )?
+        .result
+    }
+}
+
+proptest::proptest! {
+    #![proptest_config(proptest::test_runner::Config {
+        cases: if cfg!(miri) { 2 } else { 128 },
+        failure_persistence: None,
+        ..Default::default()
+    })]
+
+    #[test]
+    fn flattening_nodes_does_not_change_the_outcome(
+        widgets in proptest::collection::vec(TestWidget::strategy(), 1..4),
+        rtl in proptest::bool::ANY,
+        cursor_position in (0u16..400, 0u16..300),
+    ) {
+        use crate::input::*;
+        use crate::style::*;
+        use crate::*;
+
+        let screen_size = Vec2 {
+            x: 400.px(),
+            y: 300.px(),
+        };
+        let root_style = style! {
+            width: Sizing::Grow,
+            height: Sizing::Grow,
+            child_spacing: 3.px(),
+            ui_direction: if rtl { UiDirection::Rtl } else { UiDirection::Ltr },
+        };
+
+        let run = |node_flattening: bool| {
+            let mut gui = ByorGui::<DrawRecorder>::default();
+            gui.set_node_flattening(node_flattening);
+            gui.on_input_event(InputEvent::CursorMoved {
+                position: Vec2 {
+                    x: f32::from(cursor_position.0).px(),
+                    y: f32::from(cursor_position.1).px(),
+                },
+            });
+
+            // the second frame sees the hover states resolved at the end of the first, and the
+            // third one is built after the cursor moved a little, which keeps hovered nodes sticky
+            for frame in 0..3 {
+                if frame == 2 {
+                    gui.on_input_event(InputEvent::CursorMoved {
+                        position: Vec2 {
+                            x: f32::from(cursor_position.0).px() + 1.px(),
+                            y: f32::from(cursor_position.1).px() + 1.px(),
+                        },
+                    });
+                }
+
+                gui.frame(screen_size, |mut gui| {
+                    let mut next_uid = 0;
+                    gui.insert_node(
+                        None,
+                        &root_style,
+                        NodeContents::builder(|mut gui| -> Result<(), WidgetError> {
+                            for widget in &widgets {
+                                widget.show(&mut gui, &mut next_uid)?;
+                            }
+                            Ok(())
+                        }),
+                    )?
+                    .result
+                })
+                .result
+                .expect("error building GUI");
+            }
+
+            let mut states: Vec<_> = gui
+                .data
+                .previous_state
+                .iter()
+                .map(|(uid, state)| {
+                    (
+                        uid,
+                        state.hover_state,
+                        state.position,
+                        state.size,
+                        state.content_size,
+                    )
+                })
+                .collect();
+            states.sort_by_key(|&(uid, ..)| uid.0);
+
+            let mut recorder = DrawRecorder::default();
+            gui.render(&mut recorder).unwrap();
+            (gui.node_count(), states, recorder.draws)
+        };
+
+        let (node_count, states, draws) = run(false);
+        let (flattened_node_count, flattened_states, flattened_draws) = run(true);
+        assert!(flattened_node_count <= node_count);
+        assert_eq!(flattened_states, states);
+        assert_eq!(flattened_draws, draws);
+    }
+}
+
+#[test]
+fn flattening_only_removes_pass_through_wrappers() {
+    use crate::style::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let grow_style = style! {
+        width: Sizing::Grow,
+        min_width: 20.px(),
+        padding: 0.px(),
+    };
+    let tinted_style = grow_style.clone().with_background(Color::rgb(255, 0, 0));
+    let uid = Uid::from_array(b"content");
+
+    let run = |node_flattening: bool| {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        gui.set_node_flattening(node_flattening);
+        gui.frame(screen_size, |mut gui| {
+            // only the innermost wrapper passes its child through
+            gui.insert_node(
+                None,
+                &tinted_style,
+                NodeContents::builder(|mut gui| {
+                    gui.insert_node(
+                        None,
+                        &grow_style,
+                        NodeContents::builder(|mut gui| {
+                            gui.insert_node(Some(uid), &grow_style, NodeContents::EMPTY)
+                                .map(|_| ())
+                        }),
+                    )?
+                    .result
+                }),
+            )?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        let state = gui.data.previous_state.get(uid).unwrap();
+        (gui.node_count(), state.position, state.size)
+    };
+
+    let (node_count, position, size) = run(false);
+    let (flattened_node_count, flattened_position, flattened_size) = run(true);
+    assert_eq!(node_count, 4);
+    assert_eq!(flattened_node_count, 3);
+    assert_eq!(flattened_position, position);
+    assert_eq!(flattened_size, size);
+}
+
+#[test]
+fn application_errors_are_returned_from_the_frame_unchanged() {
+    use crate::style::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let files = ["a.txt", "b.txt", "missing.txt"];
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let result = gui
+        .try_frame(screen_size, |mut gui| {
+            // the result of the contents is passed through the container, so no annotations needed
+            gui.insert_node(
+                None,
+                &Style::default(),
+                NodeContents::builder(|mut gui| {
+                    for file in files {
+                        if file.starts_with("missing") {
+                            let error = std::io::Error::new(std::io::ErrorKind::NotFound, file);
+                            return gui.fail(error);
+                        }
+
+                        gui.label(file)?;
+                    }
+
+                    Ok(())
+                }),
+            )?
+            .result
+        })
+        .result;
+
+    let error = result.unwrap_err();
+    let io_error = error
+        .downcast_user_ref::<std::io::Error>()
+        .expect("not the application error");
+    assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(error.to_string(), "missing.txt");
+    // the failed frame was discarded
+    assert_eq!(gui.node_count(), 0);
+
+    let result = gui
+        .try_frame(screen_size, |mut gui| {
+            let uid = Uid::from_array(b"twice");
+            gui.insert_node(Some(uid), &Style::default(), NodeContents::EMPTY)?;
+            gui.insert_node(Some(uid), &Style::default(), NodeContents::EMPTY)?;
+            Ok(())
+        })
+        .result;
+    assert!(matches!(result, Err(WidgetError::DuplicateUid(_))));
+}
+
+#[test]
+fn scroll_bar_visibility_policies() {
+    use crate::input::*;
+    use crate::widgets::ScrollBarVisibility;
+    use crate::*;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        padding: 0.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+
+    // shows content of the given height and returns the width left for it
+    let frame = |gui: &mut ByorGui<NullRenderer>,
+                 visibility: ScrollBarVisibility,
+                 content_height: Float<Pixel>| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style)
+            .with_scroll_bar_visibility(visibility);
+        let content_style = style! {
+            width: 100.px(),
+            height: content_height,
+        };
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(scroll_view, |mut gui| {
+                gui.insert_node(None, &content_style, NodeContents::EMPTY)
+                    .map(|_| ())
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.data
+            .previous_state
+            .get(SCROLL_UID)
+            .map(|state| state.size.x)
+            .unwrap_or_default()
+    };
+    let new_gui = || {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        gui.theme_mut().insert_style(
+            widgets::ScrollBar::VERTICAL_TYPE_CLASS,
+            &style! {
+                width: 20.px(),
+            },
+        );
+        gui
+    };
+    let settle = |gui: &mut ByorGui<NullRenderer>,
+                  visibility: ScrollBarVisibility,
+                  content_height: Float<Pixel>| {
+        for _ in 0..3 {
+            frame(gui, visibility, content_height);
+        }
+        frame(gui, visibility, content_height)
+    };
+
+    let mut gui = new_gui();
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::Auto, 90.px()),
+        200.px()
+    );
+    let with_bar = settle(&mut gui, ScrollBarVisibility::Auto, 120.px());
+    assert!(with_bar < 200.px());
+    // content that barely fits keeps the bar, content with room to spare hides it
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::Auto, 98.px()),
+        with_bar
+    );
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::Auto, 90.px()),
+        200.px()
+    );
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::Auto, 98.px()),
+        200.px()
+    );
+
+    let mut gui = new_gui();
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::AlwaysShow, 50.px()),
+        with_bar
+    );
+
+    let mut gui = new_gui();
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::AlwaysReserveSpace, 50.px()),
+        with_bar
+    );
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::AlwaysReserveSpace, 120.px()),
+        with_bar
+    );
+
+    let mut gui = new_gui();
+    assert_eq!(
+        settle(&mut gui, ScrollBarVisibility::Overlay, 120.px()),
+        200.px()
+    );
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui, ScrollBarVisibility::Overlay, 120.px());
+    gui.on_input_event(InputEvent::Scrolled {
+        delta: ScrollDelta::Pixel(Vec2 {
+            x: 0.px(),
+            y: -15.px(),
+        }),
+    });
+    frame(&mut gui, ScrollBarVisibility::Overlay, 120.px());
+    let scroll = gui
+        .data
+        .persistent_state
+        .get(SCROLL_UID)
+        .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+        .copied();
+    assert_eq!(scroll, Some(15.px()));
+}
+
+#[test]
+fn uids_can_be_built_at_compile_time() {
+    use crate::*;
+
+    const SIDEBAR: Uid = uid!("sidebar");
+    const SEARCH: Uid = SIDEBAR.concat(uid!("search"));
+    const FROM_ARRAY: Uid = Uid::from_array(b"sidebar");
+
+    assert_eq!(SIDEBAR, Uid::from_slice(b"sidebar"));
+    assert_eq!(SIDEBAR, FROM_ARRAY);
+    assert_eq!(
+        SEARCH,
+        Uid::from_slice(b"sidebar").concat(Uid::from_slice(b"search"))
+    );
+    assert_ne!(SEARCH, uid!("search").concat(SIDEBAR));
+
+    // every call site gets its own UID, but a call site always produces the same one
+    let from_loop: Vec<Uid> = (0..2).map(|_| Uid::from_location()).collect();
+    assert_eq!(from_loop[0], from_loop[1]);
+    assert_ne!(Uid::from_location(), Uid::from_location());
+}
+
+#[test]
+fn computed_styles_compare_by_their_contents() {
+    use crate::style::computed::*;
+    use crate::style::*;
+    use crate::*;
+    use std::hash::{DefaultHasher, Hasher};
+
+    let theme = theme::Theme::default();
+    let root_style = Style::DEFAULT;
+    let root_cascaded_style = root_style.cascade_root(
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+        NodeInputState::default(),
+        &theme,
+    );
+    let root_computed_style = compute_style(
+        &root_style,
+        &root_cascaded_style,
+        None,
+        1.0,
+        1.0,
+        &mut SharedComputedValues::default(),
+        &mut InvalidProperties::new(),
+    );
+
+    let compute = |style: &Style| {
+        let cascaded_style = style.cascade(&root_cascaded_style, NodeInputState::default(), &theme);
+        compute_style(
+            style,
+            &cascaded_style,
+            Some(&root_computed_style),
+            1.0,
+            1.0,
+            &mut SharedComputedValues::default(),
+            &mut InvalidProperties::new(),
+        )
+    };
+    let hash = |style: &ComputedStyle| {
+        let mut hasher = DefaultHasher::new();
+        style.hash_relevant(&mut hasher);
+        hasher.finish()
+    };
+
+    // padding, background and font are allocated separately for both styles
+    let base_style = Style::default()
+        .with_padding(Padding::from(2.px()))
+        .with_background(Color::rgb(10, 20, 30))
+        .with_font_size(16.px());
+    let base = compute(&base_style);
+    let same = compute(&base_style);
+    assert!(base == same);
+    assert!(base == base.clone());
+    assert_eq!(hash(&base), hash(&same));
+
+    let changes = [
+        base_style.clone().with_enabled(false),
+        base_style.clone().with_width(Sizing::Grow),
+        base_style
+            .clone()
+            .with_height(Sizing::Fixed(20.px().into())),
+        base_style.clone().with_min_width(5.px()),
+        base_style.clone().with_min_height(5.px()),
+        base_style.clone().with_max_width(50.px()),
+        base_style.clone().with_max_height(50.px()),
+        base_style.clone().with_min_hit_width(30.px()),
+        base_style.clone().with_min_hit_height(30.px()),
+        base_style.clone().with_flex_ratio(2.0),
+        base_style.clone().with_padding(Padding::from(3.px())),
+        base_style.clone().with_child_spacing(3.px()),
+        base_style
+            .clone()
+            .with_layout_direction(Direction::TopToBottom),
+        base_style.clone().with_child_alignment(Alignment::Center),
+        base_style.clone().with_cross_axis_alignment(Alignment::End),
+        base_style.clone().with_background(Color::rgb(10, 20, 31)),
+        base_style.clone().with_corner_radius(2.px()),
+        base_style.clone().with_border_width(1.px()),
+        base_style.clone().with_border_color(Color::rgb(1, 2, 3)),
+        base_style.clone().with_drop_shadow_width(2.px()),
+        base_style
+            .clone()
+            .with_drop_shadow_color(Color::rgb(1, 2, 3)),
+        base_style.clone().with_backdrop_blur(2.px()),
+        base_style
+            .clone()
+            .with_font_family(FontStack::Single(FontFamily::Named("Test".into()))),
+        base_style.clone().with_font_size(17.px()),
+        base_style.clone().with_font_style(FontStyle::Italic),
+        base_style.clone().with_font_weight(FontWeight::BOLD),
+        base_style.clone().with_font_width(FontWidth::CONDENSED),
+        base_style
+            .clone()
+            .with_text_underline(!INITIAL_TEXT_UNDERLINE),
+        base_style
+            .clone()
+            .with_text_strikethrough(!INITIAL_TEXT_STRIKETHROUGH),
+        base_style.clone().with_text_wrap(!INITIAL_TEXT_WRAP),
+        base_style
+            .clone()
+            .with_overflow_wrap(OverflowWrap::Anywhere),
+        base_style.clone().with_hyphenation(!INITIAL_HYPHENATION),
+        base_style
+            .clone()
+            .with_locale(smol_str::SmolStr::new_static("de-DE")),
+        base_style.clone().with_text_color(Color::rgb(1, 2, 3)),
+        base_style.clone().with_text_stroke_width(1.px()),
+        base_style
+            .clone()
+            .with_text_stroke_color(Color::rgb(1, 2, 3)),
+        base_style.clone().with_selection_color(Color::rgb(1, 2, 3)),
+        base_style
+            .clone()
+            .with_selection_text_color(Some(Color::rgb(1, 2, 3))),
+        base_style
+            .clone()
+            .with_caret_color(Some(Color::rgb(1, 2, 3))),
+        base_style.clone().with_caret_width(3.px()),
+        base_style.clone().with_caret_shape(CaretShape::Block),
+        base_style
+            .clone()
+            .with_overwrite_caret_shape(CaretShape::Bar),
+        base_style
+            .clone()
+            .with_horizontal_text_alignment(HorizontalTextAlignment::Center),
+        base_style
+            .clone()
+            .with_vertical_text_alignment(VerticalTextAlignment::Center),
+        base_style
+            .clone()
+            .with_vertical_text_anchor(VerticalTextAnchor::CapHeight),
+        base_style.clone().with_ui_direction(UiDirection::Rtl),
+    ];
+
+    for style in changes {
+        let changed = compute(&style);
+        assert!(changed != base, "{style:?} compared equal");
+        assert_ne!(hash(&changed), hash(&base), "{style:?} hashed the same");
+    }
+}
+
+#[test]
+fn scroll_views_recover_from_zero_sized_frames_and_corrupt_offsets() {
+    use crate::input::*;
+    use crate::*;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+
+    let scroll_view_style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+        layout_direction: Direction::TopToBottom,
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 100.px(),
+    };
+
+    let frame = |gui: &mut ByorGui<NullRenderer>, screen_size: Vec2<Pixel>| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style);
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(scroll_view, |mut gui| {
+                gui.with_index_scope(0..10, |gui, _| {
+                    gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                        .map(|_| ())
+                })
+            })
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.data
+            .persistent_state
+            .get(SCROLL_UID)
+            .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+            .copied()
+            .unwrap_or_default()
+    };
+    let scroll_by = |gui: &mut ByorGui<NullRenderer>, y: f32| {
+        gui.on_input_event(InputEvent::Scrolled {
+            delta: ScrollDelta::Pixel(Vec2 {
+                x: 0.px(),
+                y: y.px(),
+            }),
+        });
+    };
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui, screen_size);
+    frame(&mut gui, screen_size);
+    scroll_by(&mut gui, -150.0);
+    assert_eq!(frame(&mut gui, screen_size), 150.px());
+
+    // the offset survives frames without any space, e.g. while the window is minimized
+    assert_eq!(frame(&mut gui, Vec2::ZERO), 150.px());
+    scroll_by(&mut gui, -150.0);
+    assert_eq!(frame(&mut gui, Vec2::ZERO), 150.px());
+    assert_eq!(frame(&mut gui, screen_size), 150.px());
+    assert_eq!(frame(&mut gui, screen_size), 150.px());
+
+    scroll_by(&mut gui, -100.0);
+    assert_eq!(frame(&mut gui, screen_size), 250.px());
+    scroll_by(&mut gui, -1000.0);
+    assert_eq!(
+        frame(&mut gui, screen_size),
+        400.px(),
+        "clamped to the content"
+    );
+
+    scroll_by(&mut gui, f32::NAN);
+    assert_eq!(
+        frame(&mut gui, screen_size),
+        400.px(),
+        "NaN deltas are ignored"
+    );
+
+    gui.data
+        .persistent_state
+        .get_mut(SCROLL_UID)
+        .unwrap()
+        .insert(PersistentStateKey::VerticalScroll, f32::NAN.px());
+    let _ = gui.take_diagnostics();
+    assert_eq!(frame(&mut gui, screen_size), 0.px());
+    assert!(
+        gui.take_diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.uid == Some(SCROLL_UID))
+    );
+
+    scroll_by(&mut gui, -50.0);
+    assert_eq!(
+        frame(&mut gui, screen_size),
+        50.px(),
+        "scrolling still works"
+    );
+}
+
+#[test]
+fn scroll_bars_tolerate_degenerate_bounds() {
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.on_input_event(input::InputEvent::CursorLeft);
+    for _ in 0..2 {
+        let values = gui
+            .frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+                Ok([
+                    gui.horizontal_scroll_bar(0.5, 1.0, 1.0)?,
+                    gui.horizontal_scroll_bar(0.5, 1.0, 0.0)?,
+                    gui.horizontal_scroll_bar(f32::NAN, 0.0, f32::NAN)?,
+                    gui.horizontal_scroll_bar(0.5, f32::INFINITY, 2.0)?,
+                ])
+            })
+            .result
+            .expect("error building GUI");
+        assert_eq!(values, [1.0, 1.0, 0.0, 0.5]);
+    }
+}
+
+#[test]
+fn minimum_hit_sizes_grow_the_hovered_area_only() {
+    use crate::input::*;
+    use crate::*;
+
+    const LEFT_UID: Uid = Uid::from_array(b"left");
+    const RIGHT_UID: Uid = Uid::from_array(b"right");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let container_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        child_spacing: 10.px(),
+        child_alignment: Alignment::Center,
+        cross_axis_alignment: Alignment::Center,
+    };
+    let target_style = style! {
+        size: 10.px(),
+        min_hit_size: 30.px(),
+    };
+
+    // returns whether the left and right targets were clicked
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<[bool; 2]> {
+            gui.insert_node(
+                None,
+                &container_style,
+                NodeContents::builder(|mut gui| -> widgets::WidgetResult<[bool; 2]> {
+                    let mut clicked = [false; 2];
+                    for (i, uid) in [LEFT_UID, RIGHT_UID].into_iter().enumerate() {
+                        clicked[i] = gui
+                            .insert_node(
+                                Some(uid),
+                                &target_style,
+                                NodeContents::EMPTY.with_hit_target(HitTarget::SelfOnly),
+                            )?
+                            .input_state
+                            .clicked(MouseButtons::PRIMARY);
+                    }
+                    Ok(clicked)
+                }),
+            )?
+            .result
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let hover_state = |gui: &ByorGui<NullRenderer>, uid: Uid| {
+        gui.data.previous_state.get(uid).unwrap().hover_state
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.on_input_event(InputEvent::CursorLeft);
+    frame(&mut gui);
+
+    // the targets are drawn at 85..95 and 105..115, their 30px hit rects overlap
+    let left = gui.data.previous_state.get(LEFT_UID).unwrap();
+    assert_eq!(left.size, Vec2::from(10.px()));
+    assert_eq!(left.position.x, 85.px());
+    let y = left.position.y + 5.px();
+
+    for (x, hovered, not_hovered) in [
+        (80.0, LEFT_UID, RIGHT_UID),
+        (98.0, LEFT_UID, RIGHT_UID),
+        (102.0, RIGHT_UID, LEFT_UID),
+        (119.0, RIGHT_UID, LEFT_UID),
+    ] {
+        gui.move_cursor(Vec2 { x: x.px(), y });
+        frame(&mut gui);
+        assert_eq!(
+            hover_state(&gui, hovered),
+            HoverState::DirectlyHovered,
+            "at {x}"
+        );
+        assert_eq!(
+            hover_state(&gui, not_hovered),
+            HoverState::NotHovered,
+            "at {x}"
+        );
+    }
+
+    gui.move_cursor(Vec2 { x: 80.px(), y });
+    frame(&mut gui);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui), [true, false]);
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui);
+
+    gui.move_cursor(Vec2 { x: 72.px(), y });
+    frame(&mut gui);
+    assert_eq!(hover_state(&gui, LEFT_UID), HoverState::NotHovered);
+}
+
+#[test]
+fn min_touch_target_applies_to_interactive_type_classes() {
+    use crate::*;
+
+    const BUTTON_UID: Uid = Uid::from_array(b"button");
+    const NODE_UID: Uid = Uid::from_array(b"node");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let small_style = style! {
+        size: 10.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            let button = widgets::Button::default()
+                .with_uid(BUTTON_UID)
+                .with_style(&small_style);
+            gui.show(button)?;
+            gui.insert_node(Some(NODE_UID), &small_style, NodeContents::EMPTY)?;
+            Ok(())
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let is_hovered = |gui: &ByorGui<NullRenderer>, uid: Uid| {
+        gui.data.previous_state.get(uid).unwrap().hover_state != HoverState::NotHovered
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.theme_mut()
+        .set_min_touch_target(Some(AbsoluteMeasurement::Pixel(30.px())));
+
+    // the button is drawn at 0..10, the plain node at 10..20
+    gui.move_cursor(Vec2 {
+        x: 5.px(),
+        y: 15.px(),
+    });
+    frame(&mut gui);
+    frame(&mut gui);
+    assert!(is_hovered(&gui, BUTTON_UID));
+
+    gui.move_cursor(Vec2 {
+        x: 15.px(),
+        y: 5.px(),
+    });
+    frame(&mut gui);
+    assert!(is_hovered(&gui, NODE_UID), "the node is not interactive");
+    assert!(!is_hovered(&gui, BUTTON_UID));
+
+    gui.move_cursor(Vec2 {
+        x: 5.px(),
+        y: 15.px(),
+    });
+    gui.theme_mut().set_min_touch_target(None);
+    frame(&mut gui);
+    frame(&mut gui);
+    assert!(!is_hovered(&gui, BUTTON_UID));
+}
+
+#[test]
+fn deferred_values_keep_the_gui_alive_while_loading() {
+    use crate::deferred::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>, value: &Deferred<u32, &str>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.deferred(
+                value,
+                |_| "loading".to_owned(),
+                |_, value| format!("ready {value}"),
+                |_, error| format!("failed {error}"),
+            )
+        })
+        .result
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let start = Instant::now();
+    gui.set_time(start);
+    let mut value = Deferred::default();
+    assert_eq!(frame(&mut gui, &value), "loading");
+    assert_eq!(gui.next_animation_frame(), Some(start + POLL_INTERVAL));
+
+    value = Deferred::Ready(3);
+    assert_eq!(frame(&mut gui, &value), "ready 3");
+    assert!(!gui.needs_animation());
+
+    value = Err("offline").into();
+    assert_eq!(frame(&mut gui, &value), "failed offline");
+    assert!(!gui.needs_animation());
+    assert_eq!(value.error(), Some(&"offline"));
+}
+
+#[test]
+fn grown_siblings_share_their_edges_at_any_scale_factor() {
+    use crate::*;
+
+    const PARENT_UID: Uid = Uid::from_array(b"parent");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let parent_style = style! {
+        width: 100.px(),
+        height: 20.px(),
+        padding: 1.pt(),
+        child_spacing: 1.pt(),
+    };
+    let child_style = style! {
+        width: Sizing::Grow,
+        height: Sizing::Grow,
+    };
+
+    for scale_factor in [1.0, 1.25, 1.5, 2.0] {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        gui.set_scale_factor(scale_factor);
+        gui.layout_only_frame(screen_size, |mut gui| {
+            gui.insert_node(
+                Some(PARENT_UID),
+                &parent_style,
+                NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                    for i in 0..3 {
+                        gui.insert_node(Some(Uid::new(i)), &child_style, NodeContents::EMPTY)?;
+                    }
+                    Ok(())
+                }),
+            )?
+            .result
+        })
+        .expect("error building GUI");
+
+        let parent = gui.expect_node(PARENT_UID);
+        // the padding and the child spacing
+        let gap = (1.0 * scale_factor).round().px();
+        let mut edge = parent.position().x + gap;
+        for i in 0..3 {
+            let child = gui.expect_node(Uid::new(i));
+            assert_eq!(
+                child.position().x,
+                edge,
+                "child {i} at scale {scale_factor}"
+            );
+            assert_eq!(child.size().x, child.size().x.round());
+            edge = child.position().x + child.size().x + gap;
+        }
+        assert_eq!(
+            edge,
+            parent.position().x + parent.size().x,
+            "the last child ends at the padding at scale {scale_factor}",
+        );
+    }
+}
+
+#[test]
+fn confirm_dialogs_report_the_choice_once() {
+    use crate::input::*;
+    use crate::widgets::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let options = ConfirmOptions {
+        title: "Delete file",
+        message: "Are you sure?",
+        confirm_label: "Delete",
+        danger: true,
+        ..ConfirmOptions::default()
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>, open: &mut bool| {
+        gui.frame(screen_size, |mut gui| gui.confirm_dialog(open, options))
+            .result
+            .expect("error building GUI")
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(key),
+            location: KeyLocation::Standard,
+            text: None,
+            repeat: false,
+        });
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: Key::Named(key),
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.theme_mut().insert_style(
+        Button::TYPE_CLASS,
+        &style! {
+            size: (60.px(), 20.px()),
+        },
+    );
+
+    let mut open = false;
+    assert_eq!(frame(&mut gui, &mut open), None);
+    assert!(gui.nodes().all(|node| node.text() != Some("Delete")));
+
+    open = true;
+    assert_eq!(frame(&mut gui, &mut open), None);
+    assert_eq!(frame(&mut gui, &mut open), None);
+    let delete = gui
+        .nodes()
+        .find(|node| node.text() == Some("Delete"))
+        .expect("the dialog is shown");
+    let delete_center = delete.position() + delete.size() / 2.0;
+    assert!(
+        (delete_center.x - screen_size.x / 2.0).value().abs() < 100.0,
+        "the dialog is centered"
+    );
+
+    gui.move_cursor(delete_center);
+    assert_eq!(frame(&mut gui, &mut open), None);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, &mut open), Some(DialogChoice::Confirmed));
+    assert!(!open);
+    gui.release_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, &mut open), None);
+
+    open = true;
+    frame(&mut gui, &mut open);
+    press(&mut gui, NamedKey::Escape);
+    assert_eq!(frame(&mut gui, &mut open), Some(DialogChoice::Cancelled));
+    assert!(!open);
+
+    open = true;
+    frame(&mut gui, &mut open);
+    press(&mut gui, NamedKey::Enter);
+    assert_eq!(frame(&mut gui, &mut open), Some(DialogChoice::Confirmed));
+
+    let mut message_open = true;
+    let mut message_frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.message_dialog(
+                &mut message_open,
+                MessageOptions {
+                    message: "Saved",
+                    ..MessageOptions::default()
+                },
+            )
+        })
+        .result
+        .expect("error building GUI")
+    };
+    assert!(!message_frame(&mut gui));
+    press(&mut gui, NamedKey::Escape);
+    assert!(message_frame(&mut gui));
+    assert!(!message_frame(&mut gui));
+}
+
+#[test]
+fn merged_themes_take_each_property_from_the_overlay_first() {
+    use crate::style::*;
+    use crate::theme::*;
+    use crate::*;
+
+    const BASE_BACKGROUND: Color = Color::rgb(10, 10, 10);
+    const OVERLAY_BACKGROUND: Color = Color::rgb(200, 0, 0);
+    const OVERLAY_ACCENT: Color = Color::rgb(0, 200, 0);
+
+    let mut base = Theme::default();
+    base.insert_style(
+        widgets::Button::TYPE_CLASS,
+        &style! {
+            padding: 4.px(),
+            background: BASE_BACKGROUND,
+        },
+    );
+    base.insert_token("brand", BASE_BACKGROUND);
+    base.insert_token("link", BASE_BACKGROUND);
+    base.set_role(ColorRole::Danger, BASE_BACKGROUND);
+    base.set_density(Theme::COMPACT_DENSITY);
+
+    let mut overlay = Theme::default();
+    overlay.insert_style(
+        widgets::Button::TYPE_CLASS,
+        &style! {
+            background: OVERLAY_BACKGROUND,
+        },
+    );
+    overlay.insert_token("brand", OVERLAY_BACKGROUND);
+    overlay.set_role(ColorRole::Accent, OVERLAY_ACCENT);
+
+    let theme = Theme::with_overlay(base, &overlay);
+    let style = theme.build_style(None, &[], widgets::Button::TYPE_CLASS);
+    assert!(matches!(style.padding, Property::Value(padding) if padding == Padding::from(4.px())));
+    assert!(matches!(
+        style.background,
+        Property::Value(Brush::Solid(OVERLAY_BACKGROUND))
+    ));
+
+    assert_eq!(theme.color_token("brand"), Some(OVERLAY_BACKGROUND));
+    assert_eq!(theme.color_token("link"), Some(BASE_BACKGROUND));
+    assert_eq!(theme.role(ColorRole::Accent), OVERLAY_ACCENT);
+    assert_eq!(
+        theme.role(ColorRole::Danger),
+        BASE_BACKGROUND,
+        "roles the overlay leaves alone keep the base color"
+    );
+    assert_eq!(theme.density(), Theme::COMPACT_DENSITY);
+}
+
+#[test]
+fn snapping_scroll_views_rest_on_child_edges() {
+    use crate::input::*;
+    use crate::*;
+    use std::cell::Cell;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+        child_spacing: 0.px(),
+    };
+    let mixed_heights = [30.px(), 50.px(), 20.px(), 40.px()];
+    let uniform_height = Cell::new(None);
+
+    let start = Instant::now();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style)
+            .with_snap(widgets::ScrollSnap::Item);
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(scroll_view, |mut gui| -> widgets::WidgetResult<()> {
+                for i in 0..20 {
+                    let height = uniform_height
+                        .get()
+                        .unwrap_or(mixed_heights[i % mixed_heights.len()]);
+                    let item_style = style! {
+                        width: 100.px(),
+                        height: height,
+                    };
+                    gui.uid_scope(Uid::new(i), |gui| {
+                        gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                    })?;
+                }
+                Ok(())
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.data
+            .persistent_state
+            .get(SCROLL_UID)
+            .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+            .copied()
+            .unwrap_or_default()
+    };
+    let wheel = |gui: &mut ByorGui<NullRenderer>, lines: f32| {
+        gui.on_input_event(InputEvent::Scrolled {
+            delta: ScrollDelta::Point(Vec2 {
+                x: 0.pt(),
+                y: lines.pt(),
+            }),
+        });
+    };
+
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui);
+    frame(&mut gui);
+
+    // every notch moves by the child at the start of the view, however small the delta
+    wheel(&mut gui, -10.0);
+    assert_eq!(frame(&mut gui), 30.px());
+    wheel(&mut gui, -10.0);
+    assert_eq!(frame(&mut gui), 80.px());
+    wheel(&mut gui, 10.0);
+    assert_eq!(frame(&mut gui), 30.px());
+    wheel(&mut gui, -200.0);
+    assert_eq!(
+        frame(&mut gui),
+        220.px(),
+        "large deltas snap to the nearest edge"
+    );
+
+    // precise input is followed exactly and only snaps once it stopped
+    gui.on_input_event(InputEvent::Scrolled {
+        delta: ScrollDelta::Pixel(Vec2 {
+            x: 0.px(),
+            y: -8.px(),
+        }),
+    });
+    gui.set_time(start);
+    assert_eq!(frame(&mut gui), 228.px());
+    gui.set_time(start);
+    assert_eq!(frame(&mut gui), 228.px());
+    assert!(gui.needs_animation());
+    gui.set_time(start + widgets::ScrollView::SNAP_SETTLE_DELAY);
+    assert_eq!(frame(&mut gui), 220.px());
+    assert!(!gui.needs_animation());
+
+    // dragging the thumb is continuous and snaps on release
+    let thumb_uid = SCROLL_UID
+        .concat(Uid::from_type::<widgets::scroll::ScrollBarData>())
+        .concat(Uid::from_type::<widgets::scroll::Thumb>());
+    let thumb = gui.data.previous_state.get(thumb_uid).unwrap();
+    let thumb_center = thumb.position + thumb.size / 2.0;
+    gui.move_cursor(thumb_center);
+    frame(&mut gui);
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui);
+    gui.move_cursor(
+        thumb_center
+            + Vec2 {
+                x: 0.px(),
+                y: 1.px(),
+            },
+    );
+    let dragged = frame(&mut gui);
+    let edges = gui
+        .data
+        .persistent_state
+        .get(SCROLL_UID)
+        .unwrap()
+        .get::<widgets::scroll::ScrollSnapState>(PersistentStateKey::ScrollSnap)
+        .unwrap()
+        .edges
+        .clone();
+    assert!(dragged > 220.px());
+    assert!(!edges.contains(&dragged), "{dragged:?} is an edge");
+    gui.release_button(MouseButton::Primary);
+    let released = frame(&mut gui);
+    assert!(edges.contains(&released), "{released:?} is not an edge");
+    assert_eq!(frame(&mut gui), released);
+
+    // the stored offset snaps to where the children are after they changed their size
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui);
+    wheel(&mut gui, 1000.0);
+    assert_eq!(frame(&mut gui), 0.px());
+    wheel(&mut gui, -10.0);
+    assert_eq!(frame(&mut gui), 30.px());
+    wheel(&mut gui, -10.0);
+    assert_eq!(frame(&mut gui), 80.px());
+    uniform_height.set(Some(25.px()));
+    frame(&mut gui);
+    assert_eq!(frame(&mut gui), 75.px());
+    wheel(&mut gui, -10.0);
+    assert_eq!(frame(&mut gui), 100.px());
+}
+
+#[test]
+fn retained_renderers_keep_their_state_while_the_node_exists() {
+    use crate::rendering::*;
+    use crate::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Prepare(Float<Pixel>),
+        Render { value: u32, renders: u32 },
+    }
+
+    type Log = Arc<Mutex<Vec<Event>>>;
+
+    struct Counter {
+        value: u32,
+        renders: u32,
+        log: Log,
+    }
+
+    impl RetainedNodeRenderer for Counter {
+        type Renderer = NullRenderer;
+
+        fn update(&mut self, next: Self) {
+            self.value = next.value;
+        }
+
+        fn prepare(&mut self, context: PrepareContext<'_>) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(Event::Prepare(context.size.x));
+        }
+
+        fn render(
+            &mut self,
+            _context: RenderContext<'_, NullRenderer>,
+        ) -> Result<(), std::convert::Infallible> {
+            self.renders += 1;
+            self.log.lock().unwrap().push(Event::Render {
+                value: self.value,
+                renders: self.renders,
+            });
+            Ok(())
+        }
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let log = Log::default();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>, node: Option<(Float<Pixel>, u32)>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            if let Some((width, value)) = node {
+                let style = style! {
+                    width: width,
+                    height: 20.px(),
+                };
+                gui.insert_node(
+                    Some(Uid::from_array(b"counter")),
+                    &style,
+                    NodeContents::EMPTY.with_retained_renderer(Counter {
+                        value,
+                        renders: 0,
+                        log: log.clone(),
+                    }),
+                )?;
+            }
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+        gui.render(&mut NullRenderer).unwrap();
+        std::mem::take(&mut *log.lock().unwrap())
+    };
+
+    assert_eq!(
+        frame(&mut gui, Some((50.px(), 1))),
+        [
+            Event::Prepare(50.px()),
+            Event::Render {
+                value: 1,
+                renders: 1
+            },
+        ],
+    );
+    assert_eq!(
+        frame(&mut gui, Some((50.px(), 2))),
+        [Event::Render {
+            value: 2,
+            renders: 2
+        }],
+        "the stored renderer takes the new data without being prepared again",
+    );
+    assert_eq!(
+        frame(&mut gui, Some((60.px(), 2))),
+        [
+            Event::Prepare(60.px()),
+            Event::Render {
+                value: 2,
+                renders: 3
+            },
+        ],
+    );
+
+    assert_eq!(frame(&mut gui, None), []);
+    assert_eq!(
+        frame(&mut gui, Some((60.px(), 3))),
+        [
+            Event::Prepare(60.px()),
+            Event::Render {
+                value: 3,
+                renders: 1
+            },
+        ],
+        "the renderer is dropped with its node",
+    );
+}
+
+#[test]
+fn list_boxes_select_with_modifier_clicks_and_keys() {
+    use crate::input::*;
+    use crate::*;
+
+    const LIST_UID: Uid = Uid::from_array(b"list");
+    const ITEM_COUNT: usize = 6;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+    let ctrl_a = Shortcut {
+        modifiers: Modifiers::CONTROL,
+        key: Key::Character("a".into()),
+        location: None,
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    // Returns whether the selection changed and whether Ctrl+A was left for other widgets.
+    let frame = |gui: &mut ByorGui<NullRenderer>,
+                 selection: &mut widgets::SelectionState,
+                 mode: widgets::SelectionMode| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+            gui.button("Elsewhere")?;
+            let list_box = widgets::ListBox::new(selection, ITEM_COUNT)
+                .with_mode(mode)
+                .with_uid(LIST_UID);
+            let changed = gui.list_box(list_box, |gui, _| {
+                gui.insert_node(None, &item_style, NodeContents::EMPTY)?;
+                Ok(())
+            })?;
+            Ok((
+                changed,
+                gui.global_input_state_mut().consume_shortcut(&ctrl_a),
+            ))
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let key_event = |gui: &mut ByorGui<NullRenderer>, key: Key, pressed: bool| {
+        let location = KeyLocation::Standard;
+        gui.on_input_event(if pressed {
+            InputEvent::KeyPressed {
+                key,
+                location,
+                text: None,
+                repeat: false,
+            }
+        } else {
+            InputEvent::KeyReleased {
+                key,
+                location,
+                text: None,
+            }
+        });
+    };
+    let with_modifier = |gui: &mut ByorGui<NullRenderer>,
+                         modifier: Option<NamedKey>,
+                         f: &mut dyn FnMut(&mut ByorGui<NullRenderer>)| {
+        if let Some(modifier) = modifier {
+            key_event(gui, Key::Named(modifier), true);
+        }
+        f(gui);
+        if let Some(modifier) = modifier {
+            key_event(gui, Key::Named(modifier), false);
+        }
+    };
+    let center_of = |gui: &ByorGui<NullRenderer>, uid: Uid| {
+        let state = gui.data.previous_state.get(uid).unwrap();
+        state.position + state.size / 2.0
+    };
+    let click = |gui: &mut ByorGui<NullRenderer>,
+                 selection: &mut widgets::SelectionState,
+                 mode: widgets::SelectionMode,
+                 uid: Uid,
+                 modifier: Option<NamedKey>| {
+        gui.move_cursor(center_of(gui, uid));
+        frame(gui, selection, mode);
+        let mut changed = false;
+        with_modifier(gui, modifier, &mut |gui| {
+            gui.press_button(MouseButton::Primary);
+            changed = frame(gui, selection, mode).0;
+            gui.release_button(MouseButton::Primary);
+        });
+        frame(gui, selection, mode);
+        changed
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>,
+                 selection: &mut widgets::SelectionState,
+                 key: Key,
+                 modifier: Option<NamedKey>| {
+        let mut result = (false, false);
+        with_modifier(gui, modifier, &mut |gui| {
+            key_event(gui, key.clone(), true);
+            key_event(gui, key.clone(), false);
+            result = frame(gui, selection, widgets::SelectionMode::Multiple);
+        });
+        result
+    };
+    let row = |index: usize| LIST_UID.concat(Uid::new(index));
+    let selected = |selection: &widgets::SelectionState| selection.selected().collect::<Vec<_>>();
+
+    let multiple = widgets::SelectionMode::Multiple;
+    let mut selection = widgets::SelectionState::new();
+    frame(&mut gui, &mut selection, multiple);
+    frame(&mut gui, &mut selection, multiple);
+
+    assert!(click(&mut gui, &mut selection, multiple, row(1), None));
+    assert_eq!(selected(&selection), [1]);
+    click(
+        &mut gui,
+        &mut selection,
+        multiple,
+        row(3),
+        Some(NamedKey::Control),
+    );
+    assert_eq!(selected(&selection), [1, 3]);
+    click(
+        &mut gui,
+        &mut selection,
+        multiple,
+        row(5),
+        Some(NamedKey::Shift),
+    );
+    assert_eq!(
+        selected(&selection),
+        [3, 4, 5],
+        "the range starts at the toggled row"
+    );
+    assert_eq!((selection.anchor(), selection.active()), (Some(3), Some(5)));
+
+    let up = Key::Named(NamedKey::ArrowUp);
+    let down = Key::Named(NamedKey::ArrowDown);
+    press(&mut gui, &mut selection, up.clone(), Some(NamedKey::Shift));
+    press(&mut gui, &mut selection, up.clone(), Some(NamedKey::Shift));
+    press(&mut gui, &mut selection, up, Some(NamedKey::Shift));
+    assert_eq!(selected(&selection), [2, 3]);
+    press(&mut gui, &mut selection, down.clone(), None);
+    assert_eq!(selected(&selection), [3]);
+
+    let a = Key::Character("a".into());
+    assert_eq!(
+        press(&mut gui, &mut selection, a.clone(), Some(NamedKey::Control)),
+        (true, false),
+        "the focused list consumes Ctrl+A",
+    );
+    assert_eq!(selected(&selection), [0, 1, 2, 3, 4, 5]);
+
+    // once the focus moved on, Ctrl+A and the arrow keys are left alone
+    click(
+        &mut gui,
+        &mut selection,
+        multiple,
+        Uid::from_slice(b"Elsewhere"),
+        None,
+    );
+    assert_eq!(
+        press(&mut gui, &mut selection, a, Some(NamedKey::Control)),
+        (false, true),
+    );
+    assert!(!press(&mut gui, &mut selection, down, None).0);
+    assert_eq!(selection.len(), ITEM_COUNT);
+
+    // a single selection ignores the modifiers
+    let single = widgets::SelectionMode::Single;
+    click(&mut gui, &mut selection, single, row(2), None);
+    click(
+        &mut gui,
+        &mut selection,
+        single,
+        row(4),
+        Some(NamedKey::Control),
+    );
+    assert_eq!(selected(&selection), [4]);
+    assert!(!click(
+        &mut gui,
+        &mut selection,
+        single,
+        row(4),
+        Some(NamedKey::Shift)
+    ));
+}
+
+#[test]
+fn breadcrumbs_report_clicks_and_collapse_the_middle_when_narrow() {
+    use crate::input::*;
+    use crate::*;
+
+    const CRUMBS_UID: Uid = Uid::from_array(b"crumbs");
+    const LABELS: &[&str] = &["Home", "Documents", "Projects", "byor_gui", "src"];
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>, width: f32| {
+        let size = Vec2 {
+            x: width.px(),
+            y: 100.px(),
+        };
+        gui.frame(size, |mut gui| {
+            gui.show(widgets::Breadcrumbs::new(LABELS).with_uid(CRUMBS_UID))
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let center_of_text = |gui: &ByorGui<NullRenderer>, text: &str| {
+        let node = gui.nodes().find(|node| node.text() == Some(text)).unwrap();
+        node.position() + node.size() / 2.0
+    };
+    let click = |gui: &mut ByorGui<NullRenderer>, text: &str, width: f32| {
+        gui.move_cursor(center_of_text(gui, text));
+        frame(gui, width);
+        gui.press_button(MouseButton::Primary);
+        let clicked = frame(gui, width);
+        gui.release_button(MouseButton::Primary);
+        frame(gui, width);
+        clicked
+    };
+    let shown = |gui: &ByorGui<NullRenderer>| {
+        (0..LABELS.len())
+            .filter(|&index| {
+                let uid = CRUMBS_UID.concat(Uid::new(index));
+                gui.data.previous_state.get(uid).is_some()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    frame(&mut gui, 800.0);
+    frame(&mut gui, 800.0);
+    assert_eq!(shown(&gui), [0, 1, 2, 3, 4]);
+    assert_eq!(click(&mut gui, "Projects", 800.0), Some(2));
+
+    frame(&mut gui, 200.0);
+    frame(&mut gui, 200.0);
+    let collapsed = shown(&gui);
+    assert_eq!(collapsed.first(), Some(&0));
+    assert_eq!(collapsed.last(), Some(&4));
+    assert!(
+        !collapsed.contains(&1),
+        "the second segment collapses first"
+    );
+    let width = gui.data.previous_state.get(CRUMBS_UID).unwrap().size.x;
+    assert_eq!(width, 200.px(), "the segments don't widen the breadcrumbs");
+
+    assert_eq!(click(&mut gui, "…", 200.0), None);
+    assert_eq!(click(&mut gui, "Documents", 200.0), Some(1));
+    assert!(
+        gui.nodes().all(|node| node.text() != Some("Documents")),
+        "choosing a collapsed segment closes the menu"
+    );
+}
+
+#[test]
+fn segmented_controls_select_the_clicked_segment() {
+    use crate::input::*;
+    use crate::*;
+
+    const CONTROL_UID: Uid = Uid::from_array(b"control");
+    const LABELS: &[&str] = &["Day", "Week", "Month"];
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>, selected: &mut usize| {
+        gui.frame(
+            Vec2 {
+                x: 400.px(),
+                y: 100.px(),
+            },
+            |mut gui| {
+                gui.show(widgets::SegmentedControl::new(LABELS, selected).with_uid(CONTROL_UID))
+            },
+        )
+        .result
+        .expect("error building GUI")
+    };
+    let click = |gui: &mut ByorGui<NullRenderer>, selected: &mut usize, index: usize| {
+        let state = gui
+            .data
+            .previous_state
+            .get(CONTROL_UID.concat(Uid::new(index)))
+            .unwrap();
+        gui.move_cursor(state.position + state.size / 2.0);
+        frame(gui, selected);
+        gui.press_button(MouseButton::Primary);
+        let changed = frame(gui, selected);
+        gui.release_button(MouseButton::Primary);
+        frame(gui, selected);
+        changed
+    };
+
+    let mut selected = 0;
+    frame(&mut gui, &mut selected);
+    frame(&mut gui, &mut selected);
+
+    let first = gui
+        .data
+        .previous_state
+        .get(CONTROL_UID.concat(Uid::new(0usize)))
+        .unwrap();
+    let second = gui
+        .data
+        .previous_state
+        .get(CONTROL_UID.concat(Uid::new(1usize)))
+        .unwrap();
+    assert_eq!(
+        first.position.x + first.size.x,
+        second.position.x,
+        "the segments are fused"
+    );
+
+    assert!(click(&mut gui, &mut selected, 2));
+    assert_eq!(selected, 2);
+    assert!(!click(&mut gui, &mut selected, 2));
+    assert!(click(&mut gui, &mut selected, 1));
+    assert_eq!(selected, 1);
+}
+
+#[test]
+fn focus_traps_keep_tab_inside_and_restore_the_focus_when_closed() {
+    use crate::input::*;
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 400.px(),
+        y: 300.px(),
+    };
+    let uid = |name: &str| Uid::from_slice(name.as_bytes());
+    let frame = |gui: &mut ByorGui<NullRenderer>, popup_open: bool, nested_open: bool| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            for name in ["a", "b"] {
+                gui.insert_node(
+                    Some(uid(name)),
+                    &Style::DEFAULT,
+                    NodeContents::EMPTY.with_tab_index(0),
+                )?;
+            }
+            if !popup_open {
+                return Ok(());
+            }
+
+            gui.insert_floating_node(
+                uid("popup"),
+                FloatPosition::default(),
+                &Style::DEFAULT,
+                NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                    for name in ["p1", "p2"] {
+                        gui.insert_node(
+                            Some(uid(name)),
+                            &Style::DEFAULT,
+                            NodeContents::EMPTY.with_tab_index(0),
+                        )?;
+                    }
+                    if nested_open {
+                        gui.insert_floating_node(
+                            uid("nested"),
+                            FloatPosition::default(),
+                            &Style::DEFAULT,
+                            NodeContents::builder(|mut gui| {
+                                gui.insert_node(
+                                    Some(uid("n1")),
+                                    &Style::DEFAULT,
+                                    NodeContents::EMPTY.with_tab_index(0),
+                                )
+                                .map(|_| ())
+                            })
+                            .with_focus_trap(),
+                        )?
+                        .result?;
+                    }
+                    Ok(())
+                })
+                .with_focus_trap(),
+            )?
+            .result
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let key = |gui: &mut ByorGui<NullRenderer>, key: NamedKey, pressed: bool| {
+        let key = Key::Named(key);
+        let event = if pressed {
+            InputEvent::KeyPressed {
+                key,
+                location: KeyLocation::Left,
+                text: None,
+                repeat: false,
+            }
+        } else {
+            InputEvent::KeyReleased {
+                key,
+                location: KeyLocation::Left,
+                text: None,
+            }
+        };
+        gui.on_input_event(event);
+    };
+    let tab = |gui: &mut ByorGui<NullRenderer>, popup_open: bool, nested_open: bool| {
+        key(gui, NamedKey::Tab, true);
+        frame(gui, popup_open, nested_open);
+        key(gui, NamedKey::Tab, false);
+        gui.data.focused_node
+    };
+
+    frame(&mut gui, false, false);
+    assert_eq!(tab(&mut gui, false, false), Some(uid("a")));
+
+    frame(&mut gui, true, false);
+    let order: Vec<_> = (0..3).map(|_| tab(&mut gui, true, false)).collect();
+    assert_eq!(
+        order,
+        ["p1", "p2", "p1"].map(|name| Some(uid(name))),
+        "the popup keeps the focus"
+    );
+    key(&mut gui, NamedKey::Shift, true);
+    assert_eq!(tab(&mut gui, true, false), Some(uid("p2")));
+    key(&mut gui, NamedKey::Shift, false);
+
+    frame(&mut gui, true, true);
+    assert_eq!(tab(&mut gui, true, true), Some(uid("n1")));
+    assert_eq!(
+        tab(&mut gui, true, true),
+        Some(uid("n1")),
+        "the nested trap takes over"
+    );
+
+    frame(&mut gui, true, false);
+    assert_eq!(gui.data.focused_node, Some(uid("p2")));
+    assert_eq!(tab(&mut gui, true, false), Some(uid("p1")));
+
+    frame(&mut gui, false, false);
+    assert_eq!(
+        gui.data.focused_node,
+        Some(uid("a")),
+        "the focus returns to the node focused before the popup opened"
+    );
+    assert_eq!(tab(&mut gui, false, false), Some(uid("b")));
+}
+
+#[cfg(feature = "profiling")]
+#[test]
+fn built_in_widgets_and_larger_custom_renderers_fit_inline() {
+    use crate::rendering::*;
+    use crate::*;
+
+    /// About the size of a renderer with a few colors and a transform
+    struct CustomRenderer([f32; 24]);
+
+    impl NodeRenderer for CustomRenderer {
+        type Renderer = NullRenderer;
+
+        fn render(
+            &self,
+            _context: RenderContext<'_, NullRenderer>,
+        ) -> Result<(), std::convert::Infallible> {
+            assert!(self.0.iter().all(|value| value.is_finite()));
+            Ok(())
+        }
+    }
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut text = "Some text".to_string();
+    for _ in 0..2 {
+        let screen_size = Vec2 {
+            x: 400.px(),
+            y: 400.px(),
+        };
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.button("Button")?;
+            gui.icon_button(CustomRenderer([0.0; 24]), "Icon")?;
+            gui.gauge(0.5)?;
+            gui.text_box(&mut text)?;
+            gui.horizontal_scroll_bar(0.5, 0.0, 1.0)?;
+            gui.vertical_scroll_view(|mut gui| gui.label("Scrolled"))?
+                .result?;
+            gui.form(|mut gui| gui.form_row("Label", |mut gui| gui.label("Value")))???;
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+    }
+
+    let stats = gui.allocation_stats();
+    assert!(!gui.data.renderers.is_empty());
+    assert_eq!(stats.renderer_spills, 0);
+    assert_eq!(stats.tag_spills, 0);
+    assert_eq!(
+        stats.persistent_state_spills, 1,
+        "only the editor of the text box is too large"
+    );
+}
+
+#[test]
+fn backspace_and_delete_remove_whole_grapheme_clusters() {
+    use crate::input::*;
+    use crate::widgets::TextBox;
+    use crate::*;
+
+    const FAMILY: &str = "\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+    const E_ACUTE: &str = "e\u{301}";
+    const GAG: &str = "\u{1100}\u{1161}\u{11A8}";
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 300.px(),
+        height: 20.px(),
+    };
+
+    let frame = |gui: &mut ByorGui<NullRenderer>, text: &mut String| {
+        gui.frame(screen_size, |mut gui| {
+            let text_box = TextBox::new(text)
+                .with_uid(Uid::from_array(b"text_box"))
+                .with_style(&style);
+            gui.show(text_box)
+        })
+        .result
+        .expect("error building GUI");
+    };
+    // key presses with text type it, the keys are only released to keep the modifiers clean
+    let press = |gui: &mut ByorGui<NullRenderer>, text: &mut String, key: Key, typed: &str| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: key.clone(),
+            location: KeyLocation::Standard,
+            text: (!typed.is_empty()).then(|| typed.into()),
+            repeat: false,
+        });
+        frame(gui, text);
+        gui.on_input_event(InputEvent::KeyReleased {
+            key,
+            location: KeyLocation::Standard,
+            text: None,
+        });
+        text.clone()
+    };
+    let focused_gui = |text: &mut String| {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        gui.move_cursor(Vec2 {
+            x: 1.px(),
+            y: 10.px(),
+        });
+        gui.press_button(MouseButton::Primary);
+        frame(&mut gui, text);
+        gui.release_button(MouseButton::Primary);
+        frame(&mut gui, text);
+        gui
+    };
+
+    let mut text = String::new();
+    let mut gui = focused_gui(&mut text);
+    let mut typed = Vec::new();
+    for grapheme in ["a", FAMILY, "b", E_ACUTE, GAG] {
+        // combining marks and jamo are typed one code point at a time
+        for c in grapheme.chars() {
+            let c = c.to_string();
+            typed.push(press(
+                &mut gui,
+                &mut text,
+                Key::Character(c.as_str().into()),
+                &c,
+            ));
+        }
+    }
+    assert_eq!(
+        typed.last().map(String::as_str),
+        Some(format!("a{FAMILY}b{E_ACUTE}{GAG}").as_str())
+    );
+
+    let backspace = Key::Named(NamedKey::Backspace);
+    let texts: Vec<_> = (0..6)
+        .map(|_| press(&mut gui, &mut text, backspace.clone(), ""))
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            format!("a{FAMILY}b{E_ACUTE}"),
+            format!("a{FAMILY}b"),
+            format!("a{FAMILY}"),
+            "a".to_string(),
+            String::new(),
+            String::new(),
+        ]
+    );
+
+    let mut text = format!("{GAG}{E_ACUTE}{FAMILY}a");
+    let mut gui = focused_gui(&mut text);
+    press(&mut gui, &mut text, Key::Named(NamedKey::Home), "");
+    let delete = Key::Named(NamedKey::Delete);
+    let texts: Vec<_> = (0..4)
+        .map(|_| press(&mut gui, &mut text, delete.clone(), ""))
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            format!("{E_ACUTE}{FAMILY}a"),
+            format!("{FAMILY}a"),
+            "a".to_string(),
+            String::new(),
+        ]
+    );
+}
+
+#[test]
+fn focus_gained_and_lost_are_reported_for_one_frame() {
+    use crate::input::*;
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<_> {
+            let mut states = Vec::new();
+            for name in ["a", "b"] {
+                let response = gui.insert_node(
+                    Some(Uid::from_slice(name.as_bytes())),
+                    &style,
+                    NodeContents::EMPTY.with_tab_index(0),
+                )?;
+                let input_state = response.input_state;
+                states.push((input_state.focus_gained, input_state.focus_lost));
+            }
+            Ok((states, gui.focus_source()))
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let tab = |gui: &mut ByorGui<NullRenderer>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(NamedKey::Tab),
+            location: KeyLocation::Standard,
+            text: None,
+            repeat: false,
+        });
+        frame(gui);
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: Key::Named(NamedKey::Tab),
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+
+    assert_eq!(frame(&mut gui), (vec![(false, false); 2], None));
+    tab(&mut gui);
+    assert_eq!(
+        frame(&mut gui),
+        (
+            vec![(true, false), (false, false)],
+            Some(FocusSource::Keyboard)
+        )
+    );
+    assert_eq!(
+        frame(&mut gui),
+        (vec![(false, false); 2], Some(FocusSource::Keyboard))
+    );
+
+    gui.move_cursor(Vec2 {
+        x: 110.px(),
+        y: 10.px(),
+    });
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui);
+    gui.release_button(MouseButton::Primary);
+    assert_eq!(
+        frame(&mut gui),
+        (
+            vec![(false, true), (true, false)],
+            Some(FocusSource::Pointer)
+        )
+    );
+    assert_eq!(
+        frame(&mut gui),
+        (vec![(false, false); 2], Some(FocusSource::Pointer))
+    );
+}
+
+#[test]
+fn text_boxes_select_their_text_on_focus_depending_on_the_policy() {
+    use crate::input::*;
+    use crate::widgets::{SelectOnFocus, TextBox};
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 300.px(),
+        height: 20.px(),
+    };
+
+    let mut keyboard = "hello".to_owned();
+    let mut always = "world".to_owned();
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            let text_box = TextBox::new(&mut keyboard)
+                .with_uid(Uid::from_array(b"keyboard"))
+                .with_style(&style)
+                .with_select_on_focus(SelectOnFocus::Keyboard);
+            gui.show(text_box)?;
+            let text_box = TextBox::new(&mut always)
+                .with_uid(Uid::from_array(b"always"))
+                .with_style(&style)
+                .with_select_on_focus(SelectOnFocus::Always);
+            gui.show(text_box)?;
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+        (keyboard.clone(), always.clone())
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: Key, typed: Option<&str>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: key.clone(),
+            location: KeyLocation::Standard,
+            text: typed.map(Into::into),
+            repeat: false,
+        });
+    };
+    let release = |gui: &mut ByorGui<NullRenderer>, key: Key| {
+        gui.on_input_event(InputEvent::KeyReleased {
+            key,
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+    // the text boxes are laid out next to each other
+    let click = |gui: &mut ByorGui<NullRenderer>, x: f32| {
+        gui.move_cursor(Vec2 {
+            x: x.px(),
+            y: 10.px(),
+        });
+        gui.press_button(MouseButton::Primary);
+    };
+
+    frame(&mut gui);
+    press(&mut gui, Key::Named(NamedKey::Tab), None);
+    frame(&mut gui);
+    release(&mut gui, Key::Named(NamedKey::Tab));
+    press(&mut gui, Key::Character("x".into()), Some("x"));
+    assert_eq!(
+        frame(&mut gui),
+        ("x".to_owned(), "world".to_owned()),
+        "reaching the text box with Tab selects the text"
+    );
+    release(&mut gui, Key::Character("x".into()));
+
+    click(&mut gui, 301.0);
+    frame(&mut gui);
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui);
+    press(&mut gui, Key::Character("y".into()), Some("y"));
+    assert_eq!(
+        frame(&mut gui),
+        ("x".to_owned(), "y".to_owned()),
+        "clicking selects the text with the `Always` policy"
+    );
+    release(&mut gui, Key::Character("y".into()));
+
+    click(&mut gui, 1.0);
+    frame(&mut gui);
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui);
+    press(&mut gui, Key::Character("z".into()), Some("z"));
+    assert_eq!(
+        frame(&mut gui),
+        ("xz".to_owned(), "y".to_owned()),
+        "clicking only places the caret with the `Keyboard` policy"
+    );
+}
+
+#[test]
+fn escape_cancels_drags_then_closes_popups_then_clears_the_focus() {
+    use crate::input::*;
+    use crate::widgets::ScrollBar;
+    use crate::*;
+
+    const SCROLL_BAR_UID: Uid = Uid::from_array(b"scroll_bar");
+    const POPUP_UID: Uid = Uid::from_array(b"popup");
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 300.px(),
+        height: 20.px(),
+    };
+
+    let mut value = 0.0;
+    let mut popup_open = false;
+    let mut frame = |gui: &mut ByorGui<NullRenderer>, popup_open: &mut bool| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            let scroll_bar = ScrollBar::horizontal()
+                .with_uid(SCROLL_BAR_UID)
+                .with_style(&style)
+                .with_value(value)
+                .with_thumb_size_ratio(0.25);
+            value = gui.show(scroll_bar)?;
+
+            let popup = widgets::Popup::new(popup_open).with_uid(POPUP_UID);
+            gui.show_container(popup, |mut gui| gui.label("popup"))?
+                .transpose()?;
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+        (value, *popup_open)
+    };
+    let escape = |gui: &mut ByorGui<NullRenderer>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(NamedKey::Escape),
+            location: KeyLocation::Standard,
+            text: None,
+            repeat: false,
+        });
+    };
+    let release_escape = |gui: &mut ByorGui<NullRenderer>| {
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: Key::Named(NamedKey::Escape),
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+
+    let move_cursor = |gui: &mut ByorGui<NullRenderer>, x: f32| {
+        gui.move_cursor(Vec2 {
+            x: x.px(),
+            y: 10.px(),
+        });
+    };
+
+    // start dragging the thumb, then open the popup while the button is still held
+    move_cursor(&mut gui, 30.0);
+    frame(&mut gui, &mut popup_open);
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui, &mut popup_open);
+    move_cursor(&mut gui, 80.0);
+    let (dragged, _) = frame(&mut gui, &mut popup_open);
+    assert!(dragged > 0.0);
+    popup_open = true;
+    frame(&mut gui, &mut popup_open);
+    assert!(gui.data.focused_node.is_some());
+
+    escape(&mut gui);
+    assert_eq!(frame(&mut gui, &mut popup_open), (dragged, true));
+    release_escape(&mut gui);
+    move_cursor(&mut gui, 130.0);
+    assert_eq!(
+        frame(&mut gui, &mut popup_open),
+        (dragged, true),
+        "the first press cancels the drag and leaves the popup open"
+    );
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui, &mut popup_open);
+
+    escape(&mut gui);
+    assert_eq!(
+        frame(&mut gui, &mut popup_open),
+        (dragged, false),
+        "the second press closes the popup"
+    );
+    release_escape(&mut gui);
+    assert!(gui.data.focused_node.is_some());
+
+    escape(&mut gui);
+    frame(&mut gui, &mut popup_open);
+    release_escape(&mut gui);
+    assert_eq!(
+        gui.data.focused_node, None,
+        "the third press clears the focus"
+    );
+}
+
+#[test]
+fn escape_skips_modals_that_are_not_dismissable() {
+    use crate::input::*;
+    use crate::widgets::ConfirmOptions;
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let frame = |gui: &mut ByorGui<NullRenderer>, open: &mut bool| {
+        gui.frame(screen_size, |mut gui| {
+            gui.confirm_dialog(
+                open,
+                ConfirmOptions {
+                    message: "Overwrite?",
+                    dismissable: false,
+                    ..ConfirmOptions::default()
+                },
+            )
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    let mut open = true;
+    frame(&mut gui, &mut open);
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: Key::Named(NamedKey::Escape),
+        location: KeyLocation::Standard,
+        text: None,
+        repeat: false,
+    });
+    assert_eq!(frame(&mut gui, &mut open), None);
+    assert!(open);
+}
+
+#[test]
+fn range_sliders_move_the_thumb_the_pointer_or_keyboard_picks() {
+    use crate::input::*;
+    use crate::widgets::{RangeSlider, RangeSliderResponse, RangeThumb, ThumbCrossing};
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    // 16px thumbs leave 200px to move in, so every value is 2px
+    let style = style! {
+        width: 232.px(),
+    };
+
+    let mut range = (20.0, 80.0);
+    let mut frame = |gui: &mut ByorGui<NullRenderer>, crossing: ThumbCrossing| {
+        gui.frame(screen_size, |mut gui| {
+            let slider = RangeSlider::new(&mut range)
+                .with_max(100.0)
+                .with_step(1.0)
+                .with_crossing(crossing)
+                .with_style(&style);
+            gui.show(slider)
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let response =
+        |range: (f32, f32), changed: Option<RangeThumb>| RangeSliderResponse { range, changed };
+    let move_cursor = |gui: &mut ByorGui<NullRenderer>, x: f32| {
+        gui.move_cursor(Vec2 {
+            x: x.px(),
+            y: 8.px(),
+        });
+    };
+    let blocked = ThumbCrossing::Blocked;
+
+    move_cursor(&mut gui, 10.0);
+    frame(&mut gui, blocked);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(
+        frame(&mut gui, blocked),
+        response((1.0, 80.0), Some(RangeThumb::Start)),
+        "pressing the track centers the nearer thumb under the cursor"
+    );
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui, blocked);
+
+    // the end thumb is centered at 2 * 80 + 24
+    move_cursor(&mut gui, 184.0);
+    frame(&mut gui, blocked);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, blocked), response((1.0, 80.0), None));
+    move_cursor(&mut gui, 0.0);
+    assert_eq!(
+        frame(&mut gui, blocked),
+        response((1.0, 1.0), Some(RangeThumb::End)),
+        "the thumbs can't cross"
+    );
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui, blocked);
+
+    // pressing the start thumb of stacked thumbs and dragging along moves the end thumb
+    move_cursor(&mut gui, 10.0);
+    frame(&mut gui, blocked);
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui, blocked);
+    move_cursor(&mut gui, 30.0);
+    assert_eq!(
+        frame(&mut gui, blocked),
+        response((1.0, 11.0), Some(RangeThumb::End))
+    );
+    gui.release_button(MouseButton::Primary);
+    frame(&mut gui, blocked);
+
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: Key::Named(NamedKey::ArrowRight),
+        location: KeyLocation::Standard,
+        text: None,
+        repeat: false,
+    });
+    assert_eq!(
+        frame(&mut gui, blocked),
+        response((2.0, 11.0), Some(RangeThumb::Start)),
+        "the arrow keys move the focused thumb by a step"
+    );
+    gui.on_input_event(InputEvent::KeyReleased {
+        key: Key::Named(NamedKey::ArrowRight),
+        location: KeyLocation::Standard,
+        text: None,
+    });
+
+    let swap = ThumbCrossing::Swap;
+    move_cursor(&mut gui, 12.0);
+    frame(&mut gui, swap);
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui, swap);
+    move_cursor(&mut gui, 108.0);
+    assert_eq!(
+        frame(&mut gui, swap),
+        response((11.0, 50.0), Some(RangeThumb::End)),
+        "a thumb dragged past the other one continues as the other end"
+    );
+}
+
+#[test]
+fn dates_are_counted_across_months_years_and_weekdays() {
+    use crate::widgets::{Date, Weekday};
+
+    let date = Date::new(2026, 10, 14);
+    assert_eq!(date.weekday(), Weekday::Wednesday);
+    assert_eq!(date.to_string(), "2026-10-14");
+    assert_eq!(Date::from_iso("2026-10-14"), Some(date));
+    assert_eq!(Date::from_iso("2023-02-29"), None);
+    assert_eq!(Date::from_iso("2026-1-14"), None);
+
+    assert_eq!(Date::new(1969, 12, 31).add_days(1), Date::new(1970, 1, 1));
+    assert_eq!(Date::new(2000, 2, 28).add_days(1), Date::new(2000, 2, 29));
+    assert_eq!(Date::new(1900, 2, 28).add_days(1), Date::new(1900, 3, 1));
+    assert_eq!(Date::new(2026, 1, 1).add_days(-365), Date::new(2025, 1, 1));
+    assert_eq!(Date::new(2024, 1, 31).add_months(1), Date::new(2024, 2, 29));
+    assert_eq!(
+        Date::new(2026, 1, 15).add_months(-13),
+        Date::new(2024, 12, 15)
+    );
+    assert_eq!(Weekday::Sunday.add_days(1), Weekday::Monday);
+}
+
+#[test]
+fn date_pickers_are_navigated_and_picked_with_the_keyboard() {
+    use crate::input::*;
+    use crate::widgets::{Date, DatePicker};
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut date = Date::new(2026, 10, 14);
+    let frame = |gui: &mut ByorGui<NullRenderer>, date: &mut Date| {
+        gui.frame(screen_size, |mut gui| {
+            let picker = DatePicker::new(date)
+                .with_uid(Uid::new("picker"))
+                .with_max(Date::new(2026, 11, 20))
+                .with_today(Date::new(2026, 10, 1));
+            gui.show(picker)
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(key),
+            location: KeyLocation::Standard,
+            text: None,
+            repeat: false,
+        });
+        gui.on_input_event(InputEvent::KeyReleased {
+            key: Key::Named(key),
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+
+    frame(&mut gui, &mut date);
+    // the text field comes first, then the button opening the calendar
+    press(&mut gui, NamedKey::Tab);
+    frame(&mut gui, &mut date);
+    press(&mut gui, NamedKey::Tab);
+    frame(&mut gui, &mut date);
+    press(&mut gui, NamedKey::Enter);
+    assert!(!frame(&mut gui, &mut date));
+
+    press(&mut gui, NamedKey::ArrowRight);
+    press(&mut gui, NamedKey::ArrowDown);
+    assert!(
+        !frame(&mut gui, &mut date),
+        "moving through the calendar keeps the date"
+    );
+    press(&mut gui, NamedKey::PageDown);
+    frame(&mut gui, &mut date);
+    press(&mut gui, NamedKey::ArrowDown);
+    frame(&mut gui, &mut date);
+    press(&mut gui, NamedKey::Enter);
+    assert!(frame(&mut gui, &mut date));
+    assert_eq!(
+        date,
+        Date::new(2026, 11, 20),
+        "a day past the maximum can't be reached"
+    );
+
+    press(&mut gui, NamedKey::ArrowRight);
+    assert!(
+        !frame(&mut gui, &mut date),
+        "the calendar closes once a day is picked"
+    );
+}
+
+#[test]
+fn percent_padding_and_child_spacing_resolve_against_the_node_size() {
+    use crate::style::*;
+    use crate::*;
+
+    const PANEL_UID: Uid = Uid::from_array(b"panel");
+    const FIT_UID: Uid = Uid::from_array(b"fit");
+
+    let percent_style = style! {
+        layout_direction: Direction::LeftToRight,
+        padding: 10.percent(),
+        child_spacing: 5.percent(),
+    };
+    let panel_style = percent_style
+        .clone()
+        .with_width(200.px())
+        .with_height(100.px());
+    let item_style = style! {
+        width: 20.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+        |mut gui| -> widgets::WidgetResult<()> {
+            for (uid, style) in [(PANEL_UID, &panel_style), (FIT_UID, &percent_style)] {
+                gui.insert_node(
+                    Some(uid),
+                    style,
+                    NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                        gui.insert_node(None, &item_style, NodeContents::EMPTY)?;
+                        gui.insert_node(None, &item_style, NodeContents::EMPTY)?;
+                        Ok(())
+                    }),
+                )?
+                .result?;
+            }
+            Ok(())
+        },
+    )
+    .result
+    .expect("error building GUI");
+
+    let nodes: Vec<_> = gui.nodes().collect();
+    let uids: Vec<_> = nodes.iter().map(|node| node.uid()).collect();
+    assert_eq!(
+        uids,
+        [None, Some(PANEL_UID), None, None, Some(FIT_UID), None, None,]
+    );
+
+    // 10% of the width horizontally, 10% of the height vertically, 5% of the width between
+    let panel = nodes[1].position();
+    assert_eq!(
+        nodes[2].position() - panel,
+        Vec2 {
+            x: 20.px(),
+            y: 10.px(),
+        }
+    );
+    assert_eq!(nodes[3].position().x - nodes[2].position().x, 30.px());
+
+    // percentages count as zero while fitting the contents, then resolve against the result
+    assert_eq!(
+        nodes[4].size(),
+        Vec2 {
+            x: 40.px(),
+            y: 20.px(),
+        }
+    );
+    assert_eq!(
+        nodes[5].position() - nodes[4].position(),
+        Vec2 {
+            x: 4.px(),
+            y: 2.px()
+        }
+    );
+    assert_eq!(nodes[6].position().x - nodes[5].position().x, 22.px());
+}
+
+#[test]
+fn labels_show_their_full_text_while_hovered_only_if_it_is_cut_off() {
+    use crate::*;
+
+    const TEXT: &str = "A label that is much too long";
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.theme_mut().set_overflow_tooltips(true);
+    let frame = |gui: &mut ByorGui<NullRenderer>, width: f32| {
+        let style = style! {
+            width: width.px(),
+            text_wrap: false,
+        };
+        gui.frame(
+            Vec2 {
+                x: 800.px(),
+                y: 600.px(),
+            },
+            |mut gui| gui.show(widgets::Label::default().with_text(TEXT).with_style(&style)),
+        )
+        .result
+        .expect("error building GUI");
+        gui.nodes().filter(|node| node.text() == Some(TEXT)).count()
+    };
+
+    assert_eq!(frame(&mut gui, 40.0), 1);
+    let label = gui.nodes().find(|node| node.text() == Some(TEXT)).unwrap();
+    gui.move_cursor(label.position() + label.size() / 2.0);
+    assert_eq!(
+        frame(&mut gui, 40.0),
+        2,
+        "the tooltip shows the cut off text"
+    );
+    assert_eq!(frame(&mut gui, 40.0), 2);
+
+    // the tooltip was shown according to the previous layout
+    frame(&mut gui, 400.0);
+    assert!(
+        gui.needs_animation(),
+        "the tooltip needs a frame to catch up"
+    );
+    assert_eq!(frame(&mut gui, 400.0), 1, "the text fits after the resize");
+    assert!(!gui.needs_animation());
+    assert_eq!(frame(&mut gui, 400.0), 1);
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    assert_eq!(frame(&mut gui, 40.0), 1);
+    gui.move_cursor(Vec2 {
+        x: 20.px(),
+        y: 5.px(),
+    });
+    frame(&mut gui, 40.0);
+    assert_eq!(
+        frame(&mut gui, 40.0),
+        1,
+        "overflow tooltips are off by default"
+    );
+}
+
+#[test]
+fn cached_text_layouts_are_only_rebuilt_for_a_new_revision() {
+    use crate::*;
+
+    const TEXT_UID: Uid = Uid::from_array(b"text");
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>, revision: u64, text: &str| {
+        gui.frame(
+            Vec2 {
+                x: 800.px(),
+                y: 600.px(),
+            },
+            |mut gui| -> widgets::WidgetResult<()> {
+                gui.insert_node(
+                    Some(TEXT_UID),
+                    &Style::default(),
+                    NodeContents::text_cached(TEXT_UID, revision, text),
+                )?;
+                Ok(())
+            },
+        )
+        .result
+        .expect("error building GUI");
+        gui.nodes()
+            .find(|node| node.uid() == Some(TEXT_UID))
+            .unwrap()
+            .size()
+    };
+
+    let short_size = frame(&mut gui, 0, "short");
+    assert_eq!(frame(&mut gui, 0, "short"), short_size);
+    assert_eq!(gui.data.text_layout_cache.len(), 1);
+
+    // the app didn't bump the revision, so the layout is still the one of the old text
+    assert_eq!(frame(&mut gui, 0, "a much longer text"), short_size);
+    let long_size = frame(&mut gui, 1, "a much longer text");
+    assert!(long_size.x > short_size.x);
+    assert_eq!(frame(&mut gui, 1, "a much longer text"), long_size);
+
+    gui.frame(
+        Vec2 {
+            x: 800.px(),
+            y: 600.px(),
+        },
+        |_| {},
+    );
+    assert!(
+        gui.data.text_layout_cache.is_empty(),
+        "layouts that aren't shown anymore are dropped"
+    );
+}
+
+#[test]
+fn scroll_views_are_scrolled_with_the_keyboard() {
+    use crate::input::*;
+    use crate::*;
+    use widgets::KeyboardScroll;
+
+    const OUTER_UID: Uid = Uid::from_array(b"outer");
+    const INNER_UID: Uid = Uid::from_array(b"inner");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let outer_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+    let inner_style = style! {
+        width: 100.px(),
+        height: 60.px(),
+        layout_direction: Direction::TopToBottom,
+    };
+    let item_style = style! {
+        width: 50.px(),
+        height: 30.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>, keyboard_scroll: KeyboardScroll| {
+        let outer = widgets::ScrollView::vertical()
+            .with_uid(OUTER_UID)
+            .with_style(&outer_style)
+            .with_item_height(30.px())
+            .with_keyboard_scroll(keyboard_scroll);
+        let inner = widgets::ScrollView::vertical()
+            .with_uid(INNER_UID)
+            .with_style(&inner_style)
+            .with_item_height(30.px());
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(outer, |mut gui| -> widgets::WidgetResult<()> {
+                gui.show_container(inner, |mut gui| -> widgets::WidgetResult<()> {
+                    for i in 0..4 {
+                        gui.uid_scope(Uid::new(i), |gui| {
+                            gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                        })?;
+                    }
+                    Ok(())
+                })?
+                .result?;
+                for i in 0..10 {
+                    gui.uid_scope(Uid::new(i), |gui| {
+                        gui.insert_node(None, &item_style, NodeContents::EMPTY)
+                    })?;
+                }
+                Ok(())
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        [OUTER_UID, INNER_UID].map(|uid| {
+            gui.data
+                .persistent_state
+                .get(uid)
+                .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+                .copied()
+                .unwrap_or_default()
+                .value()
+        })
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: NamedKey| {
+        let key = Key::Named(key);
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: key.clone(),
+            location: KeyLocation::Standard,
+            text: None,
+            repeat: false,
+        });
+        gui.on_input_event(InputEvent::KeyReleased {
+            key,
+            location: KeyLocation::Standard,
+            text: None,
+        });
+    };
+
+    // hover the outer view next to the inner one
+    gui.move_cursor(Vec2 {
+        x: 150.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui, KeyboardScroll::FocusWithinOrHovered);
+    frame(&mut gui, KeyboardScroll::FocusWithinOrHovered);
+
+    press(&mut gui, NamedKey::ArrowDown);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithinOrHovered),
+        [30.0, 0.0]
+    );
+    press(&mut gui, NamedKey::PageDown);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithinOrHovered),
+        [130.0, 0.0]
+    );
+    press(&mut gui, NamedKey::End);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithinOrHovered),
+        [260.0, 0.0]
+    );
+    press(&mut gui, NamedKey::ArrowDown);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithinOrHovered),
+        [260.0, 0.0],
+        "the offset is clamped to the end"
+    );
+    press(&mut gui, NamedKey::Home);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithinOrHovered),
+        [0.0, 0.0]
+    );
+
+    press(&mut gui, NamedKey::ArrowDown);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithin),
+        [0.0, 0.0],
+        "hovering is not enough"
+    );
+    press(&mut gui, NamedKey::ArrowDown);
+    assert_eq!(frame(&mut gui, KeyboardScroll::Disabled), [0.0, 0.0]);
+
+    // the innermost hovered view takes the keys
+    gui.move_cursor(Vec2 {
+        x: 10.px(),
+        y: 10.px(),
+    });
+    frame(&mut gui, KeyboardScroll::FocusWithinOrHovered);
+    press(&mut gui, NamedKey::ArrowDown);
+    assert_eq!(
+        frame(&mut gui, KeyboardScroll::FocusWithinOrHovered),
+        [0.0, 30.0]
+    );
+}
+
+#[test]
+fn override_theme_styles_win_over_instance_styles() {
+    use crate::style::*;
+    use crate::theme::*;
+    use crate::*;
+
+    const CUSTOM_CLASS: StyleClass = StyleClass::new_static("custom");
+    const RED: Color = Color::rgb(255, 0, 0);
+    const GREEN: Color = Color::rgb(0, 255, 0);
+    const BLUE: Color = Color::rgb(0, 0, 255);
+
+    let mut theme = Theme::default();
+    theme.insert_style(
+        Theme::UNIVERSAL_CLASS,
+        &style! {
+            text_color: RED,
+            border_color: RED,
+            border_width: 1.px(),
+        },
+    );
+    theme.insert_style(
+        CUSTOM_CLASS,
+        &style! {
+            border_color: GREEN,
+            background: %important(Color::BLACK),
+        },
+    );
+    theme.insert_style_with_priority(
+        Theme::UNIVERSAL_CLASS,
+        StylePriority::Override,
+        &style! {
+            text_color: Color::WHITE,
+            background: Color::WHITE,
+        },
+    );
+    let mut overlay = Theme::default();
+    overlay.insert_style_with_priority(
+        CUSTOM_CLASS,
+        StylePriority::Override,
+        &style! {
+            border_width: 2.px(),
+        },
+    );
+    theme.merge(&overlay);
+
+    let instance_style = style! {
+        text_color: BLUE,
+        border_color: BLUE,
+        border_width: 3.px(),
+        background: BLUE,
+        corner_radius: 4.px(),
+    };
+    let style = theme.build_style(
+        Some(&instance_style),
+        &[CUSTOM_CLASS],
+        widgets::Button::TYPE_CLASS,
+    );
+    assert!(matches!(style.text_color, Property::Value(Color::WHITE)));
+    assert!(matches!(style.border_color, Property::Value(BLUE)));
+    assert!(matches!(
+        style.border_width,
+        Property::Value(width) if width == 2.px().into()
+    ));
+    assert!(
+        matches!(
+            style.background,
+            Property::Value(Brush::Solid(Color::BLACK))
+        ),
+        "custom classes come before the universal class in the override layer too"
+    );
+    assert!(matches!(
+        style.corner_radius,
+        Property::Value(radius) if radius == 4.px().into()
+    ));
+    assert!(matches!(
+        theme.build_style_property(
+            |style| &style.text_color,
+            Some(&instance_style),
+            &[],
+            widgets::Label::TYPE_CLASS
+        ),
+        Property::Value(Color::WHITE)
+    ));
+
+    let style = theme.build_style(None, &[CUSTOM_CLASS], widgets::Button::TYPE_CLASS);
+    assert!(matches!(style.border_color, Property::Value(GREEN)));
+    let style = theme.build_style(None, &[], widgets::Button::TYPE_CLASS);
+    assert!(matches!(
+        style.background,
+        Property::Value(Brush::Solid(Color::WHITE))
+    ));
+
+    // outside of theme styles an important value is a normal one
+    const UID: Uid = Uid::from_array(b"node");
+    let mut gui = ByorGui::<NullRenderer>::default();
     gui.frame(
         Vec2 {
             x: 800.px(),
             y: 600.px(),
         },
-        1.0,
-        MouseState::default(),
         |mut gui| {
-            for _ in 0..100 {
-                gui.insert_node(None, &Style::DEFAULT)?;
+            gui.insert_node(
+                Some(UID),
+                &style! {
+                    text_color: %important(BLUE),
+                },
+                NodeContents::EMPTY.with_style_snapshot(),
+            )
+        },
+    )
+    .result
+    .expect("error building GUI");
+    assert_eq!(gui.computed_style(UID).unwrap().text_color(), BLUE);
+}
+
+#[test]
+fn reorderable_list_items_are_dropped_where_the_gap_is() {
+    use crate::input::*;
+    use crate::*;
+
+    const LIST_UID: Uid = Uid::from_array(b"list");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let list_style = style! {
+        width: 200.px(),
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        let list = widgets::ReorderableList::new(5)
+            .with_uid(LIST_UID)
+            .with_style(&list_style);
+        gui.frame(screen_size, |mut gui| {
+            gui.reorderable_list(list, |gui, _| {
+                gui.insert_node(None, &item_style, NodeContents::EMPTY)?;
+                Ok(())
+            })
+        })
+        .result
+        .expect("error building GUI")
+    };
+    let item_y = |gui: &ByorGui<NullRenderer>, index: usize| {
+        gui.data
+            .previous_state
+            .get(LIST_UID.concat(Uid::new(index)))
+            .map(|state| state.position.y.value())
+    };
+    let at = |y: f32| Vec2 {
+        x: 50.px(),
+        y: y.px(),
+    };
+
+    gui.move_cursor(at(10.0));
+    frame(&mut gui);
+    frame(&mut gui);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui), None);
+    gui.move_cursor(at(11.0));
+    frame(&mut gui);
+    assert_eq!(
+        item_y(&gui, 1),
+        Some(20.0),
+        "small moves don't drag the item"
+    );
+
+    // the dragged item is centered at 55, past the middle of the third of the other items
+    gui.move_cursor(at(55.0));
+    assert_eq!(frame(&mut gui), None);
+    assert_eq!(item_y(&gui, 1), Some(0.0));
+    assert_eq!(item_y(&gui, 3), Some(40.0));
+    assert_eq!(
+        item_y(&gui, 4),
+        Some(80.0),
+        "the gap takes the place of the item"
+    );
+    assert_eq!(item_y(&gui, 0), Some(45.0), "the item follows the cursor");
+
+    gui.release_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui), Some((0, 3)));
+    assert_eq!(frame(&mut gui), None);
+
+    // Escape cancels the drag
+    gui.move_cursor(at(10.0));
+    frame(&mut gui);
+    gui.press_button(MouseButton::Primary);
+    frame(&mut gui);
+    gui.move_cursor(at(90.0));
+    frame(&mut gui);
+    assert_eq!(item_y(&gui, 4), Some(60.0));
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: Key::Named(NamedKey::Escape),
+        location: KeyLocation::Standard,
+        text: None,
+        repeat: false,
+    });
+    frame(&mut gui);
+    // the drag is cancelled at the end of the frame Escape is pressed during
+    frame(&mut gui);
+    assert_eq!(item_y(&gui, 0), Some(0.0));
+    gui.release_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui), None);
+}
+
+#[test]
+fn dragging_near_the_end_of_a_scroll_view_scrolls_it() {
+    use crate::input::*;
+    use crate::*;
+    use std::time::{Duration, Instant};
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style);
+        gui.frame(screen_size, |mut gui| {
+            gui.show_container(scroll_view, |mut gui| {
+                gui.reorderable_list(widgets::ReorderableList::new(20), |gui, _| {
+                    gui.insert_node(None, &item_style, NodeContents::EMPTY)?;
+                    Ok(())
+                })
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+
+        gui.data
+            .persistent_state
+            .get(SCROLL_UID)
+            .and_then(|state| state.get::<Float<Pixel>>(PersistentStateKey::VerticalScroll))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    let start = Instant::now();
+    let mut time = start;
+    let mut next_frame = |gui: &mut ByorGui<NullRenderer>| {
+        time += Duration::from_millis(16);
+        gui.set_time(time);
+        frame(gui)
+    };
+
+    gui.move_cursor(Vec2 {
+        x: 50.px(),
+        y: 10.px(),
+    });
+    next_frame(&mut gui);
+    next_frame(&mut gui);
+    gui.press_button(MouseButton::Primary);
+    next_frame(&mut gui);
+    gui.move_cursor(Vec2 {
+        x: 50.px(),
+        y: 50.px(),
+    });
+    assert_eq!(
+        next_frame(&mut gui),
+        0.px(),
+        "the middle of the view doesn't scroll"
+    );
+
+    gui.move_cursor(Vec2 {
+        x: 50.px(),
+        y: 98.px(),
+    });
+    let first = next_frame(&mut gui);
+    assert!(first > 0.px());
+    assert!(
+        gui.next_animation_frame().is_some(),
+        "the view keeps scrolling"
+    );
+    let second = next_frame(&mut gui);
+    assert!(second > first, "the cursor doesn't have to move");
+
+    gui.release_button(MouseButton::Primary);
+    let dropped = next_frame(&mut gui);
+    assert_eq!(next_frame(&mut gui), dropped);
+}
+
+#[test]
+fn stable_scopes_keep_their_uids_when_the_builder_is_restructured() {
+    use crate::*;
+
+    const ITEM_UID: Uid = Uid::from_array(b"item");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let item_style = style! {
+        width: 50.px(),
+        height: 50.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_error_policy(ErrorPolicy::CollectAndContinue);
+    let mut item_uid =
+        |build: &dyn Fn(&mut ByorGuiContext<'_, NullRenderer>) -> widgets::WidgetResult<()>| {
+            gui.frame(screen_size, |mut gui| build(&mut gui))
+                .result
+                .expect("error building GUI");
+            let uid = gui.nodes().filter_map(|node| node.uid()).last().unwrap();
+            (uid, gui.take_diagnostics())
+        };
+    let item = |gui: &mut ByorGuiContext<'_, NullRenderer>| {
+        gui.insert_node(Some(ITEM_UID), &item_style, NodeContents::EMPTY)?;
+        Ok(())
+    };
+
+    let (flat, diagnostics) = item_uid(&|gui| gui.stable_scope("editor", item));
+    assert!(diagnostics.is_empty());
+    let (wrapped, _) = item_uid(&|gui| {
+        gui.uid_scope(uid!("tab"), |gui| {
+            gui.insert_node(
+                Some(uid!("panel")),
+                &Style::default(),
+                NodeContents::builder(|mut gui| gui.stable_scope("editor", item)),
+            )?;
+            Ok(())
+        })
+    });
+    assert_eq!(flat, wrapped);
+    let (unstable, _) = item_uid(&|gui| gui.uid_scope(Uid::from_slice(b"editor"), item));
+    assert_ne!(flat, unstable);
+
+    let (nested, _) = item_uid(&|gui| {
+        gui.stable_scope("window", |gui| {
+            gui.uid_scope(uid!("panel"), |gui| gui.stable_scope("editor", item))
+        })
+    });
+    let (nested_flat, _) =
+        item_uid(&|gui| gui.stable_scope("window", |gui| gui.stable_scope("editor", item)));
+    assert_eq!(nested, nested_flat);
+    assert_ne!(nested, flat, "nested scopes include the outer name");
+
+    let (_, diagnostics) = item_uid(&|gui| {
+        gui.stable_scope("editor", |_| ());
+        gui.stable_scope("editor", item)
+    });
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.message == "duplicate stable scope `editor`")
+    );
+}
+
+#[test]
+fn expected_nodes_report_their_layout_and_dump_the_tree_when_missing() {
+    use crate::style::*;
+    use crate::*;
+
+    const SCROLL_UID: Uid = Uid::from_array(b"scroll");
+    const LABEL_UID: Uid = Uid::from_array(b"label");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let scroll_view_style = style! {
+        width: 200.px(),
+        height: 100.px(),
+        layout_direction: Direction::TopToBottom,
+        child_spacing: 0.px(),
+        padding: 0.px(),
+    };
+    let item_style = style! {
+        width: 100.px(),
+        height: 40.px(),
+    };
+    let label_style = style! {
+        width: 60.px(),
+        font_size: 10.pt(),
+    };
+
+    let scroll_classes = [theme::StyleClass::new_static("sidebar")];
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.layout_only_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+        let scroll_view = widgets::ScrollView::vertical()
+            .with_uid(SCROLL_UID)
+            .with_style(&scroll_view_style)
+            .with_classes(&scroll_classes);
+        gui.show_container(scroll_view, |mut gui| -> widgets::WidgetResult<()> {
+            for i in 0..5 {
+                gui.insert_node(Some(Uid::new(i)), &item_style, NodeContents::EMPTY)?;
             }
+            Ok(())
+        })?
+        .result?;
+        gui.show(
+            widgets::Label::default()
+                .with_uid(LABEL_UID)
+                .with_text("one two three")
+                .with_style(&label_style),
+        )
+    })
+    .expect("error building GUI");
 
-            Result::<(), DuplicateUidError>::Ok(())
+    let second = gui.expect_node(Uid::new(1));
+    assert_eq!(
+        second.size(),
+        Vec2 {
+            x: 100.px(),
+            y: 40.px(),
         },
-    )
+    );
+    assert_eq!(
+        second.position().y - gui.expect_node(SCROLL_UID).position().y,
+        40.px(),
+    );
+    assert!(second.visible_in(SCROLL_UID));
+    assert!(!gui.expect_node(Uid::new(3)).visible_in(SCROLL_UID));
+
+    let lines = gui.expect_node(LABEL_UID).text_lines();
+    assert!(lines.len() > 1, "the label wraps: {lines:?}");
+    assert_eq!(lines.join(" "), "one two three");
+
+    let missing = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = gui.expect_node(Uid::new(5));
+    }))
+    .unwrap_err();
+    let message = missing.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("expected a node"), "{message}");
+    // classes are only recorded in debug builds
+    assert!(
+        !cfg!(debug_assertions) || message.contains("[###vertical_scroll_view, sidebar]"),
+        "{message}",
+    );
+    assert!(message.contains("\"one two three\""), "{message}");
+}
+
+#[test]
+fn adjacent_selection_rects_of_equal_width_are_merged() {
+    use crate::*;
+
+    let line = |x0: f64, x1: f64, line: f64| {
+        parley::BoundingBox::new(x0, line * 10.0, x1, (line + 1.0) * 10.0)
+    };
+    let offset = Vec2 {
+        x: 5.px(),
+        y: 100.px(),
+    };
+
+    // a selection starting within the first line, covering two full lines of equal width and
+    // ending within the last line
+    let rects = rendering::selection_rects(
+        [
+            line(30.0, 80.0, 0.0),
+            line(0.0, 80.0, 1.0),
+            line(0.0, 80.0, 2.0),
+            line(0.0, 20.0, 3.0),
+        ],
+        offset,
+    );
+    let rect = |x: f32, y: f32, width: f32, height: f32| {
+        (
+            Vec2 {
+                x: x.px(),
+                y: y.px(),
+            },
+            Vec2 {
+                x: width.px(),
+                y: height.px(),
+            },
+        )
+    };
+    assert_eq!(
+        rects,
+        [
+            rect(35.0, 100.0, 50.0, 10.0),
+            rect(5.0, 110.0, 80.0, 20.0),
+            rect(5.0, 130.0, 20.0, 10.0),
+        ],
+    );
+
+    // rects of the same width that aren't touching stay apart
+    let rects = rendering::selection_rects([line(0.0, 80.0, 0.0), line(0.0, 80.0, 2.0)], offset);
+    assert_eq!(rects.len(), 2);
+}
+
+#[test]
+fn widgets_format_values_with_the_installed_formatter() {
+    use crate::format::ValueFormatter;
+    use crate::input::*;
+    use crate::widgets::{Date, DatePicker, Gauge, Weekday};
+    use crate::*;
+
+    struct GermanFormatter;
+
+    impl ValueFormatter for GermanFormatter {
+        fn format_number(&self, value: f64, precision: usize) -> String {
+            format!("{value:.precision$}").replace('.', ",")
+        }
+
+        fn format_date(&self, date: Date) -> String {
+            format!("{:02}.{:02}.{:04}", date.day, date.month, date.year)
+        }
+
+        fn parse_date(&self, text: &str) -> Option<Date> {
+            let mut parts = text.split('.');
+            let (day, month, year) = (parts.next()?, parts.next()?, parts.next()?);
+            Date::from_iso(&format!("{year}-{month}-{day}"))
+        }
+
+        fn format_weekday(&self, weekday: Weekday) -> String {
+            ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"][weekday as usize].to_owned()
+        }
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_formatter(GermanFormatter);
+
+    let mut date = Date::new(2026, 10, 14);
+    let mut frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.show(DatePicker::new(&mut date).with_uid(Uid::new("picker")))?;
+            gui.show(Gauge::new(12.5).with_max(50.0).with_value_text(1))?;
+            gui.show(Gauge::new(12.5).with_max(50.0).with_percent_text(0))?;
+            Ok(())
+        })
+        .result
+        .expect("error building GUI");
+        date
+    };
+    let texts = |gui: &ByorGui<NullRenderer>| -> Vec<String> {
+        gui.nodes()
+            .filter_map(|node| node.text())
+            .map(str::to_owned)
+            .collect()
+    };
+    let field_text = |gui: &ByorGui<NullRenderer>| -> String {
+        gui.data
+            .persistent_state
+            .get(Uid::new("picker"))
+            .unwrap()
+            .get::<String>(PersistentStateKey::DatePickerText)
+            .unwrap()
+            .clone()
+    };
+    let press = |gui: &mut ByorGui<NullRenderer>, key: Key, text: Option<&str>| {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key,
+            location: KeyLocation::Standard,
+            text: text.map(Into::into),
+            repeat: false,
+        });
+    };
+
+    frame(&mut gui);
+    let shown = texts(&gui);
+    assert_eq!(field_text(&gui), "14.10.2026");
+    for expected in ["12,5", "25%"] {
+        assert!(shown.iter().any(|text| text == expected), "{shown:?}");
+    }
+
+    // typed dates are parsed by the formatter, and letters can't be typed
+    press(&mut gui, Key::Named(NamedKey::Tab), None);
+    frame(&mut gui);
+    press(&mut gui, Key::Named(NamedKey::Control), None);
+    press(&mut gui, Key::Character("a".into()), None);
+    gui.on_input_event(InputEvent::KeyReleased {
+        key: Key::Named(NamedKey::Control),
+        location: KeyLocation::Standard,
+        text: None,
+    });
+    for c in "01.x02.2025".chars() {
+        let c = c.to_string();
+        press(&mut gui, Key::Character(c.as_str().into()), Some(&c));
+    }
+    press(&mut gui, Key::Named(NamedKey::Enter), Some("\r"));
+    assert_eq!(frame(&mut gui), Date::new(2025, 2, 1));
+    assert_eq!(field_text(&gui), "01.02.2025");
+
+    // the calendar names the month with the default and the weekdays with the custom names
+    press(&mut gui, Key::Named(NamedKey::Tab), None);
+    frame(&mut gui);
+    press(&mut gui, Key::Named(NamedKey::Enter), None);
+    frame(&mut gui);
+    let shown = texts(&gui);
+    for expected in ["February 2025", "Di", "So"] {
+        assert!(shown.iter().any(|text| text == expected), "{shown:?}");
+    }
+}
+
+#[test]
+fn hover_sticks_to_the_hovered_node_while_the_cursor_jitters_across_its_edge() {
+    use crate::*;
+
+    const LEFT_UID: Uid = Uid::from_array(b"left");
+    const RIGHT_UID: Uid = Uid::from_array(b"right");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let container_style = style! {
+        child_spacing: 0.px(),
+    };
+    let target_style = style! {
+        size: 100.px(),
+    };
+
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.insert_node(
+                None,
+                &container_style,
+                NodeContents::builder(|mut gui| -> widgets::WidgetResult<()> {
+                    for uid in [LEFT_UID, RIGHT_UID] {
+                        gui.insert_node(
+                            Some(uid),
+                            &target_style,
+                            NodeContents::EMPTY.with_hit_target(HitTarget::SelfOnly),
+                        )?;
+                    }
+                    Ok(())
+                }),
+            )?
+            .result
+        })
+        .result
+        .expect("error building GUI")
+    };
+    // moves the cursor to `x` and returns which of the targets is hovered afterwards
+    let hovered_at = |gui: &mut ByorGui<NullRenderer>, x: f32| {
+        gui.move_cursor(Vec2 {
+            x: x.px(),
+            y: 50.px(),
+        });
+        frame(gui);
+        let hovered = |uid| {
+            gui.data.previous_state.get(uid).unwrap().hover_state == HoverState::DirectlyHovered
+        };
+        match (hovered(LEFT_UID), hovered(RIGHT_UID)) {
+            (true, false) => LEFT_UID,
+            (false, true) => RIGHT_UID,
+            states => panic!("unexpected hover states {states:?} at {x}"),
+        }
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    assert_eq!(gui.hover_hysteresis(), DEFAULT_HOVER_HYSTERESIS);
+    frame(&mut gui);
+    assert_eq!(hovered_at(&mut gui, 99.0), LEFT_UID);
+
+    // the targets share the edge at 100
+    for x in [99.6, 100.4, 99.6, 100.4, 100.0, 100.4, 101.4] {
+        assert_eq!(hovered_at(&mut gui, x), LEFT_UID, "at {x}");
+    }
+    assert_eq!(hovered_at(&mut gui, 102.0), RIGHT_UID);
+    for x in [100.4, 99.6, 100.4, 99.6, 100.0, 98.6] {
+        assert_eq!(hovered_at(&mut gui, x), RIGHT_UID, "at {x}");
+    }
+    assert_eq!(hovered_at(&mut gui, 98.0), LEFT_UID);
+
+    // jumps don't stick
+    assert_eq!(hovered_at(&mut gui, 50.0), LEFT_UID);
+    assert_eq!(hovered_at(&mut gui, 100.4), RIGHT_UID);
+
+    // without hysteresis the hover follows the cursor right away
+    gui.set_hover_hysteresis(0.px());
+    for (x, hovered) in [(99.6, LEFT_UID), (100.4, RIGHT_UID), (99.6, LEFT_UID)] {
+        assert_eq!(hovered_at(&mut gui, x), hovered, "at {x}");
+    }
+}
+
+#[test]
+fn failed_frames_consume_their_input_and_restore_the_clock() {
+    use crate::input::*;
+    use crate::*;
+    use std::time::{Duration, Instant};
+
+    const BUTTON_UID: Uid = Uid::from_array(b"button");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        size: 100.px(),
+    };
+
+    // returns whether the node was clicked and the delta time of the frame
+    let frame = |gui: &mut ByorGui<NullRenderer>, fail: bool| {
+        gui.try_frame(screen_size, |mut gui| -> Result<(bool, Duration), bool> {
+            let clicked = gui
+                .insert_node(
+                    Some(BUTTON_UID),
+                    &style,
+                    NodeContents::EMPTY.with_hit_target(HitTarget::SelfOnly),
+                )
+                .expect("error building GUI")
+                .input_state
+                .clicked(MouseButtons::PRIMARY);
+            if fail {
+                Err(clicked)
+            } else {
+                Ok((clicked, gui.delta_time()))
+            }
+        })
+        .result
+    };
+
+    let start = Instant::now();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_time(start);
+    gui.move_cursor(Vec2 {
+        x: 50.px(),
+        y: 50.px(),
+    });
+    frame(&mut gui, false).unwrap();
+
+    let later = start + Duration::from_millis(100);
+    gui.set_time(later);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, true), Err(true));
+
+    // the click isn't delivered a second time, and the retry is built at the time of the failed
+    // frame
+    assert_eq!(
+        frame(&mut gui, false),
+        Ok((false, Duration::from_millis(100)))
+    );
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn markdown_is_parsed_into_blocks_of_rich_text() {
+    use crate::widgets::markdown::*;
+
+    let text = "# Title\n\nSome **bold** text and [a link](https://example.com).\n\n1. one\n2. two\n\n```\ncode\n```\n\n---\n";
+    let blocks = parse_document(text, DEFAULT_LINK_COLOR, DEFAULT_CODE_BACKGROUND);
+
+    assert_eq!(blocks.len(), 6);
+    assert!(matches!(blocks[0].kind, BlockKind::Heading(0)));
+    assert_eq!(blocks[0].content.text, "Title");
+
+    let paragraph = &blocks[1];
+    assert!(matches!(paragraph.kind, BlockKind::Paragraph));
+    assert_eq!(paragraph.content.text, "Some bold text and a link.");
+    assert_eq!(paragraph.content.spans.len(), 2);
+    assert_eq!(paragraph.content.spans[0].range, 5..9);
+    assert_eq!(
+        paragraph.content.links,
+        [(19..25, "https://example.com".to_owned())]
+    );
+
+    for (block, expected) in blocks[2..4].iter().zip(["1.", "2."]) {
+        assert!(
+            matches!(&block.kind, BlockKind::ListItem { marker: Some(marker) } if marker == expected)
+        );
+        assert_eq!(block.indent, 1);
+    }
+
+    assert!(matches!(blocks[4].kind, BlockKind::CodeBlock));
+    assert_eq!(blocks[4].content.text, "code");
+    assert!(matches!(blocks[5].kind, BlockKind::Rule));
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn markdown_documents_are_only_parsed_again_when_they_change() {
+    use crate::widgets::Markdown;
+    use crate::widgets::markdown::*;
+    use crate::*;
+    use std::sync::Arc;
+
+    const DOCUMENT_UID: Uid = Uid::from_array(b"document");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>, text: &str| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(Markdown::new(text).with_uid(DOCUMENT_UID))
+        })
+        .result
+        .expect("error building GUI");
+
+        let document = gui
+            .data
+            .persistent_state
+            .get(DOCUMENT_UID)
+            .expect("document has no persistent state")
+            .get::<CachedDocument>(PersistentStateKey::MarkdownDocument)
+            .expect("document isn't cached");
+        Arc::clone(&document.blocks)
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let first = frame(&mut gui, "first");
+    let unchanged = frame(&mut gui, "first");
+    assert!(Arc::ptr_eq(&first, &unchanged));
+
+    let changed = frame(&mut gui, "second");
+    assert!(!Arc::ptr_eq(&first, &changed));
+    assert_eq!(changed[0].content.text, "second");
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn clicking_a_markdown_link_returns_its_url() {
+    use crate::input::*;
+    use crate::widgets::Markdown;
+    use crate::*;
+
+    const DOCUMENT_UID: Uid = Uid::from_array(b"document");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(Markdown::new("[a link](https://example.com) and more").with_uid(DOCUMENT_UID))
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    assert_eq!(frame(&mut gui), None);
+
+    let paragraph = gui
+        .data
+        .previous_state
+        .get(DOCUMENT_UID.concat(Uid::new(0usize)))
+        .expect("paragraph wasn't laid out");
+    let (position, size) = (paragraph.position, paragraph.size);
+    let click = |gui: &mut ByorGui<NullRenderer>, x: Float<Pixel>| {
+        gui.move_cursor(Vec2 {
+            x,
+            y: position.y + size.y * 0.5,
+        });
+        gui.press_button(MouseButton::Primary);
+        let link = frame(gui);
+        gui.release_button(MouseButton::Primary);
+        frame(gui);
+        link
+    };
+
+    assert_eq!(
+        click(&mut gui, position.x + 4.px()).as_deref(),
+        Some("https://example.com")
+    );
+    // past the end of the text the paragraph is still hit, but no link is
+    assert_eq!(click(&mut gui, position.x + size.x - 4.px()), None);
+}
+
+#[test]
+fn repeated_clicks_are_timed_with_the_frame_clock() {
+    use crate::input::*;
+    use crate::*;
+    use std::time::{Duration, Instant};
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let start = Instant::now();
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut click_at = |elapsed: Duration| {
+        gui.set_time(start + elapsed);
+        gui.frame(screen_size, |_| ());
+        gui.press_button(MouseButton::Primary);
+        gui.release_button(MouseButton::Primary);
+        gui.input_state().click_count()
+    };
+
+    assert_eq!(click_at(Duration::ZERO), 1);
+    assert_eq!(click_at(MULTI_CLICK_INTERVAL), 2);
+    // however little wall clock time passed, the frame clock says the next click is too late
+    assert_eq!(
+        click_at(MULTI_CLICK_INTERVAL * 2 + Duration::from_millis(1)),
+        1
+    );
+}
+
+#[test]
+fn diagnostics_are_deduplicated_per_frame_and_capped() {
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>, names: usize| {
+        gui.frame(screen_size, |mut gui| {
+            for name in 0..names {
+                for _ in 0..3 {
+                    gui.stable_scope(&format!("section {name}"), |_| ());
+                }
+            }
+        });
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    frame(&mut gui, 1);
+    assert_eq!(gui.data.diagnostics.len(), 1);
+    // a problem that persists is reported by every frame
+    frame(&mut gui, 1);
+    let diagnostics = gui.take_diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0], diagnostics[1]);
+
+    frame(&mut gui, MAX_PENDING_DIAGNOSTICS + 10);
+    assert_eq!(gui.take_diagnostics().len(), MAX_PENDING_DIAGNOSTICS);
+    frame(&mut gui, 1);
+    assert_eq!(gui.take_diagnostics().len(), 1);
+}
+
+#[test]
+fn borrowed_popups_follow_their_flag_and_close_on_outside_clicks() {
+    use crate::input::*;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let position = FloatPosition::Fixed {
+        x: 100.px().into(),
+        y: 100.px().into(),
+        pivot: Alignment2D::TOP_LEFT,
+    };
+    let content_style = style! {
+        width: 100.px(),
+        height: 50.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>, open: &mut bool| {
+        gui.frame(screen_size, |mut gui| {
+            gui.popup(open, position, |mut gui| {
+                gui.insert_node(None, &content_style, NodeContents::EMPTY)
+                    .map(|_| ())
+            })
+        })
+        .result
+        .expect("error building GUI")
+        .is_some()
+    };
+    let click_at = |gui: &mut ByorGui<NullRenderer>, open: &mut bool, x: f32, y: f32| {
+        gui.move_cursor(Vec2 {
+            x: x.px(),
+            y: y.px(),
+        });
+        // hovering is resolved by the frame after the cursor moved
+        frame(gui, open);
+        gui.press_button(MouseButton::Primary);
+        let shown = frame(gui, open);
+        gui.release_button(MouseButton::Primary);
+        shown
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let mut open = false;
+    assert!(!frame(&mut gui, &mut open));
+
+    open = true;
+    assert!(frame(&mut gui, &mut open));
+    assert!(frame(&mut gui, &mut open));
+    assert!(click_at(&mut gui, &mut open, 120.0, 120.0));
+    assert!(open);
+
+    // the popup is still shown in the frame of the outside click, but closed afterwards
+    assert!(click_at(&mut gui, &mut open, 700.0, 500.0));
+    assert!(!open);
+    assert!(!frame(&mut gui, &mut open));
+
+    open = true;
+    assert!(frame(&mut gui, &mut open));
+    open = false;
+    assert!(!frame(&mut gui, &mut open));
+}
+
+#[test]
+fn persistent_popups_are_opened_and_closed_by_uid() {
+    use crate::input::*;
+    use crate::*;
+
+    const POPUP_UID: Uid = Uid::from_array(b"popup");
+
+    #[derive(Clone, Copy)]
+    enum Action {
+        None,
+        Open,
+        Close,
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let position = FloatPosition::Fixed {
+        x: 100.px().into(),
+        y: 100.px().into(),
+        pivot: Alignment2D::TOP_LEFT,
+    };
+    let content_style = style! {
+        width: 100.px(),
+        height: 50.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>, action: Action| {
+        gui.frame(screen_size, |mut gui| {
+            match action {
+                Action::None => (),
+                Action::Open => gui.open_popup(POPUP_UID),
+                Action::Close => gui.close_popup(POPUP_UID),
+            }
+
+            let shown = gui
+                .popup_auto(POPUP_UID, position, |mut gui| {
+                    gui.insert_node(None, &content_style, NodeContents::EMPTY)
+                        .map(|_| ())
+                })
+                .expect("error building GUI")
+                .is_some();
+            (shown, gui.is_popup_open(POPUP_UID))
+        })
+        .result
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    assert_eq!(frame(&mut gui, Action::None), (false, false));
+
+    assert_eq!(frame(&mut gui, Action::Open), (true, true));
+    // the popup stays open across frames by itself
+    assert_eq!(frame(&mut gui, Action::None), (true, true));
+    assert_eq!(frame(&mut gui, Action::None), (true, true));
+
+    gui.move_cursor(Vec2 {
+        x: 120.px(),
+        y: 120.px(),
+    });
+    frame(&mut gui, Action::None);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, Action::None), (true, true));
+    gui.release_button(MouseButton::Primary);
+
+    gui.move_cursor(Vec2 {
+        x: 700.px(),
+        y: 500.px(),
+    });
+    frame(&mut gui, Action::None);
+    gui.press_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, Action::None), (true, false));
+    gui.release_button(MouseButton::Primary);
+    assert_eq!(frame(&mut gui, Action::None), (false, false));
+
+    assert_eq!(frame(&mut gui, Action::Open), (true, true));
+    assert_eq!(frame(&mut gui, Action::Close), (false, false));
+    assert_eq!(frame(&mut gui, Action::None), (false, false));
+}
+
+#[test]
+fn button_labels_are_vertically_centered_on_their_cap_height() {
+    use crate::*;
+
+    const BUTTON_UID: Uid = Uid::from_array(b"button");
+    const FONT: &[u8] = include_bytes!("../tests/fonts/Cantarell-Regular.ttf");
+
+    // the system fonts may not specify a cap height, the test font does
+    with_global_font_cache(|_, font_context| {
+        let font = parley::fontique::Blob::new(std::sync::Arc::new(FONT));
+        font_context.collection.register_fonts(font, None);
+    });
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let button_style = style! {
+        height: 60.px(),
+        padding: 0.px(),
+        font_size: 40.px(),
+        font_family: FontStack::Single(FontFamily::Named("Cantarell".into())),
+        vertical_text_alignment: VerticalTextAlignment::Center,
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| {
+        gui.show(
+            widgets::Button::default()
+                .with_text("Label")
+                .with_uid(BUTTON_UID)
+                .with_style(&button_style),
+        )
+    })
+    .result
+    .expect("error building GUI");
+
+    let root = gui.forest.primary().unwrap();
+    let mut button = None;
+    crate::forest::iter_children!(root.descendants => |node| {
+        if node.uid == Some(BUTTON_UID) {
+            button = Some((node.text_layout.expand().unwrap(), node.vertical_text_offset));
+        }
+    });
+    let (text_layout, offset) = button.expect("button wasn't inserted");
+    let text_layout = &gui.data.text_layouts[text_layout];
+
+    let line = text_layout.lines().next().unwrap();
+    let run = line.runs().next().unwrap();
+    let cap_height = crate::layout::cap_height(&run).expect("font has no cap height");
+    let cap_top = offset + (line.metrics().baseline - cap_height).px();
+    let baseline = offset + line.metrics().baseline.px();
+    // the space above the capital letters matches the space below them, up to rounding
+    assert!(((60.px() - baseline) - cap_top).abs() <= 1.px());
+    // centering the full layout box would leave more space below the baseline
+    let layout_box_offset = ((60.px() - text_layout.height().px().ceil()) / 2.0).round();
+    assert_ne!(offset, layout_box_offset);
+}
+
+#[test]
+fn frames_without_the_input_viewport_hover_again() {
+    use crate::input::*;
+    use crate::*;
+
+    const BUTTON_UID: Uid = Uid::from_array(b"button");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(screen_size, |mut gui| {
+            gui.show(
+                widgets::Button::default()
+                    .with_text("button")
+                    .with_uid(BUTTON_UID)
+                    .with_style(&style),
+            )
+        })
+        .result
+        .expect("error building GUI")
+        .is_hovered()
+    };
+
+    // user IDs never collide with the main viewport, not even one made from 0
+    let window = ViewportId::new(0);
+    assert_ne!(window, ViewportId::MAIN);
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.on_viewport_input_event(
+        window,
+        InputEvent::CursorMoved {
+            position: Vec2 {
+                x: 10.px(),
+                y: 10.px(),
+            },
+        },
+    );
+    frame(&mut gui);
+    assert!(frame(&mut gui));
+}
+
+#[test]
+fn collected_errors_of_failed_frames_are_reported() {
+    use crate::*;
+
+    const NODE_UID: Uid = Uid::from_array(b"node");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let build = |mut gui: ByorGuiContext<'_, NullRenderer>, fail: bool| {
+        gui.insert_node(Some(NODE_UID), &Style::DEFAULT, NodeContents::EMPTY)?;
+        gui.insert_node(Some(NODE_UID), &Style::DEFAULT, NodeContents::EMPTY)?;
+        if fail {
+            Err(WidgetError::user("failed"))
+        } else {
+            Ok(())
+        }
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_error_policy(ErrorPolicy::CollectAndContinue);
+    let report = gui.try_frame(screen_size, |gui| build(gui, true));
+    assert!(report.result.is_err());
+    assert_eq!(report.errors.len(), 1);
+
+    let report = gui.try_frame(screen_size, |gui| build(gui, false));
+    report.result.expect("error building GUI");
+    assert_eq!(report.errors.len(), 1);
+
+    let report = gui.try_frame(screen_size, |mut gui| {
+        gui.insert_node(Some(NODE_UID), &Style::DEFAULT, NodeContents::EMPTY)
+            .map(|_| ())
+    });
+    report.result.expect("error building GUI");
+    assert!(report.errors.is_empty());
+}
+
+#[test]
+fn cached_text_layouts_are_not_added_to_the_spare_layouts() {
+    use crate::*;
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let frame = |gui: &mut ByorGui<NullRenderer>| {
+        gui.frame(
+            Vec2 {
+                x: 800.px(),
+                y: 600.px(),
+            },
+            |mut gui| -> widgets::WidgetResult<()> {
+                for i in 0..10 {
+                    let uid = Uid::new(i);
+                    gui.insert_node(
+                        Some(uid),
+                        &Style::default(),
+                        NodeContents::text_cached(uid, 0, "cached"),
+                    )?;
+                }
+                gui.insert_node(None, &Style::default(), NodeContents::text("not cached"))?;
+                Ok(())
+            },
+        )
+        .result
+        .expect("error building GUI");
+    };
+
+    frame(&mut gui);
+    frame(&mut gui);
+    let spare_layouts = gui.data.spare_text_layouts.len();
+    for _ in 0..50 {
+        frame(&mut gui);
+        assert_eq!(gui.data.spare_text_layouts.len(), spare_layouts);
+    }
+    assert_eq!(gui.data.text_layout_cache.len(), 10);
+}
+
+#[test]
+fn flattening_keeps_wrappers_that_limit_sticky_hover() {
+    use crate::*;
+
+    const NODE_UID: Uid = Uid::from_array(b"node");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let node_style = style! {
+        width: 100.px(),
+        height: 20.px(),
+    };
+
+    let run = |node_flattening: bool| {
+        let mut gui = ByorGui::<NullRenderer>::default();
+        gui.set_node_flattening(node_flattening);
+        let mut frame = |x: f32| {
+            gui.move_cursor(Vec2 {
+                x: x.px(),
+                y: 10.px(),
+            });
+            gui.frame(screen_size, |mut gui| {
+                // the wrapper sizes itself exactly like the node
+                gui.insert_node(
+                    None,
+                    &node_style,
+                    NodeContents::builder(|mut gui| {
+                        gui.insert_node(Some(NODE_UID), &node_style, NodeContents::EMPTY)
+                            .map(|response| response.is_hovered())
+                    }),
+                )?
+                .result
+            })
+            .result
+            .expect("error building GUI")
+        };
+
+        frame(99.0);
+        frame(99.0);
+        // just past the node, within its hysteresis but outside of its wrapper
+        frame(100.5);
+        frame(100.5)
+    };
+
+    assert_eq!(run(true), run(false));
+}
+
+#[test]
+fn hover_is_resolved_again_when_the_min_hit_size_changes() {
+    use crate::*;
+
+    const NODE_UID: Uid = Uid::from_array(b"node");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let small_style = style! {
+        width: 20.px(),
+        height: 20.px(),
+    };
+    let large_hit_style = small_style.clone().with_min_hit_size(60.px());
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.move_cursor(Vec2 {
+        x: 30.px(),
+        y: 10.px(),
+    });
+    let mut frame = |style: &Style| {
+        gui.frame(screen_size, |mut gui| {
+            gui.insert_node(
+                Some(NODE_UID),
+                style,
+                NodeContents::EMPTY.with_hit_target(HitTarget::SelfOnly),
+            )
+            .map(|response| response.is_hovered())
+        })
+        .result
+        .expect("error building GUI")
+    };
+
+    // the cursor doesn't move, only the hit rect of the node grows to reach it
+    frame(&small_style);
+    assert!(!frame(&small_style));
+    frame(&large_hit_style);
+    assert!(frame(&large_hit_style));
+}
+
+#[test]
+fn boxed_application_errors_convert_into_widget_errors_with_the_question_mark() {
+    use crate::*;
+
+    fn load(name: &str) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+        Err(format!("`{name}` not found").into())
+    }
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    let result = gui
+        .try_frame(screen_size, |mut gui| -> widgets::WidgetResult<()> {
+            gui.insert_node(None, &Style::DEFAULT, NodeContents::EMPTY)?;
+            load("settings.toml")?;
+            Ok(())
+        })
+        .result;
+
+    let Err(WidgetError::User(error)) = result else {
+        panic!("expected an application error");
+    };
+    assert_eq!(error.to_string(), "`settings.toml` not found");
+}
+
+#[test]
+fn fixed_floating_nodes_are_not_clamped_to_the_viewport() {
+    use crate::*;
+
+    const PANEL_UID: Uid = Uid::from_array(b"panel");
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        width: 200.px(),
+        height: 100.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.frame(screen_size, |mut gui| {
+        let position = FloatPosition::fixed(700.px(), -50.px());
+        gui.insert_floating_node(PANEL_UID, position, &style, NodeContents::EMPTY)
+            .map(|_| ())
+    })
+    .result
+    .expect("error building GUI");
+
+    // only popups are moved back into the viewport
+    let panel = gui.data.previous_state.get(PANEL_UID).unwrap();
+    assert_eq!(
+        panel.position,
+        Vec2 {
+            x: 700.px(),
+            y: -50.px(),
+        }
+    );
+}
+
+#[test]
+fn failed_frames_keep_the_debug_overlay() {
+    use crate::input::*;
+    use crate::rendering::DebugOverlay;
+    use crate::*;
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+
+    let mut gui = ByorGui::<NullRenderer>::default();
+    gui.set_debug_shortcut(Some(DebugOverlay::DEFAULT_SHORTCUT));
+    for key in [NamedKey::Control, NamedKey::Shift] {
+        gui.on_input_event(InputEvent::KeyPressed {
+            key: Key::Named(key),
+            location: KeyLocation::Left,
+            text: None,
+            repeat: false,
+        });
+    }
+    gui.on_input_event(InputEvent::KeyPressed {
+        key: Key::Character("D".into()),
+        location: KeyLocation::Standard,
+        text: Some("D".into()),
+        repeat: false,
+    });
+
+    let report = gui.try_frame(screen_size, |_| Err::<(), _>(WidgetError::user("failed")));
+    assert!(report.result.is_err());
+    assert_eq!(gui.debug_overlay(), DebugOverlay::Off);
+
+    gui.frame(screen_size, |_| ());
+    assert_eq!(gui.debug_overlay(), DebugOverlay::Off);
+}
+
+#[test]
+fn stroked_text_records_one_outline_per_run() {
+    use crate::rendering::display_list::{DisplayList, DrawCommand};
+    use crate::style::Color;
+    use crate::*;
+
+    const FONT: &[u8] = include_bytes!("../tests/fonts/Cantarell-Regular.ttf");
+
+    // the test font has no color tables, so every run is stroked
+    with_global_font_cache(|_, font_context| {
+        let font = parley::fontique::Blob::new(std::sync::Arc::new(FONT));
+        font_context.collection.register_fonts(font, None);
+    });
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let style = style! {
+        font_family: FontStack::Single(FontFamily::Named("Cantarell".into())),
+        text_stroke_width: 2.px(),
+        text_stroke_color: Color::BLACK,
+    };
+
+    let mut gui = ByorGui::<DisplayList>::default();
+    gui.frame(screen_size, |mut gui| {
+        gui.insert_node(None, &style, NodeContents::text("first line\nsecond line"))
+            .map(|_| ())
+    })
+    .result
     .expect("error building GUI");
+
+    let mut display_list = DisplayList::new();
+    gui.render(&mut display_list).unwrap();
+
+    let mut runs = Vec::new();
+    let mut outlines = Vec::new();
+    for command in display_list.commands() {
+        match command {
+            DrawCommand::Text {
+                position, glyphs, ..
+            } => runs.push((*position, glyphs.clone())),
+            &DrawCommand::StrokeText {
+                position,
+                width,
+                color,
+                ref glyphs,
+            } => {
+                assert_eq!((width, color), (2.px(), Color::BLACK));
+                outlines.push((position, glyphs.clone()));
+            }
+            _ => (),
+        }
+    }
+    assert_eq!(runs.len(), 2);
+    assert_eq!(outlines, runs);
 }
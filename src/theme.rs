@@ -1,23 +1,305 @@
+use crate::NodeInputState;
 use crate::style::*;
+use crate::widgets::{Button, ScrollBar, Slider, TextBox};
+use smol_str::SmolStr;
 pub use smol_str::SmolStr as StyleClass;
 
-#[derive(Default)]
+/// A semantic color of a theme, usable in place of a color in styles so all of them follow the
+/// theme when the role is changed with [`Theme::set_role`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorRole {
+    Accent,
+    Danger,
+    Warning,
+    Success,
+    Surface,
+    OnSurface,
+}
+
+impl ColorRole {
+    const COUNT: usize = 6;
+
+    /// A property function resolving to the color of this role in the theme the style is
+    /// cascaded with.
+    #[must_use]
+    pub fn property<T: From<Color>>(self) -> PropertyFn<T> {
+        fn resolve<T: From<Color>, const ROLE: usize>(
+            _: &CascadedStyle,
+            _: NodeInputState,
+            _: bool,
+            theme: &Theme,
+        ) -> T {
+            theme.roles[ROLE]
+                .unwrap_or(Theme::DEFAULT_ROLES[ROLE])
+                .into()
+        }
+
+        match self {
+            Self::Accent => resolve::<T, { Self::Accent as usize }>,
+            Self::Danger => resolve::<T, { Self::Danger as usize }>,
+            Self::Warning => resolve::<T, { Self::Warning as usize }>,
+            Self::Success => resolve::<T, { Self::Success as usize }>,
+            Self::Surface => resolve::<T, { Self::Surface as usize }>,
+            Self::OnSurface => resolve::<T, { Self::OnSurface as usize }>,
+        }
+    }
+}
+
+impl<T: From<Color>, const INHERIT_FALLBACK: bool> From<ColorRole>
+    for Property<T, INHERIT_FALLBACK>
+{
+    #[inline]
+    fn from(role: ColorRole) -> Self {
+        Self::Compute(role.property())
+    }
+}
+
+impl<T: From<Color>> From<ColorRole> for _PropertyValue<T, T> {
+    #[inline]
+    fn from(role: ColorRole) -> Self {
+        Self::Compute(role.property())
+    }
+}
+
+/// Where the styles of a theme class go in the cascade of a widget's style. From lowest to highest
+/// priority the cascade is:
+///
+/// 1. the initial values of the properties
+/// 2. the [`Normal`](Self::Normal) theme styles of the custom classes, the type class and the
+///    universal class, in that order
+/// 3. the instance style of the widget
+/// 4. the [`Override`](Self::Override) theme styles, in the same class order
+///
+/// Properties that are [`Property::Important`] in a normal theme style are part of the override
+/// layer as well.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StylePriority {
+    /// Instance styles win over the style
+    #[default]
+    Normal,
+    /// The style wins over instance styles, for example so an accessibility theme can enforce its
+    /// colors
+    Override,
+}
+
 pub struct Theme {
     styles: rapidhash::RapidHashMap<StyleClass, Style>,
+    override_styles: rapidhash::RapidHashMap<StyleClass, Style>,
+    tokens: rapidhash::RapidHashMap<SmolStr, Color>,
+    /// `None` for the roles that were never set, so merging themes can tell them apart
+    roles: [Option<Color>; ColorRole::COUNT],
+    density: Option<f32>,
+    touch_target_style: Option<Style>,
+    overflow_tooltips: Option<bool>,
+}
+
+impl Default for Theme {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            styles: Default::default(),
+            override_styles: Default::default(),
+            tokens: Default::default(),
+            roles: [None; ColorRole::COUNT],
+            density: None,
+            touch_target_style: None,
+            overflow_tooltips: None,
+        }
+    }
 }
 
 impl Theme {
     pub const UNIVERSAL_CLASS: StyleClass = StyleClass::new_static("");
     pub const ROOT_TYPE_CLASS: StyleClass = StyleClass::new_static("###root");
 
+    pub const COMPACT_DENSITY: f32 = 0.75;
+    pub const NORMAL_DENSITY: f32 = 1.0;
+    pub const COMFORTABLE_DENSITY: f32 = 1.25;
+
+    /// The type classes [`set_min_touch_target`](Self::set_min_touch_target) applies to.
+    pub const INTERACTIVE_TYPE_CLASSES: [StyleClass; 5] = [
+        Button::TYPE_CLASS,
+        TextBox::TYPE_CLASS,
+        ScrollBar::HORIZONTAL_TYPE_CLASS,
+        ScrollBar::VERTICAL_TYPE_CLASS,
+        Slider::THUMB_CLASS,
+    ];
+
+    /// The role colors of a new theme, in the order of [`ColorRole`].
+    const DEFAULT_ROLES: [Color; ColorRole::COUNT] = [
+        INITIAL_SELECTION_COLOR,
+        Color::rgb(220, 53, 69),
+        Color::rgb(245, 166, 66),
+        Color::rgb(40, 167, 69),
+        Color::WHITE,
+        INITIAL_TEXT_COLOR,
+    ];
+
+    #[must_use]
+    #[inline]
+    pub fn density(&self) -> f32 {
+        self.density.unwrap_or(Self::NORMAL_DENSITY)
+    }
+
+    /// Sets a factor that paddings, child spacings and corner radii are multiplied with.
+    ///
+    /// Unlike the scale factor this leaves font sizes and all other measurements as they are.
+    /// Densities that are negative or not finite are ignored.
+    #[inline]
+    pub fn set_density(&mut self, density: f32) {
+        if density.is_finite() && (density >= 0.0) {
+            self.density = Some(density);
+        }
+    }
+
+    /// Gives every node of the [interactive type classes](Self::INTERACTIVE_TYPE_CLASSES) a
+    /// minimum hit size in both axes, for example when the app is used with touch input. Nodes
+    /// whose style or classes specify a minimum hit size keep it. `None` removes the default again.
+    pub fn set_min_touch_target(&mut self, size: Option<AbsoluteMeasurement>) {
+        self.touch_target_style = size.map(|size| Style::DEFAULT.with_min_hit_size(size));
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn overflow_tooltips(&self) -> bool {
+        self.overflow_tooltips.unwrap_or(false)
+    }
+
+    /// Whether labels show their full text in a tooltip while they are hovered and their text
+    /// is cut off, for the labels that don't decide themselves with
+    /// [`Label::with_overflow_tooltip`](crate::widgets::Label::with_overflow_tooltip). Off by default.
+    #[inline]
+    pub fn set_overflow_tooltips(&mut self, overflow_tooltips: bool) {
+        self.overflow_tooltips = Some(overflow_tooltips);
+    }
+
+    /// Defines a named color, like an accent color, that property functions and node renderers
+    /// can look up with [`color_token`](Self::color_token). Inserting an existing token replaces
+    /// its color.
+    pub fn insert_token(&mut self, name: impl Into<SmolStr>, color: Color) {
+        self.tokens.insert(name.into(), color);
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn color_token(&self, name: &str) -> Option<Color> {
+        self.tokens.get(name).copied()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn role(&self, role: ColorRole) -> Color {
+        self.roles[role as usize].unwrap_or(Self::DEFAULT_ROLES[role as usize])
+    }
+
+    /// Changes the color of a role, which recolors every style that uses the role.
+    #[inline]
+    pub fn set_role(&mut self, role: ColorRole, color: Color) {
+        self.roles[role as usize] = Some(color);
+    }
+
+    /// Layers `overlay` on top of this theme. Styles are combined class by class like
+    /// [`insert_style_with_priority`](Self::insert_style_with_priority) does, so the overlay wins
+    /// for every property it specifies at the same priority. Tokens, roles, the density, the minimum touch target and the overflow tooltip default
+    /// of the overlay replace the ones of this theme if the overlay sets them.
+    pub fn merge(&mut self, overlay: &Theme) {
+        for (class, style) in &overlay.styles {
+            self.insert_style(class.clone(), style);
+        }
+        for (class, style) in &overlay.override_styles {
+            self.insert_style_with_priority(class.clone(), StylePriority::Override, style);
+        }
+
+        for (name, &color) in &overlay.tokens {
+            self.tokens.insert(name.clone(), color);
+        }
+
+        for (role, overlay_role) in self.roles.iter_mut().zip(overlay.roles) {
+            *role = overlay_role.or(*role);
+        }
+
+        self.density = overlay.density.or(self.density);
+        self.overflow_tooltips = overlay.overflow_tooltips.or(self.overflow_tooltips);
+        if let Some(touch_target_style) = &overlay.touch_target_style {
+            self.touch_target_style = Some(touch_target_style.clone());
+        }
+    }
+
+    /// Returns `base` with `overlay` layered on top, see [`merge`](Self::merge).
+    #[must_use]
+    pub fn with_overlay(mut base: Theme, overlay: &Theme) -> Theme {
+        base.merge(overlay);
+        base
+    }
+
+    /// Inserts a style with [`StylePriority::Normal`], see
+    /// [`insert_style_with_priority`](Self::insert_style_with_priority).
+    #[inline]
     pub fn insert_style(&mut self, class: StyleClass, style: &Style) {
-        if let Some(existing_style) = self.styles.get_mut(&class) {
-            *existing_style = style.or_else(&existing_style);
-        } else {
-            self.styles.insert(class, style.clone());
+        self.insert_style_with_priority(class, StylePriority::Normal, style);
+    }
+
+    /// Combines `style` with the style of the class at the same priority, where `style` wins for
+    /// every property it specifies. Important properties of normal styles go to the override
+    /// layer, and override styles are important as a whole. Styles passed to
+    /// [`insert_node`](crate::ByorGuiContext::insert_node) directly don't go through the theme.
+    pub fn insert_style_with_priority(
+        &mut self,
+        class: StyleClass,
+        priority: StylePriority,
+        style: &Style,
+    ) {
+        fn insert(
+            styles: &mut rapidhash::RapidHashMap<StyleClass, Style>,
+            class: StyleClass,
+            style: Style,
+        ) {
+            if let Some(existing_style) = styles.get_mut(&class) {
+                *existing_style = style.or_else(existing_style);
+            } else {
+                styles.insert(class, style);
+            }
+        }
+
+        match priority {
+            StylePriority::Normal => {
+                let (style, important) = style.split_important();
+                if let Some(important) = important {
+                    insert(&mut self.override_styles, class.clone(), important);
+                }
+                insert(&mut self.styles, class, style);
+            }
+            StylePriority::Override => {
+                insert(&mut self.override_styles, class, style.without_importance());
+            }
         }
     }
 
+    #[must_use]
+    #[inline]
+    pub(crate) fn class_style(&self, class: &StyleClass) -> Option<&Style> {
+        self.styles.get(class)
+    }
+
+    /// The style of `class` on top of `base`, or `fallback` on top of `base` if the theme doesn't
+    /// style the class.
+    ///
+    /// Unlike in [`build_style`](Self::build_style) the class style isn't merged with the fallback
+    /// but replaces it as a whole, so a theme can for example restyle the hover states of a class
+    /// without inheriting the background of the fallback.
+    #[must_use]
+    pub(crate) fn class_style_or(
+        &self,
+        class: &StyleClass,
+        fallback: impl FnOnce() -> Style,
+        base: &Style,
+    ) -> Style {
+        self.class_style(class)
+            .cloned()
+            .unwrap_or_else(fallback)
+            .or_else(base)
+    }
+
     pub fn build_style(
         &self,
         explicit_style: Option<&Style>,
@@ -40,6 +322,25 @@ impl Theme {
             style = style.or_else(class_style);
         }
 
+        if let Some(touch_target_style) = &self.touch_target_style
+            && Self::INTERACTIVE_TYPE_CLASSES.contains(&type_class)
+        {
+            style = style.or_else(touch_target_style);
+        }
+
+        if !self.override_styles.is_empty() {
+            let mut override_style = Style::DEFAULT;
+            for class in custom_classes
+                .iter()
+                .chain([&type_class, &Self::UNIVERSAL_CLASS])
+            {
+                if let Some(class_style) = self.override_styles.get(class) {
+                    override_style = override_style.or_else(class_style);
+                }
+            }
+            style = override_style.or_else(&style);
+        }
+
         style
     }
 
@@ -66,6 +367,19 @@ impl Theme {
             property = property.or_else(select_property(class_style));
         }
 
+        if !self.override_styles.is_empty() {
+            let mut override_property = Property::Unspecified;
+            for class in custom_classes
+                .iter()
+                .chain([&type_class, &Self::UNIVERSAL_CLASS])
+            {
+                if let Some(class_style) = self.override_styles.get(class) {
+                    override_property = override_property.or_else(select_property(class_style));
+                }
+            }
+            property = override_property.or_else(&property);
+        }
+
         property
     }
 }
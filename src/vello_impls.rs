@@ -1,10 +1,13 @@
 use crate::rendering::*;
 use crate::*;
 use vello::Scene;
-use vello::kurbo::{self, Affine, Line, PathEl, Rect, Shape, Stroke};
+use vello::kurbo::{self, Affine, BezPath, Join, Line, PathEl, Rect, Shape, Stroke};
 use vello::peniko::color::{AlphaColor, DynamicColor, Srgb};
 use vello::peniko::{self, Fill};
 
+/// How closely rounded corners are approximated when shapes are merged into a single path.
+const PATH_TOLERANCE: f64 = 0.1;
+
 impl From<Vec2<Pixel>> for kurbo::Point {
     #[inline]
     fn from(value: Vec2<Pixel>) -> Self {
@@ -49,7 +52,6 @@ impl From<Color> for DynamicColor {
     }
 }
 
-#[must_use]
 fn convert_gradient_stops(stops: &[GradientStop]) -> impl Iterator<Item = peniko::ColorStop> {
     use peniko::color::{ColorSpaceTag, HueDirection, gradient};
 
@@ -106,9 +108,9 @@ fn convert_brush(brush: ComputedBrush) -> (peniko::Brush, Option<Affine>) {
 }
 
 #[derive(Clone, Copy)]
-#[repr(transparent)]
 struct Polygon<'a> {
     vertices: &'a [Vec2<Pixel>],
+    closed: bool,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -121,6 +123,7 @@ enum PolygonIterState {
 struct PolygonIter<'a> {
     state: PolygonIterState,
     vertices: &'a [Vec2<Pixel>],
+    closed: bool,
 }
 
 impl Iterator for PolygonIter<'_> {
@@ -140,7 +143,7 @@ impl Iterator for PolygonIter<'_> {
             }
         } else {
             self.state = PolygonIterState::Finished;
-            Some(PathEl::ClosePath)
+            self.closed.then_some(PathEl::ClosePath)
         }
     }
 }
@@ -155,6 +158,7 @@ impl Shape for Polygon<'_> {
         PolygonIter {
             state: PolygonIterState::Open,
             vertices: self.vertices,
+            closed: self.closed,
         }
     }
 
@@ -176,6 +180,162 @@ impl Shape for Polygon<'_> {
     }
 }
 
+#[must_use]
+fn convert_path(path: &Path) -> BezPath {
+    path.segments()
+        .iter()
+        .map(|&segment| match segment {
+            PathSegment::MoveTo(point) => PathEl::MoveTo(point.into()),
+            PathSegment::LineTo(point) => PathEl::LineTo(point.into()),
+            PathSegment::QuadTo { control, end } => PathEl::QuadTo(control.into(), end.into()),
+            PathSegment::CubicTo {
+                control1,
+                control2,
+                end,
+            } => PathEl::CurveTo(control1.into(), control2.into(), end.into()),
+            PathSegment::Close => PathEl::ClosePath,
+        })
+        .collect()
+}
+
+fn stroke_shape(scene: &mut Scene, shape: &impl Shape, width: Float<Pixel>, brush: ComputedBrush) {
+    if let ComputedBrush::Solid(Color { a: 0, .. }) = brush {
+        return;
+    };
+
+    let (brush, brush_transform) = convert_brush(brush);
+    scene.stroke(
+        &Stroke::new(width.value() as f64),
+        Affine::IDENTITY,
+        &brush,
+        brush_transform,
+        shape,
+    );
+}
+
+/// Strokes the glyph outlines of a run. The stroke is centered on the outlines, so it is twice as
+/// wide as the visible outline and the fill drawn on top covers the inner half.
+fn stroke_glyph_run(
+    scene: &mut Scene,
+    text: parley::GlyphRun<'_, Color>,
+    position: Vec2<Pixel>,
+    width: Float<Pixel>,
+    color: Color,
+) {
+    let run = text.run();
+    // color glyphs ignore the stroke style and would be drawn a second time instead
+    if is_color_font(run.font()) {
+        return;
+    }
+
+    let synthesis = run.synthesis();
+    let glyph_xform = synthesis
+        .skew()
+        .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
+    let stroke = Stroke::new(2.0 * width.value() as f64).with_join(Join::Round);
+
+    scene
+        .draw_glyphs(run.font())
+        .brush(&peniko::Brush::Solid(color.into()))
+        .hint(true)
+        .transform(Affine::translate(position))
+        .glyph_transform(glyph_xform)
+        .font_size(run.font_size())
+        .normalized_coords(run.normalized_coords())
+        .draw(
+            &stroke,
+            text.positioned_glyphs().map(|glyph| vello::Glyph {
+                id: glyph.id,
+                x: glyph.x,
+                y: glyph.y,
+            }),
+        );
+}
+
+/// Draws a glyph run, optionally replacing all of its brushes with `color`.
+fn draw_glyph_run(
+    scene: &mut Scene,
+    text: parley::GlyphRun<'_, Color>,
+    position: Vec2<Pixel>,
+    color: Option<Color>,
+) {
+    let style = text.style();
+    let transform = Affine::translate(position);
+
+    if let Some(underline) = &style.underline {
+        let brush = peniko::Brush::Solid(color.unwrap_or(underline.brush).into());
+
+        let run_metrics = text.run().metrics();
+        let offset = match underline.offset {
+            Some(offset) => offset,
+            None => run_metrics.underline_offset,
+        };
+        let width = match underline.size {
+            Some(size) => size,
+            None => run_metrics.underline_size,
+        };
+
+        let y = text.baseline() - offset + width / 2.0;
+
+        let line = Line::new(
+            (text.offset() as f64, y as f64),
+            ((text.offset() + text.advance()) as f64, y as f64),
+        );
+        scene.stroke(&Stroke::new(width.into()), transform, brush, None, &line);
+    }
+
+    {
+        let brush = peniko::Brush::Solid(color.unwrap_or(style.brush).into());
+
+        let run = text.run();
+        let font = run.font();
+        let font_size = run.font_size();
+        let synthesis = run.synthesis();
+        let glyph_xform = synthesis
+            .skew()
+            .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
+
+        scene
+            .draw_glyphs(font)
+            .brush(&brush)
+            .hint(true)
+            .transform(transform)
+            .glyph_transform(glyph_xform)
+            .font_size(font_size)
+            .normalized_coords(run.normalized_coords())
+            .draw(
+                Fill::NonZero,
+                text.positioned_glyphs().map(|glyph| vello::Glyph {
+                    id: glyph.id,
+                    x: glyph.x,
+                    y: glyph.y,
+                }),
+            );
+    }
+
+    if let Some(strikethrough) = &style.strikethrough {
+        let brush = peniko::Brush::Solid(color.unwrap_or(strikethrough.brush).into());
+
+        let run_metrics = text.run().metrics();
+        let offset = match strikethrough.offset {
+            Some(offset) => offset,
+            None => run_metrics.strikethrough_offset,
+        };
+        let width = match strikethrough.size {
+            Some(size) => size,
+            None => run_metrics.strikethrough_size,
+        };
+
+        let y = text.baseline() - offset + run_metrics.strikethrough_size / 2.0;
+
+        let line = Line::new(
+            (text.offset() as f64, y as f64),
+            ((text.offset() + text.advance()) as f64, y as f64),
+        );
+        scene.stroke(&Stroke::new(width.into()), transform, brush, None, &line);
+    }
+}
+
 impl Renderer for Scene {
     type Error = std::convert::Infallible;
 
@@ -265,6 +425,48 @@ impl Renderer for Scene {
         Ok(())
     }
 
+    fn fill_rects(
+        &mut self,
+        rects: &[(Vec2<Pixel>, Vec2<Pixel>)],
+        corner_radius: Float<Pixel>,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        if rects.is_empty() {
+            return Ok(());
+        }
+        if let ComputedBrush::Solid(Color { a: 0, .. }) = brush {
+            return Ok(());
+        };
+
+        // one subpath per rect, so the scene encodes a single fill
+        let mut path = BezPath::new();
+        for &(position, size) in rects {
+            let rect = Rect::from_origin_size(position, size);
+            if corner_radius == 0.px() {
+                path.extend(rect.path_elements(PATH_TOLERANCE));
+            } else {
+                path.extend(
+                    rect.to_rounded_rect(corner_radius.value() as f64)
+                        .path_elements(PATH_TOLERANCE),
+                );
+            }
+        }
+
+        let (brush, brush_transform) = convert_brush(brush);
+        self.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &brush,
+            brush_transform,
+            &path,
+        );
+
+        Ok(())
+    }
+
+    // A scene can't sample what was drawn into it so far, so backdrop blur isn't supported and
+    // nodes fall back to a more opaque background.
+
     fn draw_poly(
         &mut self,
         vertices: &[Vec2<Pixel>],
@@ -272,7 +474,10 @@ impl Renderer for Scene {
         color: Color,
     ) -> Result<(), Self::Error> {
         if color.a > 0 {
-            let poly = Polygon { vertices };
+            let poly = Polygon {
+                vertices,
+                closed: true,
+            };
             let brush = peniko::Brush::Solid(color.into());
 
             self.stroke(
@@ -296,7 +501,10 @@ impl Renderer for Scene {
             return Ok(());
         };
 
-        let poly = Polygon { vertices };
+        let poly = Polygon {
+            vertices,
+            closed: true,
+        };
         let (brush, brush_transform) = convert_brush(brush);
 
         self.fill(
@@ -310,85 +518,109 @@ impl Renderer for Scene {
         Ok(())
     }
 
-    fn draw_text(
+    fn stroke_line(
         &mut self,
-        text: parley::GlyphRun<'_, Color>,
-        position: Vec2<Pixel>,
+        from: Vec2<Pixel>,
+        to: Vec2<Pixel>,
+        width: Float<Pixel>,
+        brush: ComputedBrush,
     ) -> Result<(), Self::Error> {
-        let style = text.style();
-        let transform = Affine::translate(position);
+        stroke_shape(self, &Line::new(from, to), width, brush);
 
-        if let Some(underline) = &style.underline {
-            let brush = peniko::Brush::Solid(underline.brush.into());
+        Ok(())
+    }
 
-            let run_metrics = text.run().metrics();
-            let offset = match underline.offset {
-                Some(offset) => offset,
-                None => run_metrics.underline_offset,
-            };
-            let width = match underline.size {
-                Some(size) => size,
-                None => run_metrics.underline_size,
-            };
+    fn stroke_poly(
+        &mut self,
+        vertices: &[Vec2<Pixel>],
+        width: Float<Pixel>,
+        closed: bool,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        stroke_shape(self, &Polygon { vertices, closed }, width, brush);
 
-            let y = text.baseline() - offset + width / 2.0;
+        Ok(())
+    }
 
-            let line = Line::new(
-                (text.offset() as f64, y as f64),
-                ((text.offset() + text.advance()) as f64, y as f64),
-            );
-            self.stroke(&Stroke::new(width.into()), transform, brush, None, &line);
-        }
+    fn fill_path(&mut self, path: &Path, brush: ComputedBrush) -> Result<(), Self::Error> {
+        if let ComputedBrush::Solid(Color { a: 0, .. }) = brush {
+            return Ok(());
+        };
 
-        {
-            let brush = peniko::Brush::Solid(style.brush.into());
-
-            let run = text.run();
-            let font = run.font();
-            let font_size = run.font_size();
-            let synthesis = run.synthesis();
-            let glyph_xform = synthesis
-                .skew()
-                .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
-
-            self.draw_glyphs(font)
-                .brush(&brush)
-                .hint(true)
-                .transform(transform)
-                .glyph_transform(glyph_xform)
-                .font_size(font_size)
-                .normalized_coords(run.normalized_coords())
-                .draw(
-                    Fill::NonZero,
-                    text.positioned_glyphs().map(|glyph| vello::Glyph {
-                        id: glyph.id,
-                        x: glyph.x,
-                        y: glyph.y,
-                    }),
-                );
-        }
+        let (brush, brush_transform) = convert_brush(brush);
+        self.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &brush,
+            brush_transform,
+            &convert_path(path),
+        );
 
-        if let Some(strikethrough) = &style.strikethrough {
-            let brush = peniko::Brush::Solid(strikethrough.brush.into());
+        Ok(())
+    }
 
-            let run_metrics = text.run().metrics();
-            let offset = match strikethrough.offset {
-                Some(offset) => offset,
-                None => run_metrics.strikethrough_offset,
-            };
-            let width = match strikethrough.size {
-                Some(size) => size,
-                None => run_metrics.strikethrough_size,
-            };
+    fn stroke_path(
+        &mut self,
+        path: &Path,
+        width: Float<Pixel>,
+        brush: ComputedBrush,
+    ) -> Result<(), Self::Error> {
+        stroke_shape(self, &convert_path(path), width, brush);
 
-            let y = text.baseline() - offset + run_metrics.strikethrough_size / 2.0;
+        Ok(())
+    }
 
-            let line = Line::new(
-                (text.offset() as f64, y as f64),
-                ((text.offset() + text.advance()) as f64, y as f64),
-            );
-            self.stroke(&Stroke::new(width.into()), transform, brush, None, &line);
-        }
+    fn draw_text(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+    ) -> Result<(), Self::Error> {
+        draw_glyph_run(self, text, position, None);
+
+        Ok(())
+    }
+
+    fn draw_text_with_color(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        draw_glyph_run(self, text, position, Some(color));
+
+        Ok(())
+    }
+
+    fn stroke_text(
+        &mut self,
+        text: parley::GlyphRun<'_, Color>,
+        position: Vec2<Pixel>,
+        width: Float<Pixel>,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        stroke_glyph_run(self, text, position, width, color);
+
+        Ok(())
+    }
+}
+
+impl ByorGui<Scene> {
+    /// Renders the GUI into an existing scene, with its top left corner at `offset`.
+    ///
+    /// Nothing is drawn outside of the [screen size](Self::screen_size) of the last frame. Input
+    /// events have to be relative to `offset` as well, see [`on_input_event`](Self::on_input_event).
+    pub fn render_into(
+        &mut self,
+        scene: &mut Scene,
+        offset: Vec2<Pixel>,
+    ) -> Result<(), RenderError<std::convert::Infallible>> {
+        let mut gui_scene = Scene::new();
+        let bounds = Rect::from_origin_size(kurbo::Point::ZERO, self.screen_size());
+        gui_scene.push_clip_layer(Affine::IDENTITY, &bounds);
+        self.render(&mut gui_scene)?;
+        gui_scene.pop_layer();
+
+        scene.append(&gui_scene, Some(Affine::translate(offset)));
 
         Ok(())
     }
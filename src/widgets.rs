@@ -1,19 +1,41 @@
 pub mod button;
+pub mod date_picker;
+pub mod dialog;
+pub mod form;
+pub mod gauge;
 pub mod label;
+pub mod list_box;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+pub mod navigation;
 pub mod panel;
 pub mod popup;
+pub mod reorderable_list;
 pub mod scroll;
+pub mod slider;
 pub mod text_box;
 
 use crate::theme::StyleClass;
 use crate::*;
 
-pub use button::{Button, CanvasButton, ContentButton};
-pub use label::Label;
+pub use button::{Button, CanvasButton, ContentButton, IconButton, IconPlacement};
+pub use date_picker::{Date, DatePicker, Weekday};
+pub use dialog::{ConfirmOptions, Dialog, DialogChoice, MessageOptions};
+pub use form::Form;
+pub use gauge::Gauge;
+pub use label::{Label, ShortcutLabel};
+pub use list_box::{ListBox, SelectionMode, SelectionState};
+#[cfg(feature = "markdown")]
+pub use markdown::Markdown;
+pub use navigation::{Breadcrumbs, SegmentedControl, Segments};
 pub use panel::FlexPanel;
 pub use popup::Popup;
-pub use scroll::{ScrollBar, ScrollView};
-pub use text_box::TextBox;
+pub use reorderable_list::ReorderableList;
+pub use scroll::{
+    KeyboardScroll, ScrollBar, ScrollBarVisibility, ScrollSnap, ScrollView, ScrollViewResponse,
+};
+pub use slider::{RangeSlider, RangeSliderResponse, RangeThumb, Slider, ThumbCrossing};
+pub use text_box::{SelectOnFocus, TextBox, TextBoxResponse, TextFilter, WordBoundaryPolicy};
 
 #[derive(Debug, Clone, Copy)]
 pub enum MaybeUid {
@@ -51,12 +73,13 @@ impl MaybeUid {
     pub fn produce(self) -> Uid {
         match self {
             Self::Some(uid) => uid,
+            // must stay the same as `Uid::from_location`
             Self::None(location) => Uid::new(location),
         }
     }
 }
 
-pub type WidgetResult<T> = Result<T, DuplicateUidError>;
+pub type WidgetResult<T> = Result<T, WidgetError>;
 
 pub trait WidgetData: Sized {
     fn type_class(&self) -> StyleClass;
@@ -198,6 +221,17 @@ impl<'style, 'classes, Data: WidgetData> Widget<'style, 'classes, Data> {
 }
 
 impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    fn record_widget_classes<Data: WidgetData>(&mut self, widget: &Widget<Data>) {
+        if cfg!(debug_assertions) {
+            let uid = self.compute_recursive_uid(widget.uid.produce());
+            // the vectors of the last frame are reused, so recording doesn't allocate every frame
+            let classes = self.data.widget_classes.entry(uid).or_default();
+            classes.clear();
+            classes.push(widget.type_class());
+            classes.extend(widget.classes.iter().cloned());
+        }
+    }
+
     #[track_caller]
     pub fn show<Data: LeafWidgetData<Renderer>>(
         &mut self,
@@ -206,6 +240,7 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         let style = self
             .theme()
             .build_style(widget.style, widget.classes, widget.type_class());
+        self.record_widget_classes(&widget);
 
         widget.data.show(self, widget.uid, style)
     }
@@ -219,6 +254,7 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         let style = self
             .theme()
             .build_style(widget.style, widget.classes, widget.type_class());
+        self.record_widget_classes(&widget);
 
         widget.data.show(self, widget.uid, style, contents)
     }
@@ -254,6 +290,34 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         self.show(CanvasButton::new(renderer))
     }
 
+    #[track_caller]
+    #[inline]
+    pub fn icon_button(
+        &mut self,
+        icon: impl rendering::NodeRenderer<Renderer = Renderer>,
+        text: &str,
+    ) -> WidgetResult<NodeInputState> {
+        self.show(Button::default().with_text(text).with_icon(icon))
+    }
+
+    /// Shows [`Breadcrumbs`] and returns the index of the clicked segment.
+    #[track_caller]
+    #[inline]
+    pub fn breadcrumbs(&mut self, labels: &[&str]) -> WidgetResult<Option<usize>> {
+        self.show(Breadcrumbs::new(labels))
+    }
+
+    /// Shows a [`SegmentedControl`] and returns whether the selection changed.
+    #[track_caller]
+    #[inline]
+    pub fn segmented_control(
+        &mut self,
+        labels: &[&str],
+        selected: &mut usize,
+    ) -> WidgetResult<bool> {
+        self.show(SegmentedControl::new(labels, selected))
+    }
+
     #[track_caller]
     #[inline]
     pub fn flex_panel<R>(
@@ -283,12 +347,33 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         self.show(scroll_bar)
     }
 
+    /// Shows a [`RangeSlider`] between `min` and `max` for the lower and higher value of
+    /// `range`.
+    #[track_caller]
+    #[inline]
+    pub fn range_slider(
+        &mut self,
+        range: &mut (f32, f32),
+        min: f32,
+        max: f32,
+    ) -> WidgetResult<RangeSliderResponse> {
+        let slider = RangeSlider::new(range).with_min(min).with_max(max);
+        self.show(slider)
+    }
+
+    /// Shows a [`DatePicker`] for `date` and returns whether it changed.
+    #[track_caller]
+    #[inline]
+    pub fn date_picker(&mut self, date: &mut Date) -> WidgetResult<bool> {
+        self.show(DatePicker::new(date))
+    }
+
     #[track_caller]
     #[inline]
     pub fn horizontal_scroll_view<R>(
         &mut self,
         contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
-    ) -> WidgetResult<R> {
+    ) -> WidgetResult<ScrollViewResponse<R>> {
         self.show_container(ScrollView::horizontal(), contents)
     }
 
@@ -297,7 +382,7 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
     pub fn vertical_scroll_view<R>(
         &mut self,
         contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
-    ) -> WidgetResult<R> {
+    ) -> WidgetResult<ScrollViewResponse<R>> {
         self.show_container(ScrollView::vertical(), contents)
     }
 
@@ -312,9 +397,97 @@ impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
         self.show_container(Popup::new(open).with_position(position), contents)
     }
 
+    /// Shows `contents` as a [`Form`], see [`form_row`](Self::form_row).
+    #[track_caller]
+    #[inline]
+    pub fn form<R>(
+        &mut self,
+        contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
+    ) -> WidgetResult<R> {
+        self.show_container(Form::default(), contents)
+    }
+
+    /// Shows a full circle [`Gauge`] filled by `value` in `0..=1`.
+    #[track_caller]
+    #[inline]
+    pub fn gauge(&mut self, value: f32) -> WidgetResult<()> {
+        self.show(Gauge::new(value))
+    }
+
+    /// Shows a popup that keeps track of whether it is open by itself.
+    ///
+    /// `uid` is resolved relative to the current [`uid_scope`](Self::uid_scope), the same way as
+    /// in [`open_popup`](Self::open_popup) and [`close_popup`](Self::close_popup), so all calls
+    /// for one popup must be made from within the same scope.
+    #[track_caller]
+    #[inline]
+    pub fn popup_auto<R>(
+        &mut self,
+        uid: Uid,
+        position: FloatPosition,
+        contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
+    ) -> WidgetResult<Option<R>> {
+        let popup = Popup::persistent().with_uid(uid).with_position(position);
+        self.show_container(popup, contents)
+    }
+
+    /// Shows `contents` in a floating node centered on the screen, e.g. for a dialog.
+    #[track_caller]
+    pub fn centered_float<R>(
+        &mut self,
+        uid: Uid,
+        contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
+    ) -> WidgetResult<R> {
+        let center = self.data.screen_size / 2.0;
+        let position = FloatPosition::Fixed {
+            x: center.x.into(),
+            y: center.y.into(),
+            pivot: Alignment2D::CENTER,
+        };
+
+        let response = self.insert_floating_node(
+            uid,
+            position,
+            &Style::default(),
+            NodeContents::builder(contents),
+        )?;
+        Ok(response.result)
+    }
+
+    /// Opens the popup with the given UID that was shown using [`popup_auto`](Self::popup_auto).
+    #[inline]
+    pub fn open_popup(&mut self, uid: Uid) {
+        self.persistent_state_mut(uid)
+            .insert(PersistentStateKey::PopupOpen, true);
+    }
+
+    /// Closes the popup with the given UID that was shown using [`popup_auto`](Self::popup_auto).
+    #[inline]
+    pub fn close_popup(&mut self, uid: Uid) {
+        self.persistent_state_mut(uid)
+            .insert(PersistentStateKey::PopupOpen, false);
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_popup_open(&self, uid: Uid) -> bool {
+        self.persistent_state(uid)
+            .get::<bool>(PersistentStateKey::PopupOpen)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Shows a markdown document and returns the URL of the link that was clicked, if any.
+    #[cfg(feature = "markdown")]
+    #[track_caller]
+    #[inline]
+    pub fn markdown(&mut self, text: &str) -> WidgetResult<Option<String>> {
+        self.show(Markdown::new(text))
+    }
+
     #[track_caller]
     #[inline]
-    pub fn text_box(&mut self, text: &mut String) -> WidgetResult<()> {
+    pub fn text_box(&mut self, text: &mut String) -> WidgetResult<TextBoxResponse> {
         self.show(TextBox::new(text))
     }
 }
@@ -21,7 +21,7 @@ impl<'style, 'classes> Button<'_, 'style, 'classes> {
     #[must_use]
     #[inline]
     pub fn with_text<'text>(self, text: &'text str) -> Button<'text, 'style, 'classes> {
-        self.map_data(|data| ButtonData { text, ..data })
+        self.map_data(|_| ButtonData { text })
     }
 
     #[must_use]
@@ -32,6 +32,31 @@ impl<'style, 'classes> Button<'_, 'style, 'classes> {
     }
 }
 
+impl<'text, 'style, 'classes> Button<'text, 'style, 'classes> {
+    #[must_use]
+    #[inline]
+    pub fn with_icon<NR: rendering::NodeRenderer>(
+        self,
+        icon: NR,
+    ) -> IconButton<'text, 'style, 'classes, NR> {
+        self.map_data(|data| IconButtonData {
+            icon,
+            text: data.text,
+            placement: IconPlacement::default(),
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_icon_and_text<'new_text, NR: rendering::NodeRenderer>(
+        self,
+        icon: NR,
+        text: &'new_text str,
+    ) -> IconButton<'new_text, 'style, 'classes, NR> {
+        self.with_text(text).with_icon(icon)
+    }
+}
+
 impl WidgetData for ButtonData<'_> {
     #[inline]
     fn type_class(&self) -> StyleClass {
@@ -48,8 +73,17 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ButtonData<'_>
         uid: MaybeUid,
         style: Style,
     ) -> WidgetResult<Self::ShowResult> {
+        // centering the label on its cap height looks balanced regardless of the font's line gap
+        let style = style.or_else(&style! {
+            vertical_text_anchor: VerticalTextAnchor::CapHeight,
+        });
+
         Ok(gui
-            .insert_node(Some(uid.produce()), &style, NodeContents::text(self.text))?
+            .insert_node(
+                Some(uid.produce()),
+                &style,
+                NodeContents::text(self.text).with_keyboard_activation(),
+            )?
             .input_state)
     }
 }
@@ -80,7 +114,11 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for ContentBut
         style: Style,
         contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
     ) -> WidgetResult<Self::ShowResult<R>> {
-        gui.insert_node(Some(uid.produce()), &style, NodeContents::builder(contents))
+        gui.insert_node(
+            Some(uid.produce()),
+            &style,
+            NodeContents::builder(contents).with_keyboard_activation(),
+        )
     }
 }
 
@@ -125,8 +163,175 @@ where
             .insert_node(
                 Some(uid.produce()),
                 &style,
-                NodeContents::renderer(self.renderer),
+                NodeContents::renderer(self.renderer).with_keyboard_activation(),
             )?
             .input_state)
     }
 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IconPlacement {
+    #[default]
+    Leading,
+    Trailing,
+}
+
+pub struct IconButtonData<'text, NR: rendering::NodeRenderer> {
+    icon: NR,
+    text: &'text str,
+    placement: IconPlacement,
+}
+
+/// A button showing an icon next to an optional label.
+///
+/// The icon node uses the [`ICON_CLASS`](Self::ICON_CLASS) type class together with either
+/// [`LEADING_ICON_CLASS`](Self::LEADING_ICON_CLASS) or
+/// [`TRAILING_ICON_CLASS`](Self::TRAILING_ICON_CLASS), and is `1em` square unless styled otherwise.
+/// The label is a [`Label`] with the additional [`LABEL_CLASS`](Self::LABEL_CLASS).
+/// Both are styled with the input state of the button itself.
+pub type IconButton<'text, 'style, 'classes, NR> =
+    Widget<'style, 'classes, IconButtonData<'text, NR>>;
+
+impl<'text, 'style, 'classes, NR: rendering::NodeRenderer> IconButton<'text, 'style, 'classes, NR> {
+    pub const TYPE_CLASS: StyleClass = Button::TYPE_CLASS;
+    pub const ICON_CLASS: StyleClass = StyleClass::new_static("###button_icon");
+    pub const LEADING_ICON_CLASS: StyleClass = StyleClass::new_static("###button_icon_leading");
+    pub const TRAILING_ICON_CLASS: StyleClass = StyleClass::new_static("###button_icon_trailing");
+    pub const LABEL_CLASS: StyleClass = StyleClass::new_static("###button_label");
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(icon: NR) -> Self {
+        IconButtonData {
+            icon,
+            text: "",
+            placement: IconPlacement::default(),
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn text(&self) -> &str {
+        self.data().text
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_text<'new_text>(
+        self,
+        text: &'new_text str,
+    ) -> IconButton<'new_text, 'style, 'classes, NR> {
+        self.map_data(|data| IconButtonData { text, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn icon_placement(&self) -> IconPlacement {
+        self.data().placement
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_icon_placement(self, placement: IconPlacement) -> Self {
+        self.map_data(|data| IconButtonData { placement, ..data })
+    }
+}
+
+impl<NR: rendering::NodeRenderer> WidgetData for IconButtonData<'_, NR> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        Button::TYPE_CLASS
+    }
+}
+
+/// Renders the icon as if it was the button itself.
+struct ButtonIconRenderer<NR: rendering::NodeRenderer> {
+    icon: NR,
+    input_state: NodeInputState,
+}
+
+impl<NR: rendering::NodeRenderer> rendering::NodeRenderer for ButtonIconRenderer<NR> {
+    type Renderer = NR::Renderer;
+
+    #[inline]
+    fn render(
+        &self,
+        context: rendering::RenderContext<'_, Self::Renderer>,
+    ) -> Result<(), <Self::Renderer as rendering::Renderer>::Error> {
+        self.icon.render(rendering::RenderContext {
+            input_state: self.input_state,
+            ..context
+        })
+    }
+}
+
+impl<Renderer, NR> LeafWidgetData<Renderer> for IconButtonData<'_, NR>
+where
+    Renderer: rendering::Renderer,
+    NR: rendering::NodeRenderer<Renderer = Renderer>,
+{
+    type ShowResult = NodeInputState;
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let style = style.with_layout_direction(Direction::LeftToRight);
+
+        let response = gui.insert_node(
+            Some(uid.produce()),
+            &style,
+            NodeContents::builder(|mut gui| -> WidgetResult<()> {
+                let input_state = gui.parent_input_state();
+
+                let placement_class = match self.placement {
+                    IconPlacement::Leading => IconButton::<NR>::LEADING_ICON_CLASS,
+                    IconPlacement::Trailing => IconButton::<NR>::TRAILING_ICON_CLASS,
+                };
+                let icon_style = gui
+                    .theme()
+                    .build_style(None, &[placement_class], IconButton::<NR>::ICON_CLASS)
+                    .or_else(&style! {
+                        width: 1.em(),
+                        height: 1.em(),
+                        cross_axis_alignment: Alignment::Center,
+                    })
+                    .cascade(gui.parent_style(), input_state, gui.theme())
+                    .as_style();
+                let label_style = gui
+                    .theme()
+                    .build_style(None, &[IconButton::<NR>::LABEL_CLASS], Label::TYPE_CLASS)
+                    .or_else(&style! {
+                        cross_axis_alignment: Alignment::Center,
+                    })
+                    .cascade(gui.parent_style(), input_state, gui.theme())
+                    .as_style();
+
+                let icon = NodeContents::renderer(ButtonIconRenderer {
+                    icon: self.icon,
+                    input_state,
+                });
+
+                if self.placement == IconPlacement::Trailing && !self.text.is_empty() {
+                    gui.insert_node(None, &label_style, NodeContents::text(self.text))?;
+                }
+
+                gui.insert_node(None, &icon_style, icon)?;
+
+                if self.placement == IconPlacement::Leading && !self.text.is_empty() {
+                    gui.insert_node(None, &label_style, NodeContents::text(self.text))?;
+                }
+
+                Ok(())
+            })
+            .with_keyboard_activation(),
+        )?;
+
+        response.result?;
+        Ok(response.input_state)
+    }
+}
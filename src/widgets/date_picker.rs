@@ -0,0 +1,813 @@
+use super::*;
+use crate::input::*;
+use crate::theme::{ColorRole, StyleClass};
+use crate::*;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Identifies the parts of a date picker through [`Uid::from_type`].
+enum DateField {}
+enum OpenButton {}
+enum Calendar {}
+enum PreviousMonth {}
+enum NextMonth {}
+
+const PREVIOUS_MONTH: &str = "‹";
+const NEXT_MONTH: &str = "›";
+const OPEN_CALENDAR: &str = "▾";
+
+/// The calendar always shows six weeks, so its size doesn't change between months.
+const CALENDAR_WEEKS: usize = 6;
+
+/// The days from 0000-03-01 to 1970-01-01 in the proleptic Gregorian calendar.
+const UNIX_EPOCH_DAYS: i64 = 719_468;
+/// The days in a cycle of 400 years.
+const ERA_DAYS: i64 = 146_097;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Weekday {
+    #[default]
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Self; 7] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+        Self::Sunday,
+    ];
+
+    /// The weekday `days` after this one, going backwards for negative values.
+    #[must_use]
+    #[inline]
+    pub fn add_days(self, days: i64) -> Self {
+        Self::ALL[(self as i64 + days).rem_euclid(7) as usize]
+    }
+
+    /// The two letter English abbreviation, shown above the columns of the calendar by default.
+    #[must_use]
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Self::Monday => "Mo",
+            Self::Tuesday => "Tu",
+            Self::Wednesday => "We",
+            Self::Thursday => "Th",
+            Self::Friday => "Fr",
+            Self::Saturday => "Sa",
+            Self::Sunday => "Su",
+        }
+    }
+}
+
+/// A day in the proleptic Gregorian calendar, with `month` and `day` counting from 1.
+///
+/// Dates are ordered chronologically and formatted as `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    #[must_use]
+    #[inline]
+    pub const fn new(year: i32, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_leap_year(year: i32) -> bool {
+        ((year % 4 == 0) && (year % 100 != 0)) || (year % 400 == 0)
+    }
+
+    /// The number of days in `month` of `year`, 0 if the month doesn't exist.
+    #[must_use]
+    pub const fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    /// Whether the month and day exist, e.g. `2023-02-29` doesn't.
+    #[must_use]
+    #[inline]
+    pub const fn is_valid(self) -> bool {
+        (self.day >= 1) && (self.day <= Self::days_in_month(self.year, self.month))
+    }
+
+    /// The current day in UTC.
+    #[must_use]
+    pub fn today() -> Self {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_secs() as i64,
+            Err(before_epoch) => -(before_epoch.duration().as_secs() as i64),
+        };
+        Self::from_unix_days(seconds.div_euclid(86_400))
+    }
+
+    /// Parses a date formatted as `YYYY-MM-DD`, [`None`] if the text isn't a valid date.
+    #[must_use]
+    pub fn from_iso(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let mut next_part = |length: usize| {
+            parts
+                .next()
+                .filter(|part| (part.len() == length) && part.bytes().all(|b| b.is_ascii_digit()))
+        };
+
+        let year = next_part(4)?.parse().ok()?;
+        let month = next_part(2)?.parse().ok()?;
+        let day = next_part(2)?.parse().ok()?;
+        Some(Self { year, month, day }).filter(|date| date.is_valid())
+    }
+
+    #[must_use]
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        Weekday::Thursday.add_days(self.to_unix_days())
+    }
+
+    /// The date `days` after this one, going backwards for negative values.
+    #[must_use]
+    #[inline]
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_unix_days(self.to_unix_days() + days)
+    }
+
+    /// The same day `months` after this one, going backwards for negative values. Days past the
+    /// end of the target month are moved to its last day.
+    #[must_use]
+    pub fn add_months(self, months: i32) -> Self {
+        let month_index = self.year as i64 * 12 + self.month as i64 - 1 + months as i64;
+        let year = month_index.div_euclid(12) as i32;
+        let month = month_index.rem_euclid(12) as u8 + 1;
+        let day = self.day.clamp(1, Self::days_in_month(year, month));
+        Self { year, month, day }
+    }
+
+    /// The first day of the month of this date.
+    #[must_use]
+    #[inline]
+    pub const fn first_of_month(self) -> Self {
+        Self { day: 1, ..self }
+    }
+
+    /// The nearest valid date, moving the month into `1..=12` and the day into the month.
+    #[must_use]
+    fn normalized(self) -> Self {
+        let month = self.month.clamp(1, 12);
+        let day = self.day.clamp(1, Self::days_in_month(self.year, month));
+        Self {
+            year: self.year,
+            month,
+            day,
+        }
+    }
+
+    /// The days since 1970-01-01, based on the algorithm by Howard Hinnant.
+    #[must_use]
+    fn to_unix_days(self) -> i64 {
+        let Self { year, month, day } = self.normalized();
+        let (month, day) = (month as i64, day as i64);
+        let year = year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * ERA_DAYS + day_of_era - UNIX_EPOCH_DAYS
+    }
+
+    #[must_use]
+    fn from_unix_days(days: i64) -> Self {
+        let days = days + UNIX_EPOCH_DAYS;
+        let era = days.div_euclid(ERA_DAYS);
+        let day_of_era = days - era * ERA_DAYS;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (year_of_era * 365 + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (day_of_year * 5 + 2) / 153;
+        let day = day_of_year - (shifted_month * 153 + 2) / 5 + 1;
+        let month = (shifted_month + 2) % 12 + 1;
+        let year = year_of_era + era * 400 + (month <= 2) as i64;
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Date {
+    fn from(date: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+
+        Self {
+            year: date.year(),
+            month: date.month() as u8,
+            day: date.day() as u8,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Date {
+    /// [`None`] if the date is invalid or out of the range `chrono` supports.
+    #[must_use]
+    #[inline]
+    pub fn to_naive_date(self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::from_ymd_opt(self.year, self.month.into(), self.day.into())
+    }
+}
+
+pub struct DatePickerData<'date> {
+    date: &'date mut Date,
+    min: Option<Date>,
+    max: Option<Date>,
+    first_weekday: Weekday,
+    today: Option<Date>,
+}
+
+/// A text field showing a date with a button that opens a calendar of its month.
+///
+/// The field accepts dates typed in the format of the [formatter](ByorGui::set_formatter),
+/// `YYYY-MM-DD` by default, taken over on <kbd>Enter</kbd> or when it loses focus. In the calendar, the arrow keys move a day or a week, <kbd>Page Up</kbd> and
+/// <kbd>Page Down</kbd> move a month, and clicking a day or pressing <kbd>Enter</kbd> picks it.
+/// Days outside of the minimum and maximum are disabled.
+pub type DatePicker<'date, 'style, 'classes> = Widget<'style, 'classes, DatePickerData<'date>>;
+
+impl<'date> DatePicker<'date, '_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###date_picker");
+    /// The text field showing the date.
+    pub const FIELD_CLASS: StyleClass = StyleClass::new_static("###date_picker_field");
+    /// The row above the calendar with the month and the navigation buttons.
+    pub const HEADER_CLASS: StyleClass = StyleClass::new_static("###date_picker_header");
+    /// The buttons moving the calendar to the previous and next month.
+    pub const NAV_BUTTON_CLASS: StyleClass = StyleClass::new_static("###date_picker_nav_button");
+    /// The names of the weekdays above the columns of the calendar.
+    pub const WEEKDAY_CLASS: StyleClass = StyleClass::new_static("###date_picker_weekday");
+    pub const CELL_CLASS: StyleClass = StyleClass::new_static("###date_picker_cell");
+    /// Replaces the cell style of the current day. Without a style for this class the cell gets
+    /// a [`ColorRole::Accent`] border.
+    pub const TODAY_CELL_CLASS: StyleClass = StyleClass::new_static("###date_picker_today_cell");
+    /// Replaces the cell style of the picked day. Without a style for this class the cell gets
+    /// the [`ColorRole::Accent`] background.
+    pub const SELECTED_CELL_CLASS: StyleClass =
+        StyleClass::new_static("###date_picker_selected_cell");
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(date: &'date mut Date) -> Self {
+        DatePickerData {
+            date,
+            min: None,
+            max: None,
+            first_weekday: Weekday::Monday,
+            today: None,
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn min(&self) -> Option<Date> {
+        self.data().min
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_min(self, min: Date) -> Self {
+        self.map_data(|data| DatePickerData {
+            min: Some(min),
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn max(&self) -> Option<Date> {
+        self.data().max
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_max(self, max: Date) -> Self {
+        self.map_data(|data| DatePickerData {
+            max: Some(max),
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn first_weekday(&self) -> Weekday {
+        self.data().first_weekday
+    }
+
+    /// The weekday of the first column of the calendar, [`Weekday::Monday`] by default.
+    #[must_use]
+    #[inline]
+    pub fn with_first_weekday(self, first_weekday: Weekday) -> Self {
+        self.map_data(|data| DatePickerData {
+            first_weekday,
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn today(&self) -> Option<Date> {
+        self.data().today
+    }
+
+    /// The day highlighted as the current one, [`Date::today`] by default. Set this to show the
+    /// current day in a time zone other than UTC.
+    #[must_use]
+    #[inline]
+    pub fn with_today(self, today: Date) -> Self {
+        self.map_data(|data| DatePickerData {
+            today: Some(today),
+            ..data
+        })
+    }
+}
+
+impl WidgetData for DatePickerData<'_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        DatePicker::TYPE_CLASS
+    }
+}
+
+/// The dates a date picker allows, with the bounds made valid.
+#[derive(Clone, Copy)]
+struct DateRange {
+    min: Option<Date>,
+    max: Option<Date>,
+}
+
+impl DateRange {
+    #[must_use]
+    fn new(min: Option<Date>, max: Option<Date>) -> Self {
+        let min = min.map(Date::normalized);
+        let max = max.map(|max| {
+            let max = max.normalized();
+            min.map_or(max, |min| max.max(min))
+        });
+        Self { min, max }
+    }
+
+    #[must_use]
+    fn contains(self, date: Date) -> bool {
+        self.min.is_none_or(|min| date >= min) && self.max.is_none_or(|max| date <= max)
+    }
+
+    #[must_use]
+    fn clamp(self, date: Date) -> Date {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+}
+
+/// What was done in the calendar during this frame.
+enum CalendarAction {
+    Pick(Date),
+    Navigate(Date),
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for DatePickerData<'_> {
+    type ShowResult = bool;
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let uid = uid.produce();
+        let style = style.or_else(&style! {
+            child_spacing: 0.25.em(),
+        });
+        let field_style = gui
+            .theme()
+            .build_style(None, &[DatePicker::FIELD_CLASS], TextBox::TYPE_CLASS)
+            .or_else(&style! {
+                width: 6.em(),
+            });
+        let nav_button_style =
+            gui.theme()
+                .build_style(None, &[DatePicker::NAV_BUTTON_CLASS], Button::TYPE_CLASS);
+        let header_style = gui
+            .theme()
+            .build_style(None, &[], DatePicker::HEADER_CLASS)
+            .or_else(&style! {
+                width: Sizing::Grow,
+                padding: 0.px(),
+            });
+        let title_style = style! {
+            width: Sizing::Grow,
+            horizontal_text_alignment: HorizontalTextAlignment::Center,
+        };
+        let weekday_style = gui
+            .theme()
+            .build_style(None, &[DatePicker::WEEKDAY_CLASS], Label::TYPE_CLASS)
+            .or_else(&style! {
+                width: 2.em(),
+                text_wrap: false,
+                horizontal_text_alignment: HorizontalTextAlignment::Center,
+            });
+        let cell_style = gui
+            .theme()
+            .build_style(None, &[DatePicker::CELL_CLASS], Button::TYPE_CLASS)
+            .or_else(&style! {
+                width: 2.em(),
+                text_wrap: false,
+                horizontal_text_alignment: HorizontalTextAlignment::Center,
+            });
+        let today_cell_style = gui.theme().class_style_or(
+            &DatePicker::TODAY_CELL_CLASS,
+            || {
+                style! {
+                    border_width: 1.px(),
+                    border_color: ColorRole::Accent,
+                }
+            },
+            &cell_style,
+        );
+        let selected_cell_style = gui.theme().class_style_or(
+            &DatePicker::SELECTED_CELL_CLASS,
+            || {
+                style! {
+                    background: ColorRole::Accent,
+                }
+            },
+            &cell_style,
+        );
+        let disabled_cell_style = cell_style.clone().with_enabled(false);
+        let grid_style = style! {
+            layout_direction: Direction::TopToBottom,
+        };
+
+        let range = DateRange::new(self.min, self.max);
+        let first_weekday = self.first_weekday;
+        let today = self.today.unwrap_or_else(Date::today);
+        let initial_date = self.date.normalized();
+        let mut date = initial_date;
+
+        // the UIDs of the day cells are needed to know whether the calendar has the focus
+        let scope_uid = gui.compute_recursive_uid(uid);
+        let field_uid = scope_uid.concat(Uid::from_type::<DateField>());
+        let button_uid = scope_uid.concat(Uid::from_type::<OpenButton>());
+        let cell_uid = |day: u8| scope_uid.concat(Uid::new(usize::from(day)));
+
+        // the typed text is still needed during the frame the field loses focus to take it over
+        let field_edited = (gui.data.focused_node == Some(field_uid))
+            || (gui.data.previous_focused_node == Some(field_uid));
+        let mut text = gui
+            .persistent_state(uid)
+            .get::<String>(PersistentStateKey::DatePickerText)
+            .filter(|_| field_edited)
+            .cloned()
+            .unwrap_or_else(|| gui.formatter().format_date(date));
+        let mut cursor = gui
+            .persistent_state(uid)
+            .get::<Date>(PersistentStateKey::DatePickerCursor)
+            .copied()
+            .unwrap_or(date);
+
+        let contents = NodeContents::builder(|mut gui| -> WidgetResult<()> {
+            gui.uid_scope(uid, |gui| -> WidgetResult<()> {
+                let enabled = gui.computed_parent_style().enabled();
+                let calendar_uid = Uid::from_type::<Calendar>();
+
+                // besides digits, only what the formatter puts between them can be typed
+                let separators: String = gui
+                    .formatter()
+                    .format_date(date)
+                    .chars()
+                    .filter(|c| !c.is_ascii_digit())
+                    .collect();
+                let field = TextBox::new(&mut text)
+                    .with_uid(Uid::from_type::<DateField>())
+                    .with_style(&field_style)
+                    .with_single_line(true)
+                    .with_filter(TextFilter::new(|text| {
+                        text.chars()
+                            .all(|c| c.is_ascii_digit() || separators.contains(c))
+                    }));
+                let field_response = gui.show(field)?;
+                if field_response.submitted || field_response.focus_lost {
+                    if let Some(typed) = gui.formatter().parse_date(&text)
+                        && range.contains(typed)
+                    {
+                        date = typed;
+                    }
+                    text = gui.formatter().format_date(date);
+                }
+
+                let open_button = Button::default()
+                    .with_text(OPEN_CALENDAR)
+                    .with_uid(Uid::from_type::<OpenButton>());
+                let mut opening = false;
+                if gui.show(open_button)?.clicked(MouseButtons::PRIMARY) && enabled {
+                    if gui.is_popup_open(calendar_uid) {
+                        gui.close_popup(calendar_uid);
+                    } else {
+                        gui.open_popup(calendar_uid);
+                        cursor = range.clamp(date);
+                        opening = true;
+                    }
+                }
+
+                let position = FloatPosition::Popup {
+                    x: PopupPosition::ParentStart,
+                    y: PopupPosition::AfterParent,
+                };
+                let action = gui.popup_auto(calendar_uid, position, |mut gui| {
+                    // the focus is moved into the calendar only once the popup's focus trap
+                    // remembers the button, so it returns there when the calendar is closed
+                    if opening {
+                        gui.data.focused_node = Some(cell_uid(cursor.day));
+                    }
+
+                    show_calendar(
+                        &mut gui,
+                        CalendarContext {
+                            date,
+                            cursor,
+                            today,
+                            range,
+                            first_weekday,
+                            cell_uid: &cell_uid,
+                        },
+                        CalendarStyles {
+                            grid: &grid_style,
+                            header: &header_style,
+                            title: &title_style,
+                            nav_button: &nav_button_style,
+                            weekday: &weekday_style,
+                            cell: &cell_style,
+                            today_cell: &today_cell_style,
+                            selected_cell: &selected_cell_style,
+                            disabled_cell: &disabled_cell_style,
+                        },
+                    )
+                })?;
+
+                match action.transpose()?.flatten() {
+                    Some(CalendarAction::Pick(picked)) => {
+                        date = picked;
+                        cursor = picked;
+                        text = gui.formatter().format_date(date);
+                        gui.close_popup(calendar_uid);
+                        gui.data.focused_node = Some(button_uid);
+                    }
+                    Some(CalendarAction::Navigate(moved)) => cursor = moved,
+                    None => (),
+                }
+
+                Ok(())
+            })
+        })
+        .with_hit_target(HitTarget::Subtree);
+
+        gui.insert_node(Some(uid), &style, contents)?.result?;
+
+        gui.persistent_state_mut(uid)
+            .insert(PersistentStateKey::DatePickerText, text);
+        gui.persistent_state_mut(uid)
+            .insert(PersistentStateKey::DatePickerCursor, cursor);
+
+        *self.date = date;
+        Ok(date != initial_date)
+    }
+}
+
+struct CalendarContext<'a> {
+    date: Date,
+    cursor: Date,
+    today: Date,
+    range: DateRange,
+    first_weekday: Weekday,
+    cell_uid: &'a dyn Fn(u8) -> Uid,
+}
+
+struct CalendarStyles<'a> {
+    grid: &'a Style,
+    header: &'a Style,
+    title: &'a Style,
+    nav_button: &'a Style,
+    weekday: &'a Style,
+    cell: &'a Style,
+    today_cell: &'a Style,
+    selected_cell: &'a Style,
+    disabled_cell: &'a Style,
+}
+
+/// Shows the month of the cursor with the days in its weeks, and handles the keys while one of
+/// the days is focused.
+fn show_calendar<Renderer: rendering::Renderer>(
+    gui: &mut ByorGuiContext<'_, Renderer>,
+    context: CalendarContext<'_>,
+    styles: CalendarStyles<'_>,
+) -> WidgetResult<Option<CalendarAction>> {
+    let CalendarContext {
+        date,
+        mut cursor,
+        today,
+        range,
+        first_weekday,
+        cell_uid,
+    } = context;
+
+    let mut action = None;
+
+    // keys are handled first, so the cells move with the cursor within the same frame
+    let days_in_month = Date::days_in_month(cursor.year, cursor.month);
+    let grid_focused = (1..=days_in_month).any(|day| gui.data.focused_node == Some(cell_uid(day)));
+    if grid_focused {
+        let mirrored = gui.parent_style().ui_direction == UiDirection::Rtl;
+        let (previous_day, next_day) = if mirrored {
+            (NamedKey::ArrowRight, NamedKey::ArrowLeft)
+        } else {
+            (NamedKey::ArrowLeft, NamedKey::ArrowRight)
+        };
+        let mut moved = cursor;
+
+        // repeated presses move further, so a held key keeps moving the cursor
+        gui.global_input_state_mut().retain_key_events(|event| {
+            let KeyEvent::Pressed { key, .. } = event else {
+                return true;
+            };
+            moved = match key {
+                Key::Named(key) if *key == previous_day => moved.add_days(-1),
+                Key::Named(key) if *key == next_day => moved.add_days(1),
+                Key::Named(NamedKey::ArrowUp) => moved.add_days(-7),
+                Key::Named(NamedKey::ArrowDown) => moved.add_days(7),
+                Key::Named(NamedKey::PageUp) => moved.add_months(-1),
+                Key::Named(NamedKey::PageDown) => moved.add_months(1),
+                Key::Named(NamedKey::Enter) => {
+                    action = Some(CalendarAction::Pick(moved));
+                    return false;
+                }
+                _ => return true,
+            };
+            moved = range.clamp(moved);
+            false
+        });
+
+        if action.is_none() && (moved != cursor) {
+            cursor = moved;
+            action = Some(CalendarAction::Navigate(moved));
+            gui.data.focused_node = Some(cell_uid(moved.day));
+        }
+    }
+
+    let first_of_month = cursor.first_of_month();
+    let last_of_month = first_of_month.add_months(1).add_days(-1);
+    let offset = (first_of_month.weekday() as i64 - first_weekday as i64).rem_euclid(7);
+    let first_shown = first_of_month.add_days(-offset);
+
+    gui.insert_node(
+        None,
+        styles.grid,
+        NodeContents::builder(|mut gui| -> WidgetResult<()> {
+            let header = gui.insert_node(
+                None,
+                styles.header,
+                NodeContents::builder(|mut gui| -> WidgetResult<Option<Date>> {
+                    // a month can be shown if its nearest day to the current one is in range
+                    let nav_button = |gui: &mut ByorGuiContext<'_, Renderer>,
+                                      uid: Uid,
+                                      text: &str,
+                                      nearest_day: Date,
+                                      months: i32| {
+                        let enabled = range.contains(nearest_day);
+                        let style = styles.nav_button.clone().with_enabled(enabled);
+                        let button = Button::default()
+                            .with_text(text)
+                            .with_uid(uid)
+                            .with_style(&style);
+                        let clicked = gui.show(button)?.clicked(MouseButtons::PRIMARY) && enabled;
+                        WidgetResult::Ok(clicked.then(|| range.clamp(cursor.add_months(months))))
+                    };
+
+                    let previous = nav_button(
+                        &mut gui,
+                        Uid::from_type::<PreviousMonth>(),
+                        PREVIOUS_MONTH,
+                        first_of_month.add_days(-1),
+                        -1,
+                    )?;
+                    let title = gui.formatter().format_month(cursor.year, cursor.month);
+                    gui.show(Label::default().with_text(&title).with_style(styles.title))?;
+                    let next = nav_button(
+                        &mut gui,
+                        Uid::from_type::<NextMonth>(),
+                        NEXT_MONTH,
+                        last_of_month.add_days(1),
+                        1,
+                    )?;
+
+                    Ok(previous.or(next))
+                }),
+            )?;
+            if let Some(moved) = header.result? {
+                action = Some(CalendarAction::Navigate(moved));
+            }
+
+            let row_style = Style::DEFAULT;
+            gui.insert_node(
+                None,
+                &row_style,
+                NodeContents::builder(|mut gui| -> WidgetResult<()> {
+                    for column in 0..7 {
+                        let weekday = gui
+                            .formatter()
+                            .format_weekday(first_weekday.add_days(column));
+                        let label = Label::default()
+                            .with_text(&weekday)
+                            .with_style(styles.weekday);
+                        gui.show(label)?;
+                    }
+                    Ok(())
+                }),
+            )?
+            .result?;
+
+            for week in 0..CALENDAR_WEEKS {
+                gui.insert_node(
+                    None,
+                    &row_style,
+                    NodeContents::builder(|mut gui| -> WidgetResult<()> {
+                        for column in 0..7 {
+                            let day = first_shown.add_days((week * 7 + column) as i64);
+                            let in_month = (day.year == cursor.year) && (day.month == cursor.month);
+                            let style = if !in_month || !range.contains(day) {
+                                styles.disabled_cell
+                            } else if day == date {
+                                styles.selected_cell
+                            } else if day == today {
+                                styles.today_cell
+                            } else {
+                                styles.cell
+                            };
+
+                            // only days of the shown month can be focused, and only the cursor is a
+                            // tab stop, so Tab leaves the calendar instead of visiting every day
+                            let cell_uid = in_month.then(|| Uid::new(usize::from(day.day)));
+                            let tab_index = if day == cursor { 0 } else { -1 };
+                            let text = gui.formatter().format_number(day.day.into(), 0);
+                            let response = gui.insert_node(
+                                cell_uid,
+                                style,
+                                NodeContents::text(&text)
+                                    .with_hit_target(HitTarget::SelfOnly)
+                                    .with_tab_index(tab_index),
+                            )?;
+
+                            if in_month
+                                && range.contains(day)
+                                && response.input_state.clicked(MouseButtons::PRIMARY)
+                            {
+                                action = Some(CalendarAction::Pick(day));
+                            }
+                        }
+                        Ok(())
+                    }),
+                )?
+                .result?;
+            }
+
+            Ok(())
+        }),
+    )?
+    .result?;
+
+    Ok(action)
+}
@@ -0,0 +1,273 @@
+use super::*;
+use crate::input::*;
+use crate::theme::{ColorRole, StyleClass};
+use crate::*;
+
+/// The style classes of the dialogs shown by [`ByorGuiContext::confirm_dialog`] and
+/// [`ByorGuiContext::message_dialog`].
+pub struct Dialog;
+
+impl Dialog {
+    pub const SURFACE_CLASS: StyleClass = StyleClass::new_static("###dialog");
+    pub const TITLE_CLASS: StyleClass = StyleClass::new_static("###dialog_title");
+    pub const MESSAGE_CLASS: StyleClass = StyleClass::new_static("###dialog_message");
+    pub const BUTTON_ROW_CLASS: StyleClass = StyleClass::new_static("###dialog_buttons");
+    /// Applied on top of the button style to the confirm button of dialogs with
+    /// [`ConfirmOptions::danger`] set. Without a style for this class the button gets the
+    /// [`ColorRole::Danger`] background.
+    pub const DANGER_BUTTON_CLASS: StyleClass = StyleClass::new_static("###dialog_danger_button");
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DialogChoice {
+    Confirmed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmOptions<'text> {
+    /// Not shown if empty
+    pub title: &'text str,
+    pub message: &'text str,
+    pub confirm_label: &'text str,
+    pub cancel_label: &'text str,
+    /// Whether confirming can't be undone, like deleting something
+    pub danger: bool,
+    /// Whether <kbd>Esc</kbd> cancels the dialog, on by default
+    pub dismissable: bool,
+}
+
+impl Default for ConfirmOptions<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            title: "",
+            message: "",
+            confirm_label: "OK",
+            cancel_label: "Cancel",
+            danger: false,
+            dismissable: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MessageOptions<'text> {
+    /// Not shown if empty
+    pub title: &'text str,
+    pub message: &'text str,
+    pub dismiss_label: &'text str,
+    /// Whether <kbd>Esc</kbd> dismisses the message, on by default
+    pub dismissable: bool,
+}
+
+impl Default for MessageOptions<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            title: "",
+            message: "",
+            dismiss_label: "OK",
+            dismissable: true,
+        }
+    }
+}
+
+const ENTER: Shortcut = Shortcut {
+    modifiers: Modifiers::empty(),
+    key: Key::Named(NamedKey::Enter),
+    location: None,
+};
+
+struct ConfirmButton;
+struct CancelButton;
+
+/// A button of a dialog and the choice it makes.
+struct DialogButton<'text> {
+    label: &'text str,
+    choice: DialogChoice,
+    danger: bool,
+}
+
+impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    /// Shows a dialog asking to confirm an action while `open` is set. On the frame a choice is
+    /// made the dialog closes and the choice is returned.
+    ///
+    /// <kbd>Enter</kbd> confirms unless a focused button handles it, <kbd>Esc</kbd> cancels if
+    /// the dialog is [`dismissable`](ConfirmOptions::dismissable) and no popup is open above it.
+    /// <kbd>Tab</kbd> only moves the focus between the buttons of the dialog. The dialog is
+    /// centered on the screen, but doesn't stop the nodes underneath it from being hovered. Its
+    /// UID is derived from the caller location.
+    #[track_caller]
+    pub fn confirm_dialog(
+        &mut self,
+        open: &mut bool,
+        options: ConfirmOptions<'_>,
+    ) -> WidgetResult<Option<DialogChoice>> {
+        let uid = Uid::from_location();
+        let buttons = [
+            DialogButton {
+                label: options.cancel_label,
+                choice: DialogChoice::Cancelled,
+                danger: false,
+            },
+            DialogButton {
+                label: options.confirm_label,
+                choice: DialogChoice::Confirmed,
+                danger: options.danger,
+            },
+        ];
+        self.dialog(
+            uid,
+            open,
+            options.title,
+            options.message,
+            options.dismissable,
+            &buttons,
+        )
+    }
+
+    /// Shows a message with a single button while `open` is set. Returns whether the message was
+    /// dismissed this frame, either with the button, <kbd>Enter</kbd> or, if it is
+    /// [`dismissable`](MessageOptions::dismissable), <kbd>Esc</kbd>.
+    ///
+    /// Like [`confirm_dialog`](Self::confirm_dialog), the UID is derived from the caller location.
+    #[track_caller]
+    pub fn message_dialog(
+        &mut self,
+        open: &mut bool,
+        options: MessageOptions<'_>,
+    ) -> WidgetResult<bool> {
+        let uid = Uid::from_location();
+        let buttons = [DialogButton {
+            label: options.dismiss_label,
+            choice: DialogChoice::Confirmed,
+            danger: false,
+        }];
+        let choice = self.dialog(
+            uid,
+            open,
+            options.title,
+            options.message,
+            options.dismissable,
+            &buttons,
+        )?;
+        Ok(choice.is_some())
+    }
+
+    fn dialog(
+        &mut self,
+        uid: Uid,
+        open: &mut bool,
+        title: &str,
+        message: &str,
+        dismissable: bool,
+        buttons: &[DialogButton<'_>],
+    ) -> WidgetResult<Option<DialogChoice>> {
+        if !*open {
+            return Ok(None);
+        }
+
+        let surface_style = self
+            .theme()
+            .build_style(None, &[], Dialog::SURFACE_CLASS)
+            .or_else(&style! {
+                layout_direction: Direction::TopToBottom,
+                padding: 12.pt(),
+                child_spacing: 8.pt(),
+                background: ColorRole::Surface,
+                text_color: ColorRole::OnSurface,
+            });
+        let button_row_style = self
+            .theme()
+            .build_style(None, &[], Dialog::BUTTON_ROW_CLASS)
+            .or_else(&style! {
+                width: Sizing::Grow,
+                child_alignment: Alignment::End,
+                child_spacing: 8.pt(),
+            });
+        let danger_style = self.theme().class_style_or(
+            &Dialog::DANGER_BUTTON_CLASS,
+            || {
+                style! {
+                    background: ColorRole::Danger,
+                }
+            },
+            &Style::DEFAULT,
+        );
+        let screen_center = self.data.screen_size / 2.0;
+        let position = FloatPosition::Fixed {
+            x: screen_center.x.into(),
+            y: screen_center.y.into(),
+            pivot: Alignment2D::CENTER,
+        };
+
+        self.register_escapable(uid, Escapable::Modal { dismissable });
+        let response = self.insert_floating_node(
+            uid,
+            position,
+            &surface_style,
+            NodeContents::builder(|mut gui| -> WidgetResult<Option<DialogChoice>> {
+                gui.uid_scope(uid, |gui| {
+                    if !title.is_empty() {
+                        gui.show(
+                            Label::default()
+                                .with_text(title)
+                                .with_classes(&[Dialog::TITLE_CLASS]),
+                        )?;
+                    }
+                    gui.show(
+                        Label::default()
+                            .with_text(message)
+                            .with_classes(&[Dialog::MESSAGE_CLASS]),
+                    )?;
+
+                    gui.insert_node(
+                        None,
+                        &button_row_style,
+                        NodeContents::builder(|mut gui| -> WidgetResult<Option<DialogChoice>> {
+                            let mut choice = None;
+                            for button in buttons {
+                                let button_uid = match button.choice {
+                                    DialogChoice::Confirmed => Uid::from_type::<ConfirmButton>(),
+                                    DialogChoice::Cancelled => Uid::from_type::<CancelButton>(),
+                                };
+                                let mut widget = Button::default()
+                                    .with_text(button.label)
+                                    .with_uid(button_uid);
+                                if button.danger {
+                                    widget = widget.with_style(&danger_style);
+                                }
+
+                                if gui.show(widget)?.clicked(MouseButtons::PRIMARY) {
+                                    choice = Some(button.choice);
+                                }
+                            }
+                            Ok(choice)
+                        }),
+                    )?
+                    .result
+                })
+            })
+            .with_focus_trap(),
+        )?;
+        let mut choice = response.result?;
+
+        if self.escaped(uid) {
+            choice = choice.or(Some(DialogChoice::Cancelled));
+        }
+        // checked after the buttons, so a focused button handles Enter itself
+        if self.global_input_state_mut().consume_shortcut(&ENTER) {
+            choice = choice.or(Some(DialogChoice::Confirmed));
+        }
+        // a message dialog has only one outcome
+        if buttons.len() == 1 {
+            choice = choice.map(|_| buttons[0].choice);
+        }
+
+        if choice.is_some() {
+            *open = false;
+        }
+        Ok(choice)
+    }
+}
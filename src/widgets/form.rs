@@ -0,0 +1,157 @@
+use super::*;
+use crate::theme::StyleClass;
+use crate::*;
+
+/// The label column of the form that is currently being built.
+pub(crate) struct FormScope {
+    /// The width of the widest label in the last frame
+    label_width: Float<Pixel>,
+    label_uids: Vec<Uid>,
+}
+
+#[derive(Default)]
+pub struct FormData;
+
+/// A container whose [`form_row`](ByorGuiContext::form_row)s share the width of their label
+/// column.
+///
+/// The width is the one of the widest label in the last frame, so when labels change the column
+/// catches up one frame later.
+pub type Form<'style, 'classes> = Widget<'style, 'classes, FormData>;
+
+impl Form<'_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###form");
+    pub const ROW_CLASS: StyleClass = StyleClass::new_static("###form_row");
+    pub const LABEL_CLASS: StyleClass = StyleClass::new_static("###form_label");
+}
+
+impl WidgetData for FormData {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        Form::TYPE_CLASS
+    }
+}
+
+impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for FormData {
+    type ShowResult<T> = T;
+
+    fn show<R>(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+        contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
+    ) -> WidgetResult<Self::ShowResult<R>> {
+        let uid = uid.produce();
+
+        let label_width = gui
+            .persistent_state(uid)
+            .get::<Vec<Uid>>(PersistentStateKey::FormLabels)
+            .into_iter()
+            .flatten()
+            .filter_map(|&label_uid| gui.data.previous_state.get(label_uid))
+            .map(|state| state.size.x)
+            .fold(0.px(), Float::max);
+
+        let style = style.or_else(&Style::default().with_layout_direction(Direction::TopToBottom));
+
+        gui.data.form_stack.push(FormScope {
+            label_width,
+            label_uids: Vec::new(),
+        });
+        let response = gui.insert_node(Some(uid), &style, NodeContents::builder(contents));
+        // popped even if inserting failed, so rows after the form don't align with it
+        let scope = gui.data.form_stack.pop();
+
+        if let Some(scope) = scope {
+            gui.persistent_state_mut(uid)
+                .insert(PersistentStateKey::FormLabels, scope.label_uids);
+        }
+
+        Ok(response?.result)
+    }
+}
+
+struct FormRowLabel;
+
+impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    /// Shows a row with a label cell on the leading side and `contents` in a cell that fills the
+    /// rest of the row. Inside a [`Form`] the label cells of all rows have the same width.
+    ///
+    /// The UID of the row is derived from the label.
+    #[track_caller]
+    pub fn form_row<R>(
+        &mut self,
+        label: &str,
+        contents: impl FnOnce(ByorGuiContext<'_, Renderer>) -> R,
+    ) -> WidgetResult<R> {
+        let row_uid = Uid::from_slice(label.as_bytes());
+        let label_uid = row_uid.concat(Uid::from_type::<FormRowLabel>());
+
+        let absolute_label_uid = self.compute_recursive_uid(label_uid);
+        let label_width = match self.data.form_stack.last_mut() {
+            Some(scope) => {
+                scope.label_uids.push(absolute_label_uid);
+                scope.label_width
+            }
+            None => 0.px(),
+        };
+
+        let row_style = self
+            .theme()
+            .build_style(None, &[], Form::ROW_CLASS)
+            .or_else(&style! {
+                width: Sizing::Grow,
+                layout_direction: Direction::LeftToRight,
+                cross_axis_alignment: Alignment::Center,
+            });
+        let label_style = self
+            .theme()
+            .build_style(None, &[], Form::LABEL_CLASS)
+            .or_else(&style! {
+                child_alignment: Alignment::End,
+            });
+        // The label text itself is measured, so it must not be stretched to the column width
+        let label_column_style = style! {
+            min_width: label_width,
+            padding: 0.px(),
+            child_alignment: %inherit,
+        };
+        let control_style = style! {
+            width: Sizing::Grow,
+            padding: 0.px(),
+        };
+
+        let response = self.insert_node(
+            Some(row_uid),
+            &row_style,
+            NodeContents::builder(|mut gui| -> WidgetResult<R> {
+                gui.insert_node(
+                    None,
+                    &label_style,
+                    NodeContents::builder(|mut gui| {
+                        gui.insert_node(
+                            None,
+                            &label_column_style,
+                            NodeContents::builder(|mut gui| {
+                                gui.insert_node(
+                                    Some(label_uid),
+                                    &Style::default(),
+                                    NodeContents::text(label),
+                                )
+                                .map(|_| ())
+                            }),
+                        )?
+                        .result
+                    }),
+                )?
+                .result?;
+
+                let response =
+                    gui.insert_node(None, &control_style, NodeContents::builder(contents))?;
+                Ok(response.result)
+            }),
+        )?;
+        response.result
+    }
+}
@@ -0,0 +1,277 @@
+use super::*;
+use crate::animation::AnimationConfig;
+use crate::rendering::Path;
+use crate::theme::StyleClass;
+use crate::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::marker::PhantomData;
+
+/// What the gauge shows in its center, with the number of fractional digits.
+#[derive(Clone, Copy)]
+enum ValueText {
+    Value(usize),
+    Percent(usize),
+}
+
+pub struct GaugeData {
+    value: f32,
+    min: f32,
+    max: f32,
+    start_angle: f32,
+    sweep: f32,
+    thickness: f32,
+    value_text: Option<ValueText>,
+    animation: Option<AnimationConfig>,
+}
+
+/// A circular gauge that fills an arc according to its value.
+///
+/// The filled part of the arc is drawn in the `selection_color` of the style and the track behind
+/// it in the `border_color`.
+pub type Gauge<'style, 'classes> = Widget<'style, 'classes, GaugeData>;
+
+impl Gauge<'_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###gauge");
+
+    pub const DEFAULT_TRACK_COLOR: Color = Color::rgb(224, 224, 224);
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        GaugeData {
+            value,
+            min: 0.0,
+            max: 1.0,
+            start_angle: -FRAC_PI_2,
+            sweep: TAU,
+            thickness: 0.2,
+            value_text: None,
+            animation: None,
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.data().value
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_value(self, value: f32) -> Self {
+        self.map_data(|data| GaugeData { value, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn min(&self) -> f32 {
+        self.data().min
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_min(self, min: f32) -> Self {
+        self.map_data(|data| GaugeData { min, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn max(&self) -> f32 {
+        self.data().max
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_max(self, max: f32) -> Self {
+        self.map_data(|data| GaugeData { max, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn start_angle(&self) -> f32 {
+        self.data().start_angle
+    }
+
+    /// Sets the angle in radians the arc starts at. It grows clockwise from the positive X axis,
+    /// the default starts at the top.
+    #[must_use]
+    #[inline]
+    pub fn with_start_angle(self, start_angle: f32) -> Self {
+        self.map_data(|data| GaugeData {
+            start_angle,
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn sweep(&self) -> f32 {
+        self.data().sweep
+    }
+
+    /// Sets the angle in radians the arc spans at the maximum value, e.g. `1.5 * PI` for a
+    /// three-quarter gauge. The default is a full circle.
+    #[must_use]
+    #[inline]
+    pub fn with_sweep(self, sweep: f32) -> Self {
+        self.map_data(|data| GaugeData {
+            sweep: sweep.clamp(-TAU, TAU),
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn thickness(&self) -> f32 {
+        self.data().thickness
+    }
+
+    /// Sets the width of the arc relative to the radius of the gauge.
+    #[must_use]
+    #[inline]
+    pub fn with_thickness(self, thickness: f32) -> Self {
+        self.map_data(|data| GaugeData {
+            thickness: thickness.clamp(0.0, 1.0),
+            ..data
+        })
+    }
+
+    /// Shows the value in the center of the gauge, rounded to `decimals` fractional digits.
+    #[must_use]
+    #[inline]
+    pub fn with_value_text(self, decimals: usize) -> Self {
+        self.map_data(|data| GaugeData {
+            value_text: Some(ValueText::Value(decimals)),
+            ..data
+        })
+    }
+
+    /// Shows how full the gauge is as a percentage in its center, rounded to `decimals`
+    /// fractional digits.
+    #[must_use]
+    #[inline]
+    pub fn with_percent_text(self, decimals: usize) -> Self {
+        self.map_data(|data| GaugeData {
+            value_text: Some(ValueText::Percent(decimals)),
+            ..data
+        })
+    }
+
+    /// Animates changes of the value instead of jumping to it.
+    #[must_use]
+    #[inline]
+    pub fn with_animation(self, animation: AnimationConfig) -> Self {
+        self.map_data(|data| GaugeData {
+            animation: Some(animation),
+            ..data
+        })
+    }
+}
+
+impl WidgetData for GaugeData {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        Gauge::TYPE_CLASS
+    }
+}
+
+struct GaugeRenderer<Renderer: rendering::Renderer> {
+    start_angle: f32,
+    sweep: f32,
+    fraction: f32,
+    thickness: f32,
+    _renderer: PhantomData<fn(Renderer)>,
+}
+
+impl<Renderer: rendering::Renderer> rendering::NodeRenderer for GaugeRenderer<Renderer> {
+    type Renderer = Renderer;
+
+    fn render(
+        &self,
+        context: rendering::RenderContext<'_, Self::Renderer>,
+    ) -> Result<(), <Self::Renderer as rendering::Renderer>::Error> {
+        let outer_radius = context.size.x.min(context.size.y) / 2.0;
+        let width = outer_radius * self.thickness;
+        // the stroke is centered on the arc, so pull it in to stay inside the node
+        let radius = outer_radius - width / 2.0;
+        if (radius <= 0.px()) || (width <= 0.px()) {
+            return Ok(());
+        }
+
+        let center = context.position + context.size / 2.0;
+
+        let mut track = Path::new();
+        track.arc(center, radius, self.start_angle, self.sweep);
+        context
+            .renderer
+            .stroke_path(&track, width, context.style.border_color().into())?;
+
+        if self.fraction > 0.0 {
+            let mut fill = Path::new();
+            fill.arc(center, radius, self.start_angle, self.sweep * self.fraction);
+            context
+                .renderer
+                .stroke_path(&fill, width, context.style.selection_color().into())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for GaugeData {
+    type ShowResult = ();
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        // the animation needs persistent state, the gauge itself doesn't
+        let (uid, value) = match self.animation {
+            Some(animation) => {
+                let uid = uid.produce();
+                (Some(uid), gui.animate(uid, self.value, animation).value)
+            }
+            None => (uid.into(), self.value),
+        };
+
+        let fraction = (value - self.min) / (self.max - self.min);
+        let fraction = if fraction.is_finite() {
+            fraction.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let style = style.or_else(&style! {
+            size: 4.em(),
+            border_color: Gauge::DEFAULT_TRACK_COLOR,
+            horizontal_text_alignment: HorizontalTextAlignment::Center,
+            vertical_text_alignment: VerticalTextAlignment::Center,
+        });
+
+        let text = self.value_text.map(|value_text| match value_text {
+            ValueText::Value(decimals) => gui.formatter().format_number(value.into(), decimals),
+            ValueText::Percent(decimals) => {
+                gui.formatter().format_percent(fraction.into(), decimals)
+            }
+        });
+
+        let renderer = GaugeRenderer {
+            start_angle: self.start_angle,
+            sweep: self.sweep,
+            fraction,
+            thickness: self.thickness,
+            _renderer: PhantomData,
+        };
+
+        let contents = match &text {
+            Some(text) => NodeContents::text(text),
+            None => NodeContents::EMPTY,
+        };
+        gui.insert_node(uid, &style, contents.with_renderer(renderer))?;
+        Ok(())
+    }
+}
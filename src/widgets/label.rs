@@ -2,15 +2,21 @@ use super::*;
 use crate::theme::StyleClass;
 use crate::*;
 
+enum OverflowTooltip {}
+
 #[derive(Default)]
 pub struct LabelData<'text> {
     text: &'text str,
+    overflow_tooltip: Option<bool>,
 }
 
 pub type Label<'text, 'style, 'classes> = Widget<'style, 'classes, LabelData<'text>>;
 
 impl<'style, 'classes> Label<'_, 'style, 'classes> {
     pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###label");
+    /// Applied to the tooltip that shows the full text, on top of [`Popup::TYPE_CLASS`].
+    pub const OVERFLOW_TOOLTIP_CLASS: StyleClass =
+        StyleClass::new_static("###label_overflow_tooltip");
 
     #[must_use]
     #[inline]
@@ -21,7 +27,33 @@ impl<'style, 'classes> Label<'_, 'style, 'classes> {
     #[must_use]
     #[inline]
     pub fn with_text<'text>(self, text: &'text str) -> Label<'text, 'style, 'classes> {
-        self.map_data(|data| LabelData { text, ..data })
+        self.map_data(|data| LabelData {
+            text,
+            overflow_tooltip: data.overflow_tooltip,
+        })
+    }
+
+    /// [`None`] if the label follows [`Theme::overflow_tooltips`](crate::theme::Theme::overflow_tooltips).
+    #[must_use]
+    #[inline]
+    pub fn overflow_tooltip(&self) -> Option<bool> {
+        self.data().overflow_tooltip
+    }
+
+    /// Whether hovering the label shows its full text in a tooltip if the text doesn't fit into
+    /// the label and is cut off. Whether it is cut off is known from the last layout, so the
+    /// tooltip lags one frame behind the label changing its size and a frame for it to catch up
+    /// is requested with [`ByorGui::next_animation_frame`].
+    ///
+    /// Without a UID the label takes one from the caller location and its text, and shows no
+    /// tooltip if another node of this frame already has that UID.
+    #[must_use]
+    #[inline]
+    pub fn with_overflow_tooltip(self, overflow_tooltip: bool) -> Self {
+        self.map_data(|data| LabelData {
+            overflow_tooltip: Some(overflow_tooltip),
+            ..data
+        })
     }
 
     #[must_use]
@@ -48,7 +80,115 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for LabelData<'_> {
         uid: MaybeUid,
         style: Style,
     ) -> WidgetResult<Self::ShowResult> {
-        gui.insert_node(uid.into(), &style, NodeContents::text(self.text))?;
+        let overflow_tooltip = self
+            .overflow_tooltip
+            .unwrap_or_else(|| gui.theme().overflow_tooltips());
+        if !overflow_tooltip {
+            gui.insert_node(uid.into(), &style, NodeContents::text(self.text))?;
+            return Ok(());
+        }
+
+        let uid = match uid {
+            MaybeUid::Some(uid) => Some(uid),
+            MaybeUid::None(location) => {
+                // labels are often shown without a UID in loops, unlike interactive widgets
+                let uid = Uid::new(location).concat(Uid::from_slice(self.text.as_bytes()));
+                let recursive_uid = gui.compute_recursive_uid(uid);
+                (!gui.data.is_uid_taken(recursive_uid)).then_some(uid)
+            }
+        };
+
+        let Some(uid) = uid else {
+            gui.insert_node(None, &style, NodeContents::text(self.text))?;
+            return Ok(());
+        };
+
+        let response = gui.insert_node(Some(uid), &style, NodeContents::text(self.text))?;
+        let text_truncated = gui
+            .previous_state(uid)
+            .is_some_and(|previous_state| previous_state.text_truncated);
+        if text_truncated && response.is_directly_hovered() {
+            let tooltip_style = gui
+                .theme()
+                .build_style(None, &[Label::OVERFLOW_TOOLTIP_CLASS], Popup::TYPE_CLASS)
+                .or_else(&style! {
+                    max_width: 24.em(),
+                });
+            // above the cursor, so the cursor doesn't cover the text
+            gui.insert_floating_node(
+                uid.concat(Uid::from_type::<OverflowTooltip>()),
+                FloatPosition::CursorFixed {
+                    pivot: Alignment2D::BOTTOM_LEFT,
+                },
+                &tooltip_style,
+                NodeContents::text(self.text),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ShortcutLabelData<'shortcut> {
+    shortcut: &'shortcut Shortcut,
+    platform: Platform,
+}
+
+/// Shows a keyboard shortcut the way the platform formats it, see [`format_shortcut`].
+pub type ShortcutLabel<'shortcut, 'style, 'classes> =
+    Widget<'style, 'classes, ShortcutLabelData<'shortcut>>;
+
+impl<'shortcut> ShortcutLabel<'shortcut, '_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###shortcut_label");
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(shortcut: &'shortcut Shortcut) -> Self {
+        ShortcutLabelData {
+            shortcut,
+            platform: Platform::DEFAULT,
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn shortcut(&self) -> &Shortcut {
+        self.data().shortcut
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn platform(&self) -> Platform {
+        self.data().platform
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_platform(self, platform: Platform) -> Self {
+        self.map_data(|data| ShortcutLabelData { platform, ..data })
+    }
+}
+
+impl WidgetData for ShortcutLabelData<'_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        ShortcutLabel::TYPE_CLASS
+    }
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ShortcutLabelData<'_> {
+    type ShowResult = ();
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let text = format_shortcut(self.shortcut, self.platform);
+        gui.insert_node(uid.into(), &style, NodeContents::text(&text))?;
         Ok(())
     }
 }
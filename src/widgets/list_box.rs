@@ -0,0 +1,314 @@
+use super::*;
+use crate::input::*;
+use crate::theme::{ColorRole, StyleClass};
+use crate::*;
+use smol_str::SmolStr;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectionMode {
+    /// At most one item is selected
+    #[default]
+    Single,
+    /// Any number of items can be selected with modifier clicks and <kbd>Shift</kbd>+arrow keys
+    Multiple,
+}
+
+/// The selected items of a [`ListBox`] by index, kept by the application between frames.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SelectionState {
+    selected: BTreeSet<usize>,
+    /// Where range selections start
+    anchor: Option<usize>,
+    /// The item the arrow keys move from
+    active: Option<usize>,
+}
+
+impl SelectionState {
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// The indices of the selected items in ascending order.
+    #[inline]
+    pub fn selected(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn active(&self) -> Option<usize> {
+        self.active
+    }
+
+    /// Selects only the item at `index`.
+    pub fn select(&mut self, index: usize) {
+        self.selected.clear();
+        self.selected.insert(index);
+        self.anchor = Some(index);
+        self.active = Some(index);
+    }
+
+    /// Adds the item at `index` to the selection or removes it.
+    pub fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+        self.anchor = Some(index);
+        self.active = Some(index);
+    }
+
+    /// Selects the items from the anchor to `index`, both included.
+    pub fn extend_to(&mut self, index: usize) {
+        let anchor = self.anchor.unwrap_or(index);
+        self.selected.clear();
+        self.selected.extend(anchor.min(index)..=anchor.max(index));
+        self.anchor = Some(anchor);
+        self.active = Some(index);
+    }
+
+    pub fn select_all(&mut self, item_count: usize) {
+        self.selected.extend(0..item_count);
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+        self.active = None;
+    }
+
+    /// Forgets the items at and past `item_count`, e.g. after items were removed.
+    pub fn truncate(&mut self, item_count: usize) {
+        self.selected.split_off(&item_count);
+        self.anchor = self.anchor.filter(|&anchor| anchor < item_count);
+        self.active = self.active.filter(|&active| active < item_count);
+    }
+}
+
+pub struct ListBoxData<'selection> {
+    selection: &'selection mut SelectionState,
+    item_count: usize,
+    mode: SelectionMode,
+}
+
+/// A list of items that can be selected, shown with [`ByorGuiContext::list_box`].
+pub type ListBox<'selection, 'style, 'classes> = Widget<'style, 'classes, ListBoxData<'selection>>;
+
+impl<'selection> ListBox<'selection, '_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###list_box");
+    pub const ROW_CLASS: StyleClass = StyleClass::new_static("###list_box_row");
+    /// Replaces the row style of selected rows. Without a style for this class selected rows get
+    /// the [`ColorRole::Accent`] background.
+    pub const SELECTED_ROW_CLASS: StyleClass = StyleClass::new_static("###list_box_selected_row");
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(selection: &'selection mut SelectionState, item_count: usize) -> Self {
+        ListBoxData {
+            selection,
+            item_count,
+            mode: SelectionMode::Single,
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn item_count(&self) -> usize {
+        self.data().item_count
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn mode(&self) -> SelectionMode {
+        self.data().mode
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_mode(self, mode: SelectionMode) -> Self {
+        self.map_data(|data| ListBoxData { mode, ..data })
+    }
+}
+
+impl WidgetData for ListBoxData<'_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        ListBox::TYPE_CLASS
+    }
+}
+
+/// The modifier of clicks that toggle an item, <kbd>Command</kbd> on macOS.
+const TOGGLE_MODIFIER: Modifiers = match Platform::DEFAULT {
+    Platform::Mac => Modifiers::META,
+    _ => Modifiers::CONTROL,
+};
+
+const SELECT_ALL: Shortcut = Shortcut {
+    modifiers: TOGGLE_MODIFIER,
+    key: Key::Character(SmolStr::new_inline("A")),
+    location: None,
+};
+
+impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    /// Shows `list_box` with one row per item, filled by `row` with the contents of the item at
+    /// the index it is given. Returns whether the selection changed during this frame.
+    ///
+    /// Clicking a row selects it. In [`SelectionMode::Multiple`], <kbd>Ctrl</kbd>+click toggles
+    /// a row, <kbd>Shift</kbd>+click selects the rows from the anchor, and <kbd>Ctrl</kbd>+<kbd>A</kbd>
+    /// selects all rows. The arrow keys move the selection, with <kbd>Shift</kbd> extending it.
+    /// Keys are only handled while the list or one of its rows is focused.
+    #[track_caller]
+    pub fn list_box(
+        &mut self,
+        list_box: ListBox<'_, '_, '_>,
+        mut row: impl FnMut(&mut ByorGuiContext<'_, Renderer>, usize) -> WidgetResult<()>,
+    ) -> WidgetResult<bool> {
+        let style = self
+            .theme()
+            .build_style(list_box.style, list_box.classes, ListBox::TYPE_CLASS)
+            .or_else(&style! {
+                layout_direction: Direction::TopToBottom,
+                child_spacing: 0.px(),
+            });
+        let row_style = self
+            .theme()
+            .build_style(None, &[], ListBox::ROW_CLASS)
+            .or_else(&style! {
+                width: Sizing::Grow,
+            });
+        let selected_row_style = self.theme().class_style_or(
+            &ListBox::SELECTED_ROW_CLASS,
+            || {
+                style! {
+                    background: ColorRole::Accent,
+                }
+            },
+            &row_style,
+        );
+
+        let uid = list_box.uid.produce();
+        let ListBoxData {
+            selection,
+            item_count,
+            mode,
+        } = list_box.data;
+        selection.truncate(item_count);
+        let previous_selection = selection.clone();
+
+        self.insert_node(
+            Some(uid),
+            &style,
+            NodeContents::builder(|mut gui| -> WidgetResult<()> {
+                let mut focused = gui.parent_input_state().focused;
+                let mut clicked = None;
+                gui.uid_scope(uid, |gui| -> WidgetResult<()> {
+                    for index in 0..item_count {
+                        let style = if selection.is_selected(index) {
+                            &selected_row_style
+                        } else {
+                            &row_style
+                        };
+
+                        let response = gui.insert_node(
+                            Some(Uid::new(index)),
+                            style,
+                            NodeContents::builder(|mut gui| row(&mut gui, index))
+                                .with_hit_target(HitTarget::Subtree),
+                        )?;
+                        response.result?;
+
+                        focused |= response.input_state.focused;
+                        if response.input_state.clicked(MouseButtons::PRIMARY) {
+                            clicked = Some(index);
+                        }
+                    }
+                    Ok(())
+                })?;
+
+                let multiple = mode == SelectionMode::Multiple;
+                if let Some(index) = clicked {
+                    let modifiers = gui.global_input_state().modifiers();
+                    if multiple && modifiers.contains(Modifiers::SHIFT) {
+                        selection.extend_to(index);
+                    } else if multiple && modifiers.contains(TOGGLE_MODIFIER) {
+                        selection.toggle(index);
+                    } else {
+                        selection.select(index);
+                    }
+                }
+
+                if focused && (item_count > 0) {
+                    let last = item_count - 1;
+                    gui.global_input_state_mut().retain_key_events(|event| {
+                        if multiple {
+                            match event.matches(&SELECT_ALL) {
+                                KeyEventMatch::True => {
+                                    selection.select_all(item_count);
+                                    return false;
+                                }
+                                KeyEventMatch::ConsumeOnly => return false,
+                                KeyEventMatch::False => (),
+                            }
+                        }
+
+                        // repeated presses move further, so a held key scrolls through the list
+                        let KeyEvent::Pressed { key, modifiers, .. } = event else {
+                            return true;
+                        };
+                        let next = match (key, selection.active()) {
+                            (Key::Named(NamedKey::ArrowUp), Some(active)) => {
+                                active.saturating_sub(1)
+                            }
+                            (Key::Named(NamedKey::ArrowDown), Some(active)) => {
+                                (active + 1).min(last)
+                            }
+                            (Key::Named(NamedKey::ArrowUp | NamedKey::ArrowDown), None) => 0,
+                            _ => return true,
+                        };
+
+                        if multiple && modifiers.contains(Modifiers::SHIFT) {
+                            selection.extend_to(next);
+                        } else {
+                            selection.select(next);
+                        }
+                        false
+                    });
+                }
+
+                Ok(())
+            })
+            .with_tab_index(0),
+        )?
+        .result?;
+
+        Ok(*selection != previous_selection)
+    }
+}
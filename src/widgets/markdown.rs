@@ -0,0 +1,617 @@
+use super::*;
+use crate::theme::{StyleClass, Theme};
+use crate::*;
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::sync::Arc;
+
+pub const DEFAULT_LINK_COLOR: Color = Color::rgb(66, 135, 245);
+pub const DEFAULT_CODE_BACKGROUND: Color = Color {
+    r: 128,
+    g: 128,
+    b: 128,
+    a: 64,
+};
+
+const MONOSPACE_FONT: FontStack<'static> =
+    FontStack::Single(FontFamily::Generic(GenericFamily::Monospace));
+const INDENT_WIDTH: AbsoluteMeasurement = AbsoluteMeasurement::EM(Float::em(1.5));
+
+pub struct MarkdownData<'text> {
+    text: &'text str,
+    link_color: Color,
+    code_background: Color,
+}
+
+impl Default for MarkdownData<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            text: "",
+            link_color: DEFAULT_LINK_COLOR,
+            code_background: DEFAULT_CODE_BACKGROUND,
+        }
+    }
+}
+
+pub type Markdown<'text, 'style, 'classes> = Widget<'style, 'classes, MarkdownData<'text>>;
+
+impl<'text, 'style, 'classes> Markdown<'text, 'style, 'classes> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###markdown");
+
+    pub const HEADING_CLASSES: [StyleClass; 6] = [
+        StyleClass::new_static("###markdown_heading1"),
+        StyleClass::new_static("###markdown_heading2"),
+        StyleClass::new_static("###markdown_heading3"),
+        StyleClass::new_static("###markdown_heading4"),
+        StyleClass::new_static("###markdown_heading5"),
+        StyleClass::new_static("###markdown_heading6"),
+    ];
+    pub const PARAGRAPH_CLASS: StyleClass = StyleClass::new_static("###markdown_paragraph");
+    pub const LIST_ITEM_CLASS: StyleClass = StyleClass::new_static("###markdown_list_item");
+    pub const LIST_MARKER_CLASS: StyleClass = StyleClass::new_static("###markdown_list_marker");
+    pub const CODE_BLOCK_CLASS: StyleClass = StyleClass::new_static("###markdown_code_block");
+    pub const RULE_CLASS: StyleClass = StyleClass::new_static("###markdown_rule");
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(text: &'text str) -> Self {
+        MarkdownData {
+            text,
+            ..Default::default()
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn text(&self) -> &str {
+        self.data().text
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_text<'new_text>(
+        self,
+        text: &'new_text str,
+    ) -> Markdown<'new_text, 'style, 'classes> {
+        self.map_data(|data| MarkdownData {
+            text,
+            link_color: data.link_color,
+            code_background: data.code_background,
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn link_color(&self) -> Color {
+        self.data().link_color
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_link_color(self, link_color: Color) -> Self {
+        self.map_data(|data| MarkdownData { link_color, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn code_background(&self) -> Color {
+        self.data().code_background
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_code_background(self, code_background: Color) -> Self {
+        self.map_data(|data| MarkdownData {
+            code_background,
+            ..data
+        })
+    }
+}
+
+impl WidgetData for MarkdownData<'_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        Markdown::TYPE_CLASS
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct RichText {
+    pub(crate) text: String,
+    pub(crate) spans: Vec<TextSpan>,
+    pub(crate) links: Vec<(Range<usize>, String)>,
+}
+
+pub(crate) enum BlockKind {
+    Heading(usize),
+    Paragraph,
+    ListItem { marker: Option<String> },
+    CodeBlock,
+    Rule,
+}
+
+pub(crate) struct Block {
+    pub(crate) kind: BlockKind,
+    pub(crate) indent: u8,
+    pub(crate) content: RichText,
+}
+
+pub(crate) struct CachedDocument {
+    pub(crate) hash: Uid,
+    pub(crate) blocks: Arc<[Block]>,
+}
+
+struct DocumentBuilder {
+    link_color: Color,
+    code_background: Color,
+
+    blocks: Vec<Block>,
+    current: RichText,
+    heading: Option<usize>,
+    lists: SmallVec<[Option<u64>; 4]>,
+    block_quote_depth: u8,
+    pending_marker: Option<String>,
+    strong_depth: u32,
+    emphasis_depth: u32,
+    strikethrough_depth: u32,
+    links: SmallVec<[(usize, String); 1]>,
+    in_code_block: bool,
+}
+
+impl DocumentBuilder {
+    #[must_use]
+    fn new(link_color: Color, code_background: Color) -> Self {
+        Self {
+            link_color,
+            code_background,
+
+            blocks: Vec::new(),
+            current: RichText::default(),
+            heading: None,
+            lists: SmallVec::new(),
+            block_quote_depth: 0,
+            pending_marker: None,
+            strong_depth: 0,
+            emphasis_depth: 0,
+            strikethrough_depth: 0,
+            links: SmallVec::new(),
+            in_code_block: false,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    fn indent(&self) -> u8 {
+        self.block_quote_depth
+            .saturating_add(self.lists.len().min(u8::MAX as usize) as u8)
+    }
+
+    fn flush(&mut self) {
+        let marker = self.pending_marker.take();
+        if self.current.text.is_empty() && marker.is_none() {
+            return;
+        }
+
+        let kind = if let Some(level) = self.heading {
+            BlockKind::Heading(level)
+        } else if marker.is_some() || !self.lists.is_empty() {
+            BlockKind::ListItem { marker }
+        } else {
+            BlockKind::Paragraph
+        };
+
+        self.blocks.push(Block {
+            kind,
+            indent: self.indent(),
+            content: std::mem::take(&mut self.current),
+        });
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let start = self.current.text.len();
+        self.current.text.push_str(text);
+        let range = start..self.current.text.len();
+
+        if (self.strong_depth > 0) || (self.emphasis_depth > 0) || (self.strikethrough_depth > 0) {
+            let mut span = TextSpan::new(range);
+            if self.strong_depth > 0 {
+                span = span.with_font_weight(FontWeight::BOLD);
+            }
+            if self.emphasis_depth > 0 {
+                span = span.with_font_style(FontStyle::Italic);
+            }
+            if self.strikethrough_depth > 0 {
+                span = span.with_text_strikethrough(true);
+            }
+            self.current.spans.push(span);
+        }
+    }
+
+    fn push_code(&mut self, code: &str) {
+        let start = self.current.text.len();
+        self.current.text.push_str(code);
+        let range = start..self.current.text.len();
+
+        self.current.spans.push(
+            TextSpan::new(range)
+                .with_font_family(MONOSPACE_FONT)
+                .with_background(self.code_background),
+        );
+    }
+
+    fn handle_event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => (),
+                Tag::Heading { level, .. } => {
+                    self.flush();
+                    self.heading = Some(match level {
+                        HeadingLevel::H1 => 0,
+                        HeadingLevel::H2 => 1,
+                        HeadingLevel::H3 => 2,
+                        HeadingLevel::H4 => 3,
+                        HeadingLevel::H5 => 4,
+                        HeadingLevel::H6 => 5,
+                    });
+                }
+                Tag::BlockQuote(_) => {
+                    self.flush();
+                    self.block_quote_depth = self.block_quote_depth.saturating_add(1);
+                }
+                Tag::CodeBlock(_) => {
+                    self.flush();
+                    self.in_code_block = true;
+                }
+                Tag::List(start) => {
+                    self.flush();
+                    self.lists.push(start);
+                }
+                Tag::Item => {
+                    self.flush();
+                    self.pending_marker = Some(match self.lists.last_mut() {
+                        Some(Some(number)) => {
+                            let marker = format!("{number}.");
+                            *number += 1;
+                            marker
+                        }
+                        _ => "•".to_owned(),
+                    });
+                }
+                Tag::Emphasis => self.emphasis_depth += 1,
+                Tag::Strong => self.strong_depth += 1,
+                Tag::Strikethrough => self.strikethrough_depth += 1,
+                Tag::Link { dest_url, .. } => {
+                    self.links
+                        .push((self.current.text.len(), dest_url.into_string()));
+                }
+                _ => (),
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph | TagEnd::Item => self.flush(),
+                TagEnd::Heading(_) => {
+                    self.flush();
+                    self.heading = None;
+                }
+                TagEnd::BlockQuote(_) => {
+                    self.flush();
+                    self.block_quote_depth = self.block_quote_depth.saturating_sub(1);
+                }
+                TagEnd::CodeBlock => {
+                    let mut content = std::mem::take(&mut self.current);
+                    content
+                        .text
+                        .truncate(content.text.trim_end_matches('\n').len());
+
+                    self.blocks.push(Block {
+                        kind: BlockKind::CodeBlock,
+                        indent: self.indent(),
+                        content,
+                    });
+                    self.in_code_block = false;
+                }
+                TagEnd::List(_) => {
+                    self.flush();
+                    self.lists.pop();
+                }
+                TagEnd::Emphasis => self.emphasis_depth = self.emphasis_depth.saturating_sub(1),
+                TagEnd::Strong => self.strong_depth = self.strong_depth.saturating_sub(1),
+                TagEnd::Strikethrough => {
+                    self.strikethrough_depth = self.strikethrough_depth.saturating_sub(1)
+                }
+                TagEnd::Link => {
+                    if let Some((start, url)) = self.links.pop() {
+                        let range = start..self.current.text.len();
+                        self.current.spans.push(
+                            TextSpan::new(range.clone())
+                                .with_text_color(self.link_color)
+                                .with_text_underline(true),
+                        );
+                        self.current.links.push((range, url));
+                    }
+                }
+                _ => (),
+            },
+            Event::Text(text) => {
+                if self.in_code_block {
+                    self.current.text.push_str(&text);
+                } else {
+                    self.push_text(&text);
+                }
+            }
+            Event::Code(code) => self.push_code(&code),
+            Event::SoftBreak => self.push_text(" "),
+            Event::HardBreak => self.push_text("\n"),
+            Event::Rule => {
+                self.flush();
+                self.blocks.push(Block {
+                    kind: BlockKind::Rule,
+                    indent: self.indent(),
+                    content: RichText::default(),
+                });
+            }
+            Event::TaskListMarker(checked) => {
+                if let Some(marker) = &mut self.pending_marker {
+                    *marker = if checked { "☑" } else { "☐" }.to_owned();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    #[must_use]
+    fn build(mut self) -> Arc<[Block]> {
+        self.flush();
+        self.blocks.into()
+    }
+}
+
+#[must_use]
+pub(crate) fn parse_document(
+    text: &str,
+    link_color: Color,
+    code_background: Color,
+) -> Arc<[Block]> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let mut builder = DocumentBuilder::new(link_color, code_background);
+    for event in Parser::new_ext(text, options) {
+        builder.handle_event(event);
+    }
+    builder.build()
+}
+
+#[must_use]
+fn element_style(
+    theme: &Theme,
+    class: StyleClass,
+    type_class: StyleClass,
+    fallback: &Style,
+) -> Style {
+    theme
+        .build_style(None, &[class], type_class)
+        .or_else(fallback)
+}
+
+#[must_use]
+fn indent_style(indent: u8) -> Style {
+    style! {
+        width: Sizing::Grow,
+        padding: Padding {
+            left: (INDENT_WIDTH * f32::from(indent)).into(),
+            ..Padding::ZERO
+        },
+        layout_direction: Direction::LeftToRight,
+    }
+}
+
+struct ElementStyles {
+    headings: [Style; 6],
+    paragraph: Style,
+    list_item: Style,
+    list_marker: Style,
+    code_block: Style,
+    rule: Style,
+}
+
+impl ElementStyles {
+    #[must_use]
+    fn new(theme: &Theme) -> Self {
+        const HEADING_FONT_SIZES: [f32; 6] = [2.0, 1.5, 1.25, 1.0, 0.875, 0.85];
+
+        Self {
+            headings: std::array::from_fn(|i| {
+                element_style(
+                    theme,
+                    Markdown::HEADING_CLASSES[i].clone(),
+                    Label::TYPE_CLASS,
+                    &style! {
+                        width: Sizing::Grow,
+                        font_size: Float::em(HEADING_FONT_SIZES[i]),
+                        font_weight: FontWeight::BOLD,
+                    },
+                )
+            }),
+            paragraph: element_style(
+                theme,
+                Markdown::PARAGRAPH_CLASS,
+                Label::TYPE_CLASS,
+                &style! {
+                    width: Sizing::Grow,
+                },
+            ),
+            list_item: element_style(
+                theme,
+                Markdown::LIST_ITEM_CLASS,
+                Markdown::LIST_ITEM_CLASS,
+                &style! {
+                    width: Sizing::Grow,
+                },
+            ),
+            list_marker: element_style(
+                theme,
+                Markdown::LIST_MARKER_CLASS,
+                Label::TYPE_CLASS,
+                &style! {
+                    width: INDENT_WIDTH,
+                },
+            ),
+            code_block: element_style(
+                theme,
+                Markdown::CODE_BLOCK_CLASS,
+                Label::TYPE_CLASS,
+                &style! {
+                    width: Sizing::Grow,
+                    font_family: MONOSPACE_FONT,
+                    text_wrap: false,
+                    background: DEFAULT_CODE_BACKGROUND,
+                },
+            ),
+            rule: element_style(
+                theme,
+                Markdown::RULE_CLASS,
+                Markdown::RULE_CLASS,
+                &style! {
+                    width: Sizing::Grow,
+                    height: 1.px(),
+                    background: Color::greyscale(128),
+                },
+            ),
+        }
+    }
+}
+
+/// Inserts a text node, returning the URL of the link that was clicked this frame, if any.
+#[track_caller]
+fn rich_text<Renderer: rendering::Renderer>(
+    gui: &mut ByorGuiContext<'_, Renderer>,
+    uid: Uid,
+    style: &Style,
+    content: &RichText,
+) -> WidgetResult<Option<String>> {
+    let contents = NodeContents::rich_text(&content.text, &content.spans).with_builder(|gui| {
+        if content.links.is_empty() || !gui.parent_input_state().clicked(MouseButtons::PRIMARY) {
+            return None;
+        }
+
+        let index = gui.parent_text_index_at_cursor(&content.text, &content.spans)?;
+        content
+            .links
+            .iter()
+            .find(|(range, _)| range.contains(&index))
+            .map(|(_, url)| url.clone())
+    });
+    let contents = if content.links.is_empty() {
+        contents
+    } else {
+        contents.with_hit_target(HitTarget::SelfOnly)
+    };
+
+    Ok(gui.insert_node(Some(uid), style, contents)?.result)
+}
+
+#[track_caller]
+fn block<Renderer: rendering::Renderer>(
+    gui: &mut ByorGuiContext<'_, Renderer>,
+    uid: Uid,
+    block: &Block,
+    styles: &ElementStyles,
+) -> WidgetResult<Option<String>> {
+    match &block.kind {
+        BlockKind::Heading(level) => rich_text(gui, uid, &styles.headings[*level], &block.content),
+        BlockKind::Paragraph => rich_text(gui, uid, &styles.paragraph, &block.content),
+        BlockKind::ListItem { marker } => {
+            let contents = NodeContents::builder(|mut gui| {
+                let marker = marker.as_deref().unwrap_or_default();
+                gui.insert_node(None, &styles.list_marker, NodeContents::text(marker))?;
+                rich_text(&mut gui, uid, &styles.paragraph, &block.content)
+            });
+
+            gui.insert_node(None, &styles.list_item, contents)?.result
+        }
+        BlockKind::CodeBlock => {
+            let contents = NodeContents::text(&block.content.text);
+            gui.insert_node(None, &styles.code_block, contents)?;
+            Ok(None)
+        }
+        BlockKind::Rule => {
+            gui.insert_node(None, &styles.rule, NodeContents::EMPTY)?;
+            Ok(None)
+        }
+    }
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for MarkdownData<'_> {
+    type ShowResult = Option<String>;
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let uid = uid.produce();
+        let style = style.with_layout_direction(Direction::TopToBottom);
+
+        // Parsing is comparatively expensive, so only do it when the document changes
+        let hash = Uid::new((self.text, self.link_color, self.code_background));
+        let persistent_state = gui.persistent_state_mut(uid);
+        let cached_blocks = persistent_state
+            .get::<CachedDocument>(PersistentStateKey::MarkdownDocument)
+            .filter(|document| document.hash == hash)
+            .map(|document| Arc::clone(&document.blocks));
+        let blocks = match cached_blocks {
+            Some(blocks) => blocks,
+            None => {
+                let blocks = parse_document(self.text, self.link_color, self.code_background);
+                persistent_state.insert(
+                    PersistentStateKey::MarkdownDocument,
+                    CachedDocument {
+                        hash,
+                        blocks: Arc::clone(&blocks),
+                    },
+                );
+                blocks
+            }
+        };
+
+        let styles = ElementStyles::new(gui.theme());
+
+        gui.insert_node(
+            Some(uid),
+            &style,
+            NodeContents::builder(|mut gui| {
+                let mut clicked_link = None;
+
+                for (i, current_block) in blocks.iter().enumerate() {
+                    let block_uid = uid.concat(Uid::new(i));
+
+                    let link = if current_block.indent > 0 {
+                        let indent = match current_block.kind {
+                            // the list marker takes up one level of indentation
+                            BlockKind::ListItem { .. } => current_block.indent - 1,
+                            _ => current_block.indent,
+                        };
+
+                        gui.insert_node(
+                            None,
+                            &indent_style(indent),
+                            NodeContents::builder(|mut gui| {
+                                block(&mut gui, block_uid, current_block, &styles)
+                            }),
+                        )?
+                        .result?
+                    } else {
+                        block(&mut gui, block_uid, current_block, &styles)?
+                    };
+
+                    clicked_link = clicked_link.or(link);
+                }
+
+                Ok(clicked_link)
+            }),
+        )?
+        .result
+    }
+}
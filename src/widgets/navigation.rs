@@ -0,0 +1,404 @@
+use super::*;
+use crate::style::axis::Axis;
+use crate::theme::{ColorRole, StyleClass, Theme};
+use crate::*;
+use std::ops::Range;
+
+/// The style classes shared by the segments of [`Breadcrumbs`] and [`SegmentedControl`].
+pub struct Segments;
+
+impl Segments {
+    /// Replaces the button style of segments. Without a style for this class segments are
+    /// buttons without rounded corners, so the segments of a control are fused into one.
+    pub const CLASS: StyleClass = StyleClass::new_static("###segment");
+    /// Replaces the segment style of the selected segment of a [`SegmentedControl`]. Without a
+    /// style for this class the segment gets the [`ColorRole::Accent`] background.
+    pub const SELECTED_CLASS: StyleClass = StyleClass::new_static("###selected_segment");
+}
+
+const SEPARATOR: &str = "›";
+const ELLIPSIS: &str = "…";
+
+struct OverflowButton;
+struct OverflowMenu;
+
+/// Builds the explicit styles of unselected and selected segments.
+#[must_use]
+fn segment_styles(theme: &Theme) -> (Style, Style) {
+    let segment_style = theme.class_style_or(
+        &Segments::CLASS,
+        || {
+            style! {
+                corner_radius: 0.px(),
+            }
+        },
+        &Style::DEFAULT,
+    );
+    let selected_style = theme.class_style_or(
+        &Segments::SELECTED_CLASS,
+        || {
+            style! {
+                background: ColorRole::Accent,
+            }
+        },
+        &segment_style,
+    );
+
+    (segment_style, selected_style)
+}
+
+/// Returns whether the segment was clicked.
+fn show_segment<Renderer: rendering::Renderer>(
+    gui: &mut ByorGuiContext<'_, Renderer>,
+    uid: Uid,
+    label: &str,
+    style: &Style,
+) -> WidgetResult<bool> {
+    let button = Button::default()
+        .with_text(label)
+        .with_uid(uid)
+        .with_style(style);
+    Ok(gui.show(button)?.clicked(MouseButtons::PRIMARY))
+}
+
+pub struct BreadcrumbsData<'labels> {
+    labels: &'labels [&'labels str],
+}
+
+/// A path of clickable segments separated by chevrons, e.g. the folders leading to a file.
+/// Showing it returns the index of the clicked segment.
+///
+/// If the path doesn't fit into the width the breadcrumbs had in the last frame, the segments
+/// after the first one are collapsed into a "…" button, starting with the second one, until the
+/// rest fits. The button opens a menu listing the collapsed segments.
+pub type Breadcrumbs<'labels, 'style, 'classes> =
+    Widget<'style, 'classes, BreadcrumbsData<'labels>>;
+
+impl<'labels> Breadcrumbs<'labels, '_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###breadcrumbs");
+    pub const SEPARATOR_CLASS: StyleClass = StyleClass::new_static("###breadcrumbs_separator");
+    /// The list of collapsed segments in the popup of the "…" button
+    pub const MENU_CLASS: StyleClass = StyleClass::new_static("###breadcrumbs_menu");
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(labels: &'labels [&'labels str]) -> Self {
+        BreadcrumbsData { labels }.into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn labels(&self) -> &'labels [&'labels str] {
+        self.data().labels
+    }
+}
+
+impl WidgetData for BreadcrumbsData<'_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        Breadcrumbs::TYPE_CLASS
+    }
+}
+
+/// The width of a row of segments with separators between them.
+#[must_use]
+fn crumbs_width(
+    segment_widths: impl ExactSizeIterator<Item = Float<Pixel>>,
+    separator_width: Float<Pixel>,
+    child_spacing: Float<Pixel>,
+) -> Float<Pixel> {
+    let gaps = segment_widths.len().saturating_sub(1) as f32;
+    segment_widths.sum::<Float<Pixel>>() + gaps * (separator_width + 2.0 * child_spacing)
+}
+
+/// The segments that are collapsed so the rest fits into `available_width`. The first and the
+/// last segment are never collapsed.
+#[must_use]
+fn collapsed_crumbs(
+    segment_widths: &[Float<Pixel>],
+    ellipsis_width: Float<Pixel>,
+    separator_width: Float<Pixel>,
+    child_spacing: Float<Pixel>,
+    available_width: Float<Pixel>,
+) -> Range<usize> {
+    let count = segment_widths.len();
+    let all_width = crumbs_width(
+        segment_widths.iter().copied(),
+        separator_width,
+        child_spacing,
+    );
+    if (count <= 2) || (all_width <= available_width) {
+        return 1..1;
+    }
+
+    let first_shown = (2..count)
+        .find(|&first_shown| {
+            let widths = [segment_widths[0], ellipsis_width]
+                .into_iter()
+                .chain(segment_widths[first_shown..].iter().copied())
+                .collect::<Vec<_>>();
+            crumbs_width(widths.into_iter(), separator_width, child_spacing) <= available_width
+        })
+        .unwrap_or(count - 1);
+    1..first_shown
+}
+
+/// Shows the "…" button standing in for the `collapsed` segments and the menu it opens. Returns
+/// the index of the segment chosen from the menu.
+fn show_overflow_menu<Renderer: rendering::Renderer>(
+    gui: &mut ByorGuiContext<'_, Renderer>,
+    labels: &[&str],
+    collapsed: Range<usize>,
+    segment_style: &Style,
+    menu_style: &Style,
+    menu_item_style: &Style,
+) -> WidgetResult<Option<usize>> {
+    let button_uid = Uid::from_type::<OverflowButton>();
+    let menu_uid = Uid::from_type::<OverflowMenu>();
+    let position = FloatPosition::Popup {
+        x: PopupPosition::ParentStart,
+        y: PopupPosition::AfterParent,
+    };
+
+    // the popup is inserted into a node around the button to be placed below it
+    let chosen = gui
+        .insert_node(
+            None,
+            &Style::DEFAULT,
+            NodeContents::builder(|mut gui| -> WidgetResult<Option<usize>> {
+                if show_segment(&mut gui, button_uid, ELLIPSIS, segment_style)? {
+                    gui.open_popup(menu_uid);
+                }
+
+                let menu = gui.popup_auto(menu_uid, position, |mut gui| {
+                    gui.insert_node(
+                        None,
+                        menu_style,
+                        NodeContents::builder(|mut gui| -> WidgetResult<Option<usize>> {
+                            let mut chosen = None;
+                            for index in collapsed {
+                                let item_uid = menu_uid.concat(Uid::new(index));
+                                if show_segment(&mut gui, item_uid, labels[index], menu_item_style)?
+                                {
+                                    chosen = Some(index);
+                                }
+                            }
+                            Ok(chosen)
+                        }),
+                    )?
+                    .result
+                })?;
+                let chosen = menu.transpose()?.flatten();
+
+                if chosen.is_some() {
+                    gui.close_popup(menu_uid);
+                }
+                Ok(chosen)
+            }),
+        )?
+        .result?;
+
+    Ok(chosen)
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for BreadcrumbsData<'_> {
+    type ShowResult = Option<usize>;
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let uid = uid.produce();
+        let labels = self.labels;
+
+        let style = style.or_else(&style! {
+            width: Sizing::Grow,
+            cross_axis_alignment: Alignment::Center,
+        });
+        let separator_style =
+            gui.theme()
+                .build_style(None, &[Breadcrumbs::SEPARATOR_CLASS], Label::TYPE_CLASS);
+        let menu_style = gui
+            .theme()
+            .build_style(None, &[], Breadcrumbs::MENU_CLASS)
+            .or_else(&style! {
+                layout_direction: Direction::TopToBottom,
+                padding: 0.px(),
+            });
+        let (segment_style, _) = segment_styles(gui.theme());
+        let menu_item_style = segment_style.clone().or_else(&style! {
+            width: Sizing::Grow,
+        });
+        let measured_segment_style =
+            gui.theme()
+                .build_style(Some(&segment_style), &[], Button::TYPE_CLASS);
+        let previous_width = gui.previous_state(uid).map(|state| state.size.x);
+
+        // A stored scroll offset keeps the segments from growing the minimum width of the node,
+        // so it can shrink below them and the overflow is known in the next frame.
+        gui.persistent_state_mut(uid)
+            .insert(Axis::X.persistent_state_scroll_key(), 0.px());
+
+        let response = gui.insert_node(
+            Some(uid),
+            &style,
+            NodeContents::builder(|mut gui| -> WidgetResult<Option<usize>> {
+                let collapsed = match previous_width {
+                    Some(previous_width) => {
+                        let computed_style = gui.computed_parent_style();
+                        let padding = computed_style.padding();
+                        let available_width = previous_width - padding.left - padding.right;
+                        let child_spacing = computed_style.child_spacing();
+
+                        let segment_widths = labels
+                            .iter()
+                            .map(|label| gui.measure_text(label, &measured_segment_style).x)
+                            .collect::<Vec<_>>();
+                        collapsed_crumbs(
+                            &segment_widths,
+                            gui.measure_text(ELLIPSIS, &measured_segment_style).x,
+                            gui.measure_text(SEPARATOR, &separator_style).x,
+                            child_spacing,
+                            available_width,
+                        )
+                    }
+                    None => 1..1,
+                };
+
+                gui.uid_scope(uid, |gui| -> WidgetResult<Option<usize>> {
+                    let mut clicked = None;
+                    for (index, &label) in labels.iter().enumerate() {
+                        // the "…" button takes the place of the first collapsed segment
+                        if collapsed.contains(&index) && (index != collapsed.start) {
+                            continue;
+                        }
+
+                        if index > 0 {
+                            gui.show(
+                                Label::default()
+                                    .with_text(SEPARATOR)
+                                    .with_classes(&[Breadcrumbs::SEPARATOR_CLASS]),
+                            )?;
+                        }
+
+                        if !collapsed.contains(&index) {
+                            if show_segment(gui, Uid::new(index), label, &segment_style)? {
+                                clicked = Some(index);
+                            }
+                            continue;
+                        }
+
+                        let menu_clicked = show_overflow_menu(
+                            gui,
+                            labels,
+                            collapsed.clone(),
+                            &segment_style,
+                            &menu_style,
+                            &menu_item_style,
+                        )?;
+                        clicked = clicked.or(menu_clicked);
+                    }
+                    Ok(clicked)
+                })
+            }),
+        )?;
+
+        response.result
+    }
+}
+
+pub struct SegmentedControlData<'labels, 'selected> {
+    labels: &'labels [&'labels str],
+    selected: &'selected mut usize,
+}
+
+/// Mutually exclusive options shown as buttons fused into one row, with the selected one
+/// highlighted. Showing it returns whether the selection changed during this frame.
+pub type SegmentedControl<'labels, 'selected, 'style, 'classes> =
+    Widget<'style, 'classes, SegmentedControlData<'labels, 'selected>>;
+
+impl<'labels, 'selected> SegmentedControl<'labels, 'selected, '_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###segmented_control");
+
+    /// `selected` is the index of the selected label. No segment is highlighted if it is out of
+    /// range.
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(labels: &'labels [&'labels str], selected: &'selected mut usize) -> Self {
+        SegmentedControlData { labels, selected }.into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn labels(&self) -> &'labels [&'labels str] {
+        self.data().labels
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn selected(&self) -> usize {
+        *self.data().selected
+    }
+}
+
+impl WidgetData for SegmentedControlData<'_, '_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        SegmentedControl::TYPE_CLASS
+    }
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for SegmentedControlData<'_, '_> {
+    type ShowResult = bool;
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let uid = uid.produce();
+        let SegmentedControlData { labels, selected } = self;
+
+        let style = style.or_else(&style! {
+            padding: 0.px(),
+            child_spacing: 0.px(),
+        });
+        let (segment_style, selected_style) = segment_styles(gui.theme());
+
+        let clicked = gui
+            .insert_node(
+                Some(uid),
+                &style,
+                NodeContents::builder(|mut gui| {
+                    gui.uid_scope(uid, |gui| -> WidgetResult<Option<usize>> {
+                        let mut clicked = None;
+                        for (index, &label) in labels.iter().enumerate() {
+                            let style = if index == *selected {
+                                &selected_style
+                            } else {
+                                &segment_style
+                            };
+                            if show_segment(gui, Uid::new(index), label, style)? {
+                                clicked = Some(index);
+                            }
+                        }
+                        Ok(clicked)
+                    })
+                }),
+            )?
+            .result?;
+
+        match clicked {
+            Some(index) if index != *selected => {
+                *selected = index;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
@@ -2,9 +2,15 @@ use super::*;
 use crate::theme::StyleClass;
 use crate::*;
 
+enum PopupOpen<'open> {
+    Borrowed(&'open mut bool),
+    Persistent,
+}
+
 pub struct PopupData<'open> {
     position: FloatPosition,
-    open: &'open mut bool,
+    open: PopupOpen<'open>,
+    focus_trap: bool,
 }
 
 pub type Popup<'open, 'style, 'classes> = Widget<'style, 'classes, PopupData<'open>>;
@@ -18,7 +24,23 @@ impl<'open> Popup<'open, '_, '_> {
     pub fn new(open: &'open mut bool) -> Self {
         PopupData {
             position: FloatPosition::default(),
-            open,
+            open: PopupOpen::Borrowed(open),
+            focus_trap: true,
+        }
+        .into()
+    }
+
+    /// Creates a popup that stores whether it is open in its persistent state.
+    ///
+    /// Use [`ByorGuiContext::open_popup`] with the same UID to open it.
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn persistent() -> Self {
+        PopupData {
+            position: FloatPosition::default(),
+            open: PopupOpen::Persistent,
+            focus_trap: true,
         }
         .into()
     }
@@ -34,6 +56,20 @@ impl<'open> Popup<'open, '_, '_> {
     pub fn with_position(self, position: FloatPosition) -> Self {
         self.map_data(|data| PopupData { position, ..data })
     }
+
+    #[must_use]
+    #[inline]
+    pub fn focus_trap(&self) -> bool {
+        self.data().focus_trap
+    }
+
+    /// Whether <kbd>Tab</kbd> navigation stays inside the popup while it is open, on by default.
+    /// See [`NodeContents::with_focus_trap`].
+    #[must_use]
+    #[inline]
+    pub fn with_focus_trap(self, focus_trap: bool) -> Self {
+        self.map_data(|data| PopupData { focus_trap, ..data })
+    }
 }
 
 impl WidgetData for PopupData<'_> {
@@ -55,13 +91,26 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for PopupData<
     ) -> WidgetResult<Self::ShowResult<R>> {
         let uid = uid.produce();
 
-        let result = if *self.open {
-            let response = gui.insert_floating_node(
-                uid,
-                self.position,
-                &style,
-                NodeContents::builder(contents),
-            )?;
+        let mut open = match &self.open {
+            PopupOpen::Borrowed(open) => **open,
+            PopupOpen::Persistent => gui
+                .persistent_state(uid)
+                .get::<bool>(PersistentStateKey::PopupOpen)
+                .copied()
+                .unwrap_or(false),
+        };
+
+        if open && gui.escaped(uid) {
+            open = false;
+        }
+
+        let result = if open {
+            gui.register_escapable(uid, Escapable::Popup);
+            let mut node_contents = NodeContents::builder(contents);
+            if self.focus_trap {
+                node_contents = node_contents.with_focus_trap();
+            }
+            let response = gui.insert_floating_node(uid, self.position, &style, node_contents)?;
 
             //  If this is the first frame the popup opened, do not immediately close it
             let previous_open = gui
@@ -74,7 +123,7 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for PopupData<
                 && !gui.global_input_state().clicked_buttons().is_empty()
                 && !response.is_hovered()
             {
-                *self.open = false;
+                open = false;
             }
 
             Some(response.result)
@@ -82,8 +131,15 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for PopupData<
             None
         };
 
+        match self.open {
+            PopupOpen::Borrowed(borrowed_open) => *borrowed_open = open,
+            PopupOpen::Persistent => gui
+                .persistent_state_mut(uid)
+                .insert(PersistentStateKey::PopupOpen, open),
+        }
+
         gui.persistent_state_mut(uid)
-            .insert(PersistentStateKey::PreviousPopupState, *self.open);
+            .insert(PersistentStateKey::PreviousPopupState, open);
 
         Ok(result)
     }
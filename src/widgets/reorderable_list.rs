@@ -0,0 +1,362 @@
+use super::*;
+use crate::input::*;
+use crate::style::axis::Axis;
+use crate::theme::{ColorRole, StyleClass};
+use crate::*;
+
+/// Identifies the drag handle of an item through [`Uid::from_type`].
+enum DragHandle {}
+
+/// The item being dragged, kept in the persistent state of a reorderable list.
+#[derive(Debug, Clone, Copy)]
+struct ReorderDrag {
+    from: usize,
+    /// The cursor relative to the top left corner of the item
+    grab_offset: Vec2<Pixel>,
+    /// Where the item was pressed, until the cursor moved far enough to start dragging
+    press_position: Option<Vec2<Pixel>>,
+    /// The size of the item when it was pressed, which the gap and the ghost keep
+    size: Vec2<Pixel>,
+}
+
+impl ReorderDrag {
+    #[must_use]
+    #[inline]
+    fn is_dragging(&self) -> bool {
+        self.press_position.is_none()
+    }
+}
+
+pub struct ReorderableListData {
+    item_count: usize,
+    drag_handles: bool,
+}
+
+/// A vertical list whose items can be dragged to another position, shown with
+/// [`ByorGuiContext::reorderable_list`].
+///
+/// While an item is dragged it follows the cursor on top of everything else, and the other items
+/// make room for it where it would be dropped. Escape cancels the drag. Inside of a
+/// [`ScrollView`] the view scrolls while the cursor is close to one of its ends, so items can be
+/// moved anywhere in long lists.
+pub type ReorderableList<'style, 'classes> = Widget<'style, 'classes, ReorderableListData>;
+
+impl ReorderableList<'_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###reorderable_list");
+    pub const ITEM_CLASS: StyleClass = StyleClass::new_static("###reorderable_list_item");
+    /// Replaces the item style of the item that is being dragged. Without a style for this class
+    /// the dragged item gets a [`ColorRole::Surface`] background and a drop shadow.
+    pub const DRAGGED_ITEM_CLASS: StyleClass =
+        StyleClass::new_static("###reorderable_list_dragged_item");
+    pub const HANDLE_CLASS: StyleClass = StyleClass::new_static("###reorderable_list_handle");
+    /// Shown where the dragged item would be dropped, with the size of the item.
+    pub const GAP_CLASS: StyleClass = StyleClass::new_static("###reorderable_list_gap");
+
+    /// How far the cursor has to move with the button held before an item is dragged, so clicks
+    /// on the contents of items don't move them.
+    pub const DRAG_THRESHOLD: AbsoluteMeasurement = AbsoluteMeasurement::Point(Float::pt(4.0));
+
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(item_count: usize) -> Self {
+        ReorderableListData {
+            item_count,
+            drag_handles: false,
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn item_count(&self) -> usize {
+        self.data().item_count
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn drag_handles(&self) -> bool {
+        self.data().drag_handles
+    }
+
+    /// Shows a handle at the start of every item, and only lets the items be dragged by it.
+    /// Without handles an item is dragged by any part of it that doesn't handle presses itself.
+    #[must_use]
+    #[inline]
+    pub fn with_drag_handles(self, drag_handles: bool) -> Self {
+        self.map_data(|data| ReorderableListData {
+            drag_handles,
+            ..data
+        })
+    }
+}
+
+impl WidgetData for ReorderableListData {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        ReorderableList::TYPE_CLASS
+    }
+}
+
+/// Where the dragged item of `item_count` items ends up, from the layout of the previous frame.
+/// The positions are computed as if the gap wasn't there, so the gap moving doesn't feed back
+/// into where it goes.
+#[must_use]
+fn drop_index<Renderer: rendering::Renderer>(
+    gui: &ByorGuiContext<'_, Renderer>,
+    uid: Uid,
+    item_count: usize,
+    drag: &ReorderDrag,
+) -> usize {
+    let Some(list) = gui.previous_state(uid) else {
+        return drag.from;
+    };
+    let parent_style = gui.computed_parent_style();
+    let spacing = parent_style.child_spacing();
+    let mut top = list.position.y + parent_style.padding().along_axis(Axis::Y)[0];
+
+    let ghost_center =
+        gui.global_input_state().last_cursor_position().y - drag.grab_offset.y + drag.size.y / 2.0;
+    let mut index = 0;
+    for item in (0..item_count).filter(|&item| item != drag.from) {
+        let Some(height) = gui
+            .previous_state(uid.concat(Uid::new(item)))
+            .map(|state| state.size.y)
+        else {
+            return drag.from;
+        };
+        if (top + height / 2.0) >= ghost_center {
+            break;
+        }
+
+        index += 1;
+        top += height + spacing;
+    }
+    index
+}
+
+impl<Renderer: rendering::Renderer> ByorGuiContext<'_, Renderer> {
+    /// Shows `list` with one item per index, filled by `item` with the contents of the item at
+    /// the index it is given. Returns `Some((from, to))` during the frame an item is dropped at
+    /// another position, after which the application moves its data, e.g. with
+    /// `items.insert(to, items.remove(from))`.
+    ///
+    /// The UIDs of `item` are scoped by the index, so every item can use the same UIDs.
+    #[track_caller]
+    pub fn reorderable_list(
+        &mut self,
+        list: ReorderableList<'_, '_>,
+        mut item: impl FnMut(&mut ByorGuiContext<'_, Renderer>, usize) -> WidgetResult<()>,
+    ) -> WidgetResult<Option<(usize, usize)>> {
+        let style = self
+            .theme()
+            .build_style(list.style, list.classes, ReorderableList::TYPE_CLASS)
+            .or_else(&style! {
+                layout_direction: Direction::TopToBottom,
+                child_spacing: 0.px(),
+            });
+        let item_style = self
+            .theme()
+            .build_style(None, &[], ReorderableList::ITEM_CLASS)
+            .or_else(&style! {
+                width: Sizing::Grow,
+                cross_axis_alignment: Alignment::Center,
+            });
+        let dragged_item_style = self.theme().class_style_or(
+            &ReorderableList::DRAGGED_ITEM_CLASS,
+            || {
+                style! {
+                    background: ColorRole::Surface,
+                    drop_shadow_width: 0.5.em(),
+                    drop_shadow_color: Color {
+                        r: 0,
+                        g: 0,
+                        b: 0,
+                        a: 96,
+                    },
+                }
+            },
+            &item_style,
+        );
+        let handle_style = self
+            .theme()
+            .build_style(None, &[], ReorderableList::HANDLE_CLASS)
+            .or_else(&style! {
+                width: 0.75.em(),
+                height: 1.5.em(),
+                corner_radius: 0.25.em(),
+                background: Gauge::DEFAULT_TRACK_COLOR,
+            });
+        let gap_style = self
+            .theme()
+            .build_style(None, &[], ReorderableList::GAP_CLASS)
+            .or_else(&style! {
+                width: Sizing::Grow,
+                corner_radius: 0.25.em(),
+                border_width: 1.px(),
+                border_color: ColorRole::Accent,
+            });
+
+        let uid = list.uid.produce();
+        let ReorderableListData {
+            item_count,
+            drag_handles,
+        } = list.data;
+
+        self.insert_node(
+            Some(uid),
+            &style,
+            NodeContents::builder(|mut gui| -> WidgetResult<Option<(usize, usize)>> {
+                let enabled = gui.computed_parent_style().enabled();
+                let list_uid = gui.compute_recursive_uid(uid);
+                // the node that is pressed to drag an item
+                let grab_uid = |index| {
+                    let item_uid = list_uid.concat(Uid::new(index));
+                    if drag_handles {
+                        item_uid.concat(Uid::from_type::<DragHandle>())
+                    } else {
+                        item_uid
+                    }
+                };
+
+                let cursor = gui.global_input_state().last_cursor_position();
+                let mut drag = gui
+                    .persistent_state(uid)
+                    .get::<Option<ReorderDrag>>(PersistentStateKey::ReorderDrag)
+                    .copied()
+                    .flatten()
+                    .filter(|drag| enabled && (drag.from < item_count));
+                let mut dropped = None;
+                if let Some(current) = drag {
+                    let pressed = gui
+                        .data
+                        .compute_node_input_state(Some(grab_uid(current.from)))
+                        .pressed(MouseButtons::PRIMARY);
+                    let released = gui
+                        .global_input_state()
+                        .released_buttons()
+                        .contains(MouseButtons::PRIMARY);
+
+                    if pressed {
+                        if let Some(press_position) = current.press_position {
+                            let threshold = ReorderableList::DRAG_THRESHOLD.to_pixel(
+                                gui.scale_factor(),
+                                gui.computed_parent_style().font_size().value(),
+                            );
+                            let distance = cursor - press_position;
+                            if distance.x.abs().max(distance.y.abs()) >= threshold {
+                                drag = Some(ReorderDrag {
+                                    press_position: None,
+                                    ..current
+                                });
+                            }
+                        }
+                    } else {
+                        // without a release the drag was cancelled with Escape
+                        if current.is_dragging() && released {
+                            dropped =
+                                Some((current.from, drop_index(&gui, uid, item_count, &current)));
+                        }
+                        drag = None;
+                    }
+                }
+
+                // the items in the order they are shown, with the gap at `gap`
+                let mut order: Vec<usize> = (0..item_count).collect();
+                let mut gap = None;
+                if let Some((from, to)) = dropped {
+                    order.remove(from);
+                    order.insert(to, from);
+                } else if let Some(drag) = drag.filter(ReorderDrag::is_dragging) {
+                    order.remove(drag.from);
+                    gap = Some(drop_index(&gui, uid, item_count, &drag));
+                    gui.request_drag_autoscroll();
+                }
+
+                let mut show_item =
+                    |gui: &mut ByorGuiContext<'_, Renderer>, index: usize| -> WidgetResult<()> {
+                        gui.uid_scope(Uid::new(index), |gui| -> WidgetResult<()> {
+                            if drag_handles {
+                                gui.insert_node(
+                                    Some(Uid::from_type::<DragHandle>()),
+                                    &handle_style,
+                                    NodeContents::EMPTY,
+                                )?;
+                            }
+                            item(gui, index)
+                        })
+                    };
+                let hit_target = if drag_handles {
+                    HitTarget::SelfOnly
+                } else {
+                    HitTarget::Subtree
+                };
+                let gap_style = drag.map(|drag| gap_style.with_height(drag.size.y));
+
+                let mut pressed_item = None;
+                gui.uid_scope(uid, |gui| -> WidgetResult<()> {
+                    for (position, &index) in order.iter().enumerate() {
+                        if gap == Some(position)
+                            && let Some(gap_style) = &gap_style
+                        {
+                            gui.insert_node(None, gap_style, NodeContents::EMPTY)?;
+                        }
+
+                        gui.insert_node(
+                            Some(Uid::new(index)),
+                            &item_style,
+                            NodeContents::builder(|mut gui| show_item(&mut gui, index))
+                                .with_hit_target(hit_target),
+                        )?
+                        .result?;
+
+                        let grab = gui.data.compute_node_input_state(Some(grab_uid(index)));
+                        if grab.clicked(MouseButtons::PRIMARY) {
+                            pressed_item = Some(index);
+                        }
+                    }
+                    if gap == Some(order.len())
+                        && let Some(gap_style) = &gap_style
+                    {
+                        gui.insert_node(None, gap_style, NodeContents::EMPTY)?;
+                    }
+
+                    if let Some(drag) = drag.filter(ReorderDrag::is_dragging) {
+                        let ghost_style = dragged_item_style
+                            .clone()
+                            .with_width(drag.size.x)
+                            .with_height(drag.size.y);
+                        let position = cursor - drag.grab_offset;
+                        gui.insert_floating_node(
+                            Uid::new(drag.from),
+                            FloatPosition::fixed(position.x, position.y),
+                            &ghost_style,
+                            NodeContents::builder(|mut gui| show_item(&mut gui, drag.from))
+                                .with_hit_target(hit_target),
+                        )?
+                        .result?;
+                    }
+                    Ok(())
+                })?;
+
+                if enabled
+                    && drag.is_none()
+                    && let Some(from) = pressed_item
+                    && let Some(state) = gui.previous_state(uid.concat(Uid::new(from)))
+                {
+                    drag = Some(ReorderDrag {
+                        from,
+                        grab_offset: cursor - state.position,
+                        press_position: Some(cursor),
+                        size: state.size,
+                    });
+                }
+                gui.persistent_state_mut(uid)
+                    .insert(PersistentStateKey::ReorderDrag, drag);
+
+                Ok(dropped.filter(|(from, to)| from != to))
+            }),
+        )?
+        .result
+    }
+}
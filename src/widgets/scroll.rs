@@ -4,10 +4,11 @@ use crate::theme::StyleClass;
 use crate::*;
 use std::marker::PhantomData;
 
-const SCROLL_BAR_UID: Uid = Uid::from_array(b"##scroll_bar");
-const SCROLL_BAR_DEC_BUTTON_UID: Uid = Uid::from_array(b"##scroll_bar_dec_button");
-const SCROLL_BAR_INC_BUTTON_UID: Uid = Uid::from_array(b"##scroll_bar_inc_button");
-const SCROLL_BAR_THUMB_UID: Uid = Uid::from_array(b"##scroll_bar_thumb");
+/// Identifies the parts of a scroll bar through [`Uid::from_type`].
+enum DecButton {}
+enum IncButton {}
+pub(crate) enum Thumb {}
+enum OverlayBar {}
 
 pub struct ScrollBarData {
     axis: Axis,
@@ -152,19 +153,21 @@ enum ButtonDirection {
 impl ButtonDirection {
     #[must_use]
     #[inline]
-    const fn dec_along_axis(axis: Axis) -> Self {
-        match axis {
-            Axis::X => Self::Left,
-            Axis::Y => Self::Up,
+    const fn dec_along_axis(axis: Axis, ui_direction: UiDirection) -> Self {
+        match (axis, ui_direction) {
+            (Axis::X, UiDirection::Ltr) => Self::Left,
+            (Axis::X, UiDirection::Rtl) => Self::Right,
+            (Axis::Y, _) => Self::Up,
         }
     }
 
     #[must_use]
     #[inline]
-    const fn inc_along_axis(axis: Axis) -> Self {
-        match axis {
-            Axis::X => Self::Right,
-            Axis::Y => Self::Down,
+    const fn inc_along_axis(axis: Axis, ui_direction: UiDirection) -> Self {
+        match (axis, ui_direction) {
+            (Axis::X, UiDirection::Ltr) => Self::Right,
+            (Axis::X, UiDirection::Rtl) => Self::Left,
+            (Axis::Y, _) => Self::Down,
         }
     }
 }
@@ -196,61 +199,62 @@ impl<Renderer: rendering::Renderer> rendering::NodeRenderer for ScrollBarButtonR
         let arrow_radius = size / 4.0;
         let arrow_center = context.position + context.size / 2.0;
 
+        // the arrow is a chevron with its tip in the middle
         let mut vertices = match self.direction {
             ButtonDirection::Left => [
                 Vec2 {
-                    x: 0.4.px(),
-                    y: -0.9.px(),
+                    x: 0.3.px(),
+                    y: -0.8.px(),
                 },
                 Vec2 {
-                    x: 0.4.px(),
-                    y: 0.9.px(),
+                    x: -0.4.px(),
+                    y: 0.px(),
                 },
                 Vec2 {
-                    x: -0.6.px(),
-                    y: 0.px(),
+                    x: 0.3.px(),
+                    y: 0.8.px(),
                 },
             ],
             ButtonDirection::Right => [
                 Vec2 {
-                    x: -0.4.px(),
-                    y: -0.9.px(),
+                    x: -0.3.px(),
+                    y: -0.8.px(),
                 },
                 Vec2 {
-                    x: -0.4.px(),
-                    y: 0.9.px(),
+                    x: 0.4.px(),
+                    y: 0.px(),
                 },
                 Vec2 {
-                    x: 0.6.px(),
-                    y: 0.px(),
+                    x: -0.3.px(),
+                    y: 0.8.px(),
                 },
             ],
             ButtonDirection::Up => [
                 Vec2 {
-                    x: -0.9.px(),
-                    y: 0.4.px(),
+                    x: -0.8.px(),
+                    y: 0.3.px(),
                 },
                 Vec2 {
-                    x: 0.9.px(),
-                    y: 0.4.px(),
+                    x: 0.px(),
+                    y: -0.4.px(),
                 },
                 Vec2 {
-                    x: 0.px(),
-                    y: -0.6.px(),
+                    x: 0.8.px(),
+                    y: 0.3.px(),
                 },
             ],
             ButtonDirection::Down => [
                 Vec2 {
-                    x: -0.9.px(),
-                    y: -0.4.px(),
+                    x: -0.8.px(),
+                    y: -0.3.px(),
                 },
                 Vec2 {
-                    x: 0.9.px(),
-                    y: -0.4.px(),
+                    x: 0.px(),
+                    y: 0.4.px(),
                 },
                 Vec2 {
-                    x: 0.px(),
-                    y: 0.6.px(),
+                    x: 0.8.px(),
+                    y: -0.3.px(),
                 },
             ],
         };
@@ -260,9 +264,12 @@ impl<Renderer: rendering::Renderer> rendering::NodeRenderer for ScrollBarButtonR
             *vertex += arrow_center;
         }
 
-        context
-            .renderer
-            .fill_poly(&vertices, context.style.text_color().into())
+        context.renderer.stroke_poly(
+            &vertices,
+            arrow_radius * 0.4,
+            false,
+            context.style.text_color().into(),
+        )
     }
 }
 
@@ -297,18 +304,25 @@ fn scroll_bar_thumb<Renderer: rendering::Renderer>(
         Axis::Y => &[ScrollBar::VERTICAL_THUMB_CLASS],
     };
 
-    let mut thumb = Button::default().with_uid(uid).with_classes(class);
-
     let mut style = Style::default();
     if let Some(size_ratio) = size_ratio {
         style = style
             .with_flex_ratio(size_ratio)
             .with_size_along_axis(axis, Sizing::Grow)
             .with_max_size_along_axis(axis, INITIAL_MAX_SIZE);
-        thumb = thumb.with_style(&style);
     }
 
-    gui.show(thumb)
+    // Not shown as a `Button` because dragging the thumb with the keyboard makes no sense
+    let style = gui
+        .theme()
+        .build_style(Some(&style), class, Button::TYPE_CLASS);
+    Ok(gui
+        .insert_node(
+            Some(uid),
+            &style,
+            NodeContents::text("").with_hit_target(HitTarget::SelfOnly),
+        )?
+        .input_state)
 }
 
 impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
@@ -322,9 +336,31 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
     ) -> WidgetResult<Self::ShowResult> {
         let style = style.with_layout_direction(self.axis.primary_direction());
 
-        let step = self.step.unwrap_or((self.max - self.min) * 0.1);
-        let mut value = self.value.clamp(self.min, self.max);
-        let mut factor = (value - self.min) / (self.max - self.min);
+        // Bounds that aren't finite or are reversed would make clamping panic, and a range of zero
+        // would divide by zero.
+        let min = if self.min.is_finite() { self.min } else { 0.0 };
+        let max = if self.max.is_finite() {
+            self.max.max(min)
+        } else {
+            min
+        };
+        let range = max - min;
+
+        let step = self
+            .step
+            .filter(|step| step.is_finite())
+            .unwrap_or(range * 0.1);
+        let initial_value = if self.value.is_finite() {
+            self.value.clamp(min, max)
+        } else {
+            min
+        };
+        let mut value = initial_value;
+        let mut factor = if range > 0.0 {
+            (value - min) / range
+        } else {
+            0.0
+        };
         let mut opposite_factor = 1.0 - factor;
 
         if let Some(thumb_size_ratio) = self.thumb_size_ratio {
@@ -345,18 +381,34 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
         };
 
         let uid = uid.produce();
-        let dec_button_uid = uid.concat(SCROLL_BAR_DEC_BUTTON_UID);
-        let inc_button_uid = uid.concat(SCROLL_BAR_INC_BUTTON_UID);
-        let thumb_uid = uid.concat(SCROLL_BAR_THUMB_UID);
+        let dec_button_uid = uid.concat(Uid::from_type::<DecButton>());
+        let inc_button_uid = uid.concat(Uid::from_type::<IncButton>());
+        let thumb_uid = uid.concat(Uid::from_type::<Thumb>());
 
         gui.insert_node(
             Some(uid),
             &style,
             NodeContents::builder(|mut gui| {
+                let ui_direction = gui.parent_style().ui_direction;
+
+                // Horizontal scroll bars are mirrored in right-to-left mode, so cursor math is done
+                // in a coordinate space where the axis points away from the leading edge.
+                let mirrored = (self.axis == Axis::X) && (ui_direction == UiDirection::Rtl);
+                let to_leading = |position: Float<Pixel>| {
+                    if mirrored { -position } else { position }
+                };
+                let leading_edge = |position: Float<Pixel>, size: Float<Pixel>| {
+                    if mirrored {
+                        -(position + size)
+                    } else {
+                        position
+                    }
+                };
+
                 let dec_button_response = scroll_bar_button(
                     &mut gui,
                     dec_button_uid,
-                    ButtonDirection::dec_along_axis(self.axis),
+                    ButtonDirection::dec_along_axis(self.axis, ui_direction),
                 )?;
                 if dec_button_response.clicked(MouseButtons::PRIMARY) {
                     value -= step;
@@ -369,13 +421,18 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
                 if thumb_response.clicked(MouseButtons::PRIMARY) {
                     let thumb_pos = gui
                         .previous_state(thumb_uid)
-                        .map(|state| state.position.along_axis(self.axis))
+                        .map(|state| {
+                            leading_edge(
+                                state.position.along_axis(self.axis),
+                                state.size.along_axis(self.axis),
+                            )
+                        })
                         .unwrap_or_default();
-                    let thumb_offset = gui
-                        .global_input_state()
-                        .cursor_position()
-                        .along_axis(self.axis)
-                        - thumb_pos;
+                    let thumb_offset = to_leading(
+                        gui.global_input_state()
+                            .last_cursor_position()
+                            .along_axis(self.axis),
+                    ) - thumb_pos;
 
                     gui.persistent_state_mut(uid)
                         .insert(PersistentStateKey::ScrollBarThumbMouseOffset, thumb_offset);
@@ -383,10 +440,9 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
                     let (scroll_bar_pos, scroll_bar_size) = gui
                         .previous_state(uid)
                         .map(|state| {
-                            (
-                                state.position.along_axis(self.axis),
-                                state.size.along_axis(self.axis),
-                            )
+                            let position = state.position.along_axis(self.axis);
+                            let size = state.size.along_axis(self.axis);
+                            (leading_edge(position, size), size)
                         })
                         .unwrap_or_default();
                     let left_button_size = gui
@@ -408,7 +464,10 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
                         .unwrap_or(thumb_size / 2.0);
 
                     let parent_style = gui.computed_parent_style();
-                    let padding = parent_style.padding().along_axis(self.axis);
+                    let mut padding = parent_style.padding().along_axis(self.axis);
+                    if mirrored {
+                        padding.reverse();
+                    }
                     let spacing = parent_style.child_spacing();
 
                     let scroll_space = scroll_bar_size
@@ -419,17 +478,20 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
                         - padding[1]
                         - spacing * 4.0;
 
-                    let scroll_position = gui
-                        .global_input_state()
-                        .cursor_position()
-                        .along_axis(self.axis)
-                        - scroll_bar_pos
+                    let scroll_position = to_leading(
+                        gui.global_input_state()
+                            .last_cursor_position()
+                            .along_axis(self.axis),
+                    ) - scroll_bar_pos
                         - left_button_size
                         - thumb_mouse_offset
                         - padding[0]
                         - spacing * 2.0;
 
-                    value = (scroll_position / scroll_space) * (self.max - self.min);
+                    // without space to move in, e.g. while the bar has no size, the thumb stays put
+                    if scroll_space > 0.px() {
+                        value = min + (scroll_position / scroll_space) * range;
+                    }
                 }
 
                 gui.insert_node(None, &trailing_space_style, NodeContents::EMPTY)?;
@@ -437,21 +499,163 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for ScrollBarData {
                 let inc_button_response = scroll_bar_button(
                     &mut gui,
                     inc_button_uid,
-                    ButtonDirection::inc_along_axis(self.axis),
+                    ButtonDirection::inc_along_axis(self.axis, ui_direction),
                 )?;
                 if inc_button_response.clicked(MouseButtons::PRIMARY) {
                     value += step;
                 }
 
-                Ok(value.clamp(self.min, self.max))
+                // a disabled scroll bar keeps its value and leaves the wheel to its ancestors
+                let enabled = gui.computed_parent_style().enabled();
+                if !enabled {
+                    return Ok(initial_value);
+                }
+
+                if gui.parent_input_state().is_hovered() {
+                    let line = gui.scroll_step().to_pixel(gui.scale_factor());
+                    let input_state = gui.global_input_state_mut();
+                    let mut delta = input_state.consume_scroll(self.axis).total();
+                    if self.axis == Axis::X {
+                        // a vertical wheel scrolls horizontal bars as well
+                        delta += input_state.consume_scroll(Axis::Y).total();
+                    }
+
+                    if line > 0.px() {
+                        // scrolling down moves towards the end, like in a scroll view
+                        value -= (delta / line) * step;
+                    }
+                }
+
+                if !value.is_finite() {
+                    value = initial_value;
+                }
+                Ok(value.clamp(min, max))
             }),
         )?
         .result
     }
 }
 
+/// When the scroll bar of a [`ScrollView`] is shown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollBarVisibility {
+    /// Shown while the content overflows. Once shown, the bar is only hidden again when the
+    /// content fits with [`ScrollView::AUTO_HIDE_TOLERANCE`] to spare, so content right at the
+    /// boundary doesn't make it flicker. In between it is shown disabled.
+    #[default]
+    Auto,
+    /// Always shown, disabled while there is nothing to scroll.
+    AlwaysShow,
+    /// Shown while the content overflows, otherwise its space is kept empty so the content never
+    /// shifts.
+    AlwaysReserveSpace,
+    /// Floats above the end edge of the content while it overflows, so it never takes any space.
+    Overlay,
+}
+
+/// Where the content of a [`ScrollView`] comes to rest.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollSnap {
+    /// At any offset
+    #[default]
+    None,
+    /// With the start edge of one of its children at the start of the view. Scroll wheels move by
+    /// one child at a time, however large each child is.
+    Item,
+}
+
+/// When a [`ScrollView`] is scrolled with the keyboard. Arrow keys scroll like a scroll wheel
+/// does by one line, vertical views scroll by their size with <kbd>Page Up</kbd> and
+/// <kbd>Page Down</kbd>, and <kbd>Home</kbd> and <kbd>End</kbd> scroll to either end. Only keys
+/// without modifiers that the focused widget didn't consume scroll, and only the innermost view
+/// that qualifies.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyboardScroll {
+    /// Never
+    Disabled,
+    /// While the view or one of its descendants is focused
+    FocusWithin,
+    /// While the view or one of its descendants is focused, or while the view is hovered
+    #[default]
+    FocusWithinOrHovered,
+}
+
+/// Shared between a snapping [`ScrollView`] and the layout, which records the edges of the content
+/// children and snaps the stored offset to them once it is at rest.
+#[derive(Debug, Default)]
+pub(crate) struct ScrollSnapState {
+    /// The axis the edges were recorded along
+    pub(crate) axis: Option<Axis>,
+    /// Whether the offset gets snapped to the nearest edge during layout
+    pub(crate) at_rest: bool,
+    /// Until when the view waits for more precise scroll input before it snaps
+    pub(crate) settle_time: Option<Instant>,
+    /// Offsets that put the start edge of a child at the start of the view, in ascending order.
+    /// They are clamped to the maximum offset, so the end of the content can always be reached.
+    pub(crate) edges: Vec<Float<Pixel>>,
+}
+
+impl ScrollSnapState {
+    #[must_use]
+    pub(crate) fn nearest_edge(&self, offset: Float<Pixel>) -> Option<Float<Pixel>> {
+        let next = self.edges.partition_point(|&edge| edge < offset);
+        let after = self.edges.get(next).copied();
+        let before = next.checked_sub(1).map(|index| self.edges[index]);
+        match (before, after) {
+            (Some(before), Some(after)) => Some(if (offset - before) <= (after - offset) {
+                before
+            } else {
+                after
+            }),
+            (before, after) => before.or(after),
+        }
+    }
+
+    /// Moves from `from` to the edge nearest to `to`, but at least to the next edge in that
+    /// direction.
+    #[must_use]
+    fn step(&self, from: Float<Pixel>, to: Float<Pixel>) -> Float<Pixel> {
+        if to == from {
+            return to;
+        }
+        let Some(nearest) = self.nearest_edge(to) else {
+            return to;
+        };
+
+        if (to > from) && (nearest <= from) {
+            let next = self.edges.partition_point(|&edge| edge <= from);
+            self.edges.get(next).copied().unwrap_or(nearest)
+        } else if (to < from) && (nearest >= from) {
+            let previous = self.edges.partition_point(|&edge| edge < from);
+            previous
+                .checked_sub(1)
+                .map_or(nearest, |index| self.edges[index])
+        } else {
+            nearest
+        }
+    }
+}
+
 pub struct ScrollViewData {
     axis: Axis,
+    proportional_resize: bool,
+    item_height: Option<AbsoluteMeasurement>,
+    snap: ScrollSnap,
+    end_threshold: AbsoluteMeasurement,
+    scroll_bar_visibility: ScrollBarVisibility,
+    keyboard_scroll: KeyboardScroll,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollViewResponse<T> {
+    pub result: T,
+    /// The view got within the end threshold of its maximum scroll offset during this frame.
+    ///
+    /// This is edge-triggered: while the view stays near the end it is only set again once the
+    /// content size changed, so it can be used to request the next page of items directly.
+    pub near_end: bool,
+    /// The scroll offset was changed by the user during this frame.
+    pub scrolled_this_frame: bool,
 }
 
 pub type ScrollView<'style, 'classes> = Widget<'style, 'classes, ScrollViewData>;
@@ -461,11 +665,36 @@ impl ScrollView<'_, '_> {
         StyleClass::new_static("###horizontal_scroll_view");
     pub const VERTICAL_TYPE_CLASS: StyleClass = StyleClass::new_static("###vertical_scroll_view");
 
+    /// How much room the content has to leave before an [`Auto`](ScrollBarVisibility::Auto)
+    /// scroll bar is hidden again.
+    pub const AUTO_HIDE_TOLERANCE: AbsoluteMeasurement = AbsoluteMeasurement::Point(Float::pt(4.0));
+
+    /// How long a snapping view waits after the last precise scroll input, like from a touchpad,
+    /// before it snaps.
+    pub const SNAP_SETTLE_DELAY: Duration = Duration::from_millis(150);
+
+    /// How close to an end of the view the cursor has to be for a drag to scroll the view, see
+    /// [`ByorGuiContext::request_drag_autoscroll`]. The view scrolls faster the closer the cursor
+    /// gets to the end, up to [`AUTOSCROLL_SPEED`](Self::AUTOSCROLL_SPEED) per second.
+    pub const AUTOSCROLL_EDGE: AbsoluteMeasurement = AbsoluteMeasurement::Point(Float::pt(24.0));
+    pub const AUTOSCROLL_SPEED: AbsoluteMeasurement = AbsoluteMeasurement::Point(Float::pt(600.0));
+    /// How often a view is rebuilt while it autoscrolls.
+    pub const AUTOSCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
     #[track_caller]
     #[must_use]
     #[inline]
     pub fn new(axis: Axis) -> Self {
-        ScrollViewData { axis }.into()
+        ScrollViewData {
+            axis,
+            proportional_resize: false,
+            item_height: None,
+            snap: ScrollSnap::None,
+            end_threshold: AbsoluteMeasurement::Pixel(Float::px(0.0)),
+            scroll_bar_visibility: ScrollBarVisibility::Auto,
+            keyboard_scroll: KeyboardScroll::FocusWithinOrHovered,
+        }
+        .into()
     }
 
     #[track_caller]
@@ -481,6 +710,117 @@ impl ScrollView<'_, '_> {
     pub fn vertical() -> Self {
         Self::new(Axis::Y)
     }
+
+    #[must_use]
+    #[inline]
+    pub fn proportional_resize(&self) -> bool {
+        self.data().proportional_resize
+    }
+
+    /// If enabled, the scroll offset is scaled with the content size when the content is resized,
+    /// so the same relative position stays in view.
+    #[must_use]
+    #[inline]
+    pub fn with_proportional_resize(self, proportional_resize: bool) -> Self {
+        self.map_data(|data| ScrollViewData {
+            proportional_resize,
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn item_height(&self) -> Option<AbsoluteMeasurement> {
+        self.data().item_height
+    }
+
+    /// If set, scroll wheels move the content by whole items instead of by lines. Precise devices
+    /// like touchpads still scroll by exactly the distance they report.
+    #[must_use]
+    #[inline]
+    pub fn with_item_height(self, item_height: impl Into<AbsoluteMeasurement>) -> Self {
+        self.map_data(|data| ScrollViewData {
+            item_height: Some(item_height.into()),
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn snap(&self) -> ScrollSnap {
+        self.data().snap
+    }
+
+    /// Snapping uses the sizes of the children from the previous frame and takes precedence over
+    /// [`with_item_height`](Self::with_item_height). Dragging the thumb scrolls continuously and
+    /// only snaps once the thumb is released.
+    #[must_use]
+    #[inline]
+    pub fn with_snap(self, snap: ScrollSnap) -> Self {
+        self.map_data(|data| ScrollViewData { snap, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn end_threshold(&self) -> AbsoluteMeasurement {
+        self.data().end_threshold
+    }
+
+    /// How close to the end the content has to be scrolled for
+    /// [`ScrollViewResponse::near_end`] to be set.
+    #[must_use]
+    #[inline]
+    pub fn with_end_threshold(self, end_threshold: impl Into<AbsoluteMeasurement>) -> Self {
+        self.map_data(|data| ScrollViewData {
+            end_threshold: end_threshold.into(),
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn scroll_bar_visibility(&self) -> ScrollBarVisibility {
+        self.data().scroll_bar_visibility
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_scroll_bar_visibility(self, scroll_bar_visibility: ScrollBarVisibility) -> Self {
+        self.map_data(|data| ScrollViewData {
+            scroll_bar_visibility,
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn keyboard_scroll(&self) -> KeyboardScroll {
+        self.data().keyboard_scroll
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_keyboard_scroll(self, keyboard_scroll: KeyboardScroll) -> Self {
+        self.map_data(|data| ScrollViewData {
+            keyboard_scroll,
+            ..data
+        })
+    }
+}
+
+/// Rounds a line based scroll delta to whole items, moving by at least one item.
+#[must_use]
+fn quantize_scroll_delta(delta: Float<Pixel>, item_height: Float<Pixel>) -> Float<Pixel> {
+    if (delta == 0.px()) || (item_height <= 0.px()) {
+        return delta;
+    }
+
+    let items = (delta / item_height).abs().round().max(1.0);
+    if delta < 0.px() {
+        -item_height * items
+    } else {
+        item_height * items
+    }
 }
 
 impl WidgetData for ScrollViewData {
@@ -494,7 +834,7 @@ impl WidgetData for ScrollViewData {
 }
 
 impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for ScrollViewData {
-    type ShowResult<T> = T;
+    type ShowResult<T> = ScrollViewResponse<T>;
 
     fn show<R>(
         self,
@@ -530,8 +870,10 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for ScrollView
                     &parent_style,
                     gui.parent_input_state(),
                     true,
+                    gui.theme(),
                     INITIAL_ENABLED,
                 ),
+                gui.theme(),
                 INITIAL_CHILD_SPACING,
             );
 
@@ -542,12 +884,15 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for ScrollView
             .with_child_spacing(scroll_bar_child_spacing * 2.0);
 
         let scroll_bar_style = Style::default().with_size_along_axis(self.axis, Sizing::Grow);
+        // shown while there is nothing to scroll
+        let disabled_scroll_bar_style = scroll_bar_style.clone().with_enabled(false);
 
         gui.insert_node(
             None,
             &scroll_view_style,
             NodeContents::builder(|mut gui| {
-                let cascaded_style = style.cascade(&parent_style, gui.parent_input_state());
+                let cascaded_style =
+                    style.cascade(&parent_style, gui.parent_input_state(), gui.theme());
                 let scroll_container_style = cascaded_style
                     .as_style()
                     .with_width(Sizing::Grow)
@@ -562,14 +907,19 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for ScrollView
                     .with_border_width(0.0.px())
                     .with_background(Color::TRANSPARENT);
 
-                let mut scroll: Float<Pixel> = gui
-                    .persistent_state(uid)
-                    .get(self.axis.persistent_state_scroll_key())
-                    .copied()
-                    .unwrap_or_default();
+                // negative offsets are never valid, the upper bound is only known after layout
+                let mut scroll = gui.stored_scroll(uid, self.axis).max(0.px());
                 let mut thumb_size_ratio = 0.5;
                 let mut max_scroll = 0.px();
-
+                // how far the content reaches past the end of the view, negative if it fits
+                let mut overflow = 0.px();
+                let mut view_size = 0.px();
+                let mut page_size = 0.px();
+                let mut layout_known = false;
+
+                // views inside of this one qualify for keyboard scrolling and autoscrolling first
+                let focus_outside = std::mem::take(&mut gui.data.focused_node_inserted);
+                let autoscroll_outside = std::mem::take(&mut gui.data.drag_autoscroll);
                 let response = gui.insert_node(
                     Some(uid),
                     &scroll_container_style,
@@ -577,42 +927,327 @@ impl<Renderer: rendering::Renderer> ContainerWidgetData<Renderer> for ScrollView
                         if let Some(previous_state) = gui.previous_state(uid) {
                             let padding =
                                 gui.computed_parent_style().padding().along_axis(self.axis);
-                            let container_size =
-                                previous_state.size.along_axis(self.axis) - padding[0] - padding[1];
+                            let container_size = (previous_state.size.along_axis(self.axis)
+                                - padding[0]
+                                - padding[1])
+                                .max(0.px());
                             let content_size = previous_state.content_size.along_axis(self.axis);
                             let available_size = container_size - content_size;
-                            thumb_size_ratio = container_size / content_size;
-                            max_scroll = (-available_size).max(0.px());
+                            if content_size > 0.px() {
+                                thumb_size_ratio = container_size / content_size;
+                            }
+                            overflow = -available_size;
+                            max_scroll = overflow.max(0.px());
+                            view_size = previous_state.size.along_axis(self.axis);
+                            page_size = container_size;
+                            // the state is empty before the first layout
+                            layout_known = container_size > 0.px();
                         }
 
                         contents(gui)
                     }),
                 )?;
+                let focus_within = gui.data.focused_node_inserted;
+                gui.data.focused_node_inserted |= focus_outside;
+                let autoscroll =
+                    std::mem::replace(&mut gui.data.drag_autoscroll, autoscroll_outside);
+
+                // The content may have shrunk since the offset was stored. Before the first layout
+                // the bounds are unknown, so a restored offset is kept until then.
+                if layout_known {
+                    scroll = scroll.max(0.px()).min(max_scroll);
+                }
+                let unscrolled = scroll;
+
+                let scroll_bar_uid = uid.concat(Uid::from_type::<ScrollBarData>());
+                let thumb_uid = scroll_bar_uid.concat(Uid::from_type::<Thumb>());
+                let dragging = gui
+                    .data
+                    .compute_node_input_state(Some(thumb_uid))
+                    .pressed(MouseButtons::PRIMARY);
+                // taken out for the rest of the frame and stored again at the end
+                let mut snap_state = (self.snap == ScrollSnap::Item).then(|| {
+                    gui.persistent_state_mut(uid)
+                        .get_mut::<ScrollSnapState>(PersistentStateKey::ScrollSnap)
+                        .map(std::mem::take)
+                        .unwrap_or_default()
+                });
+                if let Some(snap_state) = &mut snap_state
+                    && (snap_state.axis != Some(self.axis))
+                {
+                    // recorded along another axis
+                    *snap_state = ScrollSnapState::default();
+                }
+
+                // A view without size, e.g. in a minimized window, can't be scrolled. Its offset is
+                // kept for when it gets its size back.
+                let scrollable = layout_known && (max_scroll > 0.px());
+                let shows_scroll_bar = match self.scroll_bar_visibility {
+                    ScrollBarVisibility::Auto => {
+                        let tolerance = ScrollView::AUTO_HIDE_TOLERANCE.to_pixel(
+                            gui.scale_factor(),
+                            gui.computed_parent_style().font_size().value(),
+                        );
+                        let was_shown = gui
+                            .persistent_state(uid)
+                            .get::<bool>(PersistentStateKey::ScrollBarShown)
+                            .copied()
+                            .unwrap_or(false);
+                        let shown = if was_shown {
+                            overflow > -tolerance
+                        } else {
+                            scrollable
+                        };
+                        gui.persistent_state_mut(uid)
+                            .insert(PersistentStateKey::ScrollBarShown, shown);
+                        shown
+                    }
+                    ScrollBarVisibility::AlwaysShow => true,
+                    ScrollBarVisibility::AlwaysReserveSpace | ScrollBarVisibility::Overlay => {
+                        scrollable
+                    }
+                };
+
+                if scrollable && response.is_hovered() {
+                    let ConsumedScroll {
+                        precise: precise_delta,
+                        lines: mut line_delta,
+                    } = gui.global_input_state_mut().consume_scroll(self.axis);
+
+                    if snap_state.is_none()
+                        && let Some(item_height) = self.item_height
+                    {
+                        let item_height = item_height.to_pixel(
+                            gui.scale_factor(),
+                            gui.computed_parent_style().font_size().value(),
+                        );
+                        line_delta = quantize_scroll_delta(line_delta, item_height);
+                    }
+
+                    // Scroll is subtractive in layouting, so we need to subtract here as well
+                    scroll -= precise_delta;
+                    if let Some(snap_state) = &mut snap_state {
+                        scroll = snap_state.step(scroll, scroll - line_delta);
+                        if precise_delta != 0.px() {
+                            snap_state.settle_time =
+                                Some(gui.time() + ScrollView::SNAP_SETTLE_DELAY);
+                        }
+                    } else {
+                        scroll -= line_delta;
+                    }
+                }
 
-                if max_scroll > 0.px() {
-                    if response.is_hovered() {
-                        // Scroll is subtractive in layouting, so we need to subtract here as well
-                        scroll -= gui
-                            .global_input_state()
-                            .scroll_delta()
-                            .along_axis(self.axis);
+                let keyboard_scroll = match self.keyboard_scroll {
+                    KeyboardScroll::Disabled => false,
+                    KeyboardScroll::FocusWithin => focus_within,
+                    KeyboardScroll::FocusWithinOrHovered => focus_within || response.is_hovered(),
+                };
+                if scrollable && keyboard_scroll {
+                    let line = match self.item_height {
+                        Some(item_height) if snap_state.is_none() => item_height.to_pixel(
+                            gui.scale_factor(),
+                            gui.computed_parent_style().font_size().value(),
+                        ),
+                        _ => gui.scroll_step().to_pixel(gui.scale_factor()),
+                    };
+                    // the content of right-to-left views starts at the right
+                    let mirrored = (self.axis == Axis::X)
+                        && (gui.parent_style().ui_direction == UiDirection::Rtl);
+                    let (backward, forward) = match (self.axis, mirrored) {
+                        (Axis::X, false) => (NamedKey::ArrowLeft, NamedKey::ArrowRight),
+                        (Axis::X, true) => (NamedKey::ArrowRight, NamedKey::ArrowLeft),
+                        (Axis::Y, _) => (NamedKey::ArrowUp, NamedKey::ArrowDown),
+                    };
+                    let pages = self.axis == Axis::Y;
+
+                    // repeated presses move further, so a held key keeps scrolling
+                    gui.global_input_state_mut().retain_key_events(|event| {
+                        let KeyEvent::Pressed { key, modifiers, .. } = event else {
+                            return true;
+                        };
+                        let Key::Named(key) = key else {
+                            return true;
+                        };
+                        if !modifiers.is_empty() {
+                            return true;
+                        }
+
+                        let target = match *key {
+                            key if key == backward => scroll - line,
+                            key if key == forward => scroll + line,
+                            NamedKey::PageUp if pages => scroll - page_size,
+                            NamedKey::PageDown if pages => scroll + page_size,
+                            NamedKey::Home => 0.px(),
+                            NamedKey::End => max_scroll,
+                            _ => return true,
+                        };
+                        let target = target.clamp(0.px(), max_scroll);
+                        scroll = match &snap_state {
+                            Some(snap_state) => snap_state.step(scroll, target),
+                            None => target,
+                        };
+                        false
+                    });
+                }
+
+                if autoscroll && !scrollable {
+                    // left to the views around this one
+                    gui.data.drag_autoscroll = true;
+                } else if autoscroll && let Some(previous_state) = gui.previous_state(uid) {
+                    let font_size = gui.computed_parent_style().font_size().value();
+                    let edge = ScrollView::AUTOSCROLL_EDGE
+                        .to_pixel(gui.scale_factor(), font_size)
+                        .min(view_size / 2.0);
+                    let start = previous_state.position.along_axis(self.axis);
+                    let cursor = gui
+                        .global_input_state()
+                        .last_cursor_position()
+                        .along_axis(self.axis);
+                    let mut depth = if cursor < (start + edge) {
+                        cursor - (start + edge)
+                    } else if cursor > (start + view_size - edge) {
+                        cursor - (start + view_size - edge)
+                    } else {
+                        0.px()
+                    };
+                    // the content of right-to-left views starts at the right
+                    if (self.axis == Axis::X)
+                        && (gui.parent_style().ui_direction == UiDirection::Rtl)
+                    {
+                        depth = -depth;
                     }
 
-                    let scroll_bar = ScrollBar::new(self.axis)
-                        .with_uid(uid.concat(SCROLL_BAR_UID))
+                    if (depth != 0.px()) && (edge > 0.px()) {
+                        let speed = ScrollView::AUTOSCROLL_SPEED
+                            .to_pixel(gui.scale_factor(), font_size)
+                            * (depth / edge).clamp(-1.0, 1.0);
+                        // a long pause between two frames of the drag doesn't make the view jump
+                        let delta_time = gui.delta_time().min(ScrollView::AUTOSCROLL_INTERVAL * 4);
+                        scroll =
+                            (scroll + speed * delta_time.as_secs_f32()).clamp(0.px(), max_scroll);
+                        gui.request_animation_frame(gui.time() + ScrollView::AUTOSCROLL_INTERVAL);
+                    }
+                }
+                let before_scroll_bar = scroll;
+
+                let scroll_bar = ScrollBar::new(self.axis)
+                    .with_uid(scroll_bar_uid)
+                    .with_step(gui.scroll_step().to_pixel(gui.scale_factor()).value());
+                let scroll_bar = if scrollable {
+                    scroll_bar
                         .with_value(scroll.value())
-                        .with_min(0.0)
                         .with_max(max_scroll.value())
-                        .with_step((POINTS_PER_SCROLL_LINE * gui.scale_factor()).value())
                         .with_thumb_size_ratio(thumb_size_ratio)
-                        .with_style(&scroll_bar_style);
-                    scroll = gui.show(scroll_bar)?.px();
+                        .with_style(&scroll_bar_style)
+                } else {
+                    scroll_bar
+                        .with_thumb_size_ratio(1.0)
+                        .with_style(&disabled_scroll_bar_style)
+                };
+
+                if self.scroll_bar_visibility == ScrollBarVisibility::Overlay {
+                    if shows_scroll_bar {
+                        // floats along the end edge of the whole view, including its padding
+                        let padding = gui.computed_parent_style().padding().along_axis(self.axis);
+                        let overlay_style = Style::default()
+                            .with_size_along_axis(self.axis, view_size + padding[0] + padding[1]);
+                        let (x, y) = match self.axis {
+                            Axis::X => (PopupPosition::ParentStart, PopupPosition::ParentEnd),
+                            Axis::Y => (PopupPosition::ParentEnd, PopupPosition::ParentStart),
+                        };
+
+                        scroll = gui
+                            .insert_floating_node(
+                                uid.concat(Uid::from_type::<OverlayBar>()),
+                                FloatPosition::Popup { x, y },
+                                &overlay_style,
+                                NodeContents::builder(|mut gui| gui.show(scroll_bar)),
+                            )?
+                            .result?
+                            .px();
+                    }
+                } else if shows_scroll_bar {
+                    let value = gui.show(scroll_bar)?.px();
+                    if scrollable {
+                        scroll = value;
+                    }
+                } else if self.scroll_bar_visibility == ScrollBarVisibility::AlwaysReserveSpace {
+                    // The bar is laid out to get its width, but clipped away entirely so it can
+                    // neither be seen nor hovered.
+                    let spacer_style = Style::default().with_size_along_axis(self.axis, 0.px());
+                    gui.insert_node(
+                        None,
+                        &spacer_style,
+                        NodeContents::builder(|mut gui| gui.show(scroll_bar)),
+                    )?
+                    .result?;
+                }
+
+                let end_threshold = self.end_threshold.to_pixel(
+                    gui.scale_factor(),
+                    gui.computed_parent_style().font_size().value(),
+                );
+                if let Some(snap_state) = &mut snap_state {
+                    // the buttons and the track step like the wheel, only the thumb is continuous
+                    if !dragging && (scroll != before_scroll_bar) {
+                        scroll = snap_state.step(before_scroll_bar, scroll);
+                    }
+
+                    let time = gui.time();
+                    if let Some(settle_time) = snap_state.settle_time {
+                        if time >= settle_time {
+                            snap_state.settle_time = None;
+                        } else {
+                            gui.request_animation_frame(settle_time);
+                        }
+                    }
+
+                    snap_state.axis = Some(self.axis);
+                    snap_state.at_rest = !dragging && snap_state.settle_time.is_none();
+                    if snap_state.at_rest
+                        && let Some(edge) = snap_state.nearest_edge(scroll)
+                    {
+                        scroll = edge;
+                    }
+                } else if let Some(snap_state) = gui
+                    .persistent_state_mut(uid)
+                    .get_mut::<ScrollSnapState>(PersistentStateKey::ScrollSnap)
+                {
+                    // snapping was turned off
+                    snap_state.at_rest = false;
+                }
+
+                if layout_known {
+                    scroll = scroll.clamp(0.px(), max_scroll);
                 }
+                let is_near_end = layout_known && (scroll >= max_scroll - end_threshold);
 
-                gui.persistent_state_mut(uid)
-                    .insert(self.axis.persistent_state_scroll_key(), scroll);
+                let persistent_state = gui.persistent_state_mut(uid);
+                // Remembers the maximum offset the view was last reported near the end at
+                let last_near_end: Option<Float<Pixel>> = persistent_state
+                    .get(PersistentStateKey::ScrollNearEnd)
+                    .copied()
+                    .flatten();
+                let near_end = is_near_end && (last_near_end != Some(max_scroll));
+
+                persistent_state.insert(self.axis.persistent_state_scroll_key(), scroll);
+                persistent_state.insert(
+                    PersistentStateKey::ProportionalScroll,
+                    self.proportional_resize,
+                );
+                persistent_state.insert(
+                    PersistentStateKey::ScrollNearEnd,
+                    is_near_end.then_some(max_scroll),
+                );
+                if let Some(snap_state) = snap_state {
+                    persistent_state.insert(PersistentStateKey::ScrollSnap, snap_state);
+                }
 
-                Ok(response.result)
+                Ok(ScrollViewResponse {
+                    result: response.result,
+                    near_end,
+                    scrolled_this_frame: scroll != unscrolled,
+                })
             }),
         )?
         .result
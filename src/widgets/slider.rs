@@ -0,0 +1,532 @@
+use super::*;
+use crate::input::*;
+use crate::style::axis::*;
+use crate::theme::{ColorRole, StyleClass};
+use crate::*;
+
+/// Identifies the thumbs of a range slider through [`Uid::from_type`].
+enum StartThumb {}
+enum EndThumb {}
+
+/// The style classes of the parts of sliders.
+pub struct Slider;
+
+impl Slider {
+    /// The parts of the track outside the selected range.
+    pub const TRACK_CLASS: StyleClass = StyleClass::new_static("###slider_track");
+    /// Replaces the track style between the thumbs of a [`RangeSlider`]. Without a style for
+    /// this class the selected range gets the [`ColorRole::Accent`] background.
+    pub const ACTIVE_TRACK_CLASS: StyleClass = StyleClass::new_static("###slider_active_track");
+    pub const THUMB_CLASS: StyleClass = StyleClass::new_static("###slider_thumb");
+}
+
+/// What happens when a thumb of a [`RangeSlider`] is moved past the other one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThumbCrossing {
+    /// The thumb stops at the value of the other one
+    #[default]
+    Blocked,
+    /// The thumbs swap places, so the moved thumb continues as the other end of the range
+    Swap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeThumb {
+    /// The thumb of the lower value
+    Start,
+    /// The thumb of the higher value
+    End,
+}
+
+impl RangeThumb {
+    #[must_use]
+    #[inline]
+    fn uid(self) -> Uid {
+        match self {
+            Self::Start => Uid::from_type::<StartThumb>(),
+            Self::End => Uid::from_type::<EndThumb>(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RangeSliderResponse {
+    /// The lower and higher value after this frame.
+    pub range: (f32, f32),
+    /// The thumb that was moved during this frame, if the range changed.
+    pub changed: Option<RangeThumb>,
+}
+
+/// The thumb being dragged, kept in the persistent state of a range slider.
+#[derive(Debug, Clone, Copy)]
+struct RangeDrag {
+    /// [`None`] while both thumbs are at the same value, until the drag direction decides
+    thumb: Option<RangeThumb>,
+    /// The distance of the cursor from the position of the dragged value
+    anchor: Float<Pixel>,
+}
+
+/// Where the thumbs of a range slider were in the previous frame, along its leading direction.
+struct TrackGeometry {
+    /// The cursor relative to the leading edge of the slider's content
+    cursor: Float<Pixel>,
+    /// How far the thumbs can move
+    space: Float<Pixel>,
+    thumb_size: Float<Pixel>,
+    spacing: Float<Pixel>,
+}
+
+impl TrackGeometry {
+    /// The distance of the thumb's leading edge from the position of its value.
+    #[must_use]
+    fn thumb_offset(&self, thumb: RangeThumb) -> Float<Pixel> {
+        match thumb {
+            RangeThumb::Start => self.spacing,
+            RangeThumb::End => self.thumb_size + self.spacing * 3.0,
+        }
+    }
+
+    #[must_use]
+    fn thumb_center(&self, thumb: RangeThumb, factor: f32) -> Float<Pixel> {
+        self.space * factor + self.thumb_offset(thumb) + self.thumb_size / 2.0
+    }
+}
+
+pub struct RangeSliderData<'range> {
+    range: &'range mut (f32, f32),
+    min: f32,
+    max: f32,
+    step: Option<f32>,
+    crossing: ThumbCrossing,
+}
+
+/// A slider with two thumbs that select a range of values between its minimum and maximum.
+///
+/// Pressing the track moves the nearer thumb to the cursor. While both thumbs are at the same
+/// value, dragging either of them moves the one that can go in the direction of the drag. A
+/// focused thumb is moved with the arrow keys, <kbd>Home</kbd> and <kbd>End</kbd>.
+pub type RangeSlider<'range, 'style, 'classes> = Widget<'style, 'classes, RangeSliderData<'range>>;
+
+impl<'range> RangeSlider<'range, '_, '_> {
+    pub const TYPE_CLASS: StyleClass = StyleClass::new_static("###range_slider");
+
+    /// Creates a range slider between 0 and 1 for the lower and higher value of `range`.
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn new(range: &'range mut (f32, f32)) -> Self {
+        RangeSliderData {
+            range,
+            min: 0.0,
+            max: 1.0,
+            step: None,
+            crossing: ThumbCrossing::Blocked,
+        }
+        .into()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn min(&self) -> f32 {
+        self.data().min
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_min(self, min: f32) -> Self {
+        self.map_data(|data| RangeSliderData { min, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn max(&self) -> f32 {
+        self.data().max
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_max(self, max: f32) -> Self {
+        self.map_data(|data| RangeSliderData { max, ..data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn step(&self) -> Option<f32> {
+        self.data().step
+    }
+
+    /// Snaps the values to multiples of `step` from the minimum. The arrow keys move a thumb by
+    /// one step, or by a tenth of the range without a step.
+    #[must_use]
+    #[inline]
+    pub fn with_step(self, step: f32) -> Self {
+        self.map_data(|data| RangeSliderData {
+            step: Some(step),
+            ..data
+        })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn crossing(&self) -> ThumbCrossing {
+        self.data().crossing
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_crossing(self, crossing: ThumbCrossing) -> Self {
+        self.map_data(|data| RangeSliderData { crossing, ..data })
+    }
+}
+
+impl WidgetData for RangeSliderData<'_> {
+    #[inline]
+    fn type_class(&self) -> StyleClass {
+        RangeSlider::TYPE_CLASS
+    }
+}
+
+/// The range of a slider with its bounds and step made valid.
+#[derive(Clone, Copy)]
+struct SliderScale {
+    min: f32,
+    max: f32,
+    step: Option<f32>,
+}
+
+impl SliderScale {
+    #[must_use]
+    fn new(min: f32, max: f32, step: Option<f32>) -> Self {
+        // like in scroll bars, bounds that aren't finite or are reversed would make clamping panic
+        let min = if min.is_finite() { min } else { 0.0 };
+        let max = if max.is_finite() { max.max(min) } else { min };
+        let step = step.filter(|step| step.is_finite() && (*step > 0.0));
+        Self { min, max, step }
+    }
+
+    #[must_use]
+    fn range(self) -> f32 {
+        self.max - self.min
+    }
+
+    #[must_use]
+    fn snap(self, value: f32) -> f32 {
+        if !value.is_finite() {
+            return self.min;
+        }
+
+        let value = match self.step {
+            Some(step) => self.min + ((value - self.min) / step).round() * step,
+            None => value,
+        };
+        value.clamp(self.min, self.max)
+    }
+
+    #[must_use]
+    fn factor(self, value: f32) -> f32 {
+        if self.range() > 0.0 {
+            (value - self.min) / self.range()
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Moves `thumb` to `value` and returns the thumb that ends up at the value, which is the other
+/// one if the thumbs were swapped.
+fn move_thumb(
+    range: &mut (f32, f32),
+    thumb: RangeThumb,
+    value: f32,
+    crossing: ThumbCrossing,
+) -> RangeThumb {
+    let (start, end) = *range;
+    match (thumb, crossing) {
+        (RangeThumb::Start, ThumbCrossing::Swap) if value > end => {
+            *range = (end, value);
+            RangeThumb::End
+        }
+        (RangeThumb::End, ThumbCrossing::Swap) if value < start => {
+            *range = (value, start);
+            RangeThumb::Start
+        }
+        (RangeThumb::Start, _) => {
+            range.0 = value.min(end);
+            RangeThumb::Start
+        }
+        (RangeThumb::End, _) => {
+            range.1 = value.max(start);
+            RangeThumb::End
+        }
+    }
+}
+
+impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for RangeSliderData<'_> {
+    type ShowResult = RangeSliderResponse;
+
+    fn show(
+        self,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        uid: MaybeUid,
+        style: Style,
+    ) -> WidgetResult<Self::ShowResult> {
+        let uid = uid.produce();
+        let style = style.or_else(&style! {
+            width: 12.em(),
+            child_spacing: 0.px(),
+        });
+        let track_style = gui
+            .theme()
+            .build_style(None, &[], Slider::TRACK_CLASS)
+            .or_else(&style! {
+                height: 0.25.em(),
+                cross_axis_alignment: Alignment::Center,
+                background: Gauge::DEFAULT_TRACK_COLOR,
+            });
+        let active_track_style = gui.theme().class_style_or(
+            &Slider::ACTIVE_TRACK_CLASS,
+            || {
+                style! {
+                    background: ColorRole::Accent,
+                }
+            },
+            &track_style,
+        );
+        let thumb_style = gui
+            .theme()
+            .build_style(None, &[], Slider::THUMB_CLASS)
+            .or_else(&style! {
+                width: 1.em(),
+                height: 1.em(),
+                corner_radius: 0.5.em(),
+                border_width: 2.px(),
+                border_color: ColorRole::Accent,
+                background: ColorRole::Surface,
+            });
+
+        let scale = SliderScale::new(self.min, self.max, self.step);
+        let crossing = self.crossing;
+        let (start, end) = *self.range;
+        let (start, end) = (scale.snap(start), scale.snap(end));
+        let initial_range = (start.min(end), start.max(end));
+        let mut range = initial_range;
+
+        let start_factor = scale.factor(range.0);
+        let end_factor = scale.factor(range.1);
+        let leading_track_style = track_style
+            .clone()
+            .with_width(Sizing::Grow)
+            .with_flex_ratio(start_factor);
+        let active_track_style = active_track_style
+            .with_width(Sizing::Grow)
+            .with_flex_ratio(end_factor - start_factor);
+        let trailing_track_style = track_style
+            .with_width(Sizing::Grow)
+            .with_flex_ratio(1.0 - end_factor);
+
+        let contents = NodeContents::builder(|mut gui| -> WidgetResult<Option<RangeThumb>> {
+            let thumb_contents = || {
+                NodeContents::EMPTY
+                    .with_hit_target(HitTarget::SelfOnly)
+                    .with_tab_index(0)
+            };
+
+            let mut thumb_responses = [NodeInputState::default(); 2];
+            gui.insert_node(None, &leading_track_style, NodeContents::EMPTY)?;
+            gui.uid_scope(uid, |gui| -> WidgetResult<()> {
+                thumb_responses[0] = gui
+                    .insert_node(
+                        Some(RangeThumb::Start.uid()),
+                        &thumb_style,
+                        thumb_contents(),
+                    )?
+                    .input_state;
+                Ok(())
+            })?;
+            gui.insert_node(None, &active_track_style, NodeContents::EMPTY)?;
+            gui.uid_scope(uid, |gui| -> WidgetResult<()> {
+                thumb_responses[1] = gui
+                    .insert_node(Some(RangeThumb::End.uid()), &thumb_style, thumb_contents())?
+                    .input_state;
+                Ok(())
+            })?;
+            gui.insert_node(None, &trailing_track_style, NodeContents::EMPTY)?;
+
+            if !gui.computed_parent_style().enabled() {
+                return Ok(None);
+            }
+
+            // Horizontal sliders are mirrored in right-to-left mode, so cursor math is done in a
+            // coordinate space where the axis points away from the leading edge.
+            let mirrored = gui.parent_style().ui_direction == UiDirection::Rtl;
+            let mut moved = None;
+
+            let [start_response, end_response] = thumb_responses;
+            let track_response = gui.parent_input_state();
+            let pressed = [start_response, end_response, track_response]
+                .iter()
+                .any(|response| response.pressed(MouseButtons::PRIMARY));
+            let geometry = track_geometry(&gui, uid, mirrored);
+
+            let mut drag = gui
+                .persistent_state(uid)
+                .get::<Option<RangeDrag>>(PersistentStateKey::RangeSliderDrag)
+                .copied()
+                .flatten()
+                .filter(|_| pressed);
+            let factor = |range: (f32, f32), thumb| match thumb {
+                RangeThumb::Start => scale.factor(range.0),
+                RangeThumb::End => scale.factor(range.1),
+            };
+
+            if let Some(geometry) = &geometry {
+                let clicked_thumb = if start_response.clicked(MouseButtons::PRIMARY) {
+                    Some(RangeThumb::Start)
+                } else if end_response.clicked(MouseButtons::PRIMARY) {
+                    Some(RangeThumb::End)
+                } else {
+                    None
+                };
+
+                if let Some(thumb) = clicked_thumb {
+                    // stacked thumbs are told apart by the direction they are dragged in
+                    drag = Some(RangeDrag {
+                        thumb: (range.0 != range.1).then_some(thumb),
+                        anchor: geometry.cursor - geometry.space * factor(range, thumb),
+                    });
+                } else if track_response.clicked(MouseButtons::PRIMARY) {
+                    let distance = |thumb| {
+                        (geometry.cursor - geometry.thumb_center(thumb, factor(range, thumb)))
+                            .value()
+                            .abs()
+                    };
+                    let thumb = if distance(RangeThumb::Start) <= distance(RangeThumb::End) {
+                        RangeThumb::Start
+                    } else {
+                        RangeThumb::End
+                    };
+
+                    // the thumb jumps to be centered under the cursor
+                    drag = Some(RangeDrag {
+                        thumb: Some(thumb),
+                        anchor: geometry.thumb_offset(thumb) + geometry.thumb_size / 2.0,
+                    });
+                }
+
+                if let Some(drag) = &mut drag
+                    && (geometry.space > 0.px())
+                {
+                    let position = (geometry.cursor - drag.anchor) / geometry.space;
+                    let value = scale.snap(scale.min + position * scale.range());
+                    let thumb = drag.thumb.or(if value < range.0 {
+                        Some(RangeThumb::Start)
+                    } else if value > range.1 {
+                        Some(RangeThumb::End)
+                    } else {
+                        None
+                    });
+
+                    if let Some(thumb) = thumb {
+                        let thumb = move_thumb(&mut range, thumb, value, crossing);
+                        drag.thumb = Some(thumb);
+                        moved = Some(thumb);
+                    }
+                }
+            }
+
+            gui.persistent_state_mut(uid)
+                .insert(PersistentStateKey::RangeSliderDrag, drag);
+
+            let focused_thumb = if start_response.focused {
+                Some(RangeThumb::Start)
+            } else if end_response.focused {
+                Some(RangeThumb::End)
+            } else {
+                None
+            };
+            if let Some(mut thumb) = focused_thumb {
+                let step = scale.step.unwrap_or(scale.range() * 0.1);
+                let (decrease, increase) = if mirrored {
+                    (NamedKey::ArrowRight, NamedKey::ArrowLeft)
+                } else {
+                    (NamedKey::ArrowLeft, NamedKey::ArrowRight)
+                };
+
+                // repeated presses move further, so a held key keeps moving the thumb
+                gui.global_input_state_mut().retain_key_events(|event| {
+                    let KeyEvent::Pressed { key, .. } = event else {
+                        return true;
+                    };
+                    let value = match thumb {
+                        RangeThumb::Start => range.0,
+                        RangeThumb::End => range.1,
+                    };
+                    let value = match key {
+                        Key::Named(key) if (*key == decrease) || (*key == NamedKey::ArrowDown) => {
+                            value - step
+                        }
+                        Key::Named(key) if (*key == increase) || (*key == NamedKey::ArrowUp) => {
+                            value + step
+                        }
+                        Key::Named(NamedKey::Home) => scale.min,
+                        Key::Named(NamedKey::End) => scale.max,
+                        _ => return true,
+                    };
+
+                    thumb = move_thumb(&mut range, thumb, scale.snap(value), crossing);
+                    moved = Some(thumb);
+                    false
+                });
+
+                // the focus follows the value if the thumbs were swapped
+                if Some(thumb) != focused_thumb {
+                    let slider_uid = gui.compute_recursive_uid(uid);
+                    gui.data.focused_node = Some(slider_uid.concat(thumb.uid()));
+                }
+            }
+
+            Ok(moved)
+        })
+        .with_hit_target(HitTarget::Subtree);
+
+        let moved = gui.insert_node(Some(uid), &style, contents)?.result?;
+
+        *self.range = range;
+        Ok(RangeSliderResponse {
+            range,
+            changed: moved.filter(|_| range != initial_range),
+        })
+    }
+}
+
+/// Measures the slider from its state in the previous frame, [`None`] before it was laid out.
+fn track_geometry<Renderer: rendering::Renderer>(
+    gui: &ByorGuiContext<'_, Renderer>,
+    uid: Uid,
+    mirrored: bool,
+) -> Option<TrackGeometry> {
+    let slider = gui.previous_state(uid)?;
+    let thumb = gui.previous_state(uid.concat(RangeThumb::Start.uid()))?;
+
+    let parent_style = gui.computed_parent_style();
+    let mut padding = parent_style.padding().along_axis(Axis::X);
+    let (position, size) = (slider.position.x, slider.size.x);
+    let (leading_edge, cursor) = if mirrored {
+        padding.reverse();
+        (
+            -(position + size),
+            -gui.global_input_state().last_cursor_position().x,
+        )
+    } else {
+        (position, gui.global_input_state().last_cursor_position().x)
+    };
+
+    let thumb_size = thumb.size.x;
+    let spacing = parent_style.child_spacing();
+    Some(TrackGeometry {
+        cursor: cursor - leading_edge - padding[0],
+        space: size - padding[0] - padding[1] - (thumb_size * 2.0) - (spacing * 4.0),
+        thumb_size,
+        spacing,
+    })
+}
@@ -1,13 +1,321 @@
 use super::*;
 use crate::theme::StyleClass;
 use crate::*;
-use parley::{PlainEditor, StyleProperty};
+use parley::{
+    Affinity, BoundingBox, Cursor, PlainEditor, PlainEditorDriver, Selection, StyleProperty,
+};
+use smallbox::smallbox;
 use smol_str::SmolStr;
 use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
+use std::num::NonZeroUsize;
+use std::ops::{Deref, DerefMut, Range};
+use std::time::{Duration, Instant};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Decides whether a text box may contain a text, see [`TextBox::with_filter`].
+pub struct TextFilter<'filter> {
+    filter: SmallBox<dyn Fn(&str) -> bool + 'filter, 2>,
+}
+
+impl<'filter> TextFilter<'filter> {
+    #[must_use]
+    #[inline]
+    pub fn new(filter: impl Fn(&str) -> bool + 'filter) -> Self {
+        Self {
+            filter: smallbox!(filter),
+        }
+    }
+
+    /// Allows whole numbers with an optional leading minus sign.
+    #[must_use]
+    pub fn integer() -> Self {
+        Self::new(|text| {
+            let digits = text.strip_prefix('-').unwrap_or(text);
+            digits.chars().all(|c| c.is_ascii_digit())
+        })
+    }
+
+    /// Allows numbers with an optional leading minus sign and at most one decimal point.
+    #[must_use]
+    pub fn decimal() -> Self {
+        Self::new(|text| {
+            let digits = text.strip_prefix('-').unwrap_or(text);
+            digits.chars().all(|c| c.is_ascii_digit() || (c == '.'))
+                && (digits.matches('.').count() <= 1)
+        })
+    }
+
+    /// Allows at most `max_len` characters.
+    #[must_use]
+    pub fn max_len(max_len: usize) -> Self {
+        Self::new(move |text| text.chars().count() <= max_len)
+    }
+
+    /// Allows only texts that are allowed by both filters.
+    #[must_use]
+    pub fn and(self, other: impl Into<TextFilter<'filter>>) -> Self {
+        let other = other.into();
+        Self::new(move |text| self.matches(text) && other.matches(text))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn matches(&self, text: &str) -> bool {
+        (self.filter)(text)
+    }
+}
+
+impl<'filter, F: Fn(&str) -> bool + 'filter> From<F> for TextFilter<'filter> {
+    #[inline]
+    fn from(filter: F) -> Self {
+        Self::new(filter)
+    }
+}
+
+/// Where word motion, word deletion and double-click selection consider words to end, see
+/// [`TextBox::with_word_boundaries`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WordBoundaryPolicy {
+    /// The word boundaries of Unicode text segmentation (UAX #29). Connecting punctuation like
+    /// `_`, and `.` between letters, are part of words, so `foo_bar.baz` is a single word.
+    #[default]
+    Unicode,
+    /// Words consist of letters, digits and `_`. Every run of other punctuation is a word of its
+    /// own, so `foo_bar.baz/qux` consists of `foo_bar`, `.`, `baz`, `/` and `qux`.
+    Identifier,
+    /// Words are only split at whitespace.
+    Whitespace,
+}
+
+/// When a text box selects its whole text as it gains focus, see
+/// [`TextBox::with_select_on_focus`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelectOnFocus {
+    /// The selection is kept, or placed where the text box was clicked
+    #[default]
+    Never,
+    /// The text is selected when the text box is reached with <kbd>Tab</kbd> or gets the focus
+    /// back from a closing popup or dialog, while clicking places the caret
+    Keyboard,
+    /// The text is selected however the text box gains focus
+    Always,
+}
+
+impl SelectOnFocus {
+    #[must_use]
+    fn selects(self, source: Option<FocusSource>) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Keyboard => matches!(source, Some(FocusSource::Keyboard | FocusSource::Restored)),
+            Self::Always => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+impl WordBoundaryPolicy {
+    #[must_use]
+    fn char_class(self, c: char) -> CharClass {
+        if c.is_whitespace() {
+            CharClass::Whitespace
+        } else if (self == Self::Whitespace) || c.is_alphanumeric() || (c == '_') {
+            CharClass::Word
+        } else {
+            CharClass::Punctuation
+        }
+    }
+
+    /// The end of the word after `index`, skipping whitespace in between.
+    #[must_use]
+    fn next_boundary(self, text: &str, index: usize) -> usize {
+        let mut chars = text[index..].char_indices().peekable();
+        while chars
+            .next_if(|&(_, c)| self.char_class(c) == CharClass::Whitespace)
+            .is_some()
+        {}
+
+        if let Some(&(_, first)) = chars.peek() {
+            let class = self.char_class(first);
+            while chars
+                .next_if(|&(_, c)| self.char_class(c) == class)
+                .is_some()
+            {}
+        }
+
+        chars
+            .peek()
+            .map_or(text.len(), |&(offset, _)| index + offset)
+    }
+
+    /// The start of the word before `index`, skipping whitespace in between.
+    #[must_use]
+    fn previous_boundary(self, text: &str, index: usize) -> usize {
+        let mut chars = text[..index].char_indices().rev().peekable();
+        while chars
+            .next_if(|&(_, c)| self.char_class(c) == CharClass::Whitespace)
+            .is_some()
+        {}
+
+        let mut boundary = chars.peek().map_or(0, |&(offset, c)| offset + c.len_utf8());
+        if let Some(&(_, last)) = chars.peek() {
+            let class = self.char_class(last);
+            while let Some((offset, _)) = chars.next_if(|&(_, c)| self.char_class(c) == class) {
+                boundary = offset;
+            }
+        }
+
+        boundary
+    }
+
+    /// The run of characters of the same class around `index`, preferring the character after
+    /// it.
+    #[must_use]
+    fn word_at(self, text: &str, index: usize) -> Range<usize> {
+        let Some(c) = text[index..]
+            .chars()
+            .next()
+            .or_else(|| text[..index].chars().next_back())
+        else {
+            return index..index;
+        };
+        let class = self.char_class(c);
+
+        let start = text[..index]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| self.char_class(c) == class)
+            .last()
+            .map_or(index, |(offset, _)| offset);
+        let end = text[index..]
+            .char_indices()
+            .find(|&(_, c)| self.char_class(c) != class)
+            .map_or(text.len(), |(offset, _)| index + offset);
+
+        start..end
+    }
+}
+
+/// Performs word operations according to a [`WordBoundaryPolicy`] other than the Unicode one the
+/// editor implements itself.
+struct WordDriver<'driver, 'editor> {
+    driver: &'driver mut PlainEditorDriver<'editor, Color>,
+    policy: WordBoundaryPolicy,
+}
+
+impl<'driver, 'editor> WordDriver<'driver, 'editor> {
+    /// Returns `None` if the editor's own word handling applies.
+    #[must_use]
+    fn new(
+        driver: &'driver mut PlainEditorDriver<'editor, Color>,
+        policy: WordBoundaryPolicy,
+    ) -> Option<Self> {
+        (policy != WordBoundaryPolicy::Unicode).then_some(Self { driver, policy })
+    }
+
+    #[must_use]
+    fn focus(&self) -> usize {
+        self.driver.editor.raw_selection().focus().index()
+    }
+
+    #[must_use]
+    fn boundary(&self, forward: bool) -> usize {
+        let text = self.driver.editor.raw_text();
+        if forward {
+            self.policy.next_boundary(text, self.focus())
+        } else {
+            self.policy.previous_boundary(text, self.focus())
+        }
+    }
+
+    fn move_word(&mut self, forward: bool) {
+        let boundary = self.boundary(forward);
+        self.driver.move_to_byte(boundary);
+    }
+
+    fn select_word(&mut self, forward: bool) {
+        let boundary = self.boundary(forward);
+        self.driver.extend_selection_to_byte(boundary);
+    }
+
+    fn delete_word(&mut self, forward: bool) {
+        if self.driver.editor.raw_selection().is_collapsed() {
+            let focus = self.focus();
+            let boundary = self.boundary(forward);
+            if boundary == focus {
+                return;
+            }
+            self.driver
+                .select_byte_range(focus.min(boundary), focus.max(boundary));
+        }
+
+        self.driver.delete_selection();
+    }
+
+    fn select_word_at_point(&mut self, x: f32, y: f32) {
+        self.driver.move_to_point(x, y);
+        let word = self
+            .policy
+            .word_at(self.driver.editor.raw_text(), self.focus());
+        self.driver.select_byte_range(word.start, word.end);
+    }
+}
+
+/// Deletes the selection or the grapheme cluster before the caret.
+///
+/// [`PlainEditorDriver::backdelete`] and [`PlainEditorDriver::delete`] work on the clusters of
+/// the shaped layout, which split graphemes whenever the font has no glyph for the combined
+/// cluster and, for backdelete, only cover emoji and line breaks. That leaves the base of a
+/// combining character or a partial Hangul syllable behind.
+fn backdelete_grapheme(driver: &mut PlainEditorDriver<'_, Color>) {
+    let selection = *driver.editor.raw_selection();
+    if !selection.is_collapsed() {
+        driver.delete_selection();
+        return;
+    }
+
+    let focus = selection.focus().index();
+    let len = driver.editor.raw_text()[..focus]
+        .graphemes(true)
+        .next_back()
+        .map_or(0, str::len);
+    if let Some(len) = NonZeroUsize::new(len) {
+        driver.delete_bytes_before_selection(len);
+    }
+}
+
+/// Deletes the selection or the grapheme cluster after the caret, see [`backdelete_grapheme`].
+fn delete_grapheme(driver: &mut PlainEditorDriver<'_, Color>) {
+    let selection = *driver.editor.raw_selection();
+    if !selection.is_collapsed() {
+        driver.delete_selection();
+        return;
+    }
+
+    let focus = selection.focus().index();
+    let len = driver.editor.raw_text()[focus..]
+        .graphemes(true)
+        .next()
+        .map_or(0, str::len);
+    if let Some(len) = NonZeroUsize::new(len) {
+        driver.delete_bytes_after_selection(len);
+    }
+}
 
 pub struct TextBoxData<'text> {
     text: &'text mut String,
+    deferred: bool,
+    single_line: bool,
+    read_only: bool,
+    mac_bindings: bool,
+    word_boundaries: WordBoundaryPolicy,
+    select_on_focus: SelectOnFocus,
+    filter: Option<TextFilter<'text>>,
 }
 
 pub type TextBox<'text, 'style, 'classes> = Widget<'style, 'classes, TextBoxData<'text>>;
@@ -19,8 +327,116 @@ impl<'text, 'style, 'classes> TextBox<'text, 'style, 'classes> {
     #[must_use]
     #[inline]
     pub fn new(text: &'text mut String) -> Self {
-        TextBoxData { text }.into()
+        TextBoxData {
+            text,
+            deferred: false,
+            single_line: false,
+            read_only: false,
+            mac_bindings: DEFAULT_MAC_BINDINGS,
+            word_boundaries: WordBoundaryPolicy::Unicode,
+            select_on_focus: SelectOnFocus::Never,
+            filter: None,
+        }
+        .into()
+    }
+
+    /// Creates a text box that only writes to `text` once editing is finished, that is when the
+    /// text is submitted or the text box loses focus.
+    #[track_caller]
+    #[must_use]
+    #[inline]
+    pub fn deferred(text: &'text mut String) -> Self {
+        TextBoxData {
+            text,
+            deferred: true,
+            single_line: false,
+            read_only: false,
+            mac_bindings: DEFAULT_MAC_BINDINGS,
+            word_boundaries: WordBoundaryPolicy::Unicode,
+            select_on_focus: SelectOnFocus::Never,
+            filter: None,
+        }
+        .into()
     }
+
+    /// Rejects every insertion that would result in a text the filter doesn't allow.
+    ///
+    /// The filter is given the whole text as it would be after the insertion.
+    #[must_use]
+    #[inline]
+    pub fn with_filter(self, filter: impl Into<TextFilter<'text>>) -> Self {
+        self.map_data(|data| TextBoxData {
+            filter: Some(filter.into()),
+            ..data
+        })
+    }
+
+    /// If enabled, the text box uses macOS key bindings: <kbd>Option</kbd> moves and deletes by
+    /// word, <kbd>Command</kbd> moves to the start and end of lines and of the text.
+    ///
+    /// This defaults to whether the `winit` feature is enabled on macOS.
+    #[must_use]
+    #[inline]
+    pub fn with_mac_bindings(self, mac_bindings: bool) -> Self {
+        self.map_data(|data| TextBoxData {
+            mac_bindings,
+            ..data
+        })
+    }
+
+    /// Sets where word motion, word deletion and double-click selection consider words to end.
+    #[must_use]
+    #[inline]
+    pub fn with_word_boundaries(self, word_boundaries: WordBoundaryPolicy) -> Self {
+        self.map_data(|data| TextBoxData {
+            word_boundaries,
+            ..data
+        })
+    }
+
+    /// Sets whether the whole text is selected when the text box gains focus, so typing replaces
+    /// it.
+    #[must_use]
+    #[inline]
+    pub fn with_select_on_focus(self, select_on_focus: SelectOnFocus) -> Self {
+        self.map_data(|data| TextBoxData {
+            select_on_focus,
+            ..data
+        })
+    }
+
+    /// If enabled, <kbd>Enter</kbd> submits the text instead of inserting a line break.
+    #[must_use]
+    #[inline]
+    pub fn with_single_line(self, single_line: bool) -> Self {
+        self.map_data(|data| TextBoxData {
+            single_line,
+            ..data
+        })
+    }
+
+    /// If enabled, the text can still be selected but not edited, and the text box never writes
+    /// to its string.
+    ///
+    /// To also prevent the text box from being focused, disable it through the `enabled` style
+    /// property instead.
+    #[must_use]
+    #[inline]
+    pub fn read_only(self, read_only: bool) -> Self {
+        self.map_data(|data| TextBoxData { read_only, ..data })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextBoxResponse {
+    /// The text was edited during this frame.
+    pub changed: bool,
+    /// <kbd>Enter</kbd> was pressed in a single line text box.
+    pub submitted: bool,
+    /// The text box lost focus during this frame.
+    pub focus_lost: bool,
+    /// An insertion was rejected by the filter during this frame.
+    pub rejected: bool,
 }
 
 impl WidgetData for TextBoxData<'_> {
@@ -30,6 +446,30 @@ impl WidgetData for TextBoxData<'_> {
     }
 }
 
+/// The editor only accepts `'static` styles, so every distinct locale is leaked once. There are
+/// only ever a handful of them.
+fn static_locale(locale: &str) -> Option<&'static str> {
+    use std::sync::Mutex;
+
+    static LOCALES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+    if locale.is_empty() {
+        return None;
+    }
+
+    let mut locales = LOCALES.lock().unwrap_or_else(|error| error.into_inner());
+    if let Some(&static_locale) = locales
+        .iter()
+        .find(|&&static_locale| static_locale == locale)
+    {
+        return Some(static_locale);
+    }
+
+    let static_locale: &'static str = Box::leak(locale.into());
+    locales.push(static_locale);
+    Some(static_locale)
+}
+
 struct Editor {
     editor: PlainEditor<Color>,
     width: Option<f32>,
@@ -39,6 +479,15 @@ struct Editor {
     font_weight: FontWeight,
     font_width: FontWidth,
     text_color: Color,
+    overflow_wrap: OverflowWrap,
+    locale: SmolStr,
+    caret_blink_start: Option<Instant>,
+    caret_visible: bool,
+    scroll_offset: f32,
+    was_focused: bool,
+    /// Typing replaces the character after the caret instead of inserting, toggled by
+    /// <kbd>Insert</kbd>.
+    overwrite: bool,
 }
 
 impl Deref for Editor {
@@ -58,17 +507,24 @@ impl DerefMut for Editor {
 }
 
 impl Editor {
+    fn content_width<Renderer: rendering::Renderer>(
+        uid: Uid,
+        gui: &ByorGuiContext<'_, Renderer>,
+    ) -> Option<f32> {
+        let padding = gui.computed_parent_style().padding();
+        gui.previous_state(uid).map(|state| {
+            (state.size.x - padding.left - padding.right)
+                .value()
+                .max(0.0)
+        })
+    }
+
     fn update_or_insert<'gui, Renderer: rendering::Renderer>(
         uid: Uid,
         gui: &'gui mut ByorGuiContext<'_, Renderer>,
     ) -> &'gui mut Self {
         let width = if gui.parent_style().text_wrap {
-            let padding = gui.computed_parent_style().padding();
-            gui.previous_state(uid).map(|state| {
-                (state.size.x - padding.left - padding.right)
-                    .value()
-                    .max(0.0)
-            })
+            Self::content_width(uid, gui)
         } else {
             None
         };
@@ -79,7 +535,10 @@ impl Editor {
         let font_weight = gui.parent_style().font_weight;
         let font_width = gui.parent_style().font_width;
         let text_color = gui.parent_style().text_color;
+        let overflow_wrap = gui.parent_style().overflow_wrap;
+        let locale = gui.parent_style().locale.clone();
 
+        gui.discard_mismatched_state::<Self>(uid, PersistentStateKey::TextBoxEditor);
         let editor = gui
             .persistent_state_mut(uid)
             .get_or_insert_with(PersistentStateKey::TextBoxEditor, || {
@@ -95,6 +554,8 @@ impl Editor {
                 styles.insert(StyleProperty::FontWeight(font_weight));
                 styles.insert(StyleProperty::FontWidth(font_width));
                 styles.insert(StyleProperty::Brush(text_color));
+                styles.insert(StyleProperty::OverflowWrap(overflow_wrap));
+                styles.insert(StyleProperty::Locale(static_locale(&locale)));
 
                 Editor {
                     editor,
@@ -105,9 +566,16 @@ impl Editor {
                     font_weight,
                     font_width,
                     text_color,
+                    overflow_wrap,
+                    locale: locale.clone(),
+                    caret_blink_start: None,
+                    caret_visible: true,
+                    scroll_offset: 0.0,
+                    was_focused: false,
+                    overwrite: false,
                 }
             })
-            .expect("invalid editor type");
+            .expect("mismatched editor was discarded");
 
         if width != editor.width {
             editor.set_width(width);
@@ -154,8 +622,118 @@ impl Editor {
             editor.text_color = text_color;
         }
 
+        if overflow_wrap != editor.overflow_wrap {
+            editor
+                .edit_styles()
+                .insert(StyleProperty::OverflowWrap(overflow_wrap));
+            editor.overflow_wrap = overflow_wrap;
+        }
+
+        if locale != editor.locale {
+            editor
+                .edit_styles()
+                .insert(StyleProperty::Locale(static_locale(&locale)));
+            editor.locale = locale;
+        }
+
         editor
     }
+
+    /// Scrolls unwrapped text horizontally so the caret stays inside the visible part of the
+    /// text box.
+    fn scroll_to_caret(&mut self, visible_width: Option<f32>, caret_width: f32, margin: f32) {
+        let Some(visible_width) = visible_width.filter(|_| self.width.is_none()) else {
+            self.scroll_offset = 0.0;
+            return;
+        };
+
+        let Some(layout) = self.try_layout() else {
+            return;
+        };
+        let margin = margin.min(visible_width / 2.0);
+        let max_offset = (layout.full_width() + caret_width + margin - visible_width).max(0.0);
+
+        let mut offset = self.scroll_offset;
+        if let Some(caret) = self.cursor_geometry(caret_width) {
+            if (caret.x0 as f32) < offset + margin {
+                offset = caret.x0 as f32 - margin;
+            } else if (caret.x1 as f32) > offset + visible_width - margin {
+                offset = caret.x1 as f32 - visible_width + margin;
+            }
+        }
+
+        self.scroll_offset = offset.clamp(0.0, max_offset);
+    }
+
+    /// The area covered by a block caret, which is the character after the caret or a bar of
+    /// `fallback_width` at the end of a line.
+    fn block_caret_geometry(&self, fallback_width: f32) -> Option<BoundingBox> {
+        let caret = self.cursor_geometry(fallback_width)?;
+        let layout = self.try_layout()?;
+
+        let index = self.raw_selection().focus().index();
+        let Some(range) = overwritten_range(self.raw_text(), index) else {
+            return Some(caret);
+        };
+        let character = Selection::new(
+            Cursor::from_byte_index(layout, range.start, Affinity::Downstream),
+            Cursor::from_byte_index(layout, range.end, Affinity::Upstream),
+        );
+        let geometry = character.geometry(layout);
+        match geometry.first() {
+            Some(&(block, _)) if block.width() > 0.0 => Some(block),
+            _ => Some(caret),
+        }
+    }
+
+    /// Restarts the blinking of the caret whenever it was moved or received focus, so it stays
+    /// visible while typing.
+    fn update_caret_blink<Renderer: rendering::Renderer>(
+        uid: Uid,
+        gui: &mut ByorGuiContext<'_, Renderer>,
+        focused: bool,
+        caret_moved: bool,
+    ) {
+        let frame_time = gui.time();
+        let blink_interval = gui.caret_blink_interval();
+
+        let Some(editor) = gui
+            .persistent_state_mut(uid)
+            .get_mut::<Self>(PersistentStateKey::TextBoxEditor)
+        else {
+            return;
+        };
+
+        if !focused {
+            editor.caret_blink_start = None;
+            editor.caret_visible = true;
+            return;
+        }
+
+        let blink_start = match editor.caret_blink_start {
+            Some(blink_start) if !caret_moved => blink_start,
+            _ => frame_time,
+        };
+        editor.caret_blink_start = Some(blink_start);
+
+        let Some(blink_interval) = blink_interval.filter(|interval| !interval.is_zero()) else {
+            editor.caret_visible = true;
+            return;
+        };
+
+        let elapsed = frame_time.saturating_duration_since(blink_start).as_nanos();
+        let interval = blink_interval.as_nanos();
+        editor.caret_visible = (elapsed / interval).is_multiple_of(2);
+
+        let until_toggle = (interval - (elapsed % interval)) as u64;
+        gui.request_animation_frame(frame_time + Duration::from_nanos(until_toggle));
+    }
+}
+
+/// The byte range of the character starting at `index`, unless `index` is at the end of a line.
+fn overwritten_range(text: &str, index: usize) -> Option<Range<usize>> {
+    let c = text[index..].chars().next()?;
+    (c != '\n').then(|| index..(index + c.len_utf8()))
 }
 
 struct TextBoxRenderer<Renderer: rendering::Renderer> {
@@ -184,38 +762,81 @@ impl<Renderer: rendering::Renderer> rendering::NodeRenderer for TextBoxRenderer<
         {
             let position = context.position
                 + Vec2 {
-                    x: context.style.padding().left,
+                    x: context.style.padding().left - editor.scroll_offset.px(),
                     y: context.style.padding().top,
                 };
 
-            for (selection, _) in editor.selection_geometry() {
-                let min = Vec2 {
-                    x: selection.x0.px(),
-                    y: selection.y0.px(),
-                };
-                let max = Vec2 {
-                    x: selection.x1.px(),
-                    y: selection.y1.px(),
-                };
-
-                context.renderer.fill_rect(
-                    position + min,
-                    max - min,
-                    0.px(),
-                    // TODO: let the user pick this color
-                    Color::rgb(66, 135, 245).into(),
-                )?;
-            }
+            let selection_rects = rendering::selection_rects(
+                editor
+                    .selection_geometry()
+                    .into_iter()
+                    .map(|(selection, _)| selection),
+                position,
+            );
+            context.renderer.fill_rects(
+                &selection_rects,
+                0.px(),
+                context.style.selection_color().into(),
+            )?;
 
+            let layout = editor.try_layout().expect("layout was not updated");
             context.renderer.draw_text_layout(
-                editor.try_layout().expect("layout was not updated"),
+                layout,
                 position,
                 &mut rendering::UnimplementedBoxRenderer::default(),
             )?;
 
-            if let Some(cursor) =
-                editor.cursor_geometry(1.pt().to_pixel(context.scale_factor).value())
+            // Selected glyphs are drawn a second time on top, clipped to the selection
+            if let Some(selection_text_color) = context.style.selection_text_color() {
+                for &(selection_position, selection_size) in &selection_rects {
+                    context
+                        .renderer
+                        .push_clip_rect(selection_position, selection_size)?;
+
+                    for line in layout.lines() {
+                        for item in line.items() {
+                            if let parley::PositionedLayoutItem::GlyphRun(text) = item {
+                                context.renderer.draw_text_with_color(
+                                    text,
+                                    position,
+                                    selection_text_color,
+                                )?;
+                            }
+                        }
+                    }
+
+                    context.renderer.pop_clip_rect()?;
+                }
+            }
+
+            let caret_shape = if editor.overwrite {
+                context.style.overwrite_caret_shape()
+            } else {
+                context.style.caret_shape()
+            };
+            let caret_width = context.style.caret_width().value();
+            let (cursor, caret_color) = match caret_shape {
+                CaretShape::Bar => (
+                    editor.cursor_geometry(caret_width),
+                    context.style.caret_color(),
+                ),
+                CaretShape::Block => {
+                    // translucent, so the covered character stays readable
+                    let color = context.style.caret_color();
+                    (
+                        editor.block_caret_geometry(caret_width),
+                        Color {
+                            a: color.a / 2,
+                            ..color
+                        },
+                    )
+                }
+            };
+
+            if let Some(cursor) = cursor
                 && context.input_state.focused
+                && context.style.enabled()
+                && editor.caret_visible
             {
                 let min = Vec2 {
                     x: cursor.x0.px(),
@@ -230,7 +851,7 @@ impl<Renderer: rendering::Renderer> rendering::NodeRenderer for TextBoxRenderer<
                     position + min,
                     max - min,
                     0.px(),
-                    context.style.text_color().into(),
+                    caret_color.into(),
                 )?;
             }
         }
@@ -239,32 +860,174 @@ impl<Renderer: rendering::Renderer> rendering::NodeRenderer for TextBoxRenderer<
     }
 }
 
+/// How often the text is scrolled while a selection is dragged past the edges of the text box.
+const DRAG_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How close the caret may get to the edges of the text box before the text is scrolled.
+const CARET_SCROLL_MARGIN: Float<Point> = Float::pt(4.0);
+
 const CTRL_A: Shortcut = Shortcut {
     modifiers: Modifiers::CONTROL,
     key: Key::Character(SmolStr::new_inline("A")),
     location: None,
 };
 
+const CMD_A: Shortcut = Shortcut {
+    modifiers: Modifiers::META,
+    key: Key::Character(SmolStr::new_inline("A")),
+    location: None,
+};
+
+/// Whether text boxes use macOS key bindings unless configured otherwise, see
+/// [`TextBox::with_mac_bindings`].
+const DEFAULT_MAC_BINDINGS: bool = matches!(Platform::DEFAULT, Platform::Mac);
+
+#[derive(Clone, Copy)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordLeft,
+    WordRight,
+    LineStart,
+    LineEnd,
+    TextStart,
+    TextEnd,
+}
+
+impl Motion {
+    /// The motion a navigation key performs, ignoring <kbd>Shift</kbd>.
+    fn from_key(key: &Key, modifiers: Modifiers, mac_bindings: bool) -> Option<Self> {
+        let Key::Named(key) = key else {
+            return None;
+        };
+
+        let motion = if mac_bindings {
+            let command = modifiers.contains(Modifiers::META);
+            let option = modifiers.contains(Modifiers::ALT);
+            match key {
+                NamedKey::ArrowLeft if command => Self::LineStart,
+                NamedKey::ArrowLeft if option => Self::WordLeft,
+                NamedKey::ArrowLeft => Self::Left,
+                NamedKey::ArrowRight if command => Self::LineEnd,
+                NamedKey::ArrowRight if option => Self::WordRight,
+                NamedKey::ArrowRight => Self::Right,
+                NamedKey::ArrowUp if command => Self::TextStart,
+                NamedKey::ArrowUp => Self::Up,
+                NamedKey::ArrowDown if command => Self::TextEnd,
+                NamedKey::ArrowDown => Self::Down,
+                NamedKey::Home => Self::TextStart,
+                NamedKey::End => Self::TextEnd,
+                _ => return None,
+            }
+        } else {
+            let control = modifiers.contains(Modifiers::CONTROL);
+            match key {
+                NamedKey::ArrowLeft if control => Self::WordLeft,
+                NamedKey::ArrowLeft => Self::Left,
+                NamedKey::ArrowRight if control => Self::WordRight,
+                NamedKey::ArrowRight => Self::Right,
+                NamedKey::ArrowUp => Self::Up,
+                NamedKey::ArrowDown => Self::Down,
+                NamedKey::Home if control => Self::TextStart,
+                NamedKey::Home => Self::LineStart,
+                NamedKey::End if control => Self::TextEnd,
+                NamedKey::End => Self::LineEnd,
+                _ => return None,
+            }
+        };
+
+        Some(motion)
+    }
+}
+
 enum EditAction {
     Insert(SmolStr),
     Delete,
     DeleteWord,
     Backdelete,
     BackdeleteWord,
-    MoveLeft,
-    MoveRight,
-    MoveUp,
-    MoveDown,
-    MoveWordLeft,
-    MoveWordRight,
+    Move(Motion),
+    /// Moves only the focus of the selection, keeping its anchor in place.
+    Extend(Motion),
     SelectAll,
+    ToggleOverwrite,
+    Submit,
+}
+
+impl EditAction {
+    /// Whether the action is rejected by a read-only text box.
+    #[must_use]
+    fn is_mutating(&self) -> bool {
+        match self {
+            Self::Insert(_)
+            | Self::Delete
+            | Self::DeleteWord
+            | Self::Backdelete
+            | Self::BackdeleteWord
+            | Self::ToggleOverwrite
+            | Self::Submit => true,
+            Self::Move(_) | Self::Extend(_) | Self::SelectAll => false,
+        }
+    }
+}
+
+enum PointerAction {
+    MoveTo,
+    ExtendTo,
+    SelectWord,
+    SelectLine,
+    Drag,
 }
 
-fn build_edit_action_list(input_state: &mut InputState) -> SmallVec<[EditAction; 2]> {
+/// Determines what the mouse does to the text box, and where relative to the text box's content.
+fn pointer_action<Renderer: rendering::Renderer>(
+    uid: Uid,
+    gui: &ByorGuiContext<'_, Renderer>,
+) -> Option<(PointerAction, Vec2<Pixel>)> {
+    let input_state = gui.parent_input_state();
+    if !input_state.pressed(MouseButtons::PRIMARY) {
+        return None;
+    }
+
+    let global_input_state = gui.global_input_state();
+    let action = if !input_state.clicked(MouseButtons::PRIMARY) {
+        PointerAction::Drag
+    } else if global_input_state.modifiers().contains(Modifiers::SHIFT) {
+        PointerAction::ExtendTo
+    } else {
+        match global_input_state.click_count() {
+            0 | 1 => PointerAction::MoveTo,
+            2 => PointerAction::SelectWord,
+            _ => PointerAction::SelectLine,
+        }
+    };
+
+    let padding = gui.computed_parent_style().padding();
+    let position = gui.previous_state(uid)?.position
+        + Vec2 {
+            x: padding.left,
+            y: padding.top,
+        };
+
+    Some((action, global_input_state.cursor_position()? - position))
+}
+
+fn build_edit_action_list(
+    input_state: &mut InputState,
+    single_line: bool,
+    mac_bindings: bool,
+) -> SmallVec<[EditAction; 2]> {
     let mut edit_actions = SmallVec::new();
+    let (select_all, word_modifier) = if mac_bindings {
+        (&CMD_A, Modifiers::ALT)
+    } else {
+        (&CTRL_A, Modifiers::CONTROL)
+    };
 
     input_state.retain_key_events(|event| {
-        match event.matches(&CTRL_A) {
+        match event.matches(select_all) {
             KeyEventMatch::True => {
                 edit_actions.push(EditAction::SelectAll);
                 return false;
@@ -273,12 +1036,23 @@ fn build_edit_action_list(input_state: &mut InputState) -> SmallVec<[EditAction;
             KeyEventMatch::False => (),
         }
 
+        if let KeyEvent::Pressed { key, modifiers, .. } = event
+            && let Some(motion) = Motion::from_key(key, *modifiers, mac_bindings)
+        {
+            if modifiers.contains(Modifiers::SHIFT) {
+                edit_actions.push(EditAction::Extend(motion));
+            } else {
+                edit_actions.push(EditAction::Move(motion));
+            }
+            return false;
+        }
+
         match event {
             KeyEvent::Pressed {
                 key: Key::Named(NamedKey::Delete),
                 modifiers,
                 ..
-            } if modifiers.contains(Modifiers::CONTROL) => {
+            } if modifiers.contains(word_modifier) => {
                 edit_actions.push(EditAction::DeleteWord);
                 return false;
             }
@@ -293,7 +1067,7 @@ fn build_edit_action_list(input_state: &mut InputState) -> SmallVec<[EditAction;
                 key: Key::Named(NamedKey::Backspace),
                 modifiers,
                 ..
-            } if modifiers.contains(Modifiers::CONTROL) => {
+            } if modifiers.contains(word_modifier) => {
                 edit_actions.push(EditAction::BackdeleteWord);
                 return false;
             }
@@ -305,47 +1079,22 @@ fn build_edit_action_list(input_state: &mut InputState) -> SmallVec<[EditAction;
                 return false;
             }
             KeyEvent::Pressed {
-                key: Key::Named(NamedKey::ArrowLeft),
-                modifiers,
+                key: Key::Named(NamedKey::Enter),
                 ..
-            } if modifiers.contains(Modifiers::CONTROL) => {
-                edit_actions.push(EditAction::MoveWordLeft);
+            } if single_line => {
+                edit_actions.push(EditAction::Submit);
                 return false;
             }
-            KeyEvent::Pressed {
-                key: Key::Named(NamedKey::ArrowLeft),
+            KeyEvent::Released {
+                key: Key::Named(NamedKey::Enter),
                 ..
-            } => {
-                edit_actions.push(EditAction::MoveLeft);
-                return false;
-            }
+            } if single_line => return false,
             KeyEvent::Pressed {
-                key: Key::Named(NamedKey::ArrowRight),
+                key: Key::Named(NamedKey::Insert),
                 modifiers,
                 ..
-            } if modifiers.contains(Modifiers::CONTROL) => {
-                edit_actions.push(EditAction::MoveWordRight);
-                return false;
-            }
-            KeyEvent::Pressed {
-                key: Key::Named(NamedKey::ArrowRight),
-                ..
-            } => {
-                edit_actions.push(EditAction::MoveRight);
-                return false;
-            }
-            KeyEvent::Pressed {
-                key: Key::Named(NamedKey::ArrowUp),
-                ..
-            } => {
-                edit_actions.push(EditAction::MoveUp);
-                return false;
-            }
-            KeyEvent::Pressed {
-                key: Key::Named(NamedKey::ArrowDown),
-                ..
-            } => {
-                edit_actions.push(EditAction::MoveDown);
+            } if modifiers.is_empty() => {
+                edit_actions.push(EditAction::ToggleOverwrite);
                 return false;
             }
             KeyEvent::Pressed {
@@ -364,7 +1113,7 @@ fn build_edit_action_list(input_state: &mut InputState) -> SmallVec<[EditAction;
 }
 
 impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for TextBoxData<'_> {
-    type ShowResult = ();
+    type ShowResult = TextBoxResponse;
 
     fn show(
         self,
@@ -375,67 +1124,218 @@ impl<Renderer: rendering::Renderer> LeafWidgetData<Renderer> for TextBoxData<'_>
         let uid = uid.produce();
 
         let contents = NodeContents::default()
+            .with_hit_target(HitTarget::SelfOnly)
+            .with_tab_index(0)
             .with_renderer(TextBoxRenderer::default())
             .with_builder(|mut gui| {
-                let edit_actions = if gui.parent_input_state().focused {
-                    build_edit_action_list(gui.global_input_state_mut())
+                // a disabled text box behaves as if it was never focused or clicked
+                let enabled = gui.computed_parent_style().enabled();
+                let focused = enabled && gui.parent_input_state().focused;
+                let mut edit_actions = if focused {
+                    build_edit_action_list(
+                        gui.global_input_state_mut(),
+                        self.single_line,
+                        self.mac_bindings,
+                    )
                 } else {
                     SmallVec::new()
                 };
+                if self.read_only {
+                    edit_actions.retain(|edit_action| !edit_action.is_mutating());
+                }
 
+                let pointer_action = enabled.then(|| pointer_action(uid, &gui)).flatten();
+                let select_all = focused
+                    && gui.parent_input_state().focus_gained
+                    && self.select_on_focus.selects(gui.focus_source());
+                let visible_width = Editor::content_width(uid, &gui);
+                let caret_width = gui.computed_parent_style().caret_width().value();
+                let scroll_margin = CARET_SCROLL_MARGIN.to_pixel(gui.scale_factor()).value();
                 let editor = Editor::update_or_insert(uid, &mut gui);
 
-                if *self.text != editor.raw_text() {
-                    editor.set_text(&self.text);
+                let mut response = TextBoxResponse {
+                    focus_lost: editor.was_focused && !focused,
+                    ..Default::default()
+                };
+                editor.was_focused = focused;
+
+                // while editing, a deferred text box owns the text
+                let editing = self.deferred && !self.read_only && (focused || response.focus_lost);
+
+                let mut caret_moved =
+                    !edit_actions.is_empty() || pointer_action.is_some() || select_all;
+                if !editing && (*self.text != editor.raw_text()) {
+                    editor.set_text(self.text);
+                    caret_moved = true;
                 }
 
+                let word_boundaries = self.word_boundaries;
+                let scroll_offset = editor.scroll_offset;
+                let mut overwrite = editor.overwrite;
                 let mut text_changed = false;
                 with_global_font_cache(|layout_context, font_context| {
                     let mut driver = editor.driver(font_context, layout_context);
 
+                    if let Some((action, point)) = &pointer_action {
+                        let x = point.x.value() + scroll_offset;
+                        let y = point.y.value();
+                        match action {
+                            PointerAction::MoveTo => driver.move_to_point(x, y),
+                            PointerAction::ExtendTo => driver.shift_click_extension(x, y),
+                            PointerAction::SelectWord => {
+                                match WordDriver::new(&mut driver, word_boundaries) {
+                                    Some(mut words) => words.select_word_at_point(x, y),
+                                    None => driver.select_word_at_point(x, y),
+                                }
+                            }
+                            PointerAction::SelectLine => driver.select_line_at_point(x, y),
+                            PointerAction::Drag => driver.extend_selection_to_point(x, y),
+                        }
+                    }
+
+                    if select_all {
+                        driver.select_all();
+                    }
+
                     for edit_action in &edit_actions {
                         match edit_action {
                             EditAction::Insert(text) => {
+                                let mut selection = driver.editor.raw_selection().text_range();
+                                if overwrite && selection.is_empty() {
+                                    // replace as many characters as are typed, up to the line end
+                                    for _ in text.chars() {
+                                        let text = driver.editor.raw_text();
+                                        match overwritten_range(text, selection.end) {
+                                            Some(range) => selection.end = range.end,
+                                            None => break,
+                                        }
+                                    }
+                                }
+
+                                if let Some(filter) = &self.filter {
+                                    let mut new_text = driver.editor.raw_text().to_owned();
+                                    new_text.replace_range(selection.clone(), text);
+
+                                    if !filter.matches(&new_text) {
+                                        response.rejected = true;
+                                        continue;
+                                    }
+                                }
+
+                                if !selection.is_empty() {
+                                    driver.select_byte_range(selection.start, selection.end);
+                                }
                                 driver.insert_or_replace_selection(text);
                                 text_changed = true;
                             }
                             EditAction::Delete => {
-                                driver.delete();
+                                delete_grapheme(&mut driver);
                                 text_changed = true;
                             }
                             EditAction::DeleteWord => {
-                                driver.delete_word();
+                                match WordDriver::new(&mut driver, word_boundaries) {
+                                    Some(mut words) => words.delete_word(true),
+                                    None => driver.delete_word(),
+                                }
                                 text_changed = true;
                             }
                             EditAction::Backdelete => {
-                                driver.backdelete();
+                                backdelete_grapheme(&mut driver);
                                 text_changed = true;
                             }
                             EditAction::BackdeleteWord => {
-                                driver.backdelete_word();
+                                match WordDriver::new(&mut driver, word_boundaries) {
+                                    Some(mut words) => words.delete_word(false),
+                                    None => driver.backdelete_word(),
+                                }
                                 text_changed = true;
                             }
-                            EditAction::MoveLeft => driver.move_left(),
-                            EditAction::MoveRight => driver.move_right(),
-                            EditAction::MoveUp => driver.move_up(),
-                            EditAction::MoveDown => driver.move_down(),
-                            EditAction::MoveWordLeft => driver.move_word_left(),
-                            EditAction::MoveWordRight => driver.move_word_right(),
+                            &EditAction::Move(motion) => match motion {
+                                Motion::Left => driver.move_left(),
+                                Motion::Right => driver.move_right(),
+                                Motion::Up => driver.move_up(),
+                                Motion::Down => driver.move_down(),
+                                Motion::WordLeft => {
+                                    match WordDriver::new(&mut driver, word_boundaries) {
+                                        Some(mut words) => words.move_word(false),
+                                        None => driver.move_word_left(),
+                                    }
+                                }
+                                Motion::WordRight => {
+                                    match WordDriver::new(&mut driver, word_boundaries) {
+                                        Some(mut words) => words.move_word(true),
+                                        None => driver.move_word_right(),
+                                    }
+                                }
+                                Motion::LineStart => driver.move_to_line_start(),
+                                Motion::LineEnd => driver.move_to_line_end(),
+                                Motion::TextStart => driver.move_to_text_start(),
+                                Motion::TextEnd => driver.move_to_text_end(),
+                            },
+                            &EditAction::Extend(motion) => match motion {
+                                Motion::Left => driver.select_left(),
+                                Motion::Right => driver.select_right(),
+                                Motion::Up => driver.select_up(),
+                                Motion::Down => driver.select_down(),
+                                Motion::WordLeft => {
+                                    match WordDriver::new(&mut driver, word_boundaries) {
+                                        Some(mut words) => words.select_word(false),
+                                        None => driver.select_word_left(),
+                                    }
+                                }
+                                Motion::WordRight => {
+                                    match WordDriver::new(&mut driver, word_boundaries) {
+                                        Some(mut words) => words.select_word(true),
+                                        None => driver.select_word_right(),
+                                    }
+                                }
+                                Motion::LineStart => driver.select_to_line_start(),
+                                Motion::LineEnd => driver.select_to_line_end(),
+                                Motion::TextStart => driver.select_to_text_start(),
+                                Motion::TextEnd => driver.select_to_text_end(),
+                            },
                             EditAction::SelectAll => driver.select_all(),
+                            EditAction::ToggleOverwrite => overwrite = !overwrite,
+                            EditAction::Submit => response.submitted = true,
                         }
                     }
 
                     driver.refresh_layout()
                 });
 
-                if text_changed {
+                editor.overwrite = overwrite;
+                response.changed = text_changed;
+                let commit = if self.read_only {
+                    false
+                } else if self.deferred {
+                    response.submitted || response.focus_lost
+                } else {
+                    text_changed
+                };
+                if commit && (*self.text != editor.raw_text()) {
                     self.text.clear();
                     self.text.push_str(editor.raw_text());
                 }
-            });
 
-        gui.insert_node(Some(uid), &style, contents)?;
+                editor.scroll_to_caret(visible_width, caret_width, scroll_margin);
+                let scrollable = editor.width.is_none();
 
-        Ok(())
+                // keep scrolling while a selection is dragged past the edges
+                if let Some((PointerAction::Drag, point)) = pointer_action
+                    && let Some(visible_width) = visible_width
+                    && scrollable
+                    && ((point.x.value() < scroll_margin)
+                        || (point.x.value() > (visible_width - scroll_margin)))
+                {
+                    let frame_time = gui.time();
+                    gui.request_animation_frame(frame_time + DRAG_SCROLL_INTERVAL);
+                }
+
+                Editor::update_caret_blink(uid, &mut gui, focused, caret_moved);
+
+                response
+            });
+
+        Ok(gui.insert_node(Some(uid), &style, contents)?.result)
     }
 }
@@ -36,7 +36,25 @@ impl TryFrom<winit::event::MouseButton> for input::MouseButton {
             winit::event::MouseButton::Middle => Ok(input::MouseButton::Middle),
             winit::event::MouseButton::Back => Ok(input::MouseButton::Back),
             winit::event::MouseButton::Forward => Ok(input::MouseButton::Forward),
-            winit::event::MouseButton::Other(other) => Err(other),
+            // the numbering of extra buttons is platform specific and passed through as is
+            winit::event::MouseButton::Other(other) => u8::try_from(other)
+                .map(input::MouseButton::Other)
+                .map_err(|_| other),
+        }
+    }
+}
+
+impl From<winit::event::MouseScrollDelta> for input::ScrollDelta {
+    fn from(delta: winit::event::MouseScrollDelta) -> Self {
+        match delta {
+            winit::event::MouseScrollDelta::LineDelta(x, y) => input::ScrollDelta::Point(Vec2 {
+                x: x * input::POINTS_PER_SCROLL_LINE,
+                y: y * input::POINTS_PER_SCROLL_LINE,
+            }),
+            // only precise devices report pixel deltas
+            winit::event::MouseScrollDelta::PixelDelta(delta) => {
+                input::ScrollDelta::Pixel(delta.into())
+            }
         }
     }
 }
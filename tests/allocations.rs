@@ -0,0 +1,121 @@
+//! Counts the heap allocations made while rebuilding a frame that doesn't change.
+//!
+//! The counting allocator replaces the global allocator of the whole test binary, which is why
+//! this test lives in a binary of its own.
+
+use byor_gui::rendering::display_list::DisplayList;
+use byor_gui::widgets::WidgetResult;
+use byor_gui::*;
+use parley::fontique::{Blob, Collection, CollectionOptions, GenericFamily};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+
+const FONT: &[u8] = include_bytes!("fonts/Cantarell-Regular.ttf");
+
+/// Counts the allocations made by the current thread, so tests running in parallel don't
+/// interfere with each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATION_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    let _ = ALLOCATION_COUNT.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Every generic font family is mapped to the test font, so text is actually shaped.
+fn use_test_font() {
+    with_global_font_cache(|_, font_context| {
+        let mut collection = Collection::new(CollectionOptions {
+            shared: false,
+            system_fonts: false,
+        });
+        let families = collection.register_fonts(Blob::new(Arc::new(FONT)), None);
+        for &generic in GenericFamily::all() {
+            collection.set_generic_families(generic, families.iter().map(|&(family, _)| family));
+        }
+        font_context.collection = collection;
+    });
+}
+
+#[test]
+fn static_frames_of_built_in_widgets_do_not_allocate() {
+    use_test_font();
+
+    let screen_size = Vec2 {
+        x: 800.px(),
+        y: 600.px(),
+    };
+    let mut name = String::from("Ferris");
+    let mut range = (20.0, 80.0);
+    let mut mode = 1;
+
+    let mut gui = ByorGui::<DisplayList>::default();
+    gui.reserve_nodes(256);
+    let mut frame = |gui: &mut ByorGui<DisplayList>| {
+        gui.frame(screen_size, |mut gui| -> WidgetResult<()> {
+            gui.flex_panel(|mut gui| -> WidgetResult<()> {
+                gui.label("Settings")?;
+                gui.text_box(&mut name)?;
+                gui.segmented_control(&["Light", "Dark", "System"], &mut mode)?;
+                gui.range_slider(&mut range, 0.0, 100.0)?;
+                gui.gauge(0.5)?;
+                gui.button("Apply")?;
+                gui.button("Cancel")?;
+                Ok(())
+            })??;
+            gui.vertical_scroll_view(|mut gui| -> WidgetResult<()> {
+                for item in 0..20 {
+                    gui.uid_scope(Uid::new(item), |gui| {
+                        gui.label("A list item that is long enough to wrap onto a second line")
+                    })?;
+                }
+                Ok(())
+            })?
+            .result
+        })
+        .result
+        .expect("error building GUI");
+    };
+
+    // the first frames lay out the scroll view and fill the reused buffers
+    for _ in 0..3 {
+        frame(&mut gui);
+    }
+    let node_count = gui.node_count();
+    assert!(node_count > 20);
+
+    let allocations_before = ALLOCATION_COUNT.with(|count| count.get());
+    for _ in 0..3 {
+        frame(&mut gui);
+    }
+    let allocations_after = ALLOCATION_COUNT.with(|count| count.get());
+    assert_eq!(allocations_after - allocations_before, 0);
+    assert_eq!(gui.node_count(), node_count);
+}
@@ -0,0 +1,119 @@
+//! A small widget written once for both orientations with the public axis helpers, the way a
+//! slider or splitter outside of this crate would be.
+
+use byor_gui::input::*;
+use byor_gui::style::axis::*;
+use byor_gui::style::*;
+use byor_gui::widgets::WidgetResult;
+use byor_gui::*;
+
+const VALUE_KEY: PersistentStateKey = PersistentStateKey::Custom("meter_value");
+
+/// A track that is filled up to its value, which the mouse wheel changes.
+fn meter<R: rendering::Renderer>(
+    gui: &mut ByorGuiContext<'_, R>,
+    uid: Uid,
+    axis: Axis,
+) -> WidgetResult<f32> {
+    let track_style = Style::default()
+        .with_layout_direction(axis.primary_direction())
+        .with_size_along_axis(axis, 100.px())
+        .with_size_along_axis(!axis, 10.px())
+        .with_padding(2.px());
+
+    gui.insert_node(
+        Some(uid),
+        &track_style,
+        NodeContents::builder(|mut gui| -> WidgetResult<f32> {
+            let mut value = gui
+                .persistent_state(uid)
+                .get::<f32>(VALUE_KEY)
+                .copied()
+                .unwrap_or(0.5);
+            if gui.parent_input_state().is_hovered() {
+                let delta = gui.global_input_state_mut().consume_scroll(axis).total();
+                value = (value + delta.value() / 100.0).clamp(0.0, 1.0);
+            }
+            gui.persistent_state_mut(uid).insert(VALUE_KEY, value);
+
+            let [leading, trailing] = gui.computed_parent_style().padding().along_axis(axis);
+            let track_length = gui
+                .previous_state(uid)
+                .map(|state| state.size.along_axis(axis))
+                .unwrap_or_default();
+            let fill_length = (track_length - leading - trailing) * value;
+
+            let fill_style = Style::default()
+                .with_size_along_axis(axis, fill_length)
+                .with_size_along_axis(!axis, Sizing::Grow);
+            gui.insert_node(
+                Some(uid.concat(uid!("fill"))),
+                &fill_style,
+                NodeContents::EMPTY,
+            )?;
+
+            Ok(value)
+        }),
+    )?
+    .result
+}
+
+#[test]
+fn axis_generic_widgets_work_along_both_axes() {
+    const METER: Uid = uid!("meter");
+
+    for axis in [Axis::X, Axis::Y] {
+        let mut gui = ByorGui::<rendering::display_list::DisplayList>::default();
+        let frame = |gui: &mut ByorGui<_>| {
+            let mut value = 0.0;
+            gui.frame(
+                Vec2 {
+                    x: 200.px(),
+                    y: 200.px(),
+                },
+                |mut gui| -> WidgetResult<()> {
+                    value = meter(&mut gui, METER, axis)?;
+                    Ok(())
+                },
+            )
+            .result
+            .unwrap();
+            value
+        };
+        let fill_size = |gui: &ByorGui<_>| {
+            gui.nodes()
+                .find(|node| node.uid() == Some(METER.concat(uid!("fill"))))
+                .map(|node| node.size())
+                .unwrap()
+        };
+
+        frame(&mut gui);
+        assert_eq!(frame(&mut gui), 0.5);
+        let size = fill_size(&gui);
+        assert_eq!(size.along_axis(axis), 48.px());
+        assert_eq!(size.along_axis(!axis), 6.px());
+
+        let position = gui
+            .nodes()
+            .find(|node| node.uid() == Some(METER))
+            .map(|node| {
+                node.position()
+                    + Vec2 {
+                        x: 5.px(),
+                        y: 5.px(),
+                    }
+            })
+            .unwrap();
+        gui.move_cursor(position);
+        frame(&mut gui);
+
+        let mut delta = Vec2::ZERO;
+        *delta.along_axis_mut(axis) = 25.px();
+        gui.on_input_event(InputEvent::Scrolled {
+            delta: ScrollDelta::Pixel(delta),
+        });
+        assert_eq!(frame(&mut gui), 0.75);
+        frame(&mut gui);
+        assert_eq!(fill_size(&gui).along_axis(axis), 72.px());
+    }
+}
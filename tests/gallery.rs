@@ -0,0 +1,234 @@
+//! Renders the gallery example headlessly and compares the draw commands against the snapshots in
+//! `tests/snapshots`.
+//!
+//! After an intended visual change, run the tests with `BYOR_GUI_BLESS=1` to update the snapshots
+//! and review the diff before committing it.
+
+#[path = "../examples/gallery/gallery.rs"]
+mod gallery;
+
+use byor_gui::input::*;
+use byor_gui::rendering::display_list::DisplayList;
+use byor_gui::*;
+use gallery::{Gallery, Sample, Tab};
+use parley::fontique::{Blob, Collection, CollectionOptions, GenericFamily};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const SCREEN_SIZE: Vec2<Pixel> = Vec2 {
+    x: Float::px(800.0),
+    y: Float::px(600.0),
+};
+
+/// Every generic font family is mapped to this font, so the layout doesn't depend on the fonts
+/// installed on the machine running the tests.
+const FONT: &[u8] = include_bytes!("fonts/Cantarell-Regular.ttf");
+
+fn use_test_font() {
+    with_global_font_cache(|_, font_context| {
+        let mut collection = Collection::new(CollectionOptions {
+            shared: false,
+            system_fonts: false,
+        });
+        let families = collection.register_fonts(Blob::new(Arc::new(FONT)), None);
+        for &generic in GenericFamily::all() {
+            collection.set_generic_families(generic, families.iter().map(|&(family, _)| family));
+        }
+        font_context.collection = collection;
+    });
+}
+
+fn new_gui() -> ByorGui<DisplayList> {
+    use_test_font();
+
+    let mut gui = ByorGui::default();
+    gallery::create_theme(gui.theme_mut());
+    // a blinking caret would make the output depend on the time the test runs
+    gui.set_caret_blink_interval(None);
+    gui
+}
+
+/// Shows the gallery until its layout settled and returns the draw commands of the last frame.
+fn render(gui: &mut ByorGui<DisplayList>, gallery: &mut Gallery) -> String {
+    for _ in 0..4 {
+        gui.frame(SCREEN_SIZE, |gui| gallery::show_gallery(gallery, gui))
+            .result
+            .expect("error building GUI");
+    }
+
+    let mut display_list = DisplayList::new();
+    gui.render(&mut display_list).unwrap();
+    display_list.to_string()
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{name}.txt"));
+
+    if std::env::var_os("BYOR_GUI_BLESS").is_some() {
+        std::fs::write(&path, actual).expect("failed to write snapshot");
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "snapshot `{}` is missing, run with `BYOR_GUI_BLESS=1` to create it",
+            path.display(),
+        );
+    };
+    if expected == actual {
+        return;
+    }
+
+    let line = expected
+        .lines()
+        .zip(actual.lines())
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    panic!(
+        "snapshot `{name}` differs in line {}\n\
+         expected: {}\n  actual: {}\n\
+         run with `BYOR_GUI_BLESS=1` to accept the new output",
+        line + 1,
+        expected.lines().nth(line).unwrap_or("<end of snapshot>"),
+        actual.lines().nth(line).unwrap_or("<end of output>"),
+    );
+}
+
+fn snapshot_name(tab: Tab, state: &str) -> String {
+    format!("{}_{state}", tab.name().to_lowercase())
+}
+
+/// The center of the enabled sample in the row labeled `row`.
+fn center_of_sample(gui: &ByorGui<DisplayList>, row: &'static str) -> Vec2<Pixel> {
+    let sample = Sample { row, enabled: true };
+    let node = gui
+        .nodes()
+        .find(|node| node.tag::<Sample>() == Some(&sample))
+        .unwrap_or_else(|| panic!("no row is labeled `{row}`"));
+    node.position() + node.size() / 2.0
+}
+
+/// The center of the first node showing `text`.
+fn center_of(gui: &ByorGui<DisplayList>, text: &str) -> Vec2<Pixel> {
+    let node = gui
+        .nodes()
+        .find(|node| node.text() == Some(text))
+        .unwrap_or_else(|| panic!("no node shows `{text}`"));
+    node.position() + node.size() / 2.0
+}
+
+#[test]
+fn gallery_tabs_match_their_snapshots() {
+    for &tab in Tab::ALL {
+        // the snapshots of this tab are taken with the feature enabled
+        if (tab == Tab::Markdown) && cfg!(not(feature = "markdown")) {
+            continue;
+        }
+
+        for right_to_left in [false, true] {
+            let mut gui = new_gui();
+            let mut gallery = Gallery {
+                tab,
+                right_to_left,
+                ..Gallery::default()
+            };
+
+            let state = if right_to_left { "rtl" } else { "ltr" };
+            assert_snapshot(&snapshot_name(tab, state), &render(&mut gui, &mut gallery));
+        }
+    }
+}
+
+#[test]
+fn hovered_and_pressed_buttons_match_their_snapshots() {
+    let mut gui = new_gui();
+    let mut gallery = Gallery::default();
+    render(&mut gui, &mut gallery);
+
+    gui.move_cursor(center_of(&gui, "Click me"));
+    assert_snapshot(
+        &snapshot_name(Tab::Buttons, "hovered"),
+        &render(&mut gui, &mut gallery),
+    );
+
+    gui.press_button(MouseButton::Primary);
+    assert_snapshot(
+        &snapshot_name(Tab::Buttons, "pressed"),
+        &render(&mut gui, &mut gallery),
+    );
+}
+
+#[test]
+fn focused_text_box_matches_its_snapshot() {
+    let mut gui = new_gui();
+    let mut gallery = Gallery {
+        tab: Tab::Text,
+        ..Gallery::default()
+    };
+    render(&mut gui, &mut gallery);
+
+    gui.move_cursor(center_of_sample(&gui, "Single line"));
+    render(&mut gui, &mut gallery);
+    gui.press_button(MouseButton::Primary);
+    render(&mut gui, &mut gallery);
+    gui.release_button(MouseButton::Primary);
+    gui.move_cursor(Vec2::ZERO);
+    assert_snapshot(
+        &snapshot_name(Tab::Text, "focused"),
+        &render(&mut gui, &mut gallery),
+    );
+}
+
+#[test]
+fn open_popup_matches_its_snapshot() {
+    let mut gui = new_gui();
+    let mut gallery = Gallery {
+        tab: Tab::Containers,
+        ..Gallery::default()
+    };
+    render(&mut gui, &mut gallery);
+
+    gui.move_cursor(center_of(&gui, "Open popup"));
+    render(&mut gui, &mut gallery);
+    gui.press_button(MouseButton::Primary);
+    render(&mut gui, &mut gallery);
+    gui.release_button(MouseButton::Primary);
+    // moved away so the button isn't drawn hovered
+    gui.move_cursor(Vec2::ZERO);
+
+    let output = render(&mut gui, &mut gallery);
+    assert!(gallery.popup_open);
+    assert_snapshot(&snapshot_name(Tab::Containers, "popup"), &output);
+}
+
+#[test]
+fn open_dialog_matches_its_snapshot() {
+    let mut gui = new_gui();
+    let mut gallery = Gallery {
+        tab: Tab::Containers,
+        dialog_open: true,
+        ..Gallery::default()
+    };
+
+    let output = render(&mut gui, &mut gallery);
+    assert!(gallery.dialog_open);
+    assert_snapshot(&snapshot_name(Tab::Containers, "dialog"), &output);
+}
+
+#[test]
+fn high_contrast_theme_matches_its_snapshot() {
+    let mut gui = new_gui();
+    gallery::apply_high_contrast(gui.theme_mut());
+    let mut gallery = Gallery {
+        high_contrast: true,
+        ..Gallery::default()
+    };
+
+    assert_snapshot(
+        &snapshot_name(Tab::Buttons, "high_contrast"),
+        &render(&mut gui, &mut gallery),
+    );
+}